@@ -1712,8 +1712,8 @@ fn convert_file<'a, 'b>(libast: &'a FullLibraryAST, crate_types: &CrateTypes<'a>
 		writeln_docs(&mut out, &attrs, "");
 
 		if module == "" {
-			// Special-case the top-level lib.rs with various lint allows and a pointer to the c_types
-			// and bitcoin hand-written modules.
+			// Special-case the top-level lib.rs with various lint allows and a pointer to the c_types,
+			// bitcoin and extensions hand-written modules.
 			writeln!(out, "//! C Bindings").unwrap();
 			writeln!(out, "#![allow(unknown_lints)]").unwrap();
 			writeln!(out, "#![allow(non_camel_case_types)]").unwrap();
@@ -1729,6 +1729,7 @@ fn convert_file<'a, 'b>(libast: &'a FullLibraryAST, crate_types: &CrateTypes<'a>
 			writeln!(out, "pub mod version;").unwrap();
 			writeln!(out, "pub mod c_types;").unwrap();
 			writeln!(out, "pub mod bitcoin;").unwrap();
+			writeln!(out, "pub mod extensions;").unwrap();
 		} else {
 			writeln!(out, "{}", DEFAULT_IMPORTS).unwrap();
 		}
@@ -75,6 +75,7 @@ cd c-bindings-gen && cargo build --release && cd ..
 # note that we keep the few manually-generated files first:
 mv lightning-c-bindings/src/c_types/mod.rs ./
 mv lightning-c-bindings/src/bitcoin ./
+mv lightning-c-bindings/src/extensions ./
 
 # Before we try to sed the Cargo.toml, generate version define tags
 # (ignoring any files that we're about to generate)
@@ -117,6 +118,7 @@ rm -rf lightning-c-bindings/src
 mkdir -p lightning-c-bindings/src/{c_types,lightning}
 mv ./mod.rs lightning-c-bindings/src/c_types/
 mv ./bitcoin lightning-c-bindings/src/
+mv ./extensions lightning-c-bindings/src/
 
 # Finally, run the c-bindings-gen binary, building fresh bindings.
 OUT="$(pwd)/lightning-c-bindings/src"
@@ -139,12 +141,15 @@ pub mod $2 {
 	echo "}" >> /tmp/$1-crate-source.txt
 	cat /tmp/$1-crate-source.txt >> /tmp/crate-source.txt
 	rm /tmp/$1-crate-source.txt
-	if [ "$HOST_PLATFORM" = "host: x86_64-apple-darwin" ]; then
-		# OSX sed is for some reason not compatible with GNU sed
-		sed -E -i '' 's|#?'$1' = \{ .*|'$1' = \{ path = "'"$LIGHTNING_PATH"'/'$1'" '"$4"' }|' lightning-c-bindings/Cargo.toml
-	else
-		sed -E -i 's|#?'$1' = \{ .*|'$1' = \{ path = "'"$LIGHTNING_PATH"'/'$1'" '"$4"' }|' lightning-c-bindings/Cargo.toml
-	fi
+	# ldk-msg-tool shares our workspace, so must use the same lightning crate we build against
+	for MANIFEST in lightning-c-bindings/Cargo.toml ldk-msg-tool/Cargo.toml; do
+		if [ "$HOST_PLATFORM" = "host: x86_64-apple-darwin" ]; then
+			# OSX sed is for some reason not compatible with GNU sed
+			sed -E -i '' 's|#?'$1' = \{ .*|'$1' = \{ path = "'"$LIGHTNING_PATH"'/'$1'" '"$4"' }|' $MANIFEST
+		else
+			sed -E -i 's|#?'$1' = \{ .*|'$1' = \{ path = "'"$LIGHTNING_PATH"'/'$1'" '"$4"' }|' $MANIFEST
+		fi
+	done
 }
 
 function drop_crate() {
@@ -0,0 +1,217 @@
+//! Decodes Lightning wire messages to JSON and encodes them back, for reading hex dumps of
+//! messages offline. See the README for usage.
+
+mod onion;
+mod schema;
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process;
+
+use bitcoin::hashes::hex::{FromHex, ToHex};
+
+use serde_json::{Map, Value};
+
+const USAGE: &str = "\
+Usage:
+  ldk-msg-tool decode [--type TYPE] [HEX | --file PATH]
+      Decodes a message, starting with its two-byte type unless --type is given, to JSON.
+  ldk-msg-tool encode [--out PATH] [JSON | --file PATH]
+      Encodes a JSON message, as printed by decode, printing it as hex or writing the raw
+      bytes to PATH.
+  ldk-msg-tool decode-onion-error [HEX | --file PATH]
+      Decodes a decrypted onion failure packet or failure message to JSON.
+  ldk-msg-tool list
+      Lists the supported message types.
+
+Input is read from stdin if neither an argument nor --file is given. Files may contain either
+hex or raw bytes.";
+
+struct Args {
+	command: String,
+	msg_type: Option<u16>,
+	file: Option<String>,
+	out: Option<String>,
+	input: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+	let mut args = std::env::args().skip(1);
+	let command = args.next().ok_or("no command given")?;
+	let mut res = Args { command, msg_type: None, file: None, out: None, input: None };
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--type" => {
+				let t = args.next().ok_or("--type requires a message type")?;
+				res.msg_type = Some(t.parse().map_err(|_| format!("invalid message type {}", t))?);
+			},
+			"--file" => res.file = Some(args.next().ok_or("--file requires a path")?),
+			"--out" => res.out = Some(args.next().ok_or("--out requires a path")?),
+			_ if res.input.is_none() && !arg.starts_with("--") => res.input = Some(arg),
+			_ => return Err(format!("unexpected argument {}", arg)),
+		}
+	}
+	if res.input.is_some() && res.file.is_some() {
+		return Err("give either an input argument or --file, not both".to_owned());
+	}
+	Ok(res)
+}
+
+/// Reads the raw input bytes from the argument, file or stdin.
+fn read_input(args: &Args) -> Result<Vec<u8>, String> {
+	if let Some(input) = &args.input {
+		return Ok(input.as_bytes().to_vec());
+	}
+	let mut data = Vec::new();
+	match &args.file {
+		Some(path) => data = fs::read(path).map_err(|e| format!("failed to read {}: {}", path, e))?,
+		None => { io::stdin().read_to_end(&mut data).map_err(|e| format!("failed to read stdin: {}", e))?; },
+	}
+	Ok(data)
+}
+
+/// Whether the given bytes look like a hex string rather than binary data.
+fn is_hex_text(data: &[u8]) -> bool {
+	let s = data.strip_prefix(b"0x").unwrap_or(data);
+	!s.is_empty() && s.iter().all(|c| c.is_ascii_hexdigit() || c.is_ascii_whitespace())
+}
+
+/// Decodes a hex dump, ignoring any whitespace and an optional leading `0x`.
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+	let s = s.trim();
+	let digits: String = s.strip_prefix("0x").unwrap_or(s).chars().filter(|c| !c.is_ascii_whitespace()).collect();
+	Vec::<u8>::from_hex(&digits).map_err(|e| format!("invalid hex input: {}", e))
+}
+
+/// Reads the input as message bytes, given either as hex or (from a file or stdin) raw bytes.
+fn read_bytes(args: &Args) -> Result<Vec<u8>, String> {
+	let data = read_input(args)?;
+	if args.input.is_some() || is_hex_text(&data) {
+		decode_hex(std::str::from_utf8(&data).map_err(|_| "input is not valid hex")?)
+	} else {
+		Ok(data)
+	}
+}
+
+/// Reports whether LDK writes the message back out as it was read.
+fn ldk_check(payload: &[u8], reencoded: &[u8]) -> Value {
+	let mut res = Map::new();
+	if reencoded == payload {
+		res.insert("status".to_owned(), Value::from("ok"));
+	} else {
+		res.insert("status".to_owned(), Value::from("reencoded_differently"));
+		res.insert("reencoded".to_owned(), Value::from(reencoded.to_hex()));
+	}
+	Value::Object(res)
+}
+
+fn decode(args: &Args) -> Result<Value, String> {
+	let bytes = read_bytes(args)?;
+	let (msg_type, payload) = match args.msg_type {
+		Some(msg_type) => (msg_type, &bytes[..]),
+		None => {
+			if bytes.len() < 2 {
+				return Err("message is too short to contain a type".to_owned());
+			}
+			(u16::from_be_bytes([bytes[0], bytes[1]]), &bytes[2..])
+		},
+	};
+
+	let mut res = Map::new();
+	res.insert("type".to_owned(), Value::from(msg_type));
+	match schema::by_type(msg_type) {
+		Some(msg) => {
+			let decoded = msg.decode(payload)?;
+			res.insert("name".to_owned(), Value::from(msg.name));
+			res.insert("fields".to_owned(), decoded.fields);
+			res.insert("ldk".to_owned(), ldk_check(payload, &decoded.reencoded));
+		},
+		None => {
+			res.insert("name".to_owned(), Value::from("unknown"));
+			res.insert("payload".to_owned(), Value::from(payload.to_hex()));
+		},
+	}
+	Ok(Value::Object(res))
+}
+
+fn encode(args: &Args) -> Result<Vec<u8>, String> {
+	let input = read_input(args)?;
+	let json: Value = serde_json::from_slice(&input).map_err(|e| format!("invalid JSON: {}", e))?;
+	let obj = json.as_object().ok_or("expected a JSON object")?;
+
+	let msg = match (obj.get("type"), obj.get("name")) {
+		(Some(t), _) => {
+			let msg_type = t.as_u64().filter(|t| *t <= u16::max_value() as u64).ok_or("type must be a u16")?;
+			schema::by_type(msg_type as u16).ok_or(format!("unsupported message type {}", msg_type))?
+		},
+		(None, Some(name)) => {
+			let name = name.as_str().ok_or("name must be a string")?;
+			schema::by_name(name).ok_or(format!("unsupported message {}", name))?
+		},
+		(None, None) => return Err("the message needs a type or name".to_owned()),
+	};
+	let fields = obj.get("fields").and_then(|f| f.as_object()).ok_or("the message needs a fields object")?;
+
+	let payload = msg.encode(fields)?;
+	let mut res = msg.msg_type.to_be_bytes().to_vec();
+	res.extend_from_slice(&payload);
+	Ok(res)
+}
+
+fn list() -> Value {
+	let mut res = Map::new();
+	for msg in schema::MESSAGES {
+		res.insert(msg.msg_type.to_string(), Value::from(msg.name));
+	}
+	Value::Object(res)
+}
+
+fn print_json(val: &Value) {
+	println!("{}", serde_json::to_string_pretty(val).unwrap());
+}
+
+fn run() -> Result<(), String> {
+	let args = parse_args().map_err(|e| format!("{}\n\n{}", e, USAGE))?;
+	match args.command.as_str() {
+		"decode" => print_json(&decode(&args)?),
+		"encode" => {
+			let bytes = encode(&args)?;
+			match &args.out {
+				Some(path) => fs::write(path, &bytes).map_err(|e| format!("failed to write {}: {}", path, e))?,
+				None => println!("{}", bytes.to_hex()),
+			}
+		},
+		"decode-onion-error" => print_json(&onion::decode_failure(&read_bytes(&args)?)?),
+		"list" => print_json(&list()),
+		"help"|"--help"|"-h" => println!("{}", USAGE),
+		cmd => return Err(format!("unknown command {}\n\n{}", cmd, USAGE)),
+	}
+	io::stdout().flush().map_err(|e| e.to_string())
+}
+
+fn main() {
+	if let Err(e) = run() {
+		eprintln!("error: {}", e);
+		process::exit(1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn hex_input_is_decoded() {
+		assert_eq!(decode_hex(" 0x0012\n 00ff ").unwrap(), vec![0x00, 0x12, 0x00, 0xff]);
+		assert!(decode_hex("001").is_err());
+		assert!(decode_hex("zz").is_err());
+	}
+
+	#[test]
+	fn hex_text_is_told_apart_from_bytes() {
+		assert!(is_hex_text(b"0x0012\n"));
+		assert!(is_hex_text(b"00ff"));
+		assert!(!is_hex_text(&[0x00, 0x12]));
+		assert!(!is_hex_text(b""));
+	}
+}
@@ -0,0 +1,276 @@
+//! Decoding of BOLT 4 onion failure messages.
+//!
+//! The `reason` in an `update_fail_htlc` is encrypted with a shared secret per hop which only
+//! the sender of the payment knows, so only failures which have already been decrypted (eg
+//! taken from a log of the paying node) can be decoded.
+//!
+//! Failure messages are not among LDK's public message types, so their layouts are given here.
+//! The `channel_update`s they contain are decoded with LDK like any other message.
+
+use std::io::Cursor;
+
+use bitcoin::hashes::hex::ToHex;
+
+use lightning::util::ser::{BigSize, Readable};
+
+use serde_json::{Map, Value};
+
+use crate::schema;
+
+const BADONION: u16 = 0x8000;
+const PERM: u16 = 0x4000;
+const NODE: u16 = 0x2000;
+const UPDATE: u16 = 0x1000;
+
+/// How a single field of a failure message is encoded.
+enum Kind {
+	U16,
+	U32,
+	U64,
+	/// A BOLT 1 variable-length integer.
+	BigSize,
+	/// A fixed-length byte string, shown as hex.
+	Bytes(usize),
+	/// A u16-length-prefixed channel_update (with or without its type), shown as a decoded
+	/// message.
+	ChannelUpdate,
+	/// A field which is only present if there are bytes left in the message.
+	Optional(&'static Kind),
+}
+
+/// A named field in a failure message.
+struct Field(&'static str, Kind);
+
+struct Failure {
+	code: u16,
+	name: &'static str,
+	fields: &'static [Field],
+}
+
+const FAILURES: &[Failure] = &[
+	Failure { code: PERM|1, name: "invalid_realm", fields: &[] },
+	Failure { code: NODE|2, name: "temporary_node_failure", fields: &[] },
+	Failure { code: PERM|NODE|2, name: "permanent_node_failure", fields: &[] },
+	Failure { code: PERM|NODE|3, name: "required_node_feature_missing", fields: &[] },
+	Failure { code: BADONION|PERM|4, name: "invalid_onion_version", fields: &[Field("sha256_of_onion", Kind::Bytes(32))] },
+	Failure { code: BADONION|PERM|5, name: "invalid_onion_hmac", fields: &[Field("sha256_of_onion", Kind::Bytes(32))] },
+	Failure { code: BADONION|PERM|6, name: "invalid_onion_key", fields: &[Field("sha256_of_onion", Kind::Bytes(32))] },
+	Failure { code: UPDATE|7, name: "temporary_channel_failure", fields: &[Field("channel_update", Kind::ChannelUpdate)] },
+	Failure { code: PERM|8, name: "permanent_channel_failure", fields: &[] },
+	Failure { code: PERM|9, name: "required_channel_feature_missing", fields: &[] },
+	Failure { code: PERM|10, name: "unknown_next_peer", fields: &[] },
+	Failure { code: UPDATE|11, name: "amount_below_minimum", fields: &[Field("htlc_msat", Kind::U64), Field("channel_update", Kind::ChannelUpdate)] },
+	Failure { code: UPDATE|12, name: "fee_insufficient", fields: &[Field("htlc_msat", Kind::U64), Field("channel_update", Kind::ChannelUpdate)] },
+	Failure { code: UPDATE|13, name: "incorrect_cltv_expiry", fields: &[Field("cltv_expiry", Kind::U32), Field("channel_update", Kind::ChannelUpdate)] },
+	Failure { code: UPDATE|14, name: "expiry_too_soon", fields: &[Field("channel_update", Kind::ChannelUpdate)] },
+	Failure { code: PERM|15, name: "incorrect_or_unknown_payment_details", fields: &[Field("htlc_msat", Kind::Optional(&Kind::U64)), Field("height", Kind::Optional(&Kind::U32))] },
+	Failure { code: 18, name: "final_incorrect_cltv_expiry", fields: &[Field("cltv_expiry", Kind::U32)] },
+	Failure { code: 19, name: "final_incorrect_htlc_amount", fields: &[Field("incoming_htlc_amt", Kind::U64)] },
+	Failure { code: UPDATE|20, name: "channel_disabled", fields: &[Field("disabled_flags", Kind::U16), Field("channel_update", Kind::ChannelUpdate)] },
+	Failure { code: 21, name: "expiry_too_far", fields: &[] },
+	Failure { code: PERM|22, name: "invalid_onion_payload", fields: &[Field("type", Kind::BigSize), Field("offset", Kind::U16)] },
+	Failure { code: 23, name: "mpp_timeout", fields: &[] },
+];
+
+/// A cursor over a failure message being decoded, whose fields are read with LDK.
+struct Reader<'a>(Cursor<&'a [u8]>);
+
+impl<'a> Reader<'a> {
+	fn new(data: &'a [u8]) -> Self {
+		Self(Cursor::new(data))
+	}
+
+	fn pos(&self) -> usize {
+		self.0.position() as usize
+	}
+
+	fn remaining(&self) -> usize {
+		self.0.get_ref().len() - self.pos()
+	}
+
+	fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+		if self.remaining() < len {
+			return Err(format!("message truncated at byte {}: needed {} more bytes but only {} remain", self.pos(), len, self.remaining()));
+		}
+		let data: &'a [u8] = *self.0.get_ref();
+		let res = &data[self.pos()..self.pos() + len];
+		self.0.set_position((self.pos() + len) as u64);
+		Ok(res)
+	}
+
+	fn rest(&mut self) -> &'a [u8] {
+		let remaining = self.remaining();
+		self.take(remaining).unwrap()
+	}
+
+	fn read<T: Readable>(&mut self) -> Result<T, String> {
+		let pos = self.pos();
+		T::read(&mut self.0).map_err(|e| format!("failed to read the field at byte {}: {:?}", pos, e))
+	}
+}
+
+fn decode_field(kind: &Kind, reader: &mut Reader) -> Result<Option<Value>, String> {
+	Ok(Some(match kind {
+		Kind::U16 => Value::from(reader.read::<u16>()?),
+		Kind::U32 => Value::from(reader.read::<u32>()?),
+		Kind::U64 => Value::from(reader.read::<u64>()?),
+		Kind::BigSize => Value::from(reader.read::<BigSize>()?.0),
+		Kind::Bytes(len) => Value::from(reader.take(*len)?.to_hex()),
+		Kind::ChannelUpdate => {
+			let len = reader.read::<u16>()? as usize;
+			let mut data = reader.take(len)?;
+			// Some implementations include the message type, others do not.
+			if data.len() >= 2 && u16::from_be_bytes([data[0], data[1]]) == schema::CHANNEL_UPDATE_TYPE {
+				data = &data[2..];
+			}
+			schema::by_type(schema::CHANNEL_UPDATE_TYPE).unwrap().decode(data)?.fields
+		},
+		Kind::Optional(inner) => {
+			if reader.remaining() == 0 { return Ok(None); }
+			return decode_field(inner, reader);
+		},
+	}))
+}
+
+/// Decodes the given fields from the reader, leaving any further bytes unread.
+fn decode_fields_from(fields: &[Field], reader: &mut Reader) -> Result<Map<String, Value>, String> {
+	let mut decoded = Map::new();
+	for Field(name, kind) in fields {
+		if let Some(val) = decode_field(kind, reader).map_err(|e| format!("{}: {}", name, e))? {
+			decoded.insert((*name).to_owned(), val);
+		}
+	}
+	Ok(decoded)
+}
+
+/// The length of the HMAC at the start of a decrypted failure packet.
+const HMAC_LEN: usize = 32;
+
+/// If `data` is a whole decrypted failure packet (`hmac`, `failure_len`, `failuremsg`, `pad_len`,
+/// `pad`), returns the failure message within it.
+fn failure_msg_from_packet(data: &[u8]) -> Option<&[u8]> {
+	if data.len() < HMAC_LEN + 4 { return None; }
+	let mut reader = Reader::new(&data[HMAC_LEN..]);
+	let failure_len = reader.read::<u16>().ok()? as usize;
+	let failure_msg = reader.take(failure_len).ok()?;
+	let pad_len = reader.read::<u16>().ok()? as usize;
+	if reader.remaining() != pad_len { return None; }
+	Some(failure_msg)
+}
+
+/// Decodes a decrypted onion failure, given either as the whole failure packet or as just the
+/// failure message (starting with the failure code).
+pub fn decode_failure(data: &[u8]) -> Result<Value, String> {
+	let mut res = Map::new();
+	let failure_msg = match failure_msg_from_packet(data) {
+		Some(msg) => {
+			res.insert("hmac".to_owned(), Value::from(data[..HMAC_LEN].to_hex()));
+			msg
+		},
+		None => data,
+	};
+
+	let mut reader = Reader::new(failure_msg);
+	let code = reader.read::<u16>()?;
+	res.insert("failure_code".to_owned(), Value::from(code));
+	let mut flags = Vec::new();
+	for (flag, name) in [(BADONION, "BADONION"), (PERM, "PERM"), (NODE, "NODE"), (UPDATE, "UPDATE")].iter() {
+		if code & flag != 0 { flags.push(Value::from(*name)); }
+	}
+	res.insert("flags".to_owned(), Value::Array(flags));
+
+	match FAILURES.iter().find(|f| f.code == code) {
+		Some(failure) => {
+			res.insert("name".to_owned(), Value::from(failure.name));
+			res.insert("fields".to_owned(), Value::Object(decode_fields_from(failure.fields, &mut reader)?));
+		},
+		None => { res.insert("name".to_owned(), Value::from("unknown")); },
+	}
+	if reader.remaining() > 0 {
+		res.insert("unknown_trailing_data".to_owned(), Value::from(reader.rest().to_hex()));
+	}
+	Ok(Value::Object(res))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn channel_update() -> Vec<u8> {
+		let mut update = vec![1; 64];
+		update.extend_from_slice(&[2; 32]);
+		update.extend_from_slice(&[0; 8]);
+		update.extend_from_slice(&[0; 4]);
+		update.extend_from_slice(&[0, 1]);
+		update.extend_from_slice(&[0; 2 + 8 + 4 + 4]);
+		update
+	}
+
+	#[test]
+	fn failure_messages_decode() {
+		let res = decode_failure(&[0x20, 0x02]).unwrap();
+		assert_eq!(res["name"], Value::from("temporary_node_failure"));
+		assert_eq!(res["flags"], serde_json::json!(["NODE"]));
+
+		let mut msg = vec![0x40, 22, 0xfd, 0x01, 0x00, 0x00, 0x05];
+		let res = decode_failure(&msg).unwrap();
+		assert_eq!(res["fields"]["type"], Value::from(256));
+		assert_eq!(res["fields"]["offset"], Value::from(5));
+
+		msg.push(0xff);
+		assert_eq!(decode_failure(&msg).unwrap()["unknown_trailing_data"], Value::from("ff"));
+	}
+
+	#[test]
+	fn non_canonical_bigsizes_are_rejected() {
+		assert!(decode_failure(&[0x40, 22, 0xfd, 0x00, 0x01, 0x00, 0x05]).is_err());
+	}
+
+	#[test]
+	fn optional_fields_may_be_omitted() {
+		let res = decode_failure(&[0x40, 15]).unwrap();
+		assert_eq!(res["fields"], serde_json::json!({}));
+
+		let mut msg = vec![0x40, 15];
+		msg.extend_from_slice(&1000u64.to_be_bytes());
+		msg.extend_from_slice(&700_000u32.to_be_bytes());
+		let res = decode_failure(&msg).unwrap();
+		assert_eq!(res["fields"]["htlc_msat"], Value::from(1000));
+		assert_eq!(res["fields"]["height"], Value::from(700_000));
+	}
+
+	#[test]
+	fn channel_updates_decode_with_or_without_their_type() {
+		for with_type in [false, true].iter() {
+			let mut update = if *with_type { schema::CHANNEL_UPDATE_TYPE.to_be_bytes().to_vec() } else { Vec::new() };
+			update.extend_from_slice(&channel_update());
+			let mut msg = vec![0x10, 12];
+			msg.extend_from_slice(&1000u64.to_be_bytes());
+			msg.extend_from_slice(&(update.len() as u16).to_be_bytes());
+			msg.extend_from_slice(&update);
+			let res = decode_failure(&msg).unwrap();
+			assert_eq!(res["name"], Value::from("fee_insufficient"));
+			assert_eq!(res["fields"]["channel_update"]["channel_flags"], Value::from(1));
+		}
+	}
+
+	#[test]
+	fn whole_packets_decode() {
+		let msg = [0x20u8, 0x02];
+		let mut packet = vec![7; HMAC_LEN];
+		packet.extend_from_slice(&(msg.len() as u16).to_be_bytes());
+		packet.extend_from_slice(&msg);
+		packet.extend_from_slice(&3u16.to_be_bytes());
+		packet.extend_from_slice(&[0; 3]);
+		let res = decode_failure(&packet).unwrap();
+		assert_eq!(res["hmac"], Value::from([7; HMAC_LEN].to_hex()));
+		assert_eq!(res["name"], Value::from("temporary_node_failure"));
+		assert!(res.get("unknown_trailing_data").is_none());
+	}
+
+	#[test]
+	fn truncated_failures_are_rejected() {
+		assert!(decode_failure(&[0x40]).is_err());
+		assert!(decode_failure(&[0x40, 22, 0x05, 0x00]).is_err());
+	}
+}
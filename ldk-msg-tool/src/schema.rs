@@ -0,0 +1,975 @@
+//! Conversion between the JSON representation of messages and LDK's own message types.
+//!
+//! Messages are always read and written with LDK's `Readable` and `Writeable` implementations,
+//! so the wire layout is LDK's rather than one maintained here. Fields are named as in the BOLTs,
+//! and binary values are shown as the hex of their LDK encoding.
+//!
+//! A few parts of some messages are not public in LDK (the onion packet of `update_add_htlc`,
+//! the reason of `update_fail_htlc`, the onion hash of `update_fail_malformed_htlc` and the
+//! excess data of gossip messages). These are shown by taking them from LDK's encoding of the
+//! message, and messages containing them are built by writing each field with LDK and reading
+//! the result back.
+
+use std::cell::RefCell;
+use std::io::Cursor;
+
+use bitcoin::blockdata::script::Script;
+use bitcoin::hash_types::{BlockHash, Txid};
+use bitcoin::hashes::hex::{FromHex, ToHex};
+use bitcoin::secp256k1::key::PublicKey;
+use bitcoin::secp256k1::Signature;
+
+use lightning::ln::{PaymentHash, PaymentPreimage};
+use lightning::ln::features::{ChannelFeatures, ChannelTypeFeatures, InitFeatures, NodeFeatures};
+use lightning::ln::msgs::{self, OptionalField};
+use lightning::util::ser::{Readable, Writeable};
+
+use serde_json::{Map, Value};
+
+/// The length of the onion packet at the end of an `update_add_htlc`.
+const ONION_PACKET_LEN: usize = 1366;
+
+/// A decoded message.
+pub struct Decoded {
+	/// The message's fields, as a JSON object.
+	pub fields: Value,
+	/// The message as LDK writes it back out, which differs from the original if LDK ignored
+	/// parts of it (eg unknown odd TLV records).
+	pub reencoded: Vec<u8>,
+}
+
+/// A message type the tool can convert.
+pub struct Message {
+	pub msg_type: u16,
+	pub name: &'static str,
+	decode: fn(&[u8]) -> Result<Decoded, String>,
+	encode: fn(&Fields) -> Result<Vec<u8>, String>,
+}
+
+impl Message {
+	/// Reads a message payload (without its type) with LDK and converts it to JSON.
+	pub fn decode(&self, payload: &[u8]) -> Result<Decoded, String> {
+		(self.decode)(payload)
+	}
+
+	/// Builds the message from a JSON object of its fields and writes its payload (without its
+	/// type) with LDK.
+	pub fn encode(&self, fields: &Map<String, Value>) -> Result<Vec<u8>, String> {
+		let fields = Fields::new(fields);
+		let res = (self.encode)(&fields)?;
+		fields.check_unknown()?;
+		Ok(res)
+	}
+}
+
+/// An LDK message which can be converted to and from a JSON object of its fields.
+trait JsonMessage: Readable + Writeable + Sized {
+	fn to_json(&self, fields: &mut Map<String, Value>);
+	fn from_json(fields: &Fields) -> Result<Self, String>;
+}
+
+fn decode_message<M: JsonMessage>(payload: &[u8]) -> Result<Decoded, String> {
+	let msg = M::read(&mut Cursor::new(payload)).map_err(|e| format!("LDK failed to read the message: {:?}", e))?;
+	let mut fields = Map::new();
+	msg.to_json(&mut fields);
+	Ok(Decoded { fields: Value::Object(fields), reencoded: msg.encode() })
+}
+
+fn encode_message<M: JsonMessage>(fields: &Fields) -> Result<Vec<u8>, String> {
+	Ok(M::from_json(fields)?.encode())
+}
+
+macro_rules! message {
+	($msg_type: expr, $name: expr, $ty: ty) => {
+		Message { msg_type: $msg_type, name: $name, decode: decode_message::<$ty>, encode: encode_message::<$ty> }
+	}
+}
+
+pub const MESSAGES: &[Message] = &[
+	message!(16, "init", msgs::Init),
+	message!(17, "error", msgs::ErrorMessage),
+	message!(18, "ping", msgs::Ping),
+	message!(19, "pong", msgs::Pong),
+	message!(32, "open_channel", msgs::OpenChannel),
+	message!(33, "accept_channel", msgs::AcceptChannel),
+	message!(34, "funding_created", msgs::FundingCreated),
+	message!(35, "funding_signed", msgs::FundingSigned),
+	message!(36, "funding_locked", msgs::FundingLocked),
+	message!(38, "shutdown", msgs::Shutdown),
+	message!(39, "closing_signed", msgs::ClosingSigned),
+	message!(128, "update_add_htlc", msgs::UpdateAddHTLC),
+	message!(130, "update_fulfill_htlc", msgs::UpdateFulfillHTLC),
+	message!(131, "update_fail_htlc", msgs::UpdateFailHTLC),
+	message!(132, "commitment_signed", msgs::CommitmentSigned),
+	message!(133, "revoke_and_ack", msgs::RevokeAndACK),
+	message!(134, "update_fee", msgs::UpdateFee),
+	message!(135, "update_fail_malformed_htlc", msgs::UpdateFailMalformedHTLC),
+	message!(136, "channel_reestablish", msgs::ChannelReestablish),
+	message!(256, "channel_announcement", msgs::ChannelAnnouncement),
+	message!(257, "node_announcement", msgs::NodeAnnouncement),
+	message!(258, "channel_update", msgs::ChannelUpdate),
+	message!(259, "announcement_signatures", msgs::AnnouncementSignatures),
+	message!(261, "query_short_channel_ids", msgs::QueryShortChannelIds),
+	message!(262, "reply_short_channel_ids_end", msgs::ReplyShortChannelIdsEnd),
+	message!(263, "query_channel_range", msgs::QueryChannelRange),
+	message!(264, "reply_channel_range", msgs::ReplyChannelRange),
+	message!(265, "gossip_timestamp_filter", msgs::GossipTimestampFilter),
+];
+
+pub const CHANNEL_UPDATE_TYPE: u16 = 258;
+
+pub fn by_type(msg_type: u16) -> Option<&'static Message> {
+	MESSAGES.iter().find(|m| m.msg_type == msg_type)
+}
+
+pub fn by_name(name: &str) -> Option<&'static Message> {
+	MESSAGES.iter().find(|m| m.name == name)
+}
+
+/// Reads a value with LDK, requiring that all of `data` is consumed.
+fn read_all<T: Readable>(data: &[u8]) -> Result<T, String> {
+	let mut cursor = Cursor::new(data);
+	let res = T::read(&mut cursor).map_err(|e| format!("{:?}", e))?;
+	if cursor.position() as usize != data.len() {
+		return Err(format!("{} unexpected trailing bytes", data.len() - cursor.position() as usize));
+	}
+	Ok(res)
+}
+
+fn put(fields: &mut Map<String, Value>, name: &str, val: Value) {
+	fields.insert(name.to_owned(), val);
+}
+
+/// Shows a value as the hex of its LDK encoding.
+fn hex_value<T: Writeable>(val: &T) -> Value {
+	Value::from(val.encode().to_hex())
+}
+
+/// Shows a feature set as the hex of its flags, without the length prefix LDK writes.
+fn features_value<T: Writeable>(features: &T) -> Value {
+	Value::from(features.encode()[2..].to_hex())
+}
+
+fn script_value(script: &Script) -> Value {
+	Value::from(script.as_bytes().to_hex())
+}
+
+fn scid_value(scid: u64) -> Value {
+	Value::from(format!("{}x{}x{}", scid >> 40, (scid >> 16) & 0xff_ffff, scid & 0xffff))
+}
+
+fn scids_value(scids: &[u64]) -> Value {
+	Value::Array(scids.iter().map(|scid| scid_value(*scid)).collect())
+}
+
+fn scid_from_value(val: &Value) -> Result<u64, String> {
+	if let Some(scid) = val.as_u64() {
+		return Ok(scid);
+	}
+	let s = val.as_str().ok_or("short channel ids must be a number or a BLOCKxTXxOUTPUT string")?;
+	let parts: Vec<&str> = s.split('x').collect();
+	if parts.len() != 3 {
+		return Err(format!("invalid short channel id {}", s));
+	}
+	let block: u64 = parts[0].parse().map_err(|_| format!("invalid short channel id {}", s))?;
+	let tx: u64 = parts[1].parse().map_err(|_| format!("invalid short channel id {}", s))?;
+	let output: u64 = parts[2].parse().map_err(|_| format!("invalid short channel id {}", s))?;
+	if block > 0xff_ffff || tx > 0xff_ffff || output > 0xffff {
+		return Err(format!("short channel id {} out of range", s));
+	}
+	Ok(block << 40 | tx << 16 | output)
+}
+
+/// The fields of a message being encoded, tracking which were used so that unknown ones can be
+/// rejected.
+pub struct Fields<'a> {
+	obj: &'a Map<String, Value>,
+	used: RefCell<Vec<&'static str>>,
+}
+
+impl<'a> Fields<'a> {
+	fn new(obj: &'a Map<String, Value>) -> Self {
+		Self { obj, used: RefCell::new(Vec::new()) }
+	}
+
+	fn check_unknown(&self) -> Result<(), String> {
+		let used = self.used.borrow();
+		match self.obj.keys().find(|key| !used.iter().any(|name| *name == key.as_str())) {
+			Some(key) => Err(format!("unknown field {}", key)),
+			None => Ok(()),
+		}
+	}
+
+	fn has(&self, name: &'static str) -> bool {
+		self.used.borrow_mut().push(name);
+		self.obj.contains_key(name)
+	}
+
+	fn get(&self, name: &'static str) -> Result<&'a Value, String> {
+		self.used.borrow_mut().push(name);
+		self.obj.get(name).ok_or_else(|| format!("{}: missing", name))
+	}
+
+	fn int(&self, name: &'static str, max: u64) -> Result<u64, String> {
+		let n = self.get(name)?.as_u64().ok_or_else(|| format!("{}: expected a non-negative integer", name))?;
+		if n > max {
+			return Err(format!("{}: {} is too large", name, n));
+		}
+		Ok(n)
+	}
+	fn u8(&self, name: &'static str) -> Result<u8, String> { Ok(self.int(name, u8::max_value() as u64)? as u8) }
+	fn u16(&self, name: &'static str) -> Result<u16, String> { Ok(self.int(name, u16::max_value() as u64)? as u16) }
+	fn u32(&self, name: &'static str) -> Result<u32, String> { Ok(self.int(name, u32::max_value() as u64)? as u32) }
+	fn u64(&self, name: &'static str) -> Result<u64, String> { self.int(name, u64::max_value()) }
+
+	fn bool(&self, name: &'static str) -> Result<bool, String> {
+		self.get(name)?.as_bool().ok_or_else(|| format!("{}: expected a boolean", name))
+	}
+
+	fn string(&self, name: &'static str) -> Result<String, String> {
+		Ok(self.get(name)?.as_str().ok_or_else(|| format!("{}: expected a string", name))?.to_owned())
+	}
+
+	fn bytes(&self, name: &'static str) -> Result<Vec<u8>, String> {
+		let s = self.get(name)?.as_str().ok_or_else(|| format!("{}: expected a hex string", name))?;
+		Vec::<u8>::from_hex(s).map_err(|e| format!("{}: {}", name, e))
+	}
+
+	/// Reads a value from the hex of its LDK encoding.
+	fn read<T: Readable>(&self, name: &'static str) -> Result<T, String> {
+		read_all(&self.bytes(name)?).map_err(|e| format!("{}: {}", name, e))
+	}
+
+	/// Reads a feature set from the hex of its flags.
+	fn features<T: Readable>(&self, name: &'static str) -> Result<T, String> {
+		let flags = self.bytes(name)?;
+		if flags.len() > u16::max_value() as usize {
+			return Err(format!("{}: too many feature bytes", name));
+		}
+		let mut data = (flags.len() as u16).to_be_bytes().to_vec();
+		data.extend_from_slice(&flags);
+		read_all(&data).map_err(|e| format!("{}: {}", name, e))
+	}
+
+	fn script(&self, name: &'static str) -> Result<Script, String> {
+		Ok(Script::from(self.bytes(name)?))
+	}
+
+	fn scid(&self, name: &'static str) -> Result<u64, String> {
+		scid_from_value(self.get(name)?).map_err(|e| format!("{}: {}", name, e))
+	}
+
+	fn scids(&self, name: &'static str) -> Result<Vec<u64>, String> {
+		let scids = self.get(name)?.as_array().ok_or_else(|| format!("{}: expected an array of short channel ids", name))?;
+		scids.iter().map(|scid| scid_from_value(scid).map_err(|e| format!("{}: {}", name, e))).collect()
+	}
+
+	fn sigs(&self, name: &'static str) -> Result<Vec<Signature>, String> {
+		let sigs = self.get(name)?.as_array().ok_or_else(|| format!("{}: expected an array of signatures", name))?;
+		sigs.iter().map(|sig| {
+			let data = Vec::<u8>::from_hex(sig.as_str().ok_or_else(|| format!("{}: expected hex strings", name))?)
+				.map_err(|e| format!("{}: {}", name, e))?;
+			read_all(&data).map_err(|e| format!("{}: {}", name, e))
+		}).collect()
+	}
+
+	/// Runs `f` on the fields of the nested object `name`.
+	fn object<T, F: FnOnce(&Fields) -> Result<T, String>>(&self, name: &'static str, f: F) -> Result<T, String> {
+		let obj = self.get(name)?.as_object().ok_or_else(|| format!("{}: expected an object", name))?;
+		let fields = Fields::new(obj);
+		let res = f(&fields).map_err(|e| format!("{}.{}", name, e))?;
+		fields.check_unknown().map_err(|e| format!("{}: {}", name, e))?;
+		Ok(res)
+	}
+}
+
+/// Builds a message whose fields are not all public in LDK by writing each field with LDK, in
+/// the order LDK reads them, and reading the result back.
+struct MessageWriter(Vec<u8>);
+
+impl MessageWriter {
+	fn new() -> Self { MessageWriter(Vec::new()) }
+	fn write<T: Writeable>(mut self, val: T) -> Self {
+		self.0.extend_from_slice(&val.encode());
+		self
+	}
+	fn raw(mut self, data: &[u8]) -> Self {
+		self.0.extend_from_slice(data);
+		self
+	}
+	fn read<M: Readable>(self) -> Result<M, String> {
+		read_all(&self.0).map_err(|e| format!("LDK failed to read the built message: {}", e))
+	}
+}
+
+impl JsonMessage for msgs::Init {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "features", features_value(&self.features));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::Init { features: f.features::<InitFeatures>("features")? })
+	}
+}
+
+impl JsonMessage for msgs::ErrorMessage {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "data", Value::from(self.data.clone()));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::ErrorMessage { channel_id: f.read("channel_id")?, data: f.string("data")? })
+	}
+}
+
+impl JsonMessage for msgs::Ping {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "num_pong_bytes", Value::from(self.ponglen));
+		put(f, "byteslen", Value::from(self.byteslen));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::Ping { ponglen: f.u16("num_pong_bytes")?, byteslen: f.u16("byteslen")? })
+	}
+}
+
+impl JsonMessage for msgs::Pong {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "byteslen", Value::from(self.byteslen));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::Pong { byteslen: f.u16("byteslen")? })
+	}
+}
+
+fn shutdown_script_from_json(f: &Fields) -> Result<OptionalField<Script>, String> {
+	Ok(if f.has("shutdown_scriptpubkey") { OptionalField::Present(f.script("shutdown_scriptpubkey")?) } else { OptionalField::Absent })
+}
+
+impl JsonMessage for msgs::OpenChannel {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "chain_hash", hex_value(&self.chain_hash));
+		put(f, "temporary_channel_id", hex_value(&self.temporary_channel_id));
+		put(f, "funding_satoshis", Value::from(self.funding_satoshis));
+		put(f, "push_msat", Value::from(self.push_msat));
+		put(f, "dust_limit_satoshis", Value::from(self.dust_limit_satoshis));
+		put(f, "max_htlc_value_in_flight_msat", Value::from(self.max_htlc_value_in_flight_msat));
+		put(f, "channel_reserve_satoshis", Value::from(self.channel_reserve_satoshis));
+		put(f, "htlc_minimum_msat", Value::from(self.htlc_minimum_msat));
+		put(f, "feerate_per_kw", Value::from(self.feerate_per_kw));
+		put(f, "to_self_delay", Value::from(self.to_self_delay));
+		put(f, "max_accepted_htlcs", Value::from(self.max_accepted_htlcs));
+		put(f, "funding_pubkey", hex_value(&self.funding_pubkey));
+		put(f, "revocation_basepoint", hex_value(&self.revocation_basepoint));
+		put(f, "payment_basepoint", hex_value(&self.payment_point));
+		put(f, "delayed_payment_basepoint", hex_value(&self.delayed_payment_basepoint));
+		put(f, "htlc_basepoint", hex_value(&self.htlc_basepoint));
+		put(f, "first_per_commitment_point", hex_value(&self.first_per_commitment_point));
+		put(f, "channel_flags", Value::from(self.channel_flags));
+		if let OptionalField::Present(script) = &self.shutdown_scriptpubkey {
+			put(f, "shutdown_scriptpubkey", script_value(script));
+		}
+		if let Some(channel_type) = &self.channel_type {
+			put(f, "channel_type", hex_value(channel_type));
+		}
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::OpenChannel {
+			chain_hash: f.read::<BlockHash>("chain_hash")?,
+			temporary_channel_id: f.read("temporary_channel_id")?,
+			funding_satoshis: f.u64("funding_satoshis")?,
+			push_msat: f.u64("push_msat")?,
+			dust_limit_satoshis: f.u64("dust_limit_satoshis")?,
+			max_htlc_value_in_flight_msat: f.u64("max_htlc_value_in_flight_msat")?,
+			channel_reserve_satoshis: f.u64("channel_reserve_satoshis")?,
+			htlc_minimum_msat: f.u64("htlc_minimum_msat")?,
+			feerate_per_kw: f.u32("feerate_per_kw")?,
+			to_self_delay: f.u16("to_self_delay")?,
+			max_accepted_htlcs: f.u16("max_accepted_htlcs")?,
+			funding_pubkey: f.read::<PublicKey>("funding_pubkey")?,
+			revocation_basepoint: f.read("revocation_basepoint")?,
+			payment_point: f.read("payment_basepoint")?,
+			delayed_payment_basepoint: f.read("delayed_payment_basepoint")?,
+			htlc_basepoint: f.read("htlc_basepoint")?,
+			first_per_commitment_point: f.read("first_per_commitment_point")?,
+			channel_flags: f.u8("channel_flags")?,
+			shutdown_scriptpubkey: shutdown_script_from_json(f)?,
+			channel_type: if f.has("channel_type") { Some(f.read::<ChannelTypeFeatures>("channel_type")?) } else { None },
+		})
+	}
+}
+
+impl JsonMessage for msgs::AcceptChannel {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "temporary_channel_id", hex_value(&self.temporary_channel_id));
+		put(f, "dust_limit_satoshis", Value::from(self.dust_limit_satoshis));
+		put(f, "max_htlc_value_in_flight_msat", Value::from(self.max_htlc_value_in_flight_msat));
+		put(f, "channel_reserve_satoshis", Value::from(self.channel_reserve_satoshis));
+		put(f, "htlc_minimum_msat", Value::from(self.htlc_minimum_msat));
+		put(f, "minimum_depth", Value::from(self.minimum_depth));
+		put(f, "to_self_delay", Value::from(self.to_self_delay));
+		put(f, "max_accepted_htlcs", Value::from(self.max_accepted_htlcs));
+		put(f, "funding_pubkey", hex_value(&self.funding_pubkey));
+		put(f, "revocation_basepoint", hex_value(&self.revocation_basepoint));
+		put(f, "payment_basepoint", hex_value(&self.payment_point));
+		put(f, "delayed_payment_basepoint", hex_value(&self.delayed_payment_basepoint));
+		put(f, "htlc_basepoint", hex_value(&self.htlc_basepoint));
+		put(f, "first_per_commitment_point", hex_value(&self.first_per_commitment_point));
+		if let OptionalField::Present(script) = &self.shutdown_scriptpubkey {
+			put(f, "shutdown_scriptpubkey", script_value(script));
+		}
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::AcceptChannel {
+			temporary_channel_id: f.read("temporary_channel_id")?,
+			dust_limit_satoshis: f.u64("dust_limit_satoshis")?,
+			max_htlc_value_in_flight_msat: f.u64("max_htlc_value_in_flight_msat")?,
+			channel_reserve_satoshis: f.u64("channel_reserve_satoshis")?,
+			htlc_minimum_msat: f.u64("htlc_minimum_msat")?,
+			minimum_depth: f.u32("minimum_depth")?,
+			to_self_delay: f.u16("to_self_delay")?,
+			max_accepted_htlcs: f.u16("max_accepted_htlcs")?,
+			funding_pubkey: f.read::<PublicKey>("funding_pubkey")?,
+			revocation_basepoint: f.read("revocation_basepoint")?,
+			payment_point: f.read("payment_basepoint")?,
+			delayed_payment_basepoint: f.read("delayed_payment_basepoint")?,
+			htlc_basepoint: f.read("htlc_basepoint")?,
+			first_per_commitment_point: f.read("first_per_commitment_point")?,
+			shutdown_scriptpubkey: shutdown_script_from_json(f)?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::FundingCreated {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "temporary_channel_id", hex_value(&self.temporary_channel_id));
+		put(f, "funding_txid", hex_value(&self.funding_txid));
+		put(f, "funding_output_index", Value::from(self.funding_output_index));
+		put(f, "signature", hex_value(&self.signature));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::FundingCreated {
+			temporary_channel_id: f.read("temporary_channel_id")?,
+			funding_txid: f.read::<Txid>("funding_txid")?,
+			funding_output_index: f.u16("funding_output_index")?,
+			signature: f.read::<Signature>("signature")?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::FundingSigned {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "signature", hex_value(&self.signature));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::FundingSigned { channel_id: f.read("channel_id")?, signature: f.read::<Signature>("signature")? })
+	}
+}
+
+impl JsonMessage for msgs::FundingLocked {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "next_per_commitment_point", hex_value(&self.next_per_commitment_point));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::FundingLocked { channel_id: f.read("channel_id")?, next_per_commitment_point: f.read::<PublicKey>("next_per_commitment_point")? })
+	}
+}
+
+impl JsonMessage for msgs::Shutdown {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "scriptpubkey", script_value(&self.scriptpubkey));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::Shutdown { channel_id: f.read("channel_id")?, scriptpubkey: f.script("scriptpubkey")? })
+	}
+}
+
+impl JsonMessage for msgs::ClosingSigned {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "fee_satoshis", Value::from(self.fee_satoshis));
+		put(f, "signature", hex_value(&self.signature));
+		if let Some(fee_range) = &self.fee_range {
+			let mut range = Map::new();
+			put(&mut range, "min_fee_satoshis", Value::from(fee_range.min_fee_satoshis));
+			put(&mut range, "max_fee_satoshis", Value::from(fee_range.max_fee_satoshis));
+			put(f, "fee_range", Value::Object(range));
+		}
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		let fee_range = if f.has("fee_range") {
+			Some(f.object("fee_range", |range| Ok(msgs::ClosingSignedFeeRange {
+				min_fee_satoshis: range.u64("min_fee_satoshis")?,
+				max_fee_satoshis: range.u64("max_fee_satoshis")?,
+			}))?)
+		} else { None };
+		Ok(msgs::ClosingSigned {
+			channel_id: f.read("channel_id")?,
+			fee_satoshis: f.u64("fee_satoshis")?,
+			signature: f.read::<Signature>("signature")?,
+			fee_range,
+		})
+	}
+}
+
+impl JsonMessage for msgs::UpdateAddHTLC {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "id", Value::from(self.htlc_id));
+		put(f, "amount_msat", Value::from(self.amount_msat));
+		put(f, "payment_hash", hex_value(&self.payment_hash));
+		put(f, "cltv_expiry", Value::from(self.cltv_expiry));
+		// The onion packet is not public in LDK, but is always the end of the message.
+		let encoded = self.encode();
+		put(f, "onion_routing_packet", Value::from(encoded[encoded.len() - ONION_PACKET_LEN..].to_hex()));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		let onion = f.bytes("onion_routing_packet")?;
+		if onion.len() != ONION_PACKET_LEN {
+			return Err(format!("onion_routing_packet: expected {} bytes, got {}", ONION_PACKET_LEN, onion.len()));
+		}
+		MessageWriter::new()
+			.write(f.read::<[u8; 32]>("channel_id")?)
+			.write(f.u64("id")?)
+			.write(f.u64("amount_msat")?)
+			.write(f.read::<PaymentHash>("payment_hash")?)
+			.write(f.u32("cltv_expiry")?)
+			.raw(&onion)
+			.read()
+	}
+}
+
+impl JsonMessage for msgs::UpdateFulfillHTLC {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "id", Value::from(self.htlc_id));
+		put(f, "payment_preimage", hex_value(&self.payment_preimage));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::UpdateFulfillHTLC {
+			channel_id: f.read("channel_id")?,
+			htlc_id: f.u64("id")?,
+			payment_preimage: f.read::<PaymentPreimage>("payment_preimage")?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::UpdateFailHTLC {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "id", Value::from(self.htlc_id));
+		// The reason is not public in LDK, but follows the channel id, HTLC id and its length.
+		put(f, "reason", Value::from(self.encode()[32 + 8 + 2..].to_hex()));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		MessageWriter::new()
+			.write(f.read::<[u8; 32]>("channel_id")?)
+			.write(f.u64("id")?)
+			.write(f.bytes("reason")?)
+			.read()
+	}
+}
+
+impl JsonMessage for msgs::UpdateFailMalformedHTLC {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "id", Value::from(self.htlc_id));
+		// The onion hash is not public in LDK, but follows the channel id and HTLC id.
+		put(f, "sha256_of_onion", Value::from(self.encode()[32 + 8..32 + 8 + 32].to_hex()));
+		put(f, "failure_code", Value::from(self.failure_code));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		MessageWriter::new()
+			.write(f.read::<[u8; 32]>("channel_id")?)
+			.write(f.u64("id")?)
+			.write(f.read::<[u8; 32]>("sha256_of_onion")?)
+			.write(f.u16("failure_code")?)
+			.read()
+	}
+}
+
+impl JsonMessage for msgs::CommitmentSigned {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "signature", hex_value(&self.signature));
+		put(f, "htlc_signatures", Value::Array(self.htlc_signatures.iter().map(hex_value).collect()));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::CommitmentSigned {
+			channel_id: f.read("channel_id")?,
+			signature: f.read::<Signature>("signature")?,
+			htlc_signatures: f.sigs("htlc_signatures")?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::RevokeAndACK {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "per_commitment_secret", hex_value(&self.per_commitment_secret));
+		put(f, "next_per_commitment_point", hex_value(&self.next_per_commitment_point));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::RevokeAndACK {
+			channel_id: f.read("channel_id")?,
+			per_commitment_secret: f.read("per_commitment_secret")?,
+			next_per_commitment_point: f.read::<PublicKey>("next_per_commitment_point")?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::UpdateFee {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "feerate_per_kw", Value::from(self.feerate_per_kw));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::UpdateFee { channel_id: f.read("channel_id")?, feerate_per_kw: f.u32("feerate_per_kw")? })
+	}
+}
+
+impl JsonMessage for msgs::ChannelReestablish {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "next_commitment_number", Value::from(self.next_local_commitment_number));
+		put(f, "next_revocation_number", Value::from(self.next_remote_commitment_number));
+		if let OptionalField::Present(data_loss_protect) = &self.data_loss_protect {
+			put(f, "your_last_per_commitment_secret", hex_value(&data_loss_protect.your_last_per_commitment_secret));
+			put(f, "my_current_per_commitment_point", hex_value(&data_loss_protect.my_current_per_commitment_point));
+		}
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		let data_loss_protect = if f.has("your_last_per_commitment_secret") || f.has("my_current_per_commitment_point") {
+			OptionalField::Present(msgs::DataLossProtect {
+				your_last_per_commitment_secret: f.read("your_last_per_commitment_secret")?,
+				my_current_per_commitment_point: f.read::<PublicKey>("my_current_per_commitment_point")?,
+			})
+		} else { OptionalField::Absent };
+		Ok(msgs::ChannelReestablish {
+			channel_id: f.read("channel_id")?,
+			next_local_commitment_number: f.u64("next_commitment_number")?,
+			next_remote_commitment_number: f.u64("next_revocation_number")?,
+			data_loss_protect,
+		})
+	}
+}
+
+impl JsonMessage for msgs::AnnouncementSignatures {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "channel_id", hex_value(&self.channel_id));
+		put(f, "short_channel_id", scid_value(self.short_channel_id));
+		put(f, "node_signature", hex_value(&self.node_signature));
+		put(f, "bitcoin_signature", hex_value(&self.bitcoin_signature));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::AnnouncementSignatures {
+			channel_id: f.read("channel_id")?,
+			short_channel_id: f.scid("short_channel_id")?,
+			node_signature: f.read::<Signature>("node_signature")?,
+			bitcoin_signature: f.read::<Signature>("bitcoin_signature")?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::ChannelAnnouncement {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		let contents = &self.contents;
+		put(f, "node_signature_1", hex_value(&self.node_signature_1));
+		put(f, "node_signature_2", hex_value(&self.node_signature_2));
+		put(f, "bitcoin_signature_1", hex_value(&self.bitcoin_signature_1));
+		put(f, "bitcoin_signature_2", hex_value(&self.bitcoin_signature_2));
+		put(f, "features", features_value(&contents.features));
+		put(f, "chain_hash", hex_value(&contents.chain_hash));
+		put(f, "short_channel_id", scid_value(contents.short_channel_id));
+		put(f, "node_id_1", hex_value(&contents.node_id_1));
+		put(f, "node_id_2", hex_value(&contents.node_id_2));
+		put(f, "bitcoin_key_1", hex_value(&contents.bitcoin_key_1));
+		put(f, "bitcoin_key_2", hex_value(&contents.bitcoin_key_2));
+		// The excess data is not public in LDK, but is the end of the contents.
+		let known_len = contents.features.encode().len() + 32 + 8 + 4 * 33;
+		put(f, "excess_data", Value::from(contents.encode()[known_len..].to_hex()));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		MessageWriter::new()
+			.write(f.read::<Signature>("node_signature_1")?)
+			.write(f.read::<Signature>("node_signature_2")?)
+			.write(f.read::<Signature>("bitcoin_signature_1")?)
+			.write(f.read::<Signature>("bitcoin_signature_2")?)
+			.write(f.features::<ChannelFeatures>("features")?)
+			.write(f.read::<BlockHash>("chain_hash")?)
+			.write(f.scid("short_channel_id")?)
+			.write(f.read::<PublicKey>("node_id_1")?)
+			.write(f.read::<PublicKey>("node_id_2")?)
+			.write(f.read::<PublicKey>("bitcoin_key_1")?)
+			.write(f.read::<PublicKey>("bitcoin_key_2")?)
+			.raw(&if f.has("excess_data") { f.bytes("excess_data")? } else { Vec::new() })
+			.read()
+	}
+}
+
+impl JsonMessage for msgs::NodeAnnouncement {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		let contents = &self.contents;
+		put(f, "signature", hex_value(&self.signature));
+		put(f, "features", features_value(&contents.features));
+		put(f, "timestamp", Value::from(contents.timestamp));
+		put(f, "node_id", hex_value(&contents.node_id));
+		put(f, "rgb_color", Value::from(contents.rgb.to_hex()));
+		put(f, "alias", Value::from(contents.alias.to_hex()));
+		put(f, "addresses", Value::Array(contents.addresses.iter().map(hex_value).collect()));
+		// The address data LDK did not understand and the excess data are not public in LDK, but
+		// are the end of the address list and of the contents respectively.
+		let encoded = contents.encode();
+		let addrs_start = contents.features.encode().len() + 4 + 33 + 3 + 32;
+		let addrs_len = u16::from_be_bytes([encoded[addrs_start], encoded[addrs_start + 1]]) as usize;
+		let known_addrs_len: usize = contents.addresses.iter().map(|addr| addr.encode().len()).sum();
+		let addrs_end = addrs_start + 2 + addrs_len;
+		put(f, "excess_address_data", Value::from(encoded[addrs_start + 2 + known_addrs_len..addrs_end].to_hex()));
+		put(f, "excess_data", Value::from(encoded[addrs_end..].to_hex()));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		let rgb = f.bytes("rgb_color")?;
+		if rgb.len() != 3 {
+			return Err(format!("rgb_color: expected 3 bytes, got {}", rgb.len()));
+		}
+		let addresses = f.get("addresses")?.as_array().ok_or("addresses: expected an array of hex strings")?;
+		let mut addrs = Vec::new();
+		for addr in addresses {
+			addrs.extend_from_slice(&Vec::<u8>::from_hex(addr.as_str().ok_or("addresses: expected an array of hex strings")?)
+				.map_err(|e| format!("addresses: {}", e))?);
+		}
+		if f.has("excess_address_data") {
+			addrs.extend_from_slice(&f.bytes("excess_address_data")?);
+		}
+		if addrs.len() > u16::max_value() as usize {
+			return Err("addresses: too long".to_owned());
+		}
+		MessageWriter::new()
+			.write(f.read::<Signature>("signature")?)
+			.write(f.features::<NodeFeatures>("features")?)
+			.write(f.u32("timestamp")?)
+			.write(f.read::<PublicKey>("node_id")?)
+			.raw(&rgb)
+			.write(f.read::<[u8; 32]>("alias")?)
+			.write(addrs.len() as u16)
+			.raw(&addrs)
+			.raw(&if f.has("excess_data") { f.bytes("excess_data")? } else { Vec::new() })
+			.read()
+	}
+}
+
+impl JsonMessage for msgs::ChannelUpdate {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		let contents = &self.contents;
+		let htlc_maximum_msat = match contents.htlc_maximum_msat {
+			OptionalField::Present(max) => Some(max),
+			OptionalField::Absent => None,
+		};
+		put(f, "signature", hex_value(&self.signature));
+		put(f, "chain_hash", hex_value(&contents.chain_hash));
+		put(f, "short_channel_id", scid_value(contents.short_channel_id));
+		put(f, "timestamp", Value::from(contents.timestamp));
+		put(f, "message_flags", Value::from(if htlc_maximum_msat.is_some() { 1 } else { 0 }));
+		put(f, "channel_flags", Value::from(contents.flags));
+		put(f, "cltv_expiry_delta", Value::from(contents.cltv_expiry_delta));
+		put(f, "htlc_minimum_msat", Value::from(contents.htlc_minimum_msat));
+		put(f, "fee_base_msat", Value::from(contents.fee_base_msat));
+		put(f, "fee_proportional_millionths", Value::from(contents.fee_proportional_millionths));
+		if let Some(max) = htlc_maximum_msat {
+			put(f, "htlc_maximum_msat", Value::from(max));
+		}
+		// The excess data is not public in LDK, but is the end of the contents.
+		let known_len = 32 + 8 + 4 + 1 + 1 + 2 + 8 + 4 + 4 + if htlc_maximum_msat.is_some() { 8 } else { 0 };
+		put(f, "excess_data", Value::from(contents.encode()[known_len..].to_hex()));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		let has_max = f.has("htlc_maximum_msat");
+		let message_flags = if f.has("message_flags") { f.u8("message_flags")? } else if has_max { 1 } else { 0 };
+		if (message_flags & 1 == 1) != has_max {
+			return Err("message_flags: bit 0 must be set exactly when htlc_maximum_msat is present".to_owned());
+		}
+		let mut writer = MessageWriter::new()
+			.write(f.read::<Signature>("signature")?)
+			.write(f.read::<BlockHash>("chain_hash")?)
+			.write(f.scid("short_channel_id")?)
+			.write(f.u32("timestamp")?)
+			.write(message_flags)
+			.write(f.u8("channel_flags")?)
+			.write(f.u16("cltv_expiry_delta")?)
+			.write(f.u64("htlc_minimum_msat")?)
+			.write(f.u32("fee_base_msat")?)
+			.write(f.u32("fee_proportional_millionths")?);
+		if has_max {
+			writer = writer.write(f.u64("htlc_maximum_msat")?);
+		}
+		writer.raw(&if f.has("excess_data") { f.bytes("excess_data")? } else { Vec::new() }).read()
+	}
+}
+
+impl JsonMessage for msgs::QueryShortChannelIds {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "chain_hash", hex_value(&self.chain_hash));
+		put(f, "short_channel_ids", scids_value(&self.short_channel_ids));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::QueryShortChannelIds { chain_hash: f.read::<BlockHash>("chain_hash")?, short_channel_ids: f.scids("short_channel_ids")? })
+	}
+}
+
+impl JsonMessage for msgs::ReplyShortChannelIdsEnd {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "chain_hash", hex_value(&self.chain_hash));
+		put(f, "full_information", Value::from(self.full_information));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::ReplyShortChannelIdsEnd { chain_hash: f.read::<BlockHash>("chain_hash")?, full_information: f.bool("full_information")? })
+	}
+}
+
+impl JsonMessage for msgs::QueryChannelRange {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "chain_hash", hex_value(&self.chain_hash));
+		put(f, "first_blocknum", Value::from(self.first_blocknum));
+		put(f, "number_of_blocks", Value::from(self.number_of_blocks));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::QueryChannelRange {
+			chain_hash: f.read::<BlockHash>("chain_hash")?,
+			first_blocknum: f.u32("first_blocknum")?,
+			number_of_blocks: f.u32("number_of_blocks")?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::ReplyChannelRange {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "chain_hash", hex_value(&self.chain_hash));
+		put(f, "first_blocknum", Value::from(self.first_blocknum));
+		put(f, "number_of_blocks", Value::from(self.number_of_blocks));
+		put(f, "sync_complete", Value::from(self.sync_complete));
+		put(f, "short_channel_ids", scids_value(&self.short_channel_ids));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::ReplyChannelRange {
+			chain_hash: f.read::<BlockHash>("chain_hash")?,
+			first_blocknum: f.u32("first_blocknum")?,
+			number_of_blocks: f.u32("number_of_blocks")?,
+			sync_complete: f.bool("sync_complete")?,
+			short_channel_ids: f.scids("short_channel_ids")?,
+		})
+	}
+}
+
+impl JsonMessage for msgs::GossipTimestampFilter {
+	fn to_json(&self, f: &mut Map<String, Value>) {
+		put(f, "chain_hash", hex_value(&self.chain_hash));
+		put(f, "first_timestamp", Value::from(self.first_timestamp));
+		put(f, "timestamp_range", Value::from(self.timestamp_range));
+	}
+	fn from_json(f: &Fields) -> Result<Self, String> {
+		Ok(msgs::GossipTimestampFilter {
+			chain_hash: f.read::<BlockHash>("chain_hash")?,
+			first_timestamp: f.u32("first_timestamp")?,
+			timestamp_range: f.u32("timestamp_range")?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::hashes::Hash;
+
+	/// Checks that the payload decodes, that LDK writes it back unchanged and that the decoded
+	/// JSON encodes to the same payload.
+	fn assert_round_trips(name: &str, payload: &[u8]) -> Map<String, Value> {
+		let msg = by_name(name).unwrap();
+		let decoded = msg.decode(payload).unwrap();
+		assert_eq!(decoded.reencoded, payload);
+		let fields = decoded.fields.as_object().unwrap().clone();
+		assert_eq!(msg.encode(&fields).unwrap(), payload);
+		fields
+	}
+
+	fn channel_update(htlc_maximum_msat: Option<u64>, excess_data: &[u8]) -> Vec<u8> {
+		let mut payload = vec![1; 64];
+		payload.extend_from_slice(&[2; 32]);
+		payload.extend_from_slice(&(700_000u64 << 40 | 1 << 16 | 1).to_be_bytes());
+		payload.extend_from_slice(&1_600_000_000u32.to_be_bytes());
+		payload.push(if htlc_maximum_msat.is_some() { 1 } else { 0 });
+		payload.push(1);
+		payload.extend_from_slice(&144u16.to_be_bytes());
+		payload.extend_from_slice(&1000u64.to_be_bytes());
+		payload.extend_from_slice(&1000u32.to_be_bytes());
+		payload.extend_from_slice(&100u32.to_be_bytes());
+		if let Some(max) = htlc_maximum_msat {
+			payload.extend_from_slice(&max.to_be_bytes());
+		}
+		payload.extend_from_slice(excess_data);
+		payload
+	}
+
+	#[test]
+	fn messages_round_trip() {
+		assert_round_trips("init", &msgs::Init { features: InitFeatures::known() }.encode());
+		assert_round_trips("error", &msgs::ErrorMessage { channel_id: [1; 32], data: "oops".to_owned() }.encode());
+		assert_round_trips("ping", &msgs::Ping { ponglen: 4, byteslen: 2 }.encode());
+		assert_round_trips("pong", &msgs::Pong { byteslen: 4 }.encode());
+		assert_round_trips("shutdown", &msgs::Shutdown { channel_id: [1; 32], scriptpubkey: Script::from(vec![0, 20, 3, 3]) }.encode());
+		assert_round_trips("gossip_timestamp_filter", &msgs::GossipTimestampFilter {
+			chain_hash: BlockHash::from_slice(&[2; 32]).unwrap(), first_timestamp: 1_600_000_000, timestamp_range: 3600,
+		}.encode());
+		let fields = assert_round_trips("reply_channel_range", &msgs::ReplyChannelRange {
+			chain_hash: BlockHash::from_slice(&[2; 32]).unwrap(), first_blocknum: 700_000, number_of_blocks: 10,
+			sync_complete: true, short_channel_ids: vec![700_000 << 40 | 1 << 16 | 1, 700_001 << 40],
+		}.encode());
+		assert_eq!(fields["short_channel_ids"], serde_json::json!(["700000x1x1", "700001x0x0"]));
+	}
+
+	#[test]
+	fn channel_updates_round_trip() {
+		let fields = assert_round_trips("channel_update", &channel_update(None, &[]));
+		assert!(!fields.contains_key("htlc_maximum_msat"));
+		assert_eq!(fields["message_flags"], Value::from(0));
+
+		let fields = assert_round_trips("channel_update", &channel_update(Some(5_000_000), &[0xab, 0xcd]));
+		assert_eq!(fields["htlc_maximum_msat"], Value::from(5_000_000u64));
+		assert_eq!(fields["excess_data"], Value::from("abcd"));
+		assert_eq!(fields["short_channel_id"], Value::from("700000x1x1"));
+	}
+
+	#[test]
+	fn invalid_json_is_rejected() {
+		let ping = by_name("ping").unwrap();
+		let mut fields = ping.decode(&msgs::Ping { ponglen: 4, byteslen: 2 }.encode()).unwrap().fields.as_object().unwrap().clone();
+		fields.insert("unknown".to_owned(), Value::from(1));
+		assert!(ping.encode(&fields).is_err());
+		fields.remove("unknown");
+		fields.remove("byteslen");
+		assert!(ping.encode(&fields).is_err());
+		fields.insert("byteslen".to_owned(), Value::from(0x1_0000));
+		assert!(ping.encode(&fields).is_err());
+
+		// message_flags must agree with the presence of htlc_maximum_msat.
+		let update = by_name("channel_update").unwrap();
+		let mut fields = update.decode(&channel_update(None, &[])).unwrap().fields.as_object().unwrap().clone();
+		fields.insert("message_flags".to_owned(), Value::from(1));
+		assert!(update.encode(&fields).is_err());
+	}
+
+	#[test]
+	fn truncated_messages_are_rejected() {
+		let payload = msgs::ErrorMessage { channel_id: [1; 32], data: "oops".to_owned() }.encode();
+		assert!(by_name("error").unwrap().decode(&payload[..payload.len() - 1]).is_err());
+		assert!(by_name("channel_update").unwrap().decode(&channel_update(None, &[])[..100]).is_err());
+	}
+
+	#[test]
+	fn short_channel_ids_parse_both_forms() {
+		assert_eq!(scid_from_value(&Value::from("700000x1x1")).unwrap(), 700_000 << 40 | 1 << 16 | 1);
+		assert_eq!(scid_from_value(&Value::from(42u64)).unwrap(), 42);
+		assert!(scid_from_value(&Value::from("16777216x0x0")).is_err());
+		assert!(scid_from_value(&Value::from("1x2")).is_err());
+	}
+}
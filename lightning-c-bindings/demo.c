@@ -39,6 +39,25 @@ LDKCVec_MonitorEventZ monitors_pending_monitor_events(const void *this_arg) {
 	return empty_htlc_vec;
 }
 
+LDKStr str_ref(const char *s) {
+	LDKStr res = { .chars = (const uint8_t*)s, .len = strlen(s), .chars_is_owned = false };
+	return res;
+}
+
+LDKCResult_CVec_u8ZIOErrorZ empty_store_read(const void *this_arg, LDKStr key_namespace, LDKStr key) {
+	return CResult_CVec_u8ZIOErrorZ_err(LDKIOError_NotFound);
+}
+LDKCResult_NoneErrorZ empty_store_write(const void *this_arg, LDKStr key_namespace, LDKStr key, LDKu8slice value) {
+	return CResult_NoneErrorZ_ok();
+}
+LDKCResult_NoneErrorZ empty_store_remove(const void *this_arg, LDKStr key_namespace, LDKStr key) {
+	return CResult_NoneErrorZ_ok();
+}
+LDKCResult_CVec_StrZIOErrorZ empty_store_list(const void *this_arg, LDKStr key_namespace) {
+	LDKCVec_StrZ keys = { .data = NULL, .datalen = 0 };
+	return CResult_CVec_StrZIOErrorZ_ok(keys);
+}
+
 void never_handle_event(const void *this_arg, const struct LDKEvent* event) {
 	// Note that we never actually generate any events to handle in the code below.
 	assert(false);
@@ -104,4 +123,102 @@ int main() {
 		.datalen = 0,
 	};
 	CVec_MonitorEventZ_free(empty_htlc_vec);
+
+	// Perform a BOLT 8 handshake in-process, with the keys of the BOLT 8 test vectors, and pass a
+	// message over it.
+	LDKSecretKey initiator_secret, initiator_ephemeral, responder_secret, responder_ephemeral;
+	memset(&initiator_secret, 0x11, 32);
+	memset(&initiator_ephemeral, 0x12, 32);
+	memset(&responder_secret, 0x21, 32);
+	memset(&responder_ephemeral, 0x22, 32);
+	LDKPublicKey responder_id = { .compressed_form = {
+		0x02, 0x8d, 0x75, 0x00, 0xdd, 0x4c, 0x12, 0x68, 0x5d, 0x1f, 0x56, 0x8b, 0x4c, 0x2b, 0x50, 0x48, 0xe8,
+		0x53, 0x4b, 0x87, 0x33, 0x19, 0xf3, 0xa8, 0xda, 0xa6, 0x12, 0xb4, 0x69, 0x13, 0x2e, 0xc7, 0xf7,
+	} };
+	LDKPeerChannelEncryptor initiator = PeerChannelEncryptor_new_outbound(responder_id, initiator_ephemeral);
+	LDKPeerChannelEncryptor responder = PeerChannelEncryptor_new_inbound(responder_secret);
+
+	LDKCResult_CVec_u8ZNoiseErrorZ act_one = PeerChannelEncryptor_get_act_one(&initiator);
+	assert(act_one.result_ok && act_one.contents.result->datalen == 50);
+	LDKu8slice act_one_slice = { .data = act_one.contents.result->data, .datalen = act_one.contents.result->datalen };
+	LDKCResult_CVec_u8ZNoiseErrorZ act_two = PeerChannelEncryptor_process_act_one(&responder, act_one_slice, responder_ephemeral);
+	assert(act_two.result_ok && act_two.contents.result->datalen == 50);
+	LDKu8slice act_two_slice = { .data = act_two.contents.result->data, .datalen = act_two.contents.result->datalen };
+	LDKCResult_CVec_u8ZNoiseErrorZ act_three = PeerChannelEncryptor_process_act_two(&initiator, act_two_slice, initiator_secret);
+	assert(act_three.result_ok && act_three.contents.result->datalen == 66);
+	LDKu8slice act_three_slice = { .data = act_three.contents.result->data, .datalen = act_three.contents.result->datalen };
+	LDKCResult_PublicKeyNoiseErrorZ initiator_id = PeerChannelEncryptor_process_act_three(&responder, act_three_slice);
+	assert(initiator_id.result_ok);
+	assert(initiator_id.contents.result->compressed_form[0] == 0x03 && initiator_id.contents.result->compressed_form[1] == 0x4f);
+	assert(PeerChannelEncryptor_is_ready_for_encryption(&initiator));
+	assert(PeerChannelEncryptor_is_ready_for_encryption(&responder));
+
+	LDKu8slice hello = { .data = (const uint8_t*)"hello", .datalen = 5 };
+	LDKCResult_CVec_u8ZNoiseErrorZ encrypted = PeerChannelEncryptor_encrypt_message(&initiator, hello);
+	assert(encrypted.result_ok && encrypted.contents.result->datalen == 5 + 34);
+	LDKu8slice header = { .data = encrypted.contents.result->data, .datalen = 18 };
+	LDKCResult_u16NoiseErrorZ msg_len = PeerChannelEncryptor_decrypt_length_header(&responder, header);
+	assert(msg_len.result_ok && *msg_len.contents.result == 5);
+	LDKu8slice body = { .data = encrypted.contents.result->data + 18, .datalen = 5 + 16 };
+	LDKCResult_CVec_u8ZNoiseErrorZ decrypted = PeerChannelEncryptor_decrypt_message(&responder, body);
+	assert(decrypted.result_ok && decrypted.contents.result->datalen == 5);
+	assert(!memcmp(decrypted.contents.result->data, "hello", 5));
+
+	CResult_CVec_u8ZNoiseErrorZ_free(decrypted);
+	CResult_u16NoiseErrorZ_free(msg_len);
+	CResult_CVec_u8ZNoiseErrorZ_free(encrypted);
+	CResult_PublicKeyNoiseErrorZ_free(initiator_id);
+	CResult_CVec_u8ZNoiseErrorZ_free(act_three);
+	CResult_CVec_u8ZNoiseErrorZ_free(act_two);
+	CResult_CVec_u8ZNoiseErrorZ_free(act_one);
+	PeerChannelEncryptor_free(responder);
+	PeerChannelEncryptor_free(initiator);
+
+	// Generate a BIP 39 mnemonic, and derive a node seed from one whose seed is known.
+	LDKCResult_StrSeedErrorZ mnemonic = Mnemonic_generate(24);
+	assert(mnemonic.result_ok);
+	LDKStr generated = { .chars = mnemonic.contents.result->chars, .len = mnemonic.contents.result->len, .chars_is_owned = false };
+	LDKCResult_NoneSeedErrorZ valid = Mnemonic_validate(generated);
+	assert(valid.result_ok);
+	LDKCResult_NoneSeedErrorZ bad_checksum = Mnemonic_validate(str_ref("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"));
+	assert(!bad_checksum.result_ok);
+	LDKCResult_ThirtyTwoBytesSeedErrorZ seed = Mnemonic_to_ldk_seed(str_ref("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"), str_ref(""));
+	assert(seed.result_ok);
+	assert(seed.contents.result->data[0] == 0xc9 && seed.contents.result->data[31] == 0xeb);
+	LDKKeysManager seed_keys = KeysManager_new(&seed.contents.result->data, 0, 0);
+
+	KeysManager_free(seed_keys);
+	CResult_ThirtyTwoBytesSeedErrorZ_free(seed);
+	CResult_NoneSeedErrorZ_free(bad_checksum);
+	CResult_NoneSeedErrorZ_free(valid);
+	CResult_StrSeedErrorZ_free(mnemonic);
+
+	// Run a remote signer server in-process and fetch keys and a channel signer through it.
+	LDKKVStore signer_store = {
+		.this_arg = NULL,
+		.read = empty_store_read,
+		.write = empty_store_write,
+		.remove = empty_store_remove,
+		.list = empty_store_list,
+		.free = NULL,
+	};
+	uint8_t signer_seed[32];
+	memset(signer_seed, 0x42, 32);
+	LDKKeysManager signer_keys = KeysManager_new(&signer_seed, 0, 0);
+	LDKKeysInterface local_keys = KeysManager_as_KeysInterface(&signer_keys);
+	LDKCResult_RemoteSignerServerIOErrorZ server = RemoteSignerServer_new(KeysManager_as_KeysInterface(&signer_keys), signer_store);
+	assert(server.result_ok);
+	LDKKeysInterface remote_keys = RemoteKeysInterface_new(RemoteSignerServer_as_SignerTransport(server.contents.result));
+
+	LDKSecretKey remote_secret = remote_keys.get_node_secret(remote_keys.this_arg);
+	LDKSecretKey local_secret = local_keys.get_node_secret(local_keys.this_arg);
+	assert(!memcmp(remote_secret.bytes, local_secret.bytes, 32));
+	LDKSign remote_signer = remote_keys.get_channel_signer(remote_keys.this_arg, false, 42000);
+	assert(RemoteSignerServer_signer_count(server.contents.result) == 1);
+
+	Sign_free(remote_signer);
+	KeysInterface_free(remote_keys);
+	CResult_RemoteSignerServerIOErrorZ_free(server);
+	KeysInterface_free(local_keys);
+	KeysManager_free(signer_keys);
 }
@@ -438,6 +438,19 @@ struct LDKCResult_RouteLightningErrorZ custom_find_route(const void *this_arg, s
 	return find_route(payer, route_params, params->graph_ref, first_hops, *params->logger, scorer);
 }
 
+LDKCResult_CVec_u8ZIOErrorZ empty_store_read(const void *this_arg, LDKStr key_namespace, LDKStr key) {
+	return CResult_CVec_u8ZIOErrorZ_err(LDKIOError_NotFound);
+}
+LDKCResult_NoneErrorZ empty_store_write(const void *this_arg, LDKStr key_namespace, LDKStr key, LDKu8slice value) {
+	return CResult_NoneErrorZ_ok();
+}
+LDKCResult_NoneErrorZ empty_store_remove(const void *this_arg, LDKStr key_namespace, LDKStr key) {
+	return CResult_NoneErrorZ_ok();
+}
+LDKCResult_CVec_StrZIOErrorZ empty_store_list(const void *this_arg, LDKStr key_namespace) {
+	return CResult_CVec_StrZIOErrorZ_ok(LDKCVec_StrZ { .data = NULL, .datalen = 0 });
+}
+
 int main() {
 	uint8_t channel_open_header[80];
 	uint8_t header_1[80];
@@ -951,4 +964,87 @@ int main() {
 	LDKThirtyTwoBytes kdiv_params;
 	memset(&kdiv_params, 43, 32);
 	LDK::InMemorySigner signer = InMemorySigner_new(sk, sk, sk, sk, sk, random_bytes, 42, kdiv_params);
+
+	// Perform a BOLT 8 handshake in-process, with the keys of the BOLT 8 test vectors, and pass a
+	// message over it.
+	LDKSecretKey initiator_secret, initiator_ephemeral, responder_secret, responder_ephemeral;
+	memset(&initiator_secret, 0x11, 32);
+	memset(&initiator_ephemeral, 0x12, 32);
+	memset(&responder_secret, 0x21, 32);
+	memset(&responder_ephemeral, 0x22, 32);
+	LDKPublicKey responder_id = { .compressed_form = {
+		0x02, 0x8d, 0x75, 0x00, 0xdd, 0x4c, 0x12, 0x68, 0x5d, 0x1f, 0x56, 0x8b, 0x4c, 0x2b, 0x50, 0x48, 0xe8,
+		0x53, 0x4b, 0x87, 0x33, 0x19, 0xf3, 0xa8, 0xda, 0xa6, 0x12, 0xb4, 0x69, 0x13, 0x2e, 0xc7, 0xf7,
+	} };
+	LDK::PeerChannelEncryptor initiator = PeerChannelEncryptor_new_outbound(responder_id, initiator_ephemeral);
+	LDK::PeerChannelEncryptor responder = PeerChannelEncryptor_new_inbound(responder_secret);
+
+	LDK::CResult_CVec_u8ZNoiseErrorZ act_one = PeerChannelEncryptor_get_act_one(&initiator);
+	assert(act_one->result_ok && act_one->contents.result->datalen == 50);
+	LDK::CResult_CVec_u8ZNoiseErrorZ act_two = PeerChannelEncryptor_process_act_one(&responder,
+		LDKu8slice { .data = act_one->contents.result->data, .datalen = act_one->contents.result->datalen }, responder_ephemeral);
+	assert(act_two->result_ok && act_two->contents.result->datalen == 50);
+	LDK::CResult_CVec_u8ZNoiseErrorZ act_three = PeerChannelEncryptor_process_act_two(&initiator,
+		LDKu8slice { .data = act_two->contents.result->data, .datalen = act_two->contents.result->datalen }, initiator_secret);
+	assert(act_three->result_ok && act_three->contents.result->datalen == 66);
+	LDK::CResult_PublicKeyNoiseErrorZ initiator_id = PeerChannelEncryptor_process_act_three(&responder,
+		LDKu8slice { .data = act_three->contents.result->data, .datalen = act_three->contents.result->datalen });
+	assert(initiator_id->result_ok);
+	assert(initiator_id->contents.result->compressed_form[0] == 0x03 && initiator_id->contents.result->compressed_form[1] == 0x4f);
+	assert(PeerChannelEncryptor_is_ready_for_encryption(&initiator));
+	assert(PeerChannelEncryptor_is_ready_for_encryption(&responder));
+
+	LDK::CResult_CVec_u8ZNoiseErrorZ encrypted = PeerChannelEncryptor_encrypt_message(&initiator,
+		LDKu8slice { .data = (const uint8_t*)"hello", .datalen = 5 });
+	assert(encrypted->result_ok && encrypted->contents.result->datalen == 5 + 34);
+	LDK::CResult_u16NoiseErrorZ msg_len = PeerChannelEncryptor_decrypt_length_header(&responder,
+		LDKu8slice { .data = encrypted->contents.result->data, .datalen = 18 });
+	assert(msg_len->result_ok && *msg_len->contents.result == 5);
+	LDK::CResult_CVec_u8ZNoiseErrorZ decrypted = PeerChannelEncryptor_decrypt_message(&responder,
+		LDKu8slice { .data = encrypted->contents.result->data + 18, .datalen = 5 + 16 });
+	assert(decrypted->result_ok && decrypted->contents.result->datalen == 5);
+	assert(!memcmp(decrypted->contents.result->data, "hello", 5));
+
+	// Generate a BIP 39 mnemonic, and derive a node seed from one whose seed is known.
+	LDK::CResult_StrSeedErrorZ mnemonic = Mnemonic_generate(24);
+	assert(mnemonic->result_ok);
+	LDK::CResult_NoneSeedErrorZ valid = Mnemonic_validate(LDKStr {
+		.chars = mnemonic->contents.result->chars, .len = mnemonic->contents.result->len, .chars_is_owned = false
+	});
+	assert(valid->result_ok);
+	const char *bad_checksum_words = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+	LDK::CResult_NoneSeedErrorZ bad_checksum = Mnemonic_validate(LDKStr {
+		.chars = (const uint8_t*)bad_checksum_words, .len = strlen(bad_checksum_words), .chars_is_owned = false
+	});
+	assert(!bad_checksum->result_ok);
+	const char *seed_words = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+	LDK::CResult_ThirtyTwoBytesSeedErrorZ seed = Mnemonic_to_ldk_seed(LDKStr {
+		.chars = (const uint8_t*)seed_words, .len = strlen(seed_words), .chars_is_owned = false
+	}, LDKStr { .chars = NULL, .len = 0, .chars_is_owned = false });
+	assert(seed->result_ok);
+	assert(seed->contents.result->data[0] == 0xc9 && seed->contents.result->data[31] == 0xeb);
+	LDK::KeysManager seed_keys = KeysManager_new(&seed->contents.result->data, 0, 0);
+
+	// Run a remote signer server in-process and fetch keys and a channel signer through it.
+	LDKKVStore signer_store = {
+		.this_arg = NULL,
+		.read = empty_store_read,
+		.write = empty_store_write,
+		.remove = empty_store_remove,
+		.list = empty_store_list,
+		.free = NULL,
+	};
+	uint8_t signer_seed[32];
+	memset(signer_seed, 0x42, 32);
+	LDK::KeysManager signer_keys = KeysManager_new(&signer_seed, 0, 0);
+	LDK::KeysInterface local_keys = KeysManager_as_KeysInterface(&signer_keys);
+	LDK::CResult_RemoteSignerServerIOErrorZ server = RemoteSignerServer_new(KeysManager_as_KeysInterface(&signer_keys), signer_store);
+	assert(server->result_ok);
+	LDK::KeysInterface remote_keys = RemoteKeysInterface_new(RemoteSignerServer_as_SignerTransport(server->contents.result));
+
+	LDKSecretKey remote_secret = remote_keys->get_node_secret(remote_keys->this_arg);
+	LDKSecretKey local_secret = local_keys->get_node_secret(local_keys->this_arg);
+	assert(!memcmp(remote_secret.bytes, local_secret.bytes, 32));
+	LDK::Sign remote_signer = remote_keys->get_channel_signer(remote_keys->this_arg, false, 42000);
+	assert(RemoteSignerServer_signer_count(server->contents.result) == 1);
 }
@@ -1,7 +1,7 @@
 #ifndef LDK_C_BINDINGS_H
 #define LDK_C_BINDINGS_H
 
-/* Generated with cbindgen:0.20.0 */
+/* Generated with cbindgen:0.24.5 */
 
 /* Warning, this file is autogenerated by cbindgen. Don't modify this manually. */
 
@@ -10,6 +10,21 @@
 #include <stdint.h>
 #include "ldk_rust_types.h"
 
+/**
+ * The length of act one and act two.
+ */
+#define LDKNOISE_ACT_ONE_TWO_LEN ((1 + 33) + LDKTAG_LEN)
+
+/**
+ * The length of act three.
+ */
+#define LDKNOISE_ACT_THREE_LEN (((1 + 33) + LDKTAG_LEN) + LDKTAG_LEN)
+
+/**
+ * The length of the encrypted length header preceding each message.
+ */
+#define LDKNOISE_LENGTH_HEADER_LEN (2 + LDKTAG_LEN)
+
 /**
  * An error when accessing the chain via [`Access`].
  */
@@ -203,6 +218,67 @@ typedef enum LDKCurrency {
    LDKCurrency_Sentinel,
 } LDKCurrency;
 
+/**
+ * The reasons registering or sending a custom message can fail.
+ */
+typedef enum LDKCustomMessageError {
+   /**
+    * The message type is below 32768, outside of the BOLT 1 range for custom messages.
+    */
+   LDKCustomMessageError_OutsideCustomRange,
+   /**
+    * A callback is already registered for the message type.
+    */
+   LDKCustomMessageError_AlreadyRegistered,
+   /**
+    * The message is too long to fit in a single Lightning message.
+    */
+   LDKCustomMessageError_MessageTooLong,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKCustomMessageError_Sentinel,
+} LDKCustomMessageError;
+
+/**
+ * The reasons a HODL invoice operation can fail.
+ */
+typedef enum LDKHodlInvoiceError {
+   /**
+    * The requested amount was greater than the total bitcoin supply.
+    */
+   LDKHodlInvoiceError_InvalidAmount,
+   /**
+    * `hold_blocks` was 0 or greater than five days of blocks.
+    */
+   LDKHodlInvoiceError_InvalidHoldTime,
+   /**
+    * The payment hash is not registered with the manager.
+    */
+   LDKHodlInvoiceError_UnknownPaymentHash,
+   /**
+    * A payment for the payment hash is already being held.
+    */
+   LDKHodlInvoiceError_PaymentAlreadyReceived,
+   /**
+    * No payment for the payment hash is being held.
+    */
+   LDKHodlInvoiceError_PaymentNotReceived,
+   /**
+    * The `ChannelManager` no longer holds the payment, eg because it failed the HTLCs back
+    * when they got too close to expiry.
+    */
+   LDKHodlInvoiceError_PaymentNoLongerPending,
+   /**
+    * The registration could not be written to the store.
+    */
+   LDKHodlInvoiceError_PersistenceFailed,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKHodlInvoiceError_Sentinel,
+} LDKHodlInvoiceError;
+
 /**
  * Represents an IO Error. Note that some information is lost in the conversion from Rust.
  */
@@ -231,6 +307,86 @@ typedef enum LDKIOError {
    LDKIOError_Sentinel,
 } LDKIOError;
 
+#if defined(LDK_LINUX)
+/**
+ * The reasons a socket driver operation can fail.
+ */
+typedef enum LDKLdkNetError {
+#if defined(LDK_LINUX)
+   /**
+    * The `epoll` instance, its wakeup `eventfd` or the event loop thread could not be created.
+    */
+   LDKLdkNetError_EventLoopSetupFailed,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The given address could not be parsed or did not resolve to any socket address.
+    */
+   LDKLdkNetError_InvalidAddress,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The connection was refused or otherwise failed to be established.
+    */
+   LDKLdkNetError_ConnectFailed,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The connection could not be established before the given timeout.
+    */
+   LDKLdkNetError_ConnectTimeout,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The listening socket could not be bound to the given address.
+    */
+   LDKLdkNetError_BindFailed,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * A socket was connected or bound, but could not be configured or registered with the event
+    * loop.
+    */
+   LDKLdkNetError_SocketSetupFailed,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The `PeerManager` refused the connection, e.g. because we are already connected to the
+    * peer.
+    */
+   LDKLdkNetError_PeerRejected,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The driver has already been stopped.
+    */
+   LDKLdkNetError_Stopped,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The SOCKS5 proxy could not be reached.
+    */
+   LDKLdkNetError_ProxyUnreachable,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The SOCKS5 proxy did not accept our (lack of) credentials.
+    */
+   LDKLdkNetError_ProxyAuthFailed,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * The SOCKS5 proxy sent an invalid or unexpected response.
+    */
+   LDKLdkNetError_ProxyProtocolError,
+#endif
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKLdkNetError_Sentinel,
+} LDKLdkNetError;
+#endif
+
 /**
  * An enum representing the available verbosity levels of the logger.
  */
@@ -265,6 +421,24 @@ typedef enum LDKLevel {
    LDKLevel_Sentinel,
 } LDKLevel;
 
+/**
+ * Whether a traced message was received from or sent to a peer.
+ */
+typedef enum LDKMessageDirection {
+   /**
+    * The message was received from the peer.
+    */
+   LDKMessageDirection_Inbound,
+   /**
+    * The message is being sent to the peer.
+    */
+   LDKMessageDirection_Outbound,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKMessageDirection_Sentinel,
+} LDKMessageDirection;
+
 /**
  * An enum representing the possible Bitcoin or test networks which we can run on
  */
@@ -291,6 +465,245 @@ typedef enum LDKNetwork {
    LDKNetwork_Sentinel,
 } LDKNetwork;
 
+/**
+ * The reasons a BOLT 8 handshake or message encryption step can fail.
+ */
+typedef enum LDKNoiseError {
+   /**
+    * The call does not match the current stage of the handshake, e.g. a message was encrypted
+    * before the handshake completed, or a previous step already failed.
+    */
+   LDKNoiseError_InvalidState,
+   /**
+    * The given act or length header was not of the expected length.
+    */
+   LDKNoiseError_InvalidLength,
+   /**
+    * The act had an unknown handshake version.
+    */
+   LDKNoiseError_UnknownVersion,
+   /**
+    * The act contained an invalid public key.
+    */
+   LDKNoiseError_InvalidPublicKey,
+   /**
+    * Authenticated decryption failed, i.e. the data was corrupted or not meant for us.
+    */
+   LDKNoiseError_DecryptionFailed,
+   /**
+    * The message to encrypt is longer than the maximum of 65535 bytes.
+    */
+   LDKNoiseError_MessageTooLong,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKNoiseError_Sentinel,
+} LDKNoiseError;
+
+/**
+ * Whether a payment was received or sent.
+ */
+typedef enum LDKPaymentDirection {
+   /**
+    * The payment was (or will be) received by us.
+    */
+   LDKPaymentDirection_Inbound,
+   /**
+    * The payment was sent by us.
+    */
+   LDKPaymentDirection_Outbound,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKPaymentDirection_Sentinel,
+} LDKPaymentDirection;
+
+/**
+ * How a payment ended.
+ */
+typedef enum LDKPaymentOutcomeStatus {
+   /**
+    * The payment reached its destination.
+    */
+   LDKPaymentOutcomeStatus_Succeeded,
+   /**
+    * The destination rejected the payment, eg because the invoice was already paid.
+    */
+   LDKPaymentOutcomeStatus_RejectedByDestination,
+   /**
+    * The payment failed after being attempted `max_attempts` times.
+    */
+   LDKPaymentOutcomeStatus_RetriesExhausted,
+   /**
+    * The payment failed after the strategy's `timeout_secs` passed.
+    */
+   LDKPaymentOutcomeStatus_TimedOut,
+   /**
+    * No route for a retry was found within the strategy's `max_total_fee_msat`.
+    */
+   LDKPaymentOutcomeStatus_FeeLimitReached,
+   /**
+    * No route for a retry was found, or every route used an excluded channel or node.
+    */
+   LDKPaymentOutcomeStatus_NoRouteFound,
+   /**
+    * The invoice expired before the payment could be retried.
+    */
+   LDKPaymentOutcomeStatus_InvoiceExpired,
+   /**
+    * The `Payer` refused to retry the payment.
+    */
+   LDKPaymentOutcomeStatus_SendFailed,
+   /**
+    * A failed path did not carry what is needed to retry it.
+    */
+   LDKPaymentOutcomeStatus_NotRetryable,
+   /**
+    * The payment was abandoned by something other than this payer.
+    */
+   LDKPaymentOutcomeStatus_Abandoned,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKPaymentOutcomeStatus_Sentinel,
+} LDKPaymentOutcomeStatus;
+
+/**
+ * What became of one path of a payment.
+ */
+typedef enum LDKPaymentPathStatus {
+   /**
+    * The path had not succeeded or failed when the outcome was reported.
+    */
+   LDKPaymentPathStatus_InFlight,
+   /**
+    * The path reached the destination.
+    */
+   LDKPaymentPathStatus_Succeeded,
+   /**
+    * The path failed.
+    */
+   LDKPaymentPathStatus_Failed,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKPaymentPathStatus_Sentinel,
+} LDKPaymentPathStatus;
+
+/**
+ * The status of a recorded payment.
+ */
+typedef enum LDKPaymentStatus {
+   /**
+    * The invoice has not been paid yet, or the outbound payment is still in flight.
+    */
+   LDKPaymentStatus_Pending,
+   /**
+    * The inbound payment was claimed, or the outbound payment reached its destination.
+    */
+   LDKPaymentStatus_Succeeded,
+   /**
+    * The outbound payment failed and will not be retried.
+    */
+   LDKPaymentStatus_Failed,
+   /**
+    * The invoice expired without being paid. Only reported for inbound payments.
+    */
+   LDKPaymentStatus_Expired,
+   /**
+    * The inbound payment was received (i.e. `Event::PaymentReceived` was handled for it) but
+    * has not been claimed yet. Only reported for inbound payments.
+    */
+   LDKPaymentStatus_Received,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKPaymentStatus_Sentinel,
+} LDKPaymentStatus;
+
+/**
+ * The reasons a PSBT cannot be used to fund a channel.
+ */
+typedef enum LDKPsbtFundingError {
+   /**
+    * The PSBT could not be deserialized, or its unsigned transaction already carries signatures.
+    */
+   LDKPsbtFundingError_InvalidPsbt,
+   /**
+    * No output of the PSBT pays the channel's funding script and value.
+    */
+   LDKPsbtFundingError_MissingChannelOutput,
+   /**
+    * More than one output pays the channel's funding script, making the funding output ambiguous.
+    */
+   LDKPsbtFundingError_DuplicateChannelOutput,
+   /**
+    * The PSBT does not spend any inputs.
+    */
+   LDKPsbtFundingError_NoInputs,
+   /**
+    * An input is neither finalized nor a P2WPKH spend with a partial signature which could be
+    * finalized here.
+    */
+   LDKPsbtFundingError_InputNotFinalized,
+   /**
+    * An input was finalized without a witness, ie spends a non-segwit output.
+    */
+   LDKPsbtFundingError_NonSegwitInput,
+   /**
+    * The `ChannelManager` rejected the funding transaction, eg because the channel has since been
+    * closed. Call `PsbtFunding_finalize` and `ChannelManager_funding_transaction_generated`
+    * directly to learn why.
+    */
+   LDKPsbtFundingError_FundingRejected,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKPsbtFundingError_Sentinel,
+} LDKPsbtFundingError;
+
+/**
+ * The reasons a node restore can fail.
+ */
+typedef enum LDKRestoreError {
+   /**
+    * The data directory (or store) contains neither a `ChannelManager` nor any `ChannelMonitor`s,
+    * i.e. this is a fresh node and a new `ChannelManager` should be created with `ChannelManager_new`.
+    */
+   LDKRestoreError_NoPersistedState,
+   /**
+    * `ChannelMonitor`s were found but no `ChannelManager` was. Starting a fresh `ChannelManager`
+    * here would lose track of the channels, so this requires manual intervention.
+    */
+   LDKRestoreError_ManagerNotFound,
+   /**
+    * One or more `ChannelMonitor`s could not be read or deserialized.
+    */
+   LDKRestoreError_MonitorReadFailed,
+   /**
+    * The `ChannelManager` could not be read or deserialized, or was inconsistent with the
+    * `ChannelMonitor`s on disk.
+    */
+   LDKRestoreError_ManagerReadFailed,
+   /**
+    * The [`ChainReplay`] failed to bring an object up to the chain tip.
+    */
+   LDKRestoreError_ChainReplayFailed,
+   /**
+    * The `ChainMonitor` already held `ChannelMonitor`s, or refused one of the restored ones, e.g.
+    * because its `Persist` implementation failed.
+    */
+   LDKRestoreError_MonitorWatchFailed,
+   /**
+    * The [`ChainReplay`] provided a block which could not be deserialized.
+    */
+   LDKRestoreError_InvalidBlockData,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKRestoreError_Sentinel,
+} LDKRestoreError;
+
 /**
  * Represents an error returned from libsecp256k1 during validation of some secp256k1 data
  */
@@ -337,6 +750,51 @@ typedef enum LDKSecp256k1Error {
    LDKSecp256k1Error_Sentinel,
 } LDKSecp256k1Error;
 
+/**
+ * The reasons a mnemonic or seed file operation can fail.
+ */
+typedef enum LDKSeedError {
+   /**
+    * The mnemonic does not have 12, 15, 18, 21 or 24 words, or such a word count was not given
+    * to `Mnemonic_generate`.
+    */
+   LDKSeedError_BadWordCount,
+   /**
+    * A word of the mnemonic is not in the BIP 39 English wordlist.
+    */
+   LDKSeedError_UnknownWord,
+   /**
+    * The mnemonic's checksum does not match, usually because a word was mistyped or the words
+    * are out of order.
+    */
+   LDKSeedError_InvalidChecksum,
+   /**
+    * The system's random number generator could not be read.
+    */
+   LDKSeedError_EntropyUnavailable,
+   /**
+    * `SeedFile_write` was given the path of an existing file, which it never overwrites.
+    */
+   LDKSeedError_FileExists,
+   /**
+    * The seed file could not be read or written.
+    */
+   LDKSeedError_FileAccessFailed,
+   /**
+    * The file is not a seed file, or is of an unknown version or truncated.
+    */
+   LDKSeedError_InvalidSeedFile,
+   /**
+    * The seed file failed to decrypt, either because the passphrase is wrong or because the file
+    * was corrupted.
+    */
+   LDKSeedError_WrongPassphrase,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKSeedError_Sentinel,
+} LDKSeedError;
+
 /**
  * Errors that may occur when converting a `RawInvoice` to an `Invoice`. They relate to the
  * requirements sections in BOLT #11
@@ -416,31 +874,156 @@ typedef enum LDKSiPrefix {
 } LDKSiPrefix;
 
 /**
- * A Rust str object, ie a reference to a UTF8-valid string.
- * This is *not* null-terminated so cannot be used directly as a C string!
+ * A rule enforced by a [`SignerPolicy`].
  */
-typedef struct LDKStr {
+typedef enum LDKSignerPolicyRule {
    /**
-    * A pointer to the string's bytes, in UTF8 encoding
+    * Holder commitment transactions whose per-commitment secret has been released may not be
+    * validated or signed, as our counterparty could claim the entire channel balance if one was
+    * broadcast.
     */
-   const uint8_t *chars;
+   LDKSignerPolicyRule_RevokedHolderCommitment,
    /**
-    * The number of bytes (not characters!) pointed to by `chars`
+    * Commitment transactions may only be validated or signed if their outputs, including HTLC
+    * outputs, are exactly those derived from the channel's keys, and transactions claiming
+    * channel outputs may only pay to known destination scripts.
     */
-   uintptr_t len;
+   LDKSignerPolicyRule_UnknownDestination,
    /**
-    * Whether the data pointed to by `chars` should be freed or not.
+    * Closing transactions may only pay our balance to a known shutdown script, which may not
+    * change once a closing transaction has been signed for the channel.
     */
-   bool chars_is_owned;
-} LDKStr;
+   LDKSignerPolicyRule_ShutdownScriptMismatch,
+   /**
+    * Commitment transactions may not be validated or signed, and counterparty revocations not
+    * accepted, for a commitment number older than one already seen for the same party.
+    */
+   LDKSignerPolicyRule_CommitmentNumberRegression,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKSignerPolicyRule_Sentinel,
+} LDKSignerPolicyRule;
 
 /**
- * A serialized transaction, in (pointer, length) form.
- *
- * This type optionally owns its own memory, and thus the semantics around access change based on
- * the `data_is_owned` flag. If `data_is_owned` is set, you must call `Transaction_free` to free
- * the underlying buffer before the object goes out of scope. If `data_is_owned` is not set, any
- * access to the buffer after the scope in which the object was provided to you is invalid. eg,
+ * The reasons an on-chain wallet operation can fail.
+ */
+typedef enum LDKWalletError {
+   /**
+    * The given address could not be parsed.
+    */
+   LDKWalletError_InvalidAddress,
+   /**
+    * The given address is for a different network than the wallet's.
+    */
+   LDKWalletError_WrongNetwork,
+   /**
+    * The amount to send is below the dust limit of 546 satoshis.
+    */
+   LDKWalletError_AmountBelowDust,
+   /**
+    * The wallet does not have enough confirmed funds to pay the amount and fees.
+    */
+   LDKWalletError_InsufficientFunds,
+   /**
+    * None of the given outputs need sweeping, as they pay to scripts the wallet already tracks.
+    */
+   LDKWalletError_NothingToSweep,
+   /**
+    * The `KeysManager` failed to build a transaction spending the given outputs, eg because
+    * their value would not cover the fee.
+    */
+   LDKWalletError_SweepFailed,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKWalletError_Sentinel,
+} LDKWalletError;
+
+typedef struct LDKnativeChannelManagerNotifier LDKnativeChannelManagerNotifier;
+
+#if defined(LDK_LINUX)
+typedef struct LDKnativeConnectionPolicy LDKnativeConnectionPolicy;
+#endif
+
+typedef struct LDKnativeCustomMessageRegistry LDKnativeCustomMessageRegistry;
+
+typedef struct LDKnativeEncryptedStore LDKnativeEncryptedStore;
+
+typedef struct LDKnativeEventQueue LDKnativeEventQueue;
+
+typedef struct LDKnativeFilesystemStore LDKnativeFilesystemStore;
+
+typedef struct LDKnativeHodlInvoiceManager LDKnativeHodlInvoiceManager;
+
+typedef struct LDKnativeInvoiceBuilderFields LDKnativeInvoiceBuilderFields;
+
+typedef struct LDKnativeKVStorePersister LDKnativeKVStorePersister;
+
+#if defined(LDK_LINUX)
+typedef struct LDKnativeLdkNet LDKnativeLdkNet;
+#endif
+
+typedef struct LDKnativeMessageTraceFile LDKnativeMessageTraceFile;
+
+typedef struct LDKnativeOnChainWallet LDKnativeOnChainWallet;
+
+typedef struct LDKnativeOutputSweeper LDKnativeOutputSweeper;
+
+typedef struct LDKnativePaymentStore LDKnativePaymentStore;
+
+typedef struct LDKnativePeerChannelEncryptor LDKnativePeerChannelEncryptor;
+
+#if defined(LDK_LINUX)
+typedef struct LDKnativePeerReconnector LDKnativePeerReconnector;
+#endif
+
+typedef struct LDKnativePsbtFunding LDKnativePsbtFunding;
+
+typedef struct LDKnativeRemoteSignerServer LDKnativeRemoteSignerServer;
+
+typedef struct LDKnativeRetryingInvoicePayer LDKnativeRetryingInvoicePayer;
+
+typedef struct LDKnativeSignerPolicy LDKnativeSignerPolicy;
+
+#if defined(LDK_LINUX)
+typedef struct LDKnativeSocks5Proxy LDKnativeSocks5Proxy;
+#endif
+
+#if defined(LDK_SQLITE)
+typedef struct LDKnativeSqliteStore LDKnativeSqliteStore;
+#endif
+
+typedef struct LDKnativeTracingMessageHandler LDKnativeTracingMessageHandler;
+
+typedef struct LDKnativeUpdateLogPersister LDKnativeUpdateLogPersister;
+
+/**
+ * A Rust str object, ie a reference to a UTF8-valid string.
+ * This is *not* null-terminated so cannot be used directly as a C string!
+ */
+typedef struct LDKStr {
+   /**
+    * A pointer to the string's bytes, in UTF8 encoding
+    */
+   const uint8_t *chars;
+   /**
+    * The number of bytes (not characters!) pointed to by `chars`
+    */
+   uintptr_t len;
+   /**
+    * Whether the data pointed to by `chars` should be freed or not.
+    */
+   bool chars_is_owned;
+} LDKStr;
+
+/**
+ * A serialized transaction, in (pointer, length) form.
+ *
+ * This type optionally owns its own memory, and thus the semantics around access change based on
+ * the `data_is_owned` flag. If `data_is_owned` is set, you must call `Transaction_free` to free
+ * the underlying buffer before the object goes out of scope. If `data_is_owned` is not set, any
+ * access to the buffer after the scope in which the object was provided to you is invalid. eg,
  * access after you return from the call in which a `!data_is_owned` `Transaction` is provided to
  * you would be invalid.
  *
@@ -466,6 +1049,36 @@ typedef struct LDKTransaction {
    bool data_is_owned;
 } LDKTransaction;
 
+/**
+ * A BIP 174 Partially Signed Bitcoin Transaction, in its binary serialization, in (pointer,
+ * length) form.
+ *
+ * This type optionally owns its own memory, and thus the semantics around access change based on
+ * the `data_is_owned` flag. If `data_is_owned` is set, you must call `Psbt_free` to free the
+ * underlying buffer before the object goes out of scope. If `data_is_owned` is not set, any
+ * access to the buffer after the scope in which the object was provided to you is invalid.
+ *
+ * As with `Transaction`, all `Psbt`s generated on the Rust side will have `data_is_owned` set,
+ * while `Psbt`s you pass to Rust may have `data_is_owned` either set or unset at your discretion.
+ * Most wallets exchange PSBTs base64-encoded, which must be decoded before being passed to Rust.
+ */
+typedef struct LDKPsbt {
+   /**
+    * The serialized PSBT data.
+    *
+    * This is non-const for your convenience, an object passed to Rust is never written to.
+    */
+   uint8_t *data;
+   /**
+    * The length of the serialized PSBT
+    */
+   uintptr_t datalen;
+   /**
+    * Whether the data pointed to by `data` should be freed or not.
+    */
+   bool data_is_owned;
+} LDKPsbt;
+
 /**
  * A dynamically-allocated array of u8s of arbitrary size.
  * This corresponds to std::vector in C++
@@ -9929,317 +10542,207 @@ typedef struct LDKCVec_OutPointZ {
    uintptr_t datalen;
 } LDKCVec_OutPointZ;
 
+
+
 /**
- * A trait indicating an object may generate message send events
+ * The best known block as identified by its hash and height.
  */
-typedef struct LDKMessageSendEventsProvider {
+typedef struct MUST_USE_STRUCT LDKBestBlock {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeBestBlock *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKBestBlock;
+
+/**
+ * The `Listen` trait is used to notify when blocks have been connected or disconnected from the
+ * chain.
+ *
+ * Useful when needing to replay chain data upon startup or as new chain events occur. Clients
+ * sourcing chain data using a block-oriented API should prefer this interface over [`Confirm`].
+ * Such clients fetch the entire header chain whereas clients using [`Confirm`] only fetch headers
+ * when needed.
+ */
+typedef struct LDKListen {
    /**
     * An opaque pointer which is passed to your function implementations as an argument.
     * This has no meaning in the LDK, and can be NULL or any other value.
     */
    void *this_arg;
    /**
-    * Gets the list of pending events which were generated by previous actions, clearing the list
-    * in the process.
+    * Notifies the listener that a block was added at the given height.
     */
-   struct LDKCVec_MessageSendEventZ (*get_and_clear_pending_msg_events)(const void *this_arg);
+   void (*block_connected)(const void *this_arg, struct LDKu8slice block, uint32_t height);
+   /**
+    * Notifies the listener that a block was removed at the given height.
+    */
+   void (*block_disconnected)(const void *this_arg, const uint8_t (*header)[80], uint32_t height);
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKMessageSendEventsProvider;
+} LDKListen;
 
 /**
- * A trait implemented for objects handling events from [`EventsProvider`].
+ * A source of chain data which can replay blocks into a [`Listen`]er, used to bring restored
+ * objects up to the current chain tip before they go live.
  */
-typedef struct LDKEventHandler {
+typedef struct LDKChainReplay {
    /**
     * An opaque pointer which is passed to your function implementations as an argument.
     * This has no meaning in the LDK, and can be NULL or any other value.
     */
    void *this_arg;
    /**
-    * Handles the given [`Event`].
+    * Brings `listener` from `from` to the current chain tip.
     *
-    * See [`EventsProvider`] for details that must be considered when implementing this method.
+    * If `from` is no longer in the best chain, the blocks which were reorganized out must first
+    * be disconnected (in descending height order) down to the fork point. Every block after the
+    * fork point must then be connected, in ascending height order, up to the current tip.
+    *
+    * `listener` is only valid for the duration of this call.
+    *
+    * Return false if the required blocks could not be fetched, which aborts the restore.
     */
-   void (*handle_event)(const void *this_arg, const struct LDKEvent *NONNULL_PTR event);
+   bool (*replay)(const void *this_arg, const struct LDKBestBlock *NONNULL_PTR from, const struct LDKListen *NONNULL_PTR listener);
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKEventHandler;
+} LDKChainReplay;
 
 /**
- * A trait indicating an object may generate events.
- *
- * Events are processed by passing an [`EventHandler`] to [`process_pending_events`].
- *
- * # Requirements
- *
- * See [`process_pending_events`] for requirements around event processing.
- *
- * When using this trait, [`process_pending_events`] will call [`handle_event`] for each pending
- * event since the last invocation. The handler must either act upon the event immediately
- * or preserve it for later handling.
- *
- * Note, handlers may call back into the provider and thus deadlocking must be avoided. Be sure to
- * consult the provider's documentation on the implication of processing events and how a handler
- * may safely use the provider (e.g., see [`ChannelManager::process_pending_events`] and
- * [`ChainMonitor::process_pending_events`]).
- *
- * (C-not implementable) As there is likely no reason for a user to implement this trait on their
- * own type(s).
- *
- * [`process_pending_events`]: Self::process_pending_events
- * [`handle_event`]: EventHandler::handle_event
- * [`ChannelManager::process_pending_events`]: crate::ln::channelmanager::ChannelManager#method.process_pending_events
- * [`ChainMonitor::process_pending_events`]: crate::chain::chainmonitor::ChainMonitor#method.process_pending_events
+ * A `ChannelMonitor` which was restored and loaded into the `ChainMonitor`.
  */
-typedef struct LDKEventsProvider {
+typedef struct LDKRestoredMonitor {
    /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
+    * The funding outpoint of the channel the monitor is for.
     */
-   void *this_arg;
+   struct LDKOutPoint funding_txo;
    /**
-    * Processes any events generated since the last call using the given event handler.
-    *
-    * Subsequent calls must only process new events. However, handlers must be capable of handling
-    * duplicate events across process restarts. This may occur if the provider was recovered from
-    * an old state (i.e., it hadn't been successfully persisted after processing pending events).
+    * The latest update ID which had been applied to the monitor on disk.
     */
-   void (*process_pending_events)(const void *this_arg, struct LDKEventHandler handler);
+   uint64_t latest_update_id;
    /**
-    * Frees any resources associated with this object given its this_arg pointer.
-    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    * The best block the monitor had seen when it was last persisted, from which it was synced.
     */
-   void (*free)(void *this_arg);
-} LDKEventsProvider;
+   struct LDKBestBlock synced_from;
+} LDKRestoredMonitor;
+
+/**
+ * A dynamically-allocated array of RestoredMonitors of arbitrary size.
+ * This corresponds to std::vector in C++
+ */
+typedef struct LDKCVec_RestoredMonitorZ {
+   /**
+    * The elements in the array.
+    * If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
+    */
+   struct LDKRestoredMonitor *data;
+   /**
+    * The number of elements pointed to by `data`.
+    */
+   uintptr_t datalen;
+} LDKCVec_RestoredMonitorZ;
 
+/**
+ * The result of a successful [`FilesystemPersister_restore_node`], [`KVStorePersister_restore_node`],
+ * [`UpdateLogPersister_restore_node`] or `SqliteStore_restore_node`.
+ */
+typedef struct LDKRestoredNode {
+   /**
+    * The restored `ChannelManager`, synced to the chain tip.
+    */
+   struct LDKChannelManager channel_manager;
+   /**
+    * The best block the `ChannelManager` had seen when it was last persisted, from which it
+    * was synced.
+    */
+   struct LDKBestBlock manager_synced_from;
+   /**
+    * The `ChannelMonitor`s which are now being watched by the `ChainMonitor`.
+    */
+   struct LDKCVec_RestoredMonitorZ monitors;
+   /**
+    * The chain tip all restored objects were synced to. Further chain data should be provided
+    * starting from the block after this one.
+    */
+   struct LDKBestBlock best_block;
+} LDKRestoredNode;
 
+/**
+ * The contents of CResult_RestoredNodeRestoreErrorZ
+ */
+typedef union LDKCResult_RestoredNodeRestoreErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKRestoredNode *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKRestoreError *err;
+} LDKCResult_RestoredNodeRestoreErrorZPtr;
 
 /**
- * Configuration we set when applicable.
- *
- * Default::default() provides sane defaults.
+ * A CResult_RestoredNodeRestoreErrorZ represents the result of a fallible operation,
+ * containing a RestoredNode on success and a RestoreError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
  */
-typedef struct MUST_USE_STRUCT LDKChannelHandshakeConfig {
+typedef struct LDKCResult_RestoredNodeRestoreErrorZ {
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * The contents of this CResult_RestoredNodeRestoreErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
     */
-   LDKnativeChannelHandshakeConfig *inner;
+   union LDKCResult_RestoredNodeRestoreErrorZPtr contents;
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Whether this CResult_RestoredNodeRestoreErrorZ represents a success state.
     */
-   bool is_owned;
-} LDKChannelHandshakeConfig;
+   bool result_ok;
+} LDKCResult_RestoredNodeRestoreErrorZ;
 
 
 
 /**
- * Optional channel limits which are applied during channel creation.
- *
- * These limits are only applied to our counterparty's limits, not our own.
- *
- * Use 0/<type>::max_value() as appropriate to skip checking.
- *
- * Provides sane defaults for most configurations.
+ * FilesystemPersister persists channel data on disk, where each channel's
+ * data is stored in a file named after its funding outpoint.
  *
- * Most additional limits are disabled except those with which specify a default in individual
- * field documentation. Note that this may result in barely-usable channels, but since they
- * are applied mostly only to incoming channels that's not much of a problem.
+ * Warning: this module does the best it can with calls to persist data, but it
+ * can only guarantee that the data is passed to the drive. It is up to the
+ * drive manufacturers to do the actual persistence properly, which they often
+ * don't (especially on consumer-grade hardware). Therefore, it is up to the
+ * user to validate their entire storage stack, to ensure the writes are
+ * persistent.
+ * Corollary: especially when dealing with larger amounts of money, it is best
+ * practice to have multiple channel data backups and not rely only on one
+ * FilesystemPersister.
  */
-typedef struct MUST_USE_STRUCT LDKChannelHandshakeLimits {
+typedef struct MUST_USE_STRUCT LDKFilesystemPersister {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeChannelHandshakeLimits *inner;
+   LDKnativeFilesystemPersister *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKChannelHandshakeLimits;
-
-
-
-/**
- * Top-level config which holds ChannelHandshakeLimits and ChannelConfig.
- *
- * Default::default() provides sane defaults for most configurations
- * (but currently with 0 relay fees!)
- */
-typedef struct MUST_USE_STRUCT LDKUserConfig {
-   /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
-    */
-   LDKnativeUserConfig *inner;
-   /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
-    */
-   bool is_owned;
-} LDKUserConfig;
-
-
-
-/**
- * The best known block as identified by its hash and height.
- */
-typedef struct MUST_USE_STRUCT LDKBestBlock {
-   /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
-    */
-   LDKnativeBestBlock *inner;
-   /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
-    */
-   bool is_owned;
-} LDKBestBlock;
-
-/**
- * The `Listen` trait is used to notify when blocks have been connected or disconnected from the
- * chain.
- *
- * Useful when needing to replay chain data upon startup or as new chain events occur. Clients
- * sourcing chain data using a block-oriented API should prefer this interface over [`Confirm`].
- * Such clients fetch the entire header chain whereas clients using [`Confirm`] only fetch headers
- * when needed.
- */
-typedef struct LDKListen {
-   /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
-    */
-   void *this_arg;
-   /**
-    * Notifies the listener that a block was added at the given height.
-    */
-   void (*block_connected)(const void *this_arg, struct LDKu8slice block, uint32_t height);
-   /**
-    * Notifies the listener that a block was removed at the given height.
-    */
-   void (*block_disconnected)(const void *this_arg, const uint8_t (*header)[80], uint32_t height);
-   /**
-    * Frees any resources associated with this object given its this_arg pointer.
-    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
-    */
-   void (*free)(void *this_arg);
-} LDKListen;
-
-/**
- * The `Confirm` trait is used to notify when transactions have been confirmed on chain or
- * unconfirmed during a chain reorganization.
- *
- * Clients sourcing chain data using a transaction-oriented API should prefer this interface over
- * [`Listen`]. For instance, an Electrum client may implement [`Filter`] by subscribing to activity
- * related to registered transactions and outputs. Upon notification, it would pass along the
- * matching transactions using this interface.
- *
- * # Use
- *
- * The intended use is as follows:
- * - Call [`transactions_confirmed`] to process any on-chain activity of interest.
- * - Call [`transaction_unconfirmed`] to process any transaction returned by [`get_relevant_txids`]
- *   that has been reorganized out of the chain.
- * - Call [`best_block_updated`] whenever a new chain tip becomes available.
- *
- * # Order
- *
- * Clients must call these methods in chain order. Specifically:
- * - Transactions confirmed in a block must be given before transactions confirmed in a later
- *   block.
- * - Dependent transactions within the same block must be given in topological order, possibly in
- *   separate calls.
- * - Unconfirmed transactions must be given after the original confirmations and before any
- *   reconfirmation.
- *
- * See individual method documentation for further details.
- *
- * [`transactions_confirmed`]: Self::transactions_confirmed
- * [`transaction_unconfirmed`]: Self::transaction_unconfirmed
- * [`best_block_updated`]: Self::best_block_updated
- * [`get_relevant_txids`]: Self::get_relevant_txids
- */
-typedef struct LDKConfirm {
-   /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
-    */
-   void *this_arg;
-   /**
-    * Processes transactions confirmed in a block with a given header and height.
-    *
-    * Should be called for any transactions registered by [`Filter::register_tx`] or any
-    * transactions spending an output registered by [`Filter::register_output`]. Such transactions
-    * appearing in the same block do not need to be included in the same call; instead, multiple
-    * calls with additional transactions may be made so long as they are made in [chain order].
-    *
-    * May be called before or after [`best_block_updated`] for the corresponding block. However,
-    * in the event of a chain reorganization, it must not be called with a `header` that is no
-    * longer in the chain as of the last call to [`best_block_updated`].
-    *
-    * [chain order]: Confirm#Order
-    * [`best_block_updated`]: Self::best_block_updated
-    */
-   void (*transactions_confirmed)(const void *this_arg, const uint8_t (*header)[80], struct LDKCVec_C2Tuple_usizeTransactionZZ txdata, uint32_t height);
-   /**
-    * Processes a transaction that is no longer confirmed as result of a chain reorganization.
-    *
-    * Should be called for any transaction returned by [`get_relevant_txids`] if it has been
-    * reorganized out of the best chain. Once called, the given transaction should not be returned
-    * by [`get_relevant_txids`] unless it has been reconfirmed via [`transactions_confirmed`].
-    *
-    * [`get_relevant_txids`]: Self::get_relevant_txids
-    * [`transactions_confirmed`]: Self::transactions_confirmed
-    */
-   void (*transaction_unconfirmed)(const void *this_arg, const uint8_t (*txid)[32]);
-   /**
-    * Processes an update to the best header connected at the given height.
-    *
-    * Should be called when a new header is available but may be skipped for intermediary blocks
-    * if they become available at the same time.
-    */
-   void (*best_block_updated)(const void *this_arg, const uint8_t (*header)[80], uint32_t height);
-   /**
-    * Returns transactions that should be monitored for reorganization out of the chain.
-    *
-    * Should include any transactions passed to [`transactions_confirmed`] that have insufficient
-    * confirmations to be safe from a chain reorganization. Should not include any transactions
-    * passed to [`transaction_unconfirmed`] unless later reconfirmed.
-    *
-    * May be called to determine the subset of transactions that must still be monitored for
-    * reorganization. Will be idempotent between calls but may change as a result of calls to the
-    * other interface methods. Thus, this is useful to determine which transactions may need to be
-    * given to [`transaction_unconfirmed`].
-    *
-    * [`transactions_confirmed`]: Self::transactions_confirmed
-    * [`transaction_unconfirmed`]: Self::transaction_unconfirmed
-    */
-   struct LDKCVec_TxidZ (*get_relevant_txids)(const void *this_arg);
-   /**
-    * Frees any resources associated with this object given its this_arg pointer.
-    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
-    */
-   void (*free)(void *this_arg);
-} LDKConfirm;
+} LDKFilesystemPersister;
 
 
 
@@ -10382,209 +10885,235 @@ typedef struct MUST_USE_STRUCT LDKChainMonitor {
 
 
 /**
- * Simple KeysInterface implementor that takes a 32-byte seed for use as a BIP 32 extended key
- * and derives keys from that.
+ * Top-level config which holds ChannelHandshakeLimits and ChannelConfig.
  *
- * Your node_id is seed/0'
- * ChannelMonitor closes may use seed/1'
- * Cooperative closes may use seed/2'
- * The two close keys may be needed to claim on-chain funds!
+ * Default::default() provides sane defaults for most configurations
+ * (but currently with 0 relay fees!)
  */
-typedef struct MUST_USE_STRUCT LDKKeysManager {
+typedef struct MUST_USE_STRUCT LDKUserConfig {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeKeysManager *inner;
+   LDKnativeUserConfig *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKKeysManager;
-
-
+} LDKUserConfig;
 
 /**
- * Chain-related parameters used to construct a new `ChannelManager`.
+ * Persists `ChannelMonitor`s, the `ChannelManager`, the `NetworkGraph` and the `Scorer` in a
+ * [`KVStore`].
  *
- * Typically, the block-specific parameters are derived from the best block hash for the network,
- * as a newly constructed `ChannelManager` will not have created any channels yet. These parameters
- * are not needed when deserializing a previously constructed `ChannelManager`.
+ * `ChannelMonitor`s are stored in the `"monitors"` namespace, keyed by their funding outpoint
+ * as `<txid>_<output index>`. The remaining objects are stored in the `""` namespace under the
+ * keys `"manager"`, `"network_graph"` and `"scorer"`.
  */
-typedef struct MUST_USE_STRUCT LDKChainParameters {
+typedef struct MUST_USE_STRUCT LDKKVStorePersister {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeChainParameters *inner;
+   struct LDKnativeKVStorePersister *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKChainParameters;
-
-
+} LDKKVStorePersister;
 
 /**
- * Information needed for constructing an invoice route hint for this channel.
+ * Persists `ChannelMonitor`s in a [`KVStore`] by logging each `ChannelMonitorUpdate` and only
+ * periodically rewriting the full `ChannelMonitor`.
+ *
+ * The same store may be shared with a [`KVStorePersister`] to persist the `ChannelManager`,
+ * `NetworkGraph` and `Scorer`, though `ChannelMonitor`s must then only be read via
+ * [`UpdateLogPersister_read_channelmonitors`] or `UpdateLogPersister_restore_node`, never via
+ * `KVStorePersister_read_channelmonitors` or `KVStorePersister_restore_node`.
+ *
+ * [`KVStore`]: crate::extensions::kv_store::KVStore
+ * [`KVStorePersister`]: crate::extensions::kv_store::KVStorePersister
  */
-typedef struct MUST_USE_STRUCT LDKCounterpartyForwardingInfo {
+typedef struct MUST_USE_STRUCT LDKUpdateLogPersister {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeCounterpartyForwardingInfo *inner;
+   struct LDKnativeUpdateLogPersister *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKCounterpartyForwardingInfo;
-
-
+} LDKUpdateLogPersister;
 
+#if defined(LDK_SQLITE)
 /**
- * Channel parameters which apply to our counterparty. These are split out from [`ChannelDetails`]
- * to better separate parameters.
+ * A [`KVStore`] and `Persist` implementation backed by a single SQLite database file.
+ *
+ * Until [`SqliteStore_register_chain_monitor`] is called, `ChannelMonitor`s are committed
+ * synchronously. Afterwards, `Persist` calls return `ChannelMonitorUpdateErr_TemporaryFailure`
+ * and the monitors are committed in batches on a background thread, after which
+ * `ChainMonitor_channel_monitor_updated` is called for each of them. If a commit fails it is
+ * retried, with the affected channels remaining paused until it succeeds.
+ *
+ * `ChannelMonitorUpdate`s are stored individually, and the full `ChannelMonitor` is only
+ * rewritten periodically, in the same transaction which removes the updates it contains. Each
+ * `KVStore` write is committed in its own transaction.
+ *
+ * [`KVStore`]: crate::extensions::kv_store::KVStore
  */
-typedef struct MUST_USE_STRUCT LDKChannelCounterparty {
+typedef struct MUST_USE_STRUCT LDKSqliteStore {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeChannelCounterparty *inner;
+   struct LDKnativeSqliteStore *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKChannelCounterparty;
+} LDKSqliteStore;
+#endif
 
 /**
- * A 3-byte byte array.
+ * The contents of CResult_CVec_u8ZIOErrorZ
  */
-typedef struct LDKThreeBytes {
+typedef union LDKCResult_CVec_u8ZIOErrorZPtr {
    /**
-    * The three bytes
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
     */
-   uint8_t data[3];
-} LDKThreeBytes;
+   struct LDKCVec_u8Z *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_CVec_u8ZIOErrorZPtr;
 
 /**
- * A trait to describe an object which can receive channel messages.
- *
- * Messages MAY be called in parallel when they originate from different their_node_ids, however
- * they MUST NOT be called in parallel when the two calls have the same their_node_id.
+ * A CResult_CVec_u8ZIOErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::derived::CVec_u8Z on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
  */
-typedef struct LDKChannelMessageHandler {
+typedef struct LDKCResult_CVec_u8ZIOErrorZ {
    /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
+    * The contents of this CResult_CVec_u8ZIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
     */
-   void *this_arg;
+   union LDKCResult_CVec_u8ZIOErrorZPtr contents;
    /**
-    * Handle an incoming open_channel message from the given peer.
+    * Whether this CResult_CVec_u8ZIOErrorZ represents a success state.
     */
-   void (*handle_open_channel)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKInitFeatures their_features, const struct LDKOpenChannel *NONNULL_PTR msg);
+   bool result_ok;
+} LDKCResult_CVec_u8ZIOErrorZ;
+
+/**
+ * A dynamically-allocated array of crate::c_types::Strs of arbitrary size.
+ * This corresponds to std::vector in C++
+ */
+typedef struct LDKCVec_StrZ {
    /**
-    * Handle an incoming accept_channel message from the given peer.
+    * The elements in the array.
+    * If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
     */
-   void (*handle_accept_channel)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKInitFeatures their_features, const struct LDKAcceptChannel *NONNULL_PTR msg);
+   struct LDKStr *data;
    /**
-    * Handle an incoming funding_created message from the given peer.
+    * The number of elements pointed to by `data`.
     */
-   void (*handle_funding_created)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKFundingCreated *NONNULL_PTR msg);
+   uintptr_t datalen;
+} LDKCVec_StrZ;
+
+/**
+ * The contents of CResult_CVec_StrZIOErrorZ
+ */
+typedef union LDKCResult_CVec_StrZIOErrorZPtr {
    /**
-    * Handle an incoming funding_signed message from the given peer.
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
     */
-   void (*handle_funding_signed)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKFundingSigned *NONNULL_PTR msg);
+   struct LDKCVec_StrZ *result;
    /**
-    * Handle an incoming funding_locked message from the given peer.
-    */
-   void (*handle_funding_locked)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKFundingLocked *NONNULL_PTR msg);
-   /**
-    * Handle an incoming shutdown message from the given peer.
-    */
-   void (*handle_shutdown)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKInitFeatures *NONNULL_PTR their_features, const struct LDKShutdown *NONNULL_PTR msg);
-   /**
-    * Handle an incoming closing_signed message from the given peer.
-    */
-   void (*handle_closing_signed)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKClosingSigned *NONNULL_PTR msg);
-   /**
-    * Handle an incoming update_add_htlc message from the given peer.
-    */
-   void (*handle_update_add_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateAddHTLC *NONNULL_PTR msg);
-   /**
-    * Handle an incoming update_fulfill_htlc message from the given peer.
-    */
-   void (*handle_update_fulfill_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFulfillHTLC *NONNULL_PTR msg);
-   /**
-    * Handle an incoming update_fail_htlc message from the given peer.
-    */
-   void (*handle_update_fail_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFailHTLC *NONNULL_PTR msg);
-   /**
-    * Handle an incoming update_fail_malformed_htlc message from the given peer.
-    */
-   void (*handle_update_fail_malformed_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFailMalformedHTLC *NONNULL_PTR msg);
-   /**
-    * Handle an incoming commitment_signed message from the given peer.
-    */
-   void (*handle_commitment_signed)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKCommitmentSigned *NONNULL_PTR msg);
-   /**
-    * Handle an incoming revoke_and_ack message from the given peer.
-    */
-   void (*handle_revoke_and_ack)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKRevokeAndACK *NONNULL_PTR msg);
-   /**
-    * Handle an incoming update_fee message from the given peer.
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
     */
-   void (*handle_update_fee)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFee *NONNULL_PTR msg);
+   enum LDKIOError *err;
+} LDKCResult_CVec_StrZIOErrorZPtr;
+
+/**
+ * A CResult_CVec_StrZIOErrorZ represents the result of a fallible operation,
+ * containing a CVec_StrZ on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_CVec_StrZIOErrorZ {
    /**
-    * Handle an incoming announcement_signatures message from the given peer.
+    * The contents of this CResult_CVec_StrZIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
     */
-   void (*handle_announcement_signatures)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKAnnouncementSignatures *NONNULL_PTR msg);
+   union LDKCResult_CVec_StrZIOErrorZPtr contents;
    /**
-    * Indicates a connection to the peer failed/an existing connection was lost. If no connection
-    * is believed to be possible in the future (eg they're sending us messages we don't
-    * understand or indicate they require unknown feature bits), no_connection_possible is set
-    * and any outstanding channels should be failed.
+    * Whether this CResult_CVec_StrZIOErrorZ represents a success state.
     */
-   void (*peer_disconnected)(const void *this_arg, struct LDKPublicKey their_node_id, bool no_connection_possible);
+   bool result_ok;
+} LDKCResult_CVec_StrZIOErrorZ;
+
+/**
+ * A simple namespaced key-value store.
+ *
+ * Keys are grouped into namespaces, each of which can be listed independently. Both namespaces
+ * and keys are UTF-8 strings containing only ASCII alphanumerics, `_` and `-`, though the
+ * namespace may also be the empty string.
+ *
+ * All `Str`s and `u8slice`s passed to the store are only valid for the duration of the call.
+ */
+typedef struct LDKKVStore {
    /**
-    * Handle a peer reconnecting, possibly generating channel_reestablish message(s).
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
     */
-   void (*peer_connected)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKInit *NONNULL_PTR msg);
+   void *this_arg;
    /**
-    * Handle an incoming channel_reestablish message from the given peer.
+    * Returns the value stored under `key` in `key_namespace`.
+    *
+    * If no value is stored under the key, an `IOError_NotFound` must be returned.
     */
-   void (*handle_channel_reestablish)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKChannelReestablish *NONNULL_PTR msg);
+   struct LDKCResult_CVec_u8ZIOErrorZ (*read)(const void *this_arg, struct LDKStr key_namespace, struct LDKStr key);
    /**
-    * Handle an incoming channel update from the given peer.
+    * Stores `value` under `key` in `key_namespace`, replacing any existing value.
+    *
+    * The write must be atomic, i.e. a subsequent (or concurrent) `read` must either return the
+    * previous value or the new one in its entirety, even if the process crashes during the
+    * write. It must also be durable before returning success.
     */
-   void (*handle_channel_update)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKChannelUpdate *NONNULL_PTR msg);
+   struct LDKCResult_NoneErrorZ (*write)(const void *this_arg, struct LDKStr key_namespace, struct LDKStr key, struct LDKu8slice value);
    /**
-    * Handle an incoming error message from the given peer.
+    * Removes the value stored under `key` in `key_namespace`.
+    *
+    * Removing a key which does not exist must succeed.
     */
-   void (*handle_error)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKErrorMessage *NONNULL_PTR msg);
+   struct LDKCResult_NoneErrorZ (*remove)(const void *this_arg, struct LDKStr key_namespace, struct LDKStr key);
    /**
-    * Implementation of MessageSendEventsProvider for this object.
+    * Lists all keys which currently have a value stored in `key_namespace`, in any order.
     */
-   struct LDKMessageSendEventsProvider MessageSendEventsProvider;
+   struct LDKCResult_CVec_StrZIOErrorZ (*list)(const void *this_arg, struct LDKStr key_namespace);
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKChannelMessageHandler;
+} LDKKVStore;
 
 
 
@@ -10637,4712 +11166,9074 @@ typedef struct MUST_USE_STRUCT LDKChannelManagerReadArgs {
    bool is_owned;
 } LDKChannelManagerReadArgs;
 
-
+/**
+ * Trait which handles persisting a [`ChannelManager`] to disk.
+ *
+ * [`ChannelManager`]: lightning::ln::channelmanager::ChannelManager
+ */
+typedef struct LDKChannelManagerPersister {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Persist the given [`ChannelManager`] to disk, returning an error if persistence failed
+    * (which will cause the [`BackgroundProcessor`] which called this method to exit.
+    *
+    * [`ChannelManager`]: lightning::ln::channelmanager::ChannelManager
+    */
+   struct LDKCResult_NoneErrorZ (*persist_manager)(const void *this_arg, const struct LDKChannelManager *NONNULL_PTR channel_manager);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKChannelManagerPersister;
 
 /**
- * Proof that the sender knows the per-commitment secret of the previous commitment transaction.
- * This is used to convince the recipient that the channel is at a certain commitment
- * number even if they lost that data due to a local failure.  Of course, the peer may lie
- * and even later commitments may have been revoked.
+ * A [`KVStore`] which stores each value in its own file in a data directory.
+ *
+ * [`KVStore`]: crate::extensions::kv_store::KVStore
  */
-typedef struct MUST_USE_STRUCT LDKDataLossProtect {
+typedef struct MUST_USE_STRUCT LDKFilesystemStore {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeDataLossProtect *inner;
+   struct LDKnativeFilesystemStore *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKDataLossProtect;
+} LDKFilesystemStore;
 
 /**
- * A trait to describe an object which can receive routing messages.
- *
- * # Implementor DoS Warnings
+ * A [`KVStore`] which encrypts all values written to an underlying [`KVStore`] with a key
+ * derived from the node seed, and which rejects any value which fails authentication on read
+ * with an `IOError_InvalidData`.
  *
- * For `gossip_queries` messages there are potential DoS vectors when handling
- * inbound queries. Implementors using an on-disk network graph should be aware of
- * repeated disk I/O for queries accessing different parts of the network graph.
+ * [`KVStore`]: crate::extensions::kv_store::KVStore
  */
-typedef struct LDKRoutingMessageHandler {
+typedef struct MUST_USE_STRUCT LDKEncryptedStore {
    /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   void *this_arg;
+   struct LDKnativeEncryptedStore *inner;
    /**
-    * Handle an incoming node_announcement message, returning true if it should be forwarded on,
-    * false or returning an Err otherwise.
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
     */
-   struct LDKCResult_boolLightningErrorZ (*handle_node_announcement)(const void *this_arg, const struct LDKNodeAnnouncement *NONNULL_PTR msg);
+   bool is_owned;
+} LDKEncryptedStore;
+
+/**
+ * The contents of CResult_KVStorePersisterIOErrorZ
+ */
+typedef union LDKCResult_KVStorePersisterIOErrorZPtr {
    /**
-    * Handle a channel_announcement message, returning true if it should be forwarded on, false
-    * or returning an Err otherwise.
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
     */
-   struct LDKCResult_boolLightningErrorZ (*handle_channel_announcement)(const void *this_arg, const struct LDKChannelAnnouncement *NONNULL_PTR msg);
+   struct LDKKVStorePersister *result;
    /**
-    * Handle an incoming channel_update message, returning true if it should be forwarded on,
-    * false or returning an Err otherwise.
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
     */
-   struct LDKCResult_boolLightningErrorZ (*handle_channel_update)(const void *this_arg, const struct LDKChannelUpdate *NONNULL_PTR msg);
+   enum LDKIOError *err;
+} LDKCResult_KVStorePersisterIOErrorZPtr;
+
+/**
+ * A CResult_KVStorePersisterIOErrorZ represents the result of a fallible operation,
+ * containing a KVStorePersister on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_KVStorePersisterIOErrorZ {
    /**
-    * Gets a subset of the channel announcements and updates required to dump our routing table
-    * to a remote node, starting at the short_channel_id indicated by starting_point and
-    * including the batch_amount entries immediately higher in numerical value than starting_point.
+    * The contents of this CResult_KVStorePersisterIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
     */
-   struct LDKCVec_C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZZ (*get_next_channel_announcements)(const void *this_arg, uint64_t starting_point, uint8_t batch_amount);
+   union LDKCResult_KVStorePersisterIOErrorZPtr contents;
    /**
-    * Gets a subset of the node announcements required to dump our routing table to a remote node,
-    * starting at the node *after* the provided publickey and including batch_amount entries
-    * immediately higher (as defined by <PublicKey as Ord>::cmp) than starting_point.
-    * If None is provided for starting_point, we start at the first node.
-    *
-    * Note that starting_point (or a relevant inner pointer) may be NULL or all-0s to represent None
+    * Whether this CResult_KVStorePersisterIOErrorZ represents a success state.
     */
-   struct LDKCVec_NodeAnnouncementZ (*get_next_node_announcements)(const void *this_arg, struct LDKPublicKey starting_point, uint8_t batch_amount);
+   bool result_ok;
+} LDKCResult_KVStorePersisterIOErrorZ;
+
+/**
+ * The contents of CResult_UpdateLogPersisterIOErrorZ
+ */
+typedef union LDKCResult_UpdateLogPersisterIOErrorZPtr {
    /**
-    * Called when a connection is established with a peer. This can be used to
-    * perform routing table synchronization using a strategy defined by the
-    * implementor.
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
     */
-   void (*sync_routing_table)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKInit *NONNULL_PTR init);
+   struct LDKUpdateLogPersister *result;
    /**
-    * Handles the reply of a query we initiated to learn about channels
-    * for a given range of blocks. We can expect to receive one or more
-    * replies to a single query.
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
     */
-   struct LDKCResult_NoneLightningErrorZ (*handle_reply_channel_range)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKReplyChannelRange msg);
+   enum LDKIOError *err;
+} LDKCResult_UpdateLogPersisterIOErrorZPtr;
+
+/**
+ * A CResult_UpdateLogPersisterIOErrorZ represents the result of a fallible operation,
+ * containing a UpdateLogPersister on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_UpdateLogPersisterIOErrorZ {
    /**
-    * Handles the reply of a query we initiated asking for routing gossip
-    * messages for a list of channels. We should receive this message when
-    * a node has completed its best effort to send us the pertaining routing
-    * gossip messages.
+    * The contents of this CResult_UpdateLogPersisterIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
     */
-   struct LDKCResult_NoneLightningErrorZ (*handle_reply_short_channel_ids_end)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKReplyShortChannelIdsEnd msg);
+   union LDKCResult_UpdateLogPersisterIOErrorZPtr contents;
    /**
-    * Handles when a peer asks us to send a list of short_channel_ids
-    * for the requested range of blocks.
+    * Whether this CResult_UpdateLogPersisterIOErrorZ represents a success state.
     */
-   struct LDKCResult_NoneLightningErrorZ (*handle_query_channel_range)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKQueryChannelRange msg);
+   bool result_ok;
+} LDKCResult_UpdateLogPersisterIOErrorZ;
+
+/**
+ * Receives every message passing through a [`TracingMessageHandler`].
+ */
+typedef struct LDKMessageTracer {
    /**
-    * Handles when a peer asks us to send routing gossip messages for a
-    * list of short_channel_ids.
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
     */
-   struct LDKCResult_NoneLightningErrorZ (*handle_query_short_channel_ids)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKQueryShortChannelIds msg);
+   void *this_arg;
    /**
-    * Implementation of MessageSendEventsProvider for this object.
+    * Called with each message received from or about to be sent to the peer with the given
+    * node id. `msg_type` is the BOLT 1 message type and `data` the serialized message without
+    * its type, which is only valid for the duration of the call.
+    *
+    * This is called from within `PeerManager` and `ChannelManager` calls and should return
+    * quickly. The message is passed on whether or not tracing succeeds.
     */
-   struct LDKMessageSendEventsProvider MessageSendEventsProvider;
+   void (*trace)(const void *this_arg, struct LDKPublicKey node_id, enum LDKMessageDirection direction, uint16_t msg_type, struct LDKu8slice data);
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKRoutingMessageHandler;
+} LDKMessageTracer;
 
 /**
- * Trait to be implemented by custom message (unrelated to the channel/gossip LN layers)
- * decoders.
+ * Wraps a ChannelMessageHandler, RoutingMessageHandler and CustomMessageHandler, reporting
+ * every message passed to or generated by them to a [`MessageTracer`].
  */
-typedef struct LDKCustomMessageReader {
+typedef struct MUST_USE_STRUCT LDKTracingMessageHandler {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeTracingMessageHandler *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKTracingMessageHandler;
+
+/**
+ * A trait indicating an object may generate message send events
+ */
+typedef struct LDKMessageSendEventsProvider {
    /**
     * An opaque pointer which is passed to your function implementations as an argument.
     * This has no meaning in the LDK, and can be NULL or any other value.
     */
    void *this_arg;
    /**
-    * Decodes a custom message to `CustomMessageType`. If the given message type is known to the
-    * implementation and the message could be decoded, must return `Ok(Some(message))`. If the
-    * message type is unknown to the implementation, must return `Ok(None)`. If a decoding error
-    * occur, must return `Err(DecodeError::X)` where `X` details the encountered error.
+    * Gets the list of pending events which were generated by previous actions, clearing the list
+    * in the process.
     */
-   struct LDKCResult_COption_TypeZDecodeErrorZ (*read)(const void *this_arg, uint16_t message_type, struct LDKu8slice buffer);
+   struct LDKCVec_MessageSendEventZ (*get_and_clear_pending_msg_events)(const void *this_arg);
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKCustomMessageReader;
+} LDKMessageSendEventsProvider;
 
 /**
- * Handler for BOLT1-compliant messages.
+ * A trait to describe an object which can receive channel messages.
+ *
+ * Messages MAY be called in parallel when they originate from different their_node_ids, however
+ * they MUST NOT be called in parallel when the two calls have the same their_node_id.
  */
-typedef struct LDKCustomMessageHandler {
+typedef struct LDKChannelMessageHandler {
    /**
     * An opaque pointer which is passed to your function implementations as an argument.
     * This has no meaning in the LDK, and can be NULL or any other value.
     */
    void *this_arg;
    /**
-    * Called with the message type that was received and the buffer to be read.
-    * Can return a `MessageHandlingError` if the message could not be handled.
+    * Handle an incoming open_channel message from the given peer.
     */
-   struct LDKCResult_NoneLightningErrorZ (*handle_custom_message)(const void *this_arg, struct LDKType msg, struct LDKPublicKey sender_node_id);
+   void (*handle_open_channel)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKInitFeatures their_features, const struct LDKOpenChannel *NONNULL_PTR msg);
    /**
-    * Gets the list of pending messages which were generated by the custom message
-    * handler, clearing the list in the process. The first tuple element must
-    * correspond to the intended recipients node ids. If no connection to one of the
-    * specified node does not exist, the message is simply not sent to it.
+    * Handle an incoming accept_channel message from the given peer.
     */
-   struct LDKCVec_C2Tuple_PublicKeyTypeZZ (*get_and_clear_pending_msg)(const void *this_arg);
+   void (*handle_accept_channel)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKInitFeatures their_features, const struct LDKAcceptChannel *NONNULL_PTR msg);
    /**
-    * Implementation of CustomMessageReader for this object.
+    * Handle an incoming funding_created message from the given peer.
     */
-   struct LDKCustomMessageReader CustomMessageReader;
+   void (*handle_funding_created)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKFundingCreated *NONNULL_PTR msg);
    /**
-    * Frees any resources associated with this object given its this_arg pointer.
-    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    * Handle an incoming funding_signed message from the given peer.
     */
-   void (*free)(void *this_arg);
-} LDKCustomMessageHandler;
-
-
-
-/**
- * A dummy struct which implements `RoutingMessageHandler` without storing any routing information
- * or doing any processing. You can provide one of these as the route_handler in a MessageHandler.
- */
-typedef struct MUST_USE_STRUCT LDKIgnoringMessageHandler {
+   void (*handle_funding_signed)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKFundingSigned *NONNULL_PTR msg);
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * Handle an incoming funding_locked message from the given peer.
     */
-   LDKnativeIgnoringMessageHandler *inner;
+   void (*handle_funding_locked)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKFundingLocked *NONNULL_PTR msg);
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Handle an incoming shutdown message from the given peer.
     */
-   bool is_owned;
-} LDKIgnoringMessageHandler;
-
-
-
-/**
- * A dummy struct which implements `ChannelMessageHandler` without having any channels.
- * You can provide one of these as the route_handler in a MessageHandler.
- */
-typedef struct MUST_USE_STRUCT LDKErroringMessageHandler {
+   void (*handle_shutdown)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKInitFeatures *NONNULL_PTR their_features, const struct LDKShutdown *NONNULL_PTR msg);
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * Handle an incoming closing_signed message from the given peer.
     */
-   LDKnativeErroringMessageHandler *inner;
+   void (*handle_closing_signed)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKClosingSigned *NONNULL_PTR msg);
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Handle an incoming update_add_htlc message from the given peer.
     */
-   bool is_owned;
-} LDKErroringMessageHandler;
-
-
-
-/**
- * Provides references to trait impls which handle different types of messages.
- */
-typedef struct MUST_USE_STRUCT LDKMessageHandler {
+   void (*handle_update_add_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateAddHTLC *NONNULL_PTR msg);
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * Handle an incoming update_fulfill_htlc message from the given peer.
     */
-   LDKnativeMessageHandler *inner;
+   void (*handle_update_fulfill_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFulfillHTLC *NONNULL_PTR msg);
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Handle an incoming update_fail_htlc message from the given peer.
     */
-   bool is_owned;
-} LDKMessageHandler;
-
-/**
- * Provides an object which can be used to send data to and which uniquely identifies a connection
- * to a remote host. You will need to be able to generate multiple of these which meet Eq and
- * implement Hash to meet the PeerManager API.
- *
- * For efficiency, Clone should be relatively cheap for this type.
- *
- * Two descriptors may compare equal (by [`cmp::Eq`] and [`hash::Hash`]) as long as the original
- * has been disconnected, the [`PeerManager`] has been informed of the disconnection (either by it
- * having triggered the disconnection or a call to [`PeerManager::socket_disconnected`]), and no
- * further calls to the [`PeerManager`] related to the original socket occur. This allows you to
- * use a file descriptor for your SocketDescriptor directly, however for simplicity you may wish
- * to simply use another value which is guaranteed to be globally unique instead.
- */
-typedef struct LDKSocketDescriptor {
+   void (*handle_update_fail_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFailHTLC *NONNULL_PTR msg);
    /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
+    * Handle an incoming update_fail_malformed_htlc message from the given peer.
     */
-   void *this_arg;
+   void (*handle_update_fail_malformed_htlc)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFailMalformedHTLC *NONNULL_PTR msg);
    /**
-    * Attempts to send some data from the given slice to the peer.
-    *
-    * Returns the amount of data which was sent, possibly 0 if the socket has since disconnected.
-    * Note that in the disconnected case, [`PeerManager::socket_disconnected`] must still be
-    * called and further write attempts may occur until that time.
-    *
-    * If the returned size is smaller than `data.len()`, a
-    * [`PeerManager::write_buffer_space_avail`] call must be made the next time more data can be
-    * written. Additionally, until a `send_data` event completes fully, no further
-    * [`PeerManager::read_event`] calls should be made for the same peer! Because this is to
-    * prevent denial-of-service issues, you should not read or buffer any data from the socket
-    * until then.
-    *
-    * If a [`PeerManager::read_event`] call on this descriptor had previously returned true
-    * (indicating that read events should be paused to prevent DoS in the send buffer),
-    * `resume_read` may be set indicating that read events on this descriptor should resume. A
-    * `resume_read` of false carries no meaning, and should not cause any action.
+    * Handle an incoming commitment_signed message from the given peer.
     */
-   uintptr_t (*send_data)(void *this_arg, struct LDKu8slice data, bool resume_read);
+   void (*handle_commitment_signed)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKCommitmentSigned *NONNULL_PTR msg);
    /**
-    * Disconnect the socket pointed to by this SocketDescriptor.
-    *
-    * You do *not* need to call [`PeerManager::socket_disconnected`] with this socket after this
-    * call (doing so is a noop).
+    * Handle an incoming revoke_and_ack message from the given peer.
     */
-   void (*disconnect_socket)(void *this_arg);
+   void (*handle_revoke_and_ack)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKRevokeAndACK *NONNULL_PTR msg);
    /**
-    * Checks if two objects are equal given this object's this_arg pointer and another object.
+    * Handle an incoming update_fee message from the given peer.
     */
-   bool (*eq)(const void *this_arg, const struct LDKSocketDescriptor *NONNULL_PTR other_arg);
+   void (*handle_update_fee)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKUpdateFee *NONNULL_PTR msg);
    /**
-    * Calculate a succinct non-cryptographic hash for an object given its this_arg pointer.
-    * This is used, for example, for inclusion of this object in a hash map.
+    * Handle an incoming announcement_signatures message from the given peer.
     */
-   uint64_t (*hash)(const void *this_arg);
+   void (*handle_announcement_signatures)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKAnnouncementSignatures *NONNULL_PTR msg);
    /**
-    * Called, if set, after this SocketDescriptor has been cloned into a duplicate object.
-    * The new SocketDescriptor is provided, and should be mutated as needed to perform a
-    * deep copy of the object pointed to by this_arg or avoid any double-freeing.
+    * Indicates a connection to the peer failed/an existing connection was lost. If no connection
+    * is believed to be possible in the future (eg they're sending us messages we don't
+    * understand or indicate they require unknown feature bits), no_connection_possible is set
+    * and any outstanding channels should be failed.
     */
-   void (*cloned)(struct LDKSocketDescriptor *NONNULL_PTR new_SocketDescriptor);
+   void (*peer_disconnected)(const void *this_arg, struct LDKPublicKey their_node_id, bool no_connection_possible);
+   /**
+    * Handle a peer reconnecting, possibly generating channel_reestablish message(s).
+    */
+   void (*peer_connected)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKInit *NONNULL_PTR msg);
+   /**
+    * Handle an incoming channel_reestablish message from the given peer.
+    */
+   void (*handle_channel_reestablish)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKChannelReestablish *NONNULL_PTR msg);
+   /**
+    * Handle an incoming channel update from the given peer.
+    */
+   void (*handle_channel_update)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKChannelUpdate *NONNULL_PTR msg);
+   /**
+    * Handle an incoming error message from the given peer.
+    */
+   void (*handle_error)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKErrorMessage *NONNULL_PTR msg);
+   /**
+    * Implementation of MessageSendEventsProvider for this object.
+    */
+   struct LDKMessageSendEventsProvider MessageSendEventsProvider;
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKSocketDescriptor;
-
-
+} LDKChannelMessageHandler;
 
 /**
- * A PeerManager manages a set of peers, described by their [`SocketDescriptor`] and marshalls
- * socket events into messages which it passes on to its [`MessageHandler`].
- *
- * Locks are taken internally, so you must never assume that reentrancy from a
- * [`SocketDescriptor`] call back into [`PeerManager`] methods will not deadlock.
- *
- * Calls to [`read_event`] will decode relevant messages and pass them to the
- * [`ChannelMessageHandler`], likely doing message processing in-line. Thus, the primary form of
- * parallelism in Rust-Lightning is in calls to [`read_event`]. Note, however, that calls to any
- * [`PeerManager`] functions related to the same connection must occur only in serial, making new
- * calls only after previous ones have returned.
+ * A trait to describe an object which can receive routing messages.
  *
- * Rather than using a plain PeerManager, it is preferable to use either a SimpleArcPeerManager
- * a SimpleRefPeerManager, for conciseness. See their documentation for more details, but
- * essentially you should default to using a SimpleRefPeerManager, and use a
- * SimpleArcPeerManager when you require a PeerManager with a static lifetime, such as when
- * you're using lightning-net-tokio.
+ * # Implementor DoS Warnings
  *
- * [`read_event`]: PeerManager::read_event
+ * For `gossip_queries` messages there are potential DoS vectors when handling
+ * inbound queries. Implementors using an on-disk network graph should be aware of
+ * repeated disk I/O for queries accessing different parts of the network graph.
  */
-typedef struct MUST_USE_STRUCT LDKPeerManager {
+typedef struct LDKRoutingMessageHandler {
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
     */
-   LDKnativePeerManager *inner;
+   void *this_arg;
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Handle an incoming node_announcement message, returning true if it should be forwarded on,
+    * false or returning an Err otherwise.
     */
-   bool is_owned;
-} LDKPeerManager;
-
-
-
-/**
- * Static channel fields used to build transactions given per-commitment fields, organized by
- * broadcaster/countersignatory.
- *
- * This is derived from the holder/counterparty-organized ChannelTransactionParameters via the
- * as_holder_broadcastable and as_counterparty_broadcastable functions.
- */
-typedef struct MUST_USE_STRUCT LDKDirectedChannelTransactionParameters {
+   struct LDKCResult_boolLightningErrorZ (*handle_node_announcement)(const void *this_arg, const struct LDKNodeAnnouncement *NONNULL_PTR msg);
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * Handle a channel_announcement message, returning true if it should be forwarded on, false
+    * or returning an Err otherwise.
     */
-   LDKnativeDirectedChannelTransactionParameters *inner;
+   struct LDKCResult_boolLightningErrorZ (*handle_channel_announcement)(const void *this_arg, const struct LDKChannelAnnouncement *NONNULL_PTR msg);
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Handle an incoming channel_update message, returning true if it should be forwarded on,
+    * false or returning an Err otherwise.
     */
-   bool is_owned;
-} LDKDirectedChannelTransactionParameters;
-
-
-
-/**
- * A read-only view of [`NetworkGraph`].
- */
-typedef struct MUST_USE_STRUCT LDKReadOnlyNetworkGraph {
+   struct LDKCResult_boolLightningErrorZ (*handle_channel_update)(const void *this_arg, const struct LDKChannelUpdate *NONNULL_PTR msg);
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * Gets a subset of the channel announcements and updates required to dump our routing table
+    * to a remote node, starting at the short_channel_id indicated by starting_point and
+    * including the batch_amount entries immediately higher in numerical value than starting_point.
     */
-   LDKnativeReadOnlyNetworkGraph *inner;
+   struct LDKCVec_C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZZ (*get_next_channel_announcements)(const void *this_arg, uint64_t starting_point, uint8_t batch_amount);
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Gets a subset of the node announcements required to dump our routing table to a remote node,
+    * starting at the node *after* the provided publickey and including batch_amount entries
+    * immediately higher (as defined by <PublicKey as Ord>::cmp) than starting_point.
+    * If None is provided for starting_point, we start at the first node.
+    *
+    * Note that starting_point (or a relevant inner pointer) may be NULL or all-0s to represent None
     */
-   bool is_owned;
-} LDKReadOnlyNetworkGraph;
-
-
-
-/**
- * Receives and validates network updates from peers,
- * stores authentic and relevant data as a network graph.
- * This network graph is then used for routing payments.
- * Provides interface to help with initial routing sync by
- * serving historical announcements.
- *
- * Serves as an [`EventHandler`] for applying updates from [`Event::PaymentPathFailed`] to the
- * [`NetworkGraph`].
- */
-typedef struct MUST_USE_STRUCT LDKNetGraphMsgHandler {
-   /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
-    */
-   LDKnativeNetGraphMsgHandler *inner;
+   struct LDKCVec_NodeAnnouncementZ (*get_next_node_announcements)(const void *this_arg, struct LDKPublicKey starting_point, uint8_t batch_amount);
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Called when a connection is established with a peer. This can be used to
+    * perform routing table synchronization using a strategy defined by the
+    * implementor.
     */
-   bool is_owned;
-} LDKNetGraphMsgHandler;
-
-/**
- * An interface used to score payment channels for path finding.
- *
- *\tScoring is in terms of fees willing to be paid in order to avoid routing through a channel.
- */
-typedef struct LDKScore {
+   void (*sync_routing_table)(const void *this_arg, struct LDKPublicKey their_node_id, const struct LDKInit *NONNULL_PTR init);
    /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
+    * Handles the reply of a query we initiated to learn about channels
+    * for a given range of blocks. We can expect to receive one or more
+    * replies to a single query.
     */
-   void *this_arg;
+   struct LDKCResult_NoneLightningErrorZ (*handle_reply_channel_range)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKReplyChannelRange msg);
    /**
-    * Returns the fee in msats willing to be paid to avoid routing `send_amt_msat` through the
-    * given channel in the direction from `source` to `target`.
-    *
-    * The channel's capacity (less any other MPP parts which are also being considered for use in
-    * the same payment) is given by `channel_capacity_msat`. It may be guessed from various
-    * sources or assumed from no data at all.
-    *
-    * For hints provided in the invoice, we assume the channel has sufficient capacity to accept
-    * the invoice's full amount, and provide a `channel_capacity_msat` of `None`. In all other
-    * cases it is set to `Some`, even if we're guessing at the channel value.
-    *
-    * Your code should be overflow-safe through a `channel_capacity_msat` of 21 million BTC.
+    * Handles the reply of a query we initiated asking for routing gossip
+    * messages for a list of channels. We should receive this message when
+    * a node has completed its best effort to send us the pertaining routing
+    * gossip messages.
     */
-   uint64_t (*channel_penalty_msat)(const void *this_arg, uint64_t short_channel_id, uint64_t send_amt_msat, struct LDKCOption_u64Z channel_capacity_msat, const struct LDKNodeId *NONNULL_PTR source, const struct LDKNodeId *NONNULL_PTR target);
+   struct LDKCResult_NoneLightningErrorZ (*handle_reply_short_channel_ids_end)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKReplyShortChannelIdsEnd msg);
    /**
-    * Handles updating channel penalties after failing to route through a channel.
+    * Handles when a peer asks us to send a list of short_channel_ids
+    * for the requested range of blocks.
     */
-   void (*payment_path_failed)(void *this_arg, struct LDKCVec_RouteHopZ path, uint64_t short_channel_id);
+   struct LDKCResult_NoneLightningErrorZ (*handle_query_channel_range)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKQueryChannelRange msg);
    /**
-    * Handles updating channel penalties after successfully routing along a path.
+    * Handles when a peer asks us to send routing gossip messages for a
+    * list of short_channel_ids.
     */
-   void (*payment_path_successful)(void *this_arg, struct LDKCVec_RouteHopZ path);
+   struct LDKCResult_NoneLightningErrorZ (*handle_query_short_channel_ids)(const void *this_arg, struct LDKPublicKey their_node_id, struct LDKQueryShortChannelIds msg);
    /**
-    * Serialize the object into a byte array
+    * Implementation of MessageSendEventsProvider for this object.
     */
-   struct LDKCVec_u8Z (*write)(const void *this_arg);
+   struct LDKMessageSendEventsProvider MessageSendEventsProvider;
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKScore;
+} LDKRoutingMessageHandler;
 
 /**
- * A scorer that is accessed under a lock.
- *
- * Needed so that calls to [`Score::channel_penalty_msat`] in [`find_route`] can be made while
- * having shared ownership of a scorer but without requiring internal locking in [`Score`]
- * implementations. Internal locking would be detrimental to route finding performance and could
- * result in [`Score::channel_penalty_msat`] returning a different value for the same channel.
- *
- * [`find_route`]: crate::routing::router::find_route
+ * Trait to be implemented by custom message (unrelated to the channel/gossip LN layers)
+ * decoders.
  */
-typedef struct LDKLockableScore {
+typedef struct LDKCustomMessageReader {
    /**
     * An opaque pointer which is passed to your function implementations as an argument.
     * This has no meaning in the LDK, and can be NULL or any other value.
     */
    void *this_arg;
    /**
-    * Returns the locked scorer.
+    * Decodes a custom message to `CustomMessageType`. If the given message type is known to the
+    * implementation and the message could be decoded, must return `Ok(Some(message))`. If the
+    * message type is unknown to the implementation, must return `Ok(None)`. If a decoding error
+    * occur, must return `Err(DecodeError::X)` where `X` details the encountered error.
     */
-   struct LDKScore (*lock)(const void *this_arg);
+   struct LDKCResult_COption_TypeZDecodeErrorZ (*read)(const void *this_arg, uint16_t message_type, struct LDKu8slice buffer);
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKLockableScore;
-
-
+} LDKCustomMessageReader;
 
 /**
- * A concrete implementation of [`LockableScore`] which supports multi-threading.
+ * Handler for BOLT1-compliant messages.
  */
-typedef struct MUST_USE_STRUCT LDKMultiThreadedLockableScore {
+typedef struct LDKCustomMessageHandler {
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
     */
-   LDKnativeMultiThreadedLockableScore *inner;
+   void *this_arg;
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Called with the message type that was received and the buffer to be read.
+    * Can return a `MessageHandlingError` if the message could not be handled.
     */
-   bool is_owned;
-} LDKMultiThreadedLockableScore;
-
-
-
-/**
- * FilesystemPersister persists channel data on disk, where each channel's
- * data is stored in a file named after its funding outpoint.
- *
- * Warning: this module does the best it can with calls to persist data, but it
- * can only guarantee that the data is passed to the drive. It is up to the
- * drive manufacturers to do the actual persistence properly, which they often
- * don't (especially on consumer-grade hardware). Therefore, it is up to the
- * user to validate their entire storage stack, to ensure the writes are
- * persistent.
- * Corollary: especially when dealing with larger amounts of money, it is best
- * practice to have multiple channel data backups and not rely only on one
- * FilesystemPersister.
- */
-typedef struct MUST_USE_STRUCT LDKFilesystemPersister {
+   struct LDKCResult_NoneLightningErrorZ (*handle_custom_message)(const void *this_arg, struct LDKType msg, struct LDKPublicKey sender_node_id);
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * Gets the list of pending messages which were generated by the custom message
+    * handler, clearing the list in the process. The first tuple element must
+    * correspond to the intended recipients node ids. If no connection to one of the
+    * specified node does not exist, the message is simply not sent to it.
     */
-   LDKnativeFilesystemPersister *inner;
+   struct LDKCVec_C2Tuple_PublicKeyTypeZZ (*get_and_clear_pending_msg)(const void *this_arg);
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Implementation of CustomMessageReader for this object.
     */
-   bool is_owned;
-} LDKFilesystemPersister;
-
-
+   struct LDKCustomMessageReader CustomMessageReader;
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKCustomMessageHandler;
 
 /**
- * `BackgroundProcessor` takes care of tasks that (1) need to happen periodically to keep
- * Rust-Lightning running properly, and (2) either can or should be run in the background. Its
- * responsibilities are:
- * * Processing [`Event`]s with a user-provided [`EventHandler`].
- * * Monitoring whether the [`ChannelManager`] needs to be re-persisted to disk, and if so,
- *   writing it to disk/backups by invoking the callback given to it at startup.
- *   [`ChannelManager`] persistence should be done in the background.
- * * Calling [`ChannelManager::timer_tick_occurred`] and [`PeerManager::timer_tick_occurred`]
- *   at the appropriate intervals.
- * * Calling [`NetworkGraph::remove_stale_channels`] (if a [`NetGraphMsgHandler`] is provided to
- *   [`BackgroundProcessor::start`]).
- *
- * It will also call [`PeerManager::process_events`] periodically though this shouldn't be relied
- * upon as doing so may result in high latency.
- *
- * # Note
- *
- * If [`ChannelManager`] persistence fails and the persisted manager becomes out-of-date, then
- * there is a risk of channels force-closing on startup when the manager realizes it's outdated.
- * However, as long as [`ChannelMonitor`] backups are sound, no funds besides those used for
- * unilateral chain closure fees are at risk.
- *
- * [`ChannelMonitor`]: lightning::chain::channelmonitor::ChannelMonitor
- * [`Event`]: lightning::util::events::Event
- *BackgroundProcessor will immediately stop on drop. It should be stored until shutdown.
+ * A MessageTracer which appends every traced message to a file, see `MessageTraceFile_new` for
+ * the file format.
  */
-typedef struct MUST_USE_STRUCT LDKBackgroundProcessor {
+typedef struct MUST_USE_STRUCT LDKMessageTraceFile {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeBackgroundProcessor *inner;
+   struct LDKnativeMessageTraceFile *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKBackgroundProcessor;
+} LDKMessageTraceFile;
 
 /**
- * Trait which handles persisting a [`ChannelManager`] to disk.
- *
- * [`ChannelManager`]: lightning::ln::channelmanager::ChannelManager
+ * The contents of CResult_MessageTraceFileIOErrorZ
  */
-typedef struct LDKChannelManagerPersister {
-   /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
-    */
-   void *this_arg;
+typedef union LDKCResult_MessageTraceFileIOErrorZPtr {
    /**
-    * Persist the given [`ChannelManager`] to disk, returning an error if persistence failed
-    * (which will cause the [`BackgroundProcessor`] which called this method to exit.
-    *
-    * [`ChannelManager`]: lightning::ln::channelmanager::ChannelManager
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
     */
-   struct LDKCResult_NoneErrorZ (*persist_manager)(const void *this_arg, const struct LDKChannelManager *NONNULL_PTR channel_manager);
+   struct LDKMessageTraceFile *result;
    /**
-    * Frees any resources associated with this object given its this_arg pointer.
-    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
     */
-   void (*free)(void *this_arg);
-} LDKChannelManagerPersister;
-
-
+   enum LDKIOError *err;
+} LDKCResult_MessageTraceFileIOErrorZPtr;
 
 /**
- * Data of the `RawInvoice` that is encoded in the data part
+ * A CResult_MessageTraceFileIOErrorZ represents the result of a fallible operation,
+ * containing a MessageTraceFile on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
  */
-typedef struct MUST_USE_STRUCT LDKRawDataPart {
+typedef struct LDKCResult_MessageTraceFileIOErrorZ {
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * The contents of this CResult_MessageTraceFileIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
     */
-   LDKnativeRawDataPart *inner;
+   union LDKCResult_MessageTraceFileIOErrorZPtr contents;
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * Whether this CResult_MessageTraceFileIOErrorZ represents a success state.
     */
-   bool is_owned;
-} LDKRawDataPart;
-
-
+   bool result_ok;
+} LDKCResult_MessageTraceFileIOErrorZ;
 
 /**
- * SHA-256 hash
+ * One side of a BOLT 8 encrypted connection, see the module documentation for how to drive the
+ * handshake.
  */
-typedef struct MUST_USE_STRUCT LDKSha256 {
+typedef struct MUST_USE_STRUCT LDKPeerChannelEncryptor {
    /**
     * A pointer to the opaque Rust object.
     * Nearly everywhere, inner must be non-null, however in places where
     * the Rust equivalent takes an Option, it may be set to null to indicate None.
     */
-   LDKnativeSha256 *inner;
+   struct LDKnativePeerChannelEncryptor *inner;
    /**
     * Indicates that this is the only struct which contains the same pointer.
     * Rust functions which take ownership of an object provided via an argument require
     * this to be true and invalidate the object pointed to by inner.
     */
    bool is_owned;
-} LDKSha256;
-
-
+} LDKPeerChannelEncryptor;
 
 /**
- * `min_final_cltv_expiry` to use for the last HTLC in the route
+ * The contents of CResult_CVec_u8ZNoiseErrorZ
  */
-typedef struct MUST_USE_STRUCT LDKMinFinalCltvExpiry {
+typedef union LDKCResult_CVec_u8ZNoiseErrorZPtr {
    /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
     */
-   LDKnativeMinFinalCltvExpiry *inner;
+   struct LDKCVec_u8Z *result;
    /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
     */
-   bool is_owned;
-} LDKMinFinalCltvExpiry;
+   enum LDKNoiseError *err;
+} LDKCResult_CVec_u8ZNoiseErrorZPtr;
 
 /**
- * Integer in the range `0..32`
+ * A CResult_CVec_u8ZNoiseErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::derived::CVec_u8Z on success and a NoiseError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
  */
-typedef struct LDKu5 {
-   uint8_t _0;
-} LDKu5;
+typedef struct LDKCResult_CVec_u8ZNoiseErrorZ {
+   /**
+    * The contents of this CResult_CVec_u8ZNoiseErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_CVec_u8ZNoiseErrorZPtr contents;
+   /**
+    * Whether this CResult_CVec_u8ZNoiseErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_CVec_u8ZNoiseErrorZ;
 
 /**
- * A 20-byte byte array.
+ * The contents of CResult_PublicKeyNoiseErrorZ
  */
-typedef struct LDKTwentyBytes {
+typedef union LDKCResult_PublicKeyNoiseErrorZPtr {
    /**
-    * The twenty bytes
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
     */
-   uint8_t data[20];
-} LDKTwentyBytes;
+   struct LDKPublicKey *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKNoiseError *err;
+} LDKCResult_PublicKeyNoiseErrorZPtr;
 
 /**
- * Fallback address in case no LN payment is possible
+ * A CResult_PublicKeyNoiseErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::PublicKey on success and a NoiseError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
  */
-typedef enum LDKFallback_Tag {
-   LDKFallback_SegWitProgram,
-   LDKFallback_PubKeyHash,
-   LDKFallback_ScriptHash,
+typedef struct LDKCResult_PublicKeyNoiseErrorZ {
    /**
-    * Must be last for serialization purposes
+    * The contents of this CResult_PublicKeyNoiseErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
     */
-   LDKFallback_Sentinel,
-} LDKFallback_Tag;
+   union LDKCResult_PublicKeyNoiseErrorZPtr contents;
+   /**
+    * Whether this CResult_PublicKeyNoiseErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_PublicKeyNoiseErrorZ;
 
-typedef struct LDKFallback_LDKSegWitProgram_Body {
-   struct LDKu5 version;
-   struct LDKCVec_u8Z program;
-} LDKFallback_LDKSegWitProgram_Body;
+/**
+ * The contents of CResult_u16NoiseErrorZ
+ */
+typedef union LDKCResult_u16NoiseErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   uint16_t *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKNoiseError *err;
+} LDKCResult_u16NoiseErrorZPtr;
 
-typedef struct MUST_USE_STRUCT LDKFallback {
-   LDKFallback_Tag tag;
-   union {
-      LDKFallback_LDKSegWitProgram_Body seg_wit_program;
-      struct {
-         struct LDKTwentyBytes pub_key_hash;
-      };
-      struct {
-         struct LDKTwentyBytes script_hash;
-      };
-   };
-} LDKFallback;
+/**
+ * A CResult_u16NoiseErrorZ represents the result of a fallible operation,
+ * containing a u16 on success and a NoiseError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_u16NoiseErrorZ {
+   /**
+    * The contents of this CResult_u16NoiseErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_u16NoiseErrorZPtr contents;
+   /**
+    * Whether this CResult_u16NoiseErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_u16NoiseErrorZ;
 
 /**
- * A trait defining behavior of an [`Invoice`] payer.
+ * The contents of CResult_NoneCustomMessageErrorZ
  */
-typedef struct LDKPayer {
+typedef union LDKCResult_NoneCustomMessageErrorZPtr {
+   /**
+    * Note that this value is always NULL, as there are no contents in the OK variant
+    */
+   void *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKCustomMessageError *err;
+} LDKCResult_NoneCustomMessageErrorZPtr;
+
+/**
+ * A CResult_NoneCustomMessageErrorZ represents the result of a fallible operation,
+ * containing a () on success and a CustomMessageError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_NoneCustomMessageErrorZ {
+   /**
+    * The contents of this CResult_NoneCustomMessageErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_NoneCustomMessageErrorZPtr contents;
+   /**
+    * Whether this CResult_NoneCustomMessageErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_NoneCustomMessageErrorZ;
+
+/**
+ * Handles custom messages of a single type received from peers.
+ */
+typedef struct LDKCustomMessageCallback {
    /**
     * An opaque pointer which is passed to your function implementations as an argument.
     * This has no meaning in the LDK, and can be NULL or any other value.
     */
    void *this_arg;
    /**
-    * Returns the payer's node id.
+    * Called with each message of the registered type, with `data` holding the message without
+    * its type. `data` is only valid for the duration of the call.
+    *
+    * Returns false if the message was invalid. For even message types this disconnects the peer,
+    * for odd message types the message is ignored.
+    *
+    * This is called while the `PeerManager` is processing data from the peer and may call
+    * `CustomMessageRegistry_send_custom`.
     */
-   struct LDKPublicKey (*node_id)(const void *this_arg);
+   bool (*handle_message)(const void *this_arg, struct LDKPublicKey node_id, uint16_t type_id, struct LDKu8slice data);
    /**
-    * Returns the payer's channels.
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
-   struct LDKCVec_ChannelDetailsZ (*first_hops)(const void *this_arg);
+   void (*free)(void *this_arg);
+} LDKCustomMessageCallback;
+
+/**
+ * Dispatches custom messages to callbacks registered per message type and queues custom
+ * messages to send, see `CustomMessageRegistry_as_CustomMessageHandler`.
+ */
+typedef struct MUST_USE_STRUCT LDKCustomMessageRegistry {
    /**
-    * Sends a payment over the Lightning Network using the given [`Route`].
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeCustomMessageRegistry *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKCustomMessageRegistry;
+
+/**
+ * A thread-safe queue of events, which implements [`EventHandler`] by cloning each event into
+ * the queue. See `EventQueue_as_EventHandler` and `EventQueue_next`.
+ */
+typedef struct MUST_USE_STRUCT LDKEventQueue {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeEventQueue *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKEventQueue;
+
+/**
+ * The contents of CResult_EventQueueIOErrorZ
+ */
+typedef union LDKCResult_EventQueueIOErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKEventQueue *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_EventQueueIOErrorZPtr;
+
+/**
+ * A CResult_EventQueueIOErrorZ represents the result of a fallible operation,
+ * containing a EventQueue on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_EventQueueIOErrorZ {
+   /**
+    * The contents of this CResult_EventQueueIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_EventQueueIOErrorZPtr contents;
+   /**
+    * Whether this CResult_EventQueueIOErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_EventQueueIOErrorZ;
+
+/**
+ * A trait implemented for objects handling events from [`EventsProvider`].
+ */
+typedef struct LDKEventHandler {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Handles the given [`Event`].
     *
-    * Note that payment_secret (or a relevant inner pointer) may be NULL or all-0s to represent None
+    * See [`EventsProvider`] for details that must be considered when implementing this method.
     */
-   struct LDKCResult_PaymentIdPaymentSendFailureZ (*send_payment)(const void *this_arg, const struct LDKRoute *NONNULL_PTR route, struct LDKThirtyTwoBytes payment_hash, struct LDKThirtyTwoBytes payment_secret);
+   void (*handle_event)(const void *this_arg, const struct LDKEvent *NONNULL_PTR event);
    /**
-    * Sends a spontaneous payment over the Lightning Network using the given [`Route`].
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
-   struct LDKCResult_PaymentIdPaymentSendFailureZ (*send_spontaneous_payment)(const void *this_arg, const struct LDKRoute *NONNULL_PTR route, struct LDKThirtyTwoBytes payment_preimage);
+   void (*free)(void *this_arg);
+} LDKEventHandler;
+
+/**
+ * Wakes up the host's event loop when a [`ChannelManagerNotifier`] is signalled.
+ */
+typedef struct LDKWakeupCallback {
    /**
-    * Retries a failed payment path for the [`PaymentId`] using the given [`Route`].
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
     */
-   struct LDKCResult_NonePaymentSendFailureZ (*retry_payment)(const void *this_arg, const struct LDKRoute *NONNULL_PTR route, struct LDKThirtyTwoBytes payment_id);
+   void *this_arg;
    /**
-    * Signals that no further retries for the given payment will occur.
+    * Called once each time the notifier goes from cleared to signalled.
+    *
+    * This is called from an LDK-internal thread, or from whichever thread calls
+    * `ChannelManagerNotifier_notify`, so it should only schedule work on the host's event loop
+    * (eg with `uv_async_send` or `g_main_context_wakeup`) rather than do it directly.
     */
-   void (*abandon_payment)(const void *this_arg, struct LDKThirtyTwoBytes payment_id);
+   void (*wake)(const void *this_arg);
    /**
     * Frees any resources associated with this object given its this_arg pointer.
     * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
     */
    void (*free)(void *this_arg);
-} LDKPayer;
+} LDKWakeupCallback;
+
+/**
+ * Signals the host whenever a `ChannelManager` needs persisting, without blocking any of the
+ * host's threads. See `ChannelManagerNotifier_new`.
+ */
+typedef struct MUST_USE_STRUCT LDKChannelManagerNotifier {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeChannelManagerNotifier *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKChannelManagerNotifier;
+
+/**
+ * The contents of CResult_TransactionWalletErrorZ
+ */
+typedef union LDKCResult_TransactionWalletErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKTransaction *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKWalletError *err;
+} LDKCResult_TransactionWalletErrorZPtr;
+
+/**
+ * A CResult_TransactionWalletErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::Transaction on success and a WalletError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_TransactionWalletErrorZ {
+   /**
+    * The contents of this CResult_TransactionWalletErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_TransactionWalletErrorZPtr contents;
+   /**
+    * Whether this CResult_TransactionWalletErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_TransactionWalletErrorZ;
+
+/**
+ * A minimal BIP 84 on-chain wallet derived from the `KeysManager` seed, which funds channels and
+ * sweeps outputs LDK hands back. See the module documentation for details.
+ */
+typedef struct MUST_USE_STRUCT LDKOnChainWallet {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeOnChainWallet *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKOnChainWallet;
+
+/**
+ * The contents of CResult_OnChainWalletDecodeErrorZ
+ */
+typedef union LDKCResult_OnChainWalletDecodeErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKOnChainWallet *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   struct LDKDecodeError *err;
+} LDKCResult_OnChainWalletDecodeErrorZPtr;
+
+/**
+ * A CResult_OnChainWalletDecodeErrorZ represents the result of a fallible operation,
+ * containing a OnChainWallet on success and a crate::lightning::ln::msgs::DecodeError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_OnChainWalletDecodeErrorZ {
+   /**
+    * The contents of this CResult_OnChainWalletDecodeErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_OnChainWalletDecodeErrorZPtr contents;
+   /**
+    * Whether this CResult_OnChainWalletDecodeErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_OnChainWalletDecodeErrorZ;
+
+/**
+ * The `Confirm` trait is used to notify when transactions have been confirmed on chain or
+ * unconfirmed during a chain reorganization.
+ *
+ * Clients sourcing chain data using a transaction-oriented API should prefer this interface over
+ * [`Listen`]. For instance, an Electrum client may implement [`Filter`] by subscribing to activity
+ * related to registered transactions and outputs. Upon notification, it would pass along the
+ * matching transactions using this interface.
+ *
+ * # Use
+ *
+ * The intended use is as follows:
+ * - Call [`transactions_confirmed`] to process any on-chain activity of interest.
+ * - Call [`transaction_unconfirmed`] to process any transaction returned by [`get_relevant_txids`]
+ *   that has been reorganized out of the chain.
+ * - Call [`best_block_updated`] whenever a new chain tip becomes available.
+ *
+ * # Order
+ *
+ * Clients must call these methods in chain order. Specifically:
+ * - Transactions confirmed in a block must be given before transactions confirmed in a later
+ *   block.
+ * - Dependent transactions within the same block must be given in topological order, possibly in
+ *   separate calls.
+ * - Unconfirmed transactions must be given after the original confirmations and before any
+ *   reconfirmation.
+ *
+ * See individual method documentation for further details.
+ *
+ * [`transactions_confirmed`]: Self::transactions_confirmed
+ * [`transaction_unconfirmed`]: Self::transaction_unconfirmed
+ * [`best_block_updated`]: Self::best_block_updated
+ * [`get_relevant_txids`]: Self::get_relevant_txids
+ */
+typedef struct LDKConfirm {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Processes transactions confirmed in a block with a given header and height.
+    *
+    * Should be called for any transactions registered by [`Filter::register_tx`] or any
+    * transactions spending an output registered by [`Filter::register_output`]. Such transactions
+    * appearing in the same block do not need to be included in the same call; instead, multiple
+    * calls with additional transactions may be made so long as they are made in [chain order].
+    *
+    * May be called before or after [`best_block_updated`] for the corresponding block. However,
+    * in the event of a chain reorganization, it must not be called with a `header` that is no
+    * longer in the chain as of the last call to [`best_block_updated`].
+    *
+    * [chain order]: Confirm#Order
+    * [`best_block_updated`]: Self::best_block_updated
+    */
+   void (*transactions_confirmed)(const void *this_arg, const uint8_t (*header)[80], struct LDKCVec_C2Tuple_usizeTransactionZZ txdata, uint32_t height);
+   /**
+    * Processes a transaction that is no longer confirmed as result of a chain reorganization.
+    *
+    * Should be called for any transaction returned by [`get_relevant_txids`] if it has been
+    * reorganized out of the best chain. Once called, the given transaction should not be returned
+    * by [`get_relevant_txids`] unless it has been reconfirmed via [`transactions_confirmed`].
+    *
+    * [`get_relevant_txids`]: Self::get_relevant_txids
+    * [`transactions_confirmed`]: Self::transactions_confirmed
+    */
+   void (*transaction_unconfirmed)(const void *this_arg, const uint8_t (*txid)[32]);
+   /**
+    * Processes an update to the best header connected at the given height.
+    *
+    * Should be called when a new header is available but may be skipped for intermediary blocks
+    * if they become available at the same time.
+    */
+   void (*best_block_updated)(const void *this_arg, const uint8_t (*header)[80], uint32_t height);
+   /**
+    * Returns transactions that should be monitored for reorganization out of the chain.
+    *
+    * Should include any transactions passed to [`transactions_confirmed`] that have insufficient
+    * confirmations to be safe from a chain reorganization. Should not include any transactions
+    * passed to [`transaction_unconfirmed`] unless later reconfirmed.
+    *
+    * May be called to determine the subset of transactions that must still be monitored for
+    * reorganization. Will be idempotent between calls but may change as a result of calls to the
+    * other interface methods. Thus, this is useful to determine which transactions may need to be
+    * given to [`transaction_unconfirmed`].
+    *
+    * [`transactions_confirmed`]: Self::transactions_confirmed
+    * [`transaction_unconfirmed`]: Self::transaction_unconfirmed
+    */
+   struct LDKCVec_TxidZ (*get_relevant_txids)(const void *this_arg);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKConfirm;
+
+/**
+ * Persists `SpendableOutputDescriptor`s and sweeps them, rebroadcasting and fee-bumping the
+ * sweeps until they confirm. See the module documentation for details.
+ */
+typedef struct MUST_USE_STRUCT LDKOutputSweeper {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeOutputSweeper *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKOutputSweeper;
+
+/**
+ * The contents of CResult_OutputSweeperIOErrorZ
+ */
+typedef union LDKCResult_OutputSweeperIOErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKOutputSweeper *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_OutputSweeperIOErrorZPtr;
+
+/**
+ * A CResult_OutputSweeperIOErrorZ represents the result of a fallible operation,
+ * containing a OutputSweeper on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_OutputSweeperIOErrorZ {
+   /**
+    * The contents of this CResult_OutputSweeperIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_OutputSweeperIOErrorZPtr contents;
+   /**
+    * Whether this CResult_OutputSweeperIOErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_OutputSweeperIOErrorZ;
+
+/**
+ * The contents of CResult_PsbtPsbtFundingErrorZ
+ */
+typedef union LDKCResult_PsbtPsbtFundingErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKPsbt *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKPsbtFundingError *err;
+} LDKCResult_PsbtPsbtFundingErrorZPtr;
+
+/**
+ * A CResult_PsbtPsbtFundingErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::Psbt on success and a PsbtFundingError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_PsbtPsbtFundingErrorZ {
+   /**
+    * The contents of this CResult_PsbtPsbtFundingErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_PsbtPsbtFundingErrorZPtr contents;
+   /**
+    * Whether this CResult_PsbtPsbtFundingErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_PsbtPsbtFundingErrorZ;
+
+/**
+ * The contents of CResult_TransactionPsbtFundingErrorZ
+ */
+typedef union LDKCResult_TransactionPsbtFundingErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKTransaction *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKPsbtFundingError *err;
+} LDKCResult_TransactionPsbtFundingErrorZPtr;
+
+/**
+ * A CResult_TransactionPsbtFundingErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::Transaction on success and a PsbtFundingError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_TransactionPsbtFundingErrorZ {
+   /**
+    * The contents of this CResult_TransactionPsbtFundingErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_TransactionPsbtFundingErrorZPtr contents;
+   /**
+    * Whether this CResult_TransactionPsbtFundingErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_TransactionPsbtFundingErrorZ;
+
+/**
+ * The funding of a single channel through a PSBT, as requested by an
+ * `Event::FundingGenerationReady`. See `PsbtFunding_from_event`.
+ */
+typedef struct MUST_USE_STRUCT LDKPsbtFunding {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativePsbtFunding *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKPsbtFunding;
+
+/**
+ * The contents of CResult_StrSeedErrorZ
+ */
+typedef union LDKCResult_StrSeedErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKStr *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKSeedError *err;
+} LDKCResult_StrSeedErrorZPtr;
+
+/**
+ * A CResult_StrSeedErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::Str on success and a SeedError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_StrSeedErrorZ {
+   /**
+    * The contents of this CResult_StrSeedErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_StrSeedErrorZPtr contents;
+   /**
+    * Whether this CResult_StrSeedErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_StrSeedErrorZ;
+
+/**
+ * The contents of CResult_NoneSeedErrorZ
+ */
+typedef union LDKCResult_NoneSeedErrorZPtr {
+   /**
+    * Note that this value is always NULL, as there are no contents in the OK variant
+    */
+   void *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKSeedError *err;
+} LDKCResult_NoneSeedErrorZPtr;
+
+/**
+ * A CResult_NoneSeedErrorZ represents the result of a fallible operation,
+ * containing a () on success and a SeedError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_NoneSeedErrorZ {
+   /**
+    * The contents of this CResult_NoneSeedErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_NoneSeedErrorZPtr contents;
+   /**
+    * Whether this CResult_NoneSeedErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_NoneSeedErrorZ;
+
+/**
+ * The contents of CResult_ThirtyTwoBytesSeedErrorZ
+ */
+typedef union LDKCResult_ThirtyTwoBytesSeedErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKThirtyTwoBytes *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKSeedError *err;
+} LDKCResult_ThirtyTwoBytesSeedErrorZPtr;
+
+/**
+ * A CResult_ThirtyTwoBytesSeedErrorZ represents the result of a fallible operation,
+ * containing a crate::c_types::ThirtyTwoBytes on success and a SeedError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_ThirtyTwoBytesSeedErrorZ {
+   /**
+    * The contents of this CResult_ThirtyTwoBytesSeedErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_ThirtyTwoBytesSeedErrorZPtr contents;
+   /**
+    * Whether this CResult_ThirtyTwoBytesSeedErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_ThirtyTwoBytesSeedErrorZ;
+
+/**
+ * Sends requests to a remote signer and returns its responses, eg over a pipe, a socket or an
+ * enclave's message interface. See the module documentation for the message format.
+ */
+typedef struct LDKSignerTransport {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Sends `request` to the signer and blocks until its response is received, returning the
+    * response, or an error if the signer could not be reached.
+    *
+    * This may be called from several threads at once, each of which must receive the response
+    * to its own request.
+    *
+    * Requests LDK cannot handle the failure of are sent again after an error, so the transport
+    * may reconnect to a restarted signer from within a later call.
+    */
+   struct LDKCResult_CVec_u8ZIOErrorZ (*send_request)(const void *this_arg, struct LDKu8slice request);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKSignerTransport;
+
+/**
+ * The signing side of the remote signer protocol, answering requests with a local
+ * `KeysInterface`. See `RemoteSignerServer_new`.
+ */
+typedef struct MUST_USE_STRUCT LDKRemoteSignerServer {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeRemoteSignerServer *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKRemoteSignerServer;
+
+/**
+ * The contents of CResult_RemoteSignerServerIOErrorZ
+ */
+typedef union LDKCResult_RemoteSignerServerIOErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKRemoteSignerServer *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_RemoteSignerServerIOErrorZPtr;
+
+/**
+ * A CResult_RemoteSignerServerIOErrorZ represents the result of a fallible operation,
+ * containing a RemoteSignerServer on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_RemoteSignerServerIOErrorZ {
+   /**
+    * The contents of this CResult_RemoteSignerServerIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_RemoteSignerServerIOErrorZPtr contents;
+   /**
+    * Whether this CResult_RemoteSignerServerIOErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_RemoteSignerServerIOErrorZ;
+
+/**
+ * The rules enforced by the signers of a `PolicyKeysInterface_new`, and the scripts they may
+ * pay to in addition to those of the wrapped `KeysInterface`.
+ */
+typedef struct MUST_USE_STRUCT LDKSignerPolicy {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeSignerPolicy *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKSignerPolicy;
+
+/**
+ * Signs invoices built by an [`InvoiceBuilder`] with the node's key.
+ *
+ * `InvoiceSigner_from_keys_interface` provides one which signs with a `KeysInterface`.
+ */
+typedef struct LDKInvoiceSigner {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Signs the SHA-256 hash of the given invoice preimage, ie the invoice's human-readable part
+    * followed by its data, with the node's secret key, returning a recoverable ECDSA signature,
+    * or an error if the invoice should not be signed. This is the same as
+    * `KeysInterface::sign_invoice`.
+    */
+   struct LDKCResult_RecoverableSignatureNoneZ (*sign_invoice)(const void *this_arg, struct LDKCVec_u8Z invoice_preimage);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKInvoiceSigner;
+
+/**
+ * Accumulates the fields of a BOLT 11 invoice, see the module documentation.
+ */
+typedef struct MUST_USE_STRUCT LDKInvoiceBuilder {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeInvoiceBuilderFields *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKInvoiceBuilder;
+
+/**
+ * Integer in the range `0..32`
+ */
+typedef struct LDKu5 {
+   uint8_t _0;
+} LDKu5;
+
+/**
+ * A 20-byte byte array.
+ */
+typedef struct LDKTwentyBytes {
+   /**
+    * The twenty bytes
+    */
+   uint8_t data[20];
+} LDKTwentyBytes;
+
+/**
+ * Fallback address in case no LN payment is possible
+ */
+typedef enum LDKFallback_Tag {
+   LDKFallback_SegWitProgram,
+   LDKFallback_PubKeyHash,
+   LDKFallback_ScriptHash,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKFallback_Sentinel,
+} LDKFallback_Tag;
+
+typedef struct LDKFallback_LDKSegWitProgram_Body {
+   struct LDKu5 version;
+   struct LDKCVec_u8Z program;
+} LDKFallback_LDKSegWitProgram_Body;
+
+typedef struct MUST_USE_STRUCT LDKFallback {
+   LDKFallback_Tag tag;
+   union {
+      LDKFallback_LDKSegWitProgram_Body seg_wit_program;
+      struct {
+         struct LDKTwentyBytes pub_key_hash;
+      };
+      struct {
+         struct LDKTwentyBytes script_hash;
+      };
+   };
+} LDKFallback;
+
+/**
+ * The contents of CResult_InvoiceBuilderHodlInvoiceErrorZ
+ */
+typedef union LDKCResult_InvoiceBuilderHodlInvoiceErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKInvoiceBuilder *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKHodlInvoiceError *err;
+} LDKCResult_InvoiceBuilderHodlInvoiceErrorZPtr;
+
+/**
+ * A CResult_InvoiceBuilderHodlInvoiceErrorZ represents the result of a fallible operation,
+ * containing a InvoiceBuilder on success and a HodlInvoiceError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_InvoiceBuilderHodlInvoiceErrorZ {
+   /**
+    * The contents of this CResult_InvoiceBuilderHodlInvoiceErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_InvoiceBuilderHodlInvoiceErrorZPtr contents;
+   /**
+    * Whether this CResult_InvoiceBuilderHodlInvoiceErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_InvoiceBuilderHodlInvoiceErrorZ;
+
+/**
+ * The contents of CResult_NoneHodlInvoiceErrorZ
+ */
+typedef union LDKCResult_NoneHodlInvoiceErrorZPtr {
+   /**
+    * Note that this value is always NULL, as there are no contents in the OK variant
+    */
+   void *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKHodlInvoiceError *err;
+} LDKCResult_NoneHodlInvoiceErrorZPtr;
+
+/**
+ * A CResult_NoneHodlInvoiceErrorZ represents the result of a fallible operation,
+ * containing a () on success and a HodlInvoiceError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_NoneHodlInvoiceErrorZ {
+   /**
+    * The contents of this CResult_NoneHodlInvoiceErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_NoneHodlInvoiceErrorZPtr contents;
+   /**
+    * Whether this CResult_NoneHodlInvoiceErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_NoneHodlInvoiceErrorZ;
+
+/**
+ * Tracks payments to HODL invoices, see the module documentation.
+ */
+typedef struct MUST_USE_STRUCT LDKHodlInvoiceManager {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeHodlInvoiceManager *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKHodlInvoiceManager;
+
+/**
+ * The contents of CResult_HodlInvoiceManagerIOErrorZ
+ */
+typedef union LDKCResult_HodlInvoiceManagerIOErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKHodlInvoiceManager *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_HodlInvoiceManagerIOErrorZPtr;
+
+/**
+ * A CResult_HodlInvoiceManagerIOErrorZ represents the result of a fallible operation,
+ * containing a HodlInvoiceManager on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_HodlInvoiceManagerIOErrorZ {
+   /**
+    * The contents of this CResult_HodlInvoiceManagerIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_HodlInvoiceManagerIOErrorZPtr contents;
+   /**
+    * Whether this CResult_HodlInvoiceManagerIOErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_HodlInvoiceManagerIOErrorZ;
+
+/**
+ * A recorded payment, as returned by the `PaymentStore` query methods.
+ */
+typedef struct LDKPaymentDetails {
+   /**
+    * Whether the payment is inbound or outbound.
+    */
+   enum LDKPaymentDirection direction;
+   /**
+    * The current status of the payment.
+    */
+   enum LDKPaymentStatus status;
+   /**
+    * The payment hash.
+    */
+   struct LDKThirtyTwoBytes payment_hash;
+   /**
+    * The payment id returned when the payment was sent.
+    *
+    * Note that this is all-0s for inbound payments.
+    */
+   struct LDKThirtyTwoBytes payment_id;
+   /**
+    * The payment preimage, once known.
+    *
+    * Note that this may be all-0s to represent None
+    */
+   struct LDKThirtyTwoBytes payment_preimage;
+   /**
+    * The payment secret, if the payment was made to or received for an invoice.
+    *
+    * Note that this may be all-0s to represent None
+    */
+   struct LDKThirtyTwoBytes payment_secret;
+   /**
+    * For inbound payments, the amount received once received and the invoice amount, if any,
+    * before. For outbound payments, the amount sent to the destination, excluding fees.
+    */
+   struct LDKCOption_u64Z amount_msat;
+   /**
+    * The fees paid to intermediate hops, once an outbound payment succeeded.
+    */
+   struct LDKCOption_u64Z fee_paid_msat;
+   /**
+    * The description of the invoice, or an empty string if the invoice only contained a
+    * description hash or the payment was not made for an invoice.
+    */
+   struct LDKStr description;
+   /**
+    * When the payment was recorded, in seconds since the UNIX epoch.
+    */
+   uint64_t created_at;
+   /**
+    * When the invoice expires, in seconds since the UNIX epoch, or 0 for outbound payments and
+    * payments received without an invoice.
+    */
+   uint64_t expires_at;
+   /**
+    * When the status of the payment last changed, in seconds since the UNIX epoch.
+    */
+   uint64_t updated_at;
+} LDKPaymentDetails;
+
+/**
+ * A dynamically-allocated array of PaymentDetailss of arbitrary size.
+ * This corresponds to std::vector in C++
+ */
+typedef struct LDKCVec_PaymentDetailsZ {
+   /**
+    * The elements in the array.
+    * If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
+    */
+   struct LDKPaymentDetails *data;
+   /**
+    * The number of elements pointed to by `data`.
+    */
+   uintptr_t datalen;
+} LDKCVec_PaymentDetailsZ;
+
+/**
+ * An enum which can either contain a PaymentDetails or not
+ */
+typedef enum LDKCOption_PaymentDetailsZ_Tag {
+   /**
+    * When we're in this state, this COption_PaymentDetailsZ contains a PaymentDetails
+    */
+   LDKCOption_PaymentDetailsZ_Some,
+   /**
+    * When we're in this state, this COption_PaymentDetailsZ contains nothing
+    */
+   LDKCOption_PaymentDetailsZ_None,
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKCOption_PaymentDetailsZ_Sentinel,
+} LDKCOption_PaymentDetailsZ_Tag;
+
+typedef struct LDKCOption_PaymentDetailsZ {
+   LDKCOption_PaymentDetailsZ_Tag tag;
+   union {
+      struct {
+         struct LDKPaymentDetails some;
+      };
+   };
+} LDKCOption_PaymentDetailsZ;
+
+/**
+ * Records issued invoices and outbound payments, see the module documentation.
+ */
+typedef struct MUST_USE_STRUCT LDKPaymentStore {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativePaymentStore *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKPaymentStore;
+
+/**
+ * The contents of CResult_PaymentStoreIOErrorZ
+ */
+typedef union LDKCResult_PaymentStoreIOErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKPaymentStore *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_PaymentStoreIOErrorZPtr;
+
+/**
+ * A CResult_PaymentStoreIOErrorZ represents the result of a fallible operation,
+ * containing a PaymentStore on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_PaymentStoreIOErrorZ {
+   /**
+    * The contents of this CResult_PaymentStoreIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_PaymentStoreIOErrorZPtr contents;
+   /**
+    * Whether this CResult_PaymentStoreIOErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_PaymentStoreIOErrorZ;
+
+/**
+ * A trait defining behavior of an [`Invoice`] payer.
+ */
+typedef struct LDKPayer {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Returns the payer's node id.
+    */
+   struct LDKPublicKey (*node_id)(const void *this_arg);
+   /**
+    * Returns the payer's channels.
+    */
+   struct LDKCVec_ChannelDetailsZ (*first_hops)(const void *this_arg);
+   /**
+    * Sends a payment over the Lightning Network using the given [`Route`].
+    *
+    * Note that payment_secret (or a relevant inner pointer) may be NULL or all-0s to represent None
+    */
+   struct LDKCResult_PaymentIdPaymentSendFailureZ (*send_payment)(const void *this_arg, const struct LDKRoute *NONNULL_PTR route, struct LDKThirtyTwoBytes payment_hash, struct LDKThirtyTwoBytes payment_secret);
+   /**
+    * Sends a spontaneous payment over the Lightning Network using the given [`Route`].
+    */
+   struct LDKCResult_PaymentIdPaymentSendFailureZ (*send_spontaneous_payment)(const void *this_arg, const struct LDKRoute *NONNULL_PTR route, struct LDKThirtyTwoBytes payment_preimage);
+   /**
+    * Retries a failed payment path for the [`PaymentId`] using the given [`Route`].
+    */
+   struct LDKCResult_NonePaymentSendFailureZ (*retry_payment)(const void *this_arg, const struct LDKRoute *NONNULL_PTR route, struct LDKThirtyTwoBytes payment_id);
+   /**
+    * Signals that no further retries for the given payment will occur.
+    */
+   void (*abandon_payment)(const void *this_arg, struct LDKThirtyTwoBytes payment_id);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKPayer;
+
+/**
+ * An interface used to score payment channels for path finding.
+ *
+ *\tScoring is in terms of fees willing to be paid in order to avoid routing through a channel.
+ */
+typedef struct LDKScore {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Returns the fee in msats willing to be paid to avoid routing `send_amt_msat` through the
+    * given channel in the direction from `source` to `target`.
+    *
+    * The channel's capacity (less any other MPP parts which are also being considered for use in
+    * the same payment) is given by `channel_capacity_msat`. It may be guessed from various
+    * sources or assumed from no data at all.
+    *
+    * For hints provided in the invoice, we assume the channel has sufficient capacity to accept
+    * the invoice's full amount, and provide a `channel_capacity_msat` of `None`. In all other
+    * cases it is set to `Some`, even if we're guessing at the channel value.
+    *
+    * Your code should be overflow-safe through a `channel_capacity_msat` of 21 million BTC.
+    */
+   uint64_t (*channel_penalty_msat)(const void *this_arg, uint64_t short_channel_id, uint64_t send_amt_msat, struct LDKCOption_u64Z channel_capacity_msat, const struct LDKNodeId *NONNULL_PTR source, const struct LDKNodeId *NONNULL_PTR target);
+   /**
+    * Handles updating channel penalties after failing to route through a channel.
+    */
+   void (*payment_path_failed)(void *this_arg, struct LDKCVec_RouteHopZ path, uint64_t short_channel_id);
+   /**
+    * Handles updating channel penalties after successfully routing along a path.
+    */
+   void (*payment_path_successful)(void *this_arg, struct LDKCVec_RouteHopZ path);
+   /**
+    * Serialize the object into a byte array
+    */
+   struct LDKCVec_u8Z (*write)(const void *this_arg);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKScore;
+
+/**
+ * A trait defining behavior for routing an [`Invoice`] payment.
+ */
+typedef struct LDKRouter {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Finds a [`Route`] between `payer` and `payee` for a payment with the given values.
+    *
+    * Note that first_hops (or a relevant inner pointer) may be NULL or all-0s to represent None
+    */
+   struct LDKCResult_RouteLightningErrorZ (*find_route)(const void *this_arg, struct LDKPublicKey payer, const struct LDKRouteParameters *NONNULL_PTR params, const uint8_t (*payment_hash)[32], struct LDKCVec_ChannelDetailsZ *first_hops, const struct LDKScore *NONNULL_PTR scorer);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKRouter;
+
+
+
+/**
+ * A concrete implementation of [`LockableScore`] which supports multi-threading.
+ */
+typedef struct MUST_USE_STRUCT LDKMultiThreadedLockableScore {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeMultiThreadedLockableScore *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKMultiThreadedLockableScore;
+
+
+
+/**
+ * A utility for paying [`Invoice`]s and sending spontaneous payments.
+ *
+ * See [module-level documentation] for details.
+ *
+ * [module-level documentation]: crate::payment
+ */
+typedef struct MUST_USE_STRUCT LDKInvoicePayer {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeInvoicePayer *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKInvoicePayer;
+
+/**
+ * Pays invoices like `InvoicePayer`, retrying according to a [`RetryStrategy`], see the module
+ * documentation.
+ */
+typedef struct MUST_USE_STRUCT LDKRetryingInvoicePayer {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeRetryingInvoicePayer *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKRetryingInvoicePayer;
+
+/**
+ * When a [`RetryingInvoicePayer`] stops retrying a payment.
+ */
+typedef struct LDKRetryStrategy {
+   /**
+    * The maximum number of routes a payment is attempted over, including the first. Values
+    * below 1 are treated as 1, ie no retries.
+    */
+   uint32_t max_attempts;
+   /**
+    * How long after a payment is started retries may be attempted, in seconds.
+    */
+   struct LDKCOption_u64Z timeout_secs;
+   /**
+    * The maximum total fee, in millisatoshis, of the paths of a payment which are in flight at
+    * once. Routes which would exceed it are not attempted.
+    */
+   struct LDKCOption_u64Z max_total_fee_msat;
+   /**
+    * Whether a channel responsible for a failed path is avoided for the rest of the payment.
+    */
+   bool exclude_failed_channels;
+   /**
+    * Whether a node reported as failing by a failed path is avoided for the rest of the
+    * payment.
+    */
+   bool exclude_failed_nodes;
+} LDKRetryStrategy;
+
+/**
+ * A path over which part or all of a payment was attempted.
+ */
+typedef struct LDKPaymentPathAttempt {
+   /**
+    * Which attempt the path was part of, starting from 1 for the first route.
+    */
+   uint32_t attempt;
+   /**
+    * The hops of the path.
+    */
+   struct LDKCVec_RouteHopZ path;
+   /**
+    * What became of the path.
+    */
+   enum LDKPaymentPathStatus status;
+   /**
+    * The channel responsible for the failure, if the path failed and it is known.
+    */
+   struct LDKCOption_u64Z short_channel_id;
+   /**
+    * The update to the network graph conveyed by the failure, if any.
+    */
+   struct LDKCOption_NetworkUpdateZ network_update;
+   /**
+    * Whether the destination rejected the path.
+    */
+   bool rejected_by_dest;
+} LDKPaymentPathAttempt;
+
+/**
+ * A dynamically-allocated array of PaymentPathAttempts of arbitrary size.
+ * This corresponds to std::vector in C++
+ */
+typedef struct LDKCVec_PaymentPathAttemptZ {
+   /**
+    * The elements in the array.
+    * If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
+    */
+   struct LDKPaymentPathAttempt *data;
+   /**
+    * The number of elements pointed to by `data`.
+    */
+   uintptr_t datalen;
+} LDKCVec_PaymentPathAttemptZ;
+
+/**
+ * A summary of a payment once it succeeded or failed.
+ */
+typedef struct LDKPaymentOutcome {
+   /**
+    * The id returned when the payment was sent.
+    */
+   struct LDKThirtyTwoBytes payment_id;
+   /**
+    * The payment hash.
+    */
+   struct LDKThirtyTwoBytes payment_hash;
+   /**
+    * How the payment ended.
+    */
+   enum LDKPaymentOutcomeStatus status;
+   /**
+    * The total fee paid, if the payment succeeded.
+    */
+   struct LDKCOption_u64Z fee_paid_msat;
+   /**
+    * The number of routes the payment was attempted over.
+    */
+   uint32_t attempts;
+   /**
+    * Every path the payment was attempted over, in the order they were attempted.
+    */
+   struct LDKCVec_PaymentPathAttemptZ paths;
+} LDKPaymentOutcome;
+
+/**
+ * Receives the [`PaymentOutcome`] of each payment made through a [`RetryingInvoicePayer`].
+ */
+typedef struct LDKPaymentOutcomeHandler {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Called once a payment succeeded or failed, before the corresponding `Event::PaymentSent`
+    * or `Event::PaymentFailed` is passed to the payer's `EventHandler`.
+    */
+   void (*handle_outcome)(const void *this_arg, const struct LDKPaymentOutcome *NONNULL_PTR outcome);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKPaymentOutcomeHandler;
+
+#if defined(LDK_LINUX)
+/**
+ * The contents of CResult_NoneLdkNetErrorZ
+ */
+typedef union LDKCResult_NoneLdkNetErrorZPtr {
+   /**
+    * Note that this value is always NULL, as there are no contents in the OK variant
+    */
+   void *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKLdkNetError *err;
+} LDKCResult_NoneLdkNetErrorZPtr;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * A CResult_NoneLdkNetErrorZ represents the result of a fallible operation,
+ * containing a () on success and a LdkNetError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_NoneLdkNetErrorZ {
+   /**
+    * The contents of this CResult_NoneLdkNetErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_NoneLdkNetErrorZPtr contents;
+   /**
+    * Whether this CResult_NoneLdkNetErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_NoneLdkNetErrorZ;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * A running socket driver for a `PeerManager`, created with [`LdkNet_start`].
+ */
+typedef struct MUST_USE_STRUCT LDKLdkNet {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeLdkNet *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKLdkNet;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * The contents of CResult_LdkNetLdkNetErrorZ
+ */
+typedef union LDKCResult_LdkNetLdkNetErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKLdkNet *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKLdkNetError *err;
+} LDKCResult_LdkNetLdkNetErrorZPtr;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * A CResult_LdkNetLdkNetErrorZ represents the result of a fallible operation,
+ * containing a LdkNet on success and a LdkNetError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_LdkNetLdkNetErrorZ {
+   /**
+    * The contents of this CResult_LdkNetLdkNetErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_LdkNetLdkNetErrorZPtr contents;
+   /**
+    * Whether this CResult_LdkNetLdkNetErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_LdkNetLdkNetErrorZ;
+#endif
+
+/**
+ * Provides an object which can be used to send data to and which uniquely identifies a connection
+ * to a remote host. You will need to be able to generate multiple of these which meet Eq and
+ * implement Hash to meet the PeerManager API.
+ *
+ * For efficiency, Clone should be relatively cheap for this type.
+ *
+ * Two descriptors may compare equal (by [`cmp::Eq`] and [`hash::Hash`]) as long as the original
+ * has been disconnected, the [`PeerManager`] has been informed of the disconnection (either by it
+ * having triggered the disconnection or a call to [`PeerManager::socket_disconnected`]), and no
+ * further calls to the [`PeerManager`] related to the original socket occur. This allows you to
+ * use a file descriptor for your SocketDescriptor directly, however for simplicity you may wish
+ * to simply use another value which is guaranteed to be globally unique instead.
+ */
+typedef struct LDKSocketDescriptor {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Attempts to send some data from the given slice to the peer.
+    *
+    * Returns the amount of data which was sent, possibly 0 if the socket has since disconnected.
+    * Note that in the disconnected case, [`PeerManager::socket_disconnected`] must still be
+    * called and further write attempts may occur until that time.
+    *
+    * If the returned size is smaller than `data.len()`, a
+    * [`PeerManager::write_buffer_space_avail`] call must be made the next time more data can be
+    * written. Additionally, until a `send_data` event completes fully, no further
+    * [`PeerManager::read_event`] calls should be made for the same peer! Because this is to
+    * prevent denial-of-service issues, you should not read or buffer any data from the socket
+    * until then.
+    *
+    * If a [`PeerManager::read_event`] call on this descriptor had previously returned true
+    * (indicating that read events should be paused to prevent DoS in the send buffer),
+    * `resume_read` may be set indicating that read events on this descriptor should resume. A
+    * `resume_read` of false carries no meaning, and should not cause any action.
+    */
+   uintptr_t (*send_data)(void *this_arg, struct LDKu8slice data, bool resume_read);
+   /**
+    * Disconnect the socket pointed to by this SocketDescriptor.
+    *
+    * You do *not* need to call [`PeerManager::socket_disconnected`] with this socket after this
+    * call (doing so is a noop).
+    */
+   void (*disconnect_socket)(void *this_arg);
+   /**
+    * Checks if two objects are equal given this object's this_arg pointer and another object.
+    */
+   bool (*eq)(const void *this_arg, const struct LDKSocketDescriptor *NONNULL_PTR other_arg);
+   /**
+    * Calculate a succinct non-cryptographic hash for an object given its this_arg pointer.
+    * This is used, for example, for inclusion of this object in a hash map.
+    */
+   uint64_t (*hash)(const void *this_arg);
+   /**
+    * Called, if set, after this SocketDescriptor has been cloned into a duplicate object.
+    * The new SocketDescriptor is provided, and should be mutated as needed to perform a
+    * deep copy of the object pointed to by this_arg or avoid any double-freeing.
+    */
+   void (*cloned)(struct LDKSocketDescriptor *NONNULL_PTR new_SocketDescriptor);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKSocketDescriptor;
+
+
+
+/**
+ * A PeerManager manages a set of peers, described by their [`SocketDescriptor`] and marshalls
+ * socket events into messages which it passes on to its [`MessageHandler`].
+ *
+ * Locks are taken internally, so you must never assume that reentrancy from a
+ * [`SocketDescriptor`] call back into [`PeerManager`] methods will not deadlock.
+ *
+ * Calls to [`read_event`] will decode relevant messages and pass them to the
+ * [`ChannelMessageHandler`], likely doing message processing in-line. Thus, the primary form of
+ * parallelism in Rust-Lightning is in calls to [`read_event`]. Note, however, that calls to any
+ * [`PeerManager`] functions related to the same connection must occur only in serial, making new
+ * calls only after previous ones have returned.
+ *
+ * Rather than using a plain PeerManager, it is preferable to use either a SimpleArcPeerManager
+ * a SimpleRefPeerManager, for conciseness. See their documentation for more details, but
+ * essentially you should default to using a SimpleRefPeerManager, and use a
+ * SimpleArcPeerManager when you require a PeerManager with a static lifetime, such as when
+ * you're using lightning-net-tokio.
+ *
+ * [`read_event`]: PeerManager::read_event
+ */
+typedef struct MUST_USE_STRUCT LDKPeerManager {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativePeerManager *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKPeerManager;
+
+#if defined(LDK_LINUX)
+/**
+ * Limits on inbound connections and per-connection bandwidth, installed on a socket driver with
+ * `LdkNet_set_connection_policy`.
+ */
+typedef struct MUST_USE_STRUCT LDKConnectionPolicy {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeConnectionPolicy *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKConnectionPolicy;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * Decides whether to keep a connection to a peer once its handshake has completed and its node
+ * id is known.
+ */
+typedef struct LDKPeerFilter {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Returns true if we should stay connected to the peer with the given node id, or false if
+    * it should be disconnected.
+    *
+    * This is called from the socket driver's event loop, which is blocked until it returns.
+    */
+   bool (*accept_peer)(const void *this_arg, struct LDKPublicKey node_id);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKPeerFilter;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * The address of a SOCKS5 proxy through which outbound peer connections can be made.
+ */
+typedef struct MUST_USE_STRUCT LDKSocks5Proxy {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativeSocks5Proxy *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKSocks5Proxy;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * A connect, disconnect or connection failure of a peer in a [`PeerReconnector`]'s address book.
+ */
+typedef enum LDKPeerEvent_Tag {
+#if defined(LDK_LINUX)
+   /**
+    * We completed the handshake with the peer, either on an outbound connection we made or an
+    * inbound connection from them.
+    */
+   LDKPeerEvent_Connected,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * A previously-connected peer disconnected. A reconnection attempt will be made shortly.
+    */
+   LDKPeerEvent_Disconnected,
+#endif
+#if defined(LDK_LINUX)
+   /**
+    * An attempt to connect to the peer failed.
+    */
+   LDKPeerEvent_ConnectionFailed,
+#endif
+   /**
+    * Must be last for serialization purposes
+    */
+   LDKPeerEvent_Sentinel,
+} LDKPeerEvent_Tag;
+
+#if defined(LDK_LINUX)
+typedef struct LDKPeerEvent_LDKConnected_Body {
+   /**
+    * The node id of the peer.
+    */
+   struct LDKPublicKey node_id;
+} LDKPeerEvent_LDKConnected_Body;
+#endif
+
+#if defined(LDK_LINUX)
+typedef struct LDKPeerEvent_LDKDisconnected_Body {
+   /**
+    * The node id of the peer.
+    */
+   struct LDKPublicKey node_id;
+} LDKPeerEvent_LDKDisconnected_Body;
+#endif
+
+#if defined(LDK_LINUX)
+typedef struct LDKPeerEvent_LDKConnectionFailed_Body {
+   /**
+    * The node id of the peer.
+    */
+   struct LDKPublicKey node_id;
+   /**
+    * Why the connection attempt failed.
+    */
+   enum LDKLdkNetError error;
+   /**
+    * The number of seconds until the next connection attempt.
+    */
+   uint64_t retry_in_secs;
+} LDKPeerEvent_LDKConnectionFailed_Body;
+#endif
+
+typedef struct MUST_USE_STRUCT LDKPeerEvent {
+   LDKPeerEvent_Tag tag;
+   union {
+#if defined(LDK_LINUX)
+      LDKPeerEvent_LDKConnected_Body connected;
+#endif
+#if defined(LDK_LINUX)
+      LDKPeerEvent_LDKDisconnected_Body disconnected;
+#endif
+#if defined(LDK_LINUX)
+      LDKPeerEvent_LDKConnectionFailed_Body connection_failed;
+#endif
+   };
+} LDKPeerEvent;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * A handler for [`PeerEvent`]s generated by a [`PeerReconnector`].
+ *
+ * Events are delivered from the reconnector's background threads, possibly concurrently.
+ */
+typedef struct LDKPeerEventHandler {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Handles the given [`PeerEvent`].
+    */
+   void (*handle_peer_event)(const void *this_arg, struct LDKPeerEvent event);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKPeerEventHandler;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * Keeps a persisted address book of peers and reconnects to them whenever they are not
+ * connected, created with [`PeerReconnector_new`].
+ */
+typedef struct MUST_USE_STRUCT LDKPeerReconnector {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   struct LDKnativePeerReconnector *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKPeerReconnector;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * The contents of CResult_PeerReconnectorIOErrorZ
+ */
+typedef union LDKCResult_PeerReconnectorIOErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKPeerReconnector *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_PeerReconnectorIOErrorZPtr;
+#endif
+
+#if defined(LDK_LINUX)
+/**
+ * A CResult_PeerReconnectorIOErrorZ represents the result of a fallible operation,
+ * containing a PeerReconnector on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_PeerReconnectorIOErrorZ {
+   /**
+    * The contents of this CResult_PeerReconnectorIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_PeerReconnectorIOErrorZPtr contents;
+   /**
+    * Whether this CResult_PeerReconnectorIOErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_PeerReconnectorIOErrorZ;
+#endif
+
+#if defined(LDK_SQLITE)
+/**
+ * The contents of CResult_SqliteStoreIOErrorZ
+ */
+typedef union LDKCResult_SqliteStoreIOErrorZPtr {
+   /**
+    * A pointer to the contents in the success state.
+    * Reading from this pointer when `result_ok` is not set is undefined.
+    */
+   struct LDKSqliteStore *result;
+   /**
+    * A pointer to the contents in the error state.
+    * Reading from this pointer when `result_ok` is set is undefined.
+    */
+   enum LDKIOError *err;
+} LDKCResult_SqliteStoreIOErrorZPtr;
+#endif
+
+#if defined(LDK_SQLITE)
+/**
+ * A CResult_SqliteStoreIOErrorZ represents the result of a fallible operation,
+ * containing a SqliteStore on success and a crate::c_types::IOError on failure.
+ * `result_ok` indicates the overall state, and the contents are provided via `contents`.
+ */
+typedef struct LDKCResult_SqliteStoreIOErrorZ {
+   /**
+    * The contents of this CResult_SqliteStoreIOErrorZ, accessible via either
+    * `err` or `result` depending on the state of `result_ok`.
+    */
+   union LDKCResult_SqliteStoreIOErrorZPtr contents;
+   /**
+    * Whether this CResult_SqliteStoreIOErrorZ represents a success state.
+    */
+   bool result_ok;
+} LDKCResult_SqliteStoreIOErrorZ;
+#endif
+
+/**
+ * A trait indicating an object may generate events.
+ *
+ * Events are processed by passing an [`EventHandler`] to [`process_pending_events`].
+ *
+ * # Requirements
+ *
+ * See [`process_pending_events`] for requirements around event processing.
+ *
+ * When using this trait, [`process_pending_events`] will call [`handle_event`] for each pending
+ * event since the last invocation. The handler must either act upon the event immediately
+ * or preserve it for later handling.
+ *
+ * Note, handlers may call back into the provider and thus deadlocking must be avoided. Be sure to
+ * consult the provider's documentation on the implication of processing events and how a handler
+ * may safely use the provider (e.g., see [`ChannelManager::process_pending_events`] and
+ * [`ChainMonitor::process_pending_events`]).
+ *
+ * (C-not implementable) As there is likely no reason for a user to implement this trait on their
+ * own type(s).
+ *
+ * [`process_pending_events`]: Self::process_pending_events
+ * [`handle_event`]: EventHandler::handle_event
+ * [`ChannelManager::process_pending_events`]: crate::ln::channelmanager::ChannelManager#method.process_pending_events
+ * [`ChainMonitor::process_pending_events`]: crate::chain::chainmonitor::ChainMonitor#method.process_pending_events
+ */
+typedef struct LDKEventsProvider {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Processes any events generated since the last call using the given event handler.
+    *
+    * Subsequent calls must only process new events. However, handlers must be capable of handling
+    * duplicate events across process restarts. This may occur if the provider was recovered from
+    * an old state (i.e., it hadn't been successfully persisted after processing pending events).
+    */
+   void (*process_pending_events)(const void *this_arg, struct LDKEventHandler handler);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKEventsProvider;
+
+
+
+/**
+ * Configuration we set when applicable.
+ *
+ * Default::default() provides sane defaults.
+ */
+typedef struct MUST_USE_STRUCT LDKChannelHandshakeConfig {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeChannelHandshakeConfig *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKChannelHandshakeConfig;
+
+
+
+/**
+ * Optional channel limits which are applied during channel creation.
+ *
+ * These limits are only applied to our counterparty's limits, not our own.
+ *
+ * Use 0/<type>::max_value() as appropriate to skip checking.
+ *
+ * Provides sane defaults for most configurations.
+ *
+ * Most additional limits are disabled except those with which specify a default in individual
+ * field documentation. Note that this may result in barely-usable channels, but since they
+ * are applied mostly only to incoming channels that's not much of a problem.
+ */
+typedef struct MUST_USE_STRUCT LDKChannelHandshakeLimits {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeChannelHandshakeLimits *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKChannelHandshakeLimits;
+
+
+
+/**
+ * Simple KeysInterface implementor that takes a 32-byte seed for use as a BIP 32 extended key
+ * and derives keys from that.
+ *
+ * Your node_id is seed/0'
+ * ChannelMonitor closes may use seed/1'
+ * Cooperative closes may use seed/2'
+ * The two close keys may be needed to claim on-chain funds!
+ */
+typedef struct MUST_USE_STRUCT LDKKeysManager {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeKeysManager *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKKeysManager;
+
+
+
+/**
+ * Chain-related parameters used to construct a new `ChannelManager`.
+ *
+ * Typically, the block-specific parameters are derived from the best block hash for the network,
+ * as a newly constructed `ChannelManager` will not have created any channels yet. These parameters
+ * are not needed when deserializing a previously constructed `ChannelManager`.
+ */
+typedef struct MUST_USE_STRUCT LDKChainParameters {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeChainParameters *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKChainParameters;
+
+
+
+/**
+ * Information needed for constructing an invoice route hint for this channel.
+ */
+typedef struct MUST_USE_STRUCT LDKCounterpartyForwardingInfo {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeCounterpartyForwardingInfo *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKCounterpartyForwardingInfo;
+
+
+
+/**
+ * Channel parameters which apply to our counterparty. These are split out from [`ChannelDetails`]
+ * to better separate parameters.
+ */
+typedef struct MUST_USE_STRUCT LDKChannelCounterparty {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeChannelCounterparty *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKChannelCounterparty;
+
+/**
+ * A 3-byte byte array.
+ */
+typedef struct LDKThreeBytes {
+   /**
+    * The three bytes
+    */
+   uint8_t data[3];
+} LDKThreeBytes;
+
+
+
+/**
+ * Proof that the sender knows the per-commitment secret of the previous commitment transaction.
+ * This is used to convince the recipient that the channel is at a certain commitment
+ * number even if they lost that data due to a local failure.  Of course, the peer may lie
+ * and even later commitments may have been revoked.
+ */
+typedef struct MUST_USE_STRUCT LDKDataLossProtect {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeDataLossProtect *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKDataLossProtect;
+
+
+
+/**
+ * A dummy struct which implements `RoutingMessageHandler` without storing any routing information
+ * or doing any processing. You can provide one of these as the route_handler in a MessageHandler.
+ */
+typedef struct MUST_USE_STRUCT LDKIgnoringMessageHandler {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeIgnoringMessageHandler *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKIgnoringMessageHandler;
+
+
+
+/**
+ * A dummy struct which implements `ChannelMessageHandler` without having any channels.
+ * You can provide one of these as the route_handler in a MessageHandler.
+ */
+typedef struct MUST_USE_STRUCT LDKErroringMessageHandler {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeErroringMessageHandler *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKErroringMessageHandler;
+
+
+
+/**
+ * Provides references to trait impls which handle different types of messages.
+ */
+typedef struct MUST_USE_STRUCT LDKMessageHandler {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeMessageHandler *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKMessageHandler;
+
+
+
+/**
+ * Static channel fields used to build transactions given per-commitment fields, organized by
+ * broadcaster/countersignatory.
+ *
+ * This is derived from the holder/counterparty-organized ChannelTransactionParameters via the
+ * as_holder_broadcastable and as_counterparty_broadcastable functions.
+ */
+typedef struct MUST_USE_STRUCT LDKDirectedChannelTransactionParameters {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeDirectedChannelTransactionParameters *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKDirectedChannelTransactionParameters;
+
+
+
+/**
+ * A read-only view of [`NetworkGraph`].
+ */
+typedef struct MUST_USE_STRUCT LDKReadOnlyNetworkGraph {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeReadOnlyNetworkGraph *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKReadOnlyNetworkGraph;
+
+
+
+/**
+ * Receives and validates network updates from peers,
+ * stores authentic and relevant data as a network graph.
+ * This network graph is then used for routing payments.
+ * Provides interface to help with initial routing sync by
+ * serving historical announcements.
+ *
+ * Serves as an [`EventHandler`] for applying updates from [`Event::PaymentPathFailed`] to the
+ * [`NetworkGraph`].
+ */
+typedef struct MUST_USE_STRUCT LDKNetGraphMsgHandler {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeNetGraphMsgHandler *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKNetGraphMsgHandler;
+
+/**
+ * A scorer that is accessed under a lock.
+ *
+ * Needed so that calls to [`Score::channel_penalty_msat`] in [`find_route`] can be made while
+ * having shared ownership of a scorer but without requiring internal locking in [`Score`]
+ * implementations. Internal locking would be detrimental to route finding performance and could
+ * result in [`Score::channel_penalty_msat`] returning a different value for the same channel.
+ *
+ * [`find_route`]: crate::routing::router::find_route
+ */
+typedef struct LDKLockableScore {
+   /**
+    * An opaque pointer which is passed to your function implementations as an argument.
+    * This has no meaning in the LDK, and can be NULL or any other value.
+    */
+   void *this_arg;
+   /**
+    * Returns the locked scorer.
+    */
+   struct LDKScore (*lock)(const void *this_arg);
+   /**
+    * Frees any resources associated with this object given its this_arg pointer.
+    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+    */
+   void (*free)(void *this_arg);
+} LDKLockableScore;
+
+
+
+/**
+ * `BackgroundProcessor` takes care of tasks that (1) need to happen periodically to keep
+ * Rust-Lightning running properly, and (2) either can or should be run in the background. Its
+ * responsibilities are:
+ * * Processing [`Event`]s with a user-provided [`EventHandler`].
+ * * Monitoring whether the [`ChannelManager`] needs to be re-persisted to disk, and if so,
+ *   writing it to disk/backups by invoking the callback given to it at startup.
+ *   [`ChannelManager`] persistence should be done in the background.
+ * * Calling [`ChannelManager::timer_tick_occurred`] and [`PeerManager::timer_tick_occurred`]
+ *   at the appropriate intervals.
+ * * Calling [`NetworkGraph::remove_stale_channels`] (if a [`NetGraphMsgHandler`] is provided to
+ *   [`BackgroundProcessor::start`]).
+ *
+ * It will also call [`PeerManager::process_events`] periodically though this shouldn't be relied
+ * upon as doing so may result in high latency.
+ *
+ * # Note
+ *
+ * If [`ChannelManager`] persistence fails and the persisted manager becomes out-of-date, then
+ * there is a risk of channels force-closing on startup when the manager realizes it's outdated.
+ * However, as long as [`ChannelMonitor`] backups are sound, no funds besides those used for
+ * unilateral chain closure fees are at risk.
+ *
+ * [`ChannelMonitor`]: lightning::chain::channelmonitor::ChannelMonitor
+ * [`Event`]: lightning::util::events::Event
+ *BackgroundProcessor will immediately stop on drop. It should be stored until shutdown.
+ */
+typedef struct MUST_USE_STRUCT LDKBackgroundProcessor {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeBackgroundProcessor *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKBackgroundProcessor;
+
+
+
+/**
+ * Data of the `RawInvoice` that is encoded in the data part
+ */
+typedef struct MUST_USE_STRUCT LDKRawDataPart {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeRawDataPart *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKRawDataPart;
+
+
+
+/**
+ * SHA-256 hash
+ */
+typedef struct MUST_USE_STRUCT LDKSha256 {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeSha256 *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKSha256;
+
+
+
+/**
+ * `min_final_cltv_expiry` to use for the last HTLC in the route
+ */
+typedef struct MUST_USE_STRUCT LDKMinFinalCltvExpiry {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeMinFinalCltvExpiry *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKMinFinalCltvExpiry;
+
+
+
+/**
+ * Number of attempts to retry payment path failures for an [`Invoice`].
+ *
+ * Note that this is the number of *path* failures, not full payment retries. For multi-path
+ * payments, if this is less than the total number of paths, we will never even retry all of the
+ * payment's paths.
+ */
+typedef struct MUST_USE_STRUCT LDKRetryAttempts {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeRetryAttempts *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKRetryAttempts;
+
+
+
+/**
+ * A [`Router`] implemented using [`find_route`].
+ */
+typedef struct MUST_USE_STRUCT LDKDefaultRouter {
+   /**
+    * A pointer to the opaque Rust object.
+    * Nearly everywhere, inner must be non-null, however in places where
+    * the Rust equivalent takes an Option, it may be set to null to indicate None.
+    */
+   LDKnativeDefaultRouter *inner;
+   /**
+    * Indicates that this is the only struct which contains the same pointer.
+    * Rust functions which take ownership of an object provided via an argument require
+    * this to be true and invalidate the object pointed to by inner.
+    */
+   bool is_owned;
+} LDKDefaultRouter;
+
+extern const uintptr_t MAX_BUF_SIZE;
+
+extern const uint64_t MIN_RELAY_FEE_SAT_PER_1000_WEIGHT;
+
+extern const uint64_t CLOSED_CHANNEL_UPDATE_ID;
+
+extern const uint32_t ANTI_REORG_DELAY;
+
+extern const uint16_t BREAKDOWN_TIMEOUT;
+
+extern const uint16_t MIN_CLTV_EXPIRY_DELTA;
+
+extern const uint32_t MIN_FINAL_CLTV_EXPIRY;
+
+extern const uintptr_t REVOKEABLE_REDEEMSCRIPT_MAX_LENGTH;
+
+extern const uint64_t DEFAULT_EXPIRY_TIME;
+
+extern const uint64_t DEFAULT_MIN_FINAL_CLTV_EXPIRY;
+
+extern const uint8_t TAG_PAYMENT_HASH;
+
+extern const uint8_t TAG_DESCRIPTION;
+
+extern const uint8_t TAG_PAYEE_PUB_KEY;
+
+extern const uint8_t TAG_DESCRIPTION_HASH;
+
+extern const uint8_t TAG_EXPIRY_TIME;
+
+extern const uint8_t TAG_MIN_FINAL_CLTV_EXPIRY;
+
+extern const uint8_t TAG_FALLBACK;
+
+extern const uint8_t TAG_PRIVATE_ROUTE;
+
+extern const uint8_t TAG_PAYMENT_SECRET;
+
+extern const uint8_t TAG_FEATURES;
+
+struct LDKStr _ldk_get_compiled_version(void);
+
+struct LDKStr _ldk_c_bindings_get_compiled_version(void);
+
+/**
+ * Frees the data buffer, if data_is_owned is set and datalen > 0.
+ */
+void Transaction_free(struct LDKTransaction _res);
+
+/**
+ * Frees the data buffer, if data_is_owned is set and datalen > 0.
+ */
+void Psbt_free(struct LDKPsbt _res);
+
+/**
+ * Creates a new Psbt which has the same data as `orig` but with a new buffer.
+ */
+struct LDKPsbt Psbt_clone(const struct LDKPsbt *NONNULL_PTR orig);
+
+/**
+ * Convenience function for constructing a new TxOut
+ */
+struct LDKTxOut TxOut_new(struct LDKCVec_u8Z script_pubkey, uint64_t value);
+
+/**
+ * Frees the data pointed to by script_pubkey.
+ */
+void TxOut_free(struct LDKTxOut _res);
+
+/**
+ * Creates a new TxOut which has the same data as `orig` but with a new script buffer.
+ */
+struct LDKTxOut TxOut_clone(const struct LDKTxOut *NONNULL_PTR orig);
+
+/**
+ * Frees the data buffer, if chars_is_owned is set and len > 0.
+ */
+void Str_free(struct LDKStr _res);
+
+#if defined(LDK_DEBUG_BUILD)
+/**
+ * This function exists for memory safety testing purposes. It should never be used in production
+ * code
+ */
+const void *__unmangle_inner_ptr(const void *ptr);
+#endif
+
+/**
+ * Creates a new CResult_SecretKeyErrorZ in the success state.
+ */
+struct LDKCResult_SecretKeyErrorZ CResult_SecretKeyErrorZ_ok(struct LDKSecretKey o);
+
+/**
+ * Creates a new CResult_SecretKeyErrorZ in the error state.
+ */
+struct LDKCResult_SecretKeyErrorZ CResult_SecretKeyErrorZ_err(enum LDKSecp256k1Error e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_SecretKeyErrorZ_is_ok(const struct LDKCResult_SecretKeyErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_SecretKeyErrorZ.
+ */
+void CResult_SecretKeyErrorZ_free(struct LDKCResult_SecretKeyErrorZ _res);
+
+/**
+ * Creates a new CResult_PublicKeyErrorZ in the success state.
+ */
+struct LDKCResult_PublicKeyErrorZ CResult_PublicKeyErrorZ_ok(struct LDKPublicKey o);
+
+/**
+ * Creates a new CResult_PublicKeyErrorZ in the error state.
+ */
+struct LDKCResult_PublicKeyErrorZ CResult_PublicKeyErrorZ_err(enum LDKSecp256k1Error e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_PublicKeyErrorZ_is_ok(const struct LDKCResult_PublicKeyErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_PublicKeyErrorZ.
+ */
+void CResult_PublicKeyErrorZ_free(struct LDKCResult_PublicKeyErrorZ _res);
+
+/**
+ * Creates a new CResult_PublicKeyErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_PublicKeyErrorZ CResult_PublicKeyErrorZ_clone(const struct LDKCResult_PublicKeyErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_TxCreationKeysDecodeErrorZ in the success state.
+ */
+struct LDKCResult_TxCreationKeysDecodeErrorZ CResult_TxCreationKeysDecodeErrorZ_ok(struct LDKTxCreationKeys o);
+
+/**
+ * Creates a new CResult_TxCreationKeysDecodeErrorZ in the error state.
+ */
+struct LDKCResult_TxCreationKeysDecodeErrorZ CResult_TxCreationKeysDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_TxCreationKeysDecodeErrorZ_is_ok(const struct LDKCResult_TxCreationKeysDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_TxCreationKeysDecodeErrorZ.
+ */
+void CResult_TxCreationKeysDecodeErrorZ_free(struct LDKCResult_TxCreationKeysDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_TxCreationKeysDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_TxCreationKeysDecodeErrorZ CResult_TxCreationKeysDecodeErrorZ_clone(const struct LDKCResult_TxCreationKeysDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_ChannelPublicKeysDecodeErrorZ in the success state.
+ */
+struct LDKCResult_ChannelPublicKeysDecodeErrorZ CResult_ChannelPublicKeysDecodeErrorZ_ok(struct LDKChannelPublicKeys o);
+
+/**
+ * Creates a new CResult_ChannelPublicKeysDecodeErrorZ in the error state.
+ */
+struct LDKCResult_ChannelPublicKeysDecodeErrorZ CResult_ChannelPublicKeysDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ChannelPublicKeysDecodeErrorZ_is_ok(const struct LDKCResult_ChannelPublicKeysDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ChannelPublicKeysDecodeErrorZ.
+ */
+void CResult_ChannelPublicKeysDecodeErrorZ_free(struct LDKCResult_ChannelPublicKeysDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_ChannelPublicKeysDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_ChannelPublicKeysDecodeErrorZ CResult_ChannelPublicKeysDecodeErrorZ_clone(const struct LDKCResult_ChannelPublicKeysDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_TxCreationKeysErrorZ in the success state.
+ */
+struct LDKCResult_TxCreationKeysErrorZ CResult_TxCreationKeysErrorZ_ok(struct LDKTxCreationKeys o);
+
+/**
+ * Creates a new CResult_TxCreationKeysErrorZ in the error state.
+ */
+struct LDKCResult_TxCreationKeysErrorZ CResult_TxCreationKeysErrorZ_err(enum LDKSecp256k1Error e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_TxCreationKeysErrorZ_is_ok(const struct LDKCResult_TxCreationKeysErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_TxCreationKeysErrorZ.
+ */
+void CResult_TxCreationKeysErrorZ_free(struct LDKCResult_TxCreationKeysErrorZ _res);
+
+/**
+ * Creates a new CResult_TxCreationKeysErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_TxCreationKeysErrorZ CResult_TxCreationKeysErrorZ_clone(const struct LDKCResult_TxCreationKeysErrorZ *NONNULL_PTR orig);
+
+/**
+ * Constructs a new COption_u32Z containing a u32
+ */
+struct LDKCOption_u32Z COption_u32Z_some(uint32_t o);
+
+/**
+ * Constructs a new COption_u32Z containing nothing
+ */
+struct LDKCOption_u32Z COption_u32Z_none(void);
+
+/**
+ * Frees any resources associated with the u32, if we are in the Some state
+ */
+void COption_u32Z_free(struct LDKCOption_u32Z _res);
+
+/**
+ * Creates a new COption_u32Z which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCOption_u32Z COption_u32Z_clone(const struct LDKCOption_u32Z *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_HTLCOutputInCommitmentDecodeErrorZ in the success state.
+ */
+struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ CResult_HTLCOutputInCommitmentDecodeErrorZ_ok(struct LDKHTLCOutputInCommitment o);
+
+/**
+ * Creates a new CResult_HTLCOutputInCommitmentDecodeErrorZ in the error state.
+ */
+struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ CResult_HTLCOutputInCommitmentDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_HTLCOutputInCommitmentDecodeErrorZ_is_ok(const struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_HTLCOutputInCommitmentDecodeErrorZ.
+ */
+void CResult_HTLCOutputInCommitmentDecodeErrorZ_free(struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_HTLCOutputInCommitmentDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ CResult_HTLCOutputInCommitmentDecodeErrorZ_clone(const struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Constructs a new COption_NoneZ containing a
+ */
+enum LDKCOption_NoneZ COption_NoneZ_some(void);
+
+/**
+ * Constructs a new COption_NoneZ containing nothing
+ */
+enum LDKCOption_NoneZ COption_NoneZ_none(void);
+
+/**
+ * Frees any resources associated with the , if we are in the Some state
+ */
+void COption_NoneZ_free(enum LDKCOption_NoneZ _res);
+
+/**
+ * Creates a new CResult_CounterpartyChannelTransactionParametersDecodeErrorZ in the success state.
+ */
+struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_ok(struct LDKCounterpartyChannelTransactionParameters o);
+
+/**
+ * Creates a new CResult_CounterpartyChannelTransactionParametersDecodeErrorZ in the error state.
+ */
+struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_is_ok(const struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_CounterpartyChannelTransactionParametersDecodeErrorZ.
+ */
+void CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_free(struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_CounterpartyChannelTransactionParametersDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_clone(const struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_ChannelTransactionParametersDecodeErrorZ in the success state.
+ */
+struct LDKCResult_ChannelTransactionParametersDecodeErrorZ CResult_ChannelTransactionParametersDecodeErrorZ_ok(struct LDKChannelTransactionParameters o);
+
+/**
+ * Creates a new CResult_ChannelTransactionParametersDecodeErrorZ in the error state.
+ */
+struct LDKCResult_ChannelTransactionParametersDecodeErrorZ CResult_ChannelTransactionParametersDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ChannelTransactionParametersDecodeErrorZ_is_ok(const struct LDKCResult_ChannelTransactionParametersDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ChannelTransactionParametersDecodeErrorZ.
+ */
+void CResult_ChannelTransactionParametersDecodeErrorZ_free(struct LDKCResult_ChannelTransactionParametersDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_ChannelTransactionParametersDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_ChannelTransactionParametersDecodeErrorZ CResult_ChannelTransactionParametersDecodeErrorZ_clone(const struct LDKCResult_ChannelTransactionParametersDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_SignatureZ_free(struct LDKCVec_SignatureZ _res);
+
+/**
+ * Creates a new CResult_HolderCommitmentTransactionDecodeErrorZ in the success state.
+ */
+struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ CResult_HolderCommitmentTransactionDecodeErrorZ_ok(struct LDKHolderCommitmentTransaction o);
+
+/**
+ * Creates a new CResult_HolderCommitmentTransactionDecodeErrorZ in the error state.
+ */
+struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ CResult_HolderCommitmentTransactionDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_HolderCommitmentTransactionDecodeErrorZ_is_ok(const struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_HolderCommitmentTransactionDecodeErrorZ.
+ */
+void CResult_HolderCommitmentTransactionDecodeErrorZ_free(struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_HolderCommitmentTransactionDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ CResult_HolderCommitmentTransactionDecodeErrorZ_clone(const struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_BuiltCommitmentTransactionDecodeErrorZ in the success state.
+ */
+struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ CResult_BuiltCommitmentTransactionDecodeErrorZ_ok(struct LDKBuiltCommitmentTransaction o);
+
+/**
+ * Creates a new CResult_BuiltCommitmentTransactionDecodeErrorZ in the error state.
+ */
+struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ CResult_BuiltCommitmentTransactionDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_BuiltCommitmentTransactionDecodeErrorZ_is_ok(const struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_BuiltCommitmentTransactionDecodeErrorZ.
+ */
+void CResult_BuiltCommitmentTransactionDecodeErrorZ_free(struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_BuiltCommitmentTransactionDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ CResult_BuiltCommitmentTransactionDecodeErrorZ_clone(const struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_TrustedClosingTransactionNoneZ in the success state.
+ */
+struct LDKCResult_TrustedClosingTransactionNoneZ CResult_TrustedClosingTransactionNoneZ_ok(struct LDKTrustedClosingTransaction o);
+
+/**
+ * Creates a new CResult_TrustedClosingTransactionNoneZ in the error state.
+ */
+struct LDKCResult_TrustedClosingTransactionNoneZ CResult_TrustedClosingTransactionNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_TrustedClosingTransactionNoneZ_is_ok(const struct LDKCResult_TrustedClosingTransactionNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_TrustedClosingTransactionNoneZ.
+ */
+void CResult_TrustedClosingTransactionNoneZ_free(struct LDKCResult_TrustedClosingTransactionNoneZ _res);
+
+/**
+ * Creates a new CResult_CommitmentTransactionDecodeErrorZ in the success state.
+ */
+struct LDKCResult_CommitmentTransactionDecodeErrorZ CResult_CommitmentTransactionDecodeErrorZ_ok(struct LDKCommitmentTransaction o);
+
+/**
+ * Creates a new CResult_CommitmentTransactionDecodeErrorZ in the error state.
+ */
+struct LDKCResult_CommitmentTransactionDecodeErrorZ CResult_CommitmentTransactionDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_CommitmentTransactionDecodeErrorZ_is_ok(const struct LDKCResult_CommitmentTransactionDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_CommitmentTransactionDecodeErrorZ.
+ */
+void CResult_CommitmentTransactionDecodeErrorZ_free(struct LDKCResult_CommitmentTransactionDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_CommitmentTransactionDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_CommitmentTransactionDecodeErrorZ CResult_CommitmentTransactionDecodeErrorZ_clone(const struct LDKCResult_CommitmentTransactionDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_TrustedCommitmentTransactionNoneZ in the success state.
+ */
+struct LDKCResult_TrustedCommitmentTransactionNoneZ CResult_TrustedCommitmentTransactionNoneZ_ok(struct LDKTrustedCommitmentTransaction o);
+
+/**
+ * Creates a new CResult_TrustedCommitmentTransactionNoneZ in the error state.
+ */
+struct LDKCResult_TrustedCommitmentTransactionNoneZ CResult_TrustedCommitmentTransactionNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_TrustedCommitmentTransactionNoneZ_is_ok(const struct LDKCResult_TrustedCommitmentTransactionNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_TrustedCommitmentTransactionNoneZ.
+ */
+void CResult_TrustedCommitmentTransactionNoneZ_free(struct LDKCResult_TrustedCommitmentTransactionNoneZ _res);
+
+/**
+ * Creates a new CResult_CVec_SignatureZNoneZ in the success state.
+ */
+struct LDKCResult_CVec_SignatureZNoneZ CResult_CVec_SignatureZNoneZ_ok(struct LDKCVec_SignatureZ o);
+
+/**
+ * Creates a new CResult_CVec_SignatureZNoneZ in the error state.
+ */
+struct LDKCResult_CVec_SignatureZNoneZ CResult_CVec_SignatureZNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_CVec_SignatureZNoneZ_is_ok(const struct LDKCResult_CVec_SignatureZNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_CVec_SignatureZNoneZ.
+ */
+void CResult_CVec_SignatureZNoneZ_free(struct LDKCResult_CVec_SignatureZNoneZ _res);
+
+/**
+ * Creates a new CResult_CVec_SignatureZNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_CVec_SignatureZNoneZ CResult_CVec_SignatureZNoneZ_clone(const struct LDKCResult_CVec_SignatureZNoneZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_ShutdownScriptDecodeErrorZ in the success state.
+ */
+struct LDKCResult_ShutdownScriptDecodeErrorZ CResult_ShutdownScriptDecodeErrorZ_ok(struct LDKShutdownScript o);
+
+/**
+ * Creates a new CResult_ShutdownScriptDecodeErrorZ in the error state.
+ */
+struct LDKCResult_ShutdownScriptDecodeErrorZ CResult_ShutdownScriptDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ShutdownScriptDecodeErrorZ_is_ok(const struct LDKCResult_ShutdownScriptDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ShutdownScriptDecodeErrorZ.
+ */
+void CResult_ShutdownScriptDecodeErrorZ_free(struct LDKCResult_ShutdownScriptDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_ShutdownScriptDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_ShutdownScriptDecodeErrorZ CResult_ShutdownScriptDecodeErrorZ_clone(const struct LDKCResult_ShutdownScriptDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_ShutdownScriptInvalidShutdownScriptZ in the success state.
+ */
+struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ CResult_ShutdownScriptInvalidShutdownScriptZ_ok(struct LDKShutdownScript o);
+
+/**
+ * Creates a new CResult_ShutdownScriptInvalidShutdownScriptZ in the error state.
+ */
+struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ CResult_ShutdownScriptInvalidShutdownScriptZ_err(struct LDKInvalidShutdownScript e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ShutdownScriptInvalidShutdownScriptZ_is_ok(const struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ShutdownScriptInvalidShutdownScriptZ.
+ */
+void CResult_ShutdownScriptInvalidShutdownScriptZ_free(struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ _res);
+
+/**
+ * Creates a new CResult_ShutdownScriptInvalidShutdownScriptZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ CResult_ShutdownScriptInvalidShutdownScriptZ_clone(const struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_NoneErrorZ in the success state.
+ */
+struct LDKCResult_NoneErrorZ CResult_NoneErrorZ_ok(void);
+
+/**
+ * Creates a new CResult_NoneErrorZ in the error state.
+ */
+struct LDKCResult_NoneErrorZ CResult_NoneErrorZ_err(enum LDKIOError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_NoneErrorZ_is_ok(const struct LDKCResult_NoneErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_NoneErrorZ.
+ */
+void CResult_NoneErrorZ_free(struct LDKCResult_NoneErrorZ _res);
+
+/**
+ * Creates a new CResult_NoneErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_NoneErrorZ CResult_NoneErrorZ_clone(const struct LDKCResult_NoneErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_RouteHopDecodeErrorZ in the success state.
+ */
+struct LDKCResult_RouteHopDecodeErrorZ CResult_RouteHopDecodeErrorZ_ok(struct LDKRouteHop o);
+
+/**
+ * Creates a new CResult_RouteHopDecodeErrorZ in the error state.
+ */
+struct LDKCResult_RouteHopDecodeErrorZ CResult_RouteHopDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_RouteHopDecodeErrorZ_is_ok(const struct LDKCResult_RouteHopDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_RouteHopDecodeErrorZ.
+ */
+void CResult_RouteHopDecodeErrorZ_free(struct LDKCResult_RouteHopDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_RouteHopDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_RouteHopDecodeErrorZ CResult_RouteHopDecodeErrorZ_clone(const struct LDKCResult_RouteHopDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_RouteHopZ_free(struct LDKCVec_RouteHopZ _res);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_CVec_RouteHopZZ_free(struct LDKCVec_CVec_RouteHopZZ _res);
+
+/**
+ * Creates a new CResult_RouteDecodeErrorZ in the success state.
+ */
+struct LDKCResult_RouteDecodeErrorZ CResult_RouteDecodeErrorZ_ok(struct LDKRoute o);
+
+/**
+ * Creates a new CResult_RouteDecodeErrorZ in the error state.
+ */
+struct LDKCResult_RouteDecodeErrorZ CResult_RouteDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_RouteDecodeErrorZ_is_ok(const struct LDKCResult_RouteDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_RouteDecodeErrorZ.
+ */
+void CResult_RouteDecodeErrorZ_free(struct LDKCResult_RouteDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_RouteDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_RouteDecodeErrorZ CResult_RouteDecodeErrorZ_clone(const struct LDKCResult_RouteDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_RouteParametersDecodeErrorZ in the success state.
+ */
+struct LDKCResult_RouteParametersDecodeErrorZ CResult_RouteParametersDecodeErrorZ_ok(struct LDKRouteParameters o);
+
+/**
+ * Creates a new CResult_RouteParametersDecodeErrorZ in the error state.
+ */
+struct LDKCResult_RouteParametersDecodeErrorZ CResult_RouteParametersDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_RouteParametersDecodeErrorZ_is_ok(const struct LDKCResult_RouteParametersDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_RouteParametersDecodeErrorZ.
+ */
+void CResult_RouteParametersDecodeErrorZ_free(struct LDKCResult_RouteParametersDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_RouteParametersDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_RouteParametersDecodeErrorZ CResult_RouteParametersDecodeErrorZ_clone(const struct LDKCResult_RouteParametersDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_RouteHintZ_free(struct LDKCVec_RouteHintZ _res);
+
+/**
+ * Constructs a new COption_u64Z containing a u64
+ */
+struct LDKCOption_u64Z COption_u64Z_some(uint64_t o);
+
+/**
+ * Constructs a new COption_u64Z containing nothing
+ */
+struct LDKCOption_u64Z COption_u64Z_none(void);
+
+/**
+ * Frees any resources associated with the u64, if we are in the Some state
+ */
+void COption_u64Z_free(struct LDKCOption_u64Z _res);
+
+/**
+ * Creates a new COption_u64Z which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCOption_u64Z COption_u64Z_clone(const struct LDKCOption_u64Z *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_PayeeDecodeErrorZ in the success state.
+ */
+struct LDKCResult_PayeeDecodeErrorZ CResult_PayeeDecodeErrorZ_ok(struct LDKPayee o);
+
+/**
+ * Creates a new CResult_PayeeDecodeErrorZ in the error state.
+ */
+struct LDKCResult_PayeeDecodeErrorZ CResult_PayeeDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_PayeeDecodeErrorZ_is_ok(const struct LDKCResult_PayeeDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_PayeeDecodeErrorZ.
+ */
+void CResult_PayeeDecodeErrorZ_free(struct LDKCResult_PayeeDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_PayeeDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_PayeeDecodeErrorZ CResult_PayeeDecodeErrorZ_clone(const struct LDKCResult_PayeeDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_RouteHintHopZ_free(struct LDKCVec_RouteHintHopZ _res);
+
+/**
+ * Creates a new CResult_RouteHintDecodeErrorZ in the success state.
+ */
+struct LDKCResult_RouteHintDecodeErrorZ CResult_RouteHintDecodeErrorZ_ok(struct LDKRouteHint o);
+
+/**
+ * Creates a new CResult_RouteHintDecodeErrorZ in the error state.
+ */
+struct LDKCResult_RouteHintDecodeErrorZ CResult_RouteHintDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_RouteHintDecodeErrorZ_is_ok(const struct LDKCResult_RouteHintDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_RouteHintDecodeErrorZ.
+ */
+void CResult_RouteHintDecodeErrorZ_free(struct LDKCResult_RouteHintDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_RouteHintDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_RouteHintDecodeErrorZ CResult_RouteHintDecodeErrorZ_clone(const struct LDKCResult_RouteHintDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_RouteHintHopDecodeErrorZ in the success state.
+ */
+struct LDKCResult_RouteHintHopDecodeErrorZ CResult_RouteHintHopDecodeErrorZ_ok(struct LDKRouteHintHop o);
+
+/**
+ * Creates a new CResult_RouteHintHopDecodeErrorZ in the error state.
+ */
+struct LDKCResult_RouteHintHopDecodeErrorZ CResult_RouteHintHopDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_RouteHintHopDecodeErrorZ_is_ok(const struct LDKCResult_RouteHintHopDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_RouteHintHopDecodeErrorZ.
+ */
+void CResult_RouteHintHopDecodeErrorZ_free(struct LDKCResult_RouteHintHopDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_RouteHintHopDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_RouteHintHopDecodeErrorZ CResult_RouteHintHopDecodeErrorZ_clone(const struct LDKCResult_RouteHintHopDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_ChannelDetailsZ_free(struct LDKCVec_ChannelDetailsZ _res);
+
+/**
+ * Creates a new CResult_RouteLightningErrorZ in the success state.
+ */
+struct LDKCResult_RouteLightningErrorZ CResult_RouteLightningErrorZ_ok(struct LDKRoute o);
+
+/**
+ * Creates a new CResult_RouteLightningErrorZ in the error state.
+ */
+struct LDKCResult_RouteLightningErrorZ CResult_RouteLightningErrorZ_err(struct LDKLightningError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_RouteLightningErrorZ_is_ok(const struct LDKCResult_RouteLightningErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_RouteLightningErrorZ.
+ */
+void CResult_RouteLightningErrorZ_free(struct LDKCResult_RouteLightningErrorZ _res);
+
+/**
+ * Creates a new CResult_RouteLightningErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_RouteLightningErrorZ CResult_RouteLightningErrorZ_clone(const struct LDKCResult_RouteLightningErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_TxOutAccessErrorZ in the success state.
+ */
+struct LDKCResult_TxOutAccessErrorZ CResult_TxOutAccessErrorZ_ok(struct LDKTxOut o);
+
+/**
+ * Creates a new CResult_TxOutAccessErrorZ in the error state.
+ */
+struct LDKCResult_TxOutAccessErrorZ CResult_TxOutAccessErrorZ_err(enum LDKAccessError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_TxOutAccessErrorZ_is_ok(const struct LDKCResult_TxOutAccessErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_TxOutAccessErrorZ.
+ */
+void CResult_TxOutAccessErrorZ_free(struct LDKCResult_TxOutAccessErrorZ _res);
+
+/**
+ * Creates a new CResult_TxOutAccessErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_TxOutAccessErrorZ CResult_TxOutAccessErrorZ_clone(const struct LDKCResult_TxOutAccessErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKC2Tuple_usizeTransactionZ C2Tuple_usizeTransactionZ_clone(const struct LDKC2Tuple_usizeTransactionZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new C2Tuple_usizeTransactionZ from the contained elements.
+ */
+struct LDKC2Tuple_usizeTransactionZ C2Tuple_usizeTransactionZ_new(uintptr_t a, struct LDKTransaction b);
+
+/**
+ * Frees any resources used by the C2Tuple_usizeTransactionZ.
+ */
+void C2Tuple_usizeTransactionZ_free(struct LDKC2Tuple_usizeTransactionZ _res);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_C2Tuple_usizeTransactionZZ_free(struct LDKCVec_C2Tuple_usizeTransactionZZ _res);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_TxidZ_free(struct LDKCVec_TxidZ _res);
+
+/**
+ * Creates a new CResult_NoneChannelMonitorUpdateErrZ in the success state.
+ */
+struct LDKCResult_NoneChannelMonitorUpdateErrZ CResult_NoneChannelMonitorUpdateErrZ_ok(void);
+
+/**
+ * Creates a new CResult_NoneChannelMonitorUpdateErrZ in the error state.
+ */
+struct LDKCResult_NoneChannelMonitorUpdateErrZ CResult_NoneChannelMonitorUpdateErrZ_err(enum LDKChannelMonitorUpdateErr e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_NoneChannelMonitorUpdateErrZ_is_ok(const struct LDKCResult_NoneChannelMonitorUpdateErrZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_NoneChannelMonitorUpdateErrZ.
+ */
+void CResult_NoneChannelMonitorUpdateErrZ_free(struct LDKCResult_NoneChannelMonitorUpdateErrZ _res);
+
+/**
+ * Creates a new CResult_NoneChannelMonitorUpdateErrZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_NoneChannelMonitorUpdateErrZ CResult_NoneChannelMonitorUpdateErrZ_clone(const struct LDKCResult_NoneChannelMonitorUpdateErrZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_MonitorEventZ_free(struct LDKCVec_MonitorEventZ _res);
+
+/**
+ * Constructs a new COption_C2Tuple_usizeTransactionZZ containing a crate::c_types::derived::C2Tuple_usizeTransactionZ
+ */
+struct LDKCOption_C2Tuple_usizeTransactionZZ COption_C2Tuple_usizeTransactionZZ_some(struct LDKC2Tuple_usizeTransactionZ o);
+
+/**
+ * Constructs a new COption_C2Tuple_usizeTransactionZZ containing nothing
+ */
+struct LDKCOption_C2Tuple_usizeTransactionZZ COption_C2Tuple_usizeTransactionZZ_none(void);
+
+/**
+ * Frees any resources associated with the crate::c_types::derived::C2Tuple_usizeTransactionZ, if we are in the Some state
+ */
+void COption_C2Tuple_usizeTransactionZZ_free(struct LDKCOption_C2Tuple_usizeTransactionZZ _res);
+
+/**
+ * Creates a new COption_C2Tuple_usizeTransactionZZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCOption_C2Tuple_usizeTransactionZZ COption_C2Tuple_usizeTransactionZZ_clone(const struct LDKCOption_C2Tuple_usizeTransactionZZ *NONNULL_PTR orig);
+
+/**
+ * Constructs a new COption_ClosureReasonZ containing a crate::lightning::util::events::ClosureReason
+ */
+struct LDKCOption_ClosureReasonZ COption_ClosureReasonZ_some(struct LDKClosureReason o);
+
+/**
+ * Constructs a new COption_ClosureReasonZ containing nothing
+ */
+struct LDKCOption_ClosureReasonZ COption_ClosureReasonZ_none(void);
+
+/**
+ * Frees any resources associated with the crate::lightning::util::events::ClosureReason, if we are in the Some state
+ */
+void COption_ClosureReasonZ_free(struct LDKCOption_ClosureReasonZ _res);
+
+/**
+ * Creates a new COption_ClosureReasonZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCOption_ClosureReasonZ COption_ClosureReasonZ_clone(const struct LDKCOption_ClosureReasonZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_COption_ClosureReasonZDecodeErrorZ in the success state.
+ */
+struct LDKCResult_COption_ClosureReasonZDecodeErrorZ CResult_COption_ClosureReasonZDecodeErrorZ_ok(struct LDKCOption_ClosureReasonZ o);
+
+/**
+ * Creates a new CResult_COption_ClosureReasonZDecodeErrorZ in the error state.
+ */
+struct LDKCResult_COption_ClosureReasonZDecodeErrorZ CResult_COption_ClosureReasonZDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_COption_ClosureReasonZDecodeErrorZ_is_ok(const struct LDKCResult_COption_ClosureReasonZDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_COption_ClosureReasonZDecodeErrorZ.
+ */
+void CResult_COption_ClosureReasonZDecodeErrorZ_free(struct LDKCResult_COption_ClosureReasonZDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_COption_ClosureReasonZDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_COption_ClosureReasonZDecodeErrorZ CResult_COption_ClosureReasonZDecodeErrorZ_clone(const struct LDKCResult_COption_ClosureReasonZDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Constructs a new COption_NetworkUpdateZ containing a crate::lightning::routing::network_graph::NetworkUpdate
+ */
+struct LDKCOption_NetworkUpdateZ COption_NetworkUpdateZ_some(struct LDKNetworkUpdate o);
+
+/**
+ * Constructs a new COption_NetworkUpdateZ containing nothing
+ */
+struct LDKCOption_NetworkUpdateZ COption_NetworkUpdateZ_none(void);
+
+/**
+ * Frees any resources associated with the crate::lightning::routing::network_graph::NetworkUpdate, if we are in the Some state
+ */
+void COption_NetworkUpdateZ_free(struct LDKCOption_NetworkUpdateZ _res);
+
+/**
+ * Creates a new COption_NetworkUpdateZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCOption_NetworkUpdateZ COption_NetworkUpdateZ_clone(const struct LDKCOption_NetworkUpdateZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_SpendableOutputDescriptorZ_free(struct LDKCVec_SpendableOutputDescriptorZ _res);
+
+/**
+ * Constructs a new COption_EventZ containing a crate::lightning::util::events::Event
+ */
+struct LDKCOption_EventZ COption_EventZ_some(struct LDKEvent o);
+
+/**
+ * Constructs a new COption_EventZ containing nothing
+ */
+struct LDKCOption_EventZ COption_EventZ_none(void);
+
+/**
+ * Frees any resources associated with the crate::lightning::util::events::Event, if we are in the Some state
+ */
+void COption_EventZ_free(struct LDKCOption_EventZ _res);
+
+/**
+ * Creates a new COption_EventZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCOption_EventZ COption_EventZ_clone(const struct LDKCOption_EventZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_COption_EventZDecodeErrorZ in the success state.
+ */
+struct LDKCResult_COption_EventZDecodeErrorZ CResult_COption_EventZDecodeErrorZ_ok(struct LDKCOption_EventZ o);
+
+/**
+ * Creates a new CResult_COption_EventZDecodeErrorZ in the error state.
+ */
+struct LDKCResult_COption_EventZDecodeErrorZ CResult_COption_EventZDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_COption_EventZDecodeErrorZ_is_ok(const struct LDKCResult_COption_EventZDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_COption_EventZDecodeErrorZ.
+ */
+void CResult_COption_EventZDecodeErrorZ_free(struct LDKCResult_COption_EventZDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_COption_EventZDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_COption_EventZDecodeErrorZ CResult_COption_EventZDecodeErrorZ_clone(const struct LDKCResult_COption_EventZDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_MessageSendEventZ_free(struct LDKCVec_MessageSendEventZ _res);
+
+/**
+ * Creates a new CResult_ScoringParametersDecodeErrorZ in the success state.
+ */
+struct LDKCResult_ScoringParametersDecodeErrorZ CResult_ScoringParametersDecodeErrorZ_ok(struct LDKScoringParameters o);
+
+/**
+ * Creates a new CResult_ScoringParametersDecodeErrorZ in the error state.
+ */
+struct LDKCResult_ScoringParametersDecodeErrorZ CResult_ScoringParametersDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ScoringParametersDecodeErrorZ_is_ok(const struct LDKCResult_ScoringParametersDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ScoringParametersDecodeErrorZ.
+ */
+void CResult_ScoringParametersDecodeErrorZ_free(struct LDKCResult_ScoringParametersDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_ScorerDecodeErrorZ in the success state.
+ */
+struct LDKCResult_ScorerDecodeErrorZ CResult_ScorerDecodeErrorZ_ok(struct LDKScorer o);
+
+/**
+ * Creates a new CResult_ScorerDecodeErrorZ in the error state.
+ */
+struct LDKCResult_ScorerDecodeErrorZ CResult_ScorerDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ScorerDecodeErrorZ_is_ok(const struct LDKCResult_ScorerDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ScorerDecodeErrorZ.
+ */
+void CResult_ScorerDecodeErrorZ_free(struct LDKCResult_ScorerDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_InitFeaturesDecodeErrorZ in the success state.
+ */
+struct LDKCResult_InitFeaturesDecodeErrorZ CResult_InitFeaturesDecodeErrorZ_ok(struct LDKInitFeatures o);
+
+/**
+ * Creates a new CResult_InitFeaturesDecodeErrorZ in the error state.
+ */
+struct LDKCResult_InitFeaturesDecodeErrorZ CResult_InitFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_InitFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_InitFeaturesDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_InitFeaturesDecodeErrorZ.
+ */
+void CResult_InitFeaturesDecodeErrorZ_free(struct LDKCResult_InitFeaturesDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_ChannelFeaturesDecodeErrorZ in the success state.
+ */
+struct LDKCResult_ChannelFeaturesDecodeErrorZ CResult_ChannelFeaturesDecodeErrorZ_ok(struct LDKChannelFeatures o);
+
+/**
+ * Creates a new CResult_ChannelFeaturesDecodeErrorZ in the error state.
+ */
+struct LDKCResult_ChannelFeaturesDecodeErrorZ CResult_ChannelFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ChannelFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_ChannelFeaturesDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ChannelFeaturesDecodeErrorZ.
+ */
+void CResult_ChannelFeaturesDecodeErrorZ_free(struct LDKCResult_ChannelFeaturesDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_NodeFeaturesDecodeErrorZ in the success state.
+ */
+struct LDKCResult_NodeFeaturesDecodeErrorZ CResult_NodeFeaturesDecodeErrorZ_ok(struct LDKNodeFeatures o);
+
+/**
+ * Creates a new CResult_NodeFeaturesDecodeErrorZ in the error state.
+ */
+struct LDKCResult_NodeFeaturesDecodeErrorZ CResult_NodeFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_NodeFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_NodeFeaturesDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_NodeFeaturesDecodeErrorZ.
+ */
+void CResult_NodeFeaturesDecodeErrorZ_free(struct LDKCResult_NodeFeaturesDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_InvoiceFeaturesDecodeErrorZ in the success state.
+ */
+struct LDKCResult_InvoiceFeaturesDecodeErrorZ CResult_InvoiceFeaturesDecodeErrorZ_ok(struct LDKInvoiceFeatures o);
+
+/**
+ * Creates a new CResult_InvoiceFeaturesDecodeErrorZ in the error state.
+ */
+struct LDKCResult_InvoiceFeaturesDecodeErrorZ CResult_InvoiceFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_InvoiceFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_InvoiceFeaturesDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_InvoiceFeaturesDecodeErrorZ.
+ */
+void CResult_InvoiceFeaturesDecodeErrorZ_free(struct LDKCResult_InvoiceFeaturesDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_ChannelTypeFeaturesDecodeErrorZ in the success state.
+ */
+struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ CResult_ChannelTypeFeaturesDecodeErrorZ_ok(struct LDKChannelTypeFeatures o);
+
+/**
+ * Creates a new CResult_ChannelTypeFeaturesDecodeErrorZ in the error state.
+ */
+struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ CResult_ChannelTypeFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_ChannelTypeFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_ChannelTypeFeaturesDecodeErrorZ.
+ */
+void CResult_ChannelTypeFeaturesDecodeErrorZ_free(struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_DelayedPaymentOutputDescriptorDecodeErrorZ in the success state.
+ */
+struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_ok(struct LDKDelayedPaymentOutputDescriptor o);
+
+/**
+ * Creates a new CResult_DelayedPaymentOutputDescriptorDecodeErrorZ in the error state.
+ */
+struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_is_ok(const struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_DelayedPaymentOutputDescriptorDecodeErrorZ.
+ */
+void CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_free(struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_DelayedPaymentOutputDescriptorDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_clone(const struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_StaticPaymentOutputDescriptorDecodeErrorZ in the success state.
+ */
+struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ CResult_StaticPaymentOutputDescriptorDecodeErrorZ_ok(struct LDKStaticPaymentOutputDescriptor o);
+
+/**
+ * Creates a new CResult_StaticPaymentOutputDescriptorDecodeErrorZ in the error state.
+ */
+struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ CResult_StaticPaymentOutputDescriptorDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_StaticPaymentOutputDescriptorDecodeErrorZ_is_ok(const struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_StaticPaymentOutputDescriptorDecodeErrorZ.
+ */
+void CResult_StaticPaymentOutputDescriptorDecodeErrorZ_free(struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_StaticPaymentOutputDescriptorDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ CResult_StaticPaymentOutputDescriptorDecodeErrorZ_clone(const struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_SpendableOutputDescriptorDecodeErrorZ in the success state.
+ */
+struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ CResult_SpendableOutputDescriptorDecodeErrorZ_ok(struct LDKSpendableOutputDescriptor o);
+
+/**
+ * Creates a new CResult_SpendableOutputDescriptorDecodeErrorZ in the error state.
+ */
+struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ CResult_SpendableOutputDescriptorDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_SpendableOutputDescriptorDecodeErrorZ_is_ok(const struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_SpendableOutputDescriptorDecodeErrorZ.
+ */
+void CResult_SpendableOutputDescriptorDecodeErrorZ_free(struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_SpendableOutputDescriptorDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ CResult_SpendableOutputDescriptorDecodeErrorZ_clone(const struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_NoneNoneZ in the success state.
+ */
+struct LDKCResult_NoneNoneZ CResult_NoneNoneZ_ok(void);
+
+/**
+ * Creates a new CResult_NoneNoneZ in the error state.
+ */
+struct LDKCResult_NoneNoneZ CResult_NoneNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_NoneNoneZ_is_ok(const struct LDKCResult_NoneNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_NoneNoneZ.
+ */
+void CResult_NoneNoneZ_free(struct LDKCResult_NoneNoneZ _res);
+
+/**
+ * Creates a new CResult_NoneNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_NoneNoneZ CResult_NoneNoneZ_clone(const struct LDKCResult_NoneNoneZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKC2Tuple_SignatureCVec_SignatureZZ C2Tuple_SignatureCVec_SignatureZZ_clone(const struct LDKC2Tuple_SignatureCVec_SignatureZZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new C2Tuple_SignatureCVec_SignatureZZ from the contained elements.
+ */
+struct LDKC2Tuple_SignatureCVec_SignatureZZ C2Tuple_SignatureCVec_SignatureZZ_new(struct LDKSignature a, struct LDKCVec_SignatureZ b);
+
+/**
+ * Frees any resources used by the C2Tuple_SignatureCVec_SignatureZZ.
+ */
+void C2Tuple_SignatureCVec_SignatureZZ_free(struct LDKC2Tuple_SignatureCVec_SignatureZZ _res);
+
+/**
+ * Creates a new CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ in the success state.
+ */
+struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_ok(struct LDKC2Tuple_SignatureCVec_SignatureZZ o);
+
+/**
+ * Creates a new CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ in the error state.
+ */
+struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_is_ok(const struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ.
+ */
+void CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_free(struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ _res);
+
+/**
+ * Creates a new CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_clone(const struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_SignatureNoneZ in the success state.
+ */
+struct LDKCResult_SignatureNoneZ CResult_SignatureNoneZ_ok(struct LDKSignature o);
+
+/**
+ * Creates a new CResult_SignatureNoneZ in the error state.
+ */
+struct LDKCResult_SignatureNoneZ CResult_SignatureNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_SignatureNoneZ_is_ok(const struct LDKCResult_SignatureNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_SignatureNoneZ.
+ */
+void CResult_SignatureNoneZ_free(struct LDKCResult_SignatureNoneZ _res);
+
+/**
+ * Creates a new CResult_SignatureNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_SignatureNoneZ CResult_SignatureNoneZ_clone(const struct LDKCResult_SignatureNoneZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_SignDecodeErrorZ in the success state.
+ */
+struct LDKCResult_SignDecodeErrorZ CResult_SignDecodeErrorZ_ok(struct LDKSign o);
+
+/**
+ * Creates a new CResult_SignDecodeErrorZ in the error state.
+ */
+struct LDKCResult_SignDecodeErrorZ CResult_SignDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_SignDecodeErrorZ_is_ok(const struct LDKCResult_SignDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_SignDecodeErrorZ.
+ */
+void CResult_SignDecodeErrorZ_free(struct LDKCResult_SignDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_SignDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_SignDecodeErrorZ CResult_SignDecodeErrorZ_clone(const struct LDKCResult_SignDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_u8Z_free(struct LDKCVec_u8Z _res);
+
+/**
+ * Creates a new CResult_RecoverableSignatureNoneZ in the success state.
+ */
+struct LDKCResult_RecoverableSignatureNoneZ CResult_RecoverableSignatureNoneZ_ok(struct LDKRecoverableSignature o);
+
+/**
+ * Creates a new CResult_RecoverableSignatureNoneZ in the error state.
+ */
+struct LDKCResult_RecoverableSignatureNoneZ CResult_RecoverableSignatureNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_RecoverableSignatureNoneZ_is_ok(const struct LDKCResult_RecoverableSignatureNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_RecoverableSignatureNoneZ.
+ */
+void CResult_RecoverableSignatureNoneZ_free(struct LDKCResult_RecoverableSignatureNoneZ _res);
+
+/**
+ * Creates a new CResult_RecoverableSignatureNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_RecoverableSignatureNoneZ CResult_RecoverableSignatureNoneZ_clone(const struct LDKCResult_RecoverableSignatureNoneZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_CVec_u8ZZ_free(struct LDKCVec_CVec_u8ZZ _res);
+
+/**
+ * Creates a new CResult_CVec_CVec_u8ZZNoneZ in the success state.
+ */
+struct LDKCResult_CVec_CVec_u8ZZNoneZ CResult_CVec_CVec_u8ZZNoneZ_ok(struct LDKCVec_CVec_u8ZZ o);
+
+/**
+ * Creates a new CResult_CVec_CVec_u8ZZNoneZ in the error state.
+ */
+struct LDKCResult_CVec_CVec_u8ZZNoneZ CResult_CVec_CVec_u8ZZNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_CVec_CVec_u8ZZNoneZ_is_ok(const struct LDKCResult_CVec_CVec_u8ZZNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_CVec_CVec_u8ZZNoneZ.
+ */
+void CResult_CVec_CVec_u8ZZNoneZ_free(struct LDKCResult_CVec_CVec_u8ZZNoneZ _res);
+
+/**
+ * Creates a new CResult_CVec_CVec_u8ZZNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_CVec_CVec_u8ZZNoneZ CResult_CVec_CVec_u8ZZNoneZ_clone(const struct LDKCResult_CVec_CVec_u8ZZNoneZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_InMemorySignerDecodeErrorZ in the success state.
+ */
+struct LDKCResult_InMemorySignerDecodeErrorZ CResult_InMemorySignerDecodeErrorZ_ok(struct LDKInMemorySigner o);
+
+/**
+ * Creates a new CResult_InMemorySignerDecodeErrorZ in the error state.
+ */
+struct LDKCResult_InMemorySignerDecodeErrorZ CResult_InMemorySignerDecodeErrorZ_err(struct LDKDecodeError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_InMemorySignerDecodeErrorZ_is_ok(const struct LDKCResult_InMemorySignerDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_InMemorySignerDecodeErrorZ.
+ */
+void CResult_InMemorySignerDecodeErrorZ_free(struct LDKCResult_InMemorySignerDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_InMemorySignerDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_InMemorySignerDecodeErrorZ CResult_InMemorySignerDecodeErrorZ_clone(const struct LDKCResult_InMemorySignerDecodeErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_TxOutZ_free(struct LDKCVec_TxOutZ _res);
+
+/**
+ * Creates a new CResult_TransactionNoneZ in the success state.
+ */
+struct LDKCResult_TransactionNoneZ CResult_TransactionNoneZ_ok(struct LDKTransaction o);
+
+/**
+ * Creates a new CResult_TransactionNoneZ in the error state.
+ */
+struct LDKCResult_TransactionNoneZ CResult_TransactionNoneZ_err(void);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_TransactionNoneZ_is_ok(const struct LDKCResult_TransactionNoneZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_TransactionNoneZ.
+ */
+void CResult_TransactionNoneZ_free(struct LDKCResult_TransactionNoneZ _res);
+
+/**
+ * Creates a new CResult_TransactionNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_TransactionNoneZ CResult_TransactionNoneZ_clone(const struct LDKCResult_TransactionNoneZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new C2Tuple_BlockHashChannelMonitorZ from the contained elements.
+ */
+struct LDKC2Tuple_BlockHashChannelMonitorZ C2Tuple_BlockHashChannelMonitorZ_new(struct LDKThirtyTwoBytes a, struct LDKChannelMonitor b);
+
+/**
+ * Frees any resources used by the C2Tuple_BlockHashChannelMonitorZ.
+ */
+void C2Tuple_BlockHashChannelMonitorZ_free(struct LDKC2Tuple_BlockHashChannelMonitorZ _res);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_C2Tuple_BlockHashChannelMonitorZZ_free(struct LDKCVec_C2Tuple_BlockHashChannelMonitorZZ _res);
+
+/**
+ * Creates a new CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ in the success state.
+ */
+struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_ok(struct LDKCVec_C2Tuple_BlockHashChannelMonitorZZ o);
+
+/**
+ * Creates a new CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ in the error state.
+ */
+struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_err(enum LDKIOError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_is_ok(const struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ.
+ */
+void CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_free(struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ _res);
+
+/**
+ * Constructs a new COption_u16Z containing a u16
+ */
+struct LDKCOption_u16Z COption_u16Z_some(uint16_t o);
+
+/**
+ * Constructs a new COption_u16Z containing nothing
+ */
+struct LDKCOption_u16Z COption_u16Z_none(void);
+
+/**
+ * Frees any resources associated with the u16, if we are in the Some state
+ */
+void COption_u16Z_free(struct LDKCOption_u16Z _res);
+
+/**
+ * Creates a new COption_u16Z which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCOption_u16Z COption_u16Z_clone(const struct LDKCOption_u16Z *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_NoneAPIErrorZ in the success state.
+ */
+struct LDKCResult_NoneAPIErrorZ CResult_NoneAPIErrorZ_ok(void);
+
+/**
+ * Creates a new CResult_NoneAPIErrorZ in the error state.
+ */
+struct LDKCResult_NoneAPIErrorZ CResult_NoneAPIErrorZ_err(struct LDKAPIError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_NoneAPIErrorZ_is_ok(const struct LDKCResult_NoneAPIErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_NoneAPIErrorZ.
+ */
+void CResult_NoneAPIErrorZ_free(struct LDKCResult_NoneAPIErrorZ _res);
+
+/**
+ * Creates a new CResult_NoneAPIErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_NoneAPIErrorZ CResult_NoneAPIErrorZ_clone(const struct LDKCResult_NoneAPIErrorZ *NONNULL_PTR orig);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_CResult_NoneAPIErrorZZ_free(struct LDKCVec_CResult_NoneAPIErrorZZ _res);
+
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_APIErrorZ_free(struct LDKCVec_APIErrorZ _res);
+
+/**
+ * Creates a new CResult__u832APIErrorZ in the success state.
+ */
+struct LDKCResult__u832APIErrorZ CResult__u832APIErrorZ_ok(struct LDKThirtyTwoBytes o);
+
+/**
+ * Creates a new CResult__u832APIErrorZ in the error state.
+ */
+struct LDKCResult__u832APIErrorZ CResult__u832APIErrorZ_err(struct LDKAPIError e);
+
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult__u832APIErrorZ_is_ok(const struct LDKCResult__u832APIErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult__u832APIErrorZ.
+ */
+void CResult__u832APIErrorZ_free(struct LDKCResult__u832APIErrorZ _res);
+
+/**
+ * Creates a new CResult__u832APIErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult__u832APIErrorZ CResult__u832APIErrorZ_clone(const struct LDKCResult__u832APIErrorZ *NONNULL_PTR orig);
+
+/**
+ * Creates a new CResult_PaymentIdPaymentSendFailureZ in the success state.
+ */
+struct LDKCResult_PaymentIdPaymentSendFailureZ CResult_PaymentIdPaymentSendFailureZ_ok(struct LDKThirtyTwoBytes o);
+
+/**
+ * Creates a new CResult_PaymentIdPaymentSendFailureZ in the error state.
+ */
+struct LDKCResult_PaymentIdPaymentSendFailureZ CResult_PaymentIdPaymentSendFailureZ_err(struct LDKPaymentSendFailure e);
 
 /**
- * A trait defining behavior for routing an [`Invoice`] payment.
+ * Checks if the given object is currently in the success state
  */
-typedef struct LDKRouter {
-   /**
-    * An opaque pointer which is passed to your function implementations as an argument.
-    * This has no meaning in the LDK, and can be NULL or any other value.
-    */
-   void *this_arg;
-   /**
-    * Finds a [`Route`] between `payer` and `payee` for a payment with the given values.
-    *
-    * Note that first_hops (or a relevant inner pointer) may be NULL or all-0s to represent None
-    */
-   struct LDKCResult_RouteLightningErrorZ (*find_route)(const void *this_arg, struct LDKPublicKey payer, const struct LDKRouteParameters *NONNULL_PTR params, const uint8_t (*payment_hash)[32], struct LDKCVec_ChannelDetailsZ *first_hops, const struct LDKScore *NONNULL_PTR scorer);
-   /**
-    * Frees any resources associated with this object given its this_arg pointer.
-    * Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
-    */
-   void (*free)(void *this_arg);
-} LDKRouter;
+bool CResult_PaymentIdPaymentSendFailureZ_is_ok(const struct LDKCResult_PaymentIdPaymentSendFailureZ *NONNULL_PTR o);
 
+/**
+ * Frees any resources used by the CResult_PaymentIdPaymentSendFailureZ.
+ */
+void CResult_PaymentIdPaymentSendFailureZ_free(struct LDKCResult_PaymentIdPaymentSendFailureZ _res);
 
+/**
+ * Creates a new CResult_PaymentIdPaymentSendFailureZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_PaymentIdPaymentSendFailureZ CResult_PaymentIdPaymentSendFailureZ_clone(const struct LDKCResult_PaymentIdPaymentSendFailureZ *NONNULL_PTR orig);
 
 /**
- * A utility for paying [`Invoice`]s and sending spontaneous payments.
- *
- * See [module-level documentation] for details.
- *
- * [module-level documentation]: crate::payment
+ * Creates a new CResult_NonePaymentSendFailureZ in the success state.
  */
-typedef struct MUST_USE_STRUCT LDKInvoicePayer {
-   /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
-    */
-   LDKnativeInvoicePayer *inner;
-   /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
-    */
-   bool is_owned;
-} LDKInvoicePayer;
+struct LDKCResult_NonePaymentSendFailureZ CResult_NonePaymentSendFailureZ_ok(void);
 
+/**
+ * Creates a new CResult_NonePaymentSendFailureZ in the error state.
+ */
+struct LDKCResult_NonePaymentSendFailureZ CResult_NonePaymentSendFailureZ_err(struct LDKPaymentSendFailure e);
 
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_NonePaymentSendFailureZ_is_ok(const struct LDKCResult_NonePaymentSendFailureZ *NONNULL_PTR o);
 
 /**
- * Number of attempts to retry payment path failures for an [`Invoice`].
- *
- * Note that this is the number of *path* failures, not full payment retries. For multi-path
- * payments, if this is less than the total number of paths, we will never even retry all of the
- * payment's paths.
+ * Frees any resources used by the CResult_NonePaymentSendFailureZ.
  */
-typedef struct MUST_USE_STRUCT LDKRetryAttempts {
-   /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
-    */
-   LDKnativeRetryAttempts *inner;
-   /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
-    */
-   bool is_owned;
-} LDKRetryAttempts;
+void CResult_NonePaymentSendFailureZ_free(struct LDKCResult_NonePaymentSendFailureZ _res);
 
+/**
+ * Creates a new CResult_NonePaymentSendFailureZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_NonePaymentSendFailureZ CResult_NonePaymentSendFailureZ_clone(const struct LDKCResult_NonePaymentSendFailureZ *NONNULL_PTR orig);
 
+/**
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKC2Tuple_PaymentHashPaymentIdZ C2Tuple_PaymentHashPaymentIdZ_clone(const struct LDKC2Tuple_PaymentHashPaymentIdZ *NONNULL_PTR orig);
 
 /**
- * A [`Router`] implemented using [`find_route`].
+ * Creates a new C2Tuple_PaymentHashPaymentIdZ from the contained elements.
  */
-typedef struct MUST_USE_STRUCT LDKDefaultRouter {
-   /**
-    * A pointer to the opaque Rust object.
-    * Nearly everywhere, inner must be non-null, however in places where
-    * the Rust equivalent takes an Option, it may be set to null to indicate None.
-    */
-   LDKnativeDefaultRouter *inner;
-   /**
-    * Indicates that this is the only struct which contains the same pointer.
-    * Rust functions which take ownership of an object provided via an argument require
-    * this to be true and invalidate the object pointed to by inner.
-    */
-   bool is_owned;
-} LDKDefaultRouter;
+struct LDKC2Tuple_PaymentHashPaymentIdZ C2Tuple_PaymentHashPaymentIdZ_new(struct LDKThirtyTwoBytes a, struct LDKThirtyTwoBytes b);
 
-extern const uintptr_t MAX_BUF_SIZE;
+/**
+ * Frees any resources used by the C2Tuple_PaymentHashPaymentIdZ.
+ */
+void C2Tuple_PaymentHashPaymentIdZ_free(struct LDKC2Tuple_PaymentHashPaymentIdZ _res);
 
-extern const uint64_t MIN_RELAY_FEE_SAT_PER_1000_WEIGHT;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ in the success state.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_ok(struct LDKC2Tuple_PaymentHashPaymentIdZ o);
 
-extern const uint64_t CLOSED_CHANNEL_UPDATE_ID;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ in the error state.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_err(struct LDKPaymentSendFailure e);
 
-extern const uint32_t ANTI_REORG_DELAY;
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_is_ok(const struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ *NONNULL_PTR o);
 
-extern const uint16_t BREAKDOWN_TIMEOUT;
+/**
+ * Frees any resources used by the CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ.
+ */
+void CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_free(struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ _res);
 
-extern const uint16_t MIN_CLTV_EXPIRY_DELTA;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_clone(const struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ *NONNULL_PTR orig);
 
-extern const uint32_t MIN_FINAL_CLTV_EXPIRY;
+/**
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ */
+void CVec_NetAddressZ_free(struct LDKCVec_NetAddressZ _res);
 
-extern const uintptr_t REVOKEABLE_REDEEMSCRIPT_MAX_LENGTH;
+/**
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKC2Tuple_PaymentHashPaymentSecretZ C2Tuple_PaymentHashPaymentSecretZ_clone(const struct LDKC2Tuple_PaymentHashPaymentSecretZ *NONNULL_PTR orig);
 
-extern const uint64_t DEFAULT_EXPIRY_TIME;
+/**
+ * Creates a new C2Tuple_PaymentHashPaymentSecretZ from the contained elements.
+ */
+struct LDKC2Tuple_PaymentHashPaymentSecretZ C2Tuple_PaymentHashPaymentSecretZ_new(struct LDKThirtyTwoBytes a, struct LDKThirtyTwoBytes b);
 
-extern const uint64_t DEFAULT_MIN_FINAL_CLTV_EXPIRY;
+/**
+ * Frees any resources used by the C2Tuple_PaymentHashPaymentSecretZ.
+ */
+void C2Tuple_PaymentHashPaymentSecretZ_free(struct LDKC2Tuple_PaymentHashPaymentSecretZ _res);
 
-extern const uint8_t TAG_PAYMENT_HASH;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ in the success state.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_ok(struct LDKC2Tuple_PaymentHashPaymentSecretZ o);
 
-extern const uint8_t TAG_DESCRIPTION;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ in the error state.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_err(void);
 
-extern const uint8_t TAG_PAYEE_PUB_KEY;
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_is_ok(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ *NONNULL_PTR o);
 
-extern const uint8_t TAG_DESCRIPTION_HASH;
+/**
+ * Frees any resources used by the CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ.
+ */
+void CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_free(struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ _res);
 
-extern const uint8_t TAG_EXPIRY_TIME;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_clone(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ *NONNULL_PTR orig);
 
-extern const uint8_t TAG_MIN_FINAL_CLTV_EXPIRY;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ in the success state.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_ok(struct LDKC2Tuple_PaymentHashPaymentSecretZ o);
 
-extern const uint8_t TAG_FALLBACK;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ in the error state.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_err(struct LDKAPIError e);
 
-extern const uint8_t TAG_PRIVATE_ROUTE;
+/**
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_is_ok(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ *NONNULL_PTR o);
 
-extern const uint8_t TAG_PAYMENT_SECRET;
+/**
+ * Frees any resources used by the CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ.
+ */
+void CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_free(struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ _res);
 
-extern const uint8_t TAG_FEATURES;
+/**
+ * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
+ */
+struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_clone(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ *NONNULL_PTR orig);
 
-struct LDKStr _ldk_get_compiled_version(void);
+/**
+ * Creates a new CResult_PaymentSecretNoneZ in the success state.
+ */
+struct LDKCResult_PaymentSecretNoneZ CResult_PaymentSecretNoneZ_ok(struct LDKThirtyTwoBytes o);
 
-struct LDKStr _ldk_c_bindings_get_compiled_version(void);
+/**
+ * Creates a new CResult_PaymentSecretNoneZ in the error state.
+ */
+struct LDKCResult_PaymentSecretNoneZ CResult_PaymentSecretNoneZ_err(void);
 
 /**
- * Frees the data buffer, if data_is_owned is set and datalen > 0.
+ * Checks if the given object is currently in the success state
  */
-void Transaction_free(struct LDKTransaction _res);
+bool CResult_PaymentSecretNoneZ_is_ok(const struct LDKCResult_PaymentSecretNoneZ *NONNULL_PTR o);
 
 /**
- * Convenience function for constructing a new TxOut
+ * Frees any resources used by the CResult_PaymentSecretNoneZ.
  */
-struct LDKTxOut TxOut_new(struct LDKCVec_u8Z script_pubkey, uint64_t value);
+void CResult_PaymentSecretNoneZ_free(struct LDKCResult_PaymentSecretNoneZ _res);
 
 /**
- * Frees the data pointed to by script_pubkey.
+ * Creates a new CResult_PaymentSecretNoneZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void TxOut_free(struct LDKTxOut _res);
+struct LDKCResult_PaymentSecretNoneZ CResult_PaymentSecretNoneZ_clone(const struct LDKCResult_PaymentSecretNoneZ *NONNULL_PTR orig);
 
 /**
- * Creates a new TxOut which has the same data as `orig` but with a new script buffer.
+ * Creates a new CResult_PaymentSecretAPIErrorZ in the success state.
  */
-struct LDKTxOut TxOut_clone(const struct LDKTxOut *NONNULL_PTR orig);
+struct LDKCResult_PaymentSecretAPIErrorZ CResult_PaymentSecretAPIErrorZ_ok(struct LDKThirtyTwoBytes o);
 
 /**
- * Frees the data buffer, if chars_is_owned is set and len > 0.
+ * Creates a new CResult_PaymentSecretAPIErrorZ in the error state.
  */
-void Str_free(struct LDKStr _res);
+struct LDKCResult_PaymentSecretAPIErrorZ CResult_PaymentSecretAPIErrorZ_err(struct LDKAPIError e);
 
-#if defined(LDK_DEBUG_BUILD)
 /**
- * This function exists for memory safety testing purposes. It should never be used in production
- * code
+ * Checks if the given object is currently in the success state
  */
-const void *__unmangle_inner_ptr(const void *ptr);
-#endif
+bool CResult_PaymentSecretAPIErrorZ_is_ok(const struct LDKCResult_PaymentSecretAPIErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_SecretKeyErrorZ in the success state.
+ * Frees any resources used by the CResult_PaymentSecretAPIErrorZ.
  */
-struct LDKCResult_SecretKeyErrorZ CResult_SecretKeyErrorZ_ok(struct LDKSecretKey o);
+void CResult_PaymentSecretAPIErrorZ_free(struct LDKCResult_PaymentSecretAPIErrorZ _res);
 
 /**
- * Creates a new CResult_SecretKeyErrorZ in the error state.
+ * Creates a new CResult_PaymentSecretAPIErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_SecretKeyErrorZ CResult_SecretKeyErrorZ_err(enum LDKSecp256k1Error e);
+struct LDKCResult_PaymentSecretAPIErrorZ CResult_PaymentSecretAPIErrorZ_clone(const struct LDKCResult_PaymentSecretAPIErrorZ *NONNULL_PTR orig);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_PaymentPreimageAPIErrorZ in the success state.
  */
-bool CResult_SecretKeyErrorZ_is_ok(const struct LDKCResult_SecretKeyErrorZ *NONNULL_PTR o);
+struct LDKCResult_PaymentPreimageAPIErrorZ CResult_PaymentPreimageAPIErrorZ_ok(struct LDKThirtyTwoBytes o);
 
 /**
- * Frees any resources used by the CResult_SecretKeyErrorZ.
+ * Creates a new CResult_PaymentPreimageAPIErrorZ in the error state.
  */
-void CResult_SecretKeyErrorZ_free(struct LDKCResult_SecretKeyErrorZ _res);
+struct LDKCResult_PaymentPreimageAPIErrorZ CResult_PaymentPreimageAPIErrorZ_err(struct LDKAPIError e);
 
 /**
- * Creates a new CResult_PublicKeyErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_PublicKeyErrorZ CResult_PublicKeyErrorZ_ok(struct LDKPublicKey o);
+bool CResult_PaymentPreimageAPIErrorZ_is_ok(const struct LDKCResult_PaymentPreimageAPIErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_PublicKeyErrorZ in the error state.
+ * Frees any resources used by the CResult_PaymentPreimageAPIErrorZ.
  */
-struct LDKCResult_PublicKeyErrorZ CResult_PublicKeyErrorZ_err(enum LDKSecp256k1Error e);
+void CResult_PaymentPreimageAPIErrorZ_free(struct LDKCResult_PaymentPreimageAPIErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_PaymentPreimageAPIErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-bool CResult_PublicKeyErrorZ_is_ok(const struct LDKCResult_PublicKeyErrorZ *NONNULL_PTR o);
+struct LDKCResult_PaymentPreimageAPIErrorZ CResult_PaymentPreimageAPIErrorZ_clone(const struct LDKCResult_PaymentPreimageAPIErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the CResult_PublicKeyErrorZ.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void CResult_PublicKeyErrorZ_free(struct LDKCResult_PublicKeyErrorZ _res);
+void CVec_ChannelMonitorZ_free(struct LDKCVec_ChannelMonitorZ _res);
 
 /**
- * Creates a new CResult_PublicKeyErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new C2Tuple_BlockHashChannelManagerZ from the contained elements.
  */
-struct LDKCResult_PublicKeyErrorZ CResult_PublicKeyErrorZ_clone(const struct LDKCResult_PublicKeyErrorZ *NONNULL_PTR orig);
+struct LDKC2Tuple_BlockHashChannelManagerZ C2Tuple_BlockHashChannelManagerZ_new(struct LDKThirtyTwoBytes a, struct LDKChannelManager b);
 
 /**
- * Creates a new CResult_TxCreationKeysDecodeErrorZ in the success state.
+ * Frees any resources used by the C2Tuple_BlockHashChannelManagerZ.
  */
-struct LDKCResult_TxCreationKeysDecodeErrorZ CResult_TxCreationKeysDecodeErrorZ_ok(struct LDKTxCreationKeys o);
+void C2Tuple_BlockHashChannelManagerZ_free(struct LDKC2Tuple_BlockHashChannelManagerZ _res);
 
 /**
- * Creates a new CResult_TxCreationKeysDecodeErrorZ in the error state.
+ * Creates a new CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ in the success state.
  */
-struct LDKCResult_TxCreationKeysDecodeErrorZ CResult_TxCreationKeysDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_ok(struct LDKC2Tuple_BlockHashChannelManagerZ o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ in the error state.
  */
-bool CResult_TxCreationKeysDecodeErrorZ_is_ok(const struct LDKCResult_TxCreationKeysDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Frees any resources used by the CResult_TxCreationKeysDecodeErrorZ.
+ * Checks if the given object is currently in the success state
  */
-void CResult_TxCreationKeysDecodeErrorZ_free(struct LDKCResult_TxCreationKeysDecodeErrorZ _res);
+bool CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_is_ok(const struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_TxCreationKeysDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ.
  */
-struct LDKCResult_TxCreationKeysDecodeErrorZ CResult_TxCreationKeysDecodeErrorZ_clone(const struct LDKCResult_TxCreationKeysDecodeErrorZ *NONNULL_PTR orig);
+void CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_free(struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_ChannelPublicKeysDecodeErrorZ in the success state.
+ * Creates a new CResult_ChannelConfigDecodeErrorZ in the success state.
  */
-struct LDKCResult_ChannelPublicKeysDecodeErrorZ CResult_ChannelPublicKeysDecodeErrorZ_ok(struct LDKChannelPublicKeys o);
+struct LDKCResult_ChannelConfigDecodeErrorZ CResult_ChannelConfigDecodeErrorZ_ok(struct LDKChannelConfig o);
 
 /**
- * Creates a new CResult_ChannelPublicKeysDecodeErrorZ in the error state.
+ * Creates a new CResult_ChannelConfigDecodeErrorZ in the error state.
  */
-struct LDKCResult_ChannelPublicKeysDecodeErrorZ CResult_ChannelPublicKeysDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_ChannelConfigDecodeErrorZ CResult_ChannelConfigDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_ChannelPublicKeysDecodeErrorZ_is_ok(const struct LDKCResult_ChannelPublicKeysDecodeErrorZ *NONNULL_PTR o);
+bool CResult_ChannelConfigDecodeErrorZ_is_ok(const struct LDKCResult_ChannelConfigDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_ChannelPublicKeysDecodeErrorZ.
+ * Frees any resources used by the CResult_ChannelConfigDecodeErrorZ.
  */
-void CResult_ChannelPublicKeysDecodeErrorZ_free(struct LDKCResult_ChannelPublicKeysDecodeErrorZ _res);
+void CResult_ChannelConfigDecodeErrorZ_free(struct LDKCResult_ChannelConfigDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_ChannelPublicKeysDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_ChannelConfigDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_ChannelPublicKeysDecodeErrorZ CResult_ChannelPublicKeysDecodeErrorZ_clone(const struct LDKCResult_ChannelPublicKeysDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_ChannelConfigDecodeErrorZ CResult_ChannelConfigDecodeErrorZ_clone(const struct LDKCResult_ChannelConfigDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_TxCreationKeysErrorZ in the success state.
+ * Creates a new CResult_OutPointDecodeErrorZ in the success state.
  */
-struct LDKCResult_TxCreationKeysErrorZ CResult_TxCreationKeysErrorZ_ok(struct LDKTxCreationKeys o);
+struct LDKCResult_OutPointDecodeErrorZ CResult_OutPointDecodeErrorZ_ok(struct LDKOutPoint o);
 
 /**
- * Creates a new CResult_TxCreationKeysErrorZ in the error state.
+ * Creates a new CResult_OutPointDecodeErrorZ in the error state.
  */
-struct LDKCResult_TxCreationKeysErrorZ CResult_TxCreationKeysErrorZ_err(enum LDKSecp256k1Error e);
+struct LDKCResult_OutPointDecodeErrorZ CResult_OutPointDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_TxCreationKeysErrorZ_is_ok(const struct LDKCResult_TxCreationKeysErrorZ *NONNULL_PTR o);
+bool CResult_OutPointDecodeErrorZ_is_ok(const struct LDKCResult_OutPointDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_TxCreationKeysErrorZ.
+ * Frees any resources used by the CResult_OutPointDecodeErrorZ.
  */
-void CResult_TxCreationKeysErrorZ_free(struct LDKCResult_TxCreationKeysErrorZ _res);
+void CResult_OutPointDecodeErrorZ_free(struct LDKCResult_OutPointDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_TxCreationKeysErrorZ which has the same data as `orig`
+ * Creates a new CResult_OutPointDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_TxCreationKeysErrorZ CResult_TxCreationKeysErrorZ_clone(const struct LDKCResult_TxCreationKeysErrorZ *NONNULL_PTR orig);
+struct LDKCResult_OutPointDecodeErrorZ CResult_OutPointDecodeErrorZ_clone(const struct LDKCResult_OutPointDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Constructs a new COption_u32Z containing a u32
+ * Constructs a new COption_TypeZ containing a crate::lightning::ln::wire::Type
  */
-struct LDKCOption_u32Z COption_u32Z_some(uint32_t o);
+struct LDKCOption_TypeZ COption_TypeZ_some(struct LDKType o);
 
 /**
- * Constructs a new COption_u32Z containing nothing
+ * Constructs a new COption_TypeZ containing nothing
  */
-struct LDKCOption_u32Z COption_u32Z_none(void);
+struct LDKCOption_TypeZ COption_TypeZ_none(void);
 
 /**
- * Frees any resources associated with the u32, if we are in the Some state
+ * Frees any resources associated with the crate::lightning::ln::wire::Type, if we are in the Some state
  */
-void COption_u32Z_free(struct LDKCOption_u32Z _res);
+void COption_TypeZ_free(struct LDKCOption_TypeZ _res);
 
 /**
- * Creates a new COption_u32Z which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_COption_TypeZDecodeErrorZ in the success state.
  */
-struct LDKCOption_u32Z COption_u32Z_clone(const struct LDKCOption_u32Z *NONNULL_PTR orig);
+struct LDKCResult_COption_TypeZDecodeErrorZ CResult_COption_TypeZDecodeErrorZ_ok(struct LDKCOption_TypeZ o);
 
 /**
- * Creates a new CResult_HTLCOutputInCommitmentDecodeErrorZ in the success state.
+ * Creates a new CResult_COption_TypeZDecodeErrorZ in the error state.
  */
-struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ CResult_HTLCOutputInCommitmentDecodeErrorZ_ok(struct LDKHTLCOutputInCommitment o);
+struct LDKCResult_COption_TypeZDecodeErrorZ CResult_COption_TypeZDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_HTLCOutputInCommitmentDecodeErrorZ in the error state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ CResult_HTLCOutputInCommitmentDecodeErrorZ_err(struct LDKDecodeError e);
+bool CResult_COption_TypeZDecodeErrorZ_is_ok(const struct LDKCResult_COption_TypeZDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the CResult_COption_TypeZDecodeErrorZ.
  */
-bool CResult_HTLCOutputInCommitmentDecodeErrorZ_is_ok(const struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ *NONNULL_PTR o);
+void CResult_COption_TypeZDecodeErrorZ_free(struct LDKCResult_COption_TypeZDecodeErrorZ _res);
 
 /**
- * Frees any resources used by the CResult_HTLCOutputInCommitmentDecodeErrorZ.
+ * Creates a new CResult_PaymentIdPaymentErrorZ in the success state.
  */
-void CResult_HTLCOutputInCommitmentDecodeErrorZ_free(struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ _res);
+struct LDKCResult_PaymentIdPaymentErrorZ CResult_PaymentIdPaymentErrorZ_ok(struct LDKThirtyTwoBytes o);
 
 /**
- * Creates a new CResult_HTLCOutputInCommitmentDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_PaymentIdPaymentErrorZ in the error state.
  */
-struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ CResult_HTLCOutputInCommitmentDecodeErrorZ_clone(const struct LDKCResult_HTLCOutputInCommitmentDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_PaymentIdPaymentErrorZ CResult_PaymentIdPaymentErrorZ_err(struct LDKPaymentError e);
 
 /**
- * Constructs a new COption_NoneZ containing a
+ * Checks if the given object is currently in the success state
  */
-enum LDKCOption_NoneZ COption_NoneZ_some(void);
+bool CResult_PaymentIdPaymentErrorZ_is_ok(const struct LDKCResult_PaymentIdPaymentErrorZ *NONNULL_PTR o);
 
 /**
- * Constructs a new COption_NoneZ containing nothing
+ * Frees any resources used by the CResult_PaymentIdPaymentErrorZ.
  */
-enum LDKCOption_NoneZ COption_NoneZ_none(void);
+void CResult_PaymentIdPaymentErrorZ_free(struct LDKCResult_PaymentIdPaymentErrorZ _res);
 
 /**
- * Frees any resources associated with the , if we are in the Some state
+ * Creates a new CResult_PaymentIdPaymentErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void COption_NoneZ_free(enum LDKCOption_NoneZ _res);
+struct LDKCResult_PaymentIdPaymentErrorZ CResult_PaymentIdPaymentErrorZ_clone(const struct LDKCResult_PaymentIdPaymentErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_CounterpartyChannelTransactionParametersDecodeErrorZ in the success state.
+ * Creates a new CResult_SiPrefixNoneZ in the success state.
  */
-struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_ok(struct LDKCounterpartyChannelTransactionParameters o);
+struct LDKCResult_SiPrefixNoneZ CResult_SiPrefixNoneZ_ok(enum LDKSiPrefix o);
 
 /**
- * Creates a new CResult_CounterpartyChannelTransactionParametersDecodeErrorZ in the error state.
+ * Creates a new CResult_SiPrefixNoneZ in the error state.
  */
-struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_SiPrefixNoneZ CResult_SiPrefixNoneZ_err(void);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_is_ok(const struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ *NONNULL_PTR o);
+bool CResult_SiPrefixNoneZ_is_ok(const struct LDKCResult_SiPrefixNoneZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_CounterpartyChannelTransactionParametersDecodeErrorZ.
+ * Frees any resources used by the CResult_SiPrefixNoneZ.
  */
-void CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_free(struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ _res);
+void CResult_SiPrefixNoneZ_free(struct LDKCResult_SiPrefixNoneZ _res);
 
 /**
- * Creates a new CResult_CounterpartyChannelTransactionParametersDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_SiPrefixNoneZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ CResult_CounterpartyChannelTransactionParametersDecodeErrorZ_clone(const struct LDKCResult_CounterpartyChannelTransactionParametersDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_SiPrefixNoneZ CResult_SiPrefixNoneZ_clone(const struct LDKCResult_SiPrefixNoneZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_ChannelTransactionParametersDecodeErrorZ in the success state.
+ * Creates a new CResult_InvoiceNoneZ in the success state.
  */
-struct LDKCResult_ChannelTransactionParametersDecodeErrorZ CResult_ChannelTransactionParametersDecodeErrorZ_ok(struct LDKChannelTransactionParameters o);
+struct LDKCResult_InvoiceNoneZ CResult_InvoiceNoneZ_ok(struct LDKInvoice o);
 
 /**
- * Creates a new CResult_ChannelTransactionParametersDecodeErrorZ in the error state.
+ * Creates a new CResult_InvoiceNoneZ in the error state.
  */
-struct LDKCResult_ChannelTransactionParametersDecodeErrorZ CResult_ChannelTransactionParametersDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_InvoiceNoneZ CResult_InvoiceNoneZ_err(void);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_ChannelTransactionParametersDecodeErrorZ_is_ok(const struct LDKCResult_ChannelTransactionParametersDecodeErrorZ *NONNULL_PTR o);
+bool CResult_InvoiceNoneZ_is_ok(const struct LDKCResult_InvoiceNoneZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_ChannelTransactionParametersDecodeErrorZ.
+ * Frees any resources used by the CResult_InvoiceNoneZ.
  */
-void CResult_ChannelTransactionParametersDecodeErrorZ_free(struct LDKCResult_ChannelTransactionParametersDecodeErrorZ _res);
+void CResult_InvoiceNoneZ_free(struct LDKCResult_InvoiceNoneZ _res);
 
 /**
- * Creates a new CResult_ChannelTransactionParametersDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_InvoiceNoneZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_ChannelTransactionParametersDecodeErrorZ CResult_ChannelTransactionParametersDecodeErrorZ_clone(const struct LDKCResult_ChannelTransactionParametersDecodeErrorZ *NONNULL_PTR orig);
-
-/**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
- */
-void CVec_SignatureZ_free(struct LDKCVec_SignatureZ _res);
+struct LDKCResult_InvoiceNoneZ CResult_InvoiceNoneZ_clone(const struct LDKCResult_InvoiceNoneZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_HolderCommitmentTransactionDecodeErrorZ in the success state.
+ * Creates a new CResult_SignedRawInvoiceNoneZ in the success state.
  */
-struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ CResult_HolderCommitmentTransactionDecodeErrorZ_ok(struct LDKHolderCommitmentTransaction o);
+struct LDKCResult_SignedRawInvoiceNoneZ CResult_SignedRawInvoiceNoneZ_ok(struct LDKSignedRawInvoice o);
 
 /**
- * Creates a new CResult_HolderCommitmentTransactionDecodeErrorZ in the error state.
+ * Creates a new CResult_SignedRawInvoiceNoneZ in the error state.
  */
-struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ CResult_HolderCommitmentTransactionDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_SignedRawInvoiceNoneZ CResult_SignedRawInvoiceNoneZ_err(void);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_HolderCommitmentTransactionDecodeErrorZ_is_ok(const struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ *NONNULL_PTR o);
+bool CResult_SignedRawInvoiceNoneZ_is_ok(const struct LDKCResult_SignedRawInvoiceNoneZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_HolderCommitmentTransactionDecodeErrorZ.
+ * Frees any resources used by the CResult_SignedRawInvoiceNoneZ.
  */
-void CResult_HolderCommitmentTransactionDecodeErrorZ_free(struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ _res);
+void CResult_SignedRawInvoiceNoneZ_free(struct LDKCResult_SignedRawInvoiceNoneZ _res);
 
 /**
- * Creates a new CResult_HolderCommitmentTransactionDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_SignedRawInvoiceNoneZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ CResult_HolderCommitmentTransactionDecodeErrorZ_clone(const struct LDKCResult_HolderCommitmentTransactionDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_SignedRawInvoiceNoneZ CResult_SignedRawInvoiceNoneZ_clone(const struct LDKCResult_SignedRawInvoiceNoneZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_BuiltCommitmentTransactionDecodeErrorZ in the success state.
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ CResult_BuiltCommitmentTransactionDecodeErrorZ_ok(struct LDKBuiltCommitmentTransaction o);
+struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ C3Tuple_RawInvoice_u832InvoiceSignatureZ_clone(const struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_BuiltCommitmentTransactionDecodeErrorZ in the error state.
+ * Creates a new C3Tuple_RawInvoice_u832InvoiceSignatureZ from the contained elements.
  */
-struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ CResult_BuiltCommitmentTransactionDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ C3Tuple_RawInvoice_u832InvoiceSignatureZ_new(struct LDKRawInvoice a, struct LDKThirtyTwoBytes b, struct LDKInvoiceSignature c);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the C3Tuple_RawInvoice_u832InvoiceSignatureZ.
  */
-bool CResult_BuiltCommitmentTransactionDecodeErrorZ_is_ok(const struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ *NONNULL_PTR o);
+void C3Tuple_RawInvoice_u832InvoiceSignatureZ_free(struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ _res);
 
 /**
- * Frees any resources used by the CResult_BuiltCommitmentTransactionDecodeErrorZ.
+ * Creates a new CResult_PayeePubKeyErrorZ in the success state.
  */
-void CResult_BuiltCommitmentTransactionDecodeErrorZ_free(struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ _res);
+struct LDKCResult_PayeePubKeyErrorZ CResult_PayeePubKeyErrorZ_ok(struct LDKPayeePubKey o);
 
 /**
- * Creates a new CResult_BuiltCommitmentTransactionDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_PayeePubKeyErrorZ in the error state.
  */
-struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ CResult_BuiltCommitmentTransactionDecodeErrorZ_clone(const struct LDKCResult_BuiltCommitmentTransactionDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_PayeePubKeyErrorZ CResult_PayeePubKeyErrorZ_err(enum LDKSecp256k1Error e);
 
 /**
- * Creates a new CResult_TrustedClosingTransactionNoneZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_TrustedClosingTransactionNoneZ CResult_TrustedClosingTransactionNoneZ_ok(struct LDKTrustedClosingTransaction o);
+bool CResult_PayeePubKeyErrorZ_is_ok(const struct LDKCResult_PayeePubKeyErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_TrustedClosingTransactionNoneZ in the error state.
+ * Frees any resources used by the CResult_PayeePubKeyErrorZ.
  */
-struct LDKCResult_TrustedClosingTransactionNoneZ CResult_TrustedClosingTransactionNoneZ_err(void);
+void CResult_PayeePubKeyErrorZ_free(struct LDKCResult_PayeePubKeyErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_PayeePubKeyErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-bool CResult_TrustedClosingTransactionNoneZ_is_ok(const struct LDKCResult_TrustedClosingTransactionNoneZ *NONNULL_PTR o);
+struct LDKCResult_PayeePubKeyErrorZ CResult_PayeePubKeyErrorZ_clone(const struct LDKCResult_PayeePubKeyErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the CResult_TrustedClosingTransactionNoneZ.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void CResult_TrustedClosingTransactionNoneZ_free(struct LDKCResult_TrustedClosingTransactionNoneZ _res);
+void CVec_PrivateRouteZ_free(struct LDKCVec_PrivateRouteZ _res);
 
 /**
- * Creates a new CResult_CommitmentTransactionDecodeErrorZ in the success state.
+ * Creates a new CResult_PositiveTimestampCreationErrorZ in the success state.
  */
-struct LDKCResult_CommitmentTransactionDecodeErrorZ CResult_CommitmentTransactionDecodeErrorZ_ok(struct LDKCommitmentTransaction o);
+struct LDKCResult_PositiveTimestampCreationErrorZ CResult_PositiveTimestampCreationErrorZ_ok(struct LDKPositiveTimestamp o);
 
 /**
- * Creates a new CResult_CommitmentTransactionDecodeErrorZ in the error state.
+ * Creates a new CResult_PositiveTimestampCreationErrorZ in the error state.
  */
-struct LDKCResult_CommitmentTransactionDecodeErrorZ CResult_CommitmentTransactionDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_PositiveTimestampCreationErrorZ CResult_PositiveTimestampCreationErrorZ_err(enum LDKCreationError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_CommitmentTransactionDecodeErrorZ_is_ok(const struct LDKCResult_CommitmentTransactionDecodeErrorZ *NONNULL_PTR o);
+bool CResult_PositiveTimestampCreationErrorZ_is_ok(const struct LDKCResult_PositiveTimestampCreationErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_CommitmentTransactionDecodeErrorZ.
+ * Frees any resources used by the CResult_PositiveTimestampCreationErrorZ.
  */
-void CResult_CommitmentTransactionDecodeErrorZ_free(struct LDKCResult_CommitmentTransactionDecodeErrorZ _res);
+void CResult_PositiveTimestampCreationErrorZ_free(struct LDKCResult_PositiveTimestampCreationErrorZ _res);
 
 /**
- * Creates a new CResult_CommitmentTransactionDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_PositiveTimestampCreationErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_CommitmentTransactionDecodeErrorZ CResult_CommitmentTransactionDecodeErrorZ_clone(const struct LDKCResult_CommitmentTransactionDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_PositiveTimestampCreationErrorZ CResult_PositiveTimestampCreationErrorZ_clone(const struct LDKCResult_PositiveTimestampCreationErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_TrustedCommitmentTransactionNoneZ in the success state.
+ * Creates a new CResult_NoneSemanticErrorZ in the success state.
  */
-struct LDKCResult_TrustedCommitmentTransactionNoneZ CResult_TrustedCommitmentTransactionNoneZ_ok(struct LDKTrustedCommitmentTransaction o);
+struct LDKCResult_NoneSemanticErrorZ CResult_NoneSemanticErrorZ_ok(void);
 
 /**
- * Creates a new CResult_TrustedCommitmentTransactionNoneZ in the error state.
+ * Creates a new CResult_NoneSemanticErrorZ in the error state.
  */
-struct LDKCResult_TrustedCommitmentTransactionNoneZ CResult_TrustedCommitmentTransactionNoneZ_err(void);
+struct LDKCResult_NoneSemanticErrorZ CResult_NoneSemanticErrorZ_err(enum LDKSemanticError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_TrustedCommitmentTransactionNoneZ_is_ok(const struct LDKCResult_TrustedCommitmentTransactionNoneZ *NONNULL_PTR o);
+bool CResult_NoneSemanticErrorZ_is_ok(const struct LDKCResult_NoneSemanticErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_TrustedCommitmentTransactionNoneZ.
+ * Frees any resources used by the CResult_NoneSemanticErrorZ.
  */
-void CResult_TrustedCommitmentTransactionNoneZ_free(struct LDKCResult_TrustedCommitmentTransactionNoneZ _res);
+void CResult_NoneSemanticErrorZ_free(struct LDKCResult_NoneSemanticErrorZ _res);
 
 /**
- * Creates a new CResult_CVec_SignatureZNoneZ in the success state.
+ * Creates a new CResult_NoneSemanticErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_CVec_SignatureZNoneZ CResult_CVec_SignatureZNoneZ_ok(struct LDKCVec_SignatureZ o);
+struct LDKCResult_NoneSemanticErrorZ CResult_NoneSemanticErrorZ_clone(const struct LDKCResult_NoneSemanticErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_CVec_SignatureZNoneZ in the error state.
+ * Creates a new CResult_InvoiceSemanticErrorZ in the success state.
  */
-struct LDKCResult_CVec_SignatureZNoneZ CResult_CVec_SignatureZNoneZ_err(void);
+struct LDKCResult_InvoiceSemanticErrorZ CResult_InvoiceSemanticErrorZ_ok(struct LDKInvoice o);
+
+/**
+ * Creates a new CResult_InvoiceSemanticErrorZ in the error state.
+ */
+struct LDKCResult_InvoiceSemanticErrorZ CResult_InvoiceSemanticErrorZ_err(enum LDKSemanticError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_CVec_SignatureZNoneZ_is_ok(const struct LDKCResult_CVec_SignatureZNoneZ *NONNULL_PTR o);
+bool CResult_InvoiceSemanticErrorZ_is_ok(const struct LDKCResult_InvoiceSemanticErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_CVec_SignatureZNoneZ.
+ * Frees any resources used by the CResult_InvoiceSemanticErrorZ.
  */
-void CResult_CVec_SignatureZNoneZ_free(struct LDKCResult_CVec_SignatureZNoneZ _res);
+void CResult_InvoiceSemanticErrorZ_free(struct LDKCResult_InvoiceSemanticErrorZ _res);
 
 /**
- * Creates a new CResult_CVec_SignatureZNoneZ which has the same data as `orig`
+ * Creates a new CResult_InvoiceSemanticErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_CVec_SignatureZNoneZ CResult_CVec_SignatureZNoneZ_clone(const struct LDKCResult_CVec_SignatureZNoneZ *NONNULL_PTR orig);
+struct LDKCResult_InvoiceSemanticErrorZ CResult_InvoiceSemanticErrorZ_clone(const struct LDKCResult_InvoiceSemanticErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_ShutdownScriptDecodeErrorZ in the success state.
+ * Creates a new CResult_DescriptionCreationErrorZ in the success state.
  */
-struct LDKCResult_ShutdownScriptDecodeErrorZ CResult_ShutdownScriptDecodeErrorZ_ok(struct LDKShutdownScript o);
+struct LDKCResult_DescriptionCreationErrorZ CResult_DescriptionCreationErrorZ_ok(struct LDKDescription o);
 
 /**
- * Creates a new CResult_ShutdownScriptDecodeErrorZ in the error state.
+ * Creates a new CResult_DescriptionCreationErrorZ in the error state.
  */
-struct LDKCResult_ShutdownScriptDecodeErrorZ CResult_ShutdownScriptDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_DescriptionCreationErrorZ CResult_DescriptionCreationErrorZ_err(enum LDKCreationError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_ShutdownScriptDecodeErrorZ_is_ok(const struct LDKCResult_ShutdownScriptDecodeErrorZ *NONNULL_PTR o);
+bool CResult_DescriptionCreationErrorZ_is_ok(const struct LDKCResult_DescriptionCreationErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_ShutdownScriptDecodeErrorZ.
+ * Frees any resources used by the CResult_DescriptionCreationErrorZ.
  */
-void CResult_ShutdownScriptDecodeErrorZ_free(struct LDKCResult_ShutdownScriptDecodeErrorZ _res);
+void CResult_DescriptionCreationErrorZ_free(struct LDKCResult_DescriptionCreationErrorZ _res);
 
 /**
- * Creates a new CResult_ShutdownScriptDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_DescriptionCreationErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_ShutdownScriptDecodeErrorZ CResult_ShutdownScriptDecodeErrorZ_clone(const struct LDKCResult_ShutdownScriptDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_DescriptionCreationErrorZ CResult_DescriptionCreationErrorZ_clone(const struct LDKCResult_DescriptionCreationErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_ShutdownScriptInvalidShutdownScriptZ in the success state.
+ * Creates a new CResult_ExpiryTimeCreationErrorZ in the success state.
  */
-struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ CResult_ShutdownScriptInvalidShutdownScriptZ_ok(struct LDKShutdownScript o);
+struct LDKCResult_ExpiryTimeCreationErrorZ CResult_ExpiryTimeCreationErrorZ_ok(struct LDKExpiryTime o);
 
 /**
- * Creates a new CResult_ShutdownScriptInvalidShutdownScriptZ in the error state.
+ * Creates a new CResult_ExpiryTimeCreationErrorZ in the error state.
  */
-struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ CResult_ShutdownScriptInvalidShutdownScriptZ_err(struct LDKInvalidShutdownScript e);
+struct LDKCResult_ExpiryTimeCreationErrorZ CResult_ExpiryTimeCreationErrorZ_err(enum LDKCreationError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_ShutdownScriptInvalidShutdownScriptZ_is_ok(const struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ *NONNULL_PTR o);
+bool CResult_ExpiryTimeCreationErrorZ_is_ok(const struct LDKCResult_ExpiryTimeCreationErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_ShutdownScriptInvalidShutdownScriptZ.
+ * Frees any resources used by the CResult_ExpiryTimeCreationErrorZ.
  */
-void CResult_ShutdownScriptInvalidShutdownScriptZ_free(struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ _res);
+void CResult_ExpiryTimeCreationErrorZ_free(struct LDKCResult_ExpiryTimeCreationErrorZ _res);
 
 /**
- * Creates a new CResult_ShutdownScriptInvalidShutdownScriptZ which has the same data as `orig`
+ * Creates a new CResult_ExpiryTimeCreationErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ CResult_ShutdownScriptInvalidShutdownScriptZ_clone(const struct LDKCResult_ShutdownScriptInvalidShutdownScriptZ *NONNULL_PTR orig);
+struct LDKCResult_ExpiryTimeCreationErrorZ CResult_ExpiryTimeCreationErrorZ_clone(const struct LDKCResult_ExpiryTimeCreationErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_NoneErrorZ in the success state.
+ * Creates a new CResult_PrivateRouteCreationErrorZ in the success state.
  */
-struct LDKCResult_NoneErrorZ CResult_NoneErrorZ_ok(void);
+struct LDKCResult_PrivateRouteCreationErrorZ CResult_PrivateRouteCreationErrorZ_ok(struct LDKPrivateRoute o);
 
 /**
- * Creates a new CResult_NoneErrorZ in the error state.
+ * Creates a new CResult_PrivateRouteCreationErrorZ in the error state.
  */
-struct LDKCResult_NoneErrorZ CResult_NoneErrorZ_err(enum LDKIOError e);
+struct LDKCResult_PrivateRouteCreationErrorZ CResult_PrivateRouteCreationErrorZ_err(enum LDKCreationError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_NoneErrorZ_is_ok(const struct LDKCResult_NoneErrorZ *NONNULL_PTR o);
+bool CResult_PrivateRouteCreationErrorZ_is_ok(const struct LDKCResult_PrivateRouteCreationErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_NoneErrorZ.
+ * Frees any resources used by the CResult_PrivateRouteCreationErrorZ.
  */
-void CResult_NoneErrorZ_free(struct LDKCResult_NoneErrorZ _res);
+void CResult_PrivateRouteCreationErrorZ_free(struct LDKCResult_PrivateRouteCreationErrorZ _res);
 
 /**
- * Creates a new CResult_NoneErrorZ which has the same data as `orig`
+ * Creates a new CResult_PrivateRouteCreationErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_NoneErrorZ CResult_NoneErrorZ_clone(const struct LDKCResult_NoneErrorZ *NONNULL_PTR orig);
+struct LDKCResult_PrivateRouteCreationErrorZ CResult_PrivateRouteCreationErrorZ_clone(const struct LDKCResult_PrivateRouteCreationErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_RouteHopDecodeErrorZ in the success state.
+ * Creates a new CResult_StringErrorZ in the success state.
  */
-struct LDKCResult_RouteHopDecodeErrorZ CResult_RouteHopDecodeErrorZ_ok(struct LDKRouteHop o);
+struct LDKCResult_StringErrorZ CResult_StringErrorZ_ok(struct LDKStr o);
 
 /**
- * Creates a new CResult_RouteHopDecodeErrorZ in the error state.
+ * Creates a new CResult_StringErrorZ in the error state.
  */
-struct LDKCResult_RouteHopDecodeErrorZ CResult_RouteHopDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_StringErrorZ CResult_StringErrorZ_err(enum LDKSecp256k1Error e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_RouteHopDecodeErrorZ_is_ok(const struct LDKCResult_RouteHopDecodeErrorZ *NONNULL_PTR o);
+bool CResult_StringErrorZ_is_ok(const struct LDKCResult_StringErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_RouteHopDecodeErrorZ.
+ * Frees any resources used by the CResult_StringErrorZ.
  */
-void CResult_RouteHopDecodeErrorZ_free(struct LDKCResult_RouteHopDecodeErrorZ _res);
+void CResult_StringErrorZ_free(struct LDKCResult_StringErrorZ _res);
 
 /**
- * Creates a new CResult_RouteHopDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_ChannelMonitorUpdateDecodeErrorZ in the success state.
  */
-struct LDKCResult_RouteHopDecodeErrorZ CResult_RouteHopDecodeErrorZ_clone(const struct LDKCResult_RouteHopDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ CResult_ChannelMonitorUpdateDecodeErrorZ_ok(struct LDKChannelMonitorUpdate o);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_ChannelMonitorUpdateDecodeErrorZ in the error state.
  */
-void CVec_RouteHopZ_free(struct LDKCVec_RouteHopZ _res);
+struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ CResult_ChannelMonitorUpdateDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Checks if the given object is currently in the success state
  */
-void CVec_CVec_RouteHopZZ_free(struct LDKCVec_CVec_RouteHopZZ _res);
+bool CResult_ChannelMonitorUpdateDecodeErrorZ_is_ok(const struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_RouteDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_ChannelMonitorUpdateDecodeErrorZ.
  */
-struct LDKCResult_RouteDecodeErrorZ CResult_RouteDecodeErrorZ_ok(struct LDKRoute o);
+void CResult_ChannelMonitorUpdateDecodeErrorZ_free(struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_RouteDecodeErrorZ in the error state.
+ * Creates a new CResult_ChannelMonitorUpdateDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_RouteDecodeErrorZ CResult_RouteDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ CResult_ChannelMonitorUpdateDecodeErrorZ_clone(const struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Checks if the given object is currently in the success state
+ * Constructs a new COption_MonitorEventZ containing a crate::lightning::chain::channelmonitor::MonitorEvent
  */
-bool CResult_RouteDecodeErrorZ_is_ok(const struct LDKCResult_RouteDecodeErrorZ *NONNULL_PTR o);
+struct LDKCOption_MonitorEventZ COption_MonitorEventZ_some(struct LDKMonitorEvent o);
 
 /**
- * Frees any resources used by the CResult_RouteDecodeErrorZ.
+ * Constructs a new COption_MonitorEventZ containing nothing
  */
-void CResult_RouteDecodeErrorZ_free(struct LDKCResult_RouteDecodeErrorZ _res);
+struct LDKCOption_MonitorEventZ COption_MonitorEventZ_none(void);
 
 /**
- * Creates a new CResult_RouteDecodeErrorZ which has the same data as `orig`
+ * Frees any resources associated with the crate::lightning::chain::channelmonitor::MonitorEvent, if we are in the Some state
+ */
+void COption_MonitorEventZ_free(struct LDKCOption_MonitorEventZ _res);
+
+/**
+ * Creates a new COption_MonitorEventZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_RouteDecodeErrorZ CResult_RouteDecodeErrorZ_clone(const struct LDKCResult_RouteDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCOption_MonitorEventZ COption_MonitorEventZ_clone(const struct LDKCOption_MonitorEventZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_RouteParametersDecodeErrorZ in the success state.
+ * Creates a new CResult_COption_MonitorEventZDecodeErrorZ in the success state.
  */
-struct LDKCResult_RouteParametersDecodeErrorZ CResult_RouteParametersDecodeErrorZ_ok(struct LDKRouteParameters o);
+struct LDKCResult_COption_MonitorEventZDecodeErrorZ CResult_COption_MonitorEventZDecodeErrorZ_ok(struct LDKCOption_MonitorEventZ o);
 
 /**
- * Creates a new CResult_RouteParametersDecodeErrorZ in the error state.
+ * Creates a new CResult_COption_MonitorEventZDecodeErrorZ in the error state.
  */
-struct LDKCResult_RouteParametersDecodeErrorZ CResult_RouteParametersDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_COption_MonitorEventZDecodeErrorZ CResult_COption_MonitorEventZDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_RouteParametersDecodeErrorZ_is_ok(const struct LDKCResult_RouteParametersDecodeErrorZ *NONNULL_PTR o);
+bool CResult_COption_MonitorEventZDecodeErrorZ_is_ok(const struct LDKCResult_COption_MonitorEventZDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_RouteParametersDecodeErrorZ.
+ * Frees any resources used by the CResult_COption_MonitorEventZDecodeErrorZ.
  */
-void CResult_RouteParametersDecodeErrorZ_free(struct LDKCResult_RouteParametersDecodeErrorZ _res);
+void CResult_COption_MonitorEventZDecodeErrorZ_free(struct LDKCResult_COption_MonitorEventZDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_RouteParametersDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_COption_MonitorEventZDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_RouteParametersDecodeErrorZ CResult_RouteParametersDecodeErrorZ_clone(const struct LDKCResult_RouteParametersDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_COption_MonitorEventZDecodeErrorZ CResult_COption_MonitorEventZDecodeErrorZ_clone(const struct LDKCResult_COption_MonitorEventZDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_HTLCUpdateDecodeErrorZ in the success state.
  */
-void CVec_RouteHintZ_free(struct LDKCVec_RouteHintZ _res);
+struct LDKCResult_HTLCUpdateDecodeErrorZ CResult_HTLCUpdateDecodeErrorZ_ok(struct LDKHTLCUpdate o);
 
 /**
- * Constructs a new COption_u64Z containing a u64
+ * Creates a new CResult_HTLCUpdateDecodeErrorZ in the error state.
  */
-struct LDKCOption_u64Z COption_u64Z_some(uint64_t o);
+struct LDKCResult_HTLCUpdateDecodeErrorZ CResult_HTLCUpdateDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Constructs a new COption_u64Z containing nothing
+ * Checks if the given object is currently in the success state
  */
-struct LDKCOption_u64Z COption_u64Z_none(void);
+bool CResult_HTLCUpdateDecodeErrorZ_is_ok(const struct LDKCResult_HTLCUpdateDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources associated with the u64, if we are in the Some state
+ * Frees any resources used by the CResult_HTLCUpdateDecodeErrorZ.
  */
-void COption_u64Z_free(struct LDKCOption_u64Z _res);
+void CResult_HTLCUpdateDecodeErrorZ_free(struct LDKCResult_HTLCUpdateDecodeErrorZ _res);
 
 /**
- * Creates a new COption_u64Z which has the same data as `orig`
+ * Creates a new CResult_HTLCUpdateDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCOption_u64Z COption_u64Z_clone(const struct LDKCOption_u64Z *NONNULL_PTR orig);
+struct LDKCResult_HTLCUpdateDecodeErrorZ CResult_HTLCUpdateDecodeErrorZ_clone(const struct LDKCResult_HTLCUpdateDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PayeeDecodeErrorZ in the success state.
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PayeeDecodeErrorZ CResult_PayeeDecodeErrorZ_ok(struct LDKPayee o);
+struct LDKC2Tuple_OutPointScriptZ C2Tuple_OutPointScriptZ_clone(const struct LDKC2Tuple_OutPointScriptZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PayeeDecodeErrorZ in the error state.
+ * Creates a new C2Tuple_OutPointScriptZ from the contained elements.
  */
-struct LDKCResult_PayeeDecodeErrorZ CResult_PayeeDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKC2Tuple_OutPointScriptZ C2Tuple_OutPointScriptZ_new(struct LDKOutPoint a, struct LDKCVec_u8Z b);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the C2Tuple_OutPointScriptZ.
  */
-bool CResult_PayeeDecodeErrorZ_is_ok(const struct LDKCResult_PayeeDecodeErrorZ *NONNULL_PTR o);
+void C2Tuple_OutPointScriptZ_free(struct LDKC2Tuple_OutPointScriptZ _res);
 
 /**
- * Frees any resources used by the CResult_PayeeDecodeErrorZ.
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void CResult_PayeeDecodeErrorZ_free(struct LDKCResult_PayeeDecodeErrorZ _res);
+struct LDKC2Tuple_u32ScriptZ C2Tuple_u32ScriptZ_clone(const struct LDKC2Tuple_u32ScriptZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PayeeDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new C2Tuple_u32ScriptZ from the contained elements.
  */
-struct LDKCResult_PayeeDecodeErrorZ CResult_PayeeDecodeErrorZ_clone(const struct LDKCResult_PayeeDecodeErrorZ *NONNULL_PTR orig);
+struct LDKC2Tuple_u32ScriptZ C2Tuple_u32ScriptZ_new(uint32_t a, struct LDKCVec_u8Z b);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Frees any resources used by the C2Tuple_u32ScriptZ.
  */
-void CVec_RouteHintHopZ_free(struct LDKCVec_RouteHintHopZ _res);
+void C2Tuple_u32ScriptZ_free(struct LDKC2Tuple_u32ScriptZ _res);
 
 /**
- * Creates a new CResult_RouteHintDecodeErrorZ in the success state.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_RouteHintDecodeErrorZ CResult_RouteHintDecodeErrorZ_ok(struct LDKRouteHint o);
+void CVec_C2Tuple_u32ScriptZZ_free(struct LDKCVec_C2Tuple_u32ScriptZZ _res);
 
 /**
- * Creates a new CResult_RouteHintDecodeErrorZ in the error state.
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_RouteHintDecodeErrorZ CResult_RouteHintDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ_clone(const struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ *NONNULL_PTR orig);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ from the contained elements.
  */
-bool CResult_RouteHintDecodeErrorZ_is_ok(const struct LDKCResult_RouteHintDecodeErrorZ *NONNULL_PTR o);
+struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ_new(struct LDKThirtyTwoBytes a, struct LDKCVec_C2Tuple_u32ScriptZZ b);
 
 /**
- * Frees any resources used by the CResult_RouteHintDecodeErrorZ.
+ * Frees any resources used by the C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ.
  */
-void CResult_RouteHintDecodeErrorZ_free(struct LDKCResult_RouteHintDecodeErrorZ _res);
+void C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ_free(struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ _res);
 
 /**
- * Creates a new CResult_RouteHintDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_RouteHintDecodeErrorZ CResult_RouteHintDecodeErrorZ_clone(const struct LDKCResult_RouteHintDecodeErrorZ *NONNULL_PTR orig);
+void CVec_C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZZ_free(struct LDKCVec_C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZZ _res);
 
 /**
- * Creates a new CResult_RouteHintHopDecodeErrorZ in the success state.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_RouteHintHopDecodeErrorZ CResult_RouteHintHopDecodeErrorZ_ok(struct LDKRouteHintHop o);
+void CVec_EventZ_free(struct LDKCVec_EventZ _res);
 
 /**
- * Creates a new CResult_RouteHintHopDecodeErrorZ in the error state.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_RouteHintHopDecodeErrorZ CResult_RouteHintHopDecodeErrorZ_err(struct LDKDecodeError e);
+void CVec_TransactionZ_free(struct LDKCVec_TransactionZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-bool CResult_RouteHintHopDecodeErrorZ_is_ok(const struct LDKCResult_RouteHintHopDecodeErrorZ *NONNULL_PTR o);
+struct LDKC2Tuple_u32TxOutZ C2Tuple_u32TxOutZ_clone(const struct LDKC2Tuple_u32TxOutZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the CResult_RouteHintHopDecodeErrorZ.
+ * Creates a new C2Tuple_u32TxOutZ from the contained elements.
  */
-void CResult_RouteHintHopDecodeErrorZ_free(struct LDKCResult_RouteHintHopDecodeErrorZ _res);
+struct LDKC2Tuple_u32TxOutZ C2Tuple_u32TxOutZ_new(uint32_t a, struct LDKTxOut b);
 
 /**
- * Creates a new CResult_RouteHintHopDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the C2Tuple_u32TxOutZ.
  */
-struct LDKCResult_RouteHintHopDecodeErrorZ CResult_RouteHintHopDecodeErrorZ_clone(const struct LDKCResult_RouteHintHopDecodeErrorZ *NONNULL_PTR orig);
+void C2Tuple_u32TxOutZ_free(struct LDKC2Tuple_u32TxOutZ _res);
 
 /**
  * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void CVec_ChannelDetailsZ_free(struct LDKCVec_ChannelDetailsZ _res);
+void CVec_C2Tuple_u32TxOutZZ_free(struct LDKCVec_C2Tuple_u32TxOutZZ _res);
 
 /**
- * Creates a new CResult_RouteLightningErrorZ in the success state.
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_RouteLightningErrorZ CResult_RouteLightningErrorZ_ok(struct LDKRoute o);
+struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ_clone(const struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_RouteLightningErrorZ in the error state.
+ * Creates a new C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ from the contained elements.
  */
-struct LDKCResult_RouteLightningErrorZ CResult_RouteLightningErrorZ_err(struct LDKLightningError e);
+struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ_new(struct LDKThirtyTwoBytes a, struct LDKCVec_C2Tuple_u32TxOutZZ b);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ.
  */
-bool CResult_RouteLightningErrorZ_is_ok(const struct LDKCResult_RouteLightningErrorZ *NONNULL_PTR o);
+void C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ_free(struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ _res);
 
 /**
- * Frees any resources used by the CResult_RouteLightningErrorZ.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void CResult_RouteLightningErrorZ_free(struct LDKCResult_RouteLightningErrorZ _res);
+void CVec_TransactionOutputsZ_free(struct LDKCVec_TransactionOutputsZ _res);
 
 /**
- * Creates a new CResult_RouteLightningErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_RouteLightningErrorZ CResult_RouteLightningErrorZ_clone(const struct LDKCResult_RouteLightningErrorZ *NONNULL_PTR orig);
+void CVec_BalanceZ_free(struct LDKCVec_BalanceZ _res);
 
 /**
- * Creates a new CResult_TxOutAccessErrorZ in the success state.
+ * Creates a new CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ in the success state.
  */
-struct LDKCResult_TxOutAccessErrorZ CResult_TxOutAccessErrorZ_ok(struct LDKTxOut o);
+struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_ok(struct LDKC2Tuple_BlockHashChannelMonitorZ o);
 
 /**
- * Creates a new CResult_TxOutAccessErrorZ in the error state.
+ * Creates a new CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ in the error state.
  */
-struct LDKCResult_TxOutAccessErrorZ CResult_TxOutAccessErrorZ_err(enum LDKAccessError e);
+struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_TxOutAccessErrorZ_is_ok(const struct LDKCResult_TxOutAccessErrorZ *NONNULL_PTR o);
+bool CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_is_ok(const struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_TxOutAccessErrorZ.
+ * Frees any resources used by the CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ.
  */
-void CResult_TxOutAccessErrorZ_free(struct LDKCResult_TxOutAccessErrorZ _res);
+void CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_free(struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_TxOutAccessErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_NoneLightningErrorZ in the success state.
  */
-struct LDKCResult_TxOutAccessErrorZ CResult_TxOutAccessErrorZ_clone(const struct LDKCResult_TxOutAccessErrorZ *NONNULL_PTR orig);
+struct LDKCResult_NoneLightningErrorZ CResult_NoneLightningErrorZ_ok(void);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_NoneLightningErrorZ in the error state.
  */
-struct LDKC2Tuple_usizeTransactionZ C2Tuple_usizeTransactionZ_clone(const struct LDKC2Tuple_usizeTransactionZ *NONNULL_PTR orig);
+struct LDKCResult_NoneLightningErrorZ CResult_NoneLightningErrorZ_err(struct LDKLightningError e);
 
 /**
- * Creates a new C2Tuple_usizeTransactionZ from the contained elements.
+ * Checks if the given object is currently in the success state
  */
-struct LDKC2Tuple_usizeTransactionZ C2Tuple_usizeTransactionZ_new(uintptr_t a, struct LDKTransaction b);
+bool CResult_NoneLightningErrorZ_is_ok(const struct LDKCResult_NoneLightningErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the C2Tuple_usizeTransactionZ.
+ * Frees any resources used by the CResult_NoneLightningErrorZ.
  */
-void C2Tuple_usizeTransactionZ_free(struct LDKC2Tuple_usizeTransactionZ _res);
+void CResult_NoneLightningErrorZ_free(struct LDKCResult_NoneLightningErrorZ _res);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_NoneLightningErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void CVec_C2Tuple_usizeTransactionZZ_free(struct LDKCVec_C2Tuple_usizeTransactionZZ _res);
+struct LDKCResult_NoneLightningErrorZ CResult_NoneLightningErrorZ_clone(const struct LDKCResult_NoneLightningErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new C2Tuple_PublicKeyTypeZ from the contained elements.
  */
-void CVec_TxidZ_free(struct LDKCVec_TxidZ _res);
+struct LDKC2Tuple_PublicKeyTypeZ C2Tuple_PublicKeyTypeZ_new(struct LDKPublicKey a, struct LDKType b);
 
 /**
- * Creates a new CResult_NoneChannelMonitorUpdateErrZ in the success state.
+ * Frees any resources used by the C2Tuple_PublicKeyTypeZ.
  */
-struct LDKCResult_NoneChannelMonitorUpdateErrZ CResult_NoneChannelMonitorUpdateErrZ_ok(void);
+void C2Tuple_PublicKeyTypeZ_free(struct LDKC2Tuple_PublicKeyTypeZ _res);
 
 /**
- * Creates a new CResult_NoneChannelMonitorUpdateErrZ in the error state.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_NoneChannelMonitorUpdateErrZ CResult_NoneChannelMonitorUpdateErrZ_err(enum LDKChannelMonitorUpdateErr e);
+void CVec_C2Tuple_PublicKeyTypeZZ_free(struct LDKCVec_C2Tuple_PublicKeyTypeZZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_boolLightningErrorZ in the success state.
  */
-bool CResult_NoneChannelMonitorUpdateErrZ_is_ok(const struct LDKCResult_NoneChannelMonitorUpdateErrZ *NONNULL_PTR o);
+struct LDKCResult_boolLightningErrorZ CResult_boolLightningErrorZ_ok(bool o);
 
 /**
- * Frees any resources used by the CResult_NoneChannelMonitorUpdateErrZ.
+ * Creates a new CResult_boolLightningErrorZ in the error state.
  */
-void CResult_NoneChannelMonitorUpdateErrZ_free(struct LDKCResult_NoneChannelMonitorUpdateErrZ _res);
+struct LDKCResult_boolLightningErrorZ CResult_boolLightningErrorZ_err(struct LDKLightningError e);
 
 /**
- * Creates a new CResult_NoneChannelMonitorUpdateErrZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_NoneChannelMonitorUpdateErrZ CResult_NoneChannelMonitorUpdateErrZ_clone(const struct LDKCResult_NoneChannelMonitorUpdateErrZ *NONNULL_PTR orig);
+bool CResult_boolLightningErrorZ_is_ok(const struct LDKCResult_boolLightningErrorZ *NONNULL_PTR o);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Frees any resources used by the CResult_boolLightningErrorZ.
  */
-void CVec_MonitorEventZ_free(struct LDKCVec_MonitorEventZ _res);
+void CResult_boolLightningErrorZ_free(struct LDKCResult_boolLightningErrorZ _res);
 
 /**
- * Constructs a new COption_C2Tuple_usizeTransactionZZ containing a crate::c_types::derived::C2Tuple_usizeTransactionZ
+ * Creates a new CResult_boolLightningErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCOption_C2Tuple_usizeTransactionZZ COption_C2Tuple_usizeTransactionZZ_some(struct LDKC2Tuple_usizeTransactionZ o);
+struct LDKCResult_boolLightningErrorZ CResult_boolLightningErrorZ_clone(const struct LDKCResult_boolLightningErrorZ *NONNULL_PTR orig);
 
 /**
- * Constructs a new COption_C2Tuple_usizeTransactionZZ containing nothing
+ * Creates a new tuple which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCOption_C2Tuple_usizeTransactionZZ COption_C2Tuple_usizeTransactionZZ_none(void);
+struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ_clone(const struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources associated with the crate::c_types::derived::C2Tuple_usizeTransactionZ, if we are in the Some state
+ * Creates a new C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ from the contained elements.
  */
-void COption_C2Tuple_usizeTransactionZZ_free(struct LDKCOption_C2Tuple_usizeTransactionZZ _res);
+struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ_new(struct LDKChannelAnnouncement a, struct LDKChannelUpdate b, struct LDKChannelUpdate c);
 
 /**
- * Creates a new COption_C2Tuple_usizeTransactionZZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ.
  */
-struct LDKCOption_C2Tuple_usizeTransactionZZ COption_C2Tuple_usizeTransactionZZ_clone(const struct LDKCOption_C2Tuple_usizeTransactionZZ *NONNULL_PTR orig);
+void C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ_free(struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ _res);
 
 /**
- * Constructs a new COption_ClosureReasonZ containing a crate::lightning::util::events::ClosureReason
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCOption_ClosureReasonZ COption_ClosureReasonZ_some(struct LDKClosureReason o);
+void CVec_C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZZ_free(struct LDKCVec_C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZZ _res);
 
 /**
- * Constructs a new COption_ClosureReasonZ containing nothing
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCOption_ClosureReasonZ COption_ClosureReasonZ_none(void);
+void CVec_NodeAnnouncementZ_free(struct LDKCVec_NodeAnnouncementZ _res);
 
 /**
- * Frees any resources associated with the crate::lightning::util::events::ClosureReason, if we are in the Some state
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void COption_ClosureReasonZ_free(struct LDKCOption_ClosureReasonZ _res);
+void CVec_PublicKeyZ_free(struct LDKCVec_PublicKeyZ _res);
 
 /**
- * Creates a new COption_ClosureReasonZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_CVec_u8ZPeerHandleErrorZ in the success state.
  */
-struct LDKCOption_ClosureReasonZ COption_ClosureReasonZ_clone(const struct LDKCOption_ClosureReasonZ *NONNULL_PTR orig);
+struct LDKCResult_CVec_u8ZPeerHandleErrorZ CResult_CVec_u8ZPeerHandleErrorZ_ok(struct LDKCVec_u8Z o);
 
 /**
- * Creates a new CResult_COption_ClosureReasonZDecodeErrorZ in the success state.
+ * Creates a new CResult_CVec_u8ZPeerHandleErrorZ in the error state.
  */
-struct LDKCResult_COption_ClosureReasonZDecodeErrorZ CResult_COption_ClosureReasonZDecodeErrorZ_ok(struct LDKCOption_ClosureReasonZ o);
+struct LDKCResult_CVec_u8ZPeerHandleErrorZ CResult_CVec_u8ZPeerHandleErrorZ_err(struct LDKPeerHandleError e);
 
 /**
- * Creates a new CResult_COption_ClosureReasonZDecodeErrorZ in the error state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_COption_ClosureReasonZDecodeErrorZ CResult_COption_ClosureReasonZDecodeErrorZ_err(struct LDKDecodeError e);
+bool CResult_CVec_u8ZPeerHandleErrorZ_is_ok(const struct LDKCResult_CVec_u8ZPeerHandleErrorZ *NONNULL_PTR o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the CResult_CVec_u8ZPeerHandleErrorZ.
  */
-bool CResult_COption_ClosureReasonZDecodeErrorZ_is_ok(const struct LDKCResult_COption_ClosureReasonZDecodeErrorZ *NONNULL_PTR o);
+void CResult_CVec_u8ZPeerHandleErrorZ_free(struct LDKCResult_CVec_u8ZPeerHandleErrorZ _res);
 
 /**
- * Frees any resources used by the CResult_COption_ClosureReasonZDecodeErrorZ.
+ * Creates a new CResult_CVec_u8ZPeerHandleErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void CResult_COption_ClosureReasonZDecodeErrorZ_free(struct LDKCResult_COption_ClosureReasonZDecodeErrorZ _res);
+struct LDKCResult_CVec_u8ZPeerHandleErrorZ CResult_CVec_u8ZPeerHandleErrorZ_clone(const struct LDKCResult_CVec_u8ZPeerHandleErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_COption_ClosureReasonZDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_NonePeerHandleErrorZ in the success state.
  */
-struct LDKCResult_COption_ClosureReasonZDecodeErrorZ CResult_COption_ClosureReasonZDecodeErrorZ_clone(const struct LDKCResult_COption_ClosureReasonZDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_NonePeerHandleErrorZ CResult_NonePeerHandleErrorZ_ok(void);
 
 /**
- * Constructs a new COption_NetworkUpdateZ containing a crate::lightning::routing::network_graph::NetworkUpdate
+ * Creates a new CResult_NonePeerHandleErrorZ in the error state.
  */
-struct LDKCOption_NetworkUpdateZ COption_NetworkUpdateZ_some(struct LDKNetworkUpdate o);
+struct LDKCResult_NonePeerHandleErrorZ CResult_NonePeerHandleErrorZ_err(struct LDKPeerHandleError e);
 
 /**
- * Constructs a new COption_NetworkUpdateZ containing nothing
+ * Checks if the given object is currently in the success state
  */
-struct LDKCOption_NetworkUpdateZ COption_NetworkUpdateZ_none(void);
+bool CResult_NonePeerHandleErrorZ_is_ok(const struct LDKCResult_NonePeerHandleErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources associated with the crate::lightning::routing::network_graph::NetworkUpdate, if we are in the Some state
+ * Frees any resources used by the CResult_NonePeerHandleErrorZ.
  */
-void COption_NetworkUpdateZ_free(struct LDKCOption_NetworkUpdateZ _res);
+void CResult_NonePeerHandleErrorZ_free(struct LDKCResult_NonePeerHandleErrorZ _res);
 
 /**
- * Creates a new COption_NetworkUpdateZ which has the same data as `orig`
+ * Creates a new CResult_NonePeerHandleErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCOption_NetworkUpdateZ COption_NetworkUpdateZ_clone(const struct LDKCOption_NetworkUpdateZ *NONNULL_PTR orig);
+struct LDKCResult_NonePeerHandleErrorZ CResult_NonePeerHandleErrorZ_clone(const struct LDKCResult_NonePeerHandleErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_boolPeerHandleErrorZ in the success state.
  */
-void CVec_SpendableOutputDescriptorZ_free(struct LDKCVec_SpendableOutputDescriptorZ _res);
+struct LDKCResult_boolPeerHandleErrorZ CResult_boolPeerHandleErrorZ_ok(bool o);
 
 /**
- * Constructs a new COption_EventZ containing a crate::lightning::util::events::Event
+ * Creates a new CResult_boolPeerHandleErrorZ in the error state.
  */
-struct LDKCOption_EventZ COption_EventZ_some(struct LDKEvent o);
+struct LDKCResult_boolPeerHandleErrorZ CResult_boolPeerHandleErrorZ_err(struct LDKPeerHandleError e);
 
 /**
- * Constructs a new COption_EventZ containing nothing
+ * Checks if the given object is currently in the success state
  */
-struct LDKCOption_EventZ COption_EventZ_none(void);
+bool CResult_boolPeerHandleErrorZ_is_ok(const struct LDKCResult_boolPeerHandleErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources associated with the crate::lightning::util::events::Event, if we are in the Some state
+ * Frees any resources used by the CResult_boolPeerHandleErrorZ.
  */
-void COption_EventZ_free(struct LDKCOption_EventZ _res);
+void CResult_boolPeerHandleErrorZ_free(struct LDKCResult_boolPeerHandleErrorZ _res);
 
 /**
- * Creates a new COption_EventZ which has the same data as `orig`
+ * Creates a new CResult_boolPeerHandleErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCOption_EventZ COption_EventZ_clone(const struct LDKCOption_EventZ *NONNULL_PTR orig);
+struct LDKCResult_boolPeerHandleErrorZ CResult_boolPeerHandleErrorZ_clone(const struct LDKCResult_boolPeerHandleErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_COption_EventZDecodeErrorZ in the success state.
+ * Creates a new CResult_NodeIdDecodeErrorZ in the success state.
  */
-struct LDKCResult_COption_EventZDecodeErrorZ CResult_COption_EventZDecodeErrorZ_ok(struct LDKCOption_EventZ o);
+struct LDKCResult_NodeIdDecodeErrorZ CResult_NodeIdDecodeErrorZ_ok(struct LDKNodeId o);
 
 /**
- * Creates a new CResult_COption_EventZDecodeErrorZ in the error state.
+ * Creates a new CResult_NodeIdDecodeErrorZ in the error state.
  */
-struct LDKCResult_COption_EventZDecodeErrorZ CResult_COption_EventZDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_NodeIdDecodeErrorZ CResult_NodeIdDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_COption_EventZDecodeErrorZ_is_ok(const struct LDKCResult_COption_EventZDecodeErrorZ *NONNULL_PTR o);
+bool CResult_NodeIdDecodeErrorZ_is_ok(const struct LDKCResult_NodeIdDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_COption_EventZDecodeErrorZ.
+ * Frees any resources used by the CResult_NodeIdDecodeErrorZ.
  */
-void CResult_COption_EventZDecodeErrorZ_free(struct LDKCResult_COption_EventZDecodeErrorZ _res);
+void CResult_NodeIdDecodeErrorZ_free(struct LDKCResult_NodeIdDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_COption_EventZDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_NodeIdDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_COption_EventZDecodeErrorZ CResult_COption_EventZDecodeErrorZ_clone(const struct LDKCResult_COption_EventZDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_NodeIdDecodeErrorZ CResult_NodeIdDecodeErrorZ_clone(const struct LDKCResult_NodeIdDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_COption_NetworkUpdateZDecodeErrorZ in the success state.
  */
-void CVec_MessageSendEventZ_free(struct LDKCVec_MessageSendEventZ _res);
+struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ CResult_COption_NetworkUpdateZDecodeErrorZ_ok(struct LDKCOption_NetworkUpdateZ o);
 
 /**
- * Creates a new CResult_ScoringParametersDecodeErrorZ in the success state.
+ * Creates a new CResult_COption_NetworkUpdateZDecodeErrorZ in the error state.
  */
-struct LDKCResult_ScoringParametersDecodeErrorZ CResult_ScoringParametersDecodeErrorZ_ok(struct LDKScoringParameters o);
+struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ CResult_COption_NetworkUpdateZDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_ScoringParametersDecodeErrorZ in the error state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_ScoringParametersDecodeErrorZ CResult_ScoringParametersDecodeErrorZ_err(struct LDKDecodeError e);
+bool CResult_COption_NetworkUpdateZDecodeErrorZ_is_ok(const struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the CResult_COption_NetworkUpdateZDecodeErrorZ.
  */
-bool CResult_ScoringParametersDecodeErrorZ_is_ok(const struct LDKCResult_ScoringParametersDecodeErrorZ *NONNULL_PTR o);
+void CResult_COption_NetworkUpdateZDecodeErrorZ_free(struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ _res);
 
 /**
- * Frees any resources used by the CResult_ScoringParametersDecodeErrorZ.
+ * Creates a new CResult_COption_NetworkUpdateZDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void CResult_ScoringParametersDecodeErrorZ_free(struct LDKCResult_ScoringParametersDecodeErrorZ _res);
+struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ CResult_COption_NetworkUpdateZDecodeErrorZ_clone(const struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_ScorerDecodeErrorZ in the success state.
+ * Constructs a new COption_AccessZ containing a crate::lightning::chain::Access
  */
-struct LDKCResult_ScorerDecodeErrorZ CResult_ScorerDecodeErrorZ_ok(struct LDKScorer o);
+struct LDKCOption_AccessZ COption_AccessZ_some(struct LDKAccess o);
 
 /**
- * Creates a new CResult_ScorerDecodeErrorZ in the error state.
+ * Constructs a new COption_AccessZ containing nothing
  */
-struct LDKCResult_ScorerDecodeErrorZ CResult_ScorerDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCOption_AccessZ COption_AccessZ_none(void);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources associated with the crate::lightning::chain::Access, if we are in the Some state
  */
-bool CResult_ScorerDecodeErrorZ_is_ok(const struct LDKCResult_ScorerDecodeErrorZ *NONNULL_PTR o);
+void COption_AccessZ_free(struct LDKCOption_AccessZ _res);
 
 /**
- * Frees any resources used by the CResult_ScorerDecodeErrorZ.
+ * Creates a new CResult_DirectionalChannelInfoDecodeErrorZ in the success state.
  */
-void CResult_ScorerDecodeErrorZ_free(struct LDKCResult_ScorerDecodeErrorZ _res);
+struct LDKCResult_DirectionalChannelInfoDecodeErrorZ CResult_DirectionalChannelInfoDecodeErrorZ_ok(struct LDKDirectionalChannelInfo o);
 
 /**
- * Creates a new CResult_InitFeaturesDecodeErrorZ in the success state.
+ * Creates a new CResult_DirectionalChannelInfoDecodeErrorZ in the error state.
  */
-struct LDKCResult_InitFeaturesDecodeErrorZ CResult_InitFeaturesDecodeErrorZ_ok(struct LDKInitFeatures o);
+struct LDKCResult_DirectionalChannelInfoDecodeErrorZ CResult_DirectionalChannelInfoDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_InitFeaturesDecodeErrorZ in the error state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_InitFeaturesDecodeErrorZ CResult_InitFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+bool CResult_DirectionalChannelInfoDecodeErrorZ_is_ok(const struct LDKCResult_DirectionalChannelInfoDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the CResult_DirectionalChannelInfoDecodeErrorZ.
  */
-bool CResult_InitFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_InitFeaturesDecodeErrorZ *NONNULL_PTR o);
+void CResult_DirectionalChannelInfoDecodeErrorZ_free(struct LDKCResult_DirectionalChannelInfoDecodeErrorZ _res);
 
 /**
- * Frees any resources used by the CResult_InitFeaturesDecodeErrorZ.
+ * Creates a new CResult_DirectionalChannelInfoDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void CResult_InitFeaturesDecodeErrorZ_free(struct LDKCResult_InitFeaturesDecodeErrorZ _res);
+struct LDKCResult_DirectionalChannelInfoDecodeErrorZ CResult_DirectionalChannelInfoDecodeErrorZ_clone(const struct LDKCResult_DirectionalChannelInfoDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_ChannelFeaturesDecodeErrorZ in the success state.
+ * Creates a new CResult_ChannelInfoDecodeErrorZ in the success state.
  */
-struct LDKCResult_ChannelFeaturesDecodeErrorZ CResult_ChannelFeaturesDecodeErrorZ_ok(struct LDKChannelFeatures o);
+struct LDKCResult_ChannelInfoDecodeErrorZ CResult_ChannelInfoDecodeErrorZ_ok(struct LDKChannelInfo o);
 
 /**
- * Creates a new CResult_ChannelFeaturesDecodeErrorZ in the error state.
+ * Creates a new CResult_ChannelInfoDecodeErrorZ in the error state.
  */
-struct LDKCResult_ChannelFeaturesDecodeErrorZ CResult_ChannelFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_ChannelInfoDecodeErrorZ CResult_ChannelInfoDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_ChannelFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_ChannelFeaturesDecodeErrorZ *NONNULL_PTR o);
+bool CResult_ChannelInfoDecodeErrorZ_is_ok(const struct LDKCResult_ChannelInfoDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_ChannelFeaturesDecodeErrorZ.
+ * Frees any resources used by the CResult_ChannelInfoDecodeErrorZ.
  */
-void CResult_ChannelFeaturesDecodeErrorZ_free(struct LDKCResult_ChannelFeaturesDecodeErrorZ _res);
+void CResult_ChannelInfoDecodeErrorZ_free(struct LDKCResult_ChannelInfoDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_NodeFeaturesDecodeErrorZ in the success state.
+ * Creates a new CResult_ChannelInfoDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_NodeFeaturesDecodeErrorZ CResult_NodeFeaturesDecodeErrorZ_ok(struct LDKNodeFeatures o);
+struct LDKCResult_ChannelInfoDecodeErrorZ CResult_ChannelInfoDecodeErrorZ_clone(const struct LDKCResult_ChannelInfoDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_NodeFeaturesDecodeErrorZ in the error state.
+ * Creates a new CResult_RoutingFeesDecodeErrorZ in the success state.
  */
-struct LDKCResult_NodeFeaturesDecodeErrorZ CResult_NodeFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_RoutingFeesDecodeErrorZ CResult_RoutingFeesDecodeErrorZ_ok(struct LDKRoutingFees o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_RoutingFeesDecodeErrorZ in the error state.
  */
-bool CResult_NodeFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_NodeFeaturesDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_RoutingFeesDecodeErrorZ CResult_RoutingFeesDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Frees any resources used by the CResult_NodeFeaturesDecodeErrorZ.
+ * Checks if the given object is currently in the success state
  */
-void CResult_NodeFeaturesDecodeErrorZ_free(struct LDKCResult_NodeFeaturesDecodeErrorZ _res);
+bool CResult_RoutingFeesDecodeErrorZ_is_ok(const struct LDKCResult_RoutingFeesDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_InvoiceFeaturesDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_RoutingFeesDecodeErrorZ.
  */
-struct LDKCResult_InvoiceFeaturesDecodeErrorZ CResult_InvoiceFeaturesDecodeErrorZ_ok(struct LDKInvoiceFeatures o);
+void CResult_RoutingFeesDecodeErrorZ_free(struct LDKCResult_RoutingFeesDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_InvoiceFeaturesDecodeErrorZ in the error state.
+ * Creates a new CResult_RoutingFeesDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_InvoiceFeaturesDecodeErrorZ CResult_InvoiceFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_RoutingFeesDecodeErrorZ CResult_RoutingFeesDecodeErrorZ_clone(const struct LDKCResult_RoutingFeesDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_NodeAnnouncementInfoDecodeErrorZ in the success state.
  */
-bool CResult_InvoiceFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_InvoiceFeaturesDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ CResult_NodeAnnouncementInfoDecodeErrorZ_ok(struct LDKNodeAnnouncementInfo o);
 
 /**
- * Frees any resources used by the CResult_InvoiceFeaturesDecodeErrorZ.
+ * Creates a new CResult_NodeAnnouncementInfoDecodeErrorZ in the error state.
  */
-void CResult_InvoiceFeaturesDecodeErrorZ_free(struct LDKCResult_InvoiceFeaturesDecodeErrorZ _res);
+struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ CResult_NodeAnnouncementInfoDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_ChannelTypeFeaturesDecodeErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ CResult_ChannelTypeFeaturesDecodeErrorZ_ok(struct LDKChannelTypeFeatures o);
+bool CResult_NodeAnnouncementInfoDecodeErrorZ_is_ok(const struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_ChannelTypeFeaturesDecodeErrorZ in the error state.
+ * Frees any resources used by the CResult_NodeAnnouncementInfoDecodeErrorZ.
  */
-struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ CResult_ChannelTypeFeaturesDecodeErrorZ_err(struct LDKDecodeError e);
+void CResult_NodeAnnouncementInfoDecodeErrorZ_free(struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_NodeAnnouncementInfoDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-bool CResult_ChannelTypeFeaturesDecodeErrorZ_is_ok(const struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ CResult_NodeAnnouncementInfoDecodeErrorZ_clone(const struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the CResult_ChannelTypeFeaturesDecodeErrorZ.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void CResult_ChannelTypeFeaturesDecodeErrorZ_free(struct LDKCResult_ChannelTypeFeaturesDecodeErrorZ _res);
+void CVec_u64Z_free(struct LDKCVec_u64Z _res);
 
 /**
- * Creates a new CResult_DelayedPaymentOutputDescriptorDecodeErrorZ in the success state.
+ * Creates a new CResult_NodeInfoDecodeErrorZ in the success state.
  */
-struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_ok(struct LDKDelayedPaymentOutputDescriptor o);
+struct LDKCResult_NodeInfoDecodeErrorZ CResult_NodeInfoDecodeErrorZ_ok(struct LDKNodeInfo o);
 
 /**
- * Creates a new CResult_DelayedPaymentOutputDescriptorDecodeErrorZ in the error state.
+ * Creates a new CResult_NodeInfoDecodeErrorZ in the error state.
  */
-struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_NodeInfoDecodeErrorZ CResult_NodeInfoDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_is_ok(const struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR o);
+bool CResult_NodeInfoDecodeErrorZ_is_ok(const struct LDKCResult_NodeInfoDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_DelayedPaymentOutputDescriptorDecodeErrorZ.
+ * Frees any resources used by the CResult_NodeInfoDecodeErrorZ.
  */
-void CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_free(struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ _res);
+void CResult_NodeInfoDecodeErrorZ_free(struct LDKCResult_NodeInfoDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_DelayedPaymentOutputDescriptorDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_NodeInfoDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ CResult_DelayedPaymentOutputDescriptorDecodeErrorZ_clone(const struct LDKCResult_DelayedPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_NodeInfoDecodeErrorZ CResult_NodeInfoDecodeErrorZ_clone(const struct LDKCResult_NodeInfoDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_StaticPaymentOutputDescriptorDecodeErrorZ in the success state.
+ * Creates a new CResult_NetworkGraphDecodeErrorZ in the success state.
  */
-struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ CResult_StaticPaymentOutputDescriptorDecodeErrorZ_ok(struct LDKStaticPaymentOutputDescriptor o);
+struct LDKCResult_NetworkGraphDecodeErrorZ CResult_NetworkGraphDecodeErrorZ_ok(struct LDKNetworkGraph o);
 
 /**
- * Creates a new CResult_StaticPaymentOutputDescriptorDecodeErrorZ in the error state.
+ * Creates a new CResult_NetworkGraphDecodeErrorZ in the error state.
  */
-struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ CResult_StaticPaymentOutputDescriptorDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_NetworkGraphDecodeErrorZ CResult_NetworkGraphDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_StaticPaymentOutputDescriptorDecodeErrorZ_is_ok(const struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR o);
+bool CResult_NetworkGraphDecodeErrorZ_is_ok(const struct LDKCResult_NetworkGraphDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_StaticPaymentOutputDescriptorDecodeErrorZ.
+ * Frees any resources used by the CResult_NetworkGraphDecodeErrorZ.
  */
-void CResult_StaticPaymentOutputDescriptorDecodeErrorZ_free(struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ _res);
+void CResult_NetworkGraphDecodeErrorZ_free(struct LDKCResult_NetworkGraphDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_StaticPaymentOutputDescriptorDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_NetworkGraphDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ CResult_StaticPaymentOutputDescriptorDecodeErrorZ_clone(const struct LDKCResult_StaticPaymentOutputDescriptorDecodeErrorZ *NONNULL_PTR orig);
-
-/**
- * Creates a new CResult_SpendableOutputDescriptorDecodeErrorZ in the success state.
- */
-struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ CResult_SpendableOutputDescriptorDecodeErrorZ_ok(struct LDKSpendableOutputDescriptor o);
+struct LDKCResult_NetworkGraphDecodeErrorZ CResult_NetworkGraphDecodeErrorZ_clone(const struct LDKCResult_NetworkGraphDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_SpendableOutputDescriptorDecodeErrorZ in the error state.
+ * Constructs a new COption_CVec_NetAddressZZ containing a crate::c_types::derived::CVec_NetAddressZ
  */
-struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ CResult_SpendableOutputDescriptorDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCOption_CVec_NetAddressZZ COption_CVec_NetAddressZZ_some(struct LDKCVec_NetAddressZ o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Constructs a new COption_CVec_NetAddressZZ containing nothing
  */
-bool CResult_SpendableOutputDescriptorDecodeErrorZ_is_ok(const struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ *NONNULL_PTR o);
+struct LDKCOption_CVec_NetAddressZZ COption_CVec_NetAddressZZ_none(void);
 
 /**
- * Frees any resources used by the CResult_SpendableOutputDescriptorDecodeErrorZ.
+ * Frees any resources associated with the crate::c_types::derived::CVec_NetAddressZ, if we are in the Some state
  */
-void CResult_SpendableOutputDescriptorDecodeErrorZ_free(struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ _res);
+void COption_CVec_NetAddressZZ_free(struct LDKCOption_CVec_NetAddressZZ _res);
 
 /**
- * Creates a new CResult_SpendableOutputDescriptorDecodeErrorZ which has the same data as `orig`
+ * Creates a new COption_CVec_NetAddressZZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ CResult_SpendableOutputDescriptorDecodeErrorZ_clone(const struct LDKCResult_SpendableOutputDescriptorDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCOption_CVec_NetAddressZZ COption_CVec_NetAddressZZ_clone(const struct LDKCOption_CVec_NetAddressZZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_NoneNoneZ in the success state.
+ * Creates a new CResult_NetAddressDecodeErrorZ in the success state.
  */
-struct LDKCResult_NoneNoneZ CResult_NoneNoneZ_ok(void);
+struct LDKCResult_NetAddressDecodeErrorZ CResult_NetAddressDecodeErrorZ_ok(struct LDKNetAddress o);
 
 /**
- * Creates a new CResult_NoneNoneZ in the error state.
+ * Creates a new CResult_NetAddressDecodeErrorZ in the error state.
  */
-struct LDKCResult_NoneNoneZ CResult_NoneNoneZ_err(void);
+struct LDKCResult_NetAddressDecodeErrorZ CResult_NetAddressDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_NoneNoneZ_is_ok(const struct LDKCResult_NoneNoneZ *NONNULL_PTR o);
+bool CResult_NetAddressDecodeErrorZ_is_ok(const struct LDKCResult_NetAddressDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_NoneNoneZ.
+ * Frees any resources used by the CResult_NetAddressDecodeErrorZ.
  */
-void CResult_NoneNoneZ_free(struct LDKCResult_NoneNoneZ _res);
+void CResult_NetAddressDecodeErrorZ_free(struct LDKCResult_NetAddressDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_NoneNoneZ which has the same data as `orig`
+ * Creates a new CResult_NetAddressDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_NoneNoneZ CResult_NoneNoneZ_clone(const struct LDKCResult_NoneNoneZ *NONNULL_PTR orig);
+struct LDKCResult_NetAddressDecodeErrorZ CResult_NetAddressDecodeErrorZ_clone(const struct LDKCResult_NetAddressDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKC2Tuple_SignatureCVec_SignatureZZ C2Tuple_SignatureCVec_SignatureZZ_clone(const struct LDKC2Tuple_SignatureCVec_SignatureZZ *NONNULL_PTR orig);
+void CVec_UpdateAddHTLCZ_free(struct LDKCVec_UpdateAddHTLCZ _res);
 
 /**
- * Creates a new C2Tuple_SignatureCVec_SignatureZZ from the contained elements.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKC2Tuple_SignatureCVec_SignatureZZ C2Tuple_SignatureCVec_SignatureZZ_new(struct LDKSignature a, struct LDKCVec_SignatureZ b);
+void CVec_UpdateFulfillHTLCZ_free(struct LDKCVec_UpdateFulfillHTLCZ _res);
 
 /**
- * Frees any resources used by the C2Tuple_SignatureCVec_SignatureZZ.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void C2Tuple_SignatureCVec_SignatureZZ_free(struct LDKC2Tuple_SignatureCVec_SignatureZZ _res);
+void CVec_UpdateFailHTLCZ_free(struct LDKCVec_UpdateFailHTLCZ _res);
 
 /**
- * Creates a new CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ in the success state.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_ok(struct LDKC2Tuple_SignatureCVec_SignatureZZ o);
+void CVec_UpdateFailMalformedHTLCZ_free(struct LDKCVec_UpdateFailMalformedHTLCZ _res);
 
 /**
- * Creates a new CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ in the error state.
+ * Creates a new CResult_AcceptChannelDecodeErrorZ in the success state.
  */
-struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_err(void);
+struct LDKCResult_AcceptChannelDecodeErrorZ CResult_AcceptChannelDecodeErrorZ_ok(struct LDKAcceptChannel o);
+
+/**
+ * Creates a new CResult_AcceptChannelDecodeErrorZ in the error state.
+ */
+struct LDKCResult_AcceptChannelDecodeErrorZ CResult_AcceptChannelDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_is_ok(const struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ *NONNULL_PTR o);
+bool CResult_AcceptChannelDecodeErrorZ_is_ok(const struct LDKCResult_AcceptChannelDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ.
+ * Frees any resources used by the CResult_AcceptChannelDecodeErrorZ.
  */
-void CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_free(struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ _res);
+void CResult_AcceptChannelDecodeErrorZ_free(struct LDKCResult_AcceptChannelDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ which has the same data as `orig`
+ * Creates a new CResult_AcceptChannelDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ_clone(const struct LDKCResult_C2Tuple_SignatureCVec_SignatureZZNoneZ *NONNULL_PTR orig);
+struct LDKCResult_AcceptChannelDecodeErrorZ CResult_AcceptChannelDecodeErrorZ_clone(const struct LDKCResult_AcceptChannelDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_SignatureNoneZ in the success state.
+ * Creates a new CResult_AnnouncementSignaturesDecodeErrorZ in the success state.
  */
-struct LDKCResult_SignatureNoneZ CResult_SignatureNoneZ_ok(struct LDKSignature o);
+struct LDKCResult_AnnouncementSignaturesDecodeErrorZ CResult_AnnouncementSignaturesDecodeErrorZ_ok(struct LDKAnnouncementSignatures o);
 
 /**
- * Creates a new CResult_SignatureNoneZ in the error state.
+ * Creates a new CResult_AnnouncementSignaturesDecodeErrorZ in the error state.
  */
-struct LDKCResult_SignatureNoneZ CResult_SignatureNoneZ_err(void);
+struct LDKCResult_AnnouncementSignaturesDecodeErrorZ CResult_AnnouncementSignaturesDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_SignatureNoneZ_is_ok(const struct LDKCResult_SignatureNoneZ *NONNULL_PTR o);
+bool CResult_AnnouncementSignaturesDecodeErrorZ_is_ok(const struct LDKCResult_AnnouncementSignaturesDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_SignatureNoneZ.
+ * Frees any resources used by the CResult_AnnouncementSignaturesDecodeErrorZ.
  */
-void CResult_SignatureNoneZ_free(struct LDKCResult_SignatureNoneZ _res);
+void CResult_AnnouncementSignaturesDecodeErrorZ_free(struct LDKCResult_AnnouncementSignaturesDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_SignatureNoneZ which has the same data as `orig`
+ * Creates a new CResult_AnnouncementSignaturesDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_SignatureNoneZ CResult_SignatureNoneZ_clone(const struct LDKCResult_SignatureNoneZ *NONNULL_PTR orig);
+struct LDKCResult_AnnouncementSignaturesDecodeErrorZ CResult_AnnouncementSignaturesDecodeErrorZ_clone(const struct LDKCResult_AnnouncementSignaturesDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_SignDecodeErrorZ in the success state.
+ * Creates a new CResult_ChannelReestablishDecodeErrorZ in the success state.
  */
-struct LDKCResult_SignDecodeErrorZ CResult_SignDecodeErrorZ_ok(struct LDKSign o);
+struct LDKCResult_ChannelReestablishDecodeErrorZ CResult_ChannelReestablishDecodeErrorZ_ok(struct LDKChannelReestablish o);
 
 /**
- * Creates a new CResult_SignDecodeErrorZ in the error state.
+ * Creates a new CResult_ChannelReestablishDecodeErrorZ in the error state.
  */
-struct LDKCResult_SignDecodeErrorZ CResult_SignDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_ChannelReestablishDecodeErrorZ CResult_ChannelReestablishDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_SignDecodeErrorZ_is_ok(const struct LDKCResult_SignDecodeErrorZ *NONNULL_PTR o);
+bool CResult_ChannelReestablishDecodeErrorZ_is_ok(const struct LDKCResult_ChannelReestablishDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_SignDecodeErrorZ.
+ * Frees any resources used by the CResult_ChannelReestablishDecodeErrorZ.
  */
-void CResult_SignDecodeErrorZ_free(struct LDKCResult_SignDecodeErrorZ _res);
+void CResult_ChannelReestablishDecodeErrorZ_free(struct LDKCResult_ChannelReestablishDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_SignDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_ChannelReestablishDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_SignDecodeErrorZ CResult_SignDecodeErrorZ_clone(const struct LDKCResult_SignDecodeErrorZ *NONNULL_PTR orig);
-
-/**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
- */
-void CVec_u8Z_free(struct LDKCVec_u8Z _res);
+struct LDKCResult_ChannelReestablishDecodeErrorZ CResult_ChannelReestablishDecodeErrorZ_clone(const struct LDKCResult_ChannelReestablishDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_RecoverableSignatureNoneZ in the success state.
+ * Creates a new CResult_ClosingSignedDecodeErrorZ in the success state.
  */
-struct LDKCResult_RecoverableSignatureNoneZ CResult_RecoverableSignatureNoneZ_ok(struct LDKRecoverableSignature o);
+struct LDKCResult_ClosingSignedDecodeErrorZ CResult_ClosingSignedDecodeErrorZ_ok(struct LDKClosingSigned o);
 
 /**
- * Creates a new CResult_RecoverableSignatureNoneZ in the error state.
+ * Creates a new CResult_ClosingSignedDecodeErrorZ in the error state.
  */
-struct LDKCResult_RecoverableSignatureNoneZ CResult_RecoverableSignatureNoneZ_err(void);
+struct LDKCResult_ClosingSignedDecodeErrorZ CResult_ClosingSignedDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_RecoverableSignatureNoneZ_is_ok(const struct LDKCResult_RecoverableSignatureNoneZ *NONNULL_PTR o);
+bool CResult_ClosingSignedDecodeErrorZ_is_ok(const struct LDKCResult_ClosingSignedDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_RecoverableSignatureNoneZ.
+ * Frees any resources used by the CResult_ClosingSignedDecodeErrorZ.
  */
-void CResult_RecoverableSignatureNoneZ_free(struct LDKCResult_RecoverableSignatureNoneZ _res);
+void CResult_ClosingSignedDecodeErrorZ_free(struct LDKCResult_ClosingSignedDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_RecoverableSignatureNoneZ which has the same data as `orig`
+ * Creates a new CResult_ClosingSignedDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_RecoverableSignatureNoneZ CResult_RecoverableSignatureNoneZ_clone(const struct LDKCResult_RecoverableSignatureNoneZ *NONNULL_PTR orig);
-
-/**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
- */
-void CVec_CVec_u8ZZ_free(struct LDKCVec_CVec_u8ZZ _res);
+struct LDKCResult_ClosingSignedDecodeErrorZ CResult_ClosingSignedDecodeErrorZ_clone(const struct LDKCResult_ClosingSignedDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_CVec_CVec_u8ZZNoneZ in the success state.
+ * Creates a new CResult_ClosingSignedFeeRangeDecodeErrorZ in the success state.
  */
-struct LDKCResult_CVec_CVec_u8ZZNoneZ CResult_CVec_CVec_u8ZZNoneZ_ok(struct LDKCVec_CVec_u8ZZ o);
+struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ CResult_ClosingSignedFeeRangeDecodeErrorZ_ok(struct LDKClosingSignedFeeRange o);
 
 /**
- * Creates a new CResult_CVec_CVec_u8ZZNoneZ in the error state.
+ * Creates a new CResult_ClosingSignedFeeRangeDecodeErrorZ in the error state.
  */
-struct LDKCResult_CVec_CVec_u8ZZNoneZ CResult_CVec_CVec_u8ZZNoneZ_err(void);
+struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ CResult_ClosingSignedFeeRangeDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_CVec_CVec_u8ZZNoneZ_is_ok(const struct LDKCResult_CVec_CVec_u8ZZNoneZ *NONNULL_PTR o);
+bool CResult_ClosingSignedFeeRangeDecodeErrorZ_is_ok(const struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_CVec_CVec_u8ZZNoneZ.
+ * Frees any resources used by the CResult_ClosingSignedFeeRangeDecodeErrorZ.
  */
-void CResult_CVec_CVec_u8ZZNoneZ_free(struct LDKCResult_CVec_CVec_u8ZZNoneZ _res);
+void CResult_ClosingSignedFeeRangeDecodeErrorZ_free(struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_CVec_CVec_u8ZZNoneZ which has the same data as `orig`
+ * Creates a new CResult_ClosingSignedFeeRangeDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_CVec_CVec_u8ZZNoneZ CResult_CVec_CVec_u8ZZNoneZ_clone(const struct LDKCResult_CVec_CVec_u8ZZNoneZ *NONNULL_PTR orig);
+struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ CResult_ClosingSignedFeeRangeDecodeErrorZ_clone(const struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_InMemorySignerDecodeErrorZ in the success state.
+ * Creates a new CResult_CommitmentSignedDecodeErrorZ in the success state.
  */
-struct LDKCResult_InMemorySignerDecodeErrorZ CResult_InMemorySignerDecodeErrorZ_ok(struct LDKInMemorySigner o);
+struct LDKCResult_CommitmentSignedDecodeErrorZ CResult_CommitmentSignedDecodeErrorZ_ok(struct LDKCommitmentSigned o);
 
 /**
- * Creates a new CResult_InMemorySignerDecodeErrorZ in the error state.
+ * Creates a new CResult_CommitmentSignedDecodeErrorZ in the error state.
  */
-struct LDKCResult_InMemorySignerDecodeErrorZ CResult_InMemorySignerDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_CommitmentSignedDecodeErrorZ CResult_CommitmentSignedDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_InMemorySignerDecodeErrorZ_is_ok(const struct LDKCResult_InMemorySignerDecodeErrorZ *NONNULL_PTR o);
+bool CResult_CommitmentSignedDecodeErrorZ_is_ok(const struct LDKCResult_CommitmentSignedDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_InMemorySignerDecodeErrorZ.
+ * Frees any resources used by the CResult_CommitmentSignedDecodeErrorZ.
  */
-void CResult_InMemorySignerDecodeErrorZ_free(struct LDKCResult_InMemorySignerDecodeErrorZ _res);
+void CResult_CommitmentSignedDecodeErrorZ_free(struct LDKCResult_CommitmentSignedDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_InMemorySignerDecodeErrorZ which has the same data as `orig`
+ * Creates a new CResult_CommitmentSignedDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_InMemorySignerDecodeErrorZ CResult_InMemorySignerDecodeErrorZ_clone(const struct LDKCResult_InMemorySignerDecodeErrorZ *NONNULL_PTR orig);
-
-/**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
- */
-void CVec_TxOutZ_free(struct LDKCVec_TxOutZ _res);
+struct LDKCResult_CommitmentSignedDecodeErrorZ CResult_CommitmentSignedDecodeErrorZ_clone(const struct LDKCResult_CommitmentSignedDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_TransactionNoneZ in the success state.
+ * Creates a new CResult_FundingCreatedDecodeErrorZ in the success state.
  */
-struct LDKCResult_TransactionNoneZ CResult_TransactionNoneZ_ok(struct LDKTransaction o);
+struct LDKCResult_FundingCreatedDecodeErrorZ CResult_FundingCreatedDecodeErrorZ_ok(struct LDKFundingCreated o);
 
 /**
- * Creates a new CResult_TransactionNoneZ in the error state.
+ * Creates a new CResult_FundingCreatedDecodeErrorZ in the error state.
  */
-struct LDKCResult_TransactionNoneZ CResult_TransactionNoneZ_err(void);
+struct LDKCResult_FundingCreatedDecodeErrorZ CResult_FundingCreatedDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_TransactionNoneZ_is_ok(const struct LDKCResult_TransactionNoneZ *NONNULL_PTR o);
+bool CResult_FundingCreatedDecodeErrorZ_is_ok(const struct LDKCResult_FundingCreatedDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_TransactionNoneZ.
+ * Frees any resources used by the CResult_FundingCreatedDecodeErrorZ.
  */
-void CResult_TransactionNoneZ_free(struct LDKCResult_TransactionNoneZ _res);
+void CResult_FundingCreatedDecodeErrorZ_free(struct LDKCResult_FundingCreatedDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_TransactionNoneZ which has the same data as `orig`
+ * Creates a new CResult_FundingCreatedDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_TransactionNoneZ CResult_TransactionNoneZ_clone(const struct LDKCResult_TransactionNoneZ *NONNULL_PTR orig);
-
-/**
- * Creates a new C2Tuple_BlockHashChannelMonitorZ from the contained elements.
- */
-struct LDKC2Tuple_BlockHashChannelMonitorZ C2Tuple_BlockHashChannelMonitorZ_new(struct LDKThirtyTwoBytes a, struct LDKChannelMonitor b);
+struct LDKCResult_FundingCreatedDecodeErrorZ CResult_FundingCreatedDecodeErrorZ_clone(const struct LDKCResult_FundingCreatedDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the C2Tuple_BlockHashChannelMonitorZ.
+ * Creates a new CResult_FundingSignedDecodeErrorZ in the success state.
  */
-void C2Tuple_BlockHashChannelMonitorZ_free(struct LDKC2Tuple_BlockHashChannelMonitorZ _res);
+struct LDKCResult_FundingSignedDecodeErrorZ CResult_FundingSignedDecodeErrorZ_ok(struct LDKFundingSigned o);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_FundingSignedDecodeErrorZ in the error state.
  */
-void CVec_C2Tuple_BlockHashChannelMonitorZZ_free(struct LDKCVec_C2Tuple_BlockHashChannelMonitorZZ _res);
+struct LDKCResult_FundingSignedDecodeErrorZ CResult_FundingSignedDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_ok(struct LDKCVec_C2Tuple_BlockHashChannelMonitorZZ o);
+bool CResult_FundingSignedDecodeErrorZ_is_ok(const struct LDKCResult_FundingSignedDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ in the error state.
+ * Frees any resources used by the CResult_FundingSignedDecodeErrorZ.
  */
-struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_err(enum LDKIOError e);
+void CResult_FundingSignedDecodeErrorZ_free(struct LDKCResult_FundingSignedDecodeErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_FundingSignedDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-bool CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_is_ok(const struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ *NONNULL_PTR o);
+struct LDKCResult_FundingSignedDecodeErrorZ CResult_FundingSignedDecodeErrorZ_clone(const struct LDKCResult_FundingSignedDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ.
+ * Creates a new CResult_FundingLockedDecodeErrorZ in the success state.
  */
-void CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ_free(struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ _res);
+struct LDKCResult_FundingLockedDecodeErrorZ CResult_FundingLockedDecodeErrorZ_ok(struct LDKFundingLocked o);
 
 /**
- * Constructs a new COption_u16Z containing a u16
+ * Creates a new CResult_FundingLockedDecodeErrorZ in the error state.
  */
-struct LDKCOption_u16Z COption_u16Z_some(uint16_t o);
+struct LDKCResult_FundingLockedDecodeErrorZ CResult_FundingLockedDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Constructs a new COption_u16Z containing nothing
+ * Checks if the given object is currently in the success state
  */
-struct LDKCOption_u16Z COption_u16Z_none(void);
+bool CResult_FundingLockedDecodeErrorZ_is_ok(const struct LDKCResult_FundingLockedDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources associated with the u16, if we are in the Some state
+ * Frees any resources used by the CResult_FundingLockedDecodeErrorZ.
  */
-void COption_u16Z_free(struct LDKCOption_u16Z _res);
+void CResult_FundingLockedDecodeErrorZ_free(struct LDKCResult_FundingLockedDecodeErrorZ _res);
 
 /**
- * Creates a new COption_u16Z which has the same data as `orig`
+ * Creates a new CResult_FundingLockedDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCOption_u16Z COption_u16Z_clone(const struct LDKCOption_u16Z *NONNULL_PTR orig);
+struct LDKCResult_FundingLockedDecodeErrorZ CResult_FundingLockedDecodeErrorZ_clone(const struct LDKCResult_FundingLockedDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_NoneAPIErrorZ in the success state.
+ * Creates a new CResult_InitDecodeErrorZ in the success state.
  */
-struct LDKCResult_NoneAPIErrorZ CResult_NoneAPIErrorZ_ok(void);
+struct LDKCResult_InitDecodeErrorZ CResult_InitDecodeErrorZ_ok(struct LDKInit o);
 
 /**
- * Creates a new CResult_NoneAPIErrorZ in the error state.
+ * Creates a new CResult_InitDecodeErrorZ in the error state.
  */
-struct LDKCResult_NoneAPIErrorZ CResult_NoneAPIErrorZ_err(struct LDKAPIError e);
+struct LDKCResult_InitDecodeErrorZ CResult_InitDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_NoneAPIErrorZ_is_ok(const struct LDKCResult_NoneAPIErrorZ *NONNULL_PTR o);
+bool CResult_InitDecodeErrorZ_is_ok(const struct LDKCResult_InitDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_NoneAPIErrorZ.
+ * Frees any resources used by the CResult_InitDecodeErrorZ.
  */
-void CResult_NoneAPIErrorZ_free(struct LDKCResult_NoneAPIErrorZ _res);
+void CResult_InitDecodeErrorZ_free(struct LDKCResult_InitDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_NoneAPIErrorZ which has the same data as `orig`
+ * Creates a new CResult_InitDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_NoneAPIErrorZ CResult_NoneAPIErrorZ_clone(const struct LDKCResult_NoneAPIErrorZ *NONNULL_PTR orig);
-
-/**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
- */
-void CVec_CResult_NoneAPIErrorZZ_free(struct LDKCVec_CResult_NoneAPIErrorZZ _res);
-
-/**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
- */
-void CVec_APIErrorZ_free(struct LDKCVec_APIErrorZ _res);
+struct LDKCResult_InitDecodeErrorZ CResult_InitDecodeErrorZ_clone(const struct LDKCResult_InitDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult__u832APIErrorZ in the success state.
+ * Creates a new CResult_OpenChannelDecodeErrorZ in the success state.
  */
-struct LDKCResult__u832APIErrorZ CResult__u832APIErrorZ_ok(struct LDKThirtyTwoBytes o);
+struct LDKCResult_OpenChannelDecodeErrorZ CResult_OpenChannelDecodeErrorZ_ok(struct LDKOpenChannel o);
 
 /**
- * Creates a new CResult__u832APIErrorZ in the error state.
+ * Creates a new CResult_OpenChannelDecodeErrorZ in the error state.
  */
-struct LDKCResult__u832APIErrorZ CResult__u832APIErrorZ_err(struct LDKAPIError e);
+struct LDKCResult_OpenChannelDecodeErrorZ CResult_OpenChannelDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult__u832APIErrorZ_is_ok(const struct LDKCResult__u832APIErrorZ *NONNULL_PTR o);
+bool CResult_OpenChannelDecodeErrorZ_is_ok(const struct LDKCResult_OpenChannelDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult__u832APIErrorZ.
+ * Frees any resources used by the CResult_OpenChannelDecodeErrorZ.
  */
-void CResult__u832APIErrorZ_free(struct LDKCResult__u832APIErrorZ _res);
+void CResult_OpenChannelDecodeErrorZ_free(struct LDKCResult_OpenChannelDecodeErrorZ _res);
 
 /**
- * Creates a new CResult__u832APIErrorZ which has the same data as `orig`
+ * Creates a new CResult_OpenChannelDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult__u832APIErrorZ CResult__u832APIErrorZ_clone(const struct LDKCResult__u832APIErrorZ *NONNULL_PTR orig);
+struct LDKCResult_OpenChannelDecodeErrorZ CResult_OpenChannelDecodeErrorZ_clone(const struct LDKCResult_OpenChannelDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PaymentIdPaymentSendFailureZ in the success state.
+ * Creates a new CResult_RevokeAndACKDecodeErrorZ in the success state.
  */
-struct LDKCResult_PaymentIdPaymentSendFailureZ CResult_PaymentIdPaymentSendFailureZ_ok(struct LDKThirtyTwoBytes o);
+struct LDKCResult_RevokeAndACKDecodeErrorZ CResult_RevokeAndACKDecodeErrorZ_ok(struct LDKRevokeAndACK o);
 
 /**
- * Creates a new CResult_PaymentIdPaymentSendFailureZ in the error state.
+ * Creates a new CResult_RevokeAndACKDecodeErrorZ in the error state.
  */
-struct LDKCResult_PaymentIdPaymentSendFailureZ CResult_PaymentIdPaymentSendFailureZ_err(struct LDKPaymentSendFailure e);
+struct LDKCResult_RevokeAndACKDecodeErrorZ CResult_RevokeAndACKDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PaymentIdPaymentSendFailureZ_is_ok(const struct LDKCResult_PaymentIdPaymentSendFailureZ *NONNULL_PTR o);
+bool CResult_RevokeAndACKDecodeErrorZ_is_ok(const struct LDKCResult_RevokeAndACKDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PaymentIdPaymentSendFailureZ.
+ * Frees any resources used by the CResult_RevokeAndACKDecodeErrorZ.
  */
-void CResult_PaymentIdPaymentSendFailureZ_free(struct LDKCResult_PaymentIdPaymentSendFailureZ _res);
+void CResult_RevokeAndACKDecodeErrorZ_free(struct LDKCResult_RevokeAndACKDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PaymentIdPaymentSendFailureZ which has the same data as `orig`
+ * Creates a new CResult_RevokeAndACKDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PaymentIdPaymentSendFailureZ CResult_PaymentIdPaymentSendFailureZ_clone(const struct LDKCResult_PaymentIdPaymentSendFailureZ *NONNULL_PTR orig);
+struct LDKCResult_RevokeAndACKDecodeErrorZ CResult_RevokeAndACKDecodeErrorZ_clone(const struct LDKCResult_RevokeAndACKDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_NonePaymentSendFailureZ in the success state.
+ * Creates a new CResult_ShutdownDecodeErrorZ in the success state.
  */
-struct LDKCResult_NonePaymentSendFailureZ CResult_NonePaymentSendFailureZ_ok(void);
+struct LDKCResult_ShutdownDecodeErrorZ CResult_ShutdownDecodeErrorZ_ok(struct LDKShutdown o);
 
 /**
- * Creates a new CResult_NonePaymentSendFailureZ in the error state.
+ * Creates a new CResult_ShutdownDecodeErrorZ in the error state.
  */
-struct LDKCResult_NonePaymentSendFailureZ CResult_NonePaymentSendFailureZ_err(struct LDKPaymentSendFailure e);
+struct LDKCResult_ShutdownDecodeErrorZ CResult_ShutdownDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_NonePaymentSendFailureZ_is_ok(const struct LDKCResult_NonePaymentSendFailureZ *NONNULL_PTR o);
+bool CResult_ShutdownDecodeErrorZ_is_ok(const struct LDKCResult_ShutdownDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_NonePaymentSendFailureZ.
+ * Frees any resources used by the CResult_ShutdownDecodeErrorZ.
  */
-void CResult_NonePaymentSendFailureZ_free(struct LDKCResult_NonePaymentSendFailureZ _res);
+void CResult_ShutdownDecodeErrorZ_free(struct LDKCResult_ShutdownDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_NonePaymentSendFailureZ which has the same data as `orig`
+ * Creates a new CResult_ShutdownDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_NonePaymentSendFailureZ CResult_NonePaymentSendFailureZ_clone(const struct LDKCResult_NonePaymentSendFailureZ *NONNULL_PTR orig);
+struct LDKCResult_ShutdownDecodeErrorZ CResult_ShutdownDecodeErrorZ_clone(const struct LDKCResult_ShutdownDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_UpdateFailHTLCDecodeErrorZ in the success state.
  */
-struct LDKC2Tuple_PaymentHashPaymentIdZ C2Tuple_PaymentHashPaymentIdZ_clone(const struct LDKC2Tuple_PaymentHashPaymentIdZ *NONNULL_PTR orig);
+struct LDKCResult_UpdateFailHTLCDecodeErrorZ CResult_UpdateFailHTLCDecodeErrorZ_ok(struct LDKUpdateFailHTLC o);
 
 /**
- * Creates a new C2Tuple_PaymentHashPaymentIdZ from the contained elements.
+ * Creates a new CResult_UpdateFailHTLCDecodeErrorZ in the error state.
  */
-struct LDKC2Tuple_PaymentHashPaymentIdZ C2Tuple_PaymentHashPaymentIdZ_new(struct LDKThirtyTwoBytes a, struct LDKThirtyTwoBytes b);
+struct LDKCResult_UpdateFailHTLCDecodeErrorZ CResult_UpdateFailHTLCDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Frees any resources used by the C2Tuple_PaymentHashPaymentIdZ.
+ * Checks if the given object is currently in the success state
  */
-void C2Tuple_PaymentHashPaymentIdZ_free(struct LDKC2Tuple_PaymentHashPaymentIdZ _res);
+bool CResult_UpdateFailHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFailHTLCDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ in the success state.
+ * Frees any resources used by the CResult_UpdateFailHTLCDecodeErrorZ.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_ok(struct LDKC2Tuple_PaymentHashPaymentIdZ o);
+void CResult_UpdateFailHTLCDecodeErrorZ_free(struct LDKCResult_UpdateFailHTLCDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ in the error state.
+ * Creates a new CResult_UpdateFailHTLCDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_err(struct LDKPaymentSendFailure e);
+struct LDKCResult_UpdateFailHTLCDecodeErrorZ CResult_UpdateFailHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateFailHTLCDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_UpdateFailMalformedHTLCDecodeErrorZ in the success state.
  */
-bool CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_is_ok(const struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ *NONNULL_PTR o);
+struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ CResult_UpdateFailMalformedHTLCDecodeErrorZ_ok(struct LDKUpdateFailMalformedHTLC o);
 
 /**
- * Frees any resources used by the CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ.
+ * Creates a new CResult_UpdateFailMalformedHTLCDecodeErrorZ in the error state.
  */
-void CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_free(struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ _res);
+struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ CResult_UpdateFailMalformedHTLCDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ CResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ_clone(const struct LDKCResult_C2Tuple_PaymentHashPaymentIdZPaymentSendFailureZ *NONNULL_PTR orig);
+bool CResult_UpdateFailMalformedHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Frees any resources used by the CResult_UpdateFailMalformedHTLCDecodeErrorZ.
  */
-void CVec_NetAddressZ_free(struct LDKCVec_NetAddressZ _res);
+void CResult_UpdateFailMalformedHTLCDecodeErrorZ_free(struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ _res);
 
 /**
- * Creates a new tuple which has the same data as `orig`
+ * Creates a new CResult_UpdateFailMalformedHTLCDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKC2Tuple_PaymentHashPaymentSecretZ C2Tuple_PaymentHashPaymentSecretZ_clone(const struct LDKC2Tuple_PaymentHashPaymentSecretZ *NONNULL_PTR orig);
-
-/**
- * Creates a new C2Tuple_PaymentHashPaymentSecretZ from the contained elements.
- */
-struct LDKC2Tuple_PaymentHashPaymentSecretZ C2Tuple_PaymentHashPaymentSecretZ_new(struct LDKThirtyTwoBytes a, struct LDKThirtyTwoBytes b);
-
-/**
- * Frees any resources used by the C2Tuple_PaymentHashPaymentSecretZ.
- */
-void C2Tuple_PaymentHashPaymentSecretZ_free(struct LDKC2Tuple_PaymentHashPaymentSecretZ _res);
+struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ CResult_UpdateFailMalformedHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ in the success state.
+ * Creates a new CResult_UpdateFeeDecodeErrorZ in the success state.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_ok(struct LDKC2Tuple_PaymentHashPaymentSecretZ o);
+struct LDKCResult_UpdateFeeDecodeErrorZ CResult_UpdateFeeDecodeErrorZ_ok(struct LDKUpdateFee o);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ in the error state.
+ * Creates a new CResult_UpdateFeeDecodeErrorZ in the error state.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_err(void);
+struct LDKCResult_UpdateFeeDecodeErrorZ CResult_UpdateFeeDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_is_ok(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ *NONNULL_PTR o);
+bool CResult_UpdateFeeDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFeeDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ.
+ * Frees any resources used by the CResult_UpdateFeeDecodeErrorZ.
  */
-void CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_free(struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ _res);
+void CResult_UpdateFeeDecodeErrorZ_free(struct LDKCResult_UpdateFeeDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ which has the same data as `orig`
+ * Creates a new CResult_UpdateFeeDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ CResult_C2Tuple_PaymentHashPaymentSecretZNoneZ_clone(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZNoneZ *NONNULL_PTR orig);
+struct LDKCResult_UpdateFeeDecodeErrorZ CResult_UpdateFeeDecodeErrorZ_clone(const struct LDKCResult_UpdateFeeDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ in the success state.
+ * Creates a new CResult_UpdateFulfillHTLCDecodeErrorZ in the success state.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_ok(struct LDKC2Tuple_PaymentHashPaymentSecretZ o);
+struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ CResult_UpdateFulfillHTLCDecodeErrorZ_ok(struct LDKUpdateFulfillHTLC o);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ in the error state.
+ * Creates a new CResult_UpdateFulfillHTLCDecodeErrorZ in the error state.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_err(struct LDKAPIError e);
+struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ CResult_UpdateFulfillHTLCDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_is_ok(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ *NONNULL_PTR o);
+bool CResult_UpdateFulfillHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ.
+ * Frees any resources used by the CResult_UpdateFulfillHTLCDecodeErrorZ.
  */
-void CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_free(struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ _res);
+void CResult_UpdateFulfillHTLCDecodeErrorZ_free(struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ which has the same data as `orig`
+ * Creates a new CResult_UpdateFulfillHTLCDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ CResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ_clone(const struct LDKCResult_C2Tuple_PaymentHashPaymentSecretZAPIErrorZ *NONNULL_PTR orig);
+struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ CResult_UpdateFulfillHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PaymentSecretNoneZ in the success state.
+ * Creates a new CResult_UpdateAddHTLCDecodeErrorZ in the success state.
  */
-struct LDKCResult_PaymentSecretNoneZ CResult_PaymentSecretNoneZ_ok(struct LDKThirtyTwoBytes o);
+struct LDKCResult_UpdateAddHTLCDecodeErrorZ CResult_UpdateAddHTLCDecodeErrorZ_ok(struct LDKUpdateAddHTLC o);
 
 /**
- * Creates a new CResult_PaymentSecretNoneZ in the error state.
+ * Creates a new CResult_UpdateAddHTLCDecodeErrorZ in the error state.
  */
-struct LDKCResult_PaymentSecretNoneZ CResult_PaymentSecretNoneZ_err(void);
+struct LDKCResult_UpdateAddHTLCDecodeErrorZ CResult_UpdateAddHTLCDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PaymentSecretNoneZ_is_ok(const struct LDKCResult_PaymentSecretNoneZ *NONNULL_PTR o);
+bool CResult_UpdateAddHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateAddHTLCDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PaymentSecretNoneZ.
+ * Frees any resources used by the CResult_UpdateAddHTLCDecodeErrorZ.
  */
-void CResult_PaymentSecretNoneZ_free(struct LDKCResult_PaymentSecretNoneZ _res);
+void CResult_UpdateAddHTLCDecodeErrorZ_free(struct LDKCResult_UpdateAddHTLCDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PaymentSecretNoneZ which has the same data as `orig`
+ * Creates a new CResult_UpdateAddHTLCDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PaymentSecretNoneZ CResult_PaymentSecretNoneZ_clone(const struct LDKCResult_PaymentSecretNoneZ *NONNULL_PTR orig);
+struct LDKCResult_UpdateAddHTLCDecodeErrorZ CResult_UpdateAddHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateAddHTLCDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PaymentSecretAPIErrorZ in the success state.
+ * Creates a new CResult_PingDecodeErrorZ in the success state.
  */
-struct LDKCResult_PaymentSecretAPIErrorZ CResult_PaymentSecretAPIErrorZ_ok(struct LDKThirtyTwoBytes o);
+struct LDKCResult_PingDecodeErrorZ CResult_PingDecodeErrorZ_ok(struct LDKPing o);
 
 /**
- * Creates a new CResult_PaymentSecretAPIErrorZ in the error state.
+ * Creates a new CResult_PingDecodeErrorZ in the error state.
  */
-struct LDKCResult_PaymentSecretAPIErrorZ CResult_PaymentSecretAPIErrorZ_err(struct LDKAPIError e);
+struct LDKCResult_PingDecodeErrorZ CResult_PingDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PaymentSecretAPIErrorZ_is_ok(const struct LDKCResult_PaymentSecretAPIErrorZ *NONNULL_PTR o);
+bool CResult_PingDecodeErrorZ_is_ok(const struct LDKCResult_PingDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PaymentSecretAPIErrorZ.
+ * Frees any resources used by the CResult_PingDecodeErrorZ.
  */
-void CResult_PaymentSecretAPIErrorZ_free(struct LDKCResult_PaymentSecretAPIErrorZ _res);
+void CResult_PingDecodeErrorZ_free(struct LDKCResult_PingDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PaymentSecretAPIErrorZ which has the same data as `orig`
+ * Creates a new CResult_PingDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PaymentSecretAPIErrorZ CResult_PaymentSecretAPIErrorZ_clone(const struct LDKCResult_PaymentSecretAPIErrorZ *NONNULL_PTR orig);
+struct LDKCResult_PingDecodeErrorZ CResult_PingDecodeErrorZ_clone(const struct LDKCResult_PingDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PaymentPreimageAPIErrorZ in the success state.
+ * Creates a new CResult_PongDecodeErrorZ in the success state.
  */
-struct LDKCResult_PaymentPreimageAPIErrorZ CResult_PaymentPreimageAPIErrorZ_ok(struct LDKThirtyTwoBytes o);
+struct LDKCResult_PongDecodeErrorZ CResult_PongDecodeErrorZ_ok(struct LDKPong o);
 
 /**
- * Creates a new CResult_PaymentPreimageAPIErrorZ in the error state.
+ * Creates a new CResult_PongDecodeErrorZ in the error state.
  */
-struct LDKCResult_PaymentPreimageAPIErrorZ CResult_PaymentPreimageAPIErrorZ_err(struct LDKAPIError e);
+struct LDKCResult_PongDecodeErrorZ CResult_PongDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PaymentPreimageAPIErrorZ_is_ok(const struct LDKCResult_PaymentPreimageAPIErrorZ *NONNULL_PTR o);
+bool CResult_PongDecodeErrorZ_is_ok(const struct LDKCResult_PongDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PaymentPreimageAPIErrorZ.
+ * Frees any resources used by the CResult_PongDecodeErrorZ.
  */
-void CResult_PaymentPreimageAPIErrorZ_free(struct LDKCResult_PaymentPreimageAPIErrorZ _res);
+void CResult_PongDecodeErrorZ_free(struct LDKCResult_PongDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PaymentPreimageAPIErrorZ which has the same data as `orig`
+ * Creates a new CResult_PongDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PaymentPreimageAPIErrorZ CResult_PaymentPreimageAPIErrorZ_clone(const struct LDKCResult_PaymentPreimageAPIErrorZ *NONNULL_PTR orig);
+struct LDKCResult_PongDecodeErrorZ CResult_PongDecodeErrorZ_clone(const struct LDKCResult_PongDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_UnsignedChannelAnnouncementDecodeErrorZ in the success state.
  */
-void CVec_ChannelMonitorZ_free(struct LDKCVec_ChannelMonitorZ _res);
+struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ CResult_UnsignedChannelAnnouncementDecodeErrorZ_ok(struct LDKUnsignedChannelAnnouncement o);
 
 /**
- * Creates a new C2Tuple_BlockHashChannelManagerZ from the contained elements.
+ * Creates a new CResult_UnsignedChannelAnnouncementDecodeErrorZ in the error state.
  */
-struct LDKC2Tuple_BlockHashChannelManagerZ C2Tuple_BlockHashChannelManagerZ_new(struct LDKThirtyTwoBytes a, struct LDKChannelManager b);
+struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ CResult_UnsignedChannelAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Frees any resources used by the C2Tuple_BlockHashChannelManagerZ.
+ * Checks if the given object is currently in the success state
  */
-void C2Tuple_BlockHashChannelManagerZ_free(struct LDKC2Tuple_BlockHashChannelManagerZ _res);
+bool CResult_UnsignedChannelAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_UnsignedChannelAnnouncementDecodeErrorZ.
  */
-struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_ok(struct LDKC2Tuple_BlockHashChannelManagerZ o);
+void CResult_UnsignedChannelAnnouncementDecodeErrorZ_free(struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ in the error state.
+ * Creates a new CResult_UnsignedChannelAnnouncementDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ CResult_UnsignedChannelAnnouncementDecodeErrorZ_clone(const struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_ChannelAnnouncementDecodeErrorZ in the success state.
  */
-bool CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_is_ok(const struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_ChannelAnnouncementDecodeErrorZ CResult_ChannelAnnouncementDecodeErrorZ_ok(struct LDKChannelAnnouncement o);
 
 /**
- * Frees any resources used by the CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ.
+ * Creates a new CResult_ChannelAnnouncementDecodeErrorZ in the error state.
  */
-void CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ_free(struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ _res);
+struct LDKCResult_ChannelAnnouncementDecodeErrorZ CResult_ChannelAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_ChannelConfigDecodeErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_ChannelConfigDecodeErrorZ CResult_ChannelConfigDecodeErrorZ_ok(struct LDKChannelConfig o);
+bool CResult_ChannelAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_ChannelAnnouncementDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_ChannelConfigDecodeErrorZ in the error state.
+ * Frees any resources used by the CResult_ChannelAnnouncementDecodeErrorZ.
  */
-struct LDKCResult_ChannelConfigDecodeErrorZ CResult_ChannelConfigDecodeErrorZ_err(struct LDKDecodeError e);
+void CResult_ChannelAnnouncementDecodeErrorZ_free(struct LDKCResult_ChannelAnnouncementDecodeErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_ChannelAnnouncementDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-bool CResult_ChannelConfigDecodeErrorZ_is_ok(const struct LDKCResult_ChannelConfigDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_ChannelAnnouncementDecodeErrorZ CResult_ChannelAnnouncementDecodeErrorZ_clone(const struct LDKCResult_ChannelAnnouncementDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the CResult_ChannelConfigDecodeErrorZ.
+ * Creates a new CResult_UnsignedChannelUpdateDecodeErrorZ in the success state.
  */
-void CResult_ChannelConfigDecodeErrorZ_free(struct LDKCResult_ChannelConfigDecodeErrorZ _res);
+struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ CResult_UnsignedChannelUpdateDecodeErrorZ_ok(struct LDKUnsignedChannelUpdate o);
 
 /**
- * Creates a new CResult_ChannelConfigDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_UnsignedChannelUpdateDecodeErrorZ in the error state.
  */
-struct LDKCResult_ChannelConfigDecodeErrorZ CResult_ChannelConfigDecodeErrorZ_clone(const struct LDKCResult_ChannelConfigDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ CResult_UnsignedChannelUpdateDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_OutPointDecodeErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_OutPointDecodeErrorZ CResult_OutPointDecodeErrorZ_ok(struct LDKOutPoint o);
+bool CResult_UnsignedChannelUpdateDecodeErrorZ_is_ok(const struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_OutPointDecodeErrorZ in the error state.
+ * Frees any resources used by the CResult_UnsignedChannelUpdateDecodeErrorZ.
  */
-struct LDKCResult_OutPointDecodeErrorZ CResult_OutPointDecodeErrorZ_err(struct LDKDecodeError e);
+void CResult_UnsignedChannelUpdateDecodeErrorZ_free(struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_UnsignedChannelUpdateDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-bool CResult_OutPointDecodeErrorZ_is_ok(const struct LDKCResult_OutPointDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ CResult_UnsignedChannelUpdateDecodeErrorZ_clone(const struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Frees any resources used by the CResult_OutPointDecodeErrorZ.
+ * Creates a new CResult_ChannelUpdateDecodeErrorZ in the success state.
  */
-void CResult_OutPointDecodeErrorZ_free(struct LDKCResult_OutPointDecodeErrorZ _res);
+struct LDKCResult_ChannelUpdateDecodeErrorZ CResult_ChannelUpdateDecodeErrorZ_ok(struct LDKChannelUpdate o);
 
 /**
- * Creates a new CResult_OutPointDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_ChannelUpdateDecodeErrorZ in the error state.
  */
-struct LDKCResult_OutPointDecodeErrorZ CResult_OutPointDecodeErrorZ_clone(const struct LDKCResult_OutPointDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_ChannelUpdateDecodeErrorZ CResult_ChannelUpdateDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Constructs a new COption_TypeZ containing a crate::lightning::ln::wire::Type
+ * Checks if the given object is currently in the success state
  */
-struct LDKCOption_TypeZ COption_TypeZ_some(struct LDKType o);
+bool CResult_ChannelUpdateDecodeErrorZ_is_ok(const struct LDKCResult_ChannelUpdateDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Constructs a new COption_TypeZ containing nothing
+ * Frees any resources used by the CResult_ChannelUpdateDecodeErrorZ.
  */
-struct LDKCOption_TypeZ COption_TypeZ_none(void);
+void CResult_ChannelUpdateDecodeErrorZ_free(struct LDKCResult_ChannelUpdateDecodeErrorZ _res);
 
 /**
- * Frees any resources associated with the crate::lightning::ln::wire::Type, if we are in the Some state
+ * Creates a new CResult_ChannelUpdateDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void COption_TypeZ_free(struct LDKCOption_TypeZ _res);
+struct LDKCResult_ChannelUpdateDecodeErrorZ CResult_ChannelUpdateDecodeErrorZ_clone(const struct LDKCResult_ChannelUpdateDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_COption_TypeZDecodeErrorZ in the success state.
+ * Creates a new CResult_ErrorMessageDecodeErrorZ in the success state.
  */
-struct LDKCResult_COption_TypeZDecodeErrorZ CResult_COption_TypeZDecodeErrorZ_ok(struct LDKCOption_TypeZ o);
+struct LDKCResult_ErrorMessageDecodeErrorZ CResult_ErrorMessageDecodeErrorZ_ok(struct LDKErrorMessage o);
 
 /**
- * Creates a new CResult_COption_TypeZDecodeErrorZ in the error state.
+ * Creates a new CResult_ErrorMessageDecodeErrorZ in the error state.
  */
-struct LDKCResult_COption_TypeZDecodeErrorZ CResult_COption_TypeZDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_ErrorMessageDecodeErrorZ CResult_ErrorMessageDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_COption_TypeZDecodeErrorZ_is_ok(const struct LDKCResult_COption_TypeZDecodeErrorZ *NONNULL_PTR o);
+bool CResult_ErrorMessageDecodeErrorZ_is_ok(const struct LDKCResult_ErrorMessageDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_COption_TypeZDecodeErrorZ.
+ * Frees any resources used by the CResult_ErrorMessageDecodeErrorZ.
  */
-void CResult_COption_TypeZDecodeErrorZ_free(struct LDKCResult_COption_TypeZDecodeErrorZ _res);
+void CResult_ErrorMessageDecodeErrorZ_free(struct LDKCResult_ErrorMessageDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PaymentIdPaymentErrorZ in the success state.
+ * Creates a new CResult_ErrorMessageDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PaymentIdPaymentErrorZ CResult_PaymentIdPaymentErrorZ_ok(struct LDKThirtyTwoBytes o);
+struct LDKCResult_ErrorMessageDecodeErrorZ CResult_ErrorMessageDecodeErrorZ_clone(const struct LDKCResult_ErrorMessageDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PaymentIdPaymentErrorZ in the error state.
+ * Creates a new CResult_UnsignedNodeAnnouncementDecodeErrorZ in the success state.
  */
-struct LDKCResult_PaymentIdPaymentErrorZ CResult_PaymentIdPaymentErrorZ_err(struct LDKPaymentError e);
+struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ CResult_UnsignedNodeAnnouncementDecodeErrorZ_ok(struct LDKUnsignedNodeAnnouncement o);
+
+/**
+ * Creates a new CResult_UnsignedNodeAnnouncementDecodeErrorZ in the error state.
+ */
+struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ CResult_UnsignedNodeAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PaymentIdPaymentErrorZ_is_ok(const struct LDKCResult_PaymentIdPaymentErrorZ *NONNULL_PTR o);
+bool CResult_UnsignedNodeAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PaymentIdPaymentErrorZ.
+ * Frees any resources used by the CResult_UnsignedNodeAnnouncementDecodeErrorZ.
  */
-void CResult_PaymentIdPaymentErrorZ_free(struct LDKCResult_PaymentIdPaymentErrorZ _res);
+void CResult_UnsignedNodeAnnouncementDecodeErrorZ_free(struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PaymentIdPaymentErrorZ which has the same data as `orig`
+ * Creates a new CResult_UnsignedNodeAnnouncementDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PaymentIdPaymentErrorZ CResult_PaymentIdPaymentErrorZ_clone(const struct LDKCResult_PaymentIdPaymentErrorZ *NONNULL_PTR orig);
+struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ CResult_UnsignedNodeAnnouncementDecodeErrorZ_clone(const struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_SiPrefixNoneZ in the success state.
+ * Creates a new CResult_NodeAnnouncementDecodeErrorZ in the success state.
  */
-struct LDKCResult_SiPrefixNoneZ CResult_SiPrefixNoneZ_ok(enum LDKSiPrefix o);
+struct LDKCResult_NodeAnnouncementDecodeErrorZ CResult_NodeAnnouncementDecodeErrorZ_ok(struct LDKNodeAnnouncement o);
 
 /**
- * Creates a new CResult_SiPrefixNoneZ in the error state.
+ * Creates a new CResult_NodeAnnouncementDecodeErrorZ in the error state.
  */
-struct LDKCResult_SiPrefixNoneZ CResult_SiPrefixNoneZ_err(void);
+struct LDKCResult_NodeAnnouncementDecodeErrorZ CResult_NodeAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_SiPrefixNoneZ_is_ok(const struct LDKCResult_SiPrefixNoneZ *NONNULL_PTR o);
+bool CResult_NodeAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_NodeAnnouncementDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_SiPrefixNoneZ.
+ * Frees any resources used by the CResult_NodeAnnouncementDecodeErrorZ.
  */
-void CResult_SiPrefixNoneZ_free(struct LDKCResult_SiPrefixNoneZ _res);
+void CResult_NodeAnnouncementDecodeErrorZ_free(struct LDKCResult_NodeAnnouncementDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_SiPrefixNoneZ which has the same data as `orig`
+ * Creates a new CResult_NodeAnnouncementDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_SiPrefixNoneZ CResult_SiPrefixNoneZ_clone(const struct LDKCResult_SiPrefixNoneZ *NONNULL_PTR orig);
+struct LDKCResult_NodeAnnouncementDecodeErrorZ CResult_NodeAnnouncementDecodeErrorZ_clone(const struct LDKCResult_NodeAnnouncementDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_InvoiceNoneZ in the success state.
+ * Creates a new CResult_QueryShortChannelIdsDecodeErrorZ in the success state.
  */
-struct LDKCResult_InvoiceNoneZ CResult_InvoiceNoneZ_ok(struct LDKInvoice o);
+struct LDKCResult_QueryShortChannelIdsDecodeErrorZ CResult_QueryShortChannelIdsDecodeErrorZ_ok(struct LDKQueryShortChannelIds o);
 
 /**
- * Creates a new CResult_InvoiceNoneZ in the error state.
+ * Creates a new CResult_QueryShortChannelIdsDecodeErrorZ in the error state.
  */
-struct LDKCResult_InvoiceNoneZ CResult_InvoiceNoneZ_err(void);
+struct LDKCResult_QueryShortChannelIdsDecodeErrorZ CResult_QueryShortChannelIdsDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_InvoiceNoneZ_is_ok(const struct LDKCResult_InvoiceNoneZ *NONNULL_PTR o);
+bool CResult_QueryShortChannelIdsDecodeErrorZ_is_ok(const struct LDKCResult_QueryShortChannelIdsDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_InvoiceNoneZ.
+ * Frees any resources used by the CResult_QueryShortChannelIdsDecodeErrorZ.
  */
-void CResult_InvoiceNoneZ_free(struct LDKCResult_InvoiceNoneZ _res);
+void CResult_QueryShortChannelIdsDecodeErrorZ_free(struct LDKCResult_QueryShortChannelIdsDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_InvoiceNoneZ which has the same data as `orig`
+ * Creates a new CResult_QueryShortChannelIdsDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_InvoiceNoneZ CResult_InvoiceNoneZ_clone(const struct LDKCResult_InvoiceNoneZ *NONNULL_PTR orig);
+struct LDKCResult_QueryShortChannelIdsDecodeErrorZ CResult_QueryShortChannelIdsDecodeErrorZ_clone(const struct LDKCResult_QueryShortChannelIdsDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_SignedRawInvoiceNoneZ in the success state.
+ * Creates a new CResult_ReplyShortChannelIdsEndDecodeErrorZ in the success state.
  */
-struct LDKCResult_SignedRawInvoiceNoneZ CResult_SignedRawInvoiceNoneZ_ok(struct LDKSignedRawInvoice o);
+struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ CResult_ReplyShortChannelIdsEndDecodeErrorZ_ok(struct LDKReplyShortChannelIdsEnd o);
 
 /**
- * Creates a new CResult_SignedRawInvoiceNoneZ in the error state.
+ * Creates a new CResult_ReplyShortChannelIdsEndDecodeErrorZ in the error state.
  */
-struct LDKCResult_SignedRawInvoiceNoneZ CResult_SignedRawInvoiceNoneZ_err(void);
+struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ CResult_ReplyShortChannelIdsEndDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_SignedRawInvoiceNoneZ_is_ok(const struct LDKCResult_SignedRawInvoiceNoneZ *NONNULL_PTR o);
+bool CResult_ReplyShortChannelIdsEndDecodeErrorZ_is_ok(const struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_SignedRawInvoiceNoneZ.
+ * Frees any resources used by the CResult_ReplyShortChannelIdsEndDecodeErrorZ.
  */
-void CResult_SignedRawInvoiceNoneZ_free(struct LDKCResult_SignedRawInvoiceNoneZ _res);
+void CResult_ReplyShortChannelIdsEndDecodeErrorZ_free(struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_SignedRawInvoiceNoneZ which has the same data as `orig`
+ * Creates a new CResult_ReplyShortChannelIdsEndDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_SignedRawInvoiceNoneZ CResult_SignedRawInvoiceNoneZ_clone(const struct LDKCResult_SignedRawInvoiceNoneZ *NONNULL_PTR orig);
+struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ CResult_ReplyShortChannelIdsEndDecodeErrorZ_clone(const struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_QueryChannelRangeDecodeErrorZ in the success state.
  */
-struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ C3Tuple_RawInvoice_u832InvoiceSignatureZ_clone(const struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ *NONNULL_PTR orig);
+struct LDKCResult_QueryChannelRangeDecodeErrorZ CResult_QueryChannelRangeDecodeErrorZ_ok(struct LDKQueryChannelRange o);
 
 /**
- * Creates a new C3Tuple_RawInvoice_u832InvoiceSignatureZ from the contained elements.
+ * Creates a new CResult_QueryChannelRangeDecodeErrorZ in the error state.
  */
-struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ C3Tuple_RawInvoice_u832InvoiceSignatureZ_new(struct LDKRawInvoice a, struct LDKThirtyTwoBytes b, struct LDKInvoiceSignature c);
+struct LDKCResult_QueryChannelRangeDecodeErrorZ CResult_QueryChannelRangeDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Frees any resources used by the C3Tuple_RawInvoice_u832InvoiceSignatureZ.
+ * Checks if the given object is currently in the success state
+ */
+bool CResult_QueryChannelRangeDecodeErrorZ_is_ok(const struct LDKCResult_QueryChannelRangeDecodeErrorZ *NONNULL_PTR o);
+
+/**
+ * Frees any resources used by the CResult_QueryChannelRangeDecodeErrorZ.
+ */
+void CResult_QueryChannelRangeDecodeErrorZ_free(struct LDKCResult_QueryChannelRangeDecodeErrorZ _res);
+
+/**
+ * Creates a new CResult_QueryChannelRangeDecodeErrorZ which has the same data as `orig`
+ * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-void C3Tuple_RawInvoice_u832InvoiceSignatureZ_free(struct LDKC3Tuple_RawInvoice_u832InvoiceSignatureZ _res);
+struct LDKCResult_QueryChannelRangeDecodeErrorZ CResult_QueryChannelRangeDecodeErrorZ_clone(const struct LDKCResult_QueryChannelRangeDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PayeePubKeyErrorZ in the success state.
+ * Creates a new CResult_ReplyChannelRangeDecodeErrorZ in the success state.
  */
-struct LDKCResult_PayeePubKeyErrorZ CResult_PayeePubKeyErrorZ_ok(struct LDKPayeePubKey o);
+struct LDKCResult_ReplyChannelRangeDecodeErrorZ CResult_ReplyChannelRangeDecodeErrorZ_ok(struct LDKReplyChannelRange o);
 
 /**
- * Creates a new CResult_PayeePubKeyErrorZ in the error state.
+ * Creates a new CResult_ReplyChannelRangeDecodeErrorZ in the error state.
  */
-struct LDKCResult_PayeePubKeyErrorZ CResult_PayeePubKeyErrorZ_err(enum LDKSecp256k1Error e);
+struct LDKCResult_ReplyChannelRangeDecodeErrorZ CResult_ReplyChannelRangeDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PayeePubKeyErrorZ_is_ok(const struct LDKCResult_PayeePubKeyErrorZ *NONNULL_PTR o);
+bool CResult_ReplyChannelRangeDecodeErrorZ_is_ok(const struct LDKCResult_ReplyChannelRangeDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PayeePubKeyErrorZ.
+ * Frees any resources used by the CResult_ReplyChannelRangeDecodeErrorZ.
  */
-void CResult_PayeePubKeyErrorZ_free(struct LDKCResult_PayeePubKeyErrorZ _res);
+void CResult_ReplyChannelRangeDecodeErrorZ_free(struct LDKCResult_ReplyChannelRangeDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PayeePubKeyErrorZ which has the same data as `orig`
+ * Creates a new CResult_ReplyChannelRangeDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PayeePubKeyErrorZ CResult_PayeePubKeyErrorZ_clone(const struct LDKCResult_PayeePubKeyErrorZ *NONNULL_PTR orig);
-
-/**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
- */
-void CVec_PrivateRouteZ_free(struct LDKCVec_PrivateRouteZ _res);
+struct LDKCResult_ReplyChannelRangeDecodeErrorZ CResult_ReplyChannelRangeDecodeErrorZ_clone(const struct LDKCResult_ReplyChannelRangeDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_PositiveTimestampCreationErrorZ in the success state.
+ * Creates a new CResult_GossipTimestampFilterDecodeErrorZ in the success state.
  */
-struct LDKCResult_PositiveTimestampCreationErrorZ CResult_PositiveTimestampCreationErrorZ_ok(struct LDKPositiveTimestamp o);
+struct LDKCResult_GossipTimestampFilterDecodeErrorZ CResult_GossipTimestampFilterDecodeErrorZ_ok(struct LDKGossipTimestampFilter o);
 
 /**
- * Creates a new CResult_PositiveTimestampCreationErrorZ in the error state.
+ * Creates a new CResult_GossipTimestampFilterDecodeErrorZ in the error state.
  */
-struct LDKCResult_PositiveTimestampCreationErrorZ CResult_PositiveTimestampCreationErrorZ_err(enum LDKCreationError e);
+struct LDKCResult_GossipTimestampFilterDecodeErrorZ CResult_GossipTimestampFilterDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PositiveTimestampCreationErrorZ_is_ok(const struct LDKCResult_PositiveTimestampCreationErrorZ *NONNULL_PTR o);
+bool CResult_GossipTimestampFilterDecodeErrorZ_is_ok(const struct LDKCResult_GossipTimestampFilterDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PositiveTimestampCreationErrorZ.
+ * Frees any resources used by the CResult_GossipTimestampFilterDecodeErrorZ.
  */
-void CResult_PositiveTimestampCreationErrorZ_free(struct LDKCResult_PositiveTimestampCreationErrorZ _res);
+void CResult_GossipTimestampFilterDecodeErrorZ_free(struct LDKCResult_GossipTimestampFilterDecodeErrorZ _res);
 
 /**
- * Creates a new CResult_PositiveTimestampCreationErrorZ which has the same data as `orig`
+ * Creates a new CResult_GossipTimestampFilterDecodeErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_PositiveTimestampCreationErrorZ CResult_PositiveTimestampCreationErrorZ_clone(const struct LDKCResult_PositiveTimestampCreationErrorZ *NONNULL_PTR orig);
+struct LDKCResult_GossipTimestampFilterDecodeErrorZ CResult_GossipTimestampFilterDecodeErrorZ_clone(const struct LDKCResult_GossipTimestampFilterDecodeErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_NoneSemanticErrorZ in the success state.
+ * Creates a new CResult_InvoiceSignOrCreationErrorZ in the success state.
  */
-struct LDKCResult_NoneSemanticErrorZ CResult_NoneSemanticErrorZ_ok(void);
+struct LDKCResult_InvoiceSignOrCreationErrorZ CResult_InvoiceSignOrCreationErrorZ_ok(struct LDKInvoice o);
 
 /**
- * Creates a new CResult_NoneSemanticErrorZ in the error state.
+ * Creates a new CResult_InvoiceSignOrCreationErrorZ in the error state.
  */
-struct LDKCResult_NoneSemanticErrorZ CResult_NoneSemanticErrorZ_err(enum LDKSemanticError e);
+struct LDKCResult_InvoiceSignOrCreationErrorZ CResult_InvoiceSignOrCreationErrorZ_err(struct LDKSignOrCreationError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_NoneSemanticErrorZ_is_ok(const struct LDKCResult_NoneSemanticErrorZ *NONNULL_PTR o);
+bool CResult_InvoiceSignOrCreationErrorZ_is_ok(const struct LDKCResult_InvoiceSignOrCreationErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_NoneSemanticErrorZ.
+ * Frees any resources used by the CResult_InvoiceSignOrCreationErrorZ.
  */
-void CResult_NoneSemanticErrorZ_free(struct LDKCResult_NoneSemanticErrorZ _res);
+void CResult_InvoiceSignOrCreationErrorZ_free(struct LDKCResult_InvoiceSignOrCreationErrorZ _res);
 
 /**
- * Creates a new CResult_NoneSemanticErrorZ which has the same data as `orig`
+ * Creates a new CResult_InvoiceSignOrCreationErrorZ which has the same data as `orig`
  * but with all dynamically-allocated buffers duplicated in new buffers.
  */
-struct LDKCResult_NoneSemanticErrorZ CResult_NoneSemanticErrorZ_clone(const struct LDKCResult_NoneSemanticErrorZ *NONNULL_PTR orig);
+struct LDKCResult_InvoiceSignOrCreationErrorZ CResult_InvoiceSignOrCreationErrorZ_clone(const struct LDKCResult_InvoiceSignOrCreationErrorZ *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_InvoiceSemanticErrorZ in the success state.
+ * Constructs a new COption_FilterZ containing a crate::lightning::chain::Filter
  */
-struct LDKCResult_InvoiceSemanticErrorZ CResult_InvoiceSemanticErrorZ_ok(struct LDKInvoice o);
+struct LDKCOption_FilterZ COption_FilterZ_some(struct LDKFilter o);
 
 /**
- * Creates a new CResult_InvoiceSemanticErrorZ in the error state.
+ * Constructs a new COption_FilterZ containing nothing
  */
-struct LDKCResult_InvoiceSemanticErrorZ CResult_InvoiceSemanticErrorZ_err(enum LDKSemanticError e);
+struct LDKCOption_FilterZ COption_FilterZ_none(void);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources associated with the crate::lightning::chain::Filter, if we are in the Some state
  */
-bool CResult_InvoiceSemanticErrorZ_is_ok(const struct LDKCResult_InvoiceSemanticErrorZ *NONNULL_PTR o);
+void COption_FilterZ_free(struct LDKCOption_FilterZ _res);
 
 /**
- * Frees any resources used by the CResult_InvoiceSemanticErrorZ.
+ * Creates a new CResult_LockedChannelMonitorNoneZ in the success state.
  */
-void CResult_InvoiceSemanticErrorZ_free(struct LDKCResult_InvoiceSemanticErrorZ _res);
+struct LDKCResult_LockedChannelMonitorNoneZ CResult_LockedChannelMonitorNoneZ_ok(struct LDKLockedChannelMonitor o);
 
 /**
- * Creates a new CResult_InvoiceSemanticErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_LockedChannelMonitorNoneZ in the error state.
  */
-struct LDKCResult_InvoiceSemanticErrorZ CResult_InvoiceSemanticErrorZ_clone(const struct LDKCResult_InvoiceSemanticErrorZ *NONNULL_PTR orig);
+struct LDKCResult_LockedChannelMonitorNoneZ CResult_LockedChannelMonitorNoneZ_err(void);
 
 /**
- * Creates a new CResult_DescriptionCreationErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_DescriptionCreationErrorZ CResult_DescriptionCreationErrorZ_ok(struct LDKDescription o);
+bool CResult_LockedChannelMonitorNoneZ_is_ok(const struct LDKCResult_LockedChannelMonitorNoneZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_DescriptionCreationErrorZ in the error state.
+ * Frees any resources used by the CResult_LockedChannelMonitorNoneZ.
  */
-struct LDKCResult_DescriptionCreationErrorZ CResult_DescriptionCreationErrorZ_err(enum LDKCreationError e);
+void CResult_LockedChannelMonitorNoneZ_free(struct LDKCResult_LockedChannelMonitorNoneZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-bool CResult_DescriptionCreationErrorZ_is_ok(const struct LDKCResult_DescriptionCreationErrorZ *NONNULL_PTR o);
+void CVec_OutPointZ_free(struct LDKCVec_OutPointZ _res);
 
 /**
- * Frees any resources used by the CResult_DescriptionCreationErrorZ.
+ * Calls the free function if one is set
  */
-void CResult_DescriptionCreationErrorZ_free(struct LDKCResult_DescriptionCreationErrorZ _res);
+void ChainReplay_free(struct LDKChainReplay this_ptr);
 
 /**
- * Creates a new CResult_DescriptionCreationErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the RestoredMonitor.
  */
-struct LDKCResult_DescriptionCreationErrorZ CResult_DescriptionCreationErrorZ_clone(const struct LDKCResult_DescriptionCreationErrorZ *NONNULL_PTR orig);
+void RestoredMonitor_free(struct LDKRestoredMonitor _res);
 
 /**
- * Creates a new CResult_ExpiryTimeCreationErrorZ in the success state.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_ExpiryTimeCreationErrorZ CResult_ExpiryTimeCreationErrorZ_ok(struct LDKExpiryTime o);
+void CVec_RestoredMonitorZ_free(struct LDKCVec_RestoredMonitorZ _res);
 
 /**
- * Creates a new CResult_ExpiryTimeCreationErrorZ in the error state.
+ * Frees any resources used by the RestoredNode, including the `ChannelManager`.
  */
-struct LDKCResult_ExpiryTimeCreationErrorZ CResult_ExpiryTimeCreationErrorZ_err(enum LDKCreationError e);
+void RestoredNode_free(struct LDKRestoredNode _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_RestoredNodeRestoreErrorZ in the success state.
  */
-bool CResult_ExpiryTimeCreationErrorZ_is_ok(const struct LDKCResult_ExpiryTimeCreationErrorZ *NONNULL_PTR o);
+struct LDKCResult_RestoredNodeRestoreErrorZ CResult_RestoredNodeRestoreErrorZ_ok(struct LDKRestoredNode o);
 
 /**
- * Frees any resources used by the CResult_ExpiryTimeCreationErrorZ.
+ * Creates a new CResult_RestoredNodeRestoreErrorZ in the error state.
  */
-void CResult_ExpiryTimeCreationErrorZ_free(struct LDKCResult_ExpiryTimeCreationErrorZ _res);
+struct LDKCResult_RestoredNodeRestoreErrorZ CResult_RestoredNodeRestoreErrorZ_err(enum LDKRestoreError e);
 
 /**
- * Creates a new CResult_ExpiryTimeCreationErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_ExpiryTimeCreationErrorZ CResult_ExpiryTimeCreationErrorZ_clone(const struct LDKCResult_ExpiryTimeCreationErrorZ *NONNULL_PTR orig);
+bool CResult_RestoredNodeRestoreErrorZ_is_ok(const struct LDKCResult_RestoredNodeRestoreErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_PrivateRouteCreationErrorZ in the success state.
+ * Frees any resources used by the CResult_RestoredNodeRestoreErrorZ.
  */
-struct LDKCResult_PrivateRouteCreationErrorZ CResult_PrivateRouteCreationErrorZ_ok(struct LDKPrivateRoute o);
+void CResult_RestoredNodeRestoreErrorZ_free(struct LDKCResult_RestoredNodeRestoreErrorZ _res);
 
 /**
- * Creates a new CResult_PrivateRouteCreationErrorZ in the error state.
+ * Restores a node from the data directory of this `FilesystemPersister`, which must have been
+ * written by `FilesystemPersister_persist_manager` and the `Persist` implementation of
+ * `FilesystemPersister`.
+ *
+ * On success, the `ChannelManager` is returned synced to the chain tip and every
+ * `ChannelMonitor` has been synced to the chain tip and moved into `chain_monitor`. The
+ * `chain_monitor` must thus not have been handed any `ChannelMonitor`s yet, and it must outlive
+ * the returned `ChannelManager`, which uses it as its `Watch`.
+ *
+ * `chain_replay` is called once for each `ChannelMonitor` and then once for the
+ * `ChannelManager`, each time from the best block that object was persisted at. If you use a
+ * `Filter` to fetch only relevant transactions, note that the `ChannelMonitor`s only register
+ * their outputs with it once they are moved into the `ChainMonitor`, so `chain_replay` must
+ * provide full blocks.
+ *
+ * If the data directory is empty, `RestoreError_NoPersistedState` is returned, and a new
+ * `ChannelManager` should be created instead.
+ *
+ * Everything is validated before the first `ChannelMonitor` is moved into `chain_monitor`, so on
+ * any error other than `RestoreError_MonitorWatchFailed` the `chain_monitor` is untouched. If
+ * `RestoreError_MonitorWatchFailed` is returned because its `Persist` implementation failed
+ * part-way through, `chain_monitor` may hold some of the `ChannelMonitor`s and must be discarded.
  */
-struct LDKCResult_PrivateRouteCreationErrorZ CResult_PrivateRouteCreationErrorZ_err(enum LDKCreationError e);
+MUST_USE_RES struct LDKCResult_RestoredNodeRestoreErrorZ FilesystemPersister_restore_node(const struct LDKFilesystemPersister *NONNULL_PTR this_arg, struct LDKKeysInterface keys_manager, struct LDKFeeEstimator fee_estimator, const struct LDKChainMonitor *NONNULL_PTR chain_monitor, struct LDKBroadcasterInterface tx_broadcaster, struct LDKLogger logger, struct LDKUserConfig default_config, const struct LDKChainReplay *NONNULL_PTR chain_replay);
 
 /**
- * Checks if the given object is currently in the success state
+ * Restores a node from the store of this `KVStorePersister`, exactly as
+ * [`FilesystemPersister_restore_node`] does from a data directory. See its documentation for
+ * details.
+ *
+ * Values are read through the persister's `KVStore`, so if it was built on top of an
+ * `EncryptedStore`, the `ChannelManager` and `ChannelMonitor`s are decrypted (and
+ * authenticated) as they are read, and any value which fails authentication is reported as
+ * `RestoreError_MonitorReadFailed` or `RestoreError_ManagerReadFailed`.
  */
-bool CResult_PrivateRouteCreationErrorZ_is_ok(const struct LDKCResult_PrivateRouteCreationErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_RestoredNodeRestoreErrorZ KVStorePersister_restore_node(const struct LDKKVStorePersister *NONNULL_PTR this_arg, struct LDKKeysInterface keys_manager, struct LDKFeeEstimator fee_estimator, const struct LDKChainMonitor *NONNULL_PTR chain_monitor, struct LDKBroadcasterInterface tx_broadcaster, struct LDKLogger logger, struct LDKUserConfig default_config, const struct LDKChainReplay *NONNULL_PTR chain_replay);
 
 /**
- * Frees any resources used by the CResult_PrivateRouteCreationErrorZ.
+ * Restores a node from this `UpdateLogPersister` and the `KVStorePersister` which stores the
+ * `ChannelManager` in the same `KVStore`, exactly as [`FilesystemPersister_restore_node`] does
+ * from a data directory. See its documentation for details.
+ *
+ * The `ChannelMonitor`s are read through the `UpdateLogPersister`, applying any logged
+ * `ChannelMonitorUpdate`s before they are handed to the `ChannelManager`, and a failure to
+ * apply one is reported as `RestoreError_MonitorReadFailed`. `manager_persister` is only used to
+ * read the `ChannelManager`.
  */
-void CResult_PrivateRouteCreationErrorZ_free(struct LDKCResult_PrivateRouteCreationErrorZ _res);
+MUST_USE_RES struct LDKCResult_RestoredNodeRestoreErrorZ UpdateLogPersister_restore_node(const struct LDKUpdateLogPersister *NONNULL_PTR this_arg, const struct LDKKVStorePersister *NONNULL_PTR manager_persister, struct LDKKeysInterface keys_manager, struct LDKFeeEstimator fee_estimator, const struct LDKChainMonitor *NONNULL_PTR chain_monitor, struct LDKBroadcasterInterface tx_broadcaster, struct LDKLogger logger, struct LDKUserConfig default_config, const struct LDKChainReplay *NONNULL_PTR chain_replay);
 
+#if defined(LDK_SQLITE)
 /**
- * Creates a new CResult_PrivateRouteCreationErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Restores a node from this `SqliteStore`, exactly as [`FilesystemPersister_restore_node`] does
+ * from a data directory. See its documentation for details.
+ *
+ * The `ChannelManager` must have been stored through a `KVStorePersister` on top of
+ * `SqliteStore_as_KVStore`, and the `ChannelMonitor`s through `SqliteStore_as_Persist`. Any
+ * stored `ChannelMonitorUpdate`s are applied to their `ChannelMonitor` before it is handed to
+ * the `ChannelManager`, and a failure to apply one is reported as
+ * `RestoreError_MonitorReadFailed`.
  */
-struct LDKCResult_PrivateRouteCreationErrorZ CResult_PrivateRouteCreationErrorZ_clone(const struct LDKCResult_PrivateRouteCreationErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_RestoredNodeRestoreErrorZ SqliteStore_restore_node(const struct LDKSqliteStore *NONNULL_PTR this_arg, struct LDKKeysInterface keys_manager, struct LDKFeeEstimator fee_estimator, const struct LDKChainMonitor *NONNULL_PTR chain_monitor, struct LDKBroadcasterInterface tx_broadcaster, struct LDKLogger logger, struct LDKUserConfig default_config, const struct LDKChainReplay *NONNULL_PTR chain_replay);
+#endif
 
 /**
- * Creates a new CResult_StringErrorZ in the success state.
+ * Creates a new CResult_CVec_u8ZIOErrorZ in the success state.
  */
-struct LDKCResult_StringErrorZ CResult_StringErrorZ_ok(struct LDKStr o);
+struct LDKCResult_CVec_u8ZIOErrorZ CResult_CVec_u8ZIOErrorZ_ok(struct LDKCVec_u8Z o);
 
 /**
- * Creates a new CResult_StringErrorZ in the error state.
+ * Creates a new CResult_CVec_u8ZIOErrorZ in the error state.
  */
-struct LDKCResult_StringErrorZ CResult_StringErrorZ_err(enum LDKSecp256k1Error e);
+struct LDKCResult_CVec_u8ZIOErrorZ CResult_CVec_u8ZIOErrorZ_err(enum LDKIOError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_StringErrorZ_is_ok(const struct LDKCResult_StringErrorZ *NONNULL_PTR o);
-
-/**
- * Frees any resources used by the CResult_StringErrorZ.
- */
-void CResult_StringErrorZ_free(struct LDKCResult_StringErrorZ _res);
+bool CResult_CVec_u8ZIOErrorZ_is_ok(const struct LDKCResult_CVec_u8ZIOErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_ChannelMonitorUpdateDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_CVec_u8ZIOErrorZ.
  */
-struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ CResult_ChannelMonitorUpdateDecodeErrorZ_ok(struct LDKChannelMonitorUpdate o);
+void CResult_CVec_u8ZIOErrorZ_free(struct LDKCResult_CVec_u8ZIOErrorZ _res);
 
 /**
- * Creates a new CResult_ChannelMonitorUpdateDecodeErrorZ in the error state.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ CResult_ChannelMonitorUpdateDecodeErrorZ_err(struct LDKDecodeError e);
+void CVec_StrZ_free(struct LDKCVec_StrZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_CVec_StrZIOErrorZ in the success state.
  */
-bool CResult_ChannelMonitorUpdateDecodeErrorZ_is_ok(const struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_CVec_StrZIOErrorZ CResult_CVec_StrZIOErrorZ_ok(struct LDKCVec_StrZ o);
 
 /**
- * Frees any resources used by the CResult_ChannelMonitorUpdateDecodeErrorZ.
+ * Creates a new CResult_CVec_StrZIOErrorZ in the error state.
  */
-void CResult_ChannelMonitorUpdateDecodeErrorZ_free(struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ _res);
+struct LDKCResult_CVec_StrZIOErrorZ CResult_CVec_StrZIOErrorZ_err(enum LDKIOError e);
 
 /**
- * Creates a new CResult_ChannelMonitorUpdateDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ CResult_ChannelMonitorUpdateDecodeErrorZ_clone(const struct LDKCResult_ChannelMonitorUpdateDecodeErrorZ *NONNULL_PTR orig);
+bool CResult_CVec_StrZIOErrorZ_is_ok(const struct LDKCResult_CVec_StrZIOErrorZ *NONNULL_PTR o);
 
 /**
- * Constructs a new COption_MonitorEventZ containing a crate::lightning::chain::channelmonitor::MonitorEvent
+ * Frees any resources used by the CResult_CVec_StrZIOErrorZ.
  */
-struct LDKCOption_MonitorEventZ COption_MonitorEventZ_some(struct LDKMonitorEvent o);
+void CResult_CVec_StrZIOErrorZ_free(struct LDKCResult_CVec_StrZIOErrorZ _res);
 
 /**
- * Constructs a new COption_MonitorEventZ containing nothing
+ * Calls the free function if one is set
  */
-struct LDKCOption_MonitorEventZ COption_MonitorEventZ_none(void);
+void KVStore_free(struct LDKKVStore this_ptr);
 
 /**
- * Frees any resources associated with the crate::lightning::chain::channelmonitor::MonitorEvent, if we are in the Some state
+ * Frees any resources used by the KVStorePersister, if is_owned is set and inner is non-NULL.
  */
-void COption_MonitorEventZ_free(struct LDKCOption_MonitorEventZ _res);
+void KVStorePersister_free(struct LDKKVStorePersister this_obj);
 
 /**
- * Creates a new COption_MonitorEventZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new KVStorePersister which stores everything in the given `store`.
  */
-struct LDKCOption_MonitorEventZ COption_MonitorEventZ_clone(const struct LDKCOption_MonitorEventZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKKVStorePersister KVStorePersister_new(struct LDKKVStore store);
 
 /**
- * Creates a new CResult_COption_MonitorEventZDecodeErrorZ in the success state.
+ * Read all `ChannelMonitor`s from the store.
  */
-struct LDKCResult_COption_MonitorEventZDecodeErrorZ CResult_COption_MonitorEventZDecodeErrorZ_ok(struct LDKCOption_MonitorEventZ o);
+MUST_USE_RES struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ KVStorePersister_read_channelmonitors(const struct LDKKVStorePersister *NONNULL_PTR this_arg, struct LDKKeysInterface keys_manager);
 
 /**
- * Creates a new CResult_COption_MonitorEventZDecodeErrorZ in the error state.
+ * Read the `ChannelManager` from the store. `arg` must reference all `ChannelMonitor`s, as
+ * returned by [`KVStorePersister_read_channelmonitors`].
+ *
+ * If no `ChannelManager` has been stored yet, a `DecodeError` wrapping an `IOError_NotFound`
+ * is returned.
  */
-struct LDKCResult_COption_MonitorEventZDecodeErrorZ CResult_COption_MonitorEventZDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ KVStorePersister_read_channel_manager(const struct LDKKVStorePersister *NONNULL_PTR this_arg, struct LDKChannelManagerReadArgs arg);
 
 /**
- * Checks if the given object is currently in the success state
+ * Writes the given `NetworkGraph` to the store.
  */
-bool CResult_COption_MonitorEventZDecodeErrorZ_is_ok(const struct LDKCResult_COption_MonitorEventZDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_NoneErrorZ KVStorePersister_persist_network_graph(const struct LDKKVStorePersister *NONNULL_PTR this_arg, const struct LDKNetworkGraph *NONNULL_PTR network_graph);
 
 /**
- * Frees any resources used by the CResult_COption_MonitorEventZDecodeErrorZ.
+ * Read the `NetworkGraph` from the store.
+ *
+ * If no `NetworkGraph` has been stored yet, a `DecodeError` wrapping an `IOError_NotFound` is
+ * returned.
  */
-void CResult_COption_MonitorEventZDecodeErrorZ_free(struct LDKCResult_COption_MonitorEventZDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_NetworkGraphDecodeErrorZ KVStorePersister_read_network_graph(const struct LDKKVStorePersister *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_COption_MonitorEventZDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Writes the given `Scorer` to the store.
  */
-struct LDKCResult_COption_MonitorEventZDecodeErrorZ CResult_COption_MonitorEventZDecodeErrorZ_clone(const struct LDKCResult_COption_MonitorEventZDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_NoneErrorZ KVStorePersister_persist_scorer(const struct LDKKVStorePersister *NONNULL_PTR this_arg, const struct LDKScorer *NONNULL_PTR scorer);
 
 /**
- * Creates a new CResult_HTLCUpdateDecodeErrorZ in the success state.
+ * Read the `Scorer` from the store.
+ *
+ * If no `Scorer` has been stored yet, a `DecodeError` wrapping an `IOError_NotFound` is
+ * returned.
  */
-struct LDKCResult_HTLCUpdateDecodeErrorZ CResult_HTLCUpdateDecodeErrorZ_ok(struct LDKHTLCUpdate o);
+MUST_USE_RES struct LDKCResult_ScorerDecodeErrorZ KVStorePersister_read_scorer(const struct LDKKVStorePersister *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_HTLCUpdateDecodeErrorZ in the error state.
+ * Constructs a new Persist which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned Persist must be freed before this_arg is
  */
-struct LDKCResult_HTLCUpdateDecodeErrorZ CResult_HTLCUpdateDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKPersist KVStorePersister_as_Persist(const struct LDKKVStorePersister *NONNULL_PTR this_arg);
 
 /**
- * Checks if the given object is currently in the success state
+ * Constructs a new ChannelManagerPersister which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned ChannelManagerPersister must be freed before this_arg is
  */
-bool CResult_HTLCUpdateDecodeErrorZ_is_ok(const struct LDKCResult_HTLCUpdateDecodeErrorZ *NONNULL_PTR o);
+struct LDKChannelManagerPersister KVStorePersister_as_ChannelManagerPersister(const struct LDKKVStorePersister *NONNULL_PTR this_arg);
 
 /**
- * Frees any resources used by the CResult_HTLCUpdateDecodeErrorZ.
+ * Frees any resources used by the FilesystemStore, if is_owned is set and inner is non-NULL.
  */
-void CResult_HTLCUpdateDecodeErrorZ_free(struct LDKCResult_HTLCUpdateDecodeErrorZ _res);
+void FilesystemStore_free(struct LDKFilesystemStore this_obj);
 
 /**
- * Creates a new CResult_HTLCUpdateDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new FilesystemStore which stores data in the directory at `data_dir`, creating it
+ * if needed on the first write.
  */
-struct LDKCResult_HTLCUpdateDecodeErrorZ CResult_HTLCUpdateDecodeErrorZ_clone(const struct LDKCResult_HTLCUpdateDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKFilesystemStore FilesystemStore_new(struct LDKStr data_dir);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Constructs a new KVStore which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned KVStore must be freed before this_arg is
  */
-struct LDKC2Tuple_OutPointScriptZ C2Tuple_OutPointScriptZ_clone(const struct LDKC2Tuple_OutPointScriptZ *NONNULL_PTR orig);
+struct LDKKVStore FilesystemStore_as_KVStore(const struct LDKFilesystemStore *NONNULL_PTR this_arg);
 
 /**
- * Creates a new C2Tuple_OutPointScriptZ from the contained elements.
+ * Frees any resources used by the EncryptedStore, if is_owned is set and inner is non-NULL.
  */
-struct LDKC2Tuple_OutPointScriptZ C2Tuple_OutPointScriptZ_new(struct LDKOutPoint a, struct LDKCVec_u8Z b);
+void EncryptedStore_free(struct LDKEncryptedStore this_obj);
 
 /**
- * Frees any resources used by the C2Tuple_OutPointScriptZ.
+ * Creates a new EncryptedStore which stores its encrypted values in `inner`.
+ *
+ * `seed` should be the same seed passed to `KeysManager_new`. The encryption key is derived
+ * from it independently of all other keys, and the seed itself is not retained.
  */
-void C2Tuple_OutPointScriptZ_free(struct LDKC2Tuple_OutPointScriptZ _res);
+MUST_USE_RES struct LDKEncryptedStore EncryptedStore_new(struct LDKKVStore inner, const uint8_t (*seed)[32]);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Constructs a new KVStore which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned KVStore must be freed before this_arg is
  */
-struct LDKC2Tuple_u32ScriptZ C2Tuple_u32ScriptZ_clone(const struct LDKC2Tuple_u32ScriptZ *NONNULL_PTR orig);
+struct LDKKVStore EncryptedStore_as_KVStore(const struct LDKEncryptedStore *NONNULL_PTR this_arg);
 
 /**
- * Creates a new C2Tuple_u32ScriptZ from the contained elements.
+ * Creates a new CResult_KVStorePersisterIOErrorZ in the success state.
  */
-struct LDKC2Tuple_u32ScriptZ C2Tuple_u32ScriptZ_new(uint32_t a, struct LDKCVec_u8Z b);
+struct LDKCResult_KVStorePersisterIOErrorZ CResult_KVStorePersisterIOErrorZ_ok(struct LDKKVStorePersister o);
 
 /**
- * Frees any resources used by the C2Tuple_u32ScriptZ.
+ * Creates a new CResult_KVStorePersisterIOErrorZ in the error state.
  */
-void C2Tuple_u32ScriptZ_free(struct LDKC2Tuple_u32ScriptZ _res);
+struct LDKCResult_KVStorePersisterIOErrorZ CResult_KVStorePersisterIOErrorZ_err(enum LDKIOError e);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Checks if the given object is currently in the success state
  */
-void CVec_C2Tuple_u32ScriptZZ_free(struct LDKCVec_C2Tuple_u32ScriptZZ _res);
+bool CResult_KVStorePersisterIOErrorZ_is_ok(const struct LDKCResult_KVStorePersisterIOErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the CResult_KVStorePersisterIOErrorZ.
  */
-struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ_clone(const struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ *NONNULL_PTR orig);
+void CResult_KVStorePersisterIOErrorZ_free(struct LDKCResult_KVStorePersisterIOErrorZ _res);
 
 /**
- * Creates a new C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ from the contained elements.
+ * Takes over the data directory of `persister`, returning a `KVStorePersister` which encrypts
+ * everything it writes there, in the same layout, as an `EncryptedStore` would. Use its
+ * `KVStorePersister_as_Persist` and `KVStorePersister_as_ChannelManagerPersister` in place of
+ * those of `persister`, and `KVStorePersister_restore_node` in place of
+ * `FilesystemPersister_restore_node`.
+ *
+ * The first time a data directory is wrapped, the `ChannelMonitor`s, `ChannelManager` and any
+ * other files `persister` wrote in plaintext are encrypted in place, after which an empty
+ * `encrypted_store` file marks the directory as encrypted. Any plaintext file found afterwards
+ * fails authentication on read. Note that plaintext files tampered with before the first call
+ * are encrypted as they are, and that the plaintext may still be recoverable from the disk.
+ *
+ * `seed` should be the same seed passed to `KeysManager_new`. Returns an error if the existing
+ * files could not be encrypted.
  */
-struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ_new(struct LDKThirtyTwoBytes a, struct LDKCVec_C2Tuple_u32ScriptZZ b);
+MUST_USE_RES struct LDKCResult_KVStorePersisterIOErrorZ EncryptedStore_wrap_filesystem_persister(struct LDKFilesystemPersister persister, const uint8_t (*seed)[32]);
 
 /**
- * Frees any resources used by the C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ.
+ * Frees any resources used by the UpdateLogPersister, if is_owned is set and inner is non-NULL.
  */
-void C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ_free(struct LDKC2Tuple_TxidCVec_C2Tuple_u32ScriptZZZ _res);
+void UpdateLogPersister_free(struct LDKUpdateLogPersister this_obj);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_UpdateLogPersisterIOErrorZ in the success state.
  */
-void CVec_C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZZ_free(struct LDKCVec_C2Tuple_TxidCVec_C2Tuple_u32ScriptZZZZ _res);
+struct LDKCResult_UpdateLogPersisterIOErrorZ CResult_UpdateLogPersisterIOErrorZ_ok(struct LDKUpdateLogPersister o);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new CResult_UpdateLogPersisterIOErrorZ in the error state.
  */
-void CVec_EventZ_free(struct LDKCVec_EventZ _res);
+struct LDKCResult_UpdateLogPersisterIOErrorZ CResult_UpdateLogPersisterIOErrorZ_err(enum LDKIOError e);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Checks if the given object is currently in the success state
  */
-void CVec_TransactionZ_free(struct LDKCVec_TransactionZ _res);
+bool CResult_UpdateLogPersisterIOErrorZ_is_ok(const struct LDKCResult_UpdateLogPersisterIOErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the CResult_UpdateLogPersisterIOErrorZ.
  */
-struct LDKC2Tuple_u32TxOutZ C2Tuple_u32TxOutZ_clone(const struct LDKC2Tuple_u32TxOutZ *NONNULL_PTR orig);
+void CResult_UpdateLogPersisterIOErrorZ_free(struct LDKCResult_UpdateLogPersisterIOErrorZ _res);
 
 /**
- * Creates a new C2Tuple_u32TxOutZ from the contained elements.
+ * Creates a new UpdateLogPersister which stores everything in the given `store`.
+ *
+ * Once `max_pending_updates` updates have been logged for a channel, the next update causes
+ * the full `ChannelMonitor` to be rewritten and the logged updates to be removed. Larger values
+ * reduce write volume at the cost of slower loading. A value of 0 rewrites the full
+ * `ChannelMonitor` on every update.
+ *
+ * Fails if the logged updates already in the store cannot be listed.
  */
-struct LDKC2Tuple_u32TxOutZ C2Tuple_u32TxOutZ_new(uint32_t a, struct LDKTxOut b);
+MUST_USE_RES struct LDKCResult_UpdateLogPersisterIOErrorZ UpdateLogPersister_new(struct LDKKVStore store, uintptr_t max_pending_updates);
 
 /**
- * Frees any resources used by the C2Tuple_u32TxOutZ.
+ * Read all `ChannelMonitor`s from the store, applying any logged `ChannelMonitorUpdate`s which
+ * were not yet compacted into the stored `ChannelMonitor`.
+ *
+ * The `broadcaster`, `fee_estimator` and `logger` are passed to `ChannelMonitor_update_monitor`
+ * when replaying updates.
  */
-void C2Tuple_u32TxOutZ_free(struct LDKC2Tuple_u32TxOutZ _res);
+MUST_USE_RES struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ UpdateLogPersister_read_channelmonitors(const struct LDKUpdateLogPersister *NONNULL_PTR this_arg, const struct LDKKeysInterface *NONNULL_PTR keys_manager, const struct LDKBroadcasterInterface *NONNULL_PTR broadcaster, const struct LDKFeeEstimator *NONNULL_PTR fee_estimator, const struct LDKLogger *NONNULL_PTR logger);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Constructs a new Persist which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned Persist must be freed before this_arg is
  */
-void CVec_C2Tuple_u32TxOutZZ_free(struct LDKCVec_C2Tuple_u32TxOutZZ _res);
+struct LDKPersist UpdateLogPersister_as_Persist(const struct LDKUpdateLogPersister *NONNULL_PTR this_arg);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Calls the free function if one is set
  */
-struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ_clone(const struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ *NONNULL_PTR orig);
+void MessageTracer_free(struct LDKMessageTracer this_ptr);
 
 /**
- * Creates a new C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ from the contained elements.
+ * Frees any resources used by the TracingMessageHandler, if is_owned is set and inner is non-NULL.
  */
-struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ_new(struct LDKThirtyTwoBytes a, struct LDKCVec_C2Tuple_u32TxOutZZ b);
+void TracingMessageHandler_free(struct LDKTracingMessageHandler this_obj);
 
 /**
- * Frees any resources used by the C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ.
+ * Creates a new TracingMessageHandler which reports messages to `tracer` before passing them on
+ * to `chan_handler`, `route_handler` and `custom_handler`.
+ *
+ * Pass the results of `TracingMessageHandler_as_ChannelMessageHandler` and
+ * `TracingMessageHandler_as_RoutingMessageHandler` to `MessageHandler_new`, and the result of
+ * `TracingMessageHandler_as_CustomMessageHandler` to `PeerManager_new`, in place of the wrapped
+ * handlers.
  */
-void C2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ_free(struct LDKC2Tuple_TxidCVec_C2Tuple_u32TxOutZZZ _res);
+MUST_USE_RES struct LDKTracingMessageHandler TracingMessageHandler_new(struct LDKChannelMessageHandler chan_handler, struct LDKRoutingMessageHandler route_handler, struct LDKCustomMessageHandler custom_handler, struct LDKMessageTracer tracer);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Constructs a new ChannelMessageHandler which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned ChannelMessageHandler must be freed before this_arg is
  */
-void CVec_TransactionOutputsZ_free(struct LDKCVec_TransactionOutputsZ _res);
+struct LDKChannelMessageHandler TracingMessageHandler_as_ChannelMessageHandler(const struct LDKTracingMessageHandler *NONNULL_PTR this_arg);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Constructs a new RoutingMessageHandler which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned RoutingMessageHandler must be freed before this_arg is
  */
-void CVec_BalanceZ_free(struct LDKCVec_BalanceZ _res);
+struct LDKRoutingMessageHandler TracingMessageHandler_as_RoutingMessageHandler(const struct LDKTracingMessageHandler *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ in the success state.
+ * Constructs a new CustomMessageHandler which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned CustomMessageHandler must be freed before this_arg is
  */
-struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_ok(struct LDKC2Tuple_BlockHashChannelMonitorZ o);
+struct LDKCustomMessageHandler TracingMessageHandler_as_CustomMessageHandler(const struct LDKTracingMessageHandler *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ in the error state.
+ * Frees any resources used by the MessageTraceFile, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_err(struct LDKDecodeError e);
+void MessageTraceFile_free(struct LDKMessageTraceFile this_obj);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_MessageTraceFileIOErrorZ in the success state.
  */
-bool CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_is_ok(const struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_MessageTraceFileIOErrorZ CResult_MessageTraceFileIOErrorZ_ok(struct LDKMessageTraceFile o);
 
 /**
- * Frees any resources used by the CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ.
+ * Creates a new CResult_MessageTraceFileIOErrorZ in the error state.
  */
-void CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ_free(struct LDKCResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ _res);
+struct LDKCResult_MessageTraceFileIOErrorZ CResult_MessageTraceFileIOErrorZ_err(enum LDKIOError e);
 
 /**
- * Creates a new CResult_NoneLightningErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_NoneLightningErrorZ CResult_NoneLightningErrorZ_ok(void);
+bool CResult_MessageTraceFileIOErrorZ_is_ok(const struct LDKCResult_MessageTraceFileIOErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_NoneLightningErrorZ in the error state.
+ * Frees any resources used by the CResult_MessageTraceFileIOErrorZ.
  */
-struct LDKCResult_NoneLightningErrorZ CResult_NoneLightningErrorZ_err(struct LDKLightningError e);
+void CResult_MessageTraceFileIOErrorZ_free(struct LDKCResult_MessageTraceFileIOErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Opens the trace file at `path`, creating it if it does not exist. New records are appended to
+ * any existing trace.
+ *
+ * The file starts with the 8 bytes `LDKTRACE` and a big-endian u16 version (currently 1),
+ * followed by one record per message, with all integers big-endian:
+ *  * u64 seconds and u32 nanoseconds since the UNIX epoch at which the message was traced,
+ *  * u8 direction, 0 for inbound and 1 for outbound,
+ *  * the 33-byte compressed node id of the peer,
+ *  * u16 message type,
+ *  * u32 payload length, followed by the payload.
+ *
+ * Each record is flushed as it is written. Failures to write are ignored, as the traced message
+ * is handled regardless.
  */
-bool CResult_NoneLightningErrorZ_is_ok(const struct LDKCResult_NoneLightningErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_MessageTraceFileIOErrorZ MessageTraceFile_new(struct LDKStr path);
 
 /**
- * Frees any resources used by the CResult_NoneLightningErrorZ.
+ * Constructs a new MessageTracer which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned MessageTracer must be freed before this_arg is
  */
-void CResult_NoneLightningErrorZ_free(struct LDKCResult_NoneLightningErrorZ _res);
+struct LDKMessageTracer MessageTraceFile_as_MessageTracer(const struct LDKMessageTraceFile *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_NoneLightningErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the PeerChannelEncryptor, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_NoneLightningErrorZ CResult_NoneLightningErrorZ_clone(const struct LDKCResult_NoneLightningErrorZ *NONNULL_PTR orig);
+void PeerChannelEncryptor_free(struct LDKPeerChannelEncryptor this_obj);
 
 /**
- * Creates a new C2Tuple_PublicKeyTypeZ from the contained elements.
+ * Creates a new CResult_CVec_u8ZNoiseErrorZ in the success state.
  */
-struct LDKC2Tuple_PublicKeyTypeZ C2Tuple_PublicKeyTypeZ_new(struct LDKPublicKey a, struct LDKType b);
+struct LDKCResult_CVec_u8ZNoiseErrorZ CResult_CVec_u8ZNoiseErrorZ_ok(struct LDKCVec_u8Z o);
 
 /**
- * Frees any resources used by the C2Tuple_PublicKeyTypeZ.
+ * Creates a new CResult_CVec_u8ZNoiseErrorZ in the error state.
  */
-void C2Tuple_PublicKeyTypeZ_free(struct LDKC2Tuple_PublicKeyTypeZ _res);
+struct LDKCResult_CVec_u8ZNoiseErrorZ CResult_CVec_u8ZNoiseErrorZ_err(enum LDKNoiseError e);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Checks if the given object is currently in the success state
  */
-void CVec_C2Tuple_PublicKeyTypeZZ_free(struct LDKCVec_C2Tuple_PublicKeyTypeZZ _res);
+bool CResult_CVec_u8ZNoiseErrorZ_is_ok(const struct LDKCResult_CVec_u8ZNoiseErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_boolLightningErrorZ in the success state.
+ * Frees any resources used by the CResult_CVec_u8ZNoiseErrorZ.
  */
-struct LDKCResult_boolLightningErrorZ CResult_boolLightningErrorZ_ok(bool o);
+void CResult_CVec_u8ZNoiseErrorZ_free(struct LDKCResult_CVec_u8ZNoiseErrorZ _res);
 
 /**
- * Creates a new CResult_boolLightningErrorZ in the error state.
+ * Creates a new CResult_PublicKeyNoiseErrorZ in the success state.
  */
-struct LDKCResult_boolLightningErrorZ CResult_boolLightningErrorZ_err(struct LDKLightningError e);
+struct LDKCResult_PublicKeyNoiseErrorZ CResult_PublicKeyNoiseErrorZ_ok(struct LDKPublicKey o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_PublicKeyNoiseErrorZ in the error state.
  */
-bool CResult_boolLightningErrorZ_is_ok(const struct LDKCResult_boolLightningErrorZ *NONNULL_PTR o);
+struct LDKCResult_PublicKeyNoiseErrorZ CResult_PublicKeyNoiseErrorZ_err(enum LDKNoiseError e);
 
 /**
- * Frees any resources used by the CResult_boolLightningErrorZ.
+ * Checks if the given object is currently in the success state
  */
-void CResult_boolLightningErrorZ_free(struct LDKCResult_boolLightningErrorZ _res);
+bool CResult_PublicKeyNoiseErrorZ_is_ok(const struct LDKCResult_PublicKeyNoiseErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_boolLightningErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the CResult_PublicKeyNoiseErrorZ.
  */
-struct LDKCResult_boolLightningErrorZ CResult_boolLightningErrorZ_clone(const struct LDKCResult_boolLightningErrorZ *NONNULL_PTR orig);
+void CResult_PublicKeyNoiseErrorZ_free(struct LDKCResult_PublicKeyNoiseErrorZ _res);
 
 /**
- * Creates a new tuple which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_u16NoiseErrorZ in the success state.
  */
-struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ_clone(const struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ *NONNULL_PTR orig);
+struct LDKCResult_u16NoiseErrorZ CResult_u16NoiseErrorZ_ok(uint16_t o);
 
 /**
- * Creates a new C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ from the contained elements.
+ * Creates a new CResult_u16NoiseErrorZ in the error state.
  */
-struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ_new(struct LDKChannelAnnouncement a, struct LDKChannelUpdate b, struct LDKChannelUpdate c);
+struct LDKCResult_u16NoiseErrorZ CResult_u16NoiseErrorZ_err(enum LDKNoiseError e);
 
 /**
- * Frees any resources used by the C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ.
+ * Checks if the given object is currently in the success state
  */
-void C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ_free(struct LDKC3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZ _res);
+bool CResult_u16NoiseErrorZ_is_ok(const struct LDKCResult_u16NoiseErrorZ *NONNULL_PTR o);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Frees any resources used by the CResult_u16NoiseErrorZ.
  */
-void CVec_C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZZ_free(struct LDKCVec_C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZZ _res);
+void CResult_u16NoiseErrorZ_free(struct LDKCResult_u16NoiseErrorZ _res);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new PeerChannelEncryptor which initiates a connection to the node with the given
+ * node id.
+ *
+ * `ephemeral_key` must be freshly generated from a secure random source for each connection. It
+ * is only taken as a parameter to allow reproducing test vectors.
  */
-void CVec_NodeAnnouncementZ_free(struct LDKCVec_NodeAnnouncementZ _res);
+MUST_USE_RES struct LDKPeerChannelEncryptor PeerChannelEncryptor_new_outbound(struct LDKPublicKey their_node_id, struct LDKSecretKey ephemeral_key);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Creates a new PeerChannelEncryptor which responds to a connection made to us, identified by
+ * `our_node_secret`.
  */
-void CVec_PublicKeyZ_free(struct LDKCVec_PublicKeyZ _res);
+MUST_USE_RES struct LDKPeerChannelEncryptor PeerChannelEncryptor_new_inbound(struct LDKSecretKey our_node_secret);
 
 /**
- * Creates a new CResult_CVec_u8ZPeerHandleErrorZ in the success state.
+ * Generates the 50-byte act one, which the initiator sends first.
+ *
+ * Fails with InvalidState unless this is a fresh outbound PeerChannelEncryptor.
  */
-struct LDKCResult_CVec_u8ZPeerHandleErrorZ CResult_CVec_u8ZPeerHandleErrorZ_ok(struct LDKCVec_u8Z o);
+MUST_USE_RES struct LDKCResult_CVec_u8ZNoiseErrorZ PeerChannelEncryptor_get_act_one(struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_CVec_u8ZPeerHandleErrorZ in the error state.
+ * Processes the 50-byte act one received by the responder, returning the 50-byte act two to
+ * send back.
+ *
+ * `ephemeral_key` must be freshly generated from a secure random source for each connection. It
+ * is only taken as a parameter to allow reproducing test vectors.
  */
-struct LDKCResult_CVec_u8ZPeerHandleErrorZ CResult_CVec_u8ZPeerHandleErrorZ_err(struct LDKPeerHandleError e);
+MUST_USE_RES struct LDKCResult_CVec_u8ZNoiseErrorZ PeerChannelEncryptor_process_act_one(struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg, struct LDKu8slice act_one, struct LDKSecretKey ephemeral_key);
 
 /**
- * Checks if the given object is currently in the success state
+ * Processes the 50-byte act two received by the initiator, returning the 66-byte act three to
+ * send back. `our_node_secret` is the initiator's static key, which is revealed to the
+ * responder in act three.
+ *
+ * Once this succeeds, the handshake is complete on the initiator's side and messages may be
+ * encrypted and decrypted.
  */
-bool CResult_CVec_u8ZPeerHandleErrorZ_is_ok(const struct LDKCResult_CVec_u8ZPeerHandleErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_CVec_u8ZNoiseErrorZ PeerChannelEncryptor_process_act_two(struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg, struct LDKu8slice act_two, struct LDKSecretKey our_node_secret);
 
 /**
- * Frees any resources used by the CResult_CVec_u8ZPeerHandleErrorZ.
+ * Processes the 66-byte act three received by the responder, returning the initiator's node
+ * id.
+ *
+ * Once this succeeds, the handshake is complete on the responder's side and messages may be
+ * encrypted and decrypted.
  */
-void CResult_CVec_u8ZPeerHandleErrorZ_free(struct LDKCResult_CVec_u8ZPeerHandleErrorZ _res);
+MUST_USE_RES struct LDKCResult_PublicKeyNoiseErrorZ PeerChannelEncryptor_process_act_three(struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg, struct LDKu8slice act_three);
 
 /**
- * Creates a new CResult_CVec_u8ZPeerHandleErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Returns true once the handshake has completed and messages may be encrypted and decrypted.
  */
-struct LDKCResult_CVec_u8ZPeerHandleErrorZ CResult_CVec_u8ZPeerHandleErrorZ_clone(const struct LDKCResult_CVec_u8ZPeerHandleErrorZ *NONNULL_PTR orig);
+MUST_USE_RES bool PeerChannelEncryptor_is_ready_for_encryption(const struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_NonePeerHandleErrorZ in the success state.
+ * Encrypts a message of at most 65535 bytes, returning the encrypted length header followed by
+ * the encrypted message, i.e. 34 bytes more than the message itself.
+ *
+ * `msg` is usually a serialized Lightning message starting with its two-byte type, but any
+ * bytes may be sent.
  */
-struct LDKCResult_NonePeerHandleErrorZ CResult_NonePeerHandleErrorZ_ok(void);
+MUST_USE_RES struct LDKCResult_CVec_u8ZNoiseErrorZ PeerChannelEncryptor_encrypt_message(struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg, struct LDKu8slice msg);
 
 /**
- * Creates a new CResult_NonePeerHandleErrorZ in the error state.
+ * Decrypts the 18-byte length header which precedes each message, returning the length of the
+ * message. The next `length + 16` bytes should then be passed to
+ * `PeerChannelEncryptor_decrypt_message`.
  */
-struct LDKCResult_NonePeerHandleErrorZ CResult_NonePeerHandleErrorZ_err(struct LDKPeerHandleError e);
+MUST_USE_RES struct LDKCResult_u16NoiseErrorZ PeerChannelEncryptor_decrypt_length_header(struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg, struct LDKu8slice header);
 
 /**
- * Checks if the given object is currently in the success state
+ * Decrypts a message, including its 16-byte authentication tag, following a length header
+ * decrypted with `PeerChannelEncryptor_decrypt_length_header`.
  */
-bool CResult_NonePeerHandleErrorZ_is_ok(const struct LDKCResult_NonePeerHandleErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_CVec_u8ZNoiseErrorZ PeerChannelEncryptor_decrypt_message(struct LDKPeerChannelEncryptor *NONNULL_PTR this_arg, struct LDKu8slice msg);
 
 /**
- * Frees any resources used by the CResult_NonePeerHandleErrorZ.
+ * Creates a new CResult_NoneCustomMessageErrorZ in the success state.
  */
-void CResult_NonePeerHandleErrorZ_free(struct LDKCResult_NonePeerHandleErrorZ _res);
+struct LDKCResult_NoneCustomMessageErrorZ CResult_NoneCustomMessageErrorZ_ok(void);
 
 /**
- * Creates a new CResult_NonePeerHandleErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_NoneCustomMessageErrorZ in the error state.
  */
-struct LDKCResult_NonePeerHandleErrorZ CResult_NonePeerHandleErrorZ_clone(const struct LDKCResult_NonePeerHandleErrorZ *NONNULL_PTR orig);
+struct LDKCResult_NoneCustomMessageErrorZ CResult_NoneCustomMessageErrorZ_err(enum LDKCustomMessageError e);
 
 /**
- * Creates a new CResult_boolPeerHandleErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_boolPeerHandleErrorZ CResult_boolPeerHandleErrorZ_ok(bool o);
+bool CResult_NoneCustomMessageErrorZ_is_ok(const struct LDKCResult_NoneCustomMessageErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_boolPeerHandleErrorZ in the error state.
+ * Frees any resources used by the CResult_NoneCustomMessageErrorZ.
  */
-struct LDKCResult_boolPeerHandleErrorZ CResult_boolPeerHandleErrorZ_err(struct LDKPeerHandleError e);
+void CResult_NoneCustomMessageErrorZ_free(struct LDKCResult_NoneCustomMessageErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Calls the free function if one is set
  */
-bool CResult_boolPeerHandleErrorZ_is_ok(const struct LDKCResult_boolPeerHandleErrorZ *NONNULL_PTR o);
+void CustomMessageCallback_free(struct LDKCustomMessageCallback this_ptr);
 
 /**
- * Frees any resources used by the CResult_boolPeerHandleErrorZ.
+ * Frees any resources used by the CustomMessageRegistry, if is_owned is set and inner is non-NULL.
  */
-void CResult_boolPeerHandleErrorZ_free(struct LDKCResult_boolPeerHandleErrorZ _res);
+void CustomMessageRegistry_free(struct LDKCustomMessageRegistry this_obj);
 
 /**
- * Creates a new CResult_boolPeerHandleErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CustomMessageRegistry with no registered message types.
  */
-struct LDKCResult_boolPeerHandleErrorZ CResult_boolPeerHandleErrorZ_clone(const struct LDKCResult_boolPeerHandleErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCustomMessageRegistry CustomMessageRegistry_new(void);
 
 /**
- * Creates a new CResult_NodeIdDecodeErrorZ in the success state.
+ * Registers `callback` to handle all messages of type `type_id` received from peers.
+ *
+ * Fails if `type_id` is below 32768 or a callback is already registered for it.
  */
-struct LDKCResult_NodeIdDecodeErrorZ CResult_NodeIdDecodeErrorZ_ok(struct LDKNodeId o);
+MUST_USE_RES struct LDKCResult_NoneCustomMessageErrorZ CustomMessageRegistry_register(const struct LDKCustomMessageRegistry *NONNULL_PTR this_arg, uint16_t type_id, struct LDKCustomMessageCallback callback);
 
 /**
- * Creates a new CResult_NodeIdDecodeErrorZ in the error state.
+ * Removes the callback registered for `type_id`, returning false if there was none.
+ *
+ * Later messages of this type are handled as unknown messages, i.e. ignored if `type_id` is odd
+ * and causing a disconnect if it is even.
  */
-struct LDKCResult_NodeIdDecodeErrorZ CResult_NodeIdDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES bool CustomMessageRegistry_unregister(const struct LDKCustomMessageRegistry *NONNULL_PTR this_arg, uint16_t type_id);
 
 /**
- * Checks if the given object is currently in the success state
+ * Queues a message of type `type_id` with the payload `data` to be sent to the peer with the
+ * given node id on the next `PeerManager_process_events` call. The message is dropped if we
+ * are not connected to the peer at that point.
+ *
+ * Peers which do not understand an even message type will disconnect from us upon receiving
+ * it, so even types should only be sent to peers known to support them.
+ *
+ * Fails if `type_id` is below 32768 or `data` is longer than 65533 bytes.
  */
-bool CResult_NodeIdDecodeErrorZ_is_ok(const struct LDKCResult_NodeIdDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_NoneCustomMessageErrorZ CustomMessageRegistry_send_custom(const struct LDKCustomMessageRegistry *NONNULL_PTR this_arg, struct LDKPublicKey node_id, uint16_t type_id, struct LDKu8slice data);
 
 /**
- * Frees any resources used by the CResult_NodeIdDecodeErrorZ.
+ * Constructs a new CustomMessageHandler which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned CustomMessageHandler must be freed before this_arg is
  */
-void CResult_NodeIdDecodeErrorZ_free(struct LDKCResult_NodeIdDecodeErrorZ _res);
+struct LDKCustomMessageHandler CustomMessageRegistry_as_CustomMessageHandler(const struct LDKCustomMessageRegistry *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_NodeIdDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the EventQueue, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_NodeIdDecodeErrorZ CResult_NodeIdDecodeErrorZ_clone(const struct LDKCResult_NodeIdDecodeErrorZ *NONNULL_PTR orig);
+void EventQueue_free(struct LDKEventQueue this_obj);
 
 /**
- * Creates a new CResult_COption_NetworkUpdateZDecodeErrorZ in the success state.
+ * Creates a new CResult_EventQueueIOErrorZ in the success state.
  */
-struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ CResult_COption_NetworkUpdateZDecodeErrorZ_ok(struct LDKCOption_NetworkUpdateZ o);
+struct LDKCResult_EventQueueIOErrorZ CResult_EventQueueIOErrorZ_ok(struct LDKEventQueue o);
 
 /**
- * Creates a new CResult_COption_NetworkUpdateZDecodeErrorZ in the error state.
+ * Creates a new CResult_EventQueueIOErrorZ in the error state.
  */
-struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ CResult_COption_NetworkUpdateZDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_EventQueueIOErrorZ CResult_EventQueueIOErrorZ_err(enum LDKIOError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_COption_NetworkUpdateZDecodeErrorZ_is_ok(const struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ *NONNULL_PTR o);
+bool CResult_EventQueueIOErrorZ_is_ok(const struct LDKCResult_EventQueueIOErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_COption_NetworkUpdateZDecodeErrorZ.
+ * Frees any resources used by the CResult_EventQueueIOErrorZ.
  */
-void CResult_COption_NetworkUpdateZDecodeErrorZ_free(struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ _res);
+void CResult_EventQueueIOErrorZ_free(struct LDKCResult_EventQueueIOErrorZ _res);
 
 /**
- * Creates a new CResult_COption_NetworkUpdateZDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new, empty EventQueue, which only holds events in memory.
+ *
+ * The `ChannelManager` must then only be persisted once the queue has been drained, see the
+ * module documentation.
  */
-struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ CResult_COption_NetworkUpdateZDecodeErrorZ_clone(const struct LDKCResult_COption_NetworkUpdateZDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKEventQueue EventQueue_new(void);
 
 /**
- * Constructs a new COption_AccessZ containing a crate::lightning::chain::Access
+ * Creates an EventQueue which stores each event in `store` until it is returned by
+ * `EventQueue_next`, loading any events which were still queued when it was last used.
+ *
+ * If storing an event fails, the write is retried every second, blocking the thread which is
+ * processing events, as LDK will not provide the event again. Events which could not be removed
+ * from `store` once returned are returned again after the next restart. Events which are only
+ * meaningful until a restart, such as `Event_FundingGenerationReady`, are dropped when loading.
  */
-struct LDKCOption_AccessZ COption_AccessZ_some(struct LDKAccess o);
+MUST_USE_RES struct LDKCResult_EventQueueIOErrorZ EventQueue_load(struct LDKKVStore store);
 
 /**
- * Constructs a new COption_AccessZ containing nothing
+ * Removes and returns the oldest queued event, removing it from the store as well if the queue
+ * was created with `EventQueue_load`.
+ *
+ * If the queue is empty, waits up to `timeout_millis` milliseconds for an event to be queued,
+ * returning None if none arrives in time. A timeout of 0 never blocks, while u64::MAX waits
+ * indefinitely.
  */
-struct LDKCOption_AccessZ COption_AccessZ_none(void);
+MUST_USE_RES struct LDKCOption_EventZ EventQueue_next(const struct LDKEventQueue *NONNULL_PTR this_arg, uint64_t timeout_millis);
 
 /**
- * Frees any resources associated with the crate::lightning::chain::Access, if we are in the Some state
+ * Returns the number of events currently queued.
  */
-void COption_AccessZ_free(struct LDKCOption_AccessZ _res);
+MUST_USE_RES uintptr_t EventQueue_len(const struct LDKEventQueue *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_DirectionalChannelInfoDecodeErrorZ in the success state.
+ * Returns a file descriptor which is readable while events are queued, or -1 if none could be
+ * created on this platform.
+ *
+ * The file descriptor is owned by the EventQueue and closed when it is freed. It must only be
+ * polled, never read from or written to; it is cleared once `EventQueue_next` empties the queue.
  */
-struct LDKCResult_DirectionalChannelInfoDecodeErrorZ CResult_DirectionalChannelInfoDecodeErrorZ_ok(struct LDKDirectionalChannelInfo o);
+MUST_USE_RES int32_t EventQueue_get_fd(const struct LDKEventQueue *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_DirectionalChannelInfoDecodeErrorZ in the error state.
+ * Constructs a new EventHandler which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned EventHandler must be freed before this_arg is
  */
-struct LDKCResult_DirectionalChannelInfoDecodeErrorZ CResult_DirectionalChannelInfoDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKEventHandler EventQueue_as_EventHandler(const struct LDKEventQueue *NONNULL_PTR this_arg);
 
 /**
- * Checks if the given object is currently in the success state
+ * Calls the free function if one is set
  */
-bool CResult_DirectionalChannelInfoDecodeErrorZ_is_ok(const struct LDKCResult_DirectionalChannelInfoDecodeErrorZ *NONNULL_PTR o);
+void WakeupCallback_free(struct LDKWakeupCallback this_ptr);
 
 /**
- * Frees any resources used by the CResult_DirectionalChannelInfoDecodeErrorZ.
+ * Frees any resources used by the ChannelManagerNotifier, if is_owned is set and inner is non-NULL.
+ *
+ * This stops the notifier's thread, blocking for up to 100 milliseconds until it notices.
  */
-void CResult_DirectionalChannelInfoDecodeErrorZ_free(struct LDKCResult_DirectionalChannelInfoDecodeErrorZ _res);
+void ChannelManagerNotifier_free(struct LDKChannelManagerNotifier this_obj);
 
 /**
- * Creates a new CResult_DirectionalChannelInfoDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Starts watching `channel_manager` for updates which need persisting, signalling the returned
+ * notifier's file descriptor (see `ChannelManagerNotifier_get_fd`) when one occurs.
+ *
+ * The notifier waits on the `ChannelManager` from an internal thread. LDK only guarantees to
+ * wake one waiter per update, so the `ChannelManager` must not also be waited on with
+ * `ChannelManager_await_persistable_update` or a `BackgroundProcessor`.
+ *
+ * The notifier must be freed before `channel_manager` is.
  */
-struct LDKCResult_DirectionalChannelInfoDecodeErrorZ CResult_DirectionalChannelInfoDecodeErrorZ_clone(const struct LDKCResult_DirectionalChannelInfoDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKChannelManagerNotifier ChannelManagerNotifier_new(const struct LDKChannelManager *NONNULL_PTR channel_manager);
 
 /**
- * Creates a new CResult_ChannelInfoDecodeErrorZ in the success state.
+ * Creates a notifier as `ChannelManagerNotifier_new` does, which additionally calls `callback`
+ * each time it is signalled after having been cleared.
  */
-struct LDKCResult_ChannelInfoDecodeErrorZ CResult_ChannelInfoDecodeErrorZ_ok(struct LDKChannelInfo o);
+MUST_USE_RES struct LDKChannelManagerNotifier ChannelManagerNotifier_new_with_callback(const struct LDKChannelManager *NONNULL_PTR channel_manager, struct LDKWakeupCallback callback);
 
 /**
- * Creates a new CResult_ChannelInfoDecodeErrorZ in the error state.
+ * Returns a file descriptor which is readable while the notifier is signalled, or -1 if none
+ * could be created on this platform.
+ *
+ * The file descriptor is owned by the notifier and closed when it is freed. It must only be
+ * polled, never read from or written to; use `ChannelManagerNotifier_clear` instead.
  */
-struct LDKCResult_ChannelInfoDecodeErrorZ CResult_ChannelInfoDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES int32_t ChannelManagerNotifier_get_fd(const struct LDKChannelManagerNotifier *NONNULL_PTR this_arg);
 
 /**
- * Checks if the given object is currently in the success state
+ * Returns whether the notifier is signalled, without clearing it.
  */
-bool CResult_ChannelInfoDecodeErrorZ_is_ok(const struct LDKCResult_ChannelInfoDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES bool ChannelManagerNotifier_is_signalled(const struct LDKChannelManagerNotifier *NONNULL_PTR this_arg);
 
 /**
- * Frees any resources used by the CResult_ChannelInfoDecodeErrorZ.
+ * Clears the notifier, returning whether it was signalled.
+ *
+ * Call this before persisting the `ChannelManager` and processing events, so that updates which
+ * happen while doing so signal the notifier again rather than being missed.
  */
-void CResult_ChannelInfoDecodeErrorZ_free(struct LDKCResult_ChannelInfoDecodeErrorZ _res);
+MUST_USE_RES bool ChannelManagerNotifier_clear(const struct LDKChannelManagerNotifier *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_ChannelInfoDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Signals the notifier, as if the `ChannelManager` needed persisting. Useful for waking the
+ * host when events are pending from elsewhere, such as the `ChainMonitor`.
  */
-struct LDKCResult_ChannelInfoDecodeErrorZ CResult_ChannelInfoDecodeErrorZ_clone(const struct LDKCResult_ChannelInfoDecodeErrorZ *NONNULL_PTR orig);
+void ChannelManagerNotifier_notify(const struct LDKChannelManagerNotifier *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_RoutingFeesDecodeErrorZ in the success state.
+ * Creates a new CResult_TransactionWalletErrorZ in the success state.
  */
-struct LDKCResult_RoutingFeesDecodeErrorZ CResult_RoutingFeesDecodeErrorZ_ok(struct LDKRoutingFees o);
+struct LDKCResult_TransactionWalletErrorZ CResult_TransactionWalletErrorZ_ok(struct LDKTransaction o);
 
 /**
- * Creates a new CResult_RoutingFeesDecodeErrorZ in the error state.
+ * Creates a new CResult_TransactionWalletErrorZ in the error state.
  */
-struct LDKCResult_RoutingFeesDecodeErrorZ CResult_RoutingFeesDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_TransactionWalletErrorZ CResult_TransactionWalletErrorZ_err(enum LDKWalletError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_RoutingFeesDecodeErrorZ_is_ok(const struct LDKCResult_RoutingFeesDecodeErrorZ *NONNULL_PTR o);
+bool CResult_TransactionWalletErrorZ_is_ok(const struct LDKCResult_TransactionWalletErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_RoutingFeesDecodeErrorZ.
+ * Frees any resources used by the CResult_TransactionWalletErrorZ.
  */
-void CResult_RoutingFeesDecodeErrorZ_free(struct LDKCResult_RoutingFeesDecodeErrorZ _res);
+void CResult_TransactionWalletErrorZ_free(struct LDKCResult_TransactionWalletErrorZ _res);
 
 /**
- * Creates a new CResult_RoutingFeesDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_OnChainWalletDecodeErrorZ in the success state.
  */
-struct LDKCResult_RoutingFeesDecodeErrorZ CResult_RoutingFeesDecodeErrorZ_clone(const struct LDKCResult_RoutingFeesDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_OnChainWalletDecodeErrorZ CResult_OnChainWalletDecodeErrorZ_ok(struct LDKOnChainWallet o);
 
 /**
- * Creates a new CResult_NodeAnnouncementInfoDecodeErrorZ in the success state.
+ * Creates a new CResult_OnChainWalletDecodeErrorZ in the error state.
  */
-struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ CResult_NodeAnnouncementInfoDecodeErrorZ_ok(struct LDKNodeAnnouncementInfo o);
+struct LDKCResult_OnChainWalletDecodeErrorZ CResult_OnChainWalletDecodeErrorZ_err(struct LDKDecodeError e);
 
 /**
- * Creates a new CResult_NodeAnnouncementInfoDecodeErrorZ in the error state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ CResult_NodeAnnouncementInfoDecodeErrorZ_err(struct LDKDecodeError e);
+bool CResult_OnChainWalletDecodeErrorZ_is_ok(const struct LDKCResult_OnChainWalletDecodeErrorZ *NONNULL_PTR o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the CResult_OnChainWalletDecodeErrorZ.
  */
-bool CResult_NodeAnnouncementInfoDecodeErrorZ_is_ok(const struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ *NONNULL_PTR o);
+void CResult_OnChainWalletDecodeErrorZ_free(struct LDKCResult_OnChainWalletDecodeErrorZ _res);
 
 /**
- * Frees any resources used by the CResult_NodeAnnouncementInfoDecodeErrorZ.
+ * Frees any resources used by the OnChainWallet, if is_owned is set and inner is non-NULL.
  */
-void CResult_NodeAnnouncementInfoDecodeErrorZ_free(struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ _res);
+void OnChainWallet_free(struct LDKOnChainWallet this_obj);
 
 /**
- * Creates a new CResult_NodeAnnouncementInfoDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new wallet from the same seed as passed to `KeysManager_new`, with no known outputs.
+ *
+ * Transactions are broadcast with `broadcaster`, and fees are taken from `fee_estimator`'s
+ * `Normal` target for payments and channel funding and its `Background` target for sweeps.
  */
-struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ CResult_NodeAnnouncementInfoDecodeErrorZ_clone(const struct LDKCResult_NodeAnnouncementInfoDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKOnChainWallet OnChainWallet_new(const uint8_t (*seed)[32], enum LDKNetwork network, struct LDKBroadcasterInterface broadcaster, struct LDKFeeEstimator fee_estimator);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Serialize the OnChainWallet object into a byte array which can be read by OnChainWallet_read
  */
-void CVec_u64Z_free(struct LDKCVec_u64Z _res);
+struct LDKCVec_u8Z OnChainWallet_write(const struct LDKOnChainWallet *NONNULL_PTR obj);
 
 /**
- * Creates a new CResult_NodeInfoDecodeErrorZ in the success state.
+ * Read an OnChainWallet from a byte array, created by OnChainWallet_write, given the same seed
+ * and network it was created with.
  */
-struct LDKCResult_NodeInfoDecodeErrorZ CResult_NodeInfoDecodeErrorZ_ok(struct LDKNodeInfo o);
+struct LDKCResult_OnChainWalletDecodeErrorZ OnChainWallet_read(struct LDKu8slice ser, const uint8_t (*seed)[32], enum LDKNetwork network, struct LDKBroadcasterInterface broadcaster, struct LDKFeeEstimator fee_estimator);
 
 /**
- * Creates a new CResult_NodeInfoDecodeErrorZ in the error state.
+ * Returns a new, unused receive address.
  */
-struct LDKCResult_NodeInfoDecodeErrorZ CResult_NodeInfoDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKStr OnChainWallet_get_new_address(const struct LDKOnChainWallet *NONNULL_PTR this_arg);
 
 /**
- * Checks if the given object is currently in the success state
+ * Returns the public output descriptor (with checksum) of the receive chain, or of the change
+ * chain if `internal` is set, eg for importing into a watch-only wallet.
+ *
+ * This does not cover the `KeysManager` destination and shutdown scripts.
  */
-bool CResult_NodeInfoDecodeErrorZ_is_ok(const struct LDKCResult_NodeInfoDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKStr OnChainWallet_get_descriptor(const struct LDKOnChainWallet *NONNULL_PTR this_arg, bool internal);
 
 /**
- * Frees any resources used by the CResult_NodeInfoDecodeErrorZ.
+ * Returns the total value of unspent outputs with at least `min_confirmations` confirmations.
+ *
+ * Outputs spent by transactions of ours which have not confirmed yet are not included, while
+ * the change of such transactions is included if `min_confirmations` is 0.
  */
-void CResult_NodeInfoDecodeErrorZ_free(struct LDKCResult_NodeInfoDecodeErrorZ _res);
+MUST_USE_RES uint64_t OnChainWallet_get_balance(const struct LDKOnChainWallet *NONNULL_PTR this_arg, uint32_t min_confirmations);
 
 /**
- * Creates a new CResult_NodeInfoDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Returns all scripts the wallet watches for incoming funds, which need to be registered with
+ * the chain source when using `OnChainWallet_as_Confirm`.
+ *
+ * The set grows as addresses are used, so this should be re-checked after each block.
  */
-struct LDKCResult_NodeInfoDecodeErrorZ CResult_NodeInfoDecodeErrorZ_clone(const struct LDKCResult_NodeInfoDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCVec_CVec_u8ZZ OnChainWallet_get_watched_scripts(const struct LDKOnChainWallet *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_NetworkGraphDecodeErrorZ in the success state.
+ * Sends `amount_sats` to `address` from confirmed funds and broadcasts the signed transaction,
+ * which is returned.
  */
-struct LDKCResult_NetworkGraphDecodeErrorZ CResult_NetworkGraphDecodeErrorZ_ok(struct LDKNetworkGraph o);
+MUST_USE_RES struct LDKCResult_TransactionWalletErrorZ OnChainWallet_send_to_address(const struct LDKOnChainWallet *NONNULL_PTR this_arg, struct LDKStr address, uint64_t amount_sats);
 
 /**
- * Creates a new CResult_NetworkGraphDecodeErrorZ in the error state.
+ * Creates a signed transaction paying `value_sats` to `output_script` from confirmed funds, as
+ * requested by `Event::FundingGenerationReady`, to be passed to
+ * `ChannelManager_funding_transaction_generated`.
+ *
+ * The transaction is not broadcast, the `ChannelManager` does so once the channel is set up. If
+ * it rejects the transaction, pass it to `OnChainWallet_abandon_transaction`.
  */
-struct LDKCResult_NetworkGraphDecodeErrorZ CResult_NetworkGraphDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_TransactionWalletErrorZ OnChainWallet_create_funding_transaction(const struct LDKOnChainWallet *NONNULL_PTR this_arg, struct LDKCVec_u8Z output_script, uint64_t value_sats);
 
 /**
- * Checks if the given object is currently in the success state
+ * Forgets a transaction created by the wallet which was never broadcast, making the outputs it
+ * spends available again.
+ *
+ * This must not be called for transactions which may still confirm.
  */
-bool CResult_NetworkGraphDecodeErrorZ_is_ok(const struct LDKCResult_NetworkGraphDecodeErrorZ *NONNULL_PTR o);
+void OnChainWallet_abandon_transaction(const struct LDKOnChainWallet *NONNULL_PTR this_arg, struct LDKTransaction tx);
 
 /**
- * Frees any resources used by the CResult_NetworkGraphDecodeErrorZ.
+ * Spends the outputs from an `Event::SpendableOutputs` to a wallet change address at the
+ * `Background` feerate and broadcasts the transaction, which is returned.
+ *
+ * Outputs which pay to scripts the wallet already watches are skipped. If the sweep fails, the
+ * descriptors should be stored and the sweep retried later.
  */
-void CResult_NetworkGraphDecodeErrorZ_free(struct LDKCResult_NetworkGraphDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_TransactionWalletErrorZ OnChainWallet_sweep_spendable_outputs(const struct LDKOnChainWallet *NONNULL_PTR this_arg, struct LDKCVec_SpendableOutputDescriptorZ descriptors);
 
 /**
- * Creates a new CResult_NetworkGraphDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Constructs a new Listen which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned Listen must be freed before this_arg is
  */
-struct LDKCResult_NetworkGraphDecodeErrorZ CResult_NetworkGraphDecodeErrorZ_clone(const struct LDKCResult_NetworkGraphDecodeErrorZ *NONNULL_PTR orig);
+struct LDKListen OnChainWallet_as_Listen(const struct LDKOnChainWallet *NONNULL_PTR this_arg);
 
 /**
- * Constructs a new COption_CVec_NetAddressZZ containing a crate::c_types::derived::CVec_NetAddressZ
+ * Constructs a new Confirm which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned Confirm must be freed before this_arg is
  */
-struct LDKCOption_CVec_NetAddressZZ COption_CVec_NetAddressZZ_some(struct LDKCVec_NetAddressZ o);
+struct LDKConfirm OnChainWallet_as_Confirm(const struct LDKOnChainWallet *NONNULL_PTR this_arg);
 
 /**
- * Constructs a new COption_CVec_NetAddressZZ containing nothing
+ * Wraps `inner` in an [`EventHandler`] which funds channels from the wallet on
+ * `Event::FundingGenerationReady`, passing the funding transaction to `channel_manager`, and
+ * sweeps the outputs of `Event::SpendableOutputs` into the wallet.
+ *
+ * All other events, as well as funding and sweeping events the wallet fails to handle (eg for
+ * lack of funds), are passed on to `inner`.
+ *
+ * The returned handler must be freed before `this_arg` and `channel_manager` are.
  */
-struct LDKCOption_CVec_NetAddressZZ COption_CVec_NetAddressZZ_none(void);
+MUST_USE_RES struct LDKEventHandler OnChainWallet_event_handler(const struct LDKOnChainWallet *NONNULL_PTR this_arg, const struct LDKChannelManager *NONNULL_PTR channel_manager, struct LDKEventHandler inner);
 
 /**
- * Frees any resources associated with the crate::c_types::derived::CVec_NetAddressZ, if we are in the Some state
+ * Creates a new CResult_OutputSweeperIOErrorZ in the success state.
  */
-void COption_CVec_NetAddressZZ_free(struct LDKCOption_CVec_NetAddressZZ _res);
+struct LDKCResult_OutputSweeperIOErrorZ CResult_OutputSweeperIOErrorZ_ok(struct LDKOutputSweeper o);
 
 /**
- * Creates a new COption_CVec_NetAddressZZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_OutputSweeperIOErrorZ in the error state.
  */
-struct LDKCOption_CVec_NetAddressZZ COption_CVec_NetAddressZZ_clone(const struct LDKCOption_CVec_NetAddressZZ *NONNULL_PTR orig);
+struct LDKCResult_OutputSweeperIOErrorZ CResult_OutputSweeperIOErrorZ_err(enum LDKIOError e);
 
 /**
- * Creates a new CResult_NetAddressDecodeErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_NetAddressDecodeErrorZ CResult_NetAddressDecodeErrorZ_ok(struct LDKNetAddress o);
+bool CResult_OutputSweeperIOErrorZ_is_ok(const struct LDKCResult_OutputSweeperIOErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_NetAddressDecodeErrorZ in the error state.
+ * Frees any resources used by the CResult_OutputSweeperIOErrorZ.
  */
-struct LDKCResult_NetAddressDecodeErrorZ CResult_NetAddressDecodeErrorZ_err(struct LDKDecodeError e);
+void CResult_OutputSweeperIOErrorZ_free(struct LDKCResult_OutputSweeperIOErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the OutputSweeper, if is_owned is set and inner is non-NULL.
  */
-bool CResult_NetAddressDecodeErrorZ_is_ok(const struct LDKCResult_NetAddressDecodeErrorZ *NONNULL_PTR o);
+void OutputSweeper_free(struct LDKOutputSweeper this_obj);
 
 /**
- * Frees any resources used by the CResult_NetAddressDecodeErrorZ.
+ * Creates a sweeper which sweeps outputs to `destination_script`, loading any outputs tracked
+ * before a restart from `store`.
+ *
+ * `seed` must be the seed passed to `KeysManager_new`. Sweeps are first signed at
+ * `fee_estimator`'s `Background` feerate, and are re-signed at the higher of its `Normal`
+ * feerate and a 25% increase every 3 blocks they stay unconfirmed.
+ *
+ * If `chain_source` is set, each tracked output is registered with it, as is each sweep along
+ * with `destination_script`, including those loaded from `store`.
+ *
+ * Previously tracked outputs are rebroadcast or swept once the first block is connected.
  */
-void CResult_NetAddressDecodeErrorZ_free(struct LDKCResult_NetAddressDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_OutputSweeperIOErrorZ OutputSweeper_new(struct LDKKVStore store, const uint8_t (*seed)[32], struct LDKCVec_u8Z destination_script, struct LDKCOption_FilterZ chain_source, struct LDKBroadcasterInterface broadcaster, struct LDKFeeEstimator fee_estimator, struct LDKLogger logger);
 
 /**
- * Creates a new CResult_NetAddressDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Persists the given descriptors and sweeps them, as done for `Event::SpendableOutputs` by the
+ * handler returned from `OutputSweeper_event_handler`.
+ *
+ * Returns an error if a descriptor could not be persisted, in which case it may not be tracked.
  */
-struct LDKCResult_NetAddressDecodeErrorZ CResult_NetAddressDecodeErrorZ_clone(const struct LDKCResult_NetAddressDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_NoneErrorZ OutputSweeper_track_spendable_outputs(const struct LDKOutputSweeper *NONNULL_PTR this_arg, struct LDKCVec_SpendableOutputDescriptorZ descriptors);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Returns the number of outputs currently tracked, i.e. not yet swept beyond reorg risk.
  */
-void CVec_UpdateAddHTLCZ_free(struct LDKCVec_UpdateAddHTLCZ _res);
+MUST_USE_RES uintptr_t OutputSweeper_tracked_output_count(const struct LDKOutputSweeper *NONNULL_PTR this_arg);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Wraps `inner` in an [`EventHandler`] which tracks the outputs of `Event::SpendableOutputs`
+ * with this sweeper. All other events are passed on to `inner`, as are `SpendableOutputs`
+ * events whose outputs could not be persisted.
+ *
+ * The returned handler must be freed before `this_arg` is.
  */
-void CVec_UpdateFulfillHTLCZ_free(struct LDKCVec_UpdateFulfillHTLCZ _res);
+MUST_USE_RES struct LDKEventHandler OutputSweeper_event_handler(const struct LDKOutputSweeper *NONNULL_PTR this_arg, struct LDKEventHandler inner);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Constructs a new Listen which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned Listen must be freed before this_arg is
  */
-void CVec_UpdateFailHTLCZ_free(struct LDKCVec_UpdateFailHTLCZ _res);
+struct LDKListen OutputSweeper_as_Listen(const struct LDKOutputSweeper *NONNULL_PTR this_arg);
 
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Constructs a new Confirm which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned Confirm must be freed before this_arg is
  */
-void CVec_UpdateFailMalformedHTLCZ_free(struct LDKCVec_UpdateFailMalformedHTLCZ _res);
+struct LDKConfirm OutputSweeper_as_Confirm(const struct LDKOutputSweeper *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_AcceptChannelDecodeErrorZ in the success state.
+ * Creates a new CResult_PsbtPsbtFundingErrorZ in the success state.
  */
-struct LDKCResult_AcceptChannelDecodeErrorZ CResult_AcceptChannelDecodeErrorZ_ok(struct LDKAcceptChannel o);
+struct LDKCResult_PsbtPsbtFundingErrorZ CResult_PsbtPsbtFundingErrorZ_ok(struct LDKPsbt o);
 
 /**
- * Creates a new CResult_AcceptChannelDecodeErrorZ in the error state.
+ * Creates a new CResult_PsbtPsbtFundingErrorZ in the error state.
  */
-struct LDKCResult_AcceptChannelDecodeErrorZ CResult_AcceptChannelDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_PsbtPsbtFundingErrorZ CResult_PsbtPsbtFundingErrorZ_err(enum LDKPsbtFundingError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_AcceptChannelDecodeErrorZ_is_ok(const struct LDKCResult_AcceptChannelDecodeErrorZ *NONNULL_PTR o);
+bool CResult_PsbtPsbtFundingErrorZ_is_ok(const struct LDKCResult_PsbtPsbtFundingErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_AcceptChannelDecodeErrorZ.
+ * Frees any resources used by the CResult_PsbtPsbtFundingErrorZ.
  */
-void CResult_AcceptChannelDecodeErrorZ_free(struct LDKCResult_AcceptChannelDecodeErrorZ _res);
+void CResult_PsbtPsbtFundingErrorZ_free(struct LDKCResult_PsbtPsbtFundingErrorZ _res);
 
 /**
- * Creates a new CResult_AcceptChannelDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_TransactionPsbtFundingErrorZ in the success state.
  */
-struct LDKCResult_AcceptChannelDecodeErrorZ CResult_AcceptChannelDecodeErrorZ_clone(const struct LDKCResult_AcceptChannelDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_TransactionPsbtFundingErrorZ CResult_TransactionPsbtFundingErrorZ_ok(struct LDKTransaction o);
 
 /**
- * Creates a new CResult_AnnouncementSignaturesDecodeErrorZ in the success state.
+ * Creates a new CResult_TransactionPsbtFundingErrorZ in the error state.
  */
-struct LDKCResult_AnnouncementSignaturesDecodeErrorZ CResult_AnnouncementSignaturesDecodeErrorZ_ok(struct LDKAnnouncementSignatures o);
+struct LDKCResult_TransactionPsbtFundingErrorZ CResult_TransactionPsbtFundingErrorZ_err(enum LDKPsbtFundingError e);
 
 /**
- * Creates a new CResult_AnnouncementSignaturesDecodeErrorZ in the error state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_AnnouncementSignaturesDecodeErrorZ CResult_AnnouncementSignaturesDecodeErrorZ_err(struct LDKDecodeError e);
+bool CResult_TransactionPsbtFundingErrorZ_is_ok(const struct LDKCResult_TransactionPsbtFundingErrorZ *NONNULL_PTR o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the CResult_TransactionPsbtFundingErrorZ.
  */
-bool CResult_AnnouncementSignaturesDecodeErrorZ_is_ok(const struct LDKCResult_AnnouncementSignaturesDecodeErrorZ *NONNULL_PTR o);
+void CResult_TransactionPsbtFundingErrorZ_free(struct LDKCResult_TransactionPsbtFundingErrorZ _res);
 
 /**
- * Frees any resources used by the CResult_AnnouncementSignaturesDecodeErrorZ.
+ * Frees any resources used by the PsbtFunding, if is_owned is set and inner is non-NULL.
  */
-void CResult_AnnouncementSignaturesDecodeErrorZ_free(struct LDKCResult_AnnouncementSignaturesDecodeErrorZ _res);
+void PsbtFunding_free(struct LDKPsbtFunding this_obj);
 
 /**
- * Creates a new CResult_AnnouncementSignaturesDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a PsbtFunding from the fields of an `Event::FundingGenerationReady`.
  */
-struct LDKCResult_AnnouncementSignaturesDecodeErrorZ CResult_AnnouncementSignaturesDecodeErrorZ_clone(const struct LDKCResult_AnnouncementSignaturesDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKPsbtFunding PsbtFunding_new(struct LDKThirtyTwoBytes temporary_channel_id, struct LDKCVec_u8Z output_script, uint64_t channel_value_satoshis);
 
 /**
- * Creates a new CResult_ChannelReestablishDecodeErrorZ in the success state.
+ * Creates a PsbtFunding for the channel `event` requests funding for.
+ *
+ * Note that the return value (or a relevant inner pointer) may be NULL or all-0s to represent None,
+ * which is the case for any event other than `Event::FundingGenerationReady`.
  */
-struct LDKCResult_ChannelReestablishDecodeErrorZ CResult_ChannelReestablishDecodeErrorZ_ok(struct LDKChannelReestablish o);
+MUST_USE_RES struct LDKPsbtFunding PsbtFunding_from_event(const struct LDKEvent *NONNULL_PTR event);
 
 /**
- * Creates a new CResult_ChannelReestablishDecodeErrorZ in the error state.
+ * The temporary channel id of the channel being funded.
  */
-struct LDKCResult_ChannelReestablishDecodeErrorZ CResult_ChannelReestablishDecodeErrorZ_err(struct LDKDecodeError e);
+const uint8_t (*PsbtFunding_get_temporary_channel_id(const struct LDKPsbtFunding *NONNULL_PTR this_ptr))[32];
 
 /**
- * Checks if the given object is currently in the success state
+ * The script which the funding output must pay to.
  */
-bool CResult_ChannelReestablishDecodeErrorZ_is_ok(const struct LDKCResult_ChannelReestablishDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKu8slice PsbtFunding_get_output_script(const struct LDKPsbtFunding *NONNULL_PTR this_ptr);
 
 /**
- * Frees any resources used by the CResult_ChannelReestablishDecodeErrorZ.
+ * The value which the funding output must pay, in satoshis.
  */
-void CResult_ChannelReestablishDecodeErrorZ_free(struct LDKCResult_ChannelReestablishDecodeErrorZ _res);
+MUST_USE_RES uint64_t PsbtFunding_get_channel_value_satoshis(const struct LDKPsbtFunding *NONNULL_PTR this_ptr);
 
 /**
- * Creates a new CResult_ChannelReestablishDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Returns an unsigned PSBT containing only the channel's funding output, to which a wallet should
+ * add inputs (and change) before signing it.
  */
-struct LDKCResult_ChannelReestablishDecodeErrorZ CResult_ChannelReestablishDecodeErrorZ_clone(const struct LDKCResult_ChannelReestablishDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKPsbt PsbtFunding_get_unsigned_psbt(const struct LDKPsbtFunding *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_ClosingSignedDecodeErrorZ in the success state.
+ * Adds the channel's funding output to an unsigned PSBT built by a wallet, eg one which already
+ * spends the inputs to fund the channel with.
+ *
+ * The PSBT is returned unchanged if it already pays the funding output.
  */
-struct LDKCResult_ClosingSignedDecodeErrorZ CResult_ClosingSignedDecodeErrorZ_ok(struct LDKClosingSigned o);
+MUST_USE_RES struct LDKCResult_PsbtPsbtFundingErrorZ PsbtFunding_add_channel_output(const struct LDKPsbtFunding *NONNULL_PTR this_arg, struct LDKPsbt psbt);
 
 /**
- * Creates a new CResult_ClosingSignedDecodeErrorZ in the error state.
+ * Checks that a signed PSBT pays the channel's funding output and only spends segwit outputs,
+ * finalizing any P2WPKH inputs which are not yet, and extracts the funding transaction from it.
+ *
+ * The returned transaction may be passed to `ChannelManager_funding_transaction_generated`.
  */
-struct LDKCResult_ClosingSignedDecodeErrorZ CResult_ClosingSignedDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_TransactionPsbtFundingErrorZ PsbtFunding_finalize(const struct LDKPsbtFunding *NONNULL_PTR this_arg, struct LDKPsbt signed_psbt);
 
 /**
- * Checks if the given object is currently in the success state
+ * Finalizes a signed PSBT as `PsbtFunding_finalize` does and passes the resulting transaction to
+ * `channel_manager`, which broadcasts it once the channel is set up.
+ *
+ * The funding transaction is returned on success, so that the wallet can track it.
  */
-bool CResult_ClosingSignedDecodeErrorZ_is_ok(const struct LDKCResult_ClosingSignedDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_TransactionPsbtFundingErrorZ PsbtFunding_funding_transaction_generated(const struct LDKPsbtFunding *NONNULL_PTR this_arg, const struct LDKChannelManager *NONNULL_PTR channel_manager, struct LDKPsbt signed_psbt);
 
 /**
- * Frees any resources used by the CResult_ClosingSignedDecodeErrorZ.
+ * Creates a new CResult_StrSeedErrorZ in the success state.
  */
-void CResult_ClosingSignedDecodeErrorZ_free(struct LDKCResult_ClosingSignedDecodeErrorZ _res);
+struct LDKCResult_StrSeedErrorZ CResult_StrSeedErrorZ_ok(struct LDKStr o);
 
 /**
- * Creates a new CResult_ClosingSignedDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_StrSeedErrorZ in the error state.
  */
-struct LDKCResult_ClosingSignedDecodeErrorZ CResult_ClosingSignedDecodeErrorZ_clone(const struct LDKCResult_ClosingSignedDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_StrSeedErrorZ CResult_StrSeedErrorZ_err(enum LDKSeedError e);
 
 /**
- * Creates a new CResult_ClosingSignedFeeRangeDecodeErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ CResult_ClosingSignedFeeRangeDecodeErrorZ_ok(struct LDKClosingSignedFeeRange o);
+bool CResult_StrSeedErrorZ_is_ok(const struct LDKCResult_StrSeedErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_ClosingSignedFeeRangeDecodeErrorZ in the error state.
+ * Frees any resources used by the CResult_StrSeedErrorZ.
  */
-struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ CResult_ClosingSignedFeeRangeDecodeErrorZ_err(struct LDKDecodeError e);
+void CResult_StrSeedErrorZ_free(struct LDKCResult_StrSeedErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_NoneSeedErrorZ in the success state.
  */
-bool CResult_ClosingSignedFeeRangeDecodeErrorZ_is_ok(const struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_NoneSeedErrorZ CResult_NoneSeedErrorZ_ok(void);
 
 /**
- * Frees any resources used by the CResult_ClosingSignedFeeRangeDecodeErrorZ.
+ * Creates a new CResult_NoneSeedErrorZ in the error state.
  */
-void CResult_ClosingSignedFeeRangeDecodeErrorZ_free(struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ _res);
+struct LDKCResult_NoneSeedErrorZ CResult_NoneSeedErrorZ_err(enum LDKSeedError e);
 
 /**
- * Creates a new CResult_ClosingSignedFeeRangeDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ CResult_ClosingSignedFeeRangeDecodeErrorZ_clone(const struct LDKCResult_ClosingSignedFeeRangeDecodeErrorZ *NONNULL_PTR orig);
+bool CResult_NoneSeedErrorZ_is_ok(const struct LDKCResult_NoneSeedErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_CommitmentSignedDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_NoneSeedErrorZ.
  */
-struct LDKCResult_CommitmentSignedDecodeErrorZ CResult_CommitmentSignedDecodeErrorZ_ok(struct LDKCommitmentSigned o);
+void CResult_NoneSeedErrorZ_free(struct LDKCResult_NoneSeedErrorZ _res);
 
 /**
- * Creates a new CResult_CommitmentSignedDecodeErrorZ in the error state.
+ * Creates a new CResult_ThirtyTwoBytesSeedErrorZ in the success state.
  */
-struct LDKCResult_CommitmentSignedDecodeErrorZ CResult_CommitmentSignedDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_ThirtyTwoBytesSeedErrorZ CResult_ThirtyTwoBytesSeedErrorZ_ok(struct LDKThirtyTwoBytes o);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_ThirtyTwoBytesSeedErrorZ in the error state.
  */
-bool CResult_CommitmentSignedDecodeErrorZ_is_ok(const struct LDKCResult_CommitmentSignedDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_ThirtyTwoBytesSeedErrorZ CResult_ThirtyTwoBytesSeedErrorZ_err(enum LDKSeedError e);
 
 /**
- * Frees any resources used by the CResult_CommitmentSignedDecodeErrorZ.
+ * Checks if the given object is currently in the success state
  */
-void CResult_CommitmentSignedDecodeErrorZ_free(struct LDKCResult_CommitmentSignedDecodeErrorZ _res);
+bool CResult_ThirtyTwoBytesSeedErrorZ_is_ok(const struct LDKCResult_ThirtyTwoBytesSeedErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_CommitmentSignedDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the CResult_ThirtyTwoBytesSeedErrorZ.
  */
-struct LDKCResult_CommitmentSignedDecodeErrorZ CResult_CommitmentSignedDecodeErrorZ_clone(const struct LDKCResult_CommitmentSignedDecodeErrorZ *NONNULL_PTR orig);
+void CResult_ThirtyTwoBytesSeedErrorZ_free(struct LDKCResult_ThirtyTwoBytesSeedErrorZ _res);
 
 /**
- * Creates a new CResult_FundingCreatedDecodeErrorZ in the success state.
+ * Generates a new mnemonic of `word_count` words (12, 15, 18, 21 or 24) from the system's random
+ * number generator.
+ *
+ * 24 words are recommended for new nodes.
  */
-struct LDKCResult_FundingCreatedDecodeErrorZ CResult_FundingCreatedDecodeErrorZ_ok(struct LDKFundingCreated o);
+MUST_USE_RES struct LDKCResult_StrSeedErrorZ Mnemonic_generate(uint8_t word_count);
 
 /**
- * Creates a new CResult_FundingCreatedDecodeErrorZ in the error state.
+ * Checks that `mnemonic` consists of words from the BIP 39 English wordlist with a valid
+ * checksum, returning the reason it does not otherwise.
  */
-struct LDKCResult_FundingCreatedDecodeErrorZ CResult_FundingCreatedDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_NoneSeedErrorZ Mnemonic_validate(struct LDKStr mnemonic);
 
 /**
- * Checks if the given object is currently in the success state
+ * Returns `mnemonic` in its canonical form: lower case, with single spaces between words.
+ *
+ * Applications should display and store mnemonics in this form.
  */
-bool CResult_FundingCreatedDecodeErrorZ_is_ok(const struct LDKCResult_FundingCreatedDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_StrSeedErrorZ Mnemonic_normalize(struct LDKStr mnemonic);
 
 /**
- * Frees any resources used by the CResult_FundingCreatedDecodeErrorZ.
+ * Derives the 32-byte seed for `KeysManager_new` from `mnemonic` and a BIP 39 passphrase, which
+ * may be empty, as described in the module documentation.
+ *
+ * Note that any passphrase yields a valid seed, so a mistyped passphrase silently results in a
+ * different, empty, node.
  */
-void CResult_FundingCreatedDecodeErrorZ_free(struct LDKCResult_FundingCreatedDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_ThirtyTwoBytesSeedErrorZ Mnemonic_to_ldk_seed(struct LDKStr mnemonic, struct LDKStr bip39_passphrase);
 
 /**
- * Creates a new CResult_FundingCreatedDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Writes `mnemonic` to a new seed file at `path`, encrypted under `file_passphrase`.
+ *
+ * Fails with `SeedError_FileExists` rather than overwriting an existing file. On Unix the file is
+ * only readable by the current user.
  */
-struct LDKCResult_FundingCreatedDecodeErrorZ CResult_FundingCreatedDecodeErrorZ_clone(const struct LDKCResult_FundingCreatedDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_NoneSeedErrorZ SeedFile_write(struct LDKStr path, struct LDKStr mnemonic, struct LDKStr file_passphrase);
 
 /**
- * Creates a new CResult_FundingSignedDecodeErrorZ in the success state.
+ * Reads the mnemonic from the seed file at `path`, decrypting it with `file_passphrase`.
  */
-struct LDKCResult_FundingSignedDecodeErrorZ CResult_FundingSignedDecodeErrorZ_ok(struct LDKFundingSigned o);
+MUST_USE_RES struct LDKCResult_StrSeedErrorZ SeedFile_read(struct LDKStr path, struct LDKStr file_passphrase);
 
 /**
- * Creates a new CResult_FundingSignedDecodeErrorZ in the error state.
+ * Calls the free function if one is set
  */
-struct LDKCResult_FundingSignedDecodeErrorZ CResult_FundingSignedDecodeErrorZ_err(struct LDKDecodeError e);
+void SignerTransport_free(struct LDKSignerTransport this_ptr);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a `KeysInterface` whose signers forward all calls to a remote signer over `transport`.
+ *
+ * Channel signers serialized by LDK (eg within `ChannelMonitor`s) only contain their
+ * `channel_keys_id` and public keys, and must be read back with a `KeysInterface` connected to the
+ * same signer.
  */
-bool CResult_FundingSignedDecodeErrorZ_is_ok(const struct LDKCResult_FundingSignedDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKKeysInterface RemoteKeysInterface_new(struct LDKSignerTransport transport);
 
 /**
- * Frees any resources used by the CResult_FundingSignedDecodeErrorZ.
+ * Creates a new CResult_RemoteSignerServerIOErrorZ in the success state.
  */
-void CResult_FundingSignedDecodeErrorZ_free(struct LDKCResult_FundingSignedDecodeErrorZ _res);
+struct LDKCResult_RemoteSignerServerIOErrorZ CResult_RemoteSignerServerIOErrorZ_ok(struct LDKRemoteSignerServer o);
 
 /**
- * Creates a new CResult_FundingSignedDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_RemoteSignerServerIOErrorZ in the error state.
  */
-struct LDKCResult_FundingSignedDecodeErrorZ CResult_FundingSignedDecodeErrorZ_clone(const struct LDKCResult_FundingSignedDecodeErrorZ *NONNULL_PTR orig);
+struct LDKCResult_RemoteSignerServerIOErrorZ CResult_RemoteSignerServerIOErrorZ_err(enum LDKIOError e);
 
 /**
- * Creates a new CResult_FundingLockedDecodeErrorZ in the success state.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_FundingLockedDecodeErrorZ CResult_FundingLockedDecodeErrorZ_ok(struct LDKFundingLocked o);
+bool CResult_RemoteSignerServerIOErrorZ_is_ok(const struct LDKCResult_RemoteSignerServerIOErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_FundingLockedDecodeErrorZ in the error state.
+ * Frees any resources used by the CResult_RemoteSignerServerIOErrorZ.
  */
-struct LDKCResult_FundingLockedDecodeErrorZ CResult_FundingLockedDecodeErrorZ_err(struct LDKDecodeError e);
+void CResult_RemoteSignerServerIOErrorZ_free(struct LDKCResult_RemoteSignerServerIOErrorZ _res);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the RemoteSignerServer, if is_owned is set and inner is non-NULL.
  */
-bool CResult_FundingLockedDecodeErrorZ_is_ok(const struct LDKCResult_FundingLockedDecodeErrorZ *NONNULL_PTR o);
+void RemoteSignerServer_free(struct LDKRemoteSignerServer this_obj);
 
 /**
- * Frees any resources used by the CResult_FundingLockedDecodeErrorZ.
+ * Creates a server answering remote signer requests with `keys`, eg the result of
+ * `KeysManager_as_KeysInterface`, loading the channel signers previously stored in `store`.
+ *
+ * Returns an error if a stored signer could not be read or `keys` failed to deserialize it.
  */
-void CResult_FundingLockedDecodeErrorZ_free(struct LDKCResult_FundingLockedDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_RemoteSignerServerIOErrorZ RemoteSignerServer_new(struct LDKKeysInterface keys, struct LDKKVStore store);
 
 /**
- * Creates a new CResult_FundingLockedDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Processes a single request, returning the response to send back to the client.
+ *
+ * Malformed requests, requests for unknown signers and requests the `KeysInterface` refuses are
+ * answered with a failure response.
  */
-struct LDKCResult_FundingLockedDecodeErrorZ CResult_FundingLockedDecodeErrorZ_clone(const struct LDKCResult_FundingLockedDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCVec_u8Z RemoteSignerServer_handle_request(const struct LDKRemoteSignerServer *NONNULL_PTR this_arg, struct LDKu8slice request);
 
 /**
- * Creates a new CResult_InitDecodeErrorZ in the success state.
+ * Returns the number of channel signers the server holds.
  */
-struct LDKCResult_InitDecodeErrorZ CResult_InitDecodeErrorZ_ok(struct LDKInit o);
+MUST_USE_RES uintptr_t RemoteSignerServer_signer_count(const struct LDKRemoteSignerServer *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_InitDecodeErrorZ in the error state.
+ * Constructs a new SignerTransport which passes requests directly to this_arg, for running the
+ * client and server in the same process.
+ * This copies the `inner` pointer in this_arg and thus the returned SignerTransport must be freed before this_arg is
  */
-struct LDKCResult_InitDecodeErrorZ CResult_InitDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKSignerTransport RemoteSignerServer_as_SignerTransport(const struct LDKRemoteSignerServer *NONNULL_PTR this_arg);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a SignerTransport which sends length-prefixed requests to `write_fd` and reads the
+ * responses from `read_fd`, which may be the same file descriptor, eg for a socket.
+ *
+ * The file descriptors must be blocking, and remain owned by the caller, who must close them
+ * only once the transport (and any `KeysInterface` and signers using it) has been freed.
  */
-bool CResult_InitDecodeErrorZ_is_ok(const struct LDKCResult_InitDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKSignerTransport SignerTransport_new_fd(int32_t read_fd, int32_t write_fd);
 
 /**
- * Frees any resources used by the CResult_InitDecodeErrorZ.
+ * Answers length-prefixed requests read from `read_fd`, writing the responses to `write_fd`,
+ * until `read_fd` reaches end-of-file or an I/O error occurs. This blocks the calling thread
+ * throughout.
+ *
+ * The file descriptors must be blocking, and remain owned by the caller.
  */
-void CResult_InitDecodeErrorZ_free(struct LDKCResult_InitDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_NoneErrorZ RemoteSignerServer_serve_fd(const struct LDKRemoteSignerServer *NONNULL_PTR this_arg, int32_t read_fd, int32_t write_fd);
 
 /**
- * Creates a new CResult_InitDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the SignerPolicy, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_InitDecodeErrorZ CResult_InitDecodeErrorZ_clone(const struct LDKCResult_InitDecodeErrorZ *NONNULL_PTR orig);
+void SignerPolicy_free(struct LDKSignerPolicy this_obj);
 
 /**
- * Creates a new CResult_OpenChannelDecodeErrorZ in the success state.
+ * Creates a new SignerPolicy enforcing every rule, without any additional scripts.
  */
-struct LDKCResult_OpenChannelDecodeErrorZ CResult_OpenChannelDecodeErrorZ_ok(struct LDKOpenChannel o);
+MUST_USE_RES struct LDKSignerPolicy SignerPolicy_new(void);
 
 /**
- * Creates a new CResult_OpenChannelDecodeErrorZ in the error state.
+ * Stops enforcing the given rule. Requests breaking it are still logged, at the `Warn` level.
  */
-struct LDKCResult_OpenChannelDecodeErrorZ CResult_OpenChannelDecodeErrorZ_err(struct LDKDecodeError e);
+void SignerPolicy_disable_rule(struct LDKSignerPolicy *NONNULL_PTR this_arg, enum LDKSignerPolicyRule rule);
 
 /**
- * Checks if the given object is currently in the success state
+ * Allows transactions claiming channel outputs to pay to the given script, eg one handed out by
+ * a previous `KeysInterface` before a restart.
  */
-bool CResult_OpenChannelDecodeErrorZ_is_ok(const struct LDKCResult_OpenChannelDecodeErrorZ *NONNULL_PTR o);
+void SignerPolicy_add_destination_script(struct LDKSignerPolicy *NONNULL_PTR this_arg, struct LDKCVec_u8Z script);
 
 /**
- * Frees any resources used by the CResult_OpenChannelDecodeErrorZ.
+ * Allows closing transactions to pay our balance to the given script, eg one handed out by a
+ * previous `KeysInterface` before a restart.
  */
-void CResult_OpenChannelDecodeErrorZ_free(struct LDKCResult_OpenChannelDecodeErrorZ _res);
+void SignerPolicy_add_shutdown_script(struct LDKSignerPolicy *NONNULL_PTR this_arg, struct LDKCVec_u8Z script);
 
 /**
- * Creates a new CResult_OpenChannelDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a `KeysInterface` whose signers enforce `policy` on top of those of `inner`, storing
+ * the state of each channel in `store` and logging refused requests to `logger`.
+ *
+ * Channel signers serialized by LDK contain those of `inner`, and must be read back with a
+ * `KeysInterface` created by this function, using the same `store`. Signers serialized by a
+ * `KeysManager` before it was wrapped (e.g. in existing `ChannelMonitor`s) can be read back as
+ * well, so existing channels can be moved under a policy, with their state starting empty.
+ *
+ * If a new channel's state cannot be read from `store`, its signer refuses to sign anything or
+ * release any commitment secret.
  */
-struct LDKCResult_OpenChannelDecodeErrorZ CResult_OpenChannelDecodeErrorZ_clone(const struct LDKCResult_OpenChannelDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKKeysInterface PolicyKeysInterface_new(struct LDKKeysInterface inner, struct LDKSignerPolicy policy, struct LDKKVStore store, struct LDKLogger logger);
 
 /**
- * Creates a new CResult_RevokeAndACKDecodeErrorZ in the success state.
+ * Calls the free function if one is set
  */
-struct LDKCResult_RevokeAndACKDecodeErrorZ CResult_RevokeAndACKDecodeErrorZ_ok(struct LDKRevokeAndACK o);
+void InvoiceSigner_free(struct LDKInvoiceSigner this_ptr);
 
 /**
- * Creates a new CResult_RevokeAndACKDecodeErrorZ in the error state.
+ * Creates an [`InvoiceSigner`] which signs invoices with the node secret of `keys_interface`,
+ * taking ownership of it.
  */
-struct LDKCResult_RevokeAndACKDecodeErrorZ CResult_RevokeAndACKDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKInvoiceSigner InvoiceSigner_from_keys_interface(struct LDKKeysInterface keys_interface);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the InvoiceBuilder, if is_owned is set and inner is non-NULL.
  */
-bool CResult_RevokeAndACKDecodeErrorZ_is_ok(const struct LDKCResult_RevokeAndACKDecodeErrorZ *NONNULL_PTR o);
+void InvoiceBuilder_free(struct LDKInvoiceBuilder this_obj);
 
 /**
- * Frees any resources used by the CResult_RevokeAndACKDecodeErrorZ.
+ * Creates a copy of the InvoiceBuilder
  */
-void CResult_RevokeAndACKDecodeErrorZ_free(struct LDKCResult_RevokeAndACKDecodeErrorZ _res);
+struct LDKInvoiceBuilder InvoiceBuilder_clone(const struct LDKInvoiceBuilder *NONNULL_PTR orig);
 
 /**
- * Creates a new CResult_RevokeAndACKDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new InvoiceBuilder for an invoice on the given network, for the given payment hash
+ * and secret, with a description to show the payer.
+ *
+ * The invoice's timestamp defaults to the time it is built, and its `min_final_cltv_expiry` to
+ * `MIN_FINAL_CLTV_EXPIRY`, the lowest LDK accepts for received payments. It has no amount,
+ * leaving it up to the payer, and the default expiry time of one hour.
  */
-struct LDKCResult_RevokeAndACKDecodeErrorZ CResult_RevokeAndACKDecodeErrorZ_clone(const struct LDKCResult_RevokeAndACKDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKInvoiceBuilder InvoiceBuilder_new(enum LDKCurrency currency, struct LDKThirtyTwoBytes payment_hash, struct LDKThirtyTwoBytes payment_secret, struct LDKStr description);
 
 /**
- * Creates a new CResult_ShutdownDecodeErrorZ in the success state.
+ * Sets the description shown to the payer, replacing any description or description hash.
+ *
+ * Descriptions longer than 639 bytes fail to build with `CreationError::DescriptionTooLong`.
  */
-struct LDKCResult_ShutdownDecodeErrorZ CResult_ShutdownDecodeErrorZ_ok(struct LDKShutdown o);
+void InvoiceBuilder_set_description(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, struct LDKStr description);
 
 /**
- * Creates a new CResult_ShutdownDecodeErrorZ in the error state.
+ * Sets the SHA-256 hash of a description too long to include in the invoice, replacing any
+ * description or description hash.
  */
-struct LDKCResult_ShutdownDecodeErrorZ CResult_ShutdownDecodeErrorZ_err(struct LDKDecodeError e);
+void InvoiceBuilder_set_description_hash(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, struct LDKThirtyTwoBytes description_hash);
 
 /**
- * Checks if the given object is currently in the success state
+ * Sets the amount requested, in millisatoshis.
+ *
+ * Amounts greater than the total bitcoin supply fail to build with
+ * `CreationError::InvalidAmount`.
  */
-bool CResult_ShutdownDecodeErrorZ_is_ok(const struct LDKCResult_ShutdownDecodeErrorZ *NONNULL_PTR o);
+void InvoiceBuilder_set_amount_milli_satoshis(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, uint64_t amount_msat);
 
 /**
- * Frees any resources used by the CResult_ShutdownDecodeErrorZ.
+ * Sets the creation time of the invoice, in seconds since the UNIX epoch, instead of the time
+ * it is built.
+ *
+ * Timestamps which cannot be encoded fail to build with `CreationError::TimestampOutOfBounds`.
  */
-void CResult_ShutdownDecodeErrorZ_free(struct LDKCResult_ShutdownDecodeErrorZ _res);
+void InvoiceBuilder_set_timestamp(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, uint64_t timestamp);
 
 /**
- * Creates a new CResult_ShutdownDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Sets the number of seconds after its timestamp the invoice expires.
+ *
+ * Expiry times which cannot be added to the timestamp fail to build with
+ * `CreationError::ExpiryTimeOutOfBounds`.
  */
-struct LDKCResult_ShutdownDecodeErrorZ CResult_ShutdownDecodeErrorZ_clone(const struct LDKCResult_ShutdownDecodeErrorZ *NONNULL_PTR orig);
+void InvoiceBuilder_set_expiry_time(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, uint64_t expiry_time_secs);
 
 /**
- * Creates a new CResult_UpdateFailHTLCDecodeErrorZ in the success state.
+ * Sets the minimum number of blocks the payment's final HTLC must have before expiring.
+ *
+ * Note that LDK fails received HTLCs expiring sooner than `MIN_FINAL_CLTV_EXPIRY` blocks after
+ * the current height, whatever the invoice says.
  */
-struct LDKCResult_UpdateFailHTLCDecodeErrorZ CResult_UpdateFailHTLCDecodeErrorZ_ok(struct LDKUpdateFailHTLC o);
+void InvoiceBuilder_set_min_final_cltv_expiry(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, uint64_t min_final_cltv_expiry);
 
 /**
- * Creates a new CResult_UpdateFailHTLCDecodeErrorZ in the error state.
+ * Includes the given node id in the invoice, which is otherwise recovered from its signature.
+ *
+ * When set, building fails with `SignOrCreationError::SignError` if the invoice is not signed
+ * by the given key.
  */
-struct LDKCResult_UpdateFailHTLCDecodeErrorZ CResult_UpdateFailHTLCDecodeErrorZ_err(struct LDKDecodeError e);
+void InvoiceBuilder_set_payee_pub_key(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, struct LDKPublicKey payee_pub_key);
 
 /**
- * Checks if the given object is currently in the success state
+ * Adds an on-chain address the payer may fall back to if the payment cannot be made over
+ * lightning.
  */
-bool CResult_UpdateFailHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFailHTLCDecodeErrorZ *NONNULL_PTR o);
+void InvoiceBuilder_add_fallback(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, struct LDKFallback fallback);
 
 /**
- * Frees any resources used by the CResult_UpdateFailHTLCDecodeErrorZ.
+ * Adds a route hint, eg through one of our private channels, for the payer to route through.
+ *
+ * Route hints with too many hops to encode fail to build with `CreationError::RouteTooLong`.
  */
-void CResult_UpdateFailHTLCDecodeErrorZ_free(struct LDKCResult_UpdateFailHTLCDecodeErrorZ _res);
+void InvoiceBuilder_add_route_hint(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, struct LDKRouteHint route_hint);
 
 /**
- * Creates a new CResult_UpdateFailHTLCDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Sets the `basic_mpp` feature, allowing the payer to split the payment over multiple paths.
+ *
+ * The `payment_secret` and `var_onion_optin` features, which every invoice built by LDK
+ * requires, are always set.
  */
-struct LDKCResult_UpdateFailHTLCDecodeErrorZ CResult_UpdateFailHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateFailHTLCDecodeErrorZ *NONNULL_PTR orig);
+void InvoiceBuilder_set_basic_mpp(struct LDKInvoiceBuilder *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_UpdateFailMalformedHTLCDecodeErrorZ in the success state.
+ * Sets the given features in the invoice, in addition to `basic_mpp` if
+ * `InvoiceBuilder_set_basic_mpp` was called, replacing any features set before.
+ *
+ * The `payment_secret` and `var_onion_optin` features are set as required whatever `features`
+ * says. Features unknown to LDK must only be set as optional, as invoices requiring them are
+ * invalid and fail to build with `SignOrCreationError::SignError`.
  */
-struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ CResult_UpdateFailMalformedHTLCDecodeErrorZ_ok(struct LDKUpdateFailMalformedHTLC o);
+void InvoiceBuilder_set_features(struct LDKInvoiceBuilder *NONNULL_PTR this_arg, struct LDKInvoiceFeatures features);
 
 /**
- * Creates a new CResult_UpdateFailMalformedHTLCDecodeErrorZ in the error state.
+ * Builds the invoice and has it signed by `signer`.
+ *
+ * Returns a `CreationError` if any field holds an invalid value, or `SignError` if `signer`
+ * fails, if a payee public key was set and `signer` signs with a different key, or if features
+ * unknown to LDK were set as required. The builder is left
+ * unchanged and may be built again.
  */
-struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ CResult_UpdateFailMalformedHTLCDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_InvoiceSignOrCreationErrorZ InvoiceBuilder_build_signed(const struct LDKInvoiceBuilder *NONNULL_PTR this_arg, const struct LDKInvoiceSigner *NONNULL_PTR signer);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_InvoiceBuilderHodlInvoiceErrorZ in the success state.
  */
-bool CResult_UpdateFailMalformedHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_InvoiceBuilderHodlInvoiceErrorZ CResult_InvoiceBuilderHodlInvoiceErrorZ_ok(struct LDKInvoiceBuilder o);
 
 /**
- * Frees any resources used by the CResult_UpdateFailMalformedHTLCDecodeErrorZ.
+ * Creates a new CResult_InvoiceBuilderHodlInvoiceErrorZ in the error state.
  */
-void CResult_UpdateFailMalformedHTLCDecodeErrorZ_free(struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ _res);
+struct LDKCResult_InvoiceBuilderHodlInvoiceErrorZ CResult_InvoiceBuilderHodlInvoiceErrorZ_err(enum LDKHodlInvoiceError e);
 
 /**
- * Creates a new CResult_UpdateFailMalformedHTLCDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ CResult_UpdateFailMalformedHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateFailMalformedHTLCDecodeErrorZ *NONNULL_PTR orig);
+bool CResult_InvoiceBuilderHodlInvoiceErrorZ_is_ok(const struct LDKCResult_InvoiceBuilderHodlInvoiceErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_UpdateFeeDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_InvoiceBuilderHodlInvoiceErrorZ.
  */
-struct LDKCResult_UpdateFeeDecodeErrorZ CResult_UpdateFeeDecodeErrorZ_ok(struct LDKUpdateFee o);
+void CResult_InvoiceBuilderHodlInvoiceErrorZ_free(struct LDKCResult_InvoiceBuilderHodlInvoiceErrorZ _res);
 
 /**
- * Creates a new CResult_UpdateFeeDecodeErrorZ in the error state.
+ * Creates a new CResult_NoneHodlInvoiceErrorZ in the success state.
  */
-struct LDKCResult_UpdateFeeDecodeErrorZ CResult_UpdateFeeDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_NoneHodlInvoiceErrorZ CResult_NoneHodlInvoiceErrorZ_ok(void);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_NoneHodlInvoiceErrorZ in the error state.
  */
-bool CResult_UpdateFeeDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFeeDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_NoneHodlInvoiceErrorZ CResult_NoneHodlInvoiceErrorZ_err(enum LDKHodlInvoiceError e);
 
 /**
- * Frees any resources used by the CResult_UpdateFeeDecodeErrorZ.
+ * Checks if the given object is currently in the success state
  */
-void CResult_UpdateFeeDecodeErrorZ_free(struct LDKCResult_UpdateFeeDecodeErrorZ _res);
+bool CResult_NoneHodlInvoiceErrorZ_is_ok(const struct LDKCResult_NoneHodlInvoiceErrorZ *NONNULL_PTR o);
 
 /**
- * Creates a new CResult_UpdateFeeDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the CResult_NoneHodlInvoiceErrorZ.
  */
-struct LDKCResult_UpdateFeeDecodeErrorZ CResult_UpdateFeeDecodeErrorZ_clone(const struct LDKCResult_UpdateFeeDecodeErrorZ *NONNULL_PTR orig);
+void CResult_NoneHodlInvoiceErrorZ_free(struct LDKCResult_NoneHodlInvoiceErrorZ _res);
 
 /**
- * Creates a new CResult_UpdateFulfillHTLCDecodeErrorZ in the success state.
+ * Creates a new CResult_HodlInvoiceManagerIOErrorZ in the success state.
  */
-struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ CResult_UpdateFulfillHTLCDecodeErrorZ_ok(struct LDKUpdateFulfillHTLC o);
+struct LDKCResult_HodlInvoiceManagerIOErrorZ CResult_HodlInvoiceManagerIOErrorZ_ok(struct LDKHodlInvoiceManager o);
 
 /**
- * Creates a new CResult_UpdateFulfillHTLCDecodeErrorZ in the error state.
+ * Creates a new CResult_HodlInvoiceManagerIOErrorZ in the error state.
  */
-struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ CResult_UpdateFulfillHTLCDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_HodlInvoiceManagerIOErrorZ CResult_HodlInvoiceManagerIOErrorZ_err(enum LDKIOError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_UpdateFulfillHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ *NONNULL_PTR o);
+bool CResult_HodlInvoiceManagerIOErrorZ_is_ok(const struct LDKCResult_HodlInvoiceManagerIOErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_UpdateFulfillHTLCDecodeErrorZ.
+ * Frees any resources used by the CResult_HodlInvoiceManagerIOErrorZ.
  */
-void CResult_UpdateFulfillHTLCDecodeErrorZ_free(struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ _res);
+void CResult_HodlInvoiceManagerIOErrorZ_free(struct LDKCResult_HodlInvoiceManagerIOErrorZ _res);
 
 /**
- * Creates a new CResult_UpdateFulfillHTLCDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the HodlInvoiceManager, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ CResult_UpdateFulfillHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateFulfillHTLCDecodeErrorZ *NONNULL_PTR orig);
+void HodlInvoiceManager_free(struct LDKHodlInvoiceManager this_obj);
 
 /**
- * Creates a new CResult_UpdateAddHTLCDecodeErrorZ in the success state.
+ * Creates a manager for HODL invoices paid to `channel_manager`, loading the payment hashes
+ * previously registered in `store`.
+ *
+ * The manager must be freed before `channel_manager` is.
  */
-struct LDKCResult_UpdateAddHTLCDecodeErrorZ CResult_UpdateAddHTLCDecodeErrorZ_ok(struct LDKUpdateAddHTLC o);
+MUST_USE_RES struct LDKCResult_HodlInvoiceManagerIOErrorZ HodlInvoiceManager_new(const struct LDKChannelManager *NONNULL_PTR channel_manager, struct LDKKVStore store, struct LDKLogger logger);
 
 /**
- * Creates a new CResult_UpdateAddHTLCDecodeErrorZ in the error state.
+ * Registers `payment_hash` with the `ChannelManager` and this manager, returning an
+ * [`InvoiceBuilder`] for it with the given amount, if any, and expiry time set.
+ *
+ * Once a payment arrives, it is held for `hold_blocks` blocks, which must be between 1 and 720
+ * (five days). The invoice's `min_final_cltv_expiry` is set to `hold_blocks` plus
+ * `MIN_FINAL_CLTV_EXPIRY` and should not be lowered.
+ *
+ * Registering a hash again, eg to issue another invoice for it, is allowed until a payment for
+ * it is received, and un-cancels it if it was cancelled.
  */
-struct LDKCResult_UpdateAddHTLCDecodeErrorZ CResult_UpdateAddHTLCDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_InvoiceBuilderHodlInvoiceErrorZ HodlInvoiceManager_invoice_builder(const struct LDKHodlInvoiceManager *NONNULL_PTR this_arg, enum LDKCurrency currency, struct LDKThirtyTwoBytes payment_hash, struct LDKCOption_u64Z amount_msat, struct LDKStr description, uint32_t expiry_secs, uint32_t hold_blocks);
 
 /**
- * Checks if the given object is currently in the success state
+ * Claims the held payment whose payment hash is the hash of `payment_preimage` and stops
+ * tracking it.
+ *
+ * Fails without effect if the payment hash is unknown or no payment is being held for it. If
+ * the `ChannelManager` no longer has the payment's HTLCs, the payment hash is forgotten and
+ * `PaymentNoLongerPending` is returned.
  */
-bool CResult_UpdateAddHTLCDecodeErrorZ_is_ok(const struct LDKCResult_UpdateAddHTLCDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_NoneHodlInvoiceErrorZ HodlInvoiceManager_settle(const struct LDKHodlInvoiceManager *NONNULL_PTR this_arg, struct LDKThirtyTwoBytes payment_preimage);
 
 /**
- * Frees any resources used by the CResult_UpdateAddHTLCDecodeErrorZ.
+ * Fails back the payment held for `payment_hash`, if any, and fails back any further payment to
+ * it until its invoice expires.
+ *
+ * The cancellation takes effect even if it could not be persisted, in which case
+ * `PersistenceFailed` is returned and a payment arriving after a restart would be held again.
  */
-void CResult_UpdateAddHTLCDecodeErrorZ_free(struct LDKCResult_UpdateAddHTLCDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_NoneHodlInvoiceErrorZ HodlInvoiceManager_cancel(const struct LDKHodlInvoiceManager *NONNULL_PTR this_arg, const uint8_t (*payment_hash)[32]);
 
 /**
- * Creates a new CResult_UpdateAddHTLCDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Returns the amount of the payment held for `payment_hash`, or None if no payment is held for
+ * it, eg after a restart to learn which payments still await settlement.
+ *
+ * If `persisted` is not NULL, it is set to whether the state of `payment_hash` has been written
+ * to the store. A held payment or cancellation which is not would be lost on restart, so hosts
+ * may want to wait for `HodlInvoiceManager_timer_tick_occurred` to retry the write first.
  */
-struct LDKCResult_UpdateAddHTLCDecodeErrorZ CResult_UpdateAddHTLCDecodeErrorZ_clone(const struct LDKCResult_UpdateAddHTLCDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCOption_u64Z HodlInvoiceManager_held_amount_msat(const struct LDKHodlInvoiceManager *NONNULL_PTR this_arg, const uint8_t (*payment_hash)[32], bool *persisted);
 
 /**
- * Creates a new CResult_PingDecodeErrorZ in the success state.
+ * Fails back held payments which reached their deadline, forgets registrations whose invoice
+ * expired without being paid and retries failed writes. Should be called about once a minute.
  */
-struct LDKCResult_PingDecodeErrorZ CResult_PingDecodeErrorZ_ok(struct LDKPing o);
+void HodlInvoiceManager_timer_tick_occurred(const struct LDKHodlInvoiceManager *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_PingDecodeErrorZ in the error state.
+ * Wraps `inner` in an [`EventHandler`] which starts holding payments received for registered
+ * payment hashes before passing their `Event::PaymentReceived` on to `inner`. Payments to
+ * cancelled payment hashes are failed back instead. All other events are passed on to `inner`.
+ *
+ * The returned handler must be freed before `this_arg` is.
  */
-struct LDKCResult_PingDecodeErrorZ CResult_PingDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKEventHandler HodlInvoiceManager_event_handler(const struct LDKHodlInvoiceManager *NONNULL_PTR this_arg, struct LDKEventHandler inner);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the PaymentDetails.
  */
-bool CResult_PingDecodeErrorZ_is_ok(const struct LDKCResult_PingDecodeErrorZ *NONNULL_PTR o);
+void PaymentDetails_free(struct LDKPaymentDetails _res);
 
 /**
- * Frees any resources used by the CResult_PingDecodeErrorZ.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void CResult_PingDecodeErrorZ_free(struct LDKCResult_PingDecodeErrorZ _res);
+void CVec_PaymentDetailsZ_free(struct LDKCVec_PaymentDetailsZ _res);
 
 /**
- * Creates a new CResult_PingDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources associated with the PaymentDetails, if we are in the Some state
  */
-struct LDKCResult_PingDecodeErrorZ CResult_PingDecodeErrorZ_clone(const struct LDKCResult_PingDecodeErrorZ *NONNULL_PTR orig);
+void COption_PaymentDetailsZ_free(struct LDKCOption_PaymentDetailsZ _res);
 
 /**
- * Creates a new CResult_PongDecodeErrorZ in the success state.
+ * Creates a new CResult_PaymentStoreIOErrorZ in the success state.
  */
-struct LDKCResult_PongDecodeErrorZ CResult_PongDecodeErrorZ_ok(struct LDKPong o);
+struct LDKCResult_PaymentStoreIOErrorZ CResult_PaymentStoreIOErrorZ_ok(struct LDKPaymentStore o);
 
 /**
- * Creates a new CResult_PongDecodeErrorZ in the error state.
+ * Creates a new CResult_PaymentStoreIOErrorZ in the error state.
  */
-struct LDKCResult_PongDecodeErrorZ CResult_PongDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_PaymentStoreIOErrorZ CResult_PaymentStoreIOErrorZ_err(enum LDKIOError e);
 
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_PongDecodeErrorZ_is_ok(const struct LDKCResult_PongDecodeErrorZ *NONNULL_PTR o);
+bool CResult_PaymentStoreIOErrorZ_is_ok(const struct LDKCResult_PaymentStoreIOErrorZ *NONNULL_PTR o);
 
 /**
- * Frees any resources used by the CResult_PongDecodeErrorZ.
+ * Frees any resources used by the CResult_PaymentStoreIOErrorZ.
  */
-void CResult_PongDecodeErrorZ_free(struct LDKCResult_PongDecodeErrorZ _res);
+void CResult_PaymentStoreIOErrorZ_free(struct LDKCResult_PaymentStoreIOErrorZ _res);
 
 /**
- * Creates a new CResult_PongDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the PaymentStore, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_PongDecodeErrorZ CResult_PongDecodeErrorZ_clone(const struct LDKCResult_PongDecodeErrorZ *NONNULL_PTR orig);
+void PaymentStore_free(struct LDKPaymentStore this_obj);
 
 /**
- * Creates a new CResult_UnsignedChannelAnnouncementDecodeErrorZ in the success state.
+ * Creates a payment store backed by `store`, loading the payments previously recorded in it.
  */
-struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ CResult_UnsignedChannelAnnouncementDecodeErrorZ_ok(struct LDKUnsignedChannelAnnouncement o);
+MUST_USE_RES struct LDKCResult_PaymentStoreIOErrorZ PaymentStore_new(struct LDKKVStore store, struct LDKLogger logger);
 
 /**
- * Creates a new CResult_UnsignedChannelAnnouncementDecodeErrorZ in the error state.
+ * Records an invoice we created, eg with `create_invoice_from_channelmanager`, as a pending
+ * inbound payment. Recording an invoice for an already recorded payment hash replaces it.
  */
-struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ CResult_UnsignedChannelAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_NoneErrorZ PaymentStore_record_invoice(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKInvoice *NONNULL_PTR invoice);
 
 /**
- * Checks if the given object is currently in the success state
+ * Records `invoice` as a pending outbound payment and pays it through `payer`. The record is
+ * dropped if the payment was not sent. See `InvoicePayer_pay_invoice` for details.
+ *
+ * Fails with `PaymentError_Invoice` if a payment for the same invoice is being sent by another
+ * thread.
  */
-bool CResult_UnsignedChannelAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ PaymentStore_pay_invoice(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKInvoicePayer *NONNULL_PTR payer, const struct LDKInvoice *NONNULL_PTR invoice);
 
 /**
- * Frees any resources used by the CResult_UnsignedChannelAnnouncementDecodeErrorZ.
+ * Records the zero-value `invoice` as a pending outbound payment and pays it through `payer`
+ * using the given amount. The record is dropped if the payment was not sent. See
+ * `InvoicePayer_pay_zero_value_invoice` for details.
  */
-void CResult_UnsignedChannelAnnouncementDecodeErrorZ_free(struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ PaymentStore_pay_zero_value_invoice(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKInvoicePayer *NONNULL_PTR payer, const struct LDKInvoice *NONNULL_PTR invoice, uint64_t amount_msats);
 
 /**
- * Creates a new CResult_UnsignedChannelAnnouncementDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Records a pending outbound payment to `pubkey` and pays it through `payer` using the hash of
+ * `payment_preimage`. The record is dropped if the payment was not sent. See
+ * `InvoicePayer_pay_pubkey` for details.
  */
-struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ CResult_UnsignedChannelAnnouncementDecodeErrorZ_clone(const struct LDKCResult_UnsignedChannelAnnouncementDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ PaymentStore_pay_pubkey(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKInvoicePayer *NONNULL_PTR payer, struct LDKPublicKey pubkey, struct LDKThirtyTwoBytes payment_preimage, uint64_t amount_msats, uint32_t final_cltv_expiry_delta);
 
 /**
- * Creates a new CResult_ChannelAnnouncementDecodeErrorZ in the success state.
+ * Records `invoice` as a pending outbound payment and pays it through `payer`, as
+ * [`PaymentStore_pay_invoice`] does for an `InvoicePayer`.
  */
-struct LDKCResult_ChannelAnnouncementDecodeErrorZ CResult_ChannelAnnouncementDecodeErrorZ_ok(struct LDKChannelAnnouncement o);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ PaymentStore_retrying_pay_invoice(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKRetryingInvoicePayer *NONNULL_PTR payer, const struct LDKInvoice *NONNULL_PTR invoice);
 
 /**
- * Creates a new CResult_ChannelAnnouncementDecodeErrorZ in the error state.
+ * Records the zero-value `invoice` as a pending outbound payment and pays it through `payer`, as
+ * [`PaymentStore_pay_zero_value_invoice`] does for an `InvoicePayer`.
  */
-struct LDKCResult_ChannelAnnouncementDecodeErrorZ CResult_ChannelAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ PaymentStore_retrying_pay_zero_value_invoice(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKRetryingInvoicePayer *NONNULL_PTR payer, const struct LDKInvoice *NONNULL_PTR invoice, uint64_t amount_msats);
 
 /**
- * Checks if the given object is currently in the success state
+ * Records a pending outbound payment to `pubkey` and pays it through `payer`, as
+ * [`PaymentStore_pay_pubkey`] does for an `InvoicePayer`.
  */
-bool CResult_ChannelAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_ChannelAnnouncementDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ PaymentStore_retrying_pay_pubkey(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKRetryingInvoicePayer *NONNULL_PTR payer, struct LDKPublicKey pubkey, struct LDKThirtyTwoBytes payment_preimage, uint64_t amount_msats, uint32_t final_cltv_expiry_delta);
 
 /**
- * Frees any resources used by the CResult_ChannelAnnouncementDecodeErrorZ.
+ * Claims a received payment with `channel_manager`, as `ChannelManager_claim_funds` does, and
+ * marks the inbound payment as succeeded if it could be claimed.
  */
-void CResult_ChannelAnnouncementDecodeErrorZ_free(struct LDKCResult_ChannelAnnouncementDecodeErrorZ _res);
+MUST_USE_RES bool PaymentStore_claim_funds(const struct LDKPaymentStore *NONNULL_PTR this_arg, const struct LDKChannelManager *NONNULL_PTR channel_manager, struct LDKThirtyTwoBytes payment_preimage);
 
 /**
- * Creates a new CResult_ChannelAnnouncementDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Marks the inbound payment with the given payment hash as succeeded, for payments claimed
+ * other than through [`PaymentStore_claim_funds`], eg with `HodlInvoiceManager_settle`.
  */
-struct LDKCResult_ChannelAnnouncementDecodeErrorZ CResult_ChannelAnnouncementDecodeErrorZ_clone(const struct LDKCResult_ChannelAnnouncementDecodeErrorZ *NONNULL_PTR orig);
+void PaymentStore_payment_claimed(const struct LDKPaymentStore *NONNULL_PTR this_arg, const uint8_t (*payment_hash)[32]);
 
 /**
- * Creates a new CResult_UnsignedChannelUpdateDecodeErrorZ in the success state.
+ * Returns the inbound payment with the given payment hash, if one was recorded.
  */
-struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ CResult_UnsignedChannelUpdateDecodeErrorZ_ok(struct LDKUnsignedChannelUpdate o);
+MUST_USE_RES struct LDKCOption_PaymentDetailsZ PaymentStore_get_inbound_payment(const struct LDKPaymentStore *NONNULL_PTR this_arg, const uint8_t (*payment_hash)[32]);
 
 /**
- * Creates a new CResult_UnsignedChannelUpdateDecodeErrorZ in the error state.
+ * Returns the outbound payment with the given payment id, if one was recorded.
  */
-struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ CResult_UnsignedChannelUpdateDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCOption_PaymentDetailsZ PaymentStore_get_outbound_payment(const struct LDKPaymentStore *NONNULL_PTR this_arg, const uint8_t (*payment_id)[32]);
 
 /**
- * Checks if the given object is currently in the success state
+ * Returns all recorded inbound and outbound payments, most recently created first.
  */
-bool CResult_UnsignedChannelUpdateDecodeErrorZ_is_ok(const struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCVec_PaymentDetailsZ PaymentStore_list_payments(const struct LDKPaymentStore *NONNULL_PTR this_arg);
 
 /**
- * Frees any resources used by the CResult_UnsignedChannelUpdateDecodeErrorZ.
+ * Returns the inbound payments which have not been received, and the outbound payments which
+ * are still in flight, most recently created first. Expired invoices and received but unclaimed
+ * payments are not included.
  */
-void CResult_UnsignedChannelUpdateDecodeErrorZ_free(struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ _res);
+MUST_USE_RES struct LDKCVec_PaymentDetailsZ PaymentStore_list_pending_payments(const struct LDKPaymentStore *NONNULL_PTR this_arg);
 
 /**
- * Creates a new CResult_UnsignedChannelUpdateDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Wraps `inner` in an [`EventHandler`] which updates recorded payments from payment events
+ * before passing every event on to `inner`.
+ *
+ * Inbound payments received without a recorded invoice, eg spontaneous payments, are recorded
+ * when received.
+ *
+ * The returned handler must be freed before `this_arg` is.
  */
-struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ CResult_UnsignedChannelUpdateDecodeErrorZ_clone(const struct LDKCResult_UnsignedChannelUpdateDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKEventHandler PaymentStore_event_handler(const struct LDKPaymentStore *NONNULL_PTR this_arg, struct LDKEventHandler inner);
 
 /**
- * Creates a new CResult_ChannelUpdateDecodeErrorZ in the success state.
+ * Frees any resources used by the RetryStrategy.
  */
-struct LDKCResult_ChannelUpdateDecodeErrorZ CResult_ChannelUpdateDecodeErrorZ_ok(struct LDKChannelUpdate o);
+void RetryStrategy_free(struct LDKRetryStrategy _res);
 
 /**
- * Creates a new CResult_ChannelUpdateDecodeErrorZ in the error state.
+ * Returns a strategy which attempts a payment up to four times, with no timeout or fee limit,
+ * excluding failed channels but not failed nodes.
  */
-struct LDKCResult_ChannelUpdateDecodeErrorZ CResult_ChannelUpdateDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKRetryStrategy RetryStrategy_default(void);
 
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the PaymentPathAttempt.
  */
-bool CResult_ChannelUpdateDecodeErrorZ_is_ok(const struct LDKCResult_ChannelUpdateDecodeErrorZ *NONNULL_PTR o);
+void PaymentPathAttempt_free(struct LDKPaymentPathAttempt _res);
 
 /**
- * Frees any resources used by the CResult_ChannelUpdateDecodeErrorZ.
+ * Frees the buffer pointed to by `data` if `datalen` is non-0.
  */
-void CResult_ChannelUpdateDecodeErrorZ_free(struct LDKCResult_ChannelUpdateDecodeErrorZ _res);
+void CVec_PaymentPathAttemptZ_free(struct LDKCVec_PaymentPathAttemptZ _res);
 
 /**
- * Creates a new CResult_ChannelUpdateDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the PaymentOutcome.
  */
-struct LDKCResult_ChannelUpdateDecodeErrorZ CResult_ChannelUpdateDecodeErrorZ_clone(const struct LDKCResult_ChannelUpdateDecodeErrorZ *NONNULL_PTR orig);
+void PaymentOutcome_free(struct LDKPaymentOutcome _res);
 
 /**
- * Creates a new CResult_ErrorMessageDecodeErrorZ in the success state.
+ * Calls the free function if one is set
  */
-struct LDKCResult_ErrorMessageDecodeErrorZ CResult_ErrorMessageDecodeErrorZ_ok(struct LDKErrorMessage o);
+void PaymentOutcomeHandler_free(struct LDKPaymentOutcomeHandler this_ptr);
 
 /**
- * Creates a new CResult_ErrorMessageDecodeErrorZ in the error state.
+ * Frees any resources used by the RetryingInvoicePayer, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_ErrorMessageDecodeErrorZ CResult_ErrorMessageDecodeErrorZ_err(struct LDKDecodeError e);
+void RetryingInvoicePayer_free(struct LDKRetryingInvoicePayer this_obj);
 
 /**
- * Checks if the given object is currently in the success state
+ * Creates a payer which retries failed payment paths according to `strategy`.
+ *
+ * Events are passed on to `event_handler` except for `Event::PaymentPathFailed` events for
+ * paths which were retried. Each payment's [`PaymentOutcome`] is passed to `outcome_handler`.
+ *
+ * The payer must be freed before `scorer` is.
  */
-bool CResult_ErrorMessageDecodeErrorZ_is_ok(const struct LDKCResult_ErrorMessageDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKRetryingInvoicePayer RetryingInvoicePayer_new(struct LDKPayer payer, struct LDKRouter router, const struct LDKMultiThreadedLockableScore *NONNULL_PTR scorer, struct LDKLogger logger, struct LDKEventHandler event_handler, struct LDKPaymentOutcomeHandler outcome_handler, struct LDKRetryStrategy strategy);
 
 /**
- * Frees any resources used by the CResult_ErrorMessageDecodeErrorZ.
+ * Pays the given [`Invoice`], tracking it for retries until it succeeds or fails.
+ *
+ * Fails if a payment for the invoice's payment hash is already pending through this payer.
  */
-void CResult_ErrorMessageDecodeErrorZ_free(struct LDKCResult_ErrorMessageDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ RetryingInvoicePayer_pay_invoice(const struct LDKRetryingInvoicePayer *NONNULL_PTR this_arg, const struct LDKInvoice *NONNULL_PTR invoice);
 
 /**
- * Creates a new CResult_ErrorMessageDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Pays the given zero-value [`Invoice`] using the given amount, tracking it for retries until it
+ * succeeds or fails.
  */
-struct LDKCResult_ErrorMessageDecodeErrorZ CResult_ErrorMessageDecodeErrorZ_clone(const struct LDKCResult_ErrorMessageDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ RetryingInvoicePayer_pay_zero_value_invoice(const struct LDKRetryingInvoicePayer *NONNULL_PTR this_arg, const struct LDKInvoice *NONNULL_PTR invoice, uint64_t amount_msats);
 
 /**
- * Creates a new CResult_UnsignedNodeAnnouncementDecodeErrorZ in the success state.
+ * Pays `pubkey` an amount using the hash of the given preimage, tracking it for retries until it
+ * succeeds or fails.
  */
-struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ CResult_UnsignedNodeAnnouncementDecodeErrorZ_ok(struct LDKUnsignedNodeAnnouncement o);
+MUST_USE_RES struct LDKCResult_PaymentIdPaymentErrorZ RetryingInvoicePayer_pay_pubkey(const struct LDKRetryingInvoicePayer *NONNULL_PTR this_arg, struct LDKPublicKey pubkey, struct LDKThirtyTwoBytes payment_preimage, uint64_t amount_msats, uint32_t final_cltv_expiry_delta);
 
 /**
- * Creates a new CResult_UnsignedNodeAnnouncementDecodeErrorZ in the error state.
+ * Constructs a new EventHandler which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned EventHandler must be freed before this_arg is
  */
-struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ CResult_UnsignedNodeAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKEventHandler RetryingInvoicePayer_as_EventHandler(const struct LDKRetryingInvoicePayer *NONNULL_PTR this_arg);
 
+#if defined(LDK_LINUX)
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_NoneLdkNetErrorZ in the success state.
  */
-bool CResult_UnsignedNodeAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_NoneLdkNetErrorZ CResult_NoneLdkNetErrorZ_ok(void);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Frees any resources used by the CResult_UnsignedNodeAnnouncementDecodeErrorZ.
+ * Creates a new CResult_NoneLdkNetErrorZ in the error state.
  */
-void CResult_UnsignedNodeAnnouncementDecodeErrorZ_free(struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ _res);
+struct LDKCResult_NoneLdkNetErrorZ CResult_NoneLdkNetErrorZ_err(enum LDKLdkNetError e);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_UnsignedNodeAnnouncementDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ CResult_UnsignedNodeAnnouncementDecodeErrorZ_clone(const struct LDKCResult_UnsignedNodeAnnouncementDecodeErrorZ *NONNULL_PTR orig);
+bool CResult_NoneLdkNetErrorZ_is_ok(const struct LDKCResult_NoneLdkNetErrorZ *NONNULL_PTR o);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_NodeAnnouncementDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_NoneLdkNetErrorZ.
  */
-struct LDKCResult_NodeAnnouncementDecodeErrorZ CResult_NodeAnnouncementDecodeErrorZ_ok(struct LDKNodeAnnouncement o);
+void CResult_NoneLdkNetErrorZ_free(struct LDKCResult_NoneLdkNetErrorZ _res);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_NodeAnnouncementDecodeErrorZ in the error state.
+ * Frees any resources used by the LdkNet, if is_owned is set and inner is non-NULL.
+ *
+ * This stops the driver first if [`LdkNet_stop`] has not been called.
  */
-struct LDKCResult_NodeAnnouncementDecodeErrorZ CResult_NodeAnnouncementDecodeErrorZ_err(struct LDKDecodeError e);
+void LdkNet_free(struct LDKLdkNet this_obj);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new CResult_LdkNetLdkNetErrorZ in the success state.
  */
-bool CResult_NodeAnnouncementDecodeErrorZ_is_ok(const struct LDKCResult_NodeAnnouncementDecodeErrorZ *NONNULL_PTR o);
+struct LDKCResult_LdkNetLdkNetErrorZ CResult_LdkNetLdkNetErrorZ_ok(struct LDKLdkNet o);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Frees any resources used by the CResult_NodeAnnouncementDecodeErrorZ.
+ * Creates a new CResult_LdkNetLdkNetErrorZ in the error state.
  */
-void CResult_NodeAnnouncementDecodeErrorZ_free(struct LDKCResult_NodeAnnouncementDecodeErrorZ _res);
+struct LDKCResult_LdkNetLdkNetErrorZ CResult_LdkNetLdkNetErrorZ_err(enum LDKLdkNetError e);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_NodeAnnouncementDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Checks if the given object is currently in the success state
  */
-struct LDKCResult_NodeAnnouncementDecodeErrorZ CResult_NodeAnnouncementDecodeErrorZ_clone(const struct LDKCResult_NodeAnnouncementDecodeErrorZ *NONNULL_PTR orig);
+bool CResult_LdkNetLdkNetErrorZ_is_ok(const struct LDKCResult_LdkNetLdkNetErrorZ *NONNULL_PTR o);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_QueryShortChannelIdsDecodeErrorZ in the success state.
+ * Frees any resources used by the CResult_LdkNetLdkNetErrorZ.
  */
-struct LDKCResult_QueryShortChannelIdsDecodeErrorZ CResult_QueryShortChannelIdsDecodeErrorZ_ok(struct LDKQueryShortChannelIds o);
+void CResult_LdkNetLdkNetErrorZ_free(struct LDKCResult_LdkNetLdkNetErrorZ _res);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_QueryShortChannelIdsDecodeErrorZ in the error state.
+ * Starts a background thread which handles socket events for the given `PeerManager`.
+ *
+ * The `PeerManager` is referenced, not copied, and must not be freed until the returned
+ * `LdkNet` has been stopped.
  */
-struct LDKCResult_QueryShortChannelIdsDecodeErrorZ CResult_QueryShortChannelIdsDecodeErrorZ_err(struct LDKDecodeError e);
+MUST_USE_RES struct LDKCResult_LdkNetLdkNetErrorZ LdkNet_start(const struct LDKPeerManager *NONNULL_PTR peer_manager);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Checks if the given object is currently in the success state
+ * Connects to the peer with the given node id at `addr`, and hands the connection to the
+ * `PeerManager`.
+ *
+ * `addr` may be any `host:port` string, including IPv6 addresses in the form `[addr]:port`.
+ * Hostnames are resolved with the system resolver. `timeout_secs` must be non-zero.
+ *
+ * Note that this blocks the calling thread while resolving `addr` and until the TCP connection
+ * has been established, for at most `timeout_secs` seconds per resolved address, so it should
+ * not be called from a thread which must stay responsive, such as one handling LDK events. The
+ * Lightning handshake itself completes in the background.
  */
-bool CResult_QueryShortChannelIdsDecodeErrorZ_is_ok(const struct LDKCResult_QueryShortChannelIdsDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_NoneLdkNetErrorZ LdkNet_connect(const struct LDKLdkNet *NONNULL_PTR this_arg, struct LDKPublicKey their_node_id, struct LDKStr addr, uint64_t timeout_secs);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Frees any resources used by the CResult_QueryShortChannelIdsDecodeErrorZ.
+ * Binds a listening socket to `addr` (in the same format as for [`LdkNet_connect`]) and hands
+ * all inbound connections to the `PeerManager`.
  */
-void CResult_QueryShortChannelIdsDecodeErrorZ_free(struct LDKCResult_QueryShortChannelIdsDecodeErrorZ _res);
+MUST_USE_RES struct LDKCResult_NoneLdkNetErrorZ LdkNet_listen(const struct LDKLdkNet *NONNULL_PTR this_arg, struct LDKStr addr);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_QueryShortChannelIdsDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Stops the background thread, disconnecting all peers and closing all listening sockets.
+ *
+ * Blocks until the background thread has exited, after which the `PeerManager` may be freed.
+ * Calling this more than once has no effect.
  */
-struct LDKCResult_QueryShortChannelIdsDecodeErrorZ CResult_QueryShortChannelIdsDecodeErrorZ_clone(const struct LDKCResult_QueryShortChannelIdsDecodeErrorZ *NONNULL_PTR orig);
+void LdkNet_stop(const struct LDKLdkNet *NONNULL_PTR this_arg);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_ReplyShortChannelIdsEndDecodeErrorZ in the success state.
+ * Installs a policy limiting inbound connections and per-connection bandwidth, replacing any
+ * existing policy.
+ *
+ * Inbound connection limits and the peer filter apply immediately, while read rate limits only
+ * apply to connections made after this call.
  */
-struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ CResult_ReplyShortChannelIdsEndDecodeErrorZ_ok(struct LDKReplyShortChannelIdsEnd o);
+void LdkNet_set_connection_policy(const struct LDKLdkNet *NONNULL_PTR this_arg, struct LDKConnectionPolicy policy);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_ReplyShortChannelIdsEndDecodeErrorZ in the error state.
+ * Calls the free function if one is set
  */
-struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ CResult_ReplyShortChannelIdsEndDecodeErrorZ_err(struct LDKDecodeError e);
+void PeerFilter_free(struct LDKPeerFilter this_ptr);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Checks if the given object is currently in the success state
+ * Frees any resources used by the ConnectionPolicy, if is_owned is set and inner is non-NULL.
  */
-bool CResult_ReplyShortChannelIdsEndDecodeErrorZ_is_ok(const struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ *NONNULL_PTR o);
+void ConnectionPolicy_free(struct LDKConnectionPolicy this_obj);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Frees any resources used by the CResult_ReplyShortChannelIdsEndDecodeErrorZ.
+ * Creates a new ConnectionPolicy without a peer filter.
+ *
+ * At most `max_inbound` inbound connections are accepted at once, and at most
+ * `max_inbound_per_ip` from any single IP address. Outbound connections do not count towards
+ * either limit.
+ *
+ * Data is read from each connection (inbound or outbound) at no more than `bytes_per_sec`
+ * bytes per second on average, with bursts of up to `burst_bytes`. Peers sending faster simply
+ * have their reads delayed, relying on TCP flow control to slow them down.
+ *
+ * Any of the limits may be set to 0 to disable it. Setting either `bytes_per_sec` or
+ * `burst_bytes` to 0 disables rate limiting entirely.
  */
-void CResult_ReplyShortChannelIdsEndDecodeErrorZ_free(struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ _res);
+MUST_USE_RES struct LDKConnectionPolicy ConnectionPolicy_new(uintptr_t max_inbound, uintptr_t max_inbound_per_ip, uint64_t bytes_per_sec, uint64_t burst_bytes);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_ReplyShortChannelIdsEndDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Sets the filter used to decide whether to stay connected to peers once their handshake
+ * completes, replacing any existing filter.
+ *
+ * Peers which are rejected are disconnected with `PeerManager_disconnect_by_node_id`.
  */
-struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ CResult_ReplyShortChannelIdsEndDecodeErrorZ_clone(const struct LDKCResult_ReplyShortChannelIdsEndDecodeErrorZ *NONNULL_PTR orig);
+void ConnectionPolicy_set_peer_filter(struct LDKConnectionPolicy *NONNULL_PTR this_arg, struct LDKPeerFilter peer_filter);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_QueryChannelRangeDecodeErrorZ in the success state.
+ * Frees any resources used by the Socks5Proxy, if is_owned is set and inner is non-NULL.
  */
-struct LDKCResult_QueryChannelRangeDecodeErrorZ CResult_QueryChannelRangeDecodeErrorZ_ok(struct LDKQueryChannelRange o);
+void Socks5Proxy_free(struct LDKSocks5Proxy this_obj);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_QueryChannelRangeDecodeErrorZ in the error state.
+ * Creates a copy of the Socks5Proxy
  */
-struct LDKCResult_QueryChannelRangeDecodeErrorZ CResult_QueryChannelRangeDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKSocks5Proxy Socks5Proxy_clone(const struct LDKSocks5Proxy *NONNULL_PTR orig);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Checks if the given object is currently in the success state
+ * Creates a new Socks5Proxy for the proxy listening at `proxy_addr`, e.g. `"127.0.0.1:9050"`
+ * for a local Tor daemon.
  */
-bool CResult_QueryChannelRangeDecodeErrorZ_is_ok(const struct LDKCResult_QueryChannelRangeDecodeErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKSocks5Proxy Socks5Proxy_new(struct LDKStr proxy_addr);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Frees any resources used by the CResult_QueryChannelRangeDecodeErrorZ.
+ * Creates a new Socks5Proxy for the proxy listening at `proxy_addr`, which authenticates with
+ * `username` and `password` whenever no other credentials are given for a connection, including
+ * for the connections made by a `PeerReconnector`.
+ *
+ * `username` and `password` must each be between 1 and 255 bytes long.
  */
-void CResult_QueryChannelRangeDecodeErrorZ_free(struct LDKCResult_QueryChannelRangeDecodeErrorZ _res);
+MUST_USE_RES struct LDKSocks5Proxy Socks5Proxy_new_with_credentials(struct LDKStr proxy_addr, struct LDKStr username, struct LDKStr password);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_QueryChannelRangeDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Connects to the peer with the given node id at `addr` through the proxy, and hands the
+ * connection to the `PeerManager` driven by `net`.
+ *
+ * Onion addresses are passed to the proxy by name. If `username` and `password` are both
+ * empty, the credentials given to [`Socks5Proxy_new_with_credentials`] are used, or no
+ * authentication is offered to the proxy if there are none. Otherwise they are used as stream
+ * isolation credentials and must each be between 1 and 255 bytes long.
+ *
+ * This blocks until the proxy has established the connection, with each step taking at most
+ * `timeout_secs` seconds, which must be non-zero.
  */
-struct LDKCResult_QueryChannelRangeDecodeErrorZ CResult_QueryChannelRangeDecodeErrorZ_clone(const struct LDKCResult_QueryChannelRangeDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_NoneLdkNetErrorZ Socks5Proxy_connect(const struct LDKSocks5Proxy *NONNULL_PTR this_arg, const struct LDKLdkNet *NONNULL_PTR net, struct LDKPublicKey their_node_id, const struct LDKNetAddress *NONNULL_PTR addr, struct LDKStr username, struct LDKStr password, uint64_t timeout_secs);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_ReplyChannelRangeDecodeErrorZ in the success state.
+ * Connects to the peer with the given node id at `host`:`port` through the proxy, as
+ * [`Socks5Proxy_connect`] does. `host` is resolved by the proxy, and may be a hostname, an
+ * onion address or a literal IP address.
  */
-struct LDKCResult_ReplyChannelRangeDecodeErrorZ CResult_ReplyChannelRangeDecodeErrorZ_ok(struct LDKReplyChannelRange o);
+MUST_USE_RES struct LDKCResult_NoneLdkNetErrorZ Socks5Proxy_connect_host(const struct LDKSocks5Proxy *NONNULL_PTR this_arg, const struct LDKLdkNet *NONNULL_PTR net, struct LDKPublicKey their_node_id, struct LDKStr host, uint16_t port, struct LDKStr username, struct LDKStr password, uint64_t timeout_secs);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_ReplyChannelRangeDecodeErrorZ in the error state.
+ * Frees any resources used by the PeerEvent
  */
-struct LDKCResult_ReplyChannelRangeDecodeErrorZ CResult_ReplyChannelRangeDecodeErrorZ_err(struct LDKDecodeError e);
+void PeerEvent_free(struct LDKPeerEvent this_ptr);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Checks if the given object is currently in the success state
+ * Creates a copy of the PeerEvent
  */
-bool CResult_ReplyChannelRangeDecodeErrorZ_is_ok(const struct LDKCResult_ReplyChannelRangeDecodeErrorZ *NONNULL_PTR o);
+struct LDKPeerEvent PeerEvent_clone(const struct LDKPeerEvent *NONNULL_PTR orig);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Frees any resources used by the CResult_ReplyChannelRangeDecodeErrorZ.
+ * Calls the free function if one is set
  */
-void CResult_ReplyChannelRangeDecodeErrorZ_free(struct LDKCResult_ReplyChannelRangeDecodeErrorZ _res);
+void PeerEventHandler_free(struct LDKPeerEventHandler this_ptr);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_ReplyChannelRangeDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Frees any resources used by the PeerReconnector, if is_owned is set and inner is non-NULL.
+ *
+ * This stops reconnecting and blocks until any in-progress connection attempts complete. It
+ * must be called before the `LdkNet` and `PeerManager` are freed.
  */
-struct LDKCResult_ReplyChannelRangeDecodeErrorZ CResult_ReplyChannelRangeDecodeErrorZ_clone(const struct LDKCResult_ReplyChannelRangeDecodeErrorZ *NONNULL_PTR orig);
+void PeerReconnector_free(struct LDKPeerReconnector this_obj);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_GossipTimestampFilterDecodeErrorZ in the success state.
+ * Creates a new CResult_PeerReconnectorIOErrorZ in the success state.
  */
-struct LDKCResult_GossipTimestampFilterDecodeErrorZ CResult_GossipTimestampFilterDecodeErrorZ_ok(struct LDKGossipTimestampFilter o);
+struct LDKCResult_PeerReconnectorIOErrorZ CResult_PeerReconnectorIOErrorZ_ok(struct LDKPeerReconnector o);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_GossipTimestampFilterDecodeErrorZ in the error state.
+ * Creates a new CResult_PeerReconnectorIOErrorZ in the error state.
  */
-struct LDKCResult_GossipTimestampFilterDecodeErrorZ CResult_GossipTimestampFilterDecodeErrorZ_err(struct LDKDecodeError e);
+struct LDKCResult_PeerReconnectorIOErrorZ CResult_PeerReconnectorIOErrorZ_err(enum LDKIOError e);
+#endif
 
+#if defined(LDK_LINUX)
 /**
  * Checks if the given object is currently in the success state
  */
-bool CResult_GossipTimestampFilterDecodeErrorZ_is_ok(const struct LDKCResult_GossipTimestampFilterDecodeErrorZ *NONNULL_PTR o);
+bool CResult_PeerReconnectorIOErrorZ_is_ok(const struct LDKCResult_PeerReconnectorIOErrorZ *NONNULL_PTR o);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Frees any resources used by the CResult_GossipTimestampFilterDecodeErrorZ.
+ * Frees any resources used by the CResult_PeerReconnectorIOErrorZ.
  */
-void CResult_GossipTimestampFilterDecodeErrorZ_free(struct LDKCResult_GossipTimestampFilterDecodeErrorZ _res);
+void CResult_PeerReconnectorIOErrorZ_free(struct LDKCResult_PeerReconnectorIOErrorZ _res);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_GossipTimestampFilterDecodeErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Loads the peer address book from `store` (if one has been stored) and starts a background
+ * thread which reconnects to all peers in it whenever they are not connected via `net`.
+ *
+ * If `proxy` is set, all connections are made through it, authenticating with the credentials
+ * it was created with (see `Socks5Proxy_new_with_credentials`), otherwise onion addresses are
+ * ignored. Failed connection attempts are retried after `base_backoff_secs`, doubling after
+ * each consecutive failure up to `max_backoff_secs`, with random jitter. At most
+ * `max_concurrent_connects` connection attempts, each taking at most `connect_timeout_secs`,
+ * are made at once.
+ *
+ * Note that proxy (or a relevant inner pointer) may be NULL or all-0s to represent None
  */
-struct LDKCResult_GossipTimestampFilterDecodeErrorZ CResult_GossipTimestampFilterDecodeErrorZ_clone(const struct LDKCResult_GossipTimestampFilterDecodeErrorZ *NONNULL_PTR orig);
+MUST_USE_RES struct LDKCResult_PeerReconnectorIOErrorZ PeerReconnector_new(const struct LDKLdkNet *NONNULL_PTR net, struct LDKKVStore store, struct LDKSocks5Proxy proxy, struct LDKPeerEventHandler event_handler, uintptr_t max_concurrent_connects, uint64_t base_backoff_secs, uint64_t max_backoff_secs, uint64_t connect_timeout_secs);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_InvoiceSignOrCreationErrorZ in the success state.
+ * Adds the given peer to the address book, or adds the given addresses to it if the peer is
+ * already known, and persists the address book.
  */
-struct LDKCResult_InvoiceSignOrCreationErrorZ CResult_InvoiceSignOrCreationErrorZ_ok(struct LDKInvoice o);
+MUST_USE_RES struct LDKCResult_NoneErrorZ PeerReconnector_add_peer(const struct LDKPeerReconnector *NONNULL_PTR this_arg, struct LDKPublicKey node_id, struct LDKCVec_NetAddressZ addresses);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Creates a new CResult_InvoiceSignOrCreationErrorZ in the error state.
+ * Removes the given peer from the address book and persists the address book. Any existing
+ * connection to the peer is left alone, but it will no longer be reconnected.
  */
-struct LDKCResult_InvoiceSignOrCreationErrorZ CResult_InvoiceSignOrCreationErrorZ_err(struct LDKSignOrCreationError e);
+MUST_USE_RES struct LDKCResult_NoneErrorZ PeerReconnector_remove_peer(const struct LDKPeerReconnector *NONNULL_PTR this_arg, struct LDKPublicKey node_id);
+#endif
 
+#if defined(LDK_LINUX)
 /**
- * Checks if the given object is currently in the success state
+ * Adds the counterparties of all channels in `channel_manager` to the address book, along with
+ * the addresses they announced in `network_graph`, and persists the address book.
+ *
+ * Counterparties which are not already in the address book and have not announced any
+ * addresses are skipped, as we have no way to reach them. Call this on startup and whenever
+ * new channels are opened.
  */
-bool CResult_InvoiceSignOrCreationErrorZ_is_ok(const struct LDKCResult_InvoiceSignOrCreationErrorZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_NoneErrorZ PeerReconnector_seed(const struct LDKPeerReconnector *NONNULL_PTR this_arg, const struct LDKChannelManager *NONNULL_PTR channel_manager, const struct LDKNetworkGraph *NONNULL_PTR network_graph);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Frees any resources used by the CResult_InvoiceSignOrCreationErrorZ.
+ * Frees any resources used by the SqliteStore, if is_owned is set and inner is non-NULL.
+ *
+ * Any `ChannelMonitor`s which are still queued are committed before this returns. If the
+ * commit fails it is retried every second, so this blocks until the database is writable again.
  */
-void CResult_InvoiceSignOrCreationErrorZ_free(struct LDKCResult_InvoiceSignOrCreationErrorZ _res);
+void SqliteStore_free(struct LDKSqliteStore this_obj);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Creates a new CResult_InvoiceSignOrCreationErrorZ which has the same data as `orig`
- * but with all dynamically-allocated buffers duplicated in new buffers.
+ * Creates a new CResult_SqliteStoreIOErrorZ in the success state.
  */
-struct LDKCResult_InvoiceSignOrCreationErrorZ CResult_InvoiceSignOrCreationErrorZ_clone(const struct LDKCResult_InvoiceSignOrCreationErrorZ *NONNULL_PTR orig);
+struct LDKCResult_SqliteStoreIOErrorZ CResult_SqliteStoreIOErrorZ_ok(struct LDKSqliteStore o);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Constructs a new COption_FilterZ containing a crate::lightning::chain::Filter
+ * Creates a new CResult_SqliteStoreIOErrorZ in the error state.
  */
-struct LDKCOption_FilterZ COption_FilterZ_some(struct LDKFilter o);
+struct LDKCResult_SqliteStoreIOErrorZ CResult_SqliteStoreIOErrorZ_err(enum LDKIOError e);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Constructs a new COption_FilterZ containing nothing
+ * Checks if the given object is currently in the success state
  */
-struct LDKCOption_FilterZ COption_FilterZ_none(void);
+bool CResult_SqliteStoreIOErrorZ_is_ok(const struct LDKCResult_SqliteStoreIOErrorZ *NONNULL_PTR o);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Frees any resources associated with the crate::lightning::chain::Filter, if we are in the Some state
+ * Frees any resources used by the CResult_SqliteStoreIOErrorZ.
  */
-void COption_FilterZ_free(struct LDKCOption_FilterZ _res);
+void CResult_SqliteStoreIOErrorZ_free(struct LDKCResult_SqliteStoreIOErrorZ _res);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Creates a new CResult_LockedChannelMonitorNoneZ in the success state.
+ * Opens (or creates) the SQLite database at `path`.
+ *
+ * Once `max_pending_updates` `ChannelMonitorUpdate`s have been stored for a channel, the next
+ * update causes the full `ChannelMonitor` to be rewritten and the stored updates to be removed.
+ * Larger values reduce write volume at the cost of slower loading. A value of 0 rewrites the
+ * full `ChannelMonitor` on every update.
  */
-struct LDKCResult_LockedChannelMonitorNoneZ CResult_LockedChannelMonitorNoneZ_ok(struct LDKLockedChannelMonitor o);
+MUST_USE_RES struct LDKCResult_SqliteStoreIOErrorZ SqliteStore_open(struct LDKStr path, uintptr_t max_pending_updates);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Creates a new CResult_LockedChannelMonitorNoneZ in the error state.
+ * Switches `ChannelMonitor` persistence to asynchronous commits which are completed via
+ * `ChainMonitor_channel_monitor_updated` on `chain_monitor`.
+ *
+ * `chain_monitor` must be the `ChainMonitor` this store's `Persist` implementation was given
+ * to, and it must not be freed until this `SqliteStore` has been.
  */
-struct LDKCResult_LockedChannelMonitorNoneZ CResult_LockedChannelMonitorNoneZ_err(void);
+void SqliteStore_register_chain_monitor(const struct LDKSqliteStore *NONNULL_PTR this_arg, const struct LDKChainMonitor *NONNULL_PTR chain_monitor);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Checks if the given object is currently in the success state
+ * Read all `ChannelMonitor`s from the database, applying any stored `ChannelMonitorUpdate`s which
+ * were not yet written as part of the full `ChannelMonitor`.
+ *
+ * The `broadcaster`, `fee_estimator` and `logger` are passed to `ChannelMonitor_update_monitor`
+ * when applying updates.
  */
-bool CResult_LockedChannelMonitorNoneZ_is_ok(const struct LDKCResult_LockedChannelMonitorNoneZ *NONNULL_PTR o);
+MUST_USE_RES struct LDKCResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ SqliteStore_read_channelmonitors(const struct LDKSqliteStore *NONNULL_PTR this_arg, const struct LDKKeysInterface *NONNULL_PTR keys_manager, const struct LDKBroadcasterInterface *NONNULL_PTR broadcaster, const struct LDKFeeEstimator *NONNULL_PTR fee_estimator, const struct LDKLogger *NONNULL_PTR logger);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Frees any resources used by the CResult_LockedChannelMonitorNoneZ.
+ * Constructs a new KVStore which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned KVStore must be freed before this_arg is
  */
-void CResult_LockedChannelMonitorNoneZ_free(struct LDKCResult_LockedChannelMonitorNoneZ _res);
+struct LDKKVStore SqliteStore_as_KVStore(const struct LDKSqliteStore *NONNULL_PTR this_arg);
+#endif
 
+#if defined(LDK_SQLITE)
 /**
- * Frees the buffer pointed to by `data` if `datalen` is non-0.
+ * Constructs a new Persist which calls the relevant methods on this_arg.
+ * This copies the `inner` pointer in this_arg and thus the returned Persist must be freed before this_arg is
  */
-void CVec_OutPointZ_free(struct LDKCVec_OutPointZ _res);
+struct LDKPersist SqliteStore_as_Persist(const struct LDKSqliteStore *NONNULL_PTR this_arg);
+#endif
 
 /**
  * Frees any resources used by the PaymentPurpose
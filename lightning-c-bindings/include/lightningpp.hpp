@@ -359,6 +359,95 @@ class CVec_TxOutZ;
 class CResult_C2Tuple_BlockHashChannelMonitorZDecodeErrorZ;
 class CVec_UpdateFailHTLCZ;
 class CResult_FundingLockedDecodeErrorZ;
+class CustomMessageError;
+class HodlInvoiceError;
+class LdkNetError;
+class MessageDirection;
+class NoiseError;
+class PaymentDirection;
+class PaymentOutcomeStatus;
+class PaymentPathStatus;
+class PaymentStatus;
+class PsbtFundingError;
+class RestoreError;
+class SeedError;
+class SignerPolicyRule;
+class WalletError;
+class Psbt;
+class ChainReplay;
+class RestoredMonitor;
+class CVec_RestoredMonitorZ;
+class RestoredNode;
+class CResult_RestoredNodeRestoreErrorZ;
+class KVStorePersister;
+class UpdateLogPersister;
+class SqliteStore;
+class CResult_CVec_u8ZIOErrorZ;
+class CVec_StrZ;
+class CResult_CVec_StrZIOErrorZ;
+class KVStore;
+class FilesystemStore;
+class EncryptedStore;
+class CResult_KVStorePersisterIOErrorZ;
+class CResult_UpdateLogPersisterIOErrorZ;
+class MessageTracer;
+class TracingMessageHandler;
+class MessageTraceFile;
+class CResult_MessageTraceFileIOErrorZ;
+class PeerChannelEncryptor;
+class CResult_CVec_u8ZNoiseErrorZ;
+class CResult_PublicKeyNoiseErrorZ;
+class CResult_u16NoiseErrorZ;
+class CResult_NoneCustomMessageErrorZ;
+class CustomMessageCallback;
+class CustomMessageRegistry;
+class EventQueue;
+class CResult_EventQueueIOErrorZ;
+class WakeupCallback;
+class ChannelManagerNotifier;
+class CResult_TransactionWalletErrorZ;
+class OnChainWallet;
+class CResult_OnChainWalletDecodeErrorZ;
+class OutputSweeper;
+class CResult_OutputSweeperIOErrorZ;
+class CResult_PsbtPsbtFundingErrorZ;
+class CResult_TransactionPsbtFundingErrorZ;
+class PsbtFunding;
+class CResult_StrSeedErrorZ;
+class CResult_NoneSeedErrorZ;
+class CResult_ThirtyTwoBytesSeedErrorZ;
+class SignerTransport;
+class RemoteSignerServer;
+class CResult_RemoteSignerServerIOErrorZ;
+class SignerPolicy;
+class InvoiceSigner;
+class InvoiceBuilder;
+class CResult_InvoiceBuilderHodlInvoiceErrorZ;
+class CResult_NoneHodlInvoiceErrorZ;
+class HodlInvoiceManager;
+class CResult_HodlInvoiceManagerIOErrorZ;
+class PaymentDetails;
+class CVec_PaymentDetailsZ;
+class COption_PaymentDetailsZ;
+class PaymentStore;
+class CResult_PaymentStoreIOErrorZ;
+class RetryingInvoicePayer;
+class RetryStrategy;
+class PaymentPathAttempt;
+class CVec_PaymentPathAttemptZ;
+class PaymentOutcome;
+class PaymentOutcomeHandler;
+class CResult_NoneLdkNetErrorZ;
+class LdkNet;
+class CResult_LdkNetLdkNetErrorZ;
+class ConnectionPolicy;
+class PeerFilter;
+class Socks5Proxy;
+class PeerEvent;
+class PeerEventHandler;
+class PeerReconnector;
+class CResult_PeerReconnectorIOErrorZ;
+class CResult_SqliteStoreIOErrorZ;
 
 class Str {
 private:
@@ -6409,6 +6498,1451 @@ public:
 	const LDKCResult_FundingLockedDecodeErrorZ* operator &() const { return &self; }
 	const LDKCResult_FundingLockedDecodeErrorZ* operator ->() const { return &self; }
 };
+class CustomMessageError {
+private:
+	LDKCustomMessageError self;
+public:
+	CustomMessageError(const CustomMessageError&) = delete;
+	CustomMessageError(CustomMessageError&& o) : self(o.self) { memset(&o, 0, sizeof(CustomMessageError)); }
+	CustomMessageError(LDKCustomMessageError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCustomMessageError)); }
+	operator LDKCustomMessageError() && { LDKCustomMessageError res = self; memset(&self, 0, sizeof(LDKCustomMessageError)); return res; }
+	CustomMessageError& operator=(CustomMessageError&& o) { self = o.self; memset(&o, 0, sizeof(CustomMessageError)); return *this; }
+	LDKCustomMessageError* operator &() { return &self; }
+	LDKCustomMessageError* operator ->() { return &self; }
+	const LDKCustomMessageError* operator &() const { return &self; }
+	const LDKCustomMessageError* operator ->() const { return &self; }
+};
+class HodlInvoiceError {
+private:
+	LDKHodlInvoiceError self;
+public:
+	HodlInvoiceError(const HodlInvoiceError&) = delete;
+	HodlInvoiceError(HodlInvoiceError&& o) : self(o.self) { memset(&o, 0, sizeof(HodlInvoiceError)); }
+	HodlInvoiceError(LDKHodlInvoiceError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKHodlInvoiceError)); }
+	operator LDKHodlInvoiceError() && { LDKHodlInvoiceError res = self; memset(&self, 0, sizeof(LDKHodlInvoiceError)); return res; }
+	HodlInvoiceError& operator=(HodlInvoiceError&& o) { self = o.self; memset(&o, 0, sizeof(HodlInvoiceError)); return *this; }
+	LDKHodlInvoiceError* operator &() { return &self; }
+	LDKHodlInvoiceError* operator ->() { return &self; }
+	const LDKHodlInvoiceError* operator &() const { return &self; }
+	const LDKHodlInvoiceError* operator ->() const { return &self; }
+};
+#if defined(LDK_LINUX)
+class LdkNetError {
+private:
+	LDKLdkNetError self;
+public:
+	LdkNetError(const LdkNetError&) = delete;
+	LdkNetError(LdkNetError&& o) : self(o.self) { memset(&o, 0, sizeof(LdkNetError)); }
+	LdkNetError(LDKLdkNetError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKLdkNetError)); }
+	operator LDKLdkNetError() && { LDKLdkNetError res = self; memset(&self, 0, sizeof(LDKLdkNetError)); return res; }
+	LdkNetError& operator=(LdkNetError&& o) { self = o.self; memset(&o, 0, sizeof(LdkNetError)); return *this; }
+	LDKLdkNetError* operator &() { return &self; }
+	LDKLdkNetError* operator ->() { return &self; }
+	const LDKLdkNetError* operator &() const { return &self; }
+	const LDKLdkNetError* operator ->() const { return &self; }
+};
+#endif
+class MessageDirection {
+private:
+	LDKMessageDirection self;
+public:
+	MessageDirection(const MessageDirection&) = delete;
+	MessageDirection(MessageDirection&& o) : self(o.self) { memset(&o, 0, sizeof(MessageDirection)); }
+	MessageDirection(LDKMessageDirection&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKMessageDirection)); }
+	operator LDKMessageDirection() && { LDKMessageDirection res = self; memset(&self, 0, sizeof(LDKMessageDirection)); return res; }
+	MessageDirection& operator=(MessageDirection&& o) { self = o.self; memset(&o, 0, sizeof(MessageDirection)); return *this; }
+	LDKMessageDirection* operator &() { return &self; }
+	LDKMessageDirection* operator ->() { return &self; }
+	const LDKMessageDirection* operator &() const { return &self; }
+	const LDKMessageDirection* operator ->() const { return &self; }
+};
+class NoiseError {
+private:
+	LDKNoiseError self;
+public:
+	NoiseError(const NoiseError&) = delete;
+	NoiseError(NoiseError&& o) : self(o.self) { memset(&o, 0, sizeof(NoiseError)); }
+	NoiseError(LDKNoiseError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKNoiseError)); }
+	operator LDKNoiseError() && { LDKNoiseError res = self; memset(&self, 0, sizeof(LDKNoiseError)); return res; }
+	NoiseError& operator=(NoiseError&& o) { self = o.self; memset(&o, 0, sizeof(NoiseError)); return *this; }
+	LDKNoiseError* operator &() { return &self; }
+	LDKNoiseError* operator ->() { return &self; }
+	const LDKNoiseError* operator &() const { return &self; }
+	const LDKNoiseError* operator ->() const { return &self; }
+};
+class PaymentDirection {
+private:
+	LDKPaymentDirection self;
+public:
+	PaymentDirection(const PaymentDirection&) = delete;
+	PaymentDirection(PaymentDirection&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentDirection)); }
+	PaymentDirection(LDKPaymentDirection&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentDirection)); }
+	operator LDKPaymentDirection() && { LDKPaymentDirection res = self; memset(&self, 0, sizeof(LDKPaymentDirection)); return res; }
+	PaymentDirection& operator=(PaymentDirection&& o) { self = o.self; memset(&o, 0, sizeof(PaymentDirection)); return *this; }
+	LDKPaymentDirection* operator &() { return &self; }
+	LDKPaymentDirection* operator ->() { return &self; }
+	const LDKPaymentDirection* operator &() const { return &self; }
+	const LDKPaymentDirection* operator ->() const { return &self; }
+};
+class PaymentOutcomeStatus {
+private:
+	LDKPaymentOutcomeStatus self;
+public:
+	PaymentOutcomeStatus(const PaymentOutcomeStatus&) = delete;
+	PaymentOutcomeStatus(PaymentOutcomeStatus&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentOutcomeStatus)); }
+	PaymentOutcomeStatus(LDKPaymentOutcomeStatus&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentOutcomeStatus)); }
+	operator LDKPaymentOutcomeStatus() && { LDKPaymentOutcomeStatus res = self; memset(&self, 0, sizeof(LDKPaymentOutcomeStatus)); return res; }
+	PaymentOutcomeStatus& operator=(PaymentOutcomeStatus&& o) { self = o.self; memset(&o, 0, sizeof(PaymentOutcomeStatus)); return *this; }
+	LDKPaymentOutcomeStatus* operator &() { return &self; }
+	LDKPaymentOutcomeStatus* operator ->() { return &self; }
+	const LDKPaymentOutcomeStatus* operator &() const { return &self; }
+	const LDKPaymentOutcomeStatus* operator ->() const { return &self; }
+};
+class PaymentPathStatus {
+private:
+	LDKPaymentPathStatus self;
+public:
+	PaymentPathStatus(const PaymentPathStatus&) = delete;
+	PaymentPathStatus(PaymentPathStatus&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentPathStatus)); }
+	PaymentPathStatus(LDKPaymentPathStatus&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentPathStatus)); }
+	operator LDKPaymentPathStatus() && { LDKPaymentPathStatus res = self; memset(&self, 0, sizeof(LDKPaymentPathStatus)); return res; }
+	PaymentPathStatus& operator=(PaymentPathStatus&& o) { self = o.self; memset(&o, 0, sizeof(PaymentPathStatus)); return *this; }
+	LDKPaymentPathStatus* operator &() { return &self; }
+	LDKPaymentPathStatus* operator ->() { return &self; }
+	const LDKPaymentPathStatus* operator &() const { return &self; }
+	const LDKPaymentPathStatus* operator ->() const { return &self; }
+};
+class PaymentStatus {
+private:
+	LDKPaymentStatus self;
+public:
+	PaymentStatus(const PaymentStatus&) = delete;
+	PaymentStatus(PaymentStatus&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentStatus)); }
+	PaymentStatus(LDKPaymentStatus&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentStatus)); }
+	operator LDKPaymentStatus() && { LDKPaymentStatus res = self; memset(&self, 0, sizeof(LDKPaymentStatus)); return res; }
+	PaymentStatus& operator=(PaymentStatus&& o) { self = o.self; memset(&o, 0, sizeof(PaymentStatus)); return *this; }
+	LDKPaymentStatus* operator &() { return &self; }
+	LDKPaymentStatus* operator ->() { return &self; }
+	const LDKPaymentStatus* operator &() const { return &self; }
+	const LDKPaymentStatus* operator ->() const { return &self; }
+};
+class PsbtFundingError {
+private:
+	LDKPsbtFundingError self;
+public:
+	PsbtFundingError(const PsbtFundingError&) = delete;
+	PsbtFundingError(PsbtFundingError&& o) : self(o.self) { memset(&o, 0, sizeof(PsbtFundingError)); }
+	PsbtFundingError(LDKPsbtFundingError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPsbtFundingError)); }
+	operator LDKPsbtFundingError() && { LDKPsbtFundingError res = self; memset(&self, 0, sizeof(LDKPsbtFundingError)); return res; }
+	PsbtFundingError& operator=(PsbtFundingError&& o) { self = o.self; memset(&o, 0, sizeof(PsbtFundingError)); return *this; }
+	LDKPsbtFundingError* operator &() { return &self; }
+	LDKPsbtFundingError* operator ->() { return &self; }
+	const LDKPsbtFundingError* operator &() const { return &self; }
+	const LDKPsbtFundingError* operator ->() const { return &self; }
+};
+class RestoreError {
+private:
+	LDKRestoreError self;
+public:
+	RestoreError(const RestoreError&) = delete;
+	RestoreError(RestoreError&& o) : self(o.self) { memset(&o, 0, sizeof(RestoreError)); }
+	RestoreError(LDKRestoreError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKRestoreError)); }
+	operator LDKRestoreError() && { LDKRestoreError res = self; memset(&self, 0, sizeof(LDKRestoreError)); return res; }
+	RestoreError& operator=(RestoreError&& o) { self = o.self; memset(&o, 0, sizeof(RestoreError)); return *this; }
+	LDKRestoreError* operator &() { return &self; }
+	LDKRestoreError* operator ->() { return &self; }
+	const LDKRestoreError* operator &() const { return &self; }
+	const LDKRestoreError* operator ->() const { return &self; }
+};
+class SeedError {
+private:
+	LDKSeedError self;
+public:
+	SeedError(const SeedError&) = delete;
+	SeedError(SeedError&& o) : self(o.self) { memset(&o, 0, sizeof(SeedError)); }
+	SeedError(LDKSeedError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKSeedError)); }
+	operator LDKSeedError() && { LDKSeedError res = self; memset(&self, 0, sizeof(LDKSeedError)); return res; }
+	SeedError& operator=(SeedError&& o) { self = o.self; memset(&o, 0, sizeof(SeedError)); return *this; }
+	LDKSeedError* operator &() { return &self; }
+	LDKSeedError* operator ->() { return &self; }
+	const LDKSeedError* operator &() const { return &self; }
+	const LDKSeedError* operator ->() const { return &self; }
+};
+class SignerPolicyRule {
+private:
+	LDKSignerPolicyRule self;
+public:
+	SignerPolicyRule(const SignerPolicyRule&) = delete;
+	SignerPolicyRule(SignerPolicyRule&& o) : self(o.self) { memset(&o, 0, sizeof(SignerPolicyRule)); }
+	SignerPolicyRule(LDKSignerPolicyRule&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKSignerPolicyRule)); }
+	operator LDKSignerPolicyRule() && { LDKSignerPolicyRule res = self; memset(&self, 0, sizeof(LDKSignerPolicyRule)); return res; }
+	SignerPolicyRule& operator=(SignerPolicyRule&& o) { self = o.self; memset(&o, 0, sizeof(SignerPolicyRule)); return *this; }
+	LDKSignerPolicyRule* operator &() { return &self; }
+	LDKSignerPolicyRule* operator ->() { return &self; }
+	const LDKSignerPolicyRule* operator &() const { return &self; }
+	const LDKSignerPolicyRule* operator ->() const { return &self; }
+};
+class WalletError {
+private:
+	LDKWalletError self;
+public:
+	WalletError(const WalletError&) = delete;
+	WalletError(WalletError&& o) : self(o.self) { memset(&o, 0, sizeof(WalletError)); }
+	WalletError(LDKWalletError&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKWalletError)); }
+	operator LDKWalletError() && { LDKWalletError res = self; memset(&self, 0, sizeof(LDKWalletError)); return res; }
+	WalletError& operator=(WalletError&& o) { self = o.self; memset(&o, 0, sizeof(WalletError)); return *this; }
+	LDKWalletError* operator &() { return &self; }
+	LDKWalletError* operator ->() { return &self; }
+	const LDKWalletError* operator &() const { return &self; }
+	const LDKWalletError* operator ->() const { return &self; }
+};
+class Psbt {
+private:
+	LDKPsbt self;
+public:
+	Psbt(const Psbt&) = delete;
+	Psbt(Psbt&& o) : self(o.self) { memset(&o, 0, sizeof(Psbt)); }
+	Psbt(LDKPsbt&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPsbt)); }
+	operator LDKPsbt() && { LDKPsbt res = self; memset(&self, 0, sizeof(LDKPsbt)); return res; }
+	~Psbt() { Psbt_free(self); }
+	Psbt& operator=(Psbt&& o) { Psbt_free(self); self = o.self; memset(&o, 0, sizeof(Psbt)); return *this; }
+	LDKPsbt* operator &() { return &self; }
+	LDKPsbt* operator ->() { return &self; }
+	const LDKPsbt* operator &() const { return &self; }
+	const LDKPsbt* operator ->() const { return &self; }
+};
+class ChainReplay {
+private:
+	LDKChainReplay self;
+public:
+	ChainReplay(const ChainReplay&) = delete;
+	ChainReplay(ChainReplay&& o) : self(o.self) { memset(&o, 0, sizeof(ChainReplay)); }
+	ChainReplay(LDKChainReplay&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKChainReplay)); }
+	operator LDKChainReplay() && { LDKChainReplay res = self; memset(&self, 0, sizeof(LDKChainReplay)); return res; }
+	~ChainReplay() { ChainReplay_free(self); }
+	ChainReplay& operator=(ChainReplay&& o) { ChainReplay_free(self); self = o.self; memset(&o, 0, sizeof(ChainReplay)); return *this; }
+	LDKChainReplay* operator &() { return &self; }
+	LDKChainReplay* operator ->() { return &self; }
+	const LDKChainReplay* operator &() const { return &self; }
+	const LDKChainReplay* operator ->() const { return &self; }
+	/**
+	 *  Brings `listener` from `from` to the current chain tip.
+	 * 
+	 *  If `from` is no longer in the best chain, the blocks which were reorganized out must first
+	 *  be disconnected (in descending height order) down to the fork point. Every block after the
+	 *  fork point must then be connected, in ascending height order, up to the current tip.
+	 * 
+	 *  `listener` is only valid for the duration of this call.
+	 * 
+	 *  Return false if the required blocks could not be fetched, which aborts the restore.
+	 */
+	inline bool replay(const struct LDKBestBlock *NONNULL_PTR from, const struct LDKListen *NONNULL_PTR listener);
+};
+class RestoredMonitor {
+private:
+	LDKRestoredMonitor self;
+public:
+	RestoredMonitor(const RestoredMonitor&) = delete;
+	RestoredMonitor(RestoredMonitor&& o) : self(o.self) { memset(&o, 0, sizeof(RestoredMonitor)); }
+	RestoredMonitor(LDKRestoredMonitor&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKRestoredMonitor)); }
+	operator LDKRestoredMonitor() && { LDKRestoredMonitor res = self; memset(&self, 0, sizeof(LDKRestoredMonitor)); return res; }
+	~RestoredMonitor() { RestoredMonitor_free(self); }
+	RestoredMonitor& operator=(RestoredMonitor&& o) { RestoredMonitor_free(self); self = o.self; memset(&o, 0, sizeof(RestoredMonitor)); return *this; }
+	LDKRestoredMonitor* operator &() { return &self; }
+	LDKRestoredMonitor* operator ->() { return &self; }
+	const LDKRestoredMonitor* operator &() const { return &self; }
+	const LDKRestoredMonitor* operator ->() const { return &self; }
+};
+class CVec_RestoredMonitorZ {
+private:
+	LDKCVec_RestoredMonitorZ self;
+public:
+	CVec_RestoredMonitorZ(const CVec_RestoredMonitorZ&) = delete;
+	CVec_RestoredMonitorZ(CVec_RestoredMonitorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CVec_RestoredMonitorZ)); }
+	CVec_RestoredMonitorZ(LDKCVec_RestoredMonitorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCVec_RestoredMonitorZ)); }
+	operator LDKCVec_RestoredMonitorZ() && { LDKCVec_RestoredMonitorZ res = self; memset(&self, 0, sizeof(LDKCVec_RestoredMonitorZ)); return res; }
+	~CVec_RestoredMonitorZ() { CVec_RestoredMonitorZ_free(self); }
+	CVec_RestoredMonitorZ& operator=(CVec_RestoredMonitorZ&& o) { CVec_RestoredMonitorZ_free(self); self = o.self; memset(&o, 0, sizeof(CVec_RestoredMonitorZ)); return *this; }
+	LDKCVec_RestoredMonitorZ* operator &() { return &self; }
+	LDKCVec_RestoredMonitorZ* operator ->() { return &self; }
+	const LDKCVec_RestoredMonitorZ* operator &() const { return &self; }
+	const LDKCVec_RestoredMonitorZ* operator ->() const { return &self; }
+};
+class RestoredNode {
+private:
+	LDKRestoredNode self;
+public:
+	RestoredNode(const RestoredNode&) = delete;
+	RestoredNode(RestoredNode&& o) : self(o.self) { memset(&o, 0, sizeof(RestoredNode)); }
+	RestoredNode(LDKRestoredNode&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKRestoredNode)); }
+	operator LDKRestoredNode() && { LDKRestoredNode res = self; memset(&self, 0, sizeof(LDKRestoredNode)); return res; }
+	~RestoredNode() { RestoredNode_free(self); }
+	RestoredNode& operator=(RestoredNode&& o) { RestoredNode_free(self); self = o.self; memset(&o, 0, sizeof(RestoredNode)); return *this; }
+	LDKRestoredNode* operator &() { return &self; }
+	LDKRestoredNode* operator ->() { return &self; }
+	const LDKRestoredNode* operator &() const { return &self; }
+	const LDKRestoredNode* operator ->() const { return &self; }
+};
+class CResult_RestoredNodeRestoreErrorZ {
+private:
+	LDKCResult_RestoredNodeRestoreErrorZ self;
+public:
+	CResult_RestoredNodeRestoreErrorZ(const CResult_RestoredNodeRestoreErrorZ&) = delete;
+	CResult_RestoredNodeRestoreErrorZ(CResult_RestoredNodeRestoreErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_RestoredNodeRestoreErrorZ)); }
+	CResult_RestoredNodeRestoreErrorZ(LDKCResult_RestoredNodeRestoreErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_RestoredNodeRestoreErrorZ)); }
+	operator LDKCResult_RestoredNodeRestoreErrorZ() && { LDKCResult_RestoredNodeRestoreErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_RestoredNodeRestoreErrorZ)); return res; }
+	~CResult_RestoredNodeRestoreErrorZ() { CResult_RestoredNodeRestoreErrorZ_free(self); }
+	CResult_RestoredNodeRestoreErrorZ& operator=(CResult_RestoredNodeRestoreErrorZ&& o) { CResult_RestoredNodeRestoreErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_RestoredNodeRestoreErrorZ)); return *this; }
+	LDKCResult_RestoredNodeRestoreErrorZ* operator &() { return &self; }
+	LDKCResult_RestoredNodeRestoreErrorZ* operator ->() { return &self; }
+	const LDKCResult_RestoredNodeRestoreErrorZ* operator &() const { return &self; }
+	const LDKCResult_RestoredNodeRestoreErrorZ* operator ->() const { return &self; }
+};
+class KVStorePersister {
+private:
+	LDKKVStorePersister self;
+public:
+	KVStorePersister(const KVStorePersister&) = delete;
+	KVStorePersister(KVStorePersister&& o) : self(o.self) { memset(&o, 0, sizeof(KVStorePersister)); }
+	KVStorePersister(LDKKVStorePersister&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKKVStorePersister)); }
+	operator LDKKVStorePersister() && { LDKKVStorePersister res = self; memset(&self, 0, sizeof(LDKKVStorePersister)); return res; }
+	~KVStorePersister() { KVStorePersister_free(self); }
+	KVStorePersister& operator=(KVStorePersister&& o) { KVStorePersister_free(self); self = o.self; memset(&o, 0, sizeof(KVStorePersister)); return *this; }
+	LDKKVStorePersister* operator &() { return &self; }
+	LDKKVStorePersister* operator ->() { return &self; }
+	const LDKKVStorePersister* operator &() const { return &self; }
+	const LDKKVStorePersister* operator ->() const { return &self; }
+};
+class UpdateLogPersister {
+private:
+	LDKUpdateLogPersister self;
+public:
+	UpdateLogPersister(const UpdateLogPersister&) = delete;
+	UpdateLogPersister(UpdateLogPersister&& o) : self(o.self) { memset(&o, 0, sizeof(UpdateLogPersister)); }
+	UpdateLogPersister(LDKUpdateLogPersister&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKUpdateLogPersister)); }
+	operator LDKUpdateLogPersister() && { LDKUpdateLogPersister res = self; memset(&self, 0, sizeof(LDKUpdateLogPersister)); return res; }
+	~UpdateLogPersister() { UpdateLogPersister_free(self); }
+	UpdateLogPersister& operator=(UpdateLogPersister&& o) { UpdateLogPersister_free(self); self = o.self; memset(&o, 0, sizeof(UpdateLogPersister)); return *this; }
+	LDKUpdateLogPersister* operator &() { return &self; }
+	LDKUpdateLogPersister* operator ->() { return &self; }
+	const LDKUpdateLogPersister* operator &() const { return &self; }
+	const LDKUpdateLogPersister* operator ->() const { return &self; }
+};
+#if defined(LDK_SQLITE)
+class SqliteStore {
+private:
+	LDKSqliteStore self;
+public:
+	SqliteStore(const SqliteStore&) = delete;
+	SqliteStore(SqliteStore&& o) : self(o.self) { memset(&o, 0, sizeof(SqliteStore)); }
+	SqliteStore(LDKSqliteStore&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKSqliteStore)); }
+	operator LDKSqliteStore() && { LDKSqliteStore res = self; memset(&self, 0, sizeof(LDKSqliteStore)); return res; }
+	~SqliteStore() { SqliteStore_free(self); }
+	SqliteStore& operator=(SqliteStore&& o) { SqliteStore_free(self); self = o.self; memset(&o, 0, sizeof(SqliteStore)); return *this; }
+	LDKSqliteStore* operator &() { return &self; }
+	LDKSqliteStore* operator ->() { return &self; }
+	const LDKSqliteStore* operator &() const { return &self; }
+	const LDKSqliteStore* operator ->() const { return &self; }
+};
+#endif
+class CResult_CVec_u8ZIOErrorZ {
+private:
+	LDKCResult_CVec_u8ZIOErrorZ self;
+public:
+	CResult_CVec_u8ZIOErrorZ(const CResult_CVec_u8ZIOErrorZ&) = delete;
+	CResult_CVec_u8ZIOErrorZ(CResult_CVec_u8ZIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_CVec_u8ZIOErrorZ)); }
+	CResult_CVec_u8ZIOErrorZ(LDKCResult_CVec_u8ZIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_CVec_u8ZIOErrorZ)); }
+	operator LDKCResult_CVec_u8ZIOErrorZ() && { LDKCResult_CVec_u8ZIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_CVec_u8ZIOErrorZ)); return res; }
+	~CResult_CVec_u8ZIOErrorZ() { CResult_CVec_u8ZIOErrorZ_free(self); }
+	CResult_CVec_u8ZIOErrorZ& operator=(CResult_CVec_u8ZIOErrorZ&& o) { CResult_CVec_u8ZIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_CVec_u8ZIOErrorZ)); return *this; }
+	LDKCResult_CVec_u8ZIOErrorZ* operator &() { return &self; }
+	LDKCResult_CVec_u8ZIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_CVec_u8ZIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_CVec_u8ZIOErrorZ* operator ->() const { return &self; }
+};
+class CVec_StrZ {
+private:
+	LDKCVec_StrZ self;
+public:
+	CVec_StrZ(const CVec_StrZ&) = delete;
+	CVec_StrZ(CVec_StrZ&& o) : self(o.self) { memset(&o, 0, sizeof(CVec_StrZ)); }
+	CVec_StrZ(LDKCVec_StrZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCVec_StrZ)); }
+	operator LDKCVec_StrZ() && { LDKCVec_StrZ res = self; memset(&self, 0, sizeof(LDKCVec_StrZ)); return res; }
+	~CVec_StrZ() { CVec_StrZ_free(self); }
+	CVec_StrZ& operator=(CVec_StrZ&& o) { CVec_StrZ_free(self); self = o.self; memset(&o, 0, sizeof(CVec_StrZ)); return *this; }
+	LDKCVec_StrZ* operator &() { return &self; }
+	LDKCVec_StrZ* operator ->() { return &self; }
+	const LDKCVec_StrZ* operator &() const { return &self; }
+	const LDKCVec_StrZ* operator ->() const { return &self; }
+};
+class CResult_CVec_StrZIOErrorZ {
+private:
+	LDKCResult_CVec_StrZIOErrorZ self;
+public:
+	CResult_CVec_StrZIOErrorZ(const CResult_CVec_StrZIOErrorZ&) = delete;
+	CResult_CVec_StrZIOErrorZ(CResult_CVec_StrZIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_CVec_StrZIOErrorZ)); }
+	CResult_CVec_StrZIOErrorZ(LDKCResult_CVec_StrZIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_CVec_StrZIOErrorZ)); }
+	operator LDKCResult_CVec_StrZIOErrorZ() && { LDKCResult_CVec_StrZIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_CVec_StrZIOErrorZ)); return res; }
+	~CResult_CVec_StrZIOErrorZ() { CResult_CVec_StrZIOErrorZ_free(self); }
+	CResult_CVec_StrZIOErrorZ& operator=(CResult_CVec_StrZIOErrorZ&& o) { CResult_CVec_StrZIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_CVec_StrZIOErrorZ)); return *this; }
+	LDKCResult_CVec_StrZIOErrorZ* operator &() { return &self; }
+	LDKCResult_CVec_StrZIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_CVec_StrZIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_CVec_StrZIOErrorZ* operator ->() const { return &self; }
+};
+class KVStore {
+private:
+	LDKKVStore self;
+public:
+	KVStore(const KVStore&) = delete;
+	KVStore(KVStore&& o) : self(o.self) { memset(&o, 0, sizeof(KVStore)); }
+	KVStore(LDKKVStore&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKKVStore)); }
+	operator LDKKVStore() && { LDKKVStore res = self; memset(&self, 0, sizeof(LDKKVStore)); return res; }
+	~KVStore() { KVStore_free(self); }
+	KVStore& operator=(KVStore&& o) { KVStore_free(self); self = o.self; memset(&o, 0, sizeof(KVStore)); return *this; }
+	LDKKVStore* operator &() { return &self; }
+	LDKKVStore* operator ->() { return &self; }
+	const LDKKVStore* operator &() const { return &self; }
+	const LDKKVStore* operator ->() const { return &self; }
+	/**
+	 *  Returns the value stored under `key` in `key_namespace`.
+	 * 
+	 *  If no value is stored under the key, an `IOError_NotFound` must be returned.
+	 */
+	inline LDK::CResult_CVec_u8ZIOErrorZ read(struct LDKStr key_namespace, struct LDKStr key);
+	/**
+	 *  Stores `value` under `key` in `key_namespace`, replacing any existing value.
+	 * 
+	 *  The write must be atomic, i.e. a subsequent (or concurrent) `read` must either return the
+	 *  previous value or the new one in its entirety, even if the process crashes during the
+	 *  write. It must also be durable before returning success.
+	 */
+	inline LDK::CResult_NoneErrorZ write(struct LDKStr key_namespace, struct LDKStr key, struct LDKu8slice value);
+	/**
+	 *  Removes the value stored under `key` in `key_namespace`.
+	 * 
+	 *  Removing a key which does not exist must succeed.
+	 */
+	inline LDK::CResult_NoneErrorZ remove(struct LDKStr key_namespace, struct LDKStr key);
+	/**
+	 *  Lists all keys which currently have a value stored in `key_namespace`, in any order.
+	 */
+	inline LDK::CResult_CVec_StrZIOErrorZ list(struct LDKStr key_namespace);
+};
+class FilesystemStore {
+private:
+	LDKFilesystemStore self;
+public:
+	FilesystemStore(const FilesystemStore&) = delete;
+	FilesystemStore(FilesystemStore&& o) : self(o.self) { memset(&o, 0, sizeof(FilesystemStore)); }
+	FilesystemStore(LDKFilesystemStore&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKFilesystemStore)); }
+	operator LDKFilesystemStore() && { LDKFilesystemStore res = self; memset(&self, 0, sizeof(LDKFilesystemStore)); return res; }
+	~FilesystemStore() { FilesystemStore_free(self); }
+	FilesystemStore& operator=(FilesystemStore&& o) { FilesystemStore_free(self); self = o.self; memset(&o, 0, sizeof(FilesystemStore)); return *this; }
+	LDKFilesystemStore* operator &() { return &self; }
+	LDKFilesystemStore* operator ->() { return &self; }
+	const LDKFilesystemStore* operator &() const { return &self; }
+	const LDKFilesystemStore* operator ->() const { return &self; }
+};
+class EncryptedStore {
+private:
+	LDKEncryptedStore self;
+public:
+	EncryptedStore(const EncryptedStore&) = delete;
+	EncryptedStore(EncryptedStore&& o) : self(o.self) { memset(&o, 0, sizeof(EncryptedStore)); }
+	EncryptedStore(LDKEncryptedStore&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKEncryptedStore)); }
+	operator LDKEncryptedStore() && { LDKEncryptedStore res = self; memset(&self, 0, sizeof(LDKEncryptedStore)); return res; }
+	~EncryptedStore() { EncryptedStore_free(self); }
+	EncryptedStore& operator=(EncryptedStore&& o) { EncryptedStore_free(self); self = o.self; memset(&o, 0, sizeof(EncryptedStore)); return *this; }
+	LDKEncryptedStore* operator &() { return &self; }
+	LDKEncryptedStore* operator ->() { return &self; }
+	const LDKEncryptedStore* operator &() const { return &self; }
+	const LDKEncryptedStore* operator ->() const { return &self; }
+};
+class CResult_KVStorePersisterIOErrorZ {
+private:
+	LDKCResult_KVStorePersisterIOErrorZ self;
+public:
+	CResult_KVStorePersisterIOErrorZ(const CResult_KVStorePersisterIOErrorZ&) = delete;
+	CResult_KVStorePersisterIOErrorZ(CResult_KVStorePersisterIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_KVStorePersisterIOErrorZ)); }
+	CResult_KVStorePersisterIOErrorZ(LDKCResult_KVStorePersisterIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_KVStorePersisterIOErrorZ)); }
+	operator LDKCResult_KVStorePersisterIOErrorZ() && { LDKCResult_KVStorePersisterIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_KVStorePersisterIOErrorZ)); return res; }
+	~CResult_KVStorePersisterIOErrorZ() { CResult_KVStorePersisterIOErrorZ_free(self); }
+	CResult_KVStorePersisterIOErrorZ& operator=(CResult_KVStorePersisterIOErrorZ&& o) { CResult_KVStorePersisterIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_KVStorePersisterIOErrorZ)); return *this; }
+	LDKCResult_KVStorePersisterIOErrorZ* operator &() { return &self; }
+	LDKCResult_KVStorePersisterIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_KVStorePersisterIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_KVStorePersisterIOErrorZ* operator ->() const { return &self; }
+};
+class CResult_UpdateLogPersisterIOErrorZ {
+private:
+	LDKCResult_UpdateLogPersisterIOErrorZ self;
+public:
+	CResult_UpdateLogPersisterIOErrorZ(const CResult_UpdateLogPersisterIOErrorZ&) = delete;
+	CResult_UpdateLogPersisterIOErrorZ(CResult_UpdateLogPersisterIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_UpdateLogPersisterIOErrorZ)); }
+	CResult_UpdateLogPersisterIOErrorZ(LDKCResult_UpdateLogPersisterIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_UpdateLogPersisterIOErrorZ)); }
+	operator LDKCResult_UpdateLogPersisterIOErrorZ() && { LDKCResult_UpdateLogPersisterIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_UpdateLogPersisterIOErrorZ)); return res; }
+	~CResult_UpdateLogPersisterIOErrorZ() { CResult_UpdateLogPersisterIOErrorZ_free(self); }
+	CResult_UpdateLogPersisterIOErrorZ& operator=(CResult_UpdateLogPersisterIOErrorZ&& o) { CResult_UpdateLogPersisterIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_UpdateLogPersisterIOErrorZ)); return *this; }
+	LDKCResult_UpdateLogPersisterIOErrorZ* operator &() { return &self; }
+	LDKCResult_UpdateLogPersisterIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_UpdateLogPersisterIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_UpdateLogPersisterIOErrorZ* operator ->() const { return &self; }
+};
+class MessageTracer {
+private:
+	LDKMessageTracer self;
+public:
+	MessageTracer(const MessageTracer&) = delete;
+	MessageTracer(MessageTracer&& o) : self(o.self) { memset(&o, 0, sizeof(MessageTracer)); }
+	MessageTracer(LDKMessageTracer&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKMessageTracer)); }
+	operator LDKMessageTracer() && { LDKMessageTracer res = self; memset(&self, 0, sizeof(LDKMessageTracer)); return res; }
+	~MessageTracer() { MessageTracer_free(self); }
+	MessageTracer& operator=(MessageTracer&& o) { MessageTracer_free(self); self = o.self; memset(&o, 0, sizeof(MessageTracer)); return *this; }
+	LDKMessageTracer* operator &() { return &self; }
+	LDKMessageTracer* operator ->() { return &self; }
+	const LDKMessageTracer* operator &() const { return &self; }
+	const LDKMessageTracer* operator ->() const { return &self; }
+	/**
+	 *  Called with each message received from or about to be sent to the peer with the given
+	 *  node id. `msg_type` is the BOLT 1 message type and `data` the serialized message without
+	 *  its type, which is only valid for the duration of the call.
+	 * 
+	 *  This is called from within `PeerManager` and `ChannelManager` calls and should return
+	 *  quickly. The message is passed on whether or not tracing succeeds.
+	 */
+	inline void trace(struct LDKPublicKey node_id, enum LDKMessageDirection direction, uint16_t msg_type, struct LDKu8slice data);
+};
+class TracingMessageHandler {
+private:
+	LDKTracingMessageHandler self;
+public:
+	TracingMessageHandler(const TracingMessageHandler&) = delete;
+	TracingMessageHandler(TracingMessageHandler&& o) : self(o.self) { memset(&o, 0, sizeof(TracingMessageHandler)); }
+	TracingMessageHandler(LDKTracingMessageHandler&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKTracingMessageHandler)); }
+	operator LDKTracingMessageHandler() && { LDKTracingMessageHandler res = self; memset(&self, 0, sizeof(LDKTracingMessageHandler)); return res; }
+	~TracingMessageHandler() { TracingMessageHandler_free(self); }
+	TracingMessageHandler& operator=(TracingMessageHandler&& o) { TracingMessageHandler_free(self); self = o.self; memset(&o, 0, sizeof(TracingMessageHandler)); return *this; }
+	LDKTracingMessageHandler* operator &() { return &self; }
+	LDKTracingMessageHandler* operator ->() { return &self; }
+	const LDKTracingMessageHandler* operator &() const { return &self; }
+	const LDKTracingMessageHandler* operator ->() const { return &self; }
+};
+class MessageTraceFile {
+private:
+	LDKMessageTraceFile self;
+public:
+	MessageTraceFile(const MessageTraceFile&) = delete;
+	MessageTraceFile(MessageTraceFile&& o) : self(o.self) { memset(&o, 0, sizeof(MessageTraceFile)); }
+	MessageTraceFile(LDKMessageTraceFile&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKMessageTraceFile)); }
+	operator LDKMessageTraceFile() && { LDKMessageTraceFile res = self; memset(&self, 0, sizeof(LDKMessageTraceFile)); return res; }
+	~MessageTraceFile() { MessageTraceFile_free(self); }
+	MessageTraceFile& operator=(MessageTraceFile&& o) { MessageTraceFile_free(self); self = o.self; memset(&o, 0, sizeof(MessageTraceFile)); return *this; }
+	LDKMessageTraceFile* operator &() { return &self; }
+	LDKMessageTraceFile* operator ->() { return &self; }
+	const LDKMessageTraceFile* operator &() const { return &self; }
+	const LDKMessageTraceFile* operator ->() const { return &self; }
+};
+class CResult_MessageTraceFileIOErrorZ {
+private:
+	LDKCResult_MessageTraceFileIOErrorZ self;
+public:
+	CResult_MessageTraceFileIOErrorZ(const CResult_MessageTraceFileIOErrorZ&) = delete;
+	CResult_MessageTraceFileIOErrorZ(CResult_MessageTraceFileIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_MessageTraceFileIOErrorZ)); }
+	CResult_MessageTraceFileIOErrorZ(LDKCResult_MessageTraceFileIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_MessageTraceFileIOErrorZ)); }
+	operator LDKCResult_MessageTraceFileIOErrorZ() && { LDKCResult_MessageTraceFileIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_MessageTraceFileIOErrorZ)); return res; }
+	~CResult_MessageTraceFileIOErrorZ() { CResult_MessageTraceFileIOErrorZ_free(self); }
+	CResult_MessageTraceFileIOErrorZ& operator=(CResult_MessageTraceFileIOErrorZ&& o) { CResult_MessageTraceFileIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_MessageTraceFileIOErrorZ)); return *this; }
+	LDKCResult_MessageTraceFileIOErrorZ* operator &() { return &self; }
+	LDKCResult_MessageTraceFileIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_MessageTraceFileIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_MessageTraceFileIOErrorZ* operator ->() const { return &self; }
+};
+class PeerChannelEncryptor {
+private:
+	LDKPeerChannelEncryptor self;
+public:
+	PeerChannelEncryptor(const PeerChannelEncryptor&) = delete;
+	PeerChannelEncryptor(PeerChannelEncryptor&& o) : self(o.self) { memset(&o, 0, sizeof(PeerChannelEncryptor)); }
+	PeerChannelEncryptor(LDKPeerChannelEncryptor&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPeerChannelEncryptor)); }
+	operator LDKPeerChannelEncryptor() && { LDKPeerChannelEncryptor res = self; memset(&self, 0, sizeof(LDKPeerChannelEncryptor)); return res; }
+	~PeerChannelEncryptor() { PeerChannelEncryptor_free(self); }
+	PeerChannelEncryptor& operator=(PeerChannelEncryptor&& o) { PeerChannelEncryptor_free(self); self = o.self; memset(&o, 0, sizeof(PeerChannelEncryptor)); return *this; }
+	LDKPeerChannelEncryptor* operator &() { return &self; }
+	LDKPeerChannelEncryptor* operator ->() { return &self; }
+	const LDKPeerChannelEncryptor* operator &() const { return &self; }
+	const LDKPeerChannelEncryptor* operator ->() const { return &self; }
+};
+class CResult_CVec_u8ZNoiseErrorZ {
+private:
+	LDKCResult_CVec_u8ZNoiseErrorZ self;
+public:
+	CResult_CVec_u8ZNoiseErrorZ(const CResult_CVec_u8ZNoiseErrorZ&) = delete;
+	CResult_CVec_u8ZNoiseErrorZ(CResult_CVec_u8ZNoiseErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_CVec_u8ZNoiseErrorZ)); }
+	CResult_CVec_u8ZNoiseErrorZ(LDKCResult_CVec_u8ZNoiseErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_CVec_u8ZNoiseErrorZ)); }
+	operator LDKCResult_CVec_u8ZNoiseErrorZ() && { LDKCResult_CVec_u8ZNoiseErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_CVec_u8ZNoiseErrorZ)); return res; }
+	~CResult_CVec_u8ZNoiseErrorZ() { CResult_CVec_u8ZNoiseErrorZ_free(self); }
+	CResult_CVec_u8ZNoiseErrorZ& operator=(CResult_CVec_u8ZNoiseErrorZ&& o) { CResult_CVec_u8ZNoiseErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_CVec_u8ZNoiseErrorZ)); return *this; }
+	LDKCResult_CVec_u8ZNoiseErrorZ* operator &() { return &self; }
+	LDKCResult_CVec_u8ZNoiseErrorZ* operator ->() { return &self; }
+	const LDKCResult_CVec_u8ZNoiseErrorZ* operator &() const { return &self; }
+	const LDKCResult_CVec_u8ZNoiseErrorZ* operator ->() const { return &self; }
+};
+class CResult_PublicKeyNoiseErrorZ {
+private:
+	LDKCResult_PublicKeyNoiseErrorZ self;
+public:
+	CResult_PublicKeyNoiseErrorZ(const CResult_PublicKeyNoiseErrorZ&) = delete;
+	CResult_PublicKeyNoiseErrorZ(CResult_PublicKeyNoiseErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_PublicKeyNoiseErrorZ)); }
+	CResult_PublicKeyNoiseErrorZ(LDKCResult_PublicKeyNoiseErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_PublicKeyNoiseErrorZ)); }
+	operator LDKCResult_PublicKeyNoiseErrorZ() && { LDKCResult_PublicKeyNoiseErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_PublicKeyNoiseErrorZ)); return res; }
+	~CResult_PublicKeyNoiseErrorZ() { CResult_PublicKeyNoiseErrorZ_free(self); }
+	CResult_PublicKeyNoiseErrorZ& operator=(CResult_PublicKeyNoiseErrorZ&& o) { CResult_PublicKeyNoiseErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_PublicKeyNoiseErrorZ)); return *this; }
+	LDKCResult_PublicKeyNoiseErrorZ* operator &() { return &self; }
+	LDKCResult_PublicKeyNoiseErrorZ* operator ->() { return &self; }
+	const LDKCResult_PublicKeyNoiseErrorZ* operator &() const { return &self; }
+	const LDKCResult_PublicKeyNoiseErrorZ* operator ->() const { return &self; }
+};
+class CResult_u16NoiseErrorZ {
+private:
+	LDKCResult_u16NoiseErrorZ self;
+public:
+	CResult_u16NoiseErrorZ(const CResult_u16NoiseErrorZ&) = delete;
+	CResult_u16NoiseErrorZ(CResult_u16NoiseErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_u16NoiseErrorZ)); }
+	CResult_u16NoiseErrorZ(LDKCResult_u16NoiseErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_u16NoiseErrorZ)); }
+	operator LDKCResult_u16NoiseErrorZ() && { LDKCResult_u16NoiseErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_u16NoiseErrorZ)); return res; }
+	~CResult_u16NoiseErrorZ() { CResult_u16NoiseErrorZ_free(self); }
+	CResult_u16NoiseErrorZ& operator=(CResult_u16NoiseErrorZ&& o) { CResult_u16NoiseErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_u16NoiseErrorZ)); return *this; }
+	LDKCResult_u16NoiseErrorZ* operator &() { return &self; }
+	LDKCResult_u16NoiseErrorZ* operator ->() { return &self; }
+	const LDKCResult_u16NoiseErrorZ* operator &() const { return &self; }
+	const LDKCResult_u16NoiseErrorZ* operator ->() const { return &self; }
+};
+class CResult_NoneCustomMessageErrorZ {
+private:
+	LDKCResult_NoneCustomMessageErrorZ self;
+public:
+	CResult_NoneCustomMessageErrorZ(const CResult_NoneCustomMessageErrorZ&) = delete;
+	CResult_NoneCustomMessageErrorZ(CResult_NoneCustomMessageErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_NoneCustomMessageErrorZ)); }
+	CResult_NoneCustomMessageErrorZ(LDKCResult_NoneCustomMessageErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_NoneCustomMessageErrorZ)); }
+	operator LDKCResult_NoneCustomMessageErrorZ() && { LDKCResult_NoneCustomMessageErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_NoneCustomMessageErrorZ)); return res; }
+	~CResult_NoneCustomMessageErrorZ() { CResult_NoneCustomMessageErrorZ_free(self); }
+	CResult_NoneCustomMessageErrorZ& operator=(CResult_NoneCustomMessageErrorZ&& o) { CResult_NoneCustomMessageErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_NoneCustomMessageErrorZ)); return *this; }
+	LDKCResult_NoneCustomMessageErrorZ* operator &() { return &self; }
+	LDKCResult_NoneCustomMessageErrorZ* operator ->() { return &self; }
+	const LDKCResult_NoneCustomMessageErrorZ* operator &() const { return &self; }
+	const LDKCResult_NoneCustomMessageErrorZ* operator ->() const { return &self; }
+};
+class CustomMessageCallback {
+private:
+	LDKCustomMessageCallback self;
+public:
+	CustomMessageCallback(const CustomMessageCallback&) = delete;
+	CustomMessageCallback(CustomMessageCallback&& o) : self(o.self) { memset(&o, 0, sizeof(CustomMessageCallback)); }
+	CustomMessageCallback(LDKCustomMessageCallback&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCustomMessageCallback)); }
+	operator LDKCustomMessageCallback() && { LDKCustomMessageCallback res = self; memset(&self, 0, sizeof(LDKCustomMessageCallback)); return res; }
+	~CustomMessageCallback() { CustomMessageCallback_free(self); }
+	CustomMessageCallback& operator=(CustomMessageCallback&& o) { CustomMessageCallback_free(self); self = o.self; memset(&o, 0, sizeof(CustomMessageCallback)); return *this; }
+	LDKCustomMessageCallback* operator &() { return &self; }
+	LDKCustomMessageCallback* operator ->() { return &self; }
+	const LDKCustomMessageCallback* operator &() const { return &self; }
+	const LDKCustomMessageCallback* operator ->() const { return &self; }
+	/**
+	 *  Called with each message of the registered type, with `data` holding the message without
+	 *  its type. `data` is only valid for the duration of the call.
+	 * 
+	 *  Returns false if the message was invalid. For even message types this disconnects the peer,
+	 *  for odd message types the message is ignored.
+	 * 
+	 *  This is called while the `PeerManager` is processing data from the peer and may call
+	 *  `CustomMessageRegistry_send_custom`.
+	 */
+	inline bool handle_message(struct LDKPublicKey node_id, uint16_t type_id, struct LDKu8slice data);
+};
+class CustomMessageRegistry {
+private:
+	LDKCustomMessageRegistry self;
+public:
+	CustomMessageRegistry(const CustomMessageRegistry&) = delete;
+	CustomMessageRegistry(CustomMessageRegistry&& o) : self(o.self) { memset(&o, 0, sizeof(CustomMessageRegistry)); }
+	CustomMessageRegistry(LDKCustomMessageRegistry&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCustomMessageRegistry)); }
+	operator LDKCustomMessageRegistry() && { LDKCustomMessageRegistry res = self; memset(&self, 0, sizeof(LDKCustomMessageRegistry)); return res; }
+	~CustomMessageRegistry() { CustomMessageRegistry_free(self); }
+	CustomMessageRegistry& operator=(CustomMessageRegistry&& o) { CustomMessageRegistry_free(self); self = o.self; memset(&o, 0, sizeof(CustomMessageRegistry)); return *this; }
+	LDKCustomMessageRegistry* operator &() { return &self; }
+	LDKCustomMessageRegistry* operator ->() { return &self; }
+	const LDKCustomMessageRegistry* operator &() const { return &self; }
+	const LDKCustomMessageRegistry* operator ->() const { return &self; }
+};
+class EventQueue {
+private:
+	LDKEventQueue self;
+public:
+	EventQueue(const EventQueue&) = delete;
+	EventQueue(EventQueue&& o) : self(o.self) { memset(&o, 0, sizeof(EventQueue)); }
+	EventQueue(LDKEventQueue&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKEventQueue)); }
+	operator LDKEventQueue() && { LDKEventQueue res = self; memset(&self, 0, sizeof(LDKEventQueue)); return res; }
+	~EventQueue() { EventQueue_free(self); }
+	EventQueue& operator=(EventQueue&& o) { EventQueue_free(self); self = o.self; memset(&o, 0, sizeof(EventQueue)); return *this; }
+	LDKEventQueue* operator &() { return &self; }
+	LDKEventQueue* operator ->() { return &self; }
+	const LDKEventQueue* operator &() const { return &self; }
+	const LDKEventQueue* operator ->() const { return &self; }
+};
+class CResult_EventQueueIOErrorZ {
+private:
+	LDKCResult_EventQueueIOErrorZ self;
+public:
+	CResult_EventQueueIOErrorZ(const CResult_EventQueueIOErrorZ&) = delete;
+	CResult_EventQueueIOErrorZ(CResult_EventQueueIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_EventQueueIOErrorZ)); }
+	CResult_EventQueueIOErrorZ(LDKCResult_EventQueueIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_EventQueueIOErrorZ)); }
+	operator LDKCResult_EventQueueIOErrorZ() && { LDKCResult_EventQueueIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_EventQueueIOErrorZ)); return res; }
+	~CResult_EventQueueIOErrorZ() { CResult_EventQueueIOErrorZ_free(self); }
+	CResult_EventQueueIOErrorZ& operator=(CResult_EventQueueIOErrorZ&& o) { CResult_EventQueueIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_EventQueueIOErrorZ)); return *this; }
+	LDKCResult_EventQueueIOErrorZ* operator &() { return &self; }
+	LDKCResult_EventQueueIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_EventQueueIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_EventQueueIOErrorZ* operator ->() const { return &self; }
+};
+class WakeupCallback {
+private:
+	LDKWakeupCallback self;
+public:
+	WakeupCallback(const WakeupCallback&) = delete;
+	WakeupCallback(WakeupCallback&& o) : self(o.self) { memset(&o, 0, sizeof(WakeupCallback)); }
+	WakeupCallback(LDKWakeupCallback&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKWakeupCallback)); }
+	operator LDKWakeupCallback() && { LDKWakeupCallback res = self; memset(&self, 0, sizeof(LDKWakeupCallback)); return res; }
+	~WakeupCallback() { WakeupCallback_free(self); }
+	WakeupCallback& operator=(WakeupCallback&& o) { WakeupCallback_free(self); self = o.self; memset(&o, 0, sizeof(WakeupCallback)); return *this; }
+	LDKWakeupCallback* operator &() { return &self; }
+	LDKWakeupCallback* operator ->() { return &self; }
+	const LDKWakeupCallback* operator &() const { return &self; }
+	const LDKWakeupCallback* operator ->() const { return &self; }
+	/**
+	 *  Called once each time the notifier goes from cleared to signalled.
+	 * 
+	 *  This is called from an LDK-internal thread, or from whichever thread calls
+	 *  `ChannelManagerNotifier_notify`, so it should only schedule work on the host's event loop
+	 *  (eg with `uv_async_send` or `g_main_context_wakeup`) rather than do it directly.
+	 */
+	inline void wake();
+};
+class ChannelManagerNotifier {
+private:
+	LDKChannelManagerNotifier self;
+public:
+	ChannelManagerNotifier(const ChannelManagerNotifier&) = delete;
+	ChannelManagerNotifier(ChannelManagerNotifier&& o) : self(o.self) { memset(&o, 0, sizeof(ChannelManagerNotifier)); }
+	ChannelManagerNotifier(LDKChannelManagerNotifier&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKChannelManagerNotifier)); }
+	operator LDKChannelManagerNotifier() && { LDKChannelManagerNotifier res = self; memset(&self, 0, sizeof(LDKChannelManagerNotifier)); return res; }
+	~ChannelManagerNotifier() { ChannelManagerNotifier_free(self); }
+	ChannelManagerNotifier& operator=(ChannelManagerNotifier&& o) { ChannelManagerNotifier_free(self); self = o.self; memset(&o, 0, sizeof(ChannelManagerNotifier)); return *this; }
+	LDKChannelManagerNotifier* operator &() { return &self; }
+	LDKChannelManagerNotifier* operator ->() { return &self; }
+	const LDKChannelManagerNotifier* operator &() const { return &self; }
+	const LDKChannelManagerNotifier* operator ->() const { return &self; }
+};
+class CResult_TransactionWalletErrorZ {
+private:
+	LDKCResult_TransactionWalletErrorZ self;
+public:
+	CResult_TransactionWalletErrorZ(const CResult_TransactionWalletErrorZ&) = delete;
+	CResult_TransactionWalletErrorZ(CResult_TransactionWalletErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_TransactionWalletErrorZ)); }
+	CResult_TransactionWalletErrorZ(LDKCResult_TransactionWalletErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_TransactionWalletErrorZ)); }
+	operator LDKCResult_TransactionWalletErrorZ() && { LDKCResult_TransactionWalletErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_TransactionWalletErrorZ)); return res; }
+	~CResult_TransactionWalletErrorZ() { CResult_TransactionWalletErrorZ_free(self); }
+	CResult_TransactionWalletErrorZ& operator=(CResult_TransactionWalletErrorZ&& o) { CResult_TransactionWalletErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_TransactionWalletErrorZ)); return *this; }
+	LDKCResult_TransactionWalletErrorZ* operator &() { return &self; }
+	LDKCResult_TransactionWalletErrorZ* operator ->() { return &self; }
+	const LDKCResult_TransactionWalletErrorZ* operator &() const { return &self; }
+	const LDKCResult_TransactionWalletErrorZ* operator ->() const { return &self; }
+};
+class OnChainWallet {
+private:
+	LDKOnChainWallet self;
+public:
+	OnChainWallet(const OnChainWallet&) = delete;
+	OnChainWallet(OnChainWallet&& o) : self(o.self) { memset(&o, 0, sizeof(OnChainWallet)); }
+	OnChainWallet(LDKOnChainWallet&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKOnChainWallet)); }
+	operator LDKOnChainWallet() && { LDKOnChainWallet res = self; memset(&self, 0, sizeof(LDKOnChainWallet)); return res; }
+	~OnChainWallet() { OnChainWallet_free(self); }
+	OnChainWallet& operator=(OnChainWallet&& o) { OnChainWallet_free(self); self = o.self; memset(&o, 0, sizeof(OnChainWallet)); return *this; }
+	LDKOnChainWallet* operator &() { return &self; }
+	LDKOnChainWallet* operator ->() { return &self; }
+	const LDKOnChainWallet* operator &() const { return &self; }
+	const LDKOnChainWallet* operator ->() const { return &self; }
+};
+class CResult_OnChainWalletDecodeErrorZ {
+private:
+	LDKCResult_OnChainWalletDecodeErrorZ self;
+public:
+	CResult_OnChainWalletDecodeErrorZ(const CResult_OnChainWalletDecodeErrorZ&) = delete;
+	CResult_OnChainWalletDecodeErrorZ(CResult_OnChainWalletDecodeErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_OnChainWalletDecodeErrorZ)); }
+	CResult_OnChainWalletDecodeErrorZ(LDKCResult_OnChainWalletDecodeErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_OnChainWalletDecodeErrorZ)); }
+	operator LDKCResult_OnChainWalletDecodeErrorZ() && { LDKCResult_OnChainWalletDecodeErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_OnChainWalletDecodeErrorZ)); return res; }
+	~CResult_OnChainWalletDecodeErrorZ() { CResult_OnChainWalletDecodeErrorZ_free(self); }
+	CResult_OnChainWalletDecodeErrorZ& operator=(CResult_OnChainWalletDecodeErrorZ&& o) { CResult_OnChainWalletDecodeErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_OnChainWalletDecodeErrorZ)); return *this; }
+	LDKCResult_OnChainWalletDecodeErrorZ* operator &() { return &self; }
+	LDKCResult_OnChainWalletDecodeErrorZ* operator ->() { return &self; }
+	const LDKCResult_OnChainWalletDecodeErrorZ* operator &() const { return &self; }
+	const LDKCResult_OnChainWalletDecodeErrorZ* operator ->() const { return &self; }
+};
+class OutputSweeper {
+private:
+	LDKOutputSweeper self;
+public:
+	OutputSweeper(const OutputSweeper&) = delete;
+	OutputSweeper(OutputSweeper&& o) : self(o.self) { memset(&o, 0, sizeof(OutputSweeper)); }
+	OutputSweeper(LDKOutputSweeper&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKOutputSweeper)); }
+	operator LDKOutputSweeper() && { LDKOutputSweeper res = self; memset(&self, 0, sizeof(LDKOutputSweeper)); return res; }
+	~OutputSweeper() { OutputSweeper_free(self); }
+	OutputSweeper& operator=(OutputSweeper&& o) { OutputSweeper_free(self); self = o.self; memset(&o, 0, sizeof(OutputSweeper)); return *this; }
+	LDKOutputSweeper* operator &() { return &self; }
+	LDKOutputSweeper* operator ->() { return &self; }
+	const LDKOutputSweeper* operator &() const { return &self; }
+	const LDKOutputSweeper* operator ->() const { return &self; }
+};
+class CResult_OutputSweeperIOErrorZ {
+private:
+	LDKCResult_OutputSweeperIOErrorZ self;
+public:
+	CResult_OutputSweeperIOErrorZ(const CResult_OutputSweeperIOErrorZ&) = delete;
+	CResult_OutputSweeperIOErrorZ(CResult_OutputSweeperIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_OutputSweeperIOErrorZ)); }
+	CResult_OutputSweeperIOErrorZ(LDKCResult_OutputSweeperIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_OutputSweeperIOErrorZ)); }
+	operator LDKCResult_OutputSweeperIOErrorZ() && { LDKCResult_OutputSweeperIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_OutputSweeperIOErrorZ)); return res; }
+	~CResult_OutputSweeperIOErrorZ() { CResult_OutputSweeperIOErrorZ_free(self); }
+	CResult_OutputSweeperIOErrorZ& operator=(CResult_OutputSweeperIOErrorZ&& o) { CResult_OutputSweeperIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_OutputSweeperIOErrorZ)); return *this; }
+	LDKCResult_OutputSweeperIOErrorZ* operator &() { return &self; }
+	LDKCResult_OutputSweeperIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_OutputSweeperIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_OutputSweeperIOErrorZ* operator ->() const { return &self; }
+};
+class CResult_PsbtPsbtFundingErrorZ {
+private:
+	LDKCResult_PsbtPsbtFundingErrorZ self;
+public:
+	CResult_PsbtPsbtFundingErrorZ(const CResult_PsbtPsbtFundingErrorZ&) = delete;
+	CResult_PsbtPsbtFundingErrorZ(CResult_PsbtPsbtFundingErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_PsbtPsbtFundingErrorZ)); }
+	CResult_PsbtPsbtFundingErrorZ(LDKCResult_PsbtPsbtFundingErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_PsbtPsbtFundingErrorZ)); }
+	operator LDKCResult_PsbtPsbtFundingErrorZ() && { LDKCResult_PsbtPsbtFundingErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_PsbtPsbtFundingErrorZ)); return res; }
+	~CResult_PsbtPsbtFundingErrorZ() { CResult_PsbtPsbtFundingErrorZ_free(self); }
+	CResult_PsbtPsbtFundingErrorZ& operator=(CResult_PsbtPsbtFundingErrorZ&& o) { CResult_PsbtPsbtFundingErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_PsbtPsbtFundingErrorZ)); return *this; }
+	LDKCResult_PsbtPsbtFundingErrorZ* operator &() { return &self; }
+	LDKCResult_PsbtPsbtFundingErrorZ* operator ->() { return &self; }
+	const LDKCResult_PsbtPsbtFundingErrorZ* operator &() const { return &self; }
+	const LDKCResult_PsbtPsbtFundingErrorZ* operator ->() const { return &self; }
+};
+class CResult_TransactionPsbtFundingErrorZ {
+private:
+	LDKCResult_TransactionPsbtFundingErrorZ self;
+public:
+	CResult_TransactionPsbtFundingErrorZ(const CResult_TransactionPsbtFundingErrorZ&) = delete;
+	CResult_TransactionPsbtFundingErrorZ(CResult_TransactionPsbtFundingErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_TransactionPsbtFundingErrorZ)); }
+	CResult_TransactionPsbtFundingErrorZ(LDKCResult_TransactionPsbtFundingErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_TransactionPsbtFundingErrorZ)); }
+	operator LDKCResult_TransactionPsbtFundingErrorZ() && { LDKCResult_TransactionPsbtFundingErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_TransactionPsbtFundingErrorZ)); return res; }
+	~CResult_TransactionPsbtFundingErrorZ() { CResult_TransactionPsbtFundingErrorZ_free(self); }
+	CResult_TransactionPsbtFundingErrorZ& operator=(CResult_TransactionPsbtFundingErrorZ&& o) { CResult_TransactionPsbtFundingErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_TransactionPsbtFundingErrorZ)); return *this; }
+	LDKCResult_TransactionPsbtFundingErrorZ* operator &() { return &self; }
+	LDKCResult_TransactionPsbtFundingErrorZ* operator ->() { return &self; }
+	const LDKCResult_TransactionPsbtFundingErrorZ* operator &() const { return &self; }
+	const LDKCResult_TransactionPsbtFundingErrorZ* operator ->() const { return &self; }
+};
+class PsbtFunding {
+private:
+	LDKPsbtFunding self;
+public:
+	PsbtFunding(const PsbtFunding&) = delete;
+	PsbtFunding(PsbtFunding&& o) : self(o.self) { memset(&o, 0, sizeof(PsbtFunding)); }
+	PsbtFunding(LDKPsbtFunding&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPsbtFunding)); }
+	operator LDKPsbtFunding() && { LDKPsbtFunding res = self; memset(&self, 0, sizeof(LDKPsbtFunding)); return res; }
+	~PsbtFunding() { PsbtFunding_free(self); }
+	PsbtFunding& operator=(PsbtFunding&& o) { PsbtFunding_free(self); self = o.self; memset(&o, 0, sizeof(PsbtFunding)); return *this; }
+	LDKPsbtFunding* operator &() { return &self; }
+	LDKPsbtFunding* operator ->() { return &self; }
+	const LDKPsbtFunding* operator &() const { return &self; }
+	const LDKPsbtFunding* operator ->() const { return &self; }
+};
+class CResult_StrSeedErrorZ {
+private:
+	LDKCResult_StrSeedErrorZ self;
+public:
+	CResult_StrSeedErrorZ(const CResult_StrSeedErrorZ&) = delete;
+	CResult_StrSeedErrorZ(CResult_StrSeedErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_StrSeedErrorZ)); }
+	CResult_StrSeedErrorZ(LDKCResult_StrSeedErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_StrSeedErrorZ)); }
+	operator LDKCResult_StrSeedErrorZ() && { LDKCResult_StrSeedErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_StrSeedErrorZ)); return res; }
+	~CResult_StrSeedErrorZ() { CResult_StrSeedErrorZ_free(self); }
+	CResult_StrSeedErrorZ& operator=(CResult_StrSeedErrorZ&& o) { CResult_StrSeedErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_StrSeedErrorZ)); return *this; }
+	LDKCResult_StrSeedErrorZ* operator &() { return &self; }
+	LDKCResult_StrSeedErrorZ* operator ->() { return &self; }
+	const LDKCResult_StrSeedErrorZ* operator &() const { return &self; }
+	const LDKCResult_StrSeedErrorZ* operator ->() const { return &self; }
+};
+class CResult_NoneSeedErrorZ {
+private:
+	LDKCResult_NoneSeedErrorZ self;
+public:
+	CResult_NoneSeedErrorZ(const CResult_NoneSeedErrorZ&) = delete;
+	CResult_NoneSeedErrorZ(CResult_NoneSeedErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_NoneSeedErrorZ)); }
+	CResult_NoneSeedErrorZ(LDKCResult_NoneSeedErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_NoneSeedErrorZ)); }
+	operator LDKCResult_NoneSeedErrorZ() && { LDKCResult_NoneSeedErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_NoneSeedErrorZ)); return res; }
+	~CResult_NoneSeedErrorZ() { CResult_NoneSeedErrorZ_free(self); }
+	CResult_NoneSeedErrorZ& operator=(CResult_NoneSeedErrorZ&& o) { CResult_NoneSeedErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_NoneSeedErrorZ)); return *this; }
+	LDKCResult_NoneSeedErrorZ* operator &() { return &self; }
+	LDKCResult_NoneSeedErrorZ* operator ->() { return &self; }
+	const LDKCResult_NoneSeedErrorZ* operator &() const { return &self; }
+	const LDKCResult_NoneSeedErrorZ* operator ->() const { return &self; }
+};
+class CResult_ThirtyTwoBytesSeedErrorZ {
+private:
+	LDKCResult_ThirtyTwoBytesSeedErrorZ self;
+public:
+	CResult_ThirtyTwoBytesSeedErrorZ(const CResult_ThirtyTwoBytesSeedErrorZ&) = delete;
+	CResult_ThirtyTwoBytesSeedErrorZ(CResult_ThirtyTwoBytesSeedErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_ThirtyTwoBytesSeedErrorZ)); }
+	CResult_ThirtyTwoBytesSeedErrorZ(LDKCResult_ThirtyTwoBytesSeedErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_ThirtyTwoBytesSeedErrorZ)); }
+	operator LDKCResult_ThirtyTwoBytesSeedErrorZ() && { LDKCResult_ThirtyTwoBytesSeedErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_ThirtyTwoBytesSeedErrorZ)); return res; }
+	~CResult_ThirtyTwoBytesSeedErrorZ() { CResult_ThirtyTwoBytesSeedErrorZ_free(self); }
+	CResult_ThirtyTwoBytesSeedErrorZ& operator=(CResult_ThirtyTwoBytesSeedErrorZ&& o) { CResult_ThirtyTwoBytesSeedErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_ThirtyTwoBytesSeedErrorZ)); return *this; }
+	LDKCResult_ThirtyTwoBytesSeedErrorZ* operator &() { return &self; }
+	LDKCResult_ThirtyTwoBytesSeedErrorZ* operator ->() { return &self; }
+	const LDKCResult_ThirtyTwoBytesSeedErrorZ* operator &() const { return &self; }
+	const LDKCResult_ThirtyTwoBytesSeedErrorZ* operator ->() const { return &self; }
+};
+class SignerTransport {
+private:
+	LDKSignerTransport self;
+public:
+	SignerTransport(const SignerTransport&) = delete;
+	SignerTransport(SignerTransport&& o) : self(o.self) { memset(&o, 0, sizeof(SignerTransport)); }
+	SignerTransport(LDKSignerTransport&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKSignerTransport)); }
+	operator LDKSignerTransport() && { LDKSignerTransport res = self; memset(&self, 0, sizeof(LDKSignerTransport)); return res; }
+	~SignerTransport() { SignerTransport_free(self); }
+	SignerTransport& operator=(SignerTransport&& o) { SignerTransport_free(self); self = o.self; memset(&o, 0, sizeof(SignerTransport)); return *this; }
+	LDKSignerTransport* operator &() { return &self; }
+	LDKSignerTransport* operator ->() { return &self; }
+	const LDKSignerTransport* operator &() const { return &self; }
+	const LDKSignerTransport* operator ->() const { return &self; }
+	/**
+	 *  Sends `request` to the signer and blocks until its response is received, returning the
+	 *  response, or an error if the signer could not be reached.
+	 * 
+	 *  This may be called from several threads at once, each of which must receive the response
+	 *  to its own request.
+	 * 
+	 *  Requests LDK cannot handle the failure of are sent again after an error, so the transport
+	 *  may reconnect to a restarted signer from within a later call.
+	 */
+	inline LDK::CResult_CVec_u8ZIOErrorZ send_request(struct LDKu8slice request);
+};
+class RemoteSignerServer {
+private:
+	LDKRemoteSignerServer self;
+public:
+	RemoteSignerServer(const RemoteSignerServer&) = delete;
+	RemoteSignerServer(RemoteSignerServer&& o) : self(o.self) { memset(&o, 0, sizeof(RemoteSignerServer)); }
+	RemoteSignerServer(LDKRemoteSignerServer&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKRemoteSignerServer)); }
+	operator LDKRemoteSignerServer() && { LDKRemoteSignerServer res = self; memset(&self, 0, sizeof(LDKRemoteSignerServer)); return res; }
+	~RemoteSignerServer() { RemoteSignerServer_free(self); }
+	RemoteSignerServer& operator=(RemoteSignerServer&& o) { RemoteSignerServer_free(self); self = o.self; memset(&o, 0, sizeof(RemoteSignerServer)); return *this; }
+	LDKRemoteSignerServer* operator &() { return &self; }
+	LDKRemoteSignerServer* operator ->() { return &self; }
+	const LDKRemoteSignerServer* operator &() const { return &self; }
+	const LDKRemoteSignerServer* operator ->() const { return &self; }
+};
+class CResult_RemoteSignerServerIOErrorZ {
+private:
+	LDKCResult_RemoteSignerServerIOErrorZ self;
+public:
+	CResult_RemoteSignerServerIOErrorZ(const CResult_RemoteSignerServerIOErrorZ&) = delete;
+	CResult_RemoteSignerServerIOErrorZ(CResult_RemoteSignerServerIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_RemoteSignerServerIOErrorZ)); }
+	CResult_RemoteSignerServerIOErrorZ(LDKCResult_RemoteSignerServerIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_RemoteSignerServerIOErrorZ)); }
+	operator LDKCResult_RemoteSignerServerIOErrorZ() && { LDKCResult_RemoteSignerServerIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_RemoteSignerServerIOErrorZ)); return res; }
+	~CResult_RemoteSignerServerIOErrorZ() { CResult_RemoteSignerServerIOErrorZ_free(self); }
+	CResult_RemoteSignerServerIOErrorZ& operator=(CResult_RemoteSignerServerIOErrorZ&& o) { CResult_RemoteSignerServerIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_RemoteSignerServerIOErrorZ)); return *this; }
+	LDKCResult_RemoteSignerServerIOErrorZ* operator &() { return &self; }
+	LDKCResult_RemoteSignerServerIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_RemoteSignerServerIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_RemoteSignerServerIOErrorZ* operator ->() const { return &self; }
+};
+class SignerPolicy {
+private:
+	LDKSignerPolicy self;
+public:
+	SignerPolicy(const SignerPolicy&) = delete;
+	SignerPolicy(SignerPolicy&& o) : self(o.self) { memset(&o, 0, sizeof(SignerPolicy)); }
+	SignerPolicy(LDKSignerPolicy&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKSignerPolicy)); }
+	operator LDKSignerPolicy() && { LDKSignerPolicy res = self; memset(&self, 0, sizeof(LDKSignerPolicy)); return res; }
+	~SignerPolicy() { SignerPolicy_free(self); }
+	SignerPolicy& operator=(SignerPolicy&& o) { SignerPolicy_free(self); self = o.self; memset(&o, 0, sizeof(SignerPolicy)); return *this; }
+	LDKSignerPolicy* operator &() { return &self; }
+	LDKSignerPolicy* operator ->() { return &self; }
+	const LDKSignerPolicy* operator &() const { return &self; }
+	const LDKSignerPolicy* operator ->() const { return &self; }
+};
+class InvoiceSigner {
+private:
+	LDKInvoiceSigner self;
+public:
+	InvoiceSigner(const InvoiceSigner&) = delete;
+	InvoiceSigner(InvoiceSigner&& o) : self(o.self) { memset(&o, 0, sizeof(InvoiceSigner)); }
+	InvoiceSigner(LDKInvoiceSigner&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKInvoiceSigner)); }
+	operator LDKInvoiceSigner() && { LDKInvoiceSigner res = self; memset(&self, 0, sizeof(LDKInvoiceSigner)); return res; }
+	~InvoiceSigner() { InvoiceSigner_free(self); }
+	InvoiceSigner& operator=(InvoiceSigner&& o) { InvoiceSigner_free(self); self = o.self; memset(&o, 0, sizeof(InvoiceSigner)); return *this; }
+	LDKInvoiceSigner* operator &() { return &self; }
+	LDKInvoiceSigner* operator ->() { return &self; }
+	const LDKInvoiceSigner* operator &() const { return &self; }
+	const LDKInvoiceSigner* operator ->() const { return &self; }
+	/**
+	 *  Signs the SHA-256 hash of the given invoice preimage, ie the invoice's human-readable part
+	 *  followed by its data, with the node's secret key, returning a recoverable ECDSA signature,
+	 *  or an error if the invoice should not be signed. This is the same as
+	 *  `KeysInterface::sign_invoice`.
+	 */
+	inline LDK::CResult_RecoverableSignatureNoneZ sign_invoice(struct LDKCVec_u8Z invoice_preimage);
+};
+class InvoiceBuilder {
+private:
+	LDKInvoiceBuilder self;
+public:
+	InvoiceBuilder(const InvoiceBuilder&) = delete;
+	InvoiceBuilder(InvoiceBuilder&& o) : self(o.self) { memset(&o, 0, sizeof(InvoiceBuilder)); }
+	InvoiceBuilder(LDKInvoiceBuilder&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKInvoiceBuilder)); }
+	operator LDKInvoiceBuilder() && { LDKInvoiceBuilder res = self; memset(&self, 0, sizeof(LDKInvoiceBuilder)); return res; }
+	~InvoiceBuilder() { InvoiceBuilder_free(self); }
+	InvoiceBuilder& operator=(InvoiceBuilder&& o) { InvoiceBuilder_free(self); self = o.self; memset(&o, 0, sizeof(InvoiceBuilder)); return *this; }
+	LDKInvoiceBuilder* operator &() { return &self; }
+	LDKInvoiceBuilder* operator ->() { return &self; }
+	const LDKInvoiceBuilder* operator &() const { return &self; }
+	const LDKInvoiceBuilder* operator ->() const { return &self; }
+};
+class CResult_InvoiceBuilderHodlInvoiceErrorZ {
+private:
+	LDKCResult_InvoiceBuilderHodlInvoiceErrorZ self;
+public:
+	CResult_InvoiceBuilderHodlInvoiceErrorZ(const CResult_InvoiceBuilderHodlInvoiceErrorZ&) = delete;
+	CResult_InvoiceBuilderHodlInvoiceErrorZ(CResult_InvoiceBuilderHodlInvoiceErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_InvoiceBuilderHodlInvoiceErrorZ)); }
+	CResult_InvoiceBuilderHodlInvoiceErrorZ(LDKCResult_InvoiceBuilderHodlInvoiceErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_InvoiceBuilderHodlInvoiceErrorZ)); }
+	operator LDKCResult_InvoiceBuilderHodlInvoiceErrorZ() && { LDKCResult_InvoiceBuilderHodlInvoiceErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_InvoiceBuilderHodlInvoiceErrorZ)); return res; }
+	~CResult_InvoiceBuilderHodlInvoiceErrorZ() { CResult_InvoiceBuilderHodlInvoiceErrorZ_free(self); }
+	CResult_InvoiceBuilderHodlInvoiceErrorZ& operator=(CResult_InvoiceBuilderHodlInvoiceErrorZ&& o) { CResult_InvoiceBuilderHodlInvoiceErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_InvoiceBuilderHodlInvoiceErrorZ)); return *this; }
+	LDKCResult_InvoiceBuilderHodlInvoiceErrorZ* operator &() { return &self; }
+	LDKCResult_InvoiceBuilderHodlInvoiceErrorZ* operator ->() { return &self; }
+	const LDKCResult_InvoiceBuilderHodlInvoiceErrorZ* operator &() const { return &self; }
+	const LDKCResult_InvoiceBuilderHodlInvoiceErrorZ* operator ->() const { return &self; }
+};
+class CResult_NoneHodlInvoiceErrorZ {
+private:
+	LDKCResult_NoneHodlInvoiceErrorZ self;
+public:
+	CResult_NoneHodlInvoiceErrorZ(const CResult_NoneHodlInvoiceErrorZ&) = delete;
+	CResult_NoneHodlInvoiceErrorZ(CResult_NoneHodlInvoiceErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_NoneHodlInvoiceErrorZ)); }
+	CResult_NoneHodlInvoiceErrorZ(LDKCResult_NoneHodlInvoiceErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_NoneHodlInvoiceErrorZ)); }
+	operator LDKCResult_NoneHodlInvoiceErrorZ() && { LDKCResult_NoneHodlInvoiceErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_NoneHodlInvoiceErrorZ)); return res; }
+	~CResult_NoneHodlInvoiceErrorZ() { CResult_NoneHodlInvoiceErrorZ_free(self); }
+	CResult_NoneHodlInvoiceErrorZ& operator=(CResult_NoneHodlInvoiceErrorZ&& o) { CResult_NoneHodlInvoiceErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_NoneHodlInvoiceErrorZ)); return *this; }
+	LDKCResult_NoneHodlInvoiceErrorZ* operator &() { return &self; }
+	LDKCResult_NoneHodlInvoiceErrorZ* operator ->() { return &self; }
+	const LDKCResult_NoneHodlInvoiceErrorZ* operator &() const { return &self; }
+	const LDKCResult_NoneHodlInvoiceErrorZ* operator ->() const { return &self; }
+};
+class HodlInvoiceManager {
+private:
+	LDKHodlInvoiceManager self;
+public:
+	HodlInvoiceManager(const HodlInvoiceManager&) = delete;
+	HodlInvoiceManager(HodlInvoiceManager&& o) : self(o.self) { memset(&o, 0, sizeof(HodlInvoiceManager)); }
+	HodlInvoiceManager(LDKHodlInvoiceManager&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKHodlInvoiceManager)); }
+	operator LDKHodlInvoiceManager() && { LDKHodlInvoiceManager res = self; memset(&self, 0, sizeof(LDKHodlInvoiceManager)); return res; }
+	~HodlInvoiceManager() { HodlInvoiceManager_free(self); }
+	HodlInvoiceManager& operator=(HodlInvoiceManager&& o) { HodlInvoiceManager_free(self); self = o.self; memset(&o, 0, sizeof(HodlInvoiceManager)); return *this; }
+	LDKHodlInvoiceManager* operator &() { return &self; }
+	LDKHodlInvoiceManager* operator ->() { return &self; }
+	const LDKHodlInvoiceManager* operator &() const { return &self; }
+	const LDKHodlInvoiceManager* operator ->() const { return &self; }
+};
+class CResult_HodlInvoiceManagerIOErrorZ {
+private:
+	LDKCResult_HodlInvoiceManagerIOErrorZ self;
+public:
+	CResult_HodlInvoiceManagerIOErrorZ(const CResult_HodlInvoiceManagerIOErrorZ&) = delete;
+	CResult_HodlInvoiceManagerIOErrorZ(CResult_HodlInvoiceManagerIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_HodlInvoiceManagerIOErrorZ)); }
+	CResult_HodlInvoiceManagerIOErrorZ(LDKCResult_HodlInvoiceManagerIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_HodlInvoiceManagerIOErrorZ)); }
+	operator LDKCResult_HodlInvoiceManagerIOErrorZ() && { LDKCResult_HodlInvoiceManagerIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_HodlInvoiceManagerIOErrorZ)); return res; }
+	~CResult_HodlInvoiceManagerIOErrorZ() { CResult_HodlInvoiceManagerIOErrorZ_free(self); }
+	CResult_HodlInvoiceManagerIOErrorZ& operator=(CResult_HodlInvoiceManagerIOErrorZ&& o) { CResult_HodlInvoiceManagerIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_HodlInvoiceManagerIOErrorZ)); return *this; }
+	LDKCResult_HodlInvoiceManagerIOErrorZ* operator &() { return &self; }
+	LDKCResult_HodlInvoiceManagerIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_HodlInvoiceManagerIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_HodlInvoiceManagerIOErrorZ* operator ->() const { return &self; }
+};
+class PaymentDetails {
+private:
+	LDKPaymentDetails self;
+public:
+	PaymentDetails(const PaymentDetails&) = delete;
+	PaymentDetails(PaymentDetails&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentDetails)); }
+	PaymentDetails(LDKPaymentDetails&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentDetails)); }
+	operator LDKPaymentDetails() && { LDKPaymentDetails res = self; memset(&self, 0, sizeof(LDKPaymentDetails)); return res; }
+	~PaymentDetails() { PaymentDetails_free(self); }
+	PaymentDetails& operator=(PaymentDetails&& o) { PaymentDetails_free(self); self = o.self; memset(&o, 0, sizeof(PaymentDetails)); return *this; }
+	LDKPaymentDetails* operator &() { return &self; }
+	LDKPaymentDetails* operator ->() { return &self; }
+	const LDKPaymentDetails* operator &() const { return &self; }
+	const LDKPaymentDetails* operator ->() const { return &self; }
+};
+class CVec_PaymentDetailsZ {
+private:
+	LDKCVec_PaymentDetailsZ self;
+public:
+	CVec_PaymentDetailsZ(const CVec_PaymentDetailsZ&) = delete;
+	CVec_PaymentDetailsZ(CVec_PaymentDetailsZ&& o) : self(o.self) { memset(&o, 0, sizeof(CVec_PaymentDetailsZ)); }
+	CVec_PaymentDetailsZ(LDKCVec_PaymentDetailsZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCVec_PaymentDetailsZ)); }
+	operator LDKCVec_PaymentDetailsZ() && { LDKCVec_PaymentDetailsZ res = self; memset(&self, 0, sizeof(LDKCVec_PaymentDetailsZ)); return res; }
+	~CVec_PaymentDetailsZ() { CVec_PaymentDetailsZ_free(self); }
+	CVec_PaymentDetailsZ& operator=(CVec_PaymentDetailsZ&& o) { CVec_PaymentDetailsZ_free(self); self = o.self; memset(&o, 0, sizeof(CVec_PaymentDetailsZ)); return *this; }
+	LDKCVec_PaymentDetailsZ* operator &() { return &self; }
+	LDKCVec_PaymentDetailsZ* operator ->() { return &self; }
+	const LDKCVec_PaymentDetailsZ* operator &() const { return &self; }
+	const LDKCVec_PaymentDetailsZ* operator ->() const { return &self; }
+};
+class COption_PaymentDetailsZ {
+private:
+	LDKCOption_PaymentDetailsZ self;
+public:
+	COption_PaymentDetailsZ(const COption_PaymentDetailsZ&) = delete;
+	COption_PaymentDetailsZ(COption_PaymentDetailsZ&& o) : self(o.self) { memset(&o, 0, sizeof(COption_PaymentDetailsZ)); }
+	COption_PaymentDetailsZ(LDKCOption_PaymentDetailsZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCOption_PaymentDetailsZ)); }
+	operator LDKCOption_PaymentDetailsZ() && { LDKCOption_PaymentDetailsZ res = self; memset(&self, 0, sizeof(LDKCOption_PaymentDetailsZ)); return res; }
+	~COption_PaymentDetailsZ() { COption_PaymentDetailsZ_free(self); }
+	COption_PaymentDetailsZ& operator=(COption_PaymentDetailsZ&& o) { COption_PaymentDetailsZ_free(self); self = o.self; memset(&o, 0, sizeof(COption_PaymentDetailsZ)); return *this; }
+	LDKCOption_PaymentDetailsZ* operator &() { return &self; }
+	LDKCOption_PaymentDetailsZ* operator ->() { return &self; }
+	const LDKCOption_PaymentDetailsZ* operator &() const { return &self; }
+	const LDKCOption_PaymentDetailsZ* operator ->() const { return &self; }
+};
+class PaymentStore {
+private:
+	LDKPaymentStore self;
+public:
+	PaymentStore(const PaymentStore&) = delete;
+	PaymentStore(PaymentStore&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentStore)); }
+	PaymentStore(LDKPaymentStore&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentStore)); }
+	operator LDKPaymentStore() && { LDKPaymentStore res = self; memset(&self, 0, sizeof(LDKPaymentStore)); return res; }
+	~PaymentStore() { PaymentStore_free(self); }
+	PaymentStore& operator=(PaymentStore&& o) { PaymentStore_free(self); self = o.self; memset(&o, 0, sizeof(PaymentStore)); return *this; }
+	LDKPaymentStore* operator &() { return &self; }
+	LDKPaymentStore* operator ->() { return &self; }
+	const LDKPaymentStore* operator &() const { return &self; }
+	const LDKPaymentStore* operator ->() const { return &self; }
+};
+class CResult_PaymentStoreIOErrorZ {
+private:
+	LDKCResult_PaymentStoreIOErrorZ self;
+public:
+	CResult_PaymentStoreIOErrorZ(const CResult_PaymentStoreIOErrorZ&) = delete;
+	CResult_PaymentStoreIOErrorZ(CResult_PaymentStoreIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_PaymentStoreIOErrorZ)); }
+	CResult_PaymentStoreIOErrorZ(LDKCResult_PaymentStoreIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_PaymentStoreIOErrorZ)); }
+	operator LDKCResult_PaymentStoreIOErrorZ() && { LDKCResult_PaymentStoreIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_PaymentStoreIOErrorZ)); return res; }
+	~CResult_PaymentStoreIOErrorZ() { CResult_PaymentStoreIOErrorZ_free(self); }
+	CResult_PaymentStoreIOErrorZ& operator=(CResult_PaymentStoreIOErrorZ&& o) { CResult_PaymentStoreIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_PaymentStoreIOErrorZ)); return *this; }
+	LDKCResult_PaymentStoreIOErrorZ* operator &() { return &self; }
+	LDKCResult_PaymentStoreIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_PaymentStoreIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_PaymentStoreIOErrorZ* operator ->() const { return &self; }
+};
+class RetryingInvoicePayer {
+private:
+	LDKRetryingInvoicePayer self;
+public:
+	RetryingInvoicePayer(const RetryingInvoicePayer&) = delete;
+	RetryingInvoicePayer(RetryingInvoicePayer&& o) : self(o.self) { memset(&o, 0, sizeof(RetryingInvoicePayer)); }
+	RetryingInvoicePayer(LDKRetryingInvoicePayer&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKRetryingInvoicePayer)); }
+	operator LDKRetryingInvoicePayer() && { LDKRetryingInvoicePayer res = self; memset(&self, 0, sizeof(LDKRetryingInvoicePayer)); return res; }
+	~RetryingInvoicePayer() { RetryingInvoicePayer_free(self); }
+	RetryingInvoicePayer& operator=(RetryingInvoicePayer&& o) { RetryingInvoicePayer_free(self); self = o.self; memset(&o, 0, sizeof(RetryingInvoicePayer)); return *this; }
+	LDKRetryingInvoicePayer* operator &() { return &self; }
+	LDKRetryingInvoicePayer* operator ->() { return &self; }
+	const LDKRetryingInvoicePayer* operator &() const { return &self; }
+	const LDKRetryingInvoicePayer* operator ->() const { return &self; }
+};
+class RetryStrategy {
+private:
+	LDKRetryStrategy self;
+public:
+	RetryStrategy(const RetryStrategy&) = delete;
+	RetryStrategy(RetryStrategy&& o) : self(o.self) { memset(&o, 0, sizeof(RetryStrategy)); }
+	RetryStrategy(LDKRetryStrategy&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKRetryStrategy)); }
+	operator LDKRetryStrategy() && { LDKRetryStrategy res = self; memset(&self, 0, sizeof(LDKRetryStrategy)); return res; }
+	~RetryStrategy() { RetryStrategy_free(self); }
+	RetryStrategy& operator=(RetryStrategy&& o) { RetryStrategy_free(self); self = o.self; memset(&o, 0, sizeof(RetryStrategy)); return *this; }
+	LDKRetryStrategy* operator &() { return &self; }
+	LDKRetryStrategy* operator ->() { return &self; }
+	const LDKRetryStrategy* operator &() const { return &self; }
+	const LDKRetryStrategy* operator ->() const { return &self; }
+};
+class PaymentPathAttempt {
+private:
+	LDKPaymentPathAttempt self;
+public:
+	PaymentPathAttempt(const PaymentPathAttempt&) = delete;
+	PaymentPathAttempt(PaymentPathAttempt&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentPathAttempt)); }
+	PaymentPathAttempt(LDKPaymentPathAttempt&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentPathAttempt)); }
+	operator LDKPaymentPathAttempt() && { LDKPaymentPathAttempt res = self; memset(&self, 0, sizeof(LDKPaymentPathAttempt)); return res; }
+	~PaymentPathAttempt() { PaymentPathAttempt_free(self); }
+	PaymentPathAttempt& operator=(PaymentPathAttempt&& o) { PaymentPathAttempt_free(self); self = o.self; memset(&o, 0, sizeof(PaymentPathAttempt)); return *this; }
+	LDKPaymentPathAttempt* operator &() { return &self; }
+	LDKPaymentPathAttempt* operator ->() { return &self; }
+	const LDKPaymentPathAttempt* operator &() const { return &self; }
+	const LDKPaymentPathAttempt* operator ->() const { return &self; }
+};
+class CVec_PaymentPathAttemptZ {
+private:
+	LDKCVec_PaymentPathAttemptZ self;
+public:
+	CVec_PaymentPathAttemptZ(const CVec_PaymentPathAttemptZ&) = delete;
+	CVec_PaymentPathAttemptZ(CVec_PaymentPathAttemptZ&& o) : self(o.self) { memset(&o, 0, sizeof(CVec_PaymentPathAttemptZ)); }
+	CVec_PaymentPathAttemptZ(LDKCVec_PaymentPathAttemptZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCVec_PaymentPathAttemptZ)); }
+	operator LDKCVec_PaymentPathAttemptZ() && { LDKCVec_PaymentPathAttemptZ res = self; memset(&self, 0, sizeof(LDKCVec_PaymentPathAttemptZ)); return res; }
+	~CVec_PaymentPathAttemptZ() { CVec_PaymentPathAttemptZ_free(self); }
+	CVec_PaymentPathAttemptZ& operator=(CVec_PaymentPathAttemptZ&& o) { CVec_PaymentPathAttemptZ_free(self); self = o.self; memset(&o, 0, sizeof(CVec_PaymentPathAttemptZ)); return *this; }
+	LDKCVec_PaymentPathAttemptZ* operator &() { return &self; }
+	LDKCVec_PaymentPathAttemptZ* operator ->() { return &self; }
+	const LDKCVec_PaymentPathAttemptZ* operator &() const { return &self; }
+	const LDKCVec_PaymentPathAttemptZ* operator ->() const { return &self; }
+};
+class PaymentOutcome {
+private:
+	LDKPaymentOutcome self;
+public:
+	PaymentOutcome(const PaymentOutcome&) = delete;
+	PaymentOutcome(PaymentOutcome&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentOutcome)); }
+	PaymentOutcome(LDKPaymentOutcome&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentOutcome)); }
+	operator LDKPaymentOutcome() && { LDKPaymentOutcome res = self; memset(&self, 0, sizeof(LDKPaymentOutcome)); return res; }
+	~PaymentOutcome() { PaymentOutcome_free(self); }
+	PaymentOutcome& operator=(PaymentOutcome&& o) { PaymentOutcome_free(self); self = o.self; memset(&o, 0, sizeof(PaymentOutcome)); return *this; }
+	LDKPaymentOutcome* operator &() { return &self; }
+	LDKPaymentOutcome* operator ->() { return &self; }
+	const LDKPaymentOutcome* operator &() const { return &self; }
+	const LDKPaymentOutcome* operator ->() const { return &self; }
+};
+class PaymentOutcomeHandler {
+private:
+	LDKPaymentOutcomeHandler self;
+public:
+	PaymentOutcomeHandler(const PaymentOutcomeHandler&) = delete;
+	PaymentOutcomeHandler(PaymentOutcomeHandler&& o) : self(o.self) { memset(&o, 0, sizeof(PaymentOutcomeHandler)); }
+	PaymentOutcomeHandler(LDKPaymentOutcomeHandler&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPaymentOutcomeHandler)); }
+	operator LDKPaymentOutcomeHandler() && { LDKPaymentOutcomeHandler res = self; memset(&self, 0, sizeof(LDKPaymentOutcomeHandler)); return res; }
+	~PaymentOutcomeHandler() { PaymentOutcomeHandler_free(self); }
+	PaymentOutcomeHandler& operator=(PaymentOutcomeHandler&& o) { PaymentOutcomeHandler_free(self); self = o.self; memset(&o, 0, sizeof(PaymentOutcomeHandler)); return *this; }
+	LDKPaymentOutcomeHandler* operator &() { return &self; }
+	LDKPaymentOutcomeHandler* operator ->() { return &self; }
+	const LDKPaymentOutcomeHandler* operator &() const { return &self; }
+	const LDKPaymentOutcomeHandler* operator ->() const { return &self; }
+	/**
+	 *  Called once a payment succeeded or failed, before the corresponding `Event::PaymentSent`
+	 *  or `Event::PaymentFailed` is passed to the payer's `EventHandler`.
+	 */
+	inline void handle_outcome(const struct LDKPaymentOutcome *NONNULL_PTR outcome);
+};
+#if defined(LDK_LINUX)
+class CResult_NoneLdkNetErrorZ {
+private:
+	LDKCResult_NoneLdkNetErrorZ self;
+public:
+	CResult_NoneLdkNetErrorZ(const CResult_NoneLdkNetErrorZ&) = delete;
+	CResult_NoneLdkNetErrorZ(CResult_NoneLdkNetErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_NoneLdkNetErrorZ)); }
+	CResult_NoneLdkNetErrorZ(LDKCResult_NoneLdkNetErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_NoneLdkNetErrorZ)); }
+	operator LDKCResult_NoneLdkNetErrorZ() && { LDKCResult_NoneLdkNetErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_NoneLdkNetErrorZ)); return res; }
+	~CResult_NoneLdkNetErrorZ() { CResult_NoneLdkNetErrorZ_free(self); }
+	CResult_NoneLdkNetErrorZ& operator=(CResult_NoneLdkNetErrorZ&& o) { CResult_NoneLdkNetErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_NoneLdkNetErrorZ)); return *this; }
+	LDKCResult_NoneLdkNetErrorZ* operator &() { return &self; }
+	LDKCResult_NoneLdkNetErrorZ* operator ->() { return &self; }
+	const LDKCResult_NoneLdkNetErrorZ* operator &() const { return &self; }
+	const LDKCResult_NoneLdkNetErrorZ* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_LINUX)
+class LdkNet {
+private:
+	LDKLdkNet self;
+public:
+	LdkNet(const LdkNet&) = delete;
+	LdkNet(LdkNet&& o) : self(o.self) { memset(&o, 0, sizeof(LdkNet)); }
+	LdkNet(LDKLdkNet&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKLdkNet)); }
+	operator LDKLdkNet() && { LDKLdkNet res = self; memset(&self, 0, sizeof(LDKLdkNet)); return res; }
+	~LdkNet() { LdkNet_free(self); }
+	LdkNet& operator=(LdkNet&& o) { LdkNet_free(self); self = o.self; memset(&o, 0, sizeof(LdkNet)); return *this; }
+	LDKLdkNet* operator &() { return &self; }
+	LDKLdkNet* operator ->() { return &self; }
+	const LDKLdkNet* operator &() const { return &self; }
+	const LDKLdkNet* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_LINUX)
+class CResult_LdkNetLdkNetErrorZ {
+private:
+	LDKCResult_LdkNetLdkNetErrorZ self;
+public:
+	CResult_LdkNetLdkNetErrorZ(const CResult_LdkNetLdkNetErrorZ&) = delete;
+	CResult_LdkNetLdkNetErrorZ(CResult_LdkNetLdkNetErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_LdkNetLdkNetErrorZ)); }
+	CResult_LdkNetLdkNetErrorZ(LDKCResult_LdkNetLdkNetErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_LdkNetLdkNetErrorZ)); }
+	operator LDKCResult_LdkNetLdkNetErrorZ() && { LDKCResult_LdkNetLdkNetErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_LdkNetLdkNetErrorZ)); return res; }
+	~CResult_LdkNetLdkNetErrorZ() { CResult_LdkNetLdkNetErrorZ_free(self); }
+	CResult_LdkNetLdkNetErrorZ& operator=(CResult_LdkNetLdkNetErrorZ&& o) { CResult_LdkNetLdkNetErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_LdkNetLdkNetErrorZ)); return *this; }
+	LDKCResult_LdkNetLdkNetErrorZ* operator &() { return &self; }
+	LDKCResult_LdkNetLdkNetErrorZ* operator ->() { return &self; }
+	const LDKCResult_LdkNetLdkNetErrorZ* operator &() const { return &self; }
+	const LDKCResult_LdkNetLdkNetErrorZ* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_LINUX)
+class ConnectionPolicy {
+private:
+	LDKConnectionPolicy self;
+public:
+	ConnectionPolicy(const ConnectionPolicy&) = delete;
+	ConnectionPolicy(ConnectionPolicy&& o) : self(o.self) { memset(&o, 0, sizeof(ConnectionPolicy)); }
+	ConnectionPolicy(LDKConnectionPolicy&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKConnectionPolicy)); }
+	operator LDKConnectionPolicy() && { LDKConnectionPolicy res = self; memset(&self, 0, sizeof(LDKConnectionPolicy)); return res; }
+	~ConnectionPolicy() { ConnectionPolicy_free(self); }
+	ConnectionPolicy& operator=(ConnectionPolicy&& o) { ConnectionPolicy_free(self); self = o.self; memset(&o, 0, sizeof(ConnectionPolicy)); return *this; }
+	LDKConnectionPolicy* operator &() { return &self; }
+	LDKConnectionPolicy* operator ->() { return &self; }
+	const LDKConnectionPolicy* operator &() const { return &self; }
+	const LDKConnectionPolicy* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_LINUX)
+class PeerFilter {
+private:
+	LDKPeerFilter self;
+public:
+	PeerFilter(const PeerFilter&) = delete;
+	PeerFilter(PeerFilter&& o) : self(o.self) { memset(&o, 0, sizeof(PeerFilter)); }
+	PeerFilter(LDKPeerFilter&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPeerFilter)); }
+	operator LDKPeerFilter() && { LDKPeerFilter res = self; memset(&self, 0, sizeof(LDKPeerFilter)); return res; }
+	~PeerFilter() { PeerFilter_free(self); }
+	PeerFilter& operator=(PeerFilter&& o) { PeerFilter_free(self); self = o.self; memset(&o, 0, sizeof(PeerFilter)); return *this; }
+	LDKPeerFilter* operator &() { return &self; }
+	LDKPeerFilter* operator ->() { return &self; }
+	const LDKPeerFilter* operator &() const { return &self; }
+	const LDKPeerFilter* operator ->() const { return &self; }
+	/**
+	 *  Returns true if we should stay connected to the peer with the given node id, or false if
+	 *  it should be disconnected.
+	 * 
+	 *  This is called from the socket driver's event loop, which is blocked until it returns.
+	 */
+	inline bool accept_peer(struct LDKPublicKey node_id);
+};
+#endif
+#if defined(LDK_LINUX)
+class Socks5Proxy {
+private:
+	LDKSocks5Proxy self;
+public:
+	Socks5Proxy(const Socks5Proxy&) = delete;
+	Socks5Proxy(Socks5Proxy&& o) : self(o.self) { memset(&o, 0, sizeof(Socks5Proxy)); }
+	Socks5Proxy(LDKSocks5Proxy&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKSocks5Proxy)); }
+	operator LDKSocks5Proxy() && { LDKSocks5Proxy res = self; memset(&self, 0, sizeof(LDKSocks5Proxy)); return res; }
+	~Socks5Proxy() { Socks5Proxy_free(self); }
+	Socks5Proxy& operator=(Socks5Proxy&& o) { Socks5Proxy_free(self); self = o.self; memset(&o, 0, sizeof(Socks5Proxy)); return *this; }
+	LDKSocks5Proxy* operator &() { return &self; }
+	LDKSocks5Proxy* operator ->() { return &self; }
+	const LDKSocks5Proxy* operator &() const { return &self; }
+	const LDKSocks5Proxy* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_LINUX)
+class PeerEvent {
+private:
+	LDKPeerEvent self;
+public:
+	PeerEvent(const PeerEvent&) = delete;
+	PeerEvent(PeerEvent&& o) : self(o.self) { memset(&o, 0, sizeof(PeerEvent)); }
+	PeerEvent(LDKPeerEvent&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPeerEvent)); }
+	operator LDKPeerEvent() && { LDKPeerEvent res = self; memset(&self, 0, sizeof(LDKPeerEvent)); return res; }
+	~PeerEvent() { PeerEvent_free(self); }
+	PeerEvent& operator=(PeerEvent&& o) { PeerEvent_free(self); self = o.self; memset(&o, 0, sizeof(PeerEvent)); return *this; }
+	LDKPeerEvent* operator &() { return &self; }
+	LDKPeerEvent* operator ->() { return &self; }
+	const LDKPeerEvent* operator &() const { return &self; }
+	const LDKPeerEvent* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_LINUX)
+class PeerEventHandler {
+private:
+	LDKPeerEventHandler self;
+public:
+	PeerEventHandler(const PeerEventHandler&) = delete;
+	PeerEventHandler(PeerEventHandler&& o) : self(o.self) { memset(&o, 0, sizeof(PeerEventHandler)); }
+	PeerEventHandler(LDKPeerEventHandler&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPeerEventHandler)); }
+	operator LDKPeerEventHandler() && { LDKPeerEventHandler res = self; memset(&self, 0, sizeof(LDKPeerEventHandler)); return res; }
+	~PeerEventHandler() { PeerEventHandler_free(self); }
+	PeerEventHandler& operator=(PeerEventHandler&& o) { PeerEventHandler_free(self); self = o.self; memset(&o, 0, sizeof(PeerEventHandler)); return *this; }
+	LDKPeerEventHandler* operator &() { return &self; }
+	LDKPeerEventHandler* operator ->() { return &self; }
+	const LDKPeerEventHandler* operator &() const { return &self; }
+	const LDKPeerEventHandler* operator ->() const { return &self; }
+	/**
+	 *  Handles the given [`PeerEvent`].
+	 */
+	inline void handle_peer_event(struct LDKPeerEvent event);
+};
+#endif
+#if defined(LDK_LINUX)
+class PeerReconnector {
+private:
+	LDKPeerReconnector self;
+public:
+	PeerReconnector(const PeerReconnector&) = delete;
+	PeerReconnector(PeerReconnector&& o) : self(o.self) { memset(&o, 0, sizeof(PeerReconnector)); }
+	PeerReconnector(LDKPeerReconnector&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKPeerReconnector)); }
+	operator LDKPeerReconnector() && { LDKPeerReconnector res = self; memset(&self, 0, sizeof(LDKPeerReconnector)); return res; }
+	~PeerReconnector() { PeerReconnector_free(self); }
+	PeerReconnector& operator=(PeerReconnector&& o) { PeerReconnector_free(self); self = o.self; memset(&o, 0, sizeof(PeerReconnector)); return *this; }
+	LDKPeerReconnector* operator &() { return &self; }
+	LDKPeerReconnector* operator ->() { return &self; }
+	const LDKPeerReconnector* operator &() const { return &self; }
+	const LDKPeerReconnector* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_LINUX)
+class CResult_PeerReconnectorIOErrorZ {
+private:
+	LDKCResult_PeerReconnectorIOErrorZ self;
+public:
+	CResult_PeerReconnectorIOErrorZ(const CResult_PeerReconnectorIOErrorZ&) = delete;
+	CResult_PeerReconnectorIOErrorZ(CResult_PeerReconnectorIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_PeerReconnectorIOErrorZ)); }
+	CResult_PeerReconnectorIOErrorZ(LDKCResult_PeerReconnectorIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_PeerReconnectorIOErrorZ)); }
+	operator LDKCResult_PeerReconnectorIOErrorZ() && { LDKCResult_PeerReconnectorIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_PeerReconnectorIOErrorZ)); return res; }
+	~CResult_PeerReconnectorIOErrorZ() { CResult_PeerReconnectorIOErrorZ_free(self); }
+	CResult_PeerReconnectorIOErrorZ& operator=(CResult_PeerReconnectorIOErrorZ&& o) { CResult_PeerReconnectorIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_PeerReconnectorIOErrorZ)); return *this; }
+	LDKCResult_PeerReconnectorIOErrorZ* operator &() { return &self; }
+	LDKCResult_PeerReconnectorIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_PeerReconnectorIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_PeerReconnectorIOErrorZ* operator ->() const { return &self; }
+};
+#endif
+#if defined(LDK_SQLITE)
+class CResult_SqliteStoreIOErrorZ {
+private:
+	LDKCResult_SqliteStoreIOErrorZ self;
+public:
+	CResult_SqliteStoreIOErrorZ(const CResult_SqliteStoreIOErrorZ&) = delete;
+	CResult_SqliteStoreIOErrorZ(CResult_SqliteStoreIOErrorZ&& o) : self(o.self) { memset(&o, 0, sizeof(CResult_SqliteStoreIOErrorZ)); }
+	CResult_SqliteStoreIOErrorZ(LDKCResult_SqliteStoreIOErrorZ&& m_self) : self(m_self) { memset(&m_self, 0, sizeof(LDKCResult_SqliteStoreIOErrorZ)); }
+	operator LDKCResult_SqliteStoreIOErrorZ() && { LDKCResult_SqliteStoreIOErrorZ res = self; memset(&self, 0, sizeof(LDKCResult_SqliteStoreIOErrorZ)); return res; }
+	~CResult_SqliteStoreIOErrorZ() { CResult_SqliteStoreIOErrorZ_free(self); }
+	CResult_SqliteStoreIOErrorZ& operator=(CResult_SqliteStoreIOErrorZ&& o) { CResult_SqliteStoreIOErrorZ_free(self); self = o.self; memset(&o, 0, sizeof(CResult_SqliteStoreIOErrorZ)); return *this; }
+	LDKCResult_SqliteStoreIOErrorZ* operator &() { return &self; }
+	LDKCResult_SqliteStoreIOErrorZ* operator ->() { return &self; }
+	const LDKCResult_SqliteStoreIOErrorZ* operator &() const { return &self; }
+	const LDKCResult_SqliteStoreIOErrorZ* operator ->() const { return &self; }
+};
+#endif
 
 inline LDK::CResult_NoneErrorZ ChannelManagerPersister::persist_manager(const struct LDKChannelManager *NONNULL_PTR channel_manager) {
 	LDK::CResult_NoneErrorZ ret = (self.persist_manager)(self.this_arg, channel_manager);
@@ -6742,4 +8276,56 @@ inline LDK::CResult_NoneChannelMonitorUpdateErrZ Persist::update_persisted_chann
 	LDK::CResult_NoneChannelMonitorUpdateErrZ ret = (self.update_persisted_channel)(self.this_arg, channel_id, update, data, update_id);
 	return ret;
 }
+inline bool ChainReplay::replay(const struct LDKBestBlock *NONNULL_PTR from, const struct LDKListen *NONNULL_PTR listener) {
+	bool ret = (self.replay)(self.this_arg, from, listener);
+	return ret;
+}
+inline LDK::CResult_CVec_u8ZIOErrorZ KVStore::read(struct LDKStr key_namespace, struct LDKStr key) {
+	LDK::CResult_CVec_u8ZIOErrorZ ret = (self.read)(self.this_arg, key_namespace, key);
+	return ret;
+}
+inline LDK::CResult_NoneErrorZ KVStore::write(struct LDKStr key_namespace, struct LDKStr key, struct LDKu8slice value) {
+	LDK::CResult_NoneErrorZ ret = (self.write)(self.this_arg, key_namespace, key, value);
+	return ret;
+}
+inline LDK::CResult_NoneErrorZ KVStore::remove(struct LDKStr key_namespace, struct LDKStr key) {
+	LDK::CResult_NoneErrorZ ret = (self.remove)(self.this_arg, key_namespace, key);
+	return ret;
+}
+inline LDK::CResult_CVec_StrZIOErrorZ KVStore::list(struct LDKStr key_namespace) {
+	LDK::CResult_CVec_StrZIOErrorZ ret = (self.list)(self.this_arg, key_namespace);
+	return ret;
+}
+inline void MessageTracer::trace(struct LDKPublicKey node_id, enum LDKMessageDirection direction, uint16_t msg_type, struct LDKu8slice data) {
+	(self.trace)(self.this_arg, node_id, direction, msg_type, data);
+}
+inline bool CustomMessageCallback::handle_message(struct LDKPublicKey node_id, uint16_t type_id, struct LDKu8slice data) {
+	bool ret = (self.handle_message)(self.this_arg, node_id, type_id, data);
+	return ret;
+}
+inline void WakeupCallback::wake() {
+	(self.wake)(self.this_arg);
+}
+inline LDK::CResult_CVec_u8ZIOErrorZ SignerTransport::send_request(struct LDKu8slice request) {
+	LDK::CResult_CVec_u8ZIOErrorZ ret = (self.send_request)(self.this_arg, request);
+	return ret;
+}
+inline LDK::CResult_RecoverableSignatureNoneZ InvoiceSigner::sign_invoice(struct LDKCVec_u8Z invoice_preimage) {
+	LDK::CResult_RecoverableSignatureNoneZ ret = (self.sign_invoice)(self.this_arg, invoice_preimage);
+	return ret;
+}
+inline void PaymentOutcomeHandler::handle_outcome(const struct LDKPaymentOutcome *NONNULL_PTR outcome) {
+	(self.handle_outcome)(self.this_arg, outcome);
+}
+#if defined(LDK_LINUX)
+inline bool PeerFilter::accept_peer(struct LDKPublicKey node_id) {
+	bool ret = (self.accept_peer)(self.this_arg, node_id);
+	return ret;
+}
+#endif
+#if defined(LDK_LINUX)
+inline void PeerEventHandler::handle_peer_event(struct LDKPeerEvent event) {
+	(self.handle_peer_event)(self.this_arg, event);
+}
+#endif
 }
@@ -4,6 +4,7 @@
 pub mod derived;
 
 use bitcoin::Transaction as BitcoinTransaction;
+use bitcoin::util::psbt::PartiallySignedTransaction as BitcoinPsbt;
 use bitcoin::hashes::Hash;
 use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
 use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
@@ -298,6 +299,72 @@ impl Clone for Transaction {
 /// Frees the data buffer, if data_is_owned is set and datalen > 0.
 pub extern "C" fn Transaction_free(_res: Transaction) { }
 
+#[repr(C)]
+/// A BIP 174 Partially Signed Bitcoin Transaction, in its binary serialization, in (pointer,
+/// length) form.
+///
+/// This type optionally owns its own memory, and thus the semantics around access change based on
+/// the `data_is_owned` flag. If `data_is_owned` is set, you must call `Psbt_free` to free the
+/// underlying buffer before the object goes out of scope. If `data_is_owned` is not set, any
+/// access to the buffer after the scope in which the object was provided to you is invalid.
+///
+/// As with `Transaction`, all `Psbt`s generated on the Rust side will have `data_is_owned` set,
+/// while `Psbt`s you pass to Rust may have `data_is_owned` either set or unset at your discretion.
+/// Most wallets exchange PSBTs base64-encoded, which must be decoded before being passed to Rust.
+pub struct Psbt {
+	/// The serialized PSBT data.
+	///
+	/// This is non-const for your convenience, an object passed to Rust is never written to.
+	pub data: *mut u8,
+	/// The length of the serialized PSBT
+	pub datalen: usize,
+	/// Whether the data pointed to by `data` should be freed or not.
+	pub data_is_owned: bool,
+}
+impl Psbt {
+	fn from_vec(vec: Vec<u8>) -> Self {
+		let datalen = vec.len();
+		let data = Box::into_raw(vec.into_boxed_slice());
+		Self {
+			data: unsafe { (*data).as_mut_ptr() },
+			datalen,
+			data_is_owned: true,
+		}
+	}
+	/// Deserializes the PSBT, failing if it is not valid. Unlike Transactions, PSBTs are commonly
+	/// provided by external wallets, so are checked rather than unwrapped.
+	pub(crate) fn into_bitcoin(&self) -> Result<BitcoinPsbt, lightning::ln::msgs::DecodeError> {
+		if self.datalen == 0 { return Err(lightning::ln::msgs::DecodeError::ShortRead); }
+		::bitcoin::consensus::encode::deserialize(unsafe { std::slice::from_raw_parts(self.data, self.datalen) })
+			.map_err(|_| lightning::ln::msgs::DecodeError::InvalidValue)
+	}
+	pub(crate) fn from_bitcoin(btc: &BitcoinPsbt) -> Self {
+		let vec = ::bitcoin::consensus::encode::serialize(btc);
+		Self::from_vec(vec)
+	}
+}
+impl Drop for Psbt {
+	fn drop(&mut self) {
+		if self.data_is_owned && self.datalen != 0 {
+			let _ = derived::CVec_u8Z { data: self.data as *mut u8, datalen: self.datalen };
+		}
+	}
+}
+impl Clone for Psbt {
+	fn clone(&self) -> Self {
+		let sl = unsafe { std::slice::from_raw_parts(self.data, self.datalen) };
+		let mut v = Vec::new();
+		v.extend_from_slice(&sl);
+		Self::from_vec(v)
+	}
+}
+#[no_mangle]
+/// Frees the data buffer, if data_is_owned is set and datalen > 0.
+pub extern "C" fn Psbt_free(_res: Psbt) { }
+#[no_mangle]
+/// Creates a new Psbt which has the same data as `orig` but with a new buffer.
+pub extern "C" fn Psbt_clone(orig: &Psbt) -> Psbt { orig.clone() }
+
 pub(crate) fn bitcoin_to_C_outpoint(outpoint: ::bitcoin::blockdata::transaction::OutPoint) -> crate::lightning::chain::transaction::OutPoint {
 	crate::lightning::chain::transaction::OutPoint_new(ThirtyTwoBytes { data: outpoint.txid.into_inner() }, outpoint.vout.try_into().unwrap())
 }
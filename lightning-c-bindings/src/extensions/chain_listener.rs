@@ -0,0 +1,91 @@
+//! Adapts the hand-written types which follow the chain, such as the on-chain wallet and the
+//! output sweeper, to both the `Listen` and the `Confirm` interface.
+//!
+//! Implementors of [`ChainListener`] only handle the events common to both, the `extern "C"`
+//! glue being shared here.
+
+use std::ffi::c_void;
+
+use bitcoin::blockdata::block::Block;
+use bitcoin::blockdata::transaction::Transaction as BitcoinTransaction;
+use bitcoin::hash_types::Txid;
+use bitcoin::hashes::Hash;
+
+use crate::c_types::*;
+use crate::c_types::derived::{CVec_C2Tuple_usizeTransactionZZ, CVec_TxidZ};
+use crate::lightning::chain::{Confirm, Listen};
+
+/// The chain events a type passed to [`as_listen`] or [`as_confirm`] handles.
+pub(crate) trait ChainListener {
+	/// Handles transactions confirmed in the block at `height`. A block connected through
+	/// `Listen` passes all of its transactions.
+	fn transactions_confirmed(&self, txdata: &[BitcoinTransaction], height: u32);
+	/// Handles a transaction which was reorganized out of the chain.
+	fn transaction_unconfirmed(&self, txid: &Txid);
+	/// Handles a new best block, after any transactions confirmed in it.
+	fn best_block_updated(&self, height: u32);
+	/// Handles the disconnection of the block at `height` through `Listen`, which must
+	/// unconfirm every transaction confirmed at or above it.
+	fn block_disconnected(&self, height: u32);
+	/// Returns the confirmed transactions whose reorganization must be reported through
+	/// `transaction_unconfirmed`.
+	fn relevant_txids(&self) -> Vec<Txid>;
+}
+
+/// Constructs a new Listen which calls the relevant methods on `this_arg`, which must outlive
+/// it unless `free` is set on the result.
+pub(crate) fn as_listen<T: ChainListener>(this_arg: *mut T) -> Listen {
+	Listen {
+		this_arg: this_arg as *mut c_void,
+		free: None,
+		block_connected: ChainListener_Listen_block_connected::<T>,
+		block_disconnected: ChainListener_Listen_block_disconnected::<T>,
+	}
+}
+
+extern "C" fn ChainListener_Listen_block_connected<T: ChainListener>(this_arg: *const c_void, block: u8slice, height: u32) {
+	let listener = unsafe { &*(this_arg as *const T) };
+	let block: Block = ::bitcoin::consensus::encode::deserialize(block.to_slice()).unwrap();
+	listener.transactions_confirmed(&block.txdata, height);
+	listener.best_block_updated(height);
+}
+extern "C" fn ChainListener_Listen_block_disconnected<T: ChainListener>(this_arg: *const c_void, _header: *const [u8; 80], height: u32) {
+	let listener = unsafe { &*(this_arg as *const T) };
+	listener.block_disconnected(height);
+}
+
+/// Constructs a new Confirm which calls the relevant methods on `this_arg`, which must outlive
+/// it unless `free` is set on the result.
+pub(crate) fn as_confirm<T: ChainListener>(this_arg: *mut T) -> Confirm {
+	Confirm {
+		this_arg: this_arg as *mut c_void,
+		free: None,
+		transactions_confirmed: ChainListener_Confirm_transactions_confirmed::<T>,
+		transaction_unconfirmed: ChainListener_Confirm_transaction_unconfirmed::<T>,
+		best_block_updated: ChainListener_Confirm_best_block_updated::<T>,
+		get_relevant_txids: ChainListener_Confirm_get_relevant_txids::<T>,
+	}
+}
+
+extern "C" fn ChainListener_Confirm_transactions_confirmed<T: ChainListener>(this_arg: *const c_void, _header: *const [u8; 80], txdata: CVec_C2Tuple_usizeTransactionZZ, height: u32) {
+	let listener = unsafe { &*(this_arg as *const T) };
+	let txdata: Vec<BitcoinTransaction> = txdata.into_rust().drain(..).map(|item| item.to_rust().1.into_bitcoin()).collect();
+	listener.transactions_confirmed(&txdata, height);
+}
+extern "C" fn ChainListener_Confirm_transaction_unconfirmed<T: ChainListener>(this_arg: *const c_void, txid: *const [u8; 32]) {
+	let listener = unsafe { &*(this_arg as *const T) };
+	listener.transaction_unconfirmed(&Txid::from_slice(&unsafe { &*txid }[..]).unwrap());
+}
+extern "C" fn ChainListener_Confirm_best_block_updated<T: ChainListener>(this_arg: *const c_void, _header: *const [u8; 80], height: u32) {
+	let listener = unsafe { &*(this_arg as *const T) };
+	listener.best_block_updated(height);
+}
+#[must_use]
+extern "C" fn ChainListener_Confirm_get_relevant_txids<T: ChainListener>(this_arg: *const c_void) -> CVec_TxidZ {
+	let listener = unsafe { &*(this_arg as *const T) };
+	let mut txids = listener.relevant_txids();
+	txids.sort_unstable();
+	txids.dedup();
+	let local_txids: Vec<ThirtyTwoBytes> = txids.drain(..).map(|txid| ThirtyTwoBytes { data: txid.into_inner() }).collect();
+	local_txids.into()
+}
@@ -0,0 +1,521 @@
+//! A [`CustomMessageHandler`] which dispatches custom peer messages to per-type callbacks as raw
+//! bytes, and queues raw messages to send.
+//!
+//! Rather than implementing `CustomMessageReader`, `Type` and `CustomMessageHandler` from C,
+//! register a [`CustomMessageCallback`] for each message type with
+//! `CustomMessageRegistry_register` and pass the result of
+//! `CustomMessageRegistry_as_CustomMessageHandler` to `PeerManager_new`. Messages are sent with
+//! `CustomMessageRegistry_send_custom` and go out on the next `PeerManager_process_events`.
+//!
+//! Only message types in the BOLT 1 custom range (32768 and above) may be registered or sent.
+//! The "it's OK to be odd" rule applies on receipt: a message of an unregistered even type, or one
+//! whose callback rejects it, causes the `PeerManager` to disconnect the peer, while odd messages
+//! are ignored in the same cases. Disconnecting over a custom message does not close any channels
+//! with the peer.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::{Arc, Mutex, RwLock};
+
+use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
+
+use lightning::ln::msgs::{DecodeError as nativeDecodeError, ErrorAction as nativeErrorAction, LightningError as nativeLightningError};
+use lightning::util::logger::Level;
+
+use crate::c_types::*;
+use crate::c_types::derived::{C2Tuple_PublicKeyTypeZ, COption_TypeZ, CResult_COption_TypeZDecodeErrorZ, CResult_COption_TypeZDecodeErrorZ_ok, CResult_COption_TypeZDecodeErrorZ_err, CResult_NoneLightningErrorZ, CResult_NoneLightningErrorZ_ok, CResult_NoneLightningErrorZ_err, CVec_C2Tuple_PublicKeyTypeZZ};
+use crate::lightning::ln::msgs::{DecodeError, LightningError};
+use crate::lightning::ln::peer_handler::CustomMessageHandler;
+use crate::lightning::ln::wire::{CustomMessageReader, Type};
+
+/// The first message type in the BOLT 1 range reserved for custom messages.
+const MIN_CUSTOM_TYPE: u16 = 32768;
+/// The largest payload which fits in a Lightning message along with its two-byte type.
+const MAX_PAYLOAD_LEN: usize = 65535 - 2;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The reasons registering or sending a custom message can fail.
+pub enum CustomMessageError {
+	/// The message type is below 32768, outside of the BOLT 1 range for custom messages.
+	OutsideCustomRange,
+	/// A callback is already registered for the message type.
+	AlreadyRegistered,
+	/// The message is too long to fit in a single Lightning message.
+	MessageTooLong,
+}
+
+#[repr(C)]
+/// The contents of CResult_NoneCustomMessageErrorZ
+pub union CResult_NoneCustomMessageErrorZPtr {
+	/// Note that this value is always NULL, as there are no contents in the OK variant
+	pub result: *mut c_void,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut CustomMessageError,
+}
+#[repr(C)]
+/// A CResult_NoneCustomMessageErrorZ represents the result of a fallible operation,
+/// containing a () on success and a CustomMessageError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_NoneCustomMessageErrorZ {
+	/// The contents of this CResult_NoneCustomMessageErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_NoneCustomMessageErrorZPtr,
+	/// Whether this CResult_NoneCustomMessageErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_NoneCustomMessageErrorZ in the success state.
+pub extern "C" fn CResult_NoneCustomMessageErrorZ_ok() -> CResult_NoneCustomMessageErrorZ {
+	CResult_NoneCustomMessageErrorZ {
+		contents: CResult_NoneCustomMessageErrorZPtr {
+			result: std::ptr::null_mut(),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_NoneCustomMessageErrorZ in the error state.
+pub extern "C" fn CResult_NoneCustomMessageErrorZ_err(e: CustomMessageError) -> CResult_NoneCustomMessageErrorZ {
+	CResult_NoneCustomMessageErrorZ {
+		contents: CResult_NoneCustomMessageErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_NoneCustomMessageErrorZ_is_ok(o: &CResult_NoneCustomMessageErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_NoneCustomMessageErrorZ.
+pub extern "C" fn CResult_NoneCustomMessageErrorZ_free(_res: CResult_NoneCustomMessageErrorZ) { }
+impl Drop for CResult_NoneCustomMessageErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_NoneCustomMessageErrorZ {
+	pub(crate) fn from_rust(res: Result<(), CustomMessageError>) -> Self {
+		match res {
+			Ok(()) => CResult_NoneCustomMessageErrorZ_ok(),
+			Err(e) => CResult_NoneCustomMessageErrorZ_err(e),
+		}
+	}
+}
+
+/// Handles custom messages of a single type received from peers.
+#[repr(C)]
+pub struct CustomMessageCallback {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Called with each message of the registered type, with `data` holding the message without
+	/// its type. `data` is only valid for the duration of the call.
+	///
+	/// Returns false if the message was invalid. For even message types this disconnects the peer,
+	/// for odd message types the message is ignored.
+	///
+	/// This is called while the `PeerManager` is processing data from the peer and may call
+	/// `CustomMessageRegistry_send_custom`.
+	#[must_use]
+	pub handle_message: extern "C" fn (this_arg: *const c_void, node_id: PublicKey, type_id: u16, data: u8slice) -> bool,
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for CustomMessageCallback {}
+unsafe impl Sync for CustomMessageCallback {}
+#[no_mangle]
+pub(crate) extern "C" fn CustomMessageCallback_clone_fields(orig: &CustomMessageCallback) -> CustomMessageCallback {
+	CustomMessageCallback {
+		this_arg: orig.this_arg,
+		handle_message: Clone::clone(&orig.handle_message),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn CustomMessageCallback_free(this_ptr: CustomMessageCallback) { }
+impl Drop for CustomMessageCallback {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+/// A custom message as a type and raw payload, pointed to by the `this_arg` of the [`Type`]s
+/// we create.
+struct RawCustomMessage {
+	type_id: u16,
+	data: Vec<u8>,
+}
+
+extern "C" fn RawCustomMessage_type_id(this_arg: *const c_void) -> u16 {
+	unsafe { &*(this_arg as *const RawCustomMessage) }.type_id
+}
+extern "C" fn RawCustomMessage_debug_str(this_arg: *const c_void) -> Str {
+	let msg = unsafe { &*(this_arg as *const RawCustomMessage) };
+	format!("custom message of type {} with {} bytes", msg.type_id, msg.data.len()).into()
+}
+extern "C" fn RawCustomMessage_write(this_arg: *const c_void) -> crate::c_types::derived::CVec_u8Z {
+	unsafe { &*(this_arg as *const RawCustomMessage) }.data.clone().into()
+}
+extern "C" fn RawCustomMessage_free_void(this_arg: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_arg as *mut RawCustomMessage); }
+}
+
+fn raw_message_type(type_id: u16, data: Vec<u8>) -> Type {
+	Type {
+		this_arg: Box::into_raw(Box::new(RawCustomMessage { type_id, data })) as *mut c_void,
+		type_id: RawCustomMessage_type_id,
+		debug_str: RawCustomMessage_debug_str,
+		write: RawCustomMessage_write,
+		free: Some(RawCustomMessage_free_void),
+	}
+}
+
+fn is_even(type_id: u16) -> bool {
+	type_id % 2 == 0
+}
+
+fn check_custom_type(type_id: u16) -> Result<(), CustomMessageError> {
+	if type_id < MIN_CUSTOM_TYPE { Err(CustomMessageError::OutsideCustomRange) } else { Ok(()) }
+}
+
+pub(crate) struct nativeCustomMessageRegistry {
+	callbacks: RwLock<HashMap<u16, Arc<CustomMessageCallback>>>,
+	pending_msgs: Mutex<Vec<(SecpPublicKey, u16, Vec<u8>)>>,
+}
+
+impl nativeCustomMessageRegistry {
+	fn register(&self, type_id: u16, callback: CustomMessageCallback) -> Result<(), CustomMessageError> {
+		check_custom_type(type_id)?;
+		let mut callbacks = self.callbacks.write().unwrap();
+		if callbacks.contains_key(&type_id) { return Err(CustomMessageError::AlreadyRegistered); }
+		callbacks.insert(type_id, Arc::new(callback));
+		Ok(())
+	}
+
+	fn unregister(&self, type_id: u16) -> bool {
+		self.callbacks.write().unwrap().remove(&type_id).is_some()
+	}
+
+	fn send_custom(&self, node_id: SecpPublicKey, type_id: u16, data: Vec<u8>) -> Result<(), CustomMessageError> {
+		check_custom_type(type_id)?;
+		if data.len() > MAX_PAYLOAD_LEN { return Err(CustomMessageError::MessageTooLong); }
+		self.pending_msgs.lock().unwrap().push((node_id, type_id, data));
+		Ok(())
+	}
+
+	fn is_registered(&self, type_id: u16) -> bool {
+		self.callbacks.read().unwrap().contains_key(&type_id)
+	}
+
+	fn handle_message(&self, node_id: PublicKey, type_id: u16, data: &[u8]) -> Result<(), nativeLightningError> {
+		// Clone the callback out so that it may register or unregister types without deadlocking.
+		let callback = self.callbacks.read().unwrap().get(&type_id).cloned();
+		let accepted = match callback {
+			Some(callback) => (callback.handle_message)(callback.this_arg, node_id, type_id, u8slice::from_slice(data)),
+			None => false,
+		};
+		if accepted { return Ok(()); }
+		let err = format!("Custom message of type {} was rejected", type_id);
+		let action = if is_even(type_id) {
+			nativeErrorAction::DisconnectPeer { msg: None }
+		} else {
+			nativeErrorAction::IgnoreAndLog(Level::Debug)
+		};
+		Err(nativeLightningError { err, action })
+	}
+}
+
+/// Dispatches custom messages to callbacks registered per message type and queues custom
+/// messages to send, see `CustomMessageRegistry_as_CustomMessageHandler`.
+#[must_use]
+#[repr(C)]
+pub struct CustomMessageRegistry {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeCustomMessageRegistry,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for CustomMessageRegistry {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeCustomMessageRegistry>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the CustomMessageRegistry, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn CustomMessageRegistry_free(this_obj: CustomMessageRegistry) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn CustomMessageRegistry_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeCustomMessageRegistry); }
+}
+#[allow(unused)]
+impl CustomMessageRegistry {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeCustomMessageRegistry {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeCustomMessageRegistry {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeCustomMessageRegistry {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a new CustomMessageRegistry with no registered message types.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn CustomMessageRegistry_new() -> CustomMessageRegistry {
+	CustomMessageRegistry { inner: ObjOps::heap_alloc(nativeCustomMessageRegistry {
+		callbacks: RwLock::new(HashMap::new()), pending_msgs: Mutex::new(Vec::new()),
+	}), is_owned: true }
+}
+
+/// Registers `callback` to handle all messages of type `type_id` received from peers.
+///
+/// Fails if `type_id` is below 32768 or a callback is already registered for it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn CustomMessageRegistry_register(this_arg: &CustomMessageRegistry, mut type_id: u16, mut callback: CustomMessageCallback) -> CResult_NoneCustomMessageErrorZ {
+	CResult_NoneCustomMessageErrorZ::from_rust(this_arg.get_native_ref().register(type_id, callback))
+}
+
+/// Removes the callback registered for `type_id`, returning false if there was none.
+///
+/// Later messages of this type are handled as unknown messages, i.e. ignored if `type_id` is odd
+/// and causing a disconnect if it is even.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn CustomMessageRegistry_unregister(this_arg: &CustomMessageRegistry, mut type_id: u16) -> bool {
+	this_arg.get_native_ref().unregister(type_id)
+}
+
+/// Queues a message of type `type_id` with the payload `data` to be sent to the peer with the
+/// given node id on the next `PeerManager_process_events` call. The message is dropped if we
+/// are not connected to the peer at that point.
+///
+/// Peers which do not understand an even message type will disconnect from us upon receiving
+/// it, so even types should only be sent to peers known to support them.
+///
+/// Fails if `type_id` is below 32768 or `data` is longer than 65533 bytes.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn CustomMessageRegistry_send_custom(this_arg: &CustomMessageRegistry, mut node_id: PublicKey, mut type_id: u16, mut data: u8slice) -> CResult_NoneCustomMessageErrorZ {
+	CResult_NoneCustomMessageErrorZ::from_rust(this_arg.get_native_ref().send_custom(node_id.into_rust(), type_id, data.to_slice().to_vec()))
+}
+
+impl From<nativeCustomMessageRegistry> for CustomMessageHandler {
+	fn from(obj: nativeCustomMessageRegistry) -> Self {
+		let mut rust_obj = CustomMessageRegistry { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = CustomMessageRegistry_as_CustomMessageHandler(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(CustomMessageRegistry_free_void);
+		ret
+	}
+}
+/// Constructs a new CustomMessageHandler which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned CustomMessageHandler must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn CustomMessageRegistry_as_CustomMessageHandler(this_arg: &CustomMessageRegistry) -> CustomMessageHandler {
+	CustomMessageHandler {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		handle_custom_message: CustomMessageRegistry_CustomMessageHandler_handle_custom_message,
+		get_and_clear_pending_msg: CustomMessageRegistry_CustomMessageHandler_get_and_clear_pending_msg,
+		CustomMessageReader: CustomMessageReader {
+			this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+			free: None,
+			read: CustomMessageRegistry_CustomMessageReader_read,
+		},
+	}
+}
+
+#[must_use]
+extern "C" fn CustomMessageRegistry_CustomMessageHandler_handle_custom_message(this_arg: *const c_void, mut msg: Type, mut sender_node_id: PublicKey) -> CResult_NoneLightningErrorZ {
+	let registry = unsafe { &*(this_arg as *const nativeCustomMessageRegistry) };
+	let type_id = (msg.type_id)(msg.this_arg);
+	let data = (msg.write)(msg.this_arg);
+	match registry.handle_message(sender_node_id, type_id, data.as_slice()) {
+		Ok(()) => CResult_NoneLightningErrorZ_ok(),
+		Err(e) => CResult_NoneLightningErrorZ_err(LightningError { inner: ObjOps::heap_alloc(e), is_owned: true }),
+	}
+}
+#[must_use]
+extern "C" fn CustomMessageRegistry_CustomMessageHandler_get_and_clear_pending_msg(this_arg: *const c_void) -> CVec_C2Tuple_PublicKeyTypeZZ {
+	let registry = unsafe { &*(this_arg as *const nativeCustomMessageRegistry) };
+	let pending = std::mem::replace(&mut *registry.pending_msgs.lock().unwrap(), Vec::new());
+	let mut ret = Vec::with_capacity(pending.len());
+	for (node_id, type_id, data) in pending {
+		ret.push(C2Tuple_PublicKeyTypeZ { a: PublicKey::from_rust(&node_id), b: raw_message_type(type_id, data) });
+	}
+	ret.into()
+}
+
+#[must_use]
+extern "C" fn CustomMessageRegistry_CustomMessageReader_read(this_arg: *const c_void, mut message_type: u16, mut buffer: u8slice) -> CResult_COption_TypeZDecodeErrorZ {
+	let registry = unsafe { &*(this_arg as *const nativeCustomMessageRegistry) };
+	if !registry.is_registered(message_type) {
+		// The PeerManager ignores odd unknown types. For even ones it would also close all channels
+		// with the peer, so we fail to read them instead, which only disconnects it.
+		if is_even(message_type) {
+			return CResult_COption_TypeZDecodeErrorZ_err(DecodeError { inner: ObjOps::heap_alloc(nativeDecodeError::InvalidValue), is_owned: true });
+		}
+		return CResult_COption_TypeZDecodeErrorZ_ok(COption_TypeZ::None);
+	}
+	CResult_COption_TypeZDecodeErrorZ_ok(COption_TypeZ::Some(raw_message_type(message_type, buffer.to_slice().to_vec())))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::secp256k1::Secp256k1;
+	use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
+
+	/// Records the messages it is called with, accepting them if `accept` is set.
+	struct Recorder {
+		accept: bool,
+		received: Mutex<Vec<(SecpPublicKey, u16, Vec<u8>)>>,
+	}
+
+	fn recording_callback(accept: bool) -> (CustomMessageCallback, Arc<Recorder>) {
+		let recorder = Arc::new(Recorder { accept, received: Mutex::new(Vec::new()) });
+		(CustomMessageCallback {
+			this_arg: Arc::into_raw(Arc::clone(&recorder)) as *mut c_void,
+			handle_message: Recorder_handle_message,
+			free: Some(Recorder_free),
+		}, recorder)
+	}
+	extern "C" fn Recorder_handle_message(this_arg: *const c_void, node_id: PublicKey, type_id: u16, data: u8slice) -> bool {
+		let recorder = unsafe { &*(this_arg as *const Recorder) };
+		recorder.received.lock().unwrap().push((node_id.into_rust(), type_id, data.to_slice().to_vec()));
+		recorder.accept
+	}
+	extern "C" fn Recorder_free(this_arg: *mut c_void) {
+		let _ = unsafe { Arc::from_raw(this_arg as *const Recorder) };
+	}
+
+	fn peer() -> SecpPublicKey {
+		SecpPublicKey::from_secret_key(&Secp256k1::new(), &SecpSecretKey::from_slice(&[7; 32]).unwrap())
+	}
+
+	/// Reads a message as the PeerManager would, returning `Err(())` if the read fails.
+	fn read(handler: &CustomMessageHandler, type_id: u16, data: &[u8]) -> Result<Option<Type>, ()> {
+		let res = (handler.CustomMessageReader.read)(handler.CustomMessageReader.this_arg, type_id, u8slice::from_slice(data));
+		if !res.result_ok { return Err(()); }
+		let msg = std::mem::replace(unsafe { &mut *res.contents.result }, COption_TypeZ::None);
+		Ok(if msg.is_some() { Some(msg.take()) } else { None })
+	}
+
+	/// Reads and handles a message as the PeerManager would, returning the action on failure.
+	fn receive(handler: &CustomMessageHandler, type_id: u16, data: &[u8]) -> Result<(), nativeErrorAction> {
+		let msg = read(handler, type_id, data).unwrap().expect("Expected the message to be read");
+		let res = (handler.handle_custom_message)(handler.this_arg, msg, PublicKey::from_rust(&peer()));
+		if res.result_ok { return Ok(()); }
+		Err(unsafe { &*res.contents.err }.get_native_ref().action.clone())
+	}
+
+	#[test]
+	fn messages_are_dispatched_by_type() {
+		let registry = CustomMessageRegistry_new();
+		let (even_callback, even) = recording_callback(true);
+		let (odd_callback, odd) = recording_callback(true);
+		assert!(registry.get_native_ref().register(32768, even_callback).is_ok());
+		assert!(registry.get_native_ref().register(32769, odd_callback).is_ok());
+		let handler = CustomMessageRegistry_as_CustomMessageHandler(&registry);
+
+		assert!(receive(&handler, 32768, &[1, 2, 3]).is_ok());
+		assert!(receive(&handler, 32769, &[]).is_ok());
+		assert_eq!(*even.received.lock().unwrap(), vec![(peer(), 32768, vec![1, 2, 3])]);
+		assert_eq!(*odd.received.lock().unwrap(), vec![(peer(), 32769, Vec::new())]);
+	}
+
+	#[test]
+	fn registration_is_checked() {
+		let owned_registry = CustomMessageRegistry_new();
+		let registry = owned_registry.get_native_ref();
+		assert_eq!(registry.register(32767, recording_callback(true).0), Err(CustomMessageError::OutsideCustomRange));
+		assert!(registry.register(32768, recording_callback(true).0).is_ok());
+		assert_eq!(registry.register(32768, recording_callback(true).0), Err(CustomMessageError::AlreadyRegistered));
+		assert!(registry.unregister(32768));
+		assert!(!registry.unregister(32768));
+		assert!(registry.register(32768, recording_callback(true).0).is_ok());
+	}
+
+	#[test]
+	fn unknown_even_messages_disconnect_and_unknown_odd_ones_are_ignored() {
+		let registry = CustomMessageRegistry_new();
+		let handler = CustomMessageRegistry_as_CustomMessageHandler(&registry);
+		assert!(read(&handler, 32768, &[1]).is_err());
+		assert!(read(&handler, 32769, &[1]).unwrap().is_none());
+	}
+
+	#[test]
+	fn rejected_even_messages_disconnect_and_rejected_odd_ones_are_ignored() {
+		let registry = CustomMessageRegistry_new();
+		assert!(registry.get_native_ref().register(32768, recording_callback(false).0).is_ok());
+		assert!(registry.get_native_ref().register(32769, recording_callback(false).0).is_ok());
+		let handler = CustomMessageRegistry_as_CustomMessageHandler(&registry);
+
+		match receive(&handler, 32768, &[1]) {
+			Err(nativeErrorAction::DisconnectPeer { msg: None }) => {},
+			_ => panic!("Expected a disconnect"),
+		}
+		match receive(&handler, 32769, &[1]) {
+			Err(nativeErrorAction::IgnoreAndLog(_)) => {},
+			_ => panic!("Expected the message to be ignored"),
+		}
+	}
+
+	#[test]
+	fn sent_messages_are_queued_until_collected() {
+		let registry = CustomMessageRegistry_new();
+		let handler = CustomMessageRegistry_as_CustomMessageHandler(&registry);
+		let res = CustomMessageRegistry_send_custom(&registry, PublicKey::from_rust(&peer()), 32771, u8slice::from_slice(&[4, 5]));
+		assert!(res.result_ok);
+
+		let mut pending = (handler.get_and_clear_pending_msg)(handler.this_arg).into_rust();
+		assert_eq!(pending.len(), 1);
+		let C2Tuple_PublicKeyTypeZ { a: node_id, b: msg } = pending.pop().unwrap();
+		assert_eq!(node_id.into_rust(), peer());
+		assert_eq!((msg.type_id)(msg.this_arg), 32771);
+		assert_eq!((msg.write)(msg.this_arg).into_rust(), vec![4, 5]);
+		assert!((handler.get_and_clear_pending_msg)(handler.this_arg).into_rust().is_empty());
+	}
+
+	#[test]
+	fn sent_messages_are_checked() {
+		let owned_registry = CustomMessageRegistry_new();
+		let registry = owned_registry.get_native_ref();
+		assert!(registry.send_custom(peer(), 32768, vec![0; MAX_PAYLOAD_LEN]).is_ok());
+		assert_eq!(registry.send_custom(peer(), 32768, vec![0; MAX_PAYLOAD_LEN + 1]), Err(CustomMessageError::MessageTooLong));
+		assert_eq!(registry.send_custom(peer(), 32767, Vec::new()), Err(CustomMessageError::OutsideCustomRange));
+		assert_eq!(registry.pending_msgs.lock().unwrap().len(), 1);
+	}
+}
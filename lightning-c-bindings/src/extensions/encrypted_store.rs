@@ -0,0 +1,420 @@
+//! A [`KVStore`] wrapper which encrypts and authenticates every value before it reaches the
+//! underlying store.
+//!
+//! A `Persist` or `ChannelManagerPersister` implementation serializes objects itself, so the
+//! encryption has to happen at the storage layer. Wrap the store in an [`EncryptedStore`] and
+//! build a [`KVStorePersister`] on top of it to get encrypted `Persist` and
+//! `ChannelManagerPersister` implementations, and transparently decrypting reads of the
+//! `ChannelMonitor`s, `ChannelManager` and `NetworkGraph`. To replace a `FilesystemPersister`,
+//! wrap a [`FilesystemStore`] pointed at a fresh data directory, and restore the node on restart
+//! with [`KVStorePersister_restore_node`] in place of `FilesystemPersister_restore_node`.
+//!
+//! A `FilesystemPersister` can be wrapped directly with
+//! [`EncryptedStore_wrap_filesystem_persister`], which takes over its data directory, encrypts
+//! the files it wrote in place and returns a [`KVStorePersister`] keeping the same layout. Other
+//! `Persist` and `ChannelManagerPersister` implementations never hand the serialized bytes back,
+//! so their storage backend has to be wrapped instead, by implementing [`KVStore`] for it.
+//!
+//! Values are encrypted with XChaCha20-Poly1305 under a key derived from the node seed, using a
+//! random nonce per write. The namespace and key are authenticated along with the value, so a
+//! value moved to a different key (e.g. one `ChannelMonitor` copied over another) fails to
+//! decrypt. Note that the store cannot detect an old value being restored under its own key, and
+//! that the keys themselves (which include channel funding outpoints) are not encrypted.
+//!
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+//! [`KVStorePersister`]: crate::extensions::kv_store::KVStorePersister
+//! [`FilesystemStore`]: crate::extensions::fs_store::FilesystemStore
+//! [`KVStorePersister_restore_node`]: crate::extensions::restore::KVStorePersister_restore_node
+
+use std::ffi::c_void;
+use std::path::PathBuf;
+
+use bitcoin::hashes::{Hash, HashEngine, Hmac, HmacEngine};
+use bitcoin::hashes::sha256::Hash as Sha256;
+
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+
+use crate::c_types::*;
+use crate::c_types::derived::CResult_NoneErrorZ;
+use crate::extensions::fs_store::nativeFilesystemStore;
+use crate::extensions::kv_store::*;
+use crate::lightning_persister::FilesystemPersister;
+
+/// The version byte prefixed to each encrypted value, allowing the format to change later.
+const FORMAT_VERSION: u8 = 1;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+
+/// The key, in the `""` namespace, of an empty value written once the plaintext values of a
+/// wrapped `FilesystemPersister` have all been encrypted.
+const ENCRYPTED_MARKER_KEY: &str = "encrypted_store";
+
+/// Derives the storage encryption key from the node seed with HKDF-SHA256, such that it is
+/// independent of any of the keys derived by `KeysManager`.
+fn derive_storage_key(seed: &[u8; 32]) -> [u8; 32] {
+	let mut extract = HmacEngine::<Sha256>::new(b"LDK storage encryption");
+	extract.input(seed);
+	let prk = Hmac::from_engine(extract);
+	let mut expand = HmacEngine::<Sha256>::new(&prk[..]);
+	expand.input(b"XChaCha20-Poly1305 key");
+	expand.input(&[1]);
+	Hmac::from_engine(expand).into_inner()
+}
+
+/// The associated data binding a value to the location it is stored at.
+fn value_aad(namespace: &str, key: &str) -> Vec<u8> {
+	let mut aad = Vec::with_capacity(namespace.len() + key.len() + 1);
+	aad.extend_from_slice(namespace.as_bytes());
+	aad.push(0);
+	aad.extend_from_slice(key.as_bytes());
+	aad
+}
+
+fn invalid_data(msg: &'static str) -> std::io::Error {
+	std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+pub(crate) struct nativeEncryptedStore {
+	inner: KVStore,
+	cipher: XChaCha20Poly1305,
+}
+
+impl nativeEncryptedStore {
+	pub(crate) fn new(inner: KVStore, seed: &[u8; 32]) -> Self {
+		let key = derive_storage_key(seed);
+		Self { inner, cipher: XChaCha20Poly1305::new(Key::from_slice(&key)) }
+	}
+
+	pub(crate) fn read(&self, namespace: &str, key: &str) -> Result<Vec<u8>, std::io::Error> {
+		let data = self.inner.read(namespace, key)?;
+		if data.len() < 1 + NONCE_LEN + TAG_LEN { return Err(invalid_data("Encrypted value is too short")); }
+		if data[0] != FORMAT_VERSION { return Err(invalid_data("Unknown encrypted value version")); }
+		let nonce = XNonce::from_slice(&data[1..1 + NONCE_LEN]);
+		let aad = value_aad(namespace, key);
+		self.cipher.decrypt(nonce, Payload { msg: &data[1 + NONCE_LEN..], aad: &aad })
+			.map_err(|_| invalid_data("Encrypted value failed authentication"))
+	}
+
+	pub(crate) fn write(&self, namespace: &str, key: &str, value: &[u8]) -> Result<(), std::io::Error> {
+		let mut nonce = [0; NONCE_LEN];
+		getrandom::getrandom(&mut nonce).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+		let aad = value_aad(namespace, key);
+		let ciphertext = self.cipher.encrypt(XNonce::from_slice(&nonce), Payload { msg: value, aad: &aad })
+			.map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Encryption failed"))?;
+		let mut data = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+		data.push(FORMAT_VERSION);
+		data.extend_from_slice(&nonce);
+		data.extend_from_slice(&ciphertext);
+		self.inner.write(namespace, key, &data)
+	}
+
+	/// Encrypts in place the values a `FilesystemPersister` wrote in plaintext, unless that was
+	/// already done. Values which already decrypt are left alone, so an interrupted run can be
+	/// resumed.
+	fn encrypt_plaintext_values(&self) -> Result<(), std::io::Error> {
+		match self.read(NODE_NAMESPACE, ENCRYPTED_MARKER_KEY) {
+			Ok(_) => return Ok(()),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+			Err(e) => return Err(e),
+		}
+		for namespace in [CHANNEL_MONITOR_NAMESPACE, NODE_NAMESPACE].iter() {
+			for key in self.inner.list(namespace)? {
+				if self.read(namespace, &key).is_ok() { continue; }
+				let value = self.inner.read(namespace, &key)?;
+				self.write(namespace, &key, &value)?;
+			}
+		}
+		self.write(NODE_NAMESPACE, ENCRYPTED_MARKER_KEY, &[])
+	}
+}
+
+/// A [`KVStore`] which encrypts all values written to an underlying [`KVStore`] with a key
+/// derived from the node seed, and which rejects any value which fails authentication on read
+/// with an `IOError_InvalidData`.
+///
+/// [`KVStore`]: crate::extensions::kv_store::KVStore
+#[must_use]
+#[repr(C)]
+pub struct EncryptedStore {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeEncryptedStore,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for EncryptedStore {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeEncryptedStore>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the EncryptedStore, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn EncryptedStore_free(this_obj: EncryptedStore) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn EncryptedStore_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeEncryptedStore); }
+}
+#[allow(unused)]
+impl EncryptedStore {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeEncryptedStore {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeEncryptedStore {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeEncryptedStore {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a new EncryptedStore which stores its encrypted values in `inner`.
+///
+/// `seed` should be the same seed passed to `KeysManager_new`. The encryption key is derived
+/// from it independently of all other keys, and the seed itself is not retained.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn EncryptedStore_new(mut inner: KVStore, seed: *const [u8; 32]) -> EncryptedStore {
+	EncryptedStore { inner: ObjOps::heap_alloc(nativeEncryptedStore::new(inner, unsafe { &*seed })), is_owned: true }
+}
+
+impl From<nativeEncryptedStore> for KVStore {
+	fn from(obj: nativeEncryptedStore) -> Self {
+		let mut rust_obj = EncryptedStore { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = EncryptedStore_as_KVStore(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(EncryptedStore_free_void);
+		ret
+	}
+}
+/// Constructs a new KVStore which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned KVStore must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn EncryptedStore_as_KVStore(this_arg: &EncryptedStore) -> KVStore {
+	KVStore {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		read: EncryptedStore_KVStore_read,
+		write: EncryptedStore_KVStore_write,
+		remove: EncryptedStore_KVStore_remove,
+		list: EncryptedStore_KVStore_list,
+	}
+}
+
+#[must_use]
+extern "C" fn EncryptedStore_KVStore_read(this_arg: *const c_void, mut key_namespace: Str, mut key: Str) -> CResult_CVec_u8ZIOErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeEncryptedStore) };
+	CResult_CVec_u8ZIOErrorZ::from_rust(store.read(key_namespace.into_str(), key.into_str()))
+}
+#[must_use]
+extern "C" fn EncryptedStore_KVStore_write(this_arg: *const c_void, mut key_namespace: Str, mut key: Str, mut value: u8slice) -> CResult_NoneErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeEncryptedStore) };
+	none_result_from_rust(store.write(key_namespace.into_str(), key.into_str(), value.to_slice()))
+}
+#[must_use]
+extern "C" fn EncryptedStore_KVStore_remove(this_arg: *const c_void, mut key_namespace: Str, mut key: Str) -> CResult_NoneErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeEncryptedStore) };
+	none_result_from_rust(store.inner.remove(key_namespace.into_str(), key.into_str()))
+}
+#[must_use]
+extern "C" fn EncryptedStore_KVStore_list(this_arg: *const c_void, mut key_namespace: Str) -> CResult_CVec_StrZIOErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeEncryptedStore) };
+	CResult_CVec_StrZIOErrorZ::from_rust(store.inner.list(key_namespace.into_str()))
+}
+
+#[repr(C)]
+/// The contents of CResult_KVStorePersisterIOErrorZ
+pub union CResult_KVStorePersisterIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut KVStorePersister,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_KVStorePersisterIOErrorZ represents the result of a fallible operation,
+/// containing a KVStorePersister on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_KVStorePersisterIOErrorZ {
+	/// The contents of this CResult_KVStorePersisterIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_KVStorePersisterIOErrorZPtr,
+	/// Whether this CResult_KVStorePersisterIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_KVStorePersisterIOErrorZ in the success state.
+pub extern "C" fn CResult_KVStorePersisterIOErrorZ_ok(o: KVStorePersister) -> CResult_KVStorePersisterIOErrorZ {
+	CResult_KVStorePersisterIOErrorZ {
+		contents: CResult_KVStorePersisterIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_KVStorePersisterIOErrorZ in the error state.
+pub extern "C" fn CResult_KVStorePersisterIOErrorZ_err(e: IOError) -> CResult_KVStorePersisterIOErrorZ {
+	CResult_KVStorePersisterIOErrorZ {
+		contents: CResult_KVStorePersisterIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_KVStorePersisterIOErrorZ_is_ok(o: &CResult_KVStorePersisterIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_KVStorePersisterIOErrorZ.
+pub extern "C" fn CResult_KVStorePersisterIOErrorZ_free(_res: CResult_KVStorePersisterIOErrorZ) { }
+impl Drop for CResult_KVStorePersisterIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Takes over the data directory of `persister`, returning a `KVStorePersister` which encrypts
+/// everything it writes there, in the same layout, as an `EncryptedStore` would. Use its
+/// `KVStorePersister_as_Persist` and `KVStorePersister_as_ChannelManagerPersister` in place of
+/// those of `persister`, and `KVStorePersister_restore_node` in place of
+/// `FilesystemPersister_restore_node`.
+///
+/// The first time a data directory is wrapped, the `ChannelMonitor`s, `ChannelManager` and any
+/// other files `persister` wrote in plaintext are encrypted in place, after which an empty
+/// `encrypted_store` file marks the directory as encrypted. Any plaintext file found afterwards
+/// fails authentication on read. Note that plaintext files tampered with before the first call
+/// are encrypted as they are, and that the plaintext may still be recoverable from the disk.
+///
+/// `seed` should be the same seed passed to `KeysManager_new`. Returns an error if the existing
+/// files could not be encrypted.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn EncryptedStore_wrap_filesystem_persister(mut persister: FilesystemPersister, seed: *const [u8; 32]) -> CResult_KVStorePersisterIOErrorZ {
+	let data_dir = PathBuf::from(persister.get_native_ref().get_data_dir());
+	let store = nativeEncryptedStore::new(KVStore::from(nativeFilesystemStore::new(data_dir)), unsafe { &*seed });
+	match store.encrypt_plaintext_values() {
+		Ok(()) => CResult_KVStorePersisterIOErrorZ_ok(KVStorePersister { inner: ObjOps::heap_alloc(nativeKVStorePersister::new(KVStore::from(store))), is_owned: true }),
+		Err(e) => CResult_KVStorePersisterIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::extensions::test_utils::MemoryStore;
+	use crate::lightning_persister::nativeFilesystemPersister;
+
+	const SEED: [u8; 32] = [1; 32];
+
+	/// A data directory unique to this test, with any leftovers from a previous run removed.
+	fn data_dir(name: &str) -> PathBuf {
+		let path = std::env::temp_dir().join(format!("ldk-encrypted-store-test-{}-{}", name, std::process::id()));
+		let _ = std::fs::remove_dir_all(&path);
+		path
+	}
+
+	fn assert_invalid(res: Result<Vec<u8>, std::io::Error>) {
+		assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn rejects_tampered_values() {
+		let inner = MemoryStore::new();
+		let store = nativeEncryptedStore::new(inner.as_kv_store(), &SEED);
+		store.write(CHANNEL_MONITOR_NAMESPACE, "monitor", b"channel monitor").unwrap();
+		assert_eq!(store.read(CHANNEL_MONITOR_NAMESPACE, "monitor").unwrap(), b"channel monitor");
+		let raw = inner.as_kv_store().read(CHANNEL_MONITOR_NAMESPACE, "monitor").unwrap();
+		assert_eq!(raw.len(), 1 + NONCE_LEN + b"channel monitor".len() + TAG_LEN);
+
+		// Flipping any bit of the version, nonce, ciphertext or tag is detected.
+		for i in 0..raw.len() {
+			let mut tampered = raw.clone();
+			tampered[i] ^= 1;
+			inner.as_kv_store().write(CHANNEL_MONITOR_NAMESPACE, "monitor", &tampered).unwrap();
+			assert_invalid(store.read(CHANNEL_MONITOR_NAMESPACE, "monitor"));
+		}
+
+		// As is a value moved to another key, or encrypted under another seed.
+		inner.as_kv_store().write(CHANNEL_MONITOR_NAMESPACE, "other_monitor", &raw).unwrap();
+		assert_invalid(store.read(CHANNEL_MONITOR_NAMESPACE, "other_monitor"));
+		inner.as_kv_store().write(CHANNEL_MONITOR_NAMESPACE, "monitor", &raw).unwrap();
+		assert_invalid(nativeEncryptedStore::new(inner.as_kv_store(), &[2; 32]).read(CHANNEL_MONITOR_NAMESPACE, "monitor"));
+		assert_eq!(store.read(CHANNEL_MONITOR_NAMESPACE, "monitor").unwrap(), b"channel monitor");
+	}
+
+	#[test]
+	fn rejects_truncated_values() {
+		let inner = MemoryStore::new();
+		let store = nativeEncryptedStore::new(inner.as_kv_store(), &SEED);
+		store.write(NODE_NAMESPACE, CHANNEL_MANAGER_KEY, b"channel manager").unwrap();
+		let raw = inner.as_kv_store().read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY).unwrap();
+		for len in 0..raw.len() {
+			inner.as_kv_store().write(NODE_NAMESPACE, CHANNEL_MANAGER_KEY, &raw[..len]).unwrap();
+			assert_invalid(store.read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY));
+		}
+		let mut extended = raw.clone();
+		extended.push(0);
+		inner.as_kv_store().write(NODE_NAMESPACE, CHANNEL_MANAGER_KEY, &extended).unwrap();
+		assert_invalid(store.read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY));
+	}
+
+	#[test]
+	fn wraps_filesystem_persister() {
+		let dir = data_dir("wrap");
+		let plaintext = nativeFilesystemStore::new(dir.clone());
+		plaintext.write(CHANNEL_MONITOR_NAMESPACE, "monitor", b"channel monitor").unwrap();
+		plaintext.write(NODE_NAMESPACE, CHANNEL_MANAGER_KEY, b"channel manager").unwrap();
+		let wrap = || {
+			let persister = FilesystemPersister { inner: ObjOps::heap_alloc(nativeFilesystemPersister::new(dir.to_str().unwrap().to_owned())), is_owned: true };
+			let res = EncryptedStore_wrap_filesystem_persister(persister, &SEED);
+			assert!(res.result_ok);
+		};
+
+		wrap();
+		let encrypted = nativeEncryptedStore::new(KVStore::from(nativeFilesystemStore::new(dir.clone())), &SEED);
+		let raw_monitor = plaintext.read(CHANNEL_MONITOR_NAMESPACE, "monitor").unwrap();
+		assert_ne!(raw_monitor, b"channel monitor");
+		assert_eq!(encrypted.read(CHANNEL_MONITOR_NAMESPACE, "monitor").unwrap(), b"channel monitor");
+		assert_eq!(encrypted.read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY).unwrap(), b"channel manager");
+
+		// Wrapping again neither re-encrypts anything nor accepts plaintext planted since.
+		plaintext.write(NODE_NAMESPACE, NETWORK_GRAPH_KEY, b"network graph").unwrap();
+		wrap();
+		assert_eq!(plaintext.read(CHANNEL_MONITOR_NAMESPACE, "monitor").unwrap(), raw_monitor);
+		assert_invalid(encrypted.read(NODE_NAMESPACE, NETWORK_GRAPH_KEY));
+
+		// An interrupted run is resumed without encrypting values twice.
+		plaintext.remove(NODE_NAMESPACE, ENCRYPTED_MARKER_KEY).unwrap();
+		wrap();
+		assert_eq!(encrypted.read(CHANNEL_MONITOR_NAMESPACE, "monitor").unwrap(), b"channel monitor");
+		assert_eq!(encrypted.read(NODE_NAMESPACE, NETWORK_GRAPH_KEY).unwrap(), b"network graph");
+
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
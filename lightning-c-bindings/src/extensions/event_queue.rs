@@ -0,0 +1,433 @@
+//! An [`EventHandler`] which buffers events in a queue to be polled from C, rather than handling
+//! them synchronously.
+//!
+//! `EventHandler` callbacks are invoked on whichever LDK thread processes events, often while
+//! `ChannelManager` locks are held, which does not fit languages with their own event loop. Pass
+//! the result of `EventQueue_as_EventHandler` to `ChannelManager_as_EventsProvider`'s
+//! `process_pending_events` or `BackgroundProcessor_start` instead, and fetch events with
+//! `EventQueue_next` from any thread. `EventQueue_get_fd` returns a file descriptor which is
+//! readable while events are pending, for use with `epoll`, libuv, GLib and similar loops.
+//!
+//! Note that LDK considers an event handled once it has been added to the queue, and the
+//! `ChannelManager` may be persisted (eg by the `BackgroundProcessor`) right afterwards. A queue
+//! created with `EventQueue_new` only lives in memory, so any events still queued when the process
+//! exits are lost, and the `ChannelManager` must then only be persisted once the queue has been
+//! drained, which rules out the `BackgroundProcessor`. A queue created with `EventQueue_load`
+//! instead writes each event to a [`KVStore`] before it is queued, and only removes it once it is
+//! returned by `EventQueue_next`, so it can be used with the `BackgroundProcessor`.
+//!
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::sync::{Condvar, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use lightning::util::events::Event as nativeEvent;
+use lightning::util::ser::{MaybeReadable, Writeable};
+
+use crate::c_types::*;
+use crate::c_types::derived::COption_EventZ;
+use crate::extensions::kv_store::KVStore;
+use crate::extensions::readiness::ReadinessFd;
+use crate::lightning::util::events::{Event, EventHandler};
+
+/// The namespace under which queued events are stored.
+const EVENT_QUEUE_NAMESPACE: &str = "event_queue";
+/// How long to wait before retrying a failed write of a queued event.
+const WRITE_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The key under which the event with the given sequence number is stored, which sorts in
+/// sequence order.
+fn event_key(id: u64) -> String {
+	format!("{:016x}", id)
+}
+
+pub(crate) struct nativeEventQueue {
+	/// The queued events, with the sequence numbers they are stored under.
+	events: Mutex<VecDeque<(u64, nativeEvent)>>,
+	event_added: Condvar,
+	/// Readable exactly while `events` is non-empty. Only updated with `events` locked.
+	readiness: ReadinessFd,
+	/// Where events are stored until they are returned by `next`, if anywhere.
+	store: Option<KVStore>,
+	next_id: AtomicU64,
+}
+
+impl nativeEventQueue {
+	fn new(store: Option<KVStore>) -> Self {
+		Self {
+			events: Mutex::new(VecDeque::new()), event_added: Condvar::new(), readiness: ReadinessFd::new(),
+			store, next_id: AtomicU64::new(0),
+		}
+	}
+
+	/// Creates a queue holding the events stored in `store`, in the order they were queued.
+	fn load(store: KVStore) -> Result<Self, std::io::Error> {
+		let mut ids = Vec::new();
+		for key in store.list(EVENT_QUEUE_NAMESPACE)? {
+			ids.push(u64::from_str_radix(&key, 16)
+				.map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Invalid queued event key {}", key)))?);
+		}
+		ids.sort_unstable();
+		let mut events = VecDeque::with_capacity(ids.len());
+		for id in ids.iter() {
+			let data = store.read(EVENT_QUEUE_NAMESPACE, &event_key(*id))?;
+			match MaybeReadable::read(&mut Cursor::new(&data)) {
+				Ok(Some(event)) => events.push_back((*id, event)),
+				// Some events, eg FundingGenerationReady, are meaningless after a restart.
+				Ok(None) => store.remove(EVENT_QUEUE_NAMESPACE, &event_key(*id))?,
+				Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Failed to read queued event: {:?}", e))),
+			}
+		}
+		let queue = Self::new(Some(store));
+		queue.next_id.store(ids.last().map(|id| id + 1).unwrap_or(0), Ordering::Release);
+		queue.readiness.set_ready(!events.is_empty());
+		*queue.events.lock().unwrap() = events;
+		Ok(queue)
+	}
+
+	fn push(&self, event: nativeEvent) {
+		let id = self.next_id.fetch_add(1, Ordering::AcqRel);
+		if let Some(store) = &self.store {
+			// LDK considers the event handled once we return, so we cannot give up on it. Blocking
+			// here also keeps the ChannelManager from being persisted until the event is.
+			let data = event.encode();
+			while store.write(EVENT_QUEUE_NAMESPACE, &event_key(id), &data).is_err() {
+				thread::sleep(WRITE_RETRY_INTERVAL);
+			}
+		}
+		let mut events = self.events.lock().unwrap();
+		events.push_back((id, event));
+		self.readiness.set_ready(true);
+		self.event_added.notify_all();
+	}
+
+	fn next(&self, timeout: Duration) -> Option<nativeEvent> {
+		// Timeouts too long to represent as an Instant wait forever.
+		let deadline = Instant::now().checked_add(timeout);
+		let mut events = self.events.lock().unwrap();
+		while events.is_empty() {
+			events = match deadline {
+				Some(deadline) => {
+					let now = Instant::now();
+					if now >= deadline { return None; }
+					self.event_added.wait_timeout(events, deadline - now).unwrap().0
+				},
+				None => self.event_added.wait(events).unwrap(),
+			};
+		}
+		let (id, event) = events.pop_front().unwrap();
+		self.readiness.set_ready(!events.is_empty());
+		if let Some(store) = &self.store {
+			// If the removal fails the event is returned again after a restart, which is better
+			// than losing it.
+			let _ = store.remove(EVENT_QUEUE_NAMESPACE, &event_key(id));
+		}
+		Some(event)
+	}
+}
+
+/// A thread-safe queue of events, which implements [`EventHandler`] by cloning each event into
+/// the queue. See `EventQueue_as_EventHandler` and `EventQueue_next`.
+#[must_use]
+#[repr(C)]
+pub struct EventQueue {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeEventQueue,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for EventQueue {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeEventQueue>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the EventQueue, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn EventQueue_free(this_obj: EventQueue) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn EventQueue_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeEventQueue); }
+}
+#[allow(unused)]
+impl EventQueue {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeEventQueue {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeEventQueue {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeEventQueue {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_EventQueueIOErrorZ
+pub union CResult_EventQueueIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut EventQueue,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_EventQueueIOErrorZ represents the result of a fallible operation,
+/// containing a EventQueue on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_EventQueueIOErrorZ {
+	/// The contents of this CResult_EventQueueIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_EventQueueIOErrorZPtr,
+	/// Whether this CResult_EventQueueIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_EventQueueIOErrorZ in the success state.
+pub extern "C" fn CResult_EventQueueIOErrorZ_ok(o: EventQueue) -> CResult_EventQueueIOErrorZ {
+	CResult_EventQueueIOErrorZ {
+		contents: CResult_EventQueueIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_EventQueueIOErrorZ in the error state.
+pub extern "C" fn CResult_EventQueueIOErrorZ_err(e: IOError) -> CResult_EventQueueIOErrorZ {
+	CResult_EventQueueIOErrorZ {
+		contents: CResult_EventQueueIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_EventQueueIOErrorZ_is_ok(o: &CResult_EventQueueIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_EventQueueIOErrorZ.
+pub extern "C" fn CResult_EventQueueIOErrorZ_free(_res: CResult_EventQueueIOErrorZ) { }
+impl Drop for CResult_EventQueueIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Creates a new, empty EventQueue, which only holds events in memory.
+///
+/// The `ChannelManager` must then only be persisted once the queue has been drained, see the
+/// module documentation.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn EventQueue_new() -> EventQueue {
+	EventQueue { inner: ObjOps::heap_alloc(nativeEventQueue::new(None)), is_owned: true }
+}
+
+/// Creates an EventQueue which stores each event in `store` until it is returned by
+/// `EventQueue_next`, loading any events which were still queued when it was last used.
+///
+/// If storing an event fails, the write is retried every second, blocking the thread which is
+/// processing events, as LDK will not provide the event again. Events which could not be removed
+/// from `store` once returned are returned again after the next restart. Events which are only
+/// meaningful until a restart, such as `Event_FundingGenerationReady`, are dropped when loading.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn EventQueue_load(store: KVStore) -> CResult_EventQueueIOErrorZ {
+	match nativeEventQueue::load(store) {
+		Ok(queue) => CResult_EventQueueIOErrorZ_ok(EventQueue { inner: ObjOps::heap_alloc(queue), is_owned: true }),
+		Err(e) => CResult_EventQueueIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+/// Removes and returns the oldest queued event, removing it from the store as well if the queue
+/// was created with `EventQueue_load`.
+///
+/// If the queue is empty, waits up to `timeout_millis` milliseconds for an event to be queued,
+/// returning None if none arrives in time. A timeout of 0 never blocks, while u64::MAX waits
+/// indefinitely.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn EventQueue_next(this_arg: &EventQueue, mut timeout_millis: u64) -> COption_EventZ {
+	match this_arg.get_native_ref().next(Duration::from_millis(timeout_millis)) {
+		Some(event) => COption_EventZ::Some(Event::native_into(event)),
+		None => COption_EventZ::None,
+	}
+}
+
+/// Returns the number of events currently queued.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn EventQueue_len(this_arg: &EventQueue) -> usize {
+	this_arg.get_native_ref().events.lock().unwrap().len()
+}
+
+/// Returns a file descriptor which is readable while events are queued, or -1 if none could be
+/// created on this platform.
+///
+/// The file descriptor is owned by the EventQueue and closed when it is freed. It must only be
+/// polled, never read from or written to; it is cleared once `EventQueue_next` empties the queue.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn EventQueue_get_fd(this_arg: &EventQueue) -> i32 {
+	this_arg.get_native_ref().readiness.fd()
+}
+
+impl From<nativeEventQueue> for EventHandler {
+	fn from(obj: nativeEventQueue) -> Self {
+		let mut rust_obj = EventQueue { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = EventQueue_as_EventHandler(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(EventQueue_free_void);
+		ret
+	}
+}
+/// Constructs a new EventHandler which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned EventHandler must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn EventQueue_as_EventHandler(this_arg: &EventQueue) -> EventHandler {
+	EventHandler {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		handle_event: EventQueue_EventHandler_handle_event,
+	}
+}
+
+extern "C" fn EventQueue_EventHandler_handle_event(this_arg: *const c_void, event: &Event) {
+	let queue = unsafe { &*(this_arg as *const nativeEventQueue) };
+	queue.push(event.to_native());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::sync::Arc;
+
+	use bitcoin::blockdata::script::Script;
+
+	use crate::extensions::test_utils::MemoryStore;
+
+	fn event(n: u64) -> nativeEvent {
+		nativeEvent::PendingHTLCsForwardable { time_forwardable: Duration::from_secs(n) }
+	}
+
+	fn event_number(event: Option<nativeEvent>) -> u64 {
+		match event {
+			Some(nativeEvent::PendingHTLCsForwardable { time_forwardable }) => time_forwardable.as_secs(),
+			_ => panic!("Unexpected event {:?}", event),
+		}
+	}
+
+	#[cfg(unix)]
+	fn is_readable(fd: i32) -> bool {
+		let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+		assert!(unsafe { libc::poll(&mut pollfd, 1, 0) } >= 0);
+		pollfd.revents & libc::POLLIN != 0
+	}
+
+	#[test]
+	fn events_are_returned_in_order() {
+		let queue = nativeEventQueue::new(None);
+		assert!(queue.next(Duration::from_secs(0)).is_none());
+		for n in 0..3 { queue.push(event(n)); }
+		assert_eq!(queue.events.lock().unwrap().len(), 3);
+		for n in 0..3 { assert_eq!(event_number(queue.next(Duration::from_secs(0))), n); }
+		assert!(queue.next(Duration::from_millis(10)).is_none());
+	}
+
+	#[test]
+	fn next_waits_for_a_push() {
+		let queue = Arc::new(nativeEventQueue::new(None));
+		let pusher = {
+			let queue = Arc::clone(&queue);
+			thread::spawn(move || {
+				thread::sleep(Duration::from_millis(50));
+				queue.push(event(7));
+			})
+		};
+		assert_eq!(event_number(queue.next(Duration::from_secs(u64::MAX))), 7);
+		pusher.join().unwrap();
+	}
+
+	#[test]
+	fn readiness_tracks_whether_events_are_queued() {
+		let queue = nativeEventQueue::new(None);
+		assert!(!queue.readiness.is_ready());
+		queue.push(event(0));
+		queue.push(event(1));
+		assert!(queue.readiness.is_ready());
+		#[cfg(unix)]
+		assert!(is_readable(queue.readiness.fd()));
+		queue.next(Duration::from_secs(0));
+		assert!(queue.readiness.is_ready());
+		queue.next(Duration::from_secs(0));
+		assert!(!queue.readiness.is_ready());
+		#[cfg(unix)]
+		assert!(!is_readable(queue.readiness.fd()));
+	}
+
+	#[test]
+	fn queued_events_survive_restart() {
+		let store = MemoryStore::new();
+		let queue = nativeEventQueue::load(store.as_kv_store()).unwrap();
+		for n in 0..3 { queue.push(event(n)); }
+		assert_eq!(event_number(queue.next(Duration::from_secs(0))), 0);
+		assert_eq!(store.len(), 2);
+		drop(queue);
+
+		let queue = nativeEventQueue::load(store.as_kv_store()).unwrap();
+		assert!(queue.readiness.is_ready());
+		// New events are queued after the reloaded ones.
+		queue.push(event(3));
+		for n in 1..4 { assert_eq!(event_number(queue.next(Duration::from_secs(0))), n); }
+		assert!(queue.next(Duration::from_secs(0)).is_none());
+		assert_eq!(store.len(), 0);
+	}
+
+	#[test]
+	fn events_meaningless_after_restart_are_dropped_on_load() {
+		let store = MemoryStore::new();
+		let queue = nativeEventQueue::load(store.as_kv_store()).unwrap();
+		queue.push(nativeEvent::FundingGenerationReady {
+			temporary_channel_id: [0; 32], channel_value_satoshis: 100_000, output_script: Script::new(), user_channel_id: 0,
+		});
+		queue.push(event(1));
+		drop(queue);
+
+		let queue = nativeEventQueue::load(store.as_kv_store()).unwrap();
+		assert_eq!(queue.events.lock().unwrap().len(), 1);
+		assert_eq!(store.len(), 1);
+		assert_eq!(event_number(queue.next(Duration::from_secs(0))), 1);
+	}
+}
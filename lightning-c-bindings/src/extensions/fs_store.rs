@@ -0,0 +1,192 @@
+//! A [`KVStore`] which stores each value in its own file.
+//!
+//! The layout matches that of `FilesystemPersister`, with namespaces mapping to subdirectories of
+//! the data directory (and the empty namespace to the data directory itself), so a
+//! [`KVStorePersister`] on top of a [`FilesystemStore`] can read data written by a
+//! `FilesystemPersister` and vice versa.
+//!
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+//! [`KVStorePersister`]: crate::extensions::kv_store::KVStorePersister
+
+use std::ffi::c_void;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::c_types::*;
+use crate::c_types::derived::CResult_NoneErrorZ;
+use crate::extensions::kv_store::*;
+
+pub(crate) struct nativeFilesystemStore {
+	data_dir: PathBuf,
+}
+
+impl nativeFilesystemStore {
+	pub(crate) fn new(data_dir: PathBuf) -> Self {
+		Self { data_dir }
+	}
+
+	fn namespace_path(&self, namespace: &str) -> PathBuf {
+		let mut path = self.data_dir.clone();
+		if !namespace.is_empty() { path.push(namespace); }
+		path
+	}
+
+	pub(crate) fn read(&self, namespace: &str, key: &str) -> Result<Vec<u8>, std::io::Error> {
+		let mut path = self.namespace_path(namespace);
+		path.push(key);
+		fs::read(path)
+	}
+
+	/// Writes to a temporary file which is then renamed over the destination, so that readers
+	/// never see a partially-written value.
+	pub(crate) fn write(&self, namespace: &str, key: &str, value: &[u8]) -> Result<(), std::io::Error> {
+		let dir = self.namespace_path(namespace);
+		fs::create_dir_all(&dir)?;
+		let mut tmp_path = dir.clone();
+		tmp_path.push(format!("{}.tmp", key));
+		let mut dest_path = dir.clone();
+		dest_path.push(key);
+		{
+			let mut tmp_file = fs::File::create(&tmp_path)?;
+			tmp_file.write_all(value)?;
+			tmp_file.sync_all()?;
+		}
+		fs::rename(&tmp_path, &dest_path)?;
+		// Make sure the rename itself hits the disk.
+		#[cfg(not(target_os = "windows"))]
+		fs::File::open(&dir)?.sync_all()?;
+		Ok(())
+	}
+
+	pub(crate) fn remove(&self, namespace: &str, key: &str) -> Result<(), std::io::Error> {
+		let mut path = self.namespace_path(namespace);
+		path.push(key);
+		match fs::remove_file(path) {
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+			res => res,
+		}
+	}
+
+	/// Lists the regular files in the namespace's directory, skipping temporary files left behind
+	/// by interrupted writes.
+	pub(crate) fn list(&self, namespace: &str) -> Result<Vec<String>, std::io::Error> {
+		let dir = match fs::read_dir(self.namespace_path(namespace)) {
+			Ok(dir) => dir,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(e) => return Err(e),
+		};
+		let mut keys = Vec::new();
+		for entry in dir {
+			let entry = entry?;
+			if !entry.file_type()?.is_file() { continue; }
+			if let Some(name) = entry.file_name().to_str() {
+				if !name.ends_with(".tmp") { keys.push(name.to_owned()); }
+			}
+		}
+		Ok(keys)
+	}
+}
+
+/// A [`KVStore`] which stores each value in its own file in a data directory.
+///
+/// [`KVStore`]: crate::extensions::kv_store::KVStore
+#[must_use]
+#[repr(C)]
+pub struct FilesystemStore {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeFilesystemStore,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for FilesystemStore {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeFilesystemStore>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the FilesystemStore, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn FilesystemStore_free(this_obj: FilesystemStore) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn FilesystemStore_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeFilesystemStore); }
+}
+#[allow(unused)]
+impl FilesystemStore {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeFilesystemStore {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeFilesystemStore {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeFilesystemStore {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a new FilesystemStore which stores data in the directory at `data_dir`, creating it
+/// if needed on the first write.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn FilesystemStore_new(mut data_dir: crate::c_types::Str) -> FilesystemStore {
+	FilesystemStore { inner: ObjOps::heap_alloc(nativeFilesystemStore::new(PathBuf::from(data_dir.into_string()))), is_owned: true }
+}
+
+impl From<nativeFilesystemStore> for KVStore {
+	fn from(obj: nativeFilesystemStore) -> Self {
+		let mut rust_obj = FilesystemStore { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = FilesystemStore_as_KVStore(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(FilesystemStore_free_void);
+		ret
+	}
+}
+/// Constructs a new KVStore which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned KVStore must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn FilesystemStore_as_KVStore(this_arg: &FilesystemStore) -> KVStore {
+	KVStore {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		read: FilesystemStore_KVStore_read,
+		write: FilesystemStore_KVStore_write,
+		remove: FilesystemStore_KVStore_remove,
+		list: FilesystemStore_KVStore_list,
+	}
+}
+
+#[must_use]
+extern "C" fn FilesystemStore_KVStore_read(this_arg: *const c_void, mut key_namespace: Str, mut key: Str) -> CResult_CVec_u8ZIOErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeFilesystemStore) };
+	CResult_CVec_u8ZIOErrorZ::from_rust(store.read(key_namespace.into_str(), key.into_str()))
+}
+#[must_use]
+extern "C" fn FilesystemStore_KVStore_write(this_arg: *const c_void, mut key_namespace: Str, mut key: Str, mut value: u8slice) -> CResult_NoneErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeFilesystemStore) };
+	none_result_from_rust(store.write(key_namespace.into_str(), key.into_str(), value.to_slice()))
+}
+#[must_use]
+extern "C" fn FilesystemStore_KVStore_remove(this_arg: *const c_void, mut key_namespace: Str, mut key: Str) -> CResult_NoneErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeFilesystemStore) };
+	none_result_from_rust(store.remove(key_namespace.into_str(), key.into_str()))
+}
+#[must_use]
+extern "C" fn FilesystemStore_KVStore_list(this_arg: *const c_void, mut key_namespace: Str) -> CResult_CVec_StrZIOErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeFilesystemStore) };
+	CResult_CVec_StrZIOErrorZ::from_rust(store.list(key_namespace.into_str()))
+}
@@ -0,0 +1,835 @@
+//! Invoices whose payment preimage is only learned after the payment arrives ("HODL invoices"),
+//! eg from a swap service, and which are settled or cancelled later by the host.
+//!
+//! `HodlInvoiceManager_invoice_builder` registers a payment hash supplied by the host with the
+//! `ChannelManager` and returns an [`InvoiceBuilder`] for it. Pass the result of
+//! `HodlInvoiceManager_event_handler` to the `ChannelManager` (or `BackgroundProcessor`) in place
+//! of your own `EventHandler`: `Event::PaymentReceived` for a registered hash is passed on once
+//! the manager has started holding the payment, after which the host has `hold_blocks` blocks to
+//! call `HodlInvoiceManager_settle` with the preimage or `HodlInvoiceManager_cancel`.
+//!
+//! LDK does not report the CLTV expiry of received HTLCs, so the deadline is counted from the
+//! block at which the payment is received, and the invoice asks payers for a final CLTV expiry
+//! `MIN_FINAL_CLTV_EXPIRY` blocks beyond it. A held payment which is still pending at the deadline
+//! is failed back by `HodlInvoiceManager_timer_tick_occurred`, which should be called about once a
+//! minute, eg alongside `ChannelManager_timer_tick_occurred`.
+//!
+//! Registered hashes are stored in a [`KVStore`] under the `hodl_invoices` namespace, keyed by the
+//! hex-encoded payment hash, and forgotten once settled or once their invoice has expired without
+//! a payment being held. Writes which fail after registration are retried by
+//! `HodlInvoiceManager_timer_tick_occurred`.
+//!
+//! [`InvoiceBuilder`]: crate::extensions::invoice_builder::InvoiceBuilder
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::sync::Mutex;
+
+use bitcoin::hashes::Hash;
+use bitcoin::hashes::hex::{FromHex, ToHex};
+use bitcoin::hashes::sha256::Hash as Sha256;
+
+use lightning::ln::{PaymentHash, PaymentPreimage};
+use lightning::ln::channelmanager::MIN_FINAL_CLTV_EXPIRY;
+use lightning::ln::msgs::DecodeError;
+use lightning::util::logger::{Level, Logger as _};
+use lightning::util::ser::{Readable, Writeable, Writer};
+
+use crate::c_types::*;
+use crate::c_types::derived::COption_u64Z;
+use crate::extensions::invoice_builder::*;
+use crate::extensions::now_secs;
+use crate::extensions::kv_store::KVStore;
+use crate::lightning::ln::channelmanager::{ChannelManager, nativeChannelManager};
+use crate::lightning::util::events::{Event, EventHandler};
+use crate::lightning::util::logger::Logger;
+use crate::lightning_invoice::Currency;
+
+/// The namespace under which registered payment hashes are stored.
+const HODL_INVOICES_NAMESPACE: &str = "hodl_invoices";
+/// The longest a payment may be held. LDK rejects HTLCs expiring more than a week after the
+/// current block, and payers may add a few blocks of their own to the final CLTV expiry.
+const MAX_HOLD_BLOCKS: u32 = 6 * 24 * 5;
+
+const SERIALIZATION_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The reasons a HODL invoice operation can fail.
+pub enum HodlInvoiceError {
+	/// The requested amount was greater than the total bitcoin supply.
+	InvalidAmount,
+	/// `hold_blocks` was 0 or greater than five days of blocks.
+	InvalidHoldTime,
+	/// The payment hash is not registered with the manager.
+	UnknownPaymentHash,
+	/// A payment for the payment hash is already being held.
+	PaymentAlreadyReceived,
+	/// No payment for the payment hash is being held.
+	PaymentNotReceived,
+	/// The `ChannelManager` no longer holds the payment, eg because it failed the HTLCs back
+	/// when they got too close to expiry.
+	PaymentNoLongerPending,
+	/// The registration could not be written to the store.
+	PersistenceFailed,
+}
+
+#[repr(C)]
+/// The contents of CResult_InvoiceBuilderHodlInvoiceErrorZ
+pub union CResult_InvoiceBuilderHodlInvoiceErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut InvoiceBuilder,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut HodlInvoiceError,
+}
+#[repr(C)]
+/// A CResult_InvoiceBuilderHodlInvoiceErrorZ represents the result of a fallible operation,
+/// containing a InvoiceBuilder on success and a HodlInvoiceError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_InvoiceBuilderHodlInvoiceErrorZ {
+	/// The contents of this CResult_InvoiceBuilderHodlInvoiceErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_InvoiceBuilderHodlInvoiceErrorZPtr,
+	/// Whether this CResult_InvoiceBuilderHodlInvoiceErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_InvoiceBuilderHodlInvoiceErrorZ in the success state.
+pub extern "C" fn CResult_InvoiceBuilderHodlInvoiceErrorZ_ok(o: InvoiceBuilder) -> CResult_InvoiceBuilderHodlInvoiceErrorZ {
+	CResult_InvoiceBuilderHodlInvoiceErrorZ {
+		contents: CResult_InvoiceBuilderHodlInvoiceErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_InvoiceBuilderHodlInvoiceErrorZ in the error state.
+pub extern "C" fn CResult_InvoiceBuilderHodlInvoiceErrorZ_err(e: HodlInvoiceError) -> CResult_InvoiceBuilderHodlInvoiceErrorZ {
+	CResult_InvoiceBuilderHodlInvoiceErrorZ {
+		contents: CResult_InvoiceBuilderHodlInvoiceErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_InvoiceBuilderHodlInvoiceErrorZ_is_ok(o: &CResult_InvoiceBuilderHodlInvoiceErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_InvoiceBuilderHodlInvoiceErrorZ.
+pub extern "C" fn CResult_InvoiceBuilderHodlInvoiceErrorZ_free(_res: CResult_InvoiceBuilderHodlInvoiceErrorZ) { }
+impl Drop for CResult_InvoiceBuilderHodlInvoiceErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_InvoiceBuilderHodlInvoiceErrorZ {
+	fn from_rust(res: Result<InvoiceBuilder, HodlInvoiceError>) -> Self {
+		match res {
+			Ok(o) => CResult_InvoiceBuilderHodlInvoiceErrorZ_ok(o),
+			Err(e) => CResult_InvoiceBuilderHodlInvoiceErrorZ_err(e),
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_NoneHodlInvoiceErrorZ
+pub union CResult_NoneHodlInvoiceErrorZPtr {
+	/// Note that this value is always NULL, as there are no contents in the OK variant
+	pub result: *mut std::ffi::c_void,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut HodlInvoiceError,
+}
+#[repr(C)]
+/// A CResult_NoneHodlInvoiceErrorZ represents the result of a fallible operation,
+/// containing a () on success and a HodlInvoiceError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_NoneHodlInvoiceErrorZ {
+	/// The contents of this CResult_NoneHodlInvoiceErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_NoneHodlInvoiceErrorZPtr,
+	/// Whether this CResult_NoneHodlInvoiceErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_NoneHodlInvoiceErrorZ in the success state.
+pub extern "C" fn CResult_NoneHodlInvoiceErrorZ_ok() -> CResult_NoneHodlInvoiceErrorZ {
+	CResult_NoneHodlInvoiceErrorZ {
+		contents: CResult_NoneHodlInvoiceErrorZPtr {
+			result: std::ptr::null_mut(),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_NoneHodlInvoiceErrorZ in the error state.
+pub extern "C" fn CResult_NoneHodlInvoiceErrorZ_err(e: HodlInvoiceError) -> CResult_NoneHodlInvoiceErrorZ {
+	CResult_NoneHodlInvoiceErrorZ {
+		contents: CResult_NoneHodlInvoiceErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_NoneHodlInvoiceErrorZ_is_ok(o: &CResult_NoneHodlInvoiceErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_NoneHodlInvoiceErrorZ.
+pub extern "C" fn CResult_NoneHodlInvoiceErrorZ_free(_res: CResult_NoneHodlInvoiceErrorZ) { }
+impl Drop for CResult_NoneHodlInvoiceErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_NoneHodlInvoiceErrorZ {
+	fn from_rust(res: Result<(), HodlInvoiceError>) -> Self {
+		match res {
+			Ok(()) => CResult_NoneHodlInvoiceErrorZ_ok(),
+			Err(e) => CResult_NoneHodlInvoiceErrorZ_err(e),
+		}
+	}
+}
+
+enum HodlState {
+	AwaitingPayment,
+	Received {
+		amount_msat: u64,
+		/// The height at which the payment is failed back if it is still held.
+		deadline_height: u32,
+	},
+	Cancelled,
+}
+
+struct HodlInvoice {
+	hold_blocks: u32,
+	/// When the invoice expires, in seconds since the UNIX epoch, after which the registration is
+	/// forgotten unless a payment is being held.
+	expires_at: u64,
+	state: HodlState,
+	/// Whether `state` has been written to the store. Not itself persisted.
+	persisted: bool,
+}
+
+impl Writeable for HodlInvoice {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), std::io::Error> {
+		SERIALIZATION_VERSION.write(w)?;
+		self.hold_blocks.write(w)?;
+		self.expires_at.write(w)?;
+		match self.state {
+			HodlState::AwaitingPayment => 0u8.write(w),
+			HodlState::Received { amount_msat, deadline_height } => {
+				1u8.write(w)?;
+				amount_msat.write(w)?;
+				deadline_height.write(w)
+			},
+			HodlState::Cancelled => 2u8.write(w),
+		}
+	}
+}
+
+impl Readable for HodlInvoice {
+	fn read<R: std::io::Read>(r: &mut R) -> Result<Self, DecodeError> {
+		let ver: u8 = Readable::read(r)?;
+		if ver > SERIALIZATION_VERSION { return Err(DecodeError::UnknownVersion); }
+		let hold_blocks = Readable::read(r)?;
+		let expires_at = Readable::read(r)?;
+		let state = match <u8 as Readable>::read(r)? {
+			0 => HodlState::AwaitingPayment,
+			1 => HodlState::Received { amount_msat: Readable::read(r)?, deadline_height: Readable::read(r)? },
+			2 => HodlState::Cancelled,
+			_ => return Err(DecodeError::InvalidValue),
+		};
+		Ok(Self { hold_blocks, expires_at, state, persisted: true })
+	}
+}
+
+pub(crate) struct nativeHodlInvoiceManager {
+	channel_manager: &'static nativeChannelManager,
+	store: KVStore,
+	logger: Logger,
+	invoices: Mutex<HashMap<[u8; 32], HodlInvoice>>,
+}
+
+impl nativeHodlInvoiceManager {
+	pub(crate) fn load(channel_manager: &'static nativeChannelManager, store: KVStore, logger: Logger) -> Result<Self, std::io::Error> {
+		let mut invoices = HashMap::new();
+		for key in store.list(HODL_INVOICES_NAMESPACE)? {
+			let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid stored HODL invoice");
+			let payment_hash = <[u8; 32]>::from_hex(&key).map_err(|_| invalid())?;
+			let data = store.read(HODL_INVOICES_NAMESPACE, &key)?;
+			let invoice = HodlInvoice::read(&mut Cursor::new(data)).map_err(|_| invalid())?;
+			invoices.insert(payment_hash, invoice);
+		}
+		Ok(Self { channel_manager, store, logger, invoices: Mutex::new(invoices) })
+	}
+
+	/// Writes the invoice to the store. If this fails the invoice is marked as not persisted, and
+	/// the write is retried on the next timer tick.
+	fn persist(&self, payment_hash: &[u8; 32], invoice: &mut HodlInvoice) -> Result<(), HodlInvoiceError> {
+		let res = self.store.write(HODL_INVOICES_NAMESPACE, &payment_hash.to_hex(), &invoice.encode()).map_err(|e| {
+			log_record!(self.logger, Level::Error, "Failed to persist HODL invoice {}: {}", payment_hash.to_hex(), e);
+			HodlInvoiceError::PersistenceFailed
+		});
+		invoice.persisted = res.is_ok();
+		res
+	}
+
+	fn forget(&self, invoices: &mut HashMap<[u8; 32], HodlInvoice>, payment_hash: &[u8; 32]) {
+		invoices.remove(payment_hash);
+		if let Err(e) = self.store.remove(HODL_INVOICES_NAMESPACE, &payment_hash.to_hex()) {
+			log_record!(self.logger, Level::Error, "Failed to remove HODL invoice {}: {}", payment_hash.to_hex(), e);
+		}
+	}
+
+	fn best_height(&self) -> u32 {
+		self.channel_manager.current_best_block().height()
+	}
+
+	fn register(&self, payment_hash: [u8; 32], min_value_msat: Option<u64>, expiry_secs: u32, hold_blocks: u32) -> Result<[u8; 32], HodlInvoiceError> {
+		if hold_blocks == 0 || hold_blocks > MAX_HOLD_BLOCKS { return Err(HodlInvoiceError::InvalidHoldTime); }
+		let mut invoices = self.invoices.lock().unwrap();
+		if let Some(HodlInvoice { state: HodlState::Received { .. }, .. }) = invoices.get(&payment_hash) {
+			return Err(HodlInvoiceError::PaymentAlreadyReceived);
+		}
+		let payment_secret = self.channel_manager.create_inbound_payment_for_hash(PaymentHash(payment_hash), min_value_msat, expiry_secs)
+			.map_err(|()| HodlInvoiceError::InvalidAmount)?;
+		let mut invoice = HodlInvoice { hold_blocks, expires_at: now_secs() + expiry_secs as u64, state: HodlState::AwaitingPayment, persisted: false };
+		self.persist(&payment_hash, &mut invoice)?;
+		invoices.insert(payment_hash, invoice);
+		Ok(payment_secret.0)
+	}
+
+	/// Starts holding a received payment, returning whether the event should be passed on.
+	fn payment_received(&self, payment_hash: [u8; 32], amount_msat: u64) -> bool {
+		let mut invoices = self.invoices.lock().unwrap();
+		let invoice = match invoices.get_mut(&payment_hash) {
+			Some(invoice) => invoice,
+			None => return true,
+		};
+		let deadline_height = match invoice.state {
+			HodlState::AwaitingPayment => self.best_height() + invoice.hold_blocks,
+			HodlState::Received { deadline_height, .. } => deadline_height,
+			HodlState::Cancelled => {
+				log_record!(self.logger, Level::Info, "Failing back payment to cancelled HODL invoice {}", payment_hash.to_hex());
+				self.channel_manager.fail_htlc_backwards(&PaymentHash(payment_hash));
+				return false;
+			},
+		};
+		invoice.state = HodlState::Received { amount_msat, deadline_height };
+		log_record!(self.logger, Level::Info, "Holding payment of {} msat to HODL invoice {} until block {}", amount_msat, payment_hash.to_hex(), deadline_height);
+		// The ChannelManager holds the HTLCs regardless, so the payment is still usable in memory.
+		// If the write fails it is retried on the next timer tick.
+		let _ = self.persist(&payment_hash, invoice);
+		true
+	}
+
+	fn settle(&self, payment_preimage: [u8; 32]) -> Result<(), HodlInvoiceError> {
+		let payment_hash = Sha256::hash(&payment_preimage).into_inner();
+		let mut invoices = self.invoices.lock().unwrap();
+		match invoices.get(&payment_hash) {
+			None => return Err(HodlInvoiceError::UnknownPaymentHash),
+			Some(HodlInvoice { state: HodlState::Received { .. }, .. }) => {},
+			Some(_) => return Err(HodlInvoiceError::PaymentNotReceived),
+		}
+		let claimed = self.channel_manager.claim_funds(PaymentPreimage(payment_preimage));
+		self.forget(&mut invoices, &payment_hash);
+		if claimed { Ok(()) } else { Err(HodlInvoiceError::PaymentNoLongerPending) }
+	}
+
+	fn cancel(&self, payment_hash: [u8; 32]) -> Result<(), HodlInvoiceError> {
+		let mut invoices = self.invoices.lock().unwrap();
+		let invoice = invoices.get_mut(&payment_hash).ok_or(HodlInvoiceError::UnknownPaymentHash)?;
+		if let HodlState::Received { .. } = invoice.state {
+			self.channel_manager.fail_htlc_backwards(&PaymentHash(payment_hash));
+		}
+		invoice.state = HodlState::Cancelled;
+		self.persist(&payment_hash, invoice)
+	}
+
+	fn timer_tick_occurred(&self) {
+		let height = self.best_height();
+		let now = now_secs();
+		let mut invoices = self.invoices.lock().unwrap();
+		let mut expired = Vec::new();
+		for (payment_hash, invoice) in invoices.iter_mut() {
+			match invoice.state {
+				HodlState::Received { deadline_height, .. } if height >= deadline_height => {
+					log_record!(self.logger, Level::Warn, "Failing back payment to HODL invoice {} which was not settled by block {}", payment_hash.to_hex(), deadline_height);
+					self.channel_manager.fail_htlc_backwards(&PaymentHash(*payment_hash));
+					invoice.state = HodlState::Cancelled;
+					let _ = self.persist(payment_hash, invoice);
+				},
+				HodlState::AwaitingPayment|HodlState::Cancelled if now > invoice.expires_at => expired.push(*payment_hash),
+				_ => if !invoice.persisted {
+					if self.persist(payment_hash, invoice).is_ok() {
+						log_record!(self.logger, Level::Info, "Persisted HODL invoice {} after earlier failures", payment_hash.to_hex());
+					}
+				},
+			}
+		}
+		for payment_hash in expired.iter() {
+			self.forget(&mut invoices, payment_hash);
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_HodlInvoiceManagerIOErrorZ
+pub union CResult_HodlInvoiceManagerIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut HodlInvoiceManager,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_HodlInvoiceManagerIOErrorZ represents the result of a fallible operation,
+/// containing a HodlInvoiceManager on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_HodlInvoiceManagerIOErrorZ {
+	/// The contents of this CResult_HodlInvoiceManagerIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_HodlInvoiceManagerIOErrorZPtr,
+	/// Whether this CResult_HodlInvoiceManagerIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_HodlInvoiceManagerIOErrorZ in the success state.
+pub extern "C" fn CResult_HodlInvoiceManagerIOErrorZ_ok(o: HodlInvoiceManager) -> CResult_HodlInvoiceManagerIOErrorZ {
+	CResult_HodlInvoiceManagerIOErrorZ {
+		contents: CResult_HodlInvoiceManagerIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_HodlInvoiceManagerIOErrorZ in the error state.
+pub extern "C" fn CResult_HodlInvoiceManagerIOErrorZ_err(e: IOError) -> CResult_HodlInvoiceManagerIOErrorZ {
+	CResult_HodlInvoiceManagerIOErrorZ {
+		contents: CResult_HodlInvoiceManagerIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_HodlInvoiceManagerIOErrorZ_is_ok(o: &CResult_HodlInvoiceManagerIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_HodlInvoiceManagerIOErrorZ.
+pub extern "C" fn CResult_HodlInvoiceManagerIOErrorZ_free(_res: CResult_HodlInvoiceManagerIOErrorZ) { }
+impl Drop for CResult_HodlInvoiceManagerIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Tracks payments to HODL invoices, see the module documentation.
+#[must_use]
+#[repr(C)]
+pub struct HodlInvoiceManager {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeHodlInvoiceManager,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for HodlInvoiceManager {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeHodlInvoiceManager>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the HodlInvoiceManager, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_free(this_obj: HodlInvoiceManager) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn HodlInvoiceManager_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeHodlInvoiceManager); }
+}
+#[allow(unused)]
+impl HodlInvoiceManager {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeHodlInvoiceManager {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeHodlInvoiceManager {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeHodlInvoiceManager {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a manager for HODL invoices paid to `channel_manager`, loading the payment hashes
+/// previously registered in `store`.
+///
+/// The manager must be freed before `channel_manager` is.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_new(channel_manager: &ChannelManager, mut store: KVStore, mut logger: Logger) -> CResult_HodlInvoiceManagerIOErrorZ {
+	match nativeHodlInvoiceManager::load(channel_manager.get_native_ref(), store, logger) {
+		Ok(manager) => CResult_HodlInvoiceManagerIOErrorZ_ok(HodlInvoiceManager { inner: ObjOps::heap_alloc(manager), is_owned: true }),
+		Err(e) => CResult_HodlInvoiceManagerIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+/// Registers `payment_hash` with the `ChannelManager` and this manager, returning an
+/// [`InvoiceBuilder`] for it with the given amount, if any, and expiry time set.
+///
+/// Once a payment arrives, it is held for `hold_blocks` blocks, which must be between 1 and 720
+/// (five days). The invoice's `min_final_cltv_expiry` is set to `hold_blocks` plus
+/// `MIN_FINAL_CLTV_EXPIRY` and should not be lowered.
+///
+/// Registering a hash again, eg to issue another invoice for it, is allowed until a payment for
+/// it is received, and un-cancels it if it was cancelled.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_invoice_builder(this_arg: &HodlInvoiceManager, mut currency: Currency, mut payment_hash: ThirtyTwoBytes, mut amount_msat: COption_u64Z, mut description: Str, mut expiry_secs: u32, mut hold_blocks: u32) -> CResult_InvoiceBuilderHodlInvoiceErrorZ {
+	let amount_msat = if amount_msat.is_some() { Some(amount_msat.take()) } else { None };
+	let res = this_arg.get_native_ref().register(payment_hash.data, amount_msat, expiry_secs, hold_blocks).map(|payment_secret| {
+		let mut builder = InvoiceBuilder_new(currency, payment_hash, ThirtyTwoBytes { data: payment_secret }, description);
+		if let Some(amount_msat) = amount_msat {
+			InvoiceBuilder_set_amount_milli_satoshis(&mut builder, amount_msat);
+		}
+		InvoiceBuilder_set_expiry_time(&mut builder, expiry_secs as u64);
+		InvoiceBuilder_set_min_final_cltv_expiry(&mut builder, (hold_blocks + MIN_FINAL_CLTV_EXPIRY) as u64);
+		builder
+	});
+	CResult_InvoiceBuilderHodlInvoiceErrorZ::from_rust(res)
+}
+
+/// Claims the held payment whose payment hash is the hash of `payment_preimage` and stops
+/// tracking it.
+///
+/// Fails without effect if the payment hash is unknown or no payment is being held for it. If
+/// the `ChannelManager` no longer has the payment's HTLCs, the payment hash is forgotten and
+/// `PaymentNoLongerPending` is returned.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_settle(this_arg: &HodlInvoiceManager, mut payment_preimage: ThirtyTwoBytes) -> CResult_NoneHodlInvoiceErrorZ {
+	CResult_NoneHodlInvoiceErrorZ::from_rust(this_arg.get_native_ref().settle(payment_preimage.data))
+}
+
+/// Fails back the payment held for `payment_hash`, if any, and fails back any further payment to
+/// it until its invoice expires.
+///
+/// The cancellation takes effect even if it could not be persisted, in which case
+/// `PersistenceFailed` is returned and a payment arriving after a restart would be held again.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_cancel(this_arg: &HodlInvoiceManager, payment_hash: *const [u8; 32]) -> CResult_NoneHodlInvoiceErrorZ {
+	CResult_NoneHodlInvoiceErrorZ::from_rust(this_arg.get_native_ref().cancel(unsafe { *payment_hash }))
+}
+
+/// Returns the amount of the payment held for `payment_hash`, or None if no payment is held for
+/// it, eg after a restart to learn which payments still await settlement.
+///
+/// If `persisted` is not NULL, it is set to whether the state of `payment_hash` has been written
+/// to the store. A held payment or cancellation which is not would be lost on restart, so hosts
+/// may want to wait for `HodlInvoiceManager_timer_tick_occurred` to retry the write first.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_held_amount_msat(this_arg: &HodlInvoiceManager, payment_hash: *const [u8; 32], persisted: *mut bool) -> COption_u64Z {
+	let invoices = this_arg.get_native_ref().invoices.lock().unwrap();
+	let invoice = invoices.get(unsafe { &*payment_hash });
+	if !persisted.is_null() {
+		unsafe { *persisted = invoice.map(|invoice| invoice.persisted).unwrap_or(true) };
+	}
+	match invoice {
+		Some(HodlInvoice { state: HodlState::Received { amount_msat, .. }, .. }) => COption_u64Z::Some(*amount_msat),
+		_ => COption_u64Z::None,
+	}
+}
+
+/// Fails back held payments which reached their deadline, forgets registrations whose invoice
+/// expired without being paid and retries failed writes. Should be called about once a minute.
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_timer_tick_occurred(this_arg: &HodlInvoiceManager) {
+	this_arg.get_native_ref().timer_tick_occurred()
+}
+
+struct HodlEventHandler {
+	manager: &'static nativeHodlInvoiceManager,
+	inner: EventHandler,
+}
+
+/// Wraps `inner` in an [`EventHandler`] which starts holding payments received for registered
+/// payment hashes before passing their `Event::PaymentReceived` on to `inner`. Payments to
+/// cancelled payment hashes are failed back instead. All other events are passed on to `inner`.
+///
+/// The returned handler must be freed before `this_arg` is.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn HodlInvoiceManager_event_handler(this_arg: &HodlInvoiceManager, mut inner: EventHandler) -> EventHandler {
+	let handler = Box::new(HodlEventHandler { manager: this_arg.get_native_ref(), inner });
+	EventHandler {
+		this_arg: Box::into_raw(handler) as *mut c_void,
+		handle_event: HodlEventHandler_handle_event,
+		free: Some(HodlEventHandler_free),
+	}
+}
+
+extern "C" fn HodlEventHandler_handle_event(this_arg: *const c_void, event: &Event) {
+	let handler = unsafe { &*(this_arg as *const HodlEventHandler) };
+	if let Event::PaymentReceived { payment_hash, amt, .. } = event {
+		if !handler.manager.payment_received(payment_hash.data, *amt) { return; }
+	}
+	(handler.inner.handle_event)(handler.inner.this_arg, event);
+}
+extern "C" fn HodlEventHandler_free(this_arg: *mut c_void) {
+	let _ = unsafe { Box::from_raw(this_arg as *mut HodlEventHandler) };
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Arc;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use crate::extensions::test_utils::{channel_manager, hodl_invoice_manager, MemoryStore};
+	use crate::lightning::ln::channelmanager::ChannelManager_as_Confirm;
+	use crate::lightning::util::events::PaymentPurpose;
+
+	const PAYMENT_PREIMAGE: [u8; 32] = [7; 32];
+	const HEIGHT: u32 = 100;
+
+	fn payment_hash() -> [u8; 32] {
+		Sha256::hash(&PAYMENT_PREIMAGE).into_inner()
+	}
+
+	fn held_amount_msat(manager: &HodlInvoiceManager) -> Option<u64> {
+		let mut amount_msat = HodlInvoiceManager_held_amount_msat(manager, &payment_hash(), std::ptr::null_mut());
+		if amount_msat.is_some() { Some(amount_msat.take()) } else { None }
+	}
+
+	fn is_persisted(manager: &HodlInvoiceManager) -> bool {
+		let mut persisted = false;
+		let _ = HodlInvoiceManager_held_amount_msat(manager, &payment_hash(), &mut persisted);
+		persisted
+	}
+
+	fn connect_block(channel_manager: &ChannelManager, height: u32) {
+		let confirm = ChannelManager_as_Confirm(channel_manager);
+		(confirm.best_block_updated)(confirm.this_arg, &[0; 80], height);
+	}
+
+	/// Returns an `EventHandler` counting the events passed on to it.
+	fn counting_handler() -> (EventHandler, Arc<AtomicUsize>) {
+		extern "C" fn handle_event(this_arg: *const c_void, _event: &Event) {
+			let count = unsafe { &*(this_arg as *const AtomicUsize) };
+			count.fetch_add(1, Ordering::SeqCst);
+		}
+		extern "C" fn free(this_arg: *mut c_void) {
+			let _ = unsafe { Arc::from_raw(this_arg as *const AtomicUsize) };
+		}
+		let count = Arc::new(AtomicUsize::new(0));
+		(EventHandler { this_arg: Arc::into_raw(Arc::clone(&count)) as *mut c_void, handle_event, free: Some(free) }, count)
+	}
+
+	fn payment_received(handler: &EventHandler, amt: u64) {
+		let event = Event::PaymentReceived {
+			payment_hash: ThirtyTwoBytes { data: payment_hash() }, amt,
+			purpose: PaymentPurpose::InvoicePayment { payment_preimage: ThirtyTwoBytes { data: [0; 32] }, payment_secret: ThirtyTwoBytes { data: [1; 32] } },
+		};
+		(handler.handle_event)(handler.this_arg, &event);
+	}
+
+	#[test]
+	fn registration_errors() {
+		let channel_manager = channel_manager(HEIGHT);
+		let manager = hodl_invoice_manager(&channel_manager, &MemoryStore::new());
+		let native_manager = manager.get_native_ref();
+
+		assert_eq!(native_manager.register(payment_hash(), None, 3600, 0).err(), Some(HodlInvoiceError::InvalidHoldTime));
+		assert_eq!(native_manager.register(payment_hash(), None, 3600, MAX_HOLD_BLOCKS + 1).err(), Some(HodlInvoiceError::InvalidHoldTime));
+		assert_eq!(native_manager.register(payment_hash(), Some(u64::max_value()), 3600, 6).err(), Some(HodlInvoiceError::InvalidAmount));
+		assert_eq!(native_manager.settle(PAYMENT_PREIMAGE).err(), Some(HodlInvoiceError::UnknownPaymentHash));
+		assert_eq!(native_manager.cancel(payment_hash()).err(), Some(HodlInvoiceError::UnknownPaymentHash));
+
+		assert!(native_manager.register(payment_hash(), Some(1000), 3600, 6).is_ok());
+		assert_eq!(native_manager.settle(PAYMENT_PREIMAGE).err(), Some(HodlInvoiceError::PaymentNotReceived));
+	}
+
+	#[test]
+	fn hold_and_settle() {
+		let channel_manager = channel_manager(HEIGHT);
+		let store = MemoryStore::new();
+		let (inner, passed_on) = counting_handler();
+		{
+			let manager = hodl_invoice_manager(&channel_manager, &store);
+			let handler = HodlInvoiceManager_event_handler(&manager, inner);
+			assert!(manager.get_native_ref().register(payment_hash(), Some(1000), 3600, 6).is_ok());
+			assert_eq!(store.len(), 1);
+			assert_eq!(held_amount_msat(&manager), None);
+
+			payment_received(&handler, 1000);
+			assert_eq!(passed_on.load(Ordering::SeqCst), 1);
+			assert_eq!(held_amount_msat(&manager), Some(1000));
+			assert_eq!(manager.get_native_ref().register(payment_hash(), Some(1000), 3600, 6).err(), Some(HodlInvoiceError::PaymentAlreadyReceived));
+		}
+
+		// The held payment survives a restart, and is forgotten once settled. Without any channels
+		// the ChannelManager has no HTLCs to claim.
+		let manager = hodl_invoice_manager(&channel_manager, &store);
+		assert_eq!(held_amount_msat(&manager), Some(1000));
+		assert_eq!(manager.get_native_ref().settle(PAYMENT_PREIMAGE).err(), Some(HodlInvoiceError::PaymentNoLongerPending));
+		assert_eq!(held_amount_msat(&manager), None);
+		assert_eq!(store.len(), 0);
+		assert_eq!(manager.get_native_ref().settle(PAYMENT_PREIMAGE).err(), Some(HodlInvoiceError::UnknownPaymentHash));
+	}
+
+	#[test]
+	fn cancel() {
+		let channel_manager = channel_manager(HEIGHT);
+		let manager = hodl_invoice_manager(&channel_manager, &MemoryStore::new());
+		let (inner, passed_on) = counting_handler();
+		let handler = HodlInvoiceManager_event_handler(&manager, inner);
+
+		assert!(manager.get_native_ref().register(payment_hash(), None, 3600, 6).is_ok());
+		payment_received(&handler, 1000);
+		assert!(manager.get_native_ref().cancel(payment_hash()).is_ok());
+		assert_eq!(held_amount_msat(&manager), None);
+		assert_eq!(manager.get_native_ref().settle(PAYMENT_PREIMAGE).err(), Some(HodlInvoiceError::PaymentNotReceived));
+
+		// Further payments are failed back rather than passed on, until the hash is registered again.
+		payment_received(&handler, 2000);
+		assert_eq!(passed_on.load(Ordering::SeqCst), 1);
+		assert_eq!(held_amount_msat(&manager), None);
+
+		assert!(manager.get_native_ref().register(payment_hash(), None, 3600, 6).is_ok());
+		payment_received(&handler, 3000);
+		assert_eq!(passed_on.load(Ordering::SeqCst), 2);
+		assert_eq!(held_amount_msat(&manager), Some(3000));
+	}
+
+	#[test]
+	fn held_payment_times_out() {
+		let channel_manager = channel_manager(HEIGHT);
+		let manager = hodl_invoice_manager(&channel_manager, &MemoryStore::new());
+		let (inner, passed_on) = counting_handler();
+		let handler = HodlInvoiceManager_event_handler(&manager, inner);
+
+		assert!(manager.get_native_ref().register(payment_hash(), None, 3600, 6).is_ok());
+		payment_received(&handler, 1000);
+		connect_block(&channel_manager, HEIGHT + 5);
+		HodlInvoiceManager_timer_tick_occurred(&manager);
+		assert_eq!(held_amount_msat(&manager), Some(1000));
+
+		connect_block(&channel_manager, HEIGHT + 6);
+		HodlInvoiceManager_timer_tick_occurred(&manager);
+		assert_eq!(held_amount_msat(&manager), None);
+		payment_received(&handler, 1000);
+		assert_eq!(passed_on.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn unpaid_invoice_expires() {
+		let channel_manager = channel_manager(HEIGHT);
+		let store = MemoryStore::new();
+		let manager = hodl_invoice_manager(&channel_manager, &store);
+		let native_manager = manager.get_native_ref();
+
+		assert!(native_manager.register(payment_hash(), None, 3600, 6).is_ok());
+		HodlInvoiceManager_timer_tick_occurred(&manager);
+		assert_eq!(store.len(), 1);
+
+		native_manager.invoices.lock().unwrap().get_mut(&payment_hash()).unwrap().expires_at = now_secs() - 1;
+		HodlInvoiceManager_timer_tick_occurred(&manager);
+		assert_eq!(store.len(), 0);
+		assert_eq!(native_manager.cancel(payment_hash()).err(), Some(HodlInvoiceError::UnknownPaymentHash));
+	}
+
+	#[test]
+	fn failed_writes_are_retried() {
+		let channel_manager = channel_manager(HEIGHT);
+		let store = MemoryStore::new();
+		{
+			let manager = hodl_invoice_manager(&channel_manager, &store);
+			let (inner, _) = counting_handler();
+			let handler = HodlInvoiceManager_event_handler(&manager, inner);
+
+			// A registration which cannot be persisted fails outright.
+			store.set_fail_writes(true);
+			assert_eq!(manager.get_native_ref().register(payment_hash(), None, 3600, 6).err(), Some(HodlInvoiceError::PersistenceFailed));
+			assert_eq!(manager.get_native_ref().cancel(payment_hash()).err(), Some(HodlInvoiceError::UnknownPaymentHash));
+			store.set_fail_writes(false);
+			assert!(manager.get_native_ref().register(payment_hash(), None, 3600, 6).is_ok());
+			assert!(is_persisted(&manager));
+
+			// A payment is held even if holding it cannot be persisted, until a timer tick succeeds.
+			store.set_fail_writes(true);
+			payment_received(&handler, 1000);
+			assert_eq!(held_amount_msat(&manager), Some(1000));
+			assert!(!is_persisted(&manager));
+			HodlInvoiceManager_timer_tick_occurred(&manager);
+			assert!(!is_persisted(&manager));
+			store.set_fail_writes(false);
+			HodlInvoiceManager_timer_tick_occurred(&manager);
+			assert!(is_persisted(&manager));
+		}
+		{
+			let manager = hodl_invoice_manager(&channel_manager, &store);
+			assert_eq!(held_amount_msat(&manager), Some(1000));
+
+			store.set_fail_writes(true);
+			assert_eq!(manager.get_native_ref().cancel(payment_hash()).err(), Some(HodlInvoiceError::PersistenceFailed));
+			assert_eq!(held_amount_msat(&manager), None);
+			assert!(!is_persisted(&manager));
+			store.set_fail_writes(false);
+			HodlInvoiceManager_timer_tick_occurred(&manager);
+			assert!(is_persisted(&manager));
+		}
+
+		// The cancellation survives a restart.
+		let manager = hodl_invoice_manager(&channel_manager, &store);
+		assert_eq!(held_amount_msat(&manager), None);
+		assert_eq!(manager.get_native_ref().settle(PAYMENT_PREIMAGE).err(), Some(HodlInvoiceError::PaymentNotReceived));
+	}
+}
@@ -0,0 +1,561 @@
+//! A BOLT 11 invoice builder for C users.
+//!
+//! `lightning_invoice::InvoiceBuilder` tracks which required fields have been set in its type,
+//! which cannot be expressed across the C boundary. [`InvoiceBuilder`] takes the required fields
+//! (payment hash, payment secret and description) in its constructor and accumulates the
+//! optional ones, building the invoice with `lightning_invoice::InvoiceBuilder` in
+//! `InvoiceBuilder_build_signed`. Invalid values are reported there as a `CreationError`, and the
+//! builder may be corrected and built again.
+//!
+//! Note that, unlike `create_invoice_from_channelmanager`, this does not register the payment
+//! with a `ChannelManager`. The payment hash and secret should come from
+//! `ChannelManager_create_inbound_payment` or `ChannelManager_create_inbound_payment_for_hash`.
+
+use std::ffi::c_void;
+use std::time::{Duration, UNIX_EPOCH};
+
+use bitcoin::bech32::{u5, FromBase32, ToBase32};
+use bitcoin::hashes::Hash;
+use bitcoin::hashes::sha256::Hash as Sha256;
+use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
+
+use lightning::ln::PaymentSecret;
+use lightning::ln::channelmanager::MIN_FINAL_CLTV_EXPIRY;
+use lightning::ln::features::InvoiceFeatures as nativeInvoiceFeatures;
+use lightning::routing::router::RouteHint as nativeRouteHint;
+use lightning::util::ser::{Readable, Writeable};
+use lightning_invoice::{CreationError as nativeCreationError, Currency as nativeCurrency, Fallback as nativeFallback, InvoiceBuilder as nativeInvoiceBuilder, RawInvoice, RawTaggedField, SignOrCreationError as nativeSignOrCreationError, TaggedField};
+
+use crate::c_types::*;
+use crate::c_types::derived::{CResult_InvoiceSignOrCreationErrorZ, CResult_RecoverableSignatureNoneZ, CVec_u8Z};
+use crate::lightning::chain::keysinterface::KeysInterface;
+use crate::lightning::ln::features::InvoiceFeatures;
+use crate::lightning::routing::router::RouteHint;
+use crate::lightning_invoice::{Currency, Fallback, Invoice, SignOrCreationError};
+
+/// Signs invoices built by an [`InvoiceBuilder`] with the node's key.
+///
+/// `InvoiceSigner_from_keys_interface` provides one which signs with a `KeysInterface`.
+#[repr(C)]
+pub struct InvoiceSigner {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Signs the SHA-256 hash of the given invoice preimage, ie the invoice's human-readable part
+	/// followed by its data, with the node's secret key, returning a recoverable ECDSA signature,
+	/// or an error if the invoice should not be signed. This is the same as
+	/// `KeysInterface::sign_invoice`.
+	pub sign_invoice: extern "C" fn (this_arg: *const c_void, invoice_preimage: CVec_u8Z) -> CResult_RecoverableSignatureNoneZ,
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for InvoiceSigner {}
+unsafe impl Sync for InvoiceSigner {}
+#[no_mangle]
+pub(crate) extern "C" fn InvoiceSigner_clone_fields(orig: &InvoiceSigner) -> InvoiceSigner {
+	InvoiceSigner {
+		this_arg: orig.this_arg,
+		sign_invoice: Clone::clone(&orig.sign_invoice),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn InvoiceSigner_free(this_ptr: InvoiceSigner) { }
+impl Drop for InvoiceSigner {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+/// Creates an [`InvoiceSigner`] which signs invoices with the node secret of `keys_interface`,
+/// taking ownership of it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn InvoiceSigner_from_keys_interface(keys_interface: KeysInterface) -> InvoiceSigner {
+	InvoiceSigner {
+		this_arg: Box::into_raw(Box::new(keys_interface)) as *mut c_void,
+		sign_invoice: KeysInterface_InvoiceSigner_sign_invoice,
+		free: Some(KeysInterface_InvoiceSigner_free),
+	}
+}
+extern "C" fn KeysInterface_InvoiceSigner_sign_invoice(this_arg: *const c_void, invoice_preimage: CVec_u8Z) -> CResult_RecoverableSignatureNoneZ {
+	let keys_interface = unsafe { &*(this_arg as *const KeysInterface) };
+	(keys_interface.sign_invoice)(keys_interface.this_arg, invoice_preimage)
+}
+extern "C" fn KeysInterface_InvoiceSigner_free(this_arg: *mut c_void) {
+	let _ = unsafe { Box::from_raw(this_arg as *mut KeysInterface) };
+}
+
+/// The data signed to sign an invoice, as in `lightning_invoice::RawInvoice::signable_hash`.
+fn invoice_preimage(raw_invoice: &RawInvoice) -> Vec<u8> {
+	let mut preimage = raw_invoice.hrp.to_string().into_bytes();
+	let mut data = raw_invoice.data.to_base32();
+	// Pad the data with zero bits past the next byte boundary, the excess being dropped again
+	// when converting to bytes.
+	let overhang = (data.len() * 5) % 8;
+	if overhang > 0 {
+		data.push(u5::try_from_u8(0).unwrap());
+		if overhang < 3 { data.push(u5::try_from_u8(0).unwrap()); }
+	}
+	preimage.extend_from_slice(&Vec::<u8>::from_base32(&data).expect("The data was padded with zero bits"));
+	preimage
+}
+
+/// Returns `features` with every feature set in `extra` set too.
+fn merge_features(features: &nativeInvoiceFeatures, extra: &nativeInvoiceFeatures) -> nativeInvoiceFeatures {
+	// Features serialize as a length-prefixed big-endian bitfield.
+	let (mut flags, extra_flags) = (features.encode().split_off(2), extra.encode().split_off(2));
+	if flags.len() < extra_flags.len() {
+		let mut padded = vec![0; extra_flags.len() - flags.len()];
+		padded.append(&mut flags);
+		flags = padded;
+	}
+	let offset = flags.len() - extra_flags.len();
+	for (byte, extra_byte) in flags[offset..].iter_mut().zip(extra_flags.iter()) {
+		*byte |= extra_byte;
+	}
+	let mut encoded = (flags.len() as u16).encode();
+	encoded.extend_from_slice(&flags);
+	Readable::read(&mut &encoded[..]).expect("Features are read back as written")
+}
+
+#[derive(Clone)]
+enum InvoiceDescription {
+	Direct(String),
+	Hash([u8; 32]),
+}
+
+#[derive(Clone)]
+pub(crate) struct nativeInvoiceBuilderFields {
+	currency: nativeCurrency,
+	payment_hash: [u8; 32],
+	payment_secret: [u8; 32],
+	description: InvoiceDescription,
+	amount_msat: Option<u64>,
+	timestamp: Option<u64>,
+	expiry_time_secs: Option<u64>,
+	min_final_cltv_expiry: u64,
+	payee_pub_key: Option<SecpPublicKey>,
+	fallbacks: Vec<nativeFallback>,
+	route_hints: Vec<nativeRouteHint>,
+	basic_mpp: bool,
+	features: Option<nativeInvoiceFeatures>,
+}
+
+impl nativeInvoiceBuilderFields {
+	fn build_signed(&self, signer: &InvoiceSigner) -> Result<lightning_invoice::Invoice, nativeSignOrCreationError> {
+		let builder = nativeInvoiceBuilder::new(self.currency.clone())
+			.payment_hash(Sha256::from_inner(self.payment_hash))
+			.payment_secret(PaymentSecret(self.payment_secret))
+			.min_final_cltv_expiry(self.min_final_cltv_expiry);
+		let builder = match &self.description {
+			InvoiceDescription::Direct(description) => builder.description(description.clone()),
+			InvoiceDescription::Hash(hash) => builder.description_hash(Sha256::from_inner(*hash)),
+		};
+		let mut builder = match self.timestamp {
+			Some(timestamp) => match UNIX_EPOCH.checked_add(Duration::from_secs(timestamp)) {
+				Some(time) => builder.timestamp(time),
+				None => return Err(nativeSignOrCreationError::CreationError(nativeCreationError::TimestampOutOfBounds)),
+			},
+			None => builder.current_timestamp(),
+		};
+		if let Some(amount_msat) = self.amount_msat {
+			builder = builder.amount_milli_satoshis(amount_msat);
+		}
+		if let Some(expiry_time_secs) = self.expiry_time_secs {
+			builder = builder.expiry_time(Duration::from_secs(expiry_time_secs));
+		}
+		if let Some(payee_pub_key) = self.payee_pub_key {
+			builder = builder.payee_pub_key(payee_pub_key);
+		}
+		for fallback in self.fallbacks.iter() {
+			builder = builder.fallback(fallback.clone());
+		}
+		for route_hint in self.route_hints.iter() {
+			builder = builder.private_route(route_hint.clone());
+		}
+		if self.basic_mpp {
+			builder = builder.basic_mpp();
+		}
+		let mut raw_invoice = builder.build_raw().map_err(nativeSignOrCreationError::CreationError)?;
+		if let Some(features) = &self.features {
+			for field in raw_invoice.data.tagged_fields.iter_mut() {
+				if let RawTaggedField::KnownSemantics(TaggedField::Features(invoice_features)) = field {
+					*invoice_features = merge_features(invoice_features, features);
+				}
+			}
+		}
+		let preimage = invoice_preimage(&raw_invoice);
+		let signed_invoice = raw_invoice.sign(|_| {
+			let mut ret = (signer.sign_invoice)(signer.this_arg, preimage.into());
+			match ret.result_ok {
+				true => Ok((*unsafe { Box::from_raw(<*mut _>::take_ptr(&mut ret.contents.result)) }).into_rust()),
+				false => Err(()),
+			}
+		}).map_err(nativeSignOrCreationError::SignError)?;
+		// The builder ensures the invoice is otherwise valid, so this only fails if the signature
+		// does not match the payee public key, if one was set, or unknown features are required.
+		lightning_invoice::Invoice::from_signed(signed_invoice).map_err(|_| nativeSignOrCreationError::SignError(()))
+	}
+}
+
+/// Accumulates the fields of a BOLT 11 invoice, see the module documentation.
+#[must_use]
+#[repr(C)]
+pub struct InvoiceBuilder {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeInvoiceBuilderFields,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for InvoiceBuilder {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeInvoiceBuilderFields>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the InvoiceBuilder, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_free(this_obj: InvoiceBuilder) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn InvoiceBuilder_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeInvoiceBuilderFields); }
+}
+#[allow(unused)]
+impl InvoiceBuilder {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeInvoiceBuilderFields {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeInvoiceBuilderFields {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeInvoiceBuilderFields {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+impl Clone for InvoiceBuilder {
+	fn clone(&self) -> Self {
+		Self {
+			inner: if <*mut nativeInvoiceBuilderFields>::is_null(self.inner) { std::ptr::null_mut() } else {
+				ObjOps::heap_alloc(unsafe { &*ObjOps::untweak_ptr(self.inner) }.clone()) },
+			is_owned: true,
+		}
+	}
+}
+/// Creates a copy of the InvoiceBuilder
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_clone(orig: &InvoiceBuilder) -> InvoiceBuilder {
+	orig.clone()
+}
+
+/// Creates a new InvoiceBuilder for an invoice on the given network, for the given payment hash
+/// and secret, with a description to show the payer.
+///
+/// The invoice's timestamp defaults to the time it is built, and its `min_final_cltv_expiry` to
+/// `MIN_FINAL_CLTV_EXPIRY`, the lowest LDK accepts for received payments. It has no amount,
+/// leaving it up to the payer, and the default expiry time of one hour.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_new(mut currency: Currency, mut payment_hash: ThirtyTwoBytes, mut payment_secret: ThirtyTwoBytes, mut description: Str) -> InvoiceBuilder {
+	InvoiceBuilder { inner: ObjOps::heap_alloc(nativeInvoiceBuilderFields {
+		currency: currency.into_native(),
+		payment_hash: payment_hash.data,
+		payment_secret: payment_secret.data,
+		description: InvoiceDescription::Direct(description.into_string()),
+		amount_msat: None,
+		timestamp: None,
+		expiry_time_secs: None,
+		min_final_cltv_expiry: MIN_FINAL_CLTV_EXPIRY as u64,
+		payee_pub_key: None,
+		fallbacks: Vec::new(),
+		route_hints: Vec::new(),
+		basic_mpp: false,
+		features: None,
+	}), is_owned: true }
+}
+
+/// Sets the description shown to the payer, replacing any description or description hash.
+///
+/// Descriptions longer than 639 bytes fail to build with `CreationError::DescriptionTooLong`.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_description(this_arg: &mut InvoiceBuilder, mut description: Str) {
+	this_arg.get_native_mut_ref().description = InvoiceDescription::Direct(description.into_string());
+}
+
+/// Sets the SHA-256 hash of a description too long to include in the invoice, replacing any
+/// description or description hash.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_description_hash(this_arg: &mut InvoiceBuilder, mut description_hash: ThirtyTwoBytes) {
+	this_arg.get_native_mut_ref().description = InvoiceDescription::Hash(description_hash.data);
+}
+
+/// Sets the amount requested, in millisatoshis.
+///
+/// Amounts greater than the total bitcoin supply fail to build with
+/// `CreationError::InvalidAmount`.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_amount_milli_satoshis(this_arg: &mut InvoiceBuilder, mut amount_msat: u64) {
+	this_arg.get_native_mut_ref().amount_msat = Some(amount_msat);
+}
+
+/// Sets the creation time of the invoice, in seconds since the UNIX epoch, instead of the time
+/// it is built.
+///
+/// Timestamps which cannot be encoded fail to build with `CreationError::TimestampOutOfBounds`.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_timestamp(this_arg: &mut InvoiceBuilder, mut timestamp: u64) {
+	this_arg.get_native_mut_ref().timestamp = Some(timestamp);
+}
+
+/// Sets the number of seconds after its timestamp the invoice expires.
+///
+/// Expiry times which cannot be added to the timestamp fail to build with
+/// `CreationError::ExpiryTimeOutOfBounds`.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_expiry_time(this_arg: &mut InvoiceBuilder, mut expiry_time_secs: u64) {
+	this_arg.get_native_mut_ref().expiry_time_secs = Some(expiry_time_secs);
+}
+
+/// Sets the minimum number of blocks the payment's final HTLC must have before expiring.
+///
+/// Note that LDK fails received HTLCs expiring sooner than `MIN_FINAL_CLTV_EXPIRY` blocks after
+/// the current height, whatever the invoice says.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_min_final_cltv_expiry(this_arg: &mut InvoiceBuilder, mut min_final_cltv_expiry: u64) {
+	this_arg.get_native_mut_ref().min_final_cltv_expiry = min_final_cltv_expiry;
+}
+
+/// Includes the given node id in the invoice, which is otherwise recovered from its signature.
+///
+/// When set, building fails with `SignOrCreationError::SignError` if the invoice is not signed
+/// by the given key.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_payee_pub_key(this_arg: &mut InvoiceBuilder, mut payee_pub_key: PublicKey) {
+	this_arg.get_native_mut_ref().payee_pub_key = Some(payee_pub_key.into_rust());
+}
+
+/// Adds an on-chain address the payer may fall back to if the payment cannot be made over
+/// lightning.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_add_fallback(this_arg: &mut InvoiceBuilder, mut fallback: Fallback) {
+	this_arg.get_native_mut_ref().fallbacks.push(fallback.into_native());
+}
+
+/// Adds a route hint, eg through one of our private channels, for the payer to route through.
+///
+/// Route hints with too many hops to encode fail to build with `CreationError::RouteTooLong`.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_add_route_hint(this_arg: &mut InvoiceBuilder, mut route_hint: RouteHint) {
+	this_arg.get_native_mut_ref().route_hints.push(*unsafe { Box::from_raw(route_hint.take_inner()) });
+}
+
+/// Sets the `basic_mpp` feature, allowing the payer to split the payment over multiple paths.
+///
+/// The `payment_secret` and `var_onion_optin` features, which every invoice built by LDK
+/// requires, are always set.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_basic_mpp(this_arg: &mut InvoiceBuilder) {
+	this_arg.get_native_mut_ref().basic_mpp = true;
+}
+
+/// Sets the given features in the invoice, in addition to `basic_mpp` if
+/// `InvoiceBuilder_set_basic_mpp` was called, replacing any features set before.
+///
+/// The `payment_secret` and `var_onion_optin` features are set as required whatever `features`
+/// says. Features unknown to LDK must only be set as optional, as invoices requiring them are
+/// invalid and fail to build with `SignOrCreationError::SignError`.
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_set_features(this_arg: &mut InvoiceBuilder, mut features: InvoiceFeatures) {
+	this_arg.get_native_mut_ref().features = Some(*unsafe { Box::from_raw(features.take_inner()) });
+}
+
+/// Builds the invoice and has it signed by `signer`.
+///
+/// Returns a `CreationError` if any field holds an invalid value, or `SignError` if `signer`
+/// fails, if a payee public key was set and `signer` signs with a different key, or if features
+/// unknown to LDK were set as required. The builder is left
+/// unchanged and may be built again.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn InvoiceBuilder_build_signed(this_arg: &InvoiceBuilder, signer: &InvoiceSigner) -> CResult_InvoiceSignOrCreationErrorZ {
+	match this_arg.get_native_ref().build_signed(signer) {
+		Ok(invoice) => CResultTempl::ok(Invoice { inner: ObjOps::heap_alloc(invoice), is_owned: true }).into(),
+		Err(e) => CResultTempl::err(SignOrCreationError::native_into(e)).into(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use bitcoin::secp256k1::Secp256k1;
+
+	use lightning::chain::keysinterface::KeysInterface as _;
+	use lightning::routing::network_graph::RoutingFees;
+	use lightning::routing::router::RouteHintHop;
+	use lightning_invoice::InvoiceDescription as nativeInvoiceDescription;
+
+	use crate::c_types::derived::CResult_RecoverableSignatureNoneZ_err;
+	use crate::extensions::test_utils::keys_manager;
+
+	fn node_id(seed: u8) -> SecpPublicKey {
+		SecpPublicKey::from_secret_key(&Secp256k1::new(), &keys_manager(seed).get_node_secret())
+	}
+
+	fn signer(seed: u8) -> InvoiceSigner {
+		InvoiceSigner_from_keys_interface(KeysInterface::from(keys_manager(seed)))
+	}
+
+	fn builder() -> InvoiceBuilder {
+		InvoiceBuilder_new(Currency::Regtest, ThirtyTwoBytes { data: [1; 32] }, ThirtyTwoBytes { data: [2; 32] }, "coffee".into())
+	}
+
+	fn build(builder: &InvoiceBuilder, signer: &InvoiceSigner) -> Result<lightning_invoice::Invoice, nativeSignOrCreationError> {
+		builder.get_native_ref().build_signed(signer)
+	}
+
+	fn creation_error(builder: &InvoiceBuilder) -> nativeCreationError {
+		match build(builder, &signer(1)) {
+			Err(nativeSignOrCreationError::CreationError(e)) => e,
+			res => panic!("Expected a creation error, got {:?}", res),
+		}
+	}
+
+	fn route_hint(hops: u64) -> nativeRouteHint {
+		nativeRouteHint((0..hops).map(|short_channel_id| RouteHintHop {
+			src_node_id: node_id(9), short_channel_id, fees: RoutingFees { base_msat: 1000, proportional_millionths: 100 },
+			cltv_expiry_delta: 144, htlc_minimum_msat: None, htlc_maximum_msat: None,
+		}).collect())
+	}
+
+	#[test]
+	fn builds_invoice_signed_by_keys_interface() {
+		let mut builder = builder();
+		InvoiceBuilder_set_amount_milli_satoshis(&mut builder, 42_000);
+		InvoiceBuilder_set_timestamp(&mut builder, 1_600_000_000);
+		InvoiceBuilder_set_expiry_time(&mut builder, 600);
+		let invoice = build(&builder, &signer(1)).unwrap();
+		assert!(invoice.check_signature().is_ok());
+		assert_eq!(invoice.recover_payee_pub_key(), node_id(1));
+		assert_eq!(invoice.payment_hash().into_inner(), [1; 32]);
+		assert_eq!(invoice.payment_secret().0, [2; 32]);
+		assert_eq!(invoice.amount_milli_satoshis(), Some(42_000));
+		assert_eq!(invoice.timestamp().duration_since(UNIX_EPOCH).unwrap(), Duration::from_secs(1_600_000_000));
+		assert_eq!(invoice.expiry_time(), Duration::from_secs(600));
+		assert_eq!(invoice.min_final_cltv_expiry(), MIN_FINAL_CLTV_EXPIRY as u64);
+
+		// The invoice survives being encoded and parsed.
+		assert_eq!(invoice.to_string().parse::<lightning_invoice::Invoice>().unwrap(), invoice);
+	}
+
+	#[test]
+	fn sets_description_or_description_hash() {
+		let mut builder = builder();
+		match build(&builder, &signer(1)).unwrap().description() {
+			nativeInvoiceDescription::Direct(description) => assert_eq!(description.clone().into_inner(), "coffee"),
+			nativeInvoiceDescription::Hash(_) => panic!("Expected a description"),
+		}
+		InvoiceBuilder_set_description_hash(&mut builder, ThirtyTwoBytes { data: [7; 32] });
+		match build(&builder, &signer(1)).unwrap().description() {
+			nativeInvoiceDescription::Hash(hash) => assert_eq!(hash.0.into_inner(), [7; 32]),
+			nativeInvoiceDescription::Direct(_) => panic!("Expected a description hash"),
+		}
+		InvoiceBuilder_set_description(&mut builder, "tea".into());
+		match build(&builder, &signer(1)).unwrap().description() {
+			nativeInvoiceDescription::Direct(description) => assert_eq!(description.clone().into_inner(), "tea"),
+			nativeInvoiceDescription::Hash(_) => panic!("Expected a description"),
+		}
+	}
+
+	#[test]
+	fn invalid_fields_give_creation_errors() {
+		let mut builder = builder();
+		InvoiceBuilder_set_description(&mut builder, "a".repeat(640).into());
+		assert_eq!(creation_error(&builder), nativeCreationError::DescriptionTooLong);
+		InvoiceBuilder_set_description(&mut builder, "a".repeat(639).into());
+		assert!(build(&builder, &signer(1)).is_ok());
+
+		let mut builder = self::builder();
+		InvoiceBuilder_set_amount_milli_satoshis(&mut builder, u64::MAX);
+		assert_eq!(creation_error(&builder), nativeCreationError::InvalidAmount);
+
+		let mut builder = self::builder();
+		InvoiceBuilder_set_timestamp(&mut builder, u64::MAX);
+		assert_eq!(creation_error(&builder), nativeCreationError::TimestampOutOfBounds);
+		InvoiceBuilder_set_timestamp(&mut builder, 1 << 35);
+		assert_eq!(creation_error(&builder), nativeCreationError::TimestampOutOfBounds);
+
+		let mut builder = self::builder();
+		InvoiceBuilder_set_expiry_time(&mut builder, u64::MAX);
+		assert_eq!(creation_error(&builder), nativeCreationError::ExpiryTimeOutOfBounds);
+
+		let mut builder = self::builder();
+		InvoiceBuilder_add_route_hint(&mut builder, RouteHint { inner: ObjOps::heap_alloc(route_hint(13)), is_owned: true });
+		assert_eq!(creation_error(&builder), nativeCreationError::RouteTooLong);
+	}
+
+	#[test]
+	fn includes_route_hints() {
+		let mut builder = builder();
+		InvoiceBuilder_add_route_hint(&mut builder, RouteHint { inner: ObjOps::heap_alloc(route_hint(1)), is_owned: true });
+		InvoiceBuilder_add_route_hint(&mut builder, RouteHint { inner: ObjOps::heap_alloc(route_hint(2)), is_owned: true });
+		assert_eq!(build(&builder, &signer(1)).unwrap().route_hints(), vec![route_hint(1), route_hint(2)]);
+	}
+
+	#[test]
+	fn sets_features() {
+		let mut builder = builder();
+		let invoice = build(&builder, &signer(1)).unwrap();
+		assert!(invoice.features().unwrap().supports_payment_secret());
+		assert!(!invoice.features().unwrap().supports_basic_mpp());
+
+		InvoiceBuilder_set_features(&mut builder, InvoiceFeatures { inner: ObjOps::heap_alloc(nativeInvoiceFeatures::empty()), is_owned: true });
+		let invoice = build(&builder, &signer(1)).unwrap();
+		assert!(invoice.features().unwrap().supports_payment_secret());
+
+		InvoiceBuilder_set_features(&mut builder, InvoiceFeatures { inner: ObjOps::heap_alloc(nativeInvoiceFeatures::known()), is_owned: true });
+		let invoice = build(&builder, &signer(1)).unwrap();
+		assert!(invoice.features().unwrap().supports_payment_secret());
+		assert!(invoice.features().unwrap().supports_basic_mpp());
+		assert!(invoice.to_string().parse::<lightning_invoice::Invoice>().is_ok());
+	}
+
+	extern "C" fn refuse_to_sign(_this_arg: *const c_void, _invoice_preimage: CVec_u8Z) -> CResult_RecoverableSignatureNoneZ {
+		CResult_RecoverableSignatureNoneZ_err()
+	}
+
+	#[test]
+	fn signing_failures_give_sign_errors() {
+		let refusing_signer = InvoiceSigner { this_arg: std::ptr::null_mut(), sign_invoice: refuse_to_sign, free: None };
+		assert_eq!(build(&builder(), &refusing_signer).unwrap_err(), nativeSignOrCreationError::SignError(()));
+
+		// A payee key other than the signer's is rejected, and the builder may be fixed and reused.
+		let mut builder = builder();
+		InvoiceBuilder_set_payee_pub_key(&mut builder, PublicKey::from_rust(&node_id(2)));
+		assert_eq!(build(&builder, &signer(1)).unwrap_err(), nativeSignOrCreationError::SignError(()));
+		let invoice = build(&builder, &signer(2)).unwrap();
+		assert_eq!(invoice.payee_pub_key(), Some(&node_id(2)));
+		InvoiceBuilder_set_payee_pub_key(&mut builder, PublicKey::from_rust(&node_id(1)));
+		assert!(build(&builder, &signer(1)).is_ok());
+
+		let res = InvoiceBuilder_build_signed(&builder, &refusing_signer);
+		assert!(!res.result_ok);
+	}
+}
@@ -0,0 +1,591 @@
+//! A generic key-value store interface and adapters which persist LDK objects on top of it.
+//!
+//! Implementing [`KVStore`] is all that is required to store `ChannelMonitor`s, the
+//! `ChannelManager`, the `NetworkGraph` and the `Scorer` in an arbitrary storage backend, with
+//! [`KVStorePersister`] taking care of the serialization and key layout.
+
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::str::FromStr;
+
+use bitcoin::hash_types::{BlockHash, Txid};
+use bitcoin::hashes::Hash;
+use bitcoin::hashes::hex::{FromHex, ToHex};
+
+use lightning::chain::ChannelMonitorUpdateErr;
+use lightning::chain::chainmonitor::MonitorUpdateId;
+use lightning::chain::channelmonitor::{ChannelMonitor as RustChannelMonitor, ChannelMonitorUpdate as RustChannelMonitorUpdate};
+use lightning::chain::transaction::OutPoint as RustOutPoint;
+use lightning::ln::channelmanager::ChannelManager as RustChannelManager;
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, ReadableArgs, Writeable};
+
+use crate::c_types::*;
+use crate::c_types::derived::{CResult_NoneErrorZ, CVec_u8Z};
+use crate::lightning::chain::Watch;
+use crate::lightning::chain::chaininterface::{BroadcasterInterface, FeeEstimator};
+use crate::lightning::chain::keysinterface::{KeysInterface, Sign};
+use crate::lightning::util::logger::Logger;
+
+/// The namespace under which `ChannelMonitor`s are stored, keyed by their funding outpoint.
+pub(crate) const CHANNEL_MONITOR_NAMESPACE: &str = "monitors";
+/// The namespace under which single-object keys (see below) are stored.
+pub(crate) const NODE_NAMESPACE: &str = "";
+/// The key under which the `ChannelManager` is stored.
+pub(crate) const CHANNEL_MANAGER_KEY: &str = "manager";
+/// The key under which the `NetworkGraph` is stored.
+pub(crate) const NETWORK_GRAPH_KEY: &str = "network_graph";
+/// The key under which the `Scorer` is stored.
+pub(crate) const SCORER_KEY: &str = "scorer";
+
+/// The key a `ChannelMonitor` is stored under, matching the file names of `FilesystemPersister`.
+pub(crate) fn monitor_key(funding_txo: &RustOutPoint) -> String {
+	format!("{}_{}", funding_txo.txid.to_hex(), funding_txo.index)
+}
+/// Parses a key generated by [`monitor_key`].
+pub(crate) fn parse_monitor_key(key: &str) -> Option<RustOutPoint> {
+	let mut parts = key.splitn(2, '_');
+	let txid = Txid::from_hex(parts.next()?).ok()?;
+	let index = u16::from_str(parts.next()?).ok()?;
+	Some(RustOutPoint { txid, index })
+}
+
+#[repr(C)]
+/// The contents of CResult_CVec_u8ZIOErrorZ
+pub union CResult_CVec_u8ZIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut CVec_u8Z,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_CVec_u8ZIOErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::derived::CVec_u8Z on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_CVec_u8ZIOErrorZ {
+	/// The contents of this CResult_CVec_u8ZIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_CVec_u8ZIOErrorZPtr,
+	/// Whether this CResult_CVec_u8ZIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_CVec_u8ZIOErrorZ in the success state.
+pub extern "C" fn CResult_CVec_u8ZIOErrorZ_ok(o: CVec_u8Z) -> CResult_CVec_u8ZIOErrorZ {
+	CResult_CVec_u8ZIOErrorZ {
+		contents: CResult_CVec_u8ZIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_CVec_u8ZIOErrorZ in the error state.
+pub extern "C" fn CResult_CVec_u8ZIOErrorZ_err(e: IOError) -> CResult_CVec_u8ZIOErrorZ {
+	CResult_CVec_u8ZIOErrorZ {
+		contents: CResult_CVec_u8ZIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_CVec_u8ZIOErrorZ_is_ok(o: &CResult_CVec_u8ZIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_CVec_u8ZIOErrorZ.
+pub extern "C" fn CResult_CVec_u8ZIOErrorZ_free(_res: CResult_CVec_u8ZIOErrorZ) { }
+impl Drop for CResult_CVec_u8ZIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_CVec_u8ZIOErrorZ {
+	pub(crate) fn from_rust(res: Result<Vec<u8>, std::io::Error>) -> Self {
+		match res {
+			Ok(o) => CResult_CVec_u8ZIOErrorZ_ok(o.into()),
+			Err(e) => CResult_CVec_u8ZIOErrorZ_err(IOError::from_rust(e)),
+		}
+	}
+	pub(crate) fn into_rust(mut self) -> Result<Vec<u8>, std::io::Error> {
+		if self.result_ok {
+			Ok((*unsafe { Box::from_raw(<*mut _>::take_ptr(&mut self.contents.result)) }).into_rust())
+		} else {
+			Err((*unsafe { Box::from_raw(<*mut _>::take_ptr(&mut self.contents.err)) }).to_rust())
+		}
+	}
+}
+
+#[repr(C)]
+/// A dynamically-allocated array of crate::c_types::Strs of arbitrary size.
+/// This corresponds to std::vector in C++
+pub struct CVec_StrZ {
+	/// The elements in the array.
+	/// If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
+	pub data: *mut Str,
+	/// The number of elements pointed to by `data`.
+	pub datalen: usize
+}
+impl CVec_StrZ {
+	#[allow(unused)] pub(crate) fn into_rust(&mut self) -> Vec<Str> {
+		if self.datalen == 0 { return Vec::new(); }
+		let ret = unsafe { Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.datalen)) }.into();
+		self.data = std::ptr::null_mut();
+		self.datalen = 0;
+		ret
+	}
+}
+impl From<Vec<Str>> for CVec_StrZ {
+	fn from(v: Vec<Str>) -> Self {
+		let datalen = v.len();
+		let data = Box::into_raw(v.into_boxed_slice());
+		Self { datalen, data: unsafe { (*data).as_mut_ptr() } }
+	}
+}
+#[no_mangle]
+/// Frees the buffer pointed to by `data` if `datalen` is non-0.
+pub extern "C" fn CVec_StrZ_free(_res: CVec_StrZ) { }
+impl Drop for CVec_StrZ {
+	fn drop(&mut self) {
+		if self.datalen == 0 { return; }
+		unsafe { Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.datalen)) };
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_CVec_StrZIOErrorZ
+pub union CResult_CVec_StrZIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut CVec_StrZ,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_CVec_StrZIOErrorZ represents the result of a fallible operation,
+/// containing a CVec_StrZ on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_CVec_StrZIOErrorZ {
+	/// The contents of this CResult_CVec_StrZIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_CVec_StrZIOErrorZPtr,
+	/// Whether this CResult_CVec_StrZIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_CVec_StrZIOErrorZ in the success state.
+pub extern "C" fn CResult_CVec_StrZIOErrorZ_ok(o: CVec_StrZ) -> CResult_CVec_StrZIOErrorZ {
+	CResult_CVec_StrZIOErrorZ {
+		contents: CResult_CVec_StrZIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_CVec_StrZIOErrorZ in the error state.
+pub extern "C" fn CResult_CVec_StrZIOErrorZ_err(e: IOError) -> CResult_CVec_StrZIOErrorZ {
+	CResult_CVec_StrZIOErrorZ {
+		contents: CResult_CVec_StrZIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_CVec_StrZIOErrorZ_is_ok(o: &CResult_CVec_StrZIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_CVec_StrZIOErrorZ.
+pub extern "C" fn CResult_CVec_StrZIOErrorZ_free(_res: CResult_CVec_StrZIOErrorZ) { }
+impl Drop for CResult_CVec_StrZIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_CVec_StrZIOErrorZ {
+	pub(crate) fn from_rust(res: Result<Vec<String>, std::io::Error>) -> Self {
+		match res {
+			Ok(mut o) => {
+				let keys: Vec<Str> = o.drain(..).map(|key| key.into()).collect();
+				CResult_CVec_StrZIOErrorZ_ok(keys.into())
+			},
+			Err(e) => CResult_CVec_StrZIOErrorZ_err(IOError::from_rust(e)),
+		}
+	}
+	pub(crate) fn into_rust(mut self) -> Result<Vec<String>, std::io::Error> {
+		if self.result_ok {
+			let mut keys = (*unsafe { Box::from_raw(<*mut _>::take_ptr(&mut self.contents.result)) }).into_rust();
+			Ok(keys.drain(..).map(|key| key.into_string()).collect())
+		} else {
+			Err((*unsafe { Box::from_raw(<*mut _>::take_ptr(&mut self.contents.err)) }).to_rust())
+		}
+	}
+}
+
+pub(crate) fn none_result_from_rust(res: Result<(), std::io::Error>) -> CResult_NoneErrorZ {
+	match res {
+		Ok(()) => crate::c_types::derived::CResult_NoneErrorZ_ok(),
+		Err(e) => crate::c_types::derived::CResult_NoneErrorZ_err(IOError::from_rust(e)),
+	}
+}
+pub(crate) fn none_result_into_rust(mut res: CResult_NoneErrorZ) -> Result<(), std::io::Error> {
+	if res.result_ok {
+		Ok(())
+	} else {
+		Err((*unsafe { Box::from_raw(<*mut _>::take_ptr(&mut res.contents.err)) }).to_rust())
+	}
+}
+
+/// A simple namespaced key-value store.
+///
+/// Keys are grouped into namespaces, each of which can be listed independently. Both namespaces
+/// and keys are UTF-8 strings containing only ASCII alphanumerics, `_` and `-`, though the
+/// namespace may also be the empty string.
+///
+/// All `Str`s and `u8slice`s passed to the store are only valid for the duration of the call.
+#[repr(C)]
+pub struct KVStore {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Returns the value stored under `key` in `key_namespace`.
+	///
+	/// If no value is stored under the key, an `IOError_NotFound` must be returned.
+	#[must_use]
+	pub read: extern "C" fn (this_arg: *const c_void, key_namespace: Str, key: Str) -> CResult_CVec_u8ZIOErrorZ,
+	/// Stores `value` under `key` in `key_namespace`, replacing any existing value.
+	///
+	/// The write must be atomic, i.e. a subsequent (or concurrent) `read` must either return the
+	/// previous value or the new one in its entirety, even if the process crashes during the
+	/// write. It must also be durable before returning success.
+	#[must_use]
+	pub write: extern "C" fn (this_arg: *const c_void, key_namespace: Str, key: Str, value: u8slice) -> CResult_NoneErrorZ,
+	/// Removes the value stored under `key` in `key_namespace`.
+	///
+	/// Removing a key which does not exist must succeed.
+	#[must_use]
+	pub remove: extern "C" fn (this_arg: *const c_void, key_namespace: Str, key: Str) -> CResult_NoneErrorZ,
+	/// Lists all keys which currently have a value stored in `key_namespace`, in any order.
+	#[must_use]
+	pub list: extern "C" fn (this_arg: *const c_void, key_namespace: Str) -> CResult_CVec_StrZIOErrorZ,
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for KVStore {}
+unsafe impl Sync for KVStore {}
+#[no_mangle]
+pub(crate) extern "C" fn KVStore_clone_fields(orig: &KVStore) -> KVStore {
+	KVStore {
+		this_arg: orig.this_arg,
+		read: Clone::clone(&orig.read),
+		write: Clone::clone(&orig.write),
+		remove: Clone::clone(&orig.remove),
+		list: Clone::clone(&orig.list),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Builds a non-owning `Str` which is only valid as long as `s` is.
+fn borrowed_str(s: &str) -> Str {
+	Str { chars: s.as_ptr(), len: s.len(), chars_is_owned: false }
+}
+impl KVStore {
+	pub(crate) fn read(&self, namespace: &str, key: &str) -> Result<Vec<u8>, std::io::Error> {
+		(self.read)(self.this_arg, borrowed_str(namespace), borrowed_str(key)).into_rust()
+	}
+	pub(crate) fn write(&self, namespace: &str, key: &str, value: &[u8]) -> Result<(), std::io::Error> {
+		none_result_into_rust((self.write)(self.this_arg, borrowed_str(namespace), borrowed_str(key), u8slice::from_slice(value)))
+	}
+	pub(crate) fn remove(&self, namespace: &str, key: &str) -> Result<(), std::io::Error> {
+		none_result_into_rust((self.remove)(self.this_arg, borrowed_str(namespace), borrowed_str(key)))
+	}
+	pub(crate) fn list(&self, namespace: &str) -> Result<Vec<String>, std::io::Error> {
+		(self.list)(self.this_arg, borrowed_str(namespace)).into_rust()
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn KVStore_free(this_ptr: KVStore) { }
+impl Drop for KVStore {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+/// Reads all full `ChannelMonitor`s stored in the [`CHANNEL_MONITOR_NAMESPACE`] of `store`.
+pub(crate) fn read_channelmonitors<K: std::ops::Deref>(store: &KVStore, keys_manager: K)
+-> Result<Vec<(BlockHash, RustChannelMonitor<Sign>)>, std::io::Error>
+where K::Target: lightning::chain::keysinterface::KeysInterface<Signer=Sign> + Sized {
+	let mut res = Vec::new();
+	for key in store.list(CHANNEL_MONITOR_NAMESPACE)? {
+		let funding_txo = parse_monitor_key(&key).ok_or_else(|| std::io::Error::new(
+			std::io::ErrorKind::InvalidData, "Invalid ChannelMonitor key"))?;
+		let data = store.read(CHANNEL_MONITOR_NAMESPACE, &key)?;
+		let read_res: Result<(BlockHash, RustChannelMonitor<Sign>), DecodeError> = ReadableArgs::read(&mut Cursor::new(&data), &*keys_manager);
+		let (blockhash, monitor) = read_res.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Failed to read ChannelMonitor: {:?}", e)))?;
+		if monitor.get_funding_txo().0 != funding_txo {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "ChannelMonitor was stored under the wrong key"));
+		}
+		res.push((blockhash, monitor));
+	}
+	Ok(res)
+}
+
+pub(crate) struct nativeKVStorePersister {
+	store: KVStore,
+}
+
+/// Persists `ChannelMonitor`s, the `ChannelManager`, the `NetworkGraph` and the `Scorer` in a
+/// [`KVStore`].
+///
+/// `ChannelMonitor`s are stored in the `"monitors"` namespace, keyed by their funding outpoint
+/// as `<txid>_<output index>`. The remaining objects are stored in the `""` namespace under the
+/// keys `"manager"`, `"network_graph"` and `"scorer"`.
+#[must_use]
+#[repr(C)]
+pub struct KVStorePersister {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeKVStorePersister,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for KVStorePersister {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeKVStorePersister>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the KVStorePersister, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn KVStorePersister_free(this_obj: KVStorePersister) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn KVStorePersister_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeKVStorePersister); }
+}
+#[allow(unused)]
+impl KVStorePersister {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeKVStorePersister {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeKVStorePersister {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeKVStorePersister {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+impl nativeKVStorePersister {
+	pub(crate) fn new(store: KVStore) -> Self {
+		Self { store }
+	}
+
+	pub(crate) fn read_channelmonitors<K: std::ops::Deref>(&self, keys_manager: K)
+	-> Result<Vec<(BlockHash, RustChannelMonitor<Sign>)>, std::io::Error>
+	where K::Target: lightning::chain::keysinterface::KeysInterface<Signer=Sign> + Sized {
+		read_channelmonitors(&self.store, keys_manager)
+	}
+
+	/// Reads the serialized `ChannelManager`, failing with `ErrorKind::NotFound` if none was
+	/// stored yet.
+	pub(crate) fn read_channel_manager_data(&self) -> Result<Vec<u8>, std::io::Error> {
+		self.store.read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY)
+	}
+
+	fn read_object<T: Readable>(&self, key: &str) -> Result<T, DecodeError> {
+		let data = self.store.read(NODE_NAMESPACE, key).map_err(|e| DecodeError::Io(e.kind()))?;
+		T::read(&mut Cursor::new(&data))
+	}
+}
+
+impl lightning::chain::chainmonitor::Persist<Sign> for nativeKVStorePersister {
+	fn persist_new_channel(&self, funding_txo: RustOutPoint, monitor: &RustChannelMonitor<Sign>, _update_id: MonitorUpdateId) -> Result<(), ChannelMonitorUpdateErr> {
+		self.store.write(CHANNEL_MONITOR_NAMESPACE, &monitor_key(&funding_txo), &monitor.encode())
+			.map_err(|_| ChannelMonitorUpdateErr::PermanentFailure)
+	}
+
+	fn update_persisted_channel(&self, funding_txo: RustOutPoint, _update: &Option<RustChannelMonitorUpdate>, monitor: &RustChannelMonitor<Sign>, _update_id: MonitorUpdateId) -> Result<(), ChannelMonitorUpdateErr> {
+		self.store.write(CHANNEL_MONITOR_NAMESPACE, &monitor_key(&funding_txo), &monitor.encode())
+			.map_err(|_| ChannelMonitorUpdateErr::PermanentFailure)
+	}
+}
+
+impl lightning_background_processor::ChannelManagerPersister<Sign, Watch, BroadcasterInterface, KeysInterface, FeeEstimator, Logger> for nativeKVStorePersister {
+	fn persist_manager(&self, channel_manager: &RustChannelManager<Sign, Watch, BroadcasterInterface, KeysInterface, FeeEstimator, Logger>) -> Result<(), std::io::Error> {
+		self.store.write(NODE_NAMESPACE, CHANNEL_MANAGER_KEY, &channel_manager.encode())
+	}
+}
+
+/// Creates a new KVStorePersister which stores everything in the given `store`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_new(mut store: KVStore) -> KVStorePersister {
+	KVStorePersister { inner: ObjOps::heap_alloc(nativeKVStorePersister::new(store)), is_owned: true }
+}
+
+/// Read all `ChannelMonitor`s from the store.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_read_channelmonitors(this_arg: &KVStorePersister, mut keys_manager: KeysInterface) -> crate::c_types::derived::CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ {
+	let mut ret = this_arg.get_native_ref().read_channelmonitors(&keys_manager);
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { let mut local_ret_0 = Vec::new(); for mut item in o.drain(..) { local_ret_0.push( { let (mut orig_ret_0_0_0, mut orig_ret_0_0_1) = item; let mut local_ret_0_0 = (crate::c_types::ThirtyTwoBytes { data: orig_ret_0_0_0.into_inner() }, crate::lightning::chain::channelmonitor::ChannelMonitor { inner: ObjOps::heap_alloc(orig_ret_0_0_1), is_owned: true }).into(); local_ret_0_0 }); }; local_ret_0.into() }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::c_types::IOError::from_rust(e) }).into() };
+	local_ret
+}
+
+/// Read the `ChannelManager` from the store. `arg` must reference all `ChannelMonitor`s, as
+/// returned by [`KVStorePersister_read_channelmonitors`].
+///
+/// If no `ChannelManager` has been stored yet, a `DecodeError` wrapping an `IOError_NotFound`
+/// is returned.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_read_channel_manager(this_arg: &KVStorePersister, arg: crate::lightning::ln::channelmanager::ChannelManagerReadArgs) -> crate::c_types::derived::CResult_C2Tuple_BlockHashChannelManagerZDecodeErrorZ {
+	let arg_conv = *unsafe { Box::from_raw(arg.take_inner()) };
+	let res: Result<(BlockHash, RustChannelManager<Sign, Watch, BroadcasterInterface, KeysInterface, FeeEstimator, Logger>), DecodeError> =
+		match this_arg.get_native_ref().store.read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY) {
+			Ok(data) => ReadableArgs::read(&mut Cursor::new(&data), arg_conv),
+			Err(e) => Err(DecodeError::Io(e.kind())),
+		};
+	let mut local_res = match res { Ok(mut o) => crate::c_types::CResultTempl::ok( { let (mut orig_res_0_0, mut orig_res_0_1) = o; let mut local_res_0 = (crate::c_types::ThirtyTwoBytes { data: orig_res_0_0.into_inner() }, crate::lightning::ln::channelmanager::ChannelManager { inner: ObjOps::heap_alloc(orig_res_0_1), is_owned: true }).into(); local_res_0 }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::ln::msgs::DecodeError { inner: ObjOps::heap_alloc(e), is_owned: true } }).into() };
+	local_res
+}
+
+/// Writes the given `NetworkGraph` to the store.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_persist_network_graph(this_arg: &KVStorePersister, network_graph: &crate::lightning::routing::network_graph::NetworkGraph) -> CResult_NoneErrorZ {
+	none_result_from_rust(this_arg.get_native_ref().store.write(NODE_NAMESPACE, NETWORK_GRAPH_KEY, &network_graph.get_native_ref().encode()))
+}
+
+/// Read the `NetworkGraph` from the store.
+///
+/// If no `NetworkGraph` has been stored yet, a `DecodeError` wrapping an `IOError_NotFound` is
+/// returned.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_read_network_graph(this_arg: &KVStorePersister) -> crate::c_types::derived::CResult_NetworkGraphDecodeErrorZ {
+	let res: Result<lightning::routing::network_graph::NetworkGraph, DecodeError> = this_arg.get_native_ref().read_object(NETWORK_GRAPH_KEY);
+	let mut local_res = match res { Ok(mut o) => crate::c_types::CResultTempl::ok( { crate::lightning::routing::network_graph::NetworkGraph { inner: ObjOps::heap_alloc(o), is_owned: true } }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::ln::msgs::DecodeError { inner: ObjOps::heap_alloc(e), is_owned: true } }).into() };
+	local_res
+}
+
+/// Writes the given `Scorer` to the store.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_persist_scorer(this_arg: &KVStorePersister, scorer: &crate::lightning::routing::scoring::Scorer) -> CResult_NoneErrorZ {
+	none_result_from_rust(this_arg.get_native_ref().store.write(NODE_NAMESPACE, SCORER_KEY, &scorer.get_native_ref().encode()))
+}
+
+/// Read the `Scorer` from the store.
+///
+/// If no `Scorer` has been stored yet, a `DecodeError` wrapping an `IOError_NotFound` is
+/// returned.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_read_scorer(this_arg: &KVStorePersister) -> crate::c_types::derived::CResult_ScorerDecodeErrorZ {
+	let res: Result<lightning::routing::scoring::Scorer, DecodeError> = this_arg.get_native_ref().read_object(SCORER_KEY);
+	let mut local_res = match res { Ok(mut o) => crate::c_types::CResultTempl::ok( { crate::lightning::routing::scoring::Scorer { inner: ObjOps::heap_alloc(o), is_owned: true } }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::ln::msgs::DecodeError { inner: ObjOps::heap_alloc(e), is_owned: true } }).into() };
+	local_res
+}
+
+impl From<nativeKVStorePersister> for crate::lightning::chain::chainmonitor::Persist {
+	fn from(obj: nativeKVStorePersister) -> Self {
+		let mut rust_obj = KVStorePersister { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = KVStorePersister_as_Persist(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(KVStorePersister_free_void);
+		ret
+	}
+}
+/// Constructs a new Persist which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned Persist must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn KVStorePersister_as_Persist(this_arg: &KVStorePersister) -> crate::lightning::chain::chainmonitor::Persist {
+	crate::lightning::chain::chainmonitor::Persist {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		persist_new_channel: KVStorePersister_Persist_persist_new_channel,
+		update_persisted_channel: KVStorePersister_Persist_update_persisted_channel,
+	}
+}
+
+#[must_use]
+extern "C" fn KVStorePersister_Persist_persist_new_channel(this_arg: *const c_void, mut funding_txo: crate::lightning::chain::transaction::OutPoint, monitor: &crate::lightning::chain::channelmonitor::ChannelMonitor, mut _update_id: crate::lightning::chain::chainmonitor::MonitorUpdateId) -> crate::c_types::derived::CResult_NoneChannelMonitorUpdateErrZ {
+	let mut ret = <nativeKVStorePersister as lightning::chain::chainmonitor::Persist<_>>::persist_new_channel(unsafe { &mut *(this_arg as *mut nativeKVStorePersister) }, *unsafe { Box::from_raw(funding_txo.take_inner()) }, monitor.get_native_ref(), *unsafe { Box::from_raw(_update_id.take_inner()) });
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { () /*o*/ }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::chain::ChannelMonitorUpdateErr::native_into(e) }).into() };
+	local_ret
+}
+#[must_use]
+extern "C" fn KVStorePersister_Persist_update_persisted_channel(this_arg: *const c_void, mut funding_txo: crate::lightning::chain::transaction::OutPoint, _update: &crate::lightning::chain::channelmonitor::ChannelMonitorUpdate, monitor: &crate::lightning::chain::channelmonitor::ChannelMonitor, mut _update_id: crate::lightning::chain::chainmonitor::MonitorUpdateId) -> crate::c_types::derived::CResult_NoneChannelMonitorUpdateErrZ {
+	let mut local__update = if _update.inner.is_null() { None } else { Some((* { _update.get_native_ref() }).clone()) };
+	let mut ret = <nativeKVStorePersister as lightning::chain::chainmonitor::Persist<_>>::update_persisted_channel(unsafe { &mut *(this_arg as *mut nativeKVStorePersister) }, *unsafe { Box::from_raw(funding_txo.take_inner()) }, &local__update, monitor.get_native_ref(), *unsafe { Box::from_raw(_update_id.take_inner()) });
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { () /*o*/ }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::chain::ChannelMonitorUpdateErr::native_into(e) }).into() };
+	local_ret
+}
+
+impl From<nativeKVStorePersister> for crate::lightning_background_processor::ChannelManagerPersister {
+	fn from(obj: nativeKVStorePersister) -> Self {
+		let mut rust_obj = KVStorePersister { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = KVStorePersister_as_ChannelManagerPersister(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(KVStorePersister_free_void);
+		ret
+	}
+}
+/// Constructs a new ChannelManagerPersister which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned ChannelManagerPersister must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn KVStorePersister_as_ChannelManagerPersister(this_arg: &KVStorePersister) -> crate::lightning_background_processor::ChannelManagerPersister {
+	crate::lightning_background_processor::ChannelManagerPersister {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		persist_manager: KVStorePersister_ChannelManagerPersister_persist_manager,
+	}
+}
+
+#[must_use]
+extern "C" fn KVStorePersister_ChannelManagerPersister_persist_manager(this_arg: *const c_void, channel_manager: &crate::lightning::ln::channelmanager::ChannelManager) -> CResult_NoneErrorZ {
+	let mut ret = <nativeKVStorePersister as lightning_background_processor::ChannelManagerPersister<_, _, _, _, _, _>>::persist_manager(unsafe { &mut *(this_arg as *mut nativeKVStorePersister) }, channel_manager.get_native_ref());
+	none_result_from_rust(ret)
+}
@@ -0,0 +1,867 @@
+//! A socket driver which connects a `PeerManager` to TCP sockets, replacing `ldk-net`.
+//!
+//! All sockets are non-blocking and are handled by a single background thread built on Linux's
+//! `epoll`, which calls `PeerManager_read_event`, `PeerManager_write_buffer_space_avail` and
+//! `PeerManager_socket_disconnected` as sockets become ready or close, and
+//! `PeerManager_process_events` after each batch of socket events (and at least once a second).
+//! It also calls `PeerManager_timer_tick_occurred` every ten seconds, so there is no need to do so
+//! yourself.
+//!
+//! Each connection is reference-counted, and the [`SocketDescriptor`]s handed to the
+//! `PeerManager` hold a reference, so cloning a descriptor only increments a counter.
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
+
+use crate::c_types::*;
+use crate::extensions::policy::{ConnectionPolicy, TokenBucket, nativeConnectionPolicy};
+use crate::lightning::ln::peer_handler::{PeerManager, SocketDescriptor, nativePeerManager};
+
+/// How long the event loop may sleep before calling `PeerManager_process_events` again.
+const PROCESS_EVENTS_INTERVAL_MS: i32 = 1000;
+/// How often the event loop calls `PeerManager_timer_tick_occurred`, which pings peers and
+/// disconnects those which have not responded.
+const TIMER_TICK_INTERVAL: Duration = Duration::from_secs(10);
+/// How long the event loop may sleep while any connection is waiting for its read rate limit.
+const THROTTLE_INTERVAL_MS: i32 = 50;
+/// The epoll token used for the wakeup eventfd. Connection and listener ids start at 1.
+const WAKE_TOKEN: u64 = 0;
+const READ_BUF_LEN: usize = 16384;
+const MAX_EVENTS: usize = 64;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The reasons a socket driver operation can fail.
+pub enum LdkNetError {
+	/// The `epoll` instance, its wakeup `eventfd` or the event loop thread could not be created.
+	EventLoopSetupFailed,
+	/// The given address could not be parsed or did not resolve to any socket address.
+	InvalidAddress,
+	/// The connection was refused or otherwise failed to be established.
+	ConnectFailed,
+	/// The connection could not be established before the given timeout.
+	ConnectTimeout,
+	/// The listening socket could not be bound to the given address.
+	BindFailed,
+	/// A socket was connected or bound, but could not be configured or registered with the event
+	/// loop.
+	SocketSetupFailed,
+	/// The `PeerManager` refused the connection, e.g. because we are already connected to the
+	/// peer.
+	PeerRejected,
+	/// The driver has already been stopped.
+	Stopped,
+	/// The SOCKS5 proxy could not be reached.
+	ProxyUnreachable,
+	/// The SOCKS5 proxy did not accept our (lack of) credentials.
+	ProxyAuthFailed,
+	/// The SOCKS5 proxy sent an invalid or unexpected response.
+	ProxyProtocolError,
+}
+
+#[repr(C)]
+/// The contents of CResult_NoneLdkNetErrorZ
+pub union CResult_NoneLdkNetErrorZPtr {
+	/// Note that this value is always NULL, as there are no contents in the OK variant
+	pub result: *mut c_void,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut LdkNetError,
+}
+#[repr(C)]
+/// A CResult_NoneLdkNetErrorZ represents the result of a fallible operation,
+/// containing a () on success and a LdkNetError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_NoneLdkNetErrorZ {
+	/// The contents of this CResult_NoneLdkNetErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_NoneLdkNetErrorZPtr,
+	/// Whether this CResult_NoneLdkNetErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_NoneLdkNetErrorZ in the success state.
+pub extern "C" fn CResult_NoneLdkNetErrorZ_ok() -> CResult_NoneLdkNetErrorZ {
+	CResult_NoneLdkNetErrorZ {
+		contents: CResult_NoneLdkNetErrorZPtr {
+			result: std::ptr::null_mut(),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_NoneLdkNetErrorZ in the error state.
+pub extern "C" fn CResult_NoneLdkNetErrorZ_err(e: LdkNetError) -> CResult_NoneLdkNetErrorZ {
+	CResult_NoneLdkNetErrorZ {
+		contents: CResult_NoneLdkNetErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_NoneLdkNetErrorZ_is_ok(o: &CResult_NoneLdkNetErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_NoneLdkNetErrorZ.
+pub extern "C" fn CResult_NoneLdkNetErrorZ_free(_res: CResult_NoneLdkNetErrorZ) { }
+impl Drop for CResult_NoneLdkNetErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_NoneLdkNetErrorZ {
+	pub(crate) fn from_rust(res: Result<(), LdkNetError>) -> Self {
+		match res {
+			Ok(()) => CResult_NoneLdkNetErrorZ_ok(),
+			Err(e) => CResult_NoneLdkNetErrorZ_err(e),
+		}
+	}
+}
+
+/// An `epoll` instance and an `eventfd` which can be used to wake it, closed on drop.
+pub(crate) struct Poller {
+	epoll_fd: RawFd,
+	wake_fd: RawFd,
+}
+
+impl Poller {
+	fn new() -> Result<Self, std::io::Error> {
+		let epoll_fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+		if epoll_fd < 0 { return Err(std::io::Error::last_os_error()); }
+		let wake_fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+		if wake_fd < 0 {
+			let err = std::io::Error::last_os_error();
+			unsafe { libc::close(epoll_fd); }
+			return Err(err);
+		}
+		let poller = Poller { epoll_fd, wake_fd };
+		poller.ctl(libc::EPOLL_CTL_ADD, wake_fd, libc::EPOLLIN as u32, WAKE_TOKEN)?;
+		Ok(poller)
+	}
+
+	fn ctl(&self, op: libc::c_int, fd: RawFd, events: u32, token: u64) -> Result<(), std::io::Error> {
+		let mut event = libc::epoll_event { events, u64: token };
+		if unsafe { libc::epoll_ctl(self.epoll_fd, op, fd, &mut event) } < 0 {
+			return Err(std::io::Error::last_os_error());
+		}
+		Ok(())
+	}
+
+	fn wake(&self) {
+		let one: u64 = 1;
+		unsafe { libc::write(self.wake_fd, &one as *const u64 as *const c_void, 8); }
+	}
+
+	fn clear_wake(&self) {
+		let mut count: u64 = 0;
+		unsafe { libc::read(self.wake_fd, &mut count as *mut u64 as *mut c_void, 8); }
+	}
+
+	fn wait(&self, events: &mut [libc::epoll_event], timeout_ms: i32) -> usize {
+		let res = unsafe { libc::epoll_wait(self.epoll_fd, events.as_mut_ptr(), events.len() as libc::c_int, timeout_ms) };
+		// The only expected error is EINTR, after which we simply go around the loop again.
+		if res < 0 { 0 } else { res as usize }
+	}
+}
+
+impl Drop for Poller {
+	fn drop(&mut self) {
+		unsafe {
+			libc::close(self.wake_fd);
+			libc::close(self.epoll_fd);
+		}
+	}
+}
+
+struct ConnectionState {
+	/// Set when `read_event` asked us to stop reading, until a `send_data` call resumes reads.
+	read_paused: bool,
+	/// Set when a `send_data` call could not write everything, until the socket is writable.
+	write_blocked: bool,
+	/// The read rate limit for this connection, if any.
+	bucket: Option<TokenBucket>,
+	/// Set when the rate limit has been reached, until the bucket has refilled.
+	throttled: bool,
+	/// Data we accepted but could not write yet. This is only ever used for the initial handshake
+	/// message of an outbound connection, which the `PeerManager` hands us just once.
+	queued: Vec<u8>,
+}
+
+/// A single peer connection, shared between the event loop and all [`SocketDescriptor`]s which
+/// refer to it. The socket is closed once the last reference is dropped.
+pub(crate) struct Connection {
+	id: u64,
+	stream: TcpStream,
+	/// The remote IP address for inbound connections.
+	inbound_ip: Option<IpAddr>,
+	poller: Arc<Poller>,
+	state: Mutex<ConnectionState>,
+}
+
+impl Connection {
+	/// Updates the epoll interest set to match the state. We only wait for writability while
+	/// blocked on a write, as no reads may happen until the pending write completes.
+	fn update_interest(&self, state: &ConnectionState) {
+		let events = if state.write_blocked {
+			libc::EPOLLOUT
+		} else if !state.read_paused && !state.throttled {
+			libc::EPOLLIN | libc::EPOLLRDHUP
+		} else { 0 };
+		// If this fails the connection is already being torn down.
+		let _ = self.poller.ctl(libc::EPOLL_CTL_MOD, self.stream.as_raw_fd(), events as u32, self.id);
+	}
+
+	/// Writes as much of `data` as the socket accepts. On error the socket is shut down, and the
+	/// event loop will notice and disconnect the peer.
+	fn write(&self, data: &[u8]) -> usize {
+		match (&self.stream).write(data) {
+			Ok(written) => written,
+			Err(e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::Interrupted => 0,
+			Err(_) => {
+				let _ = self.stream.shutdown(Shutdown::Both);
+				0
+			},
+		}
+	}
+
+	/// Writes as much of the queued data as possible, returning whether all of it was written.
+	fn flush_queued(&self, state: &mut ConnectionState) -> bool {
+		if state.queued.is_empty() { return true; }
+		let written = self.write(&state.queued);
+		state.queued.drain(..written);
+		state.queued.is_empty()
+	}
+
+	fn send_data(&self, data: &[u8], resume_read: bool) -> usize {
+		let mut state = self.state.lock().unwrap();
+		if resume_read { state.read_paused = false; }
+		let written = if self.flush_queued(&mut state) { self.write(data) } else { 0 };
+		state.write_blocked = written < data.len();
+		self.update_interest(&state);
+		written
+	}
+
+	pub(crate) fn descriptor(conn: &Arc<Connection>) -> SocketDescriptor {
+		SocketDescriptor {
+			this_arg: Arc::into_raw(Arc::clone(conn)) as *mut c_void,
+			send_data: Connection_send_data,
+			disconnect_socket: Connection_disconnect_socket,
+			eq: Connection_eq,
+			hash: Connection_hash,
+			cloned: Some(Connection_cloned),
+			free: Some(Connection_free),
+		}
+	}
+}
+
+#[must_use]
+extern "C" fn Connection_send_data(this_arg: *mut c_void, data: u8slice, resume_read: bool) -> usize {
+	let conn = unsafe { &*(this_arg as *const Connection) };
+	conn.send_data(data.to_slice(), resume_read)
+}
+extern "C" fn Connection_disconnect_socket(this_arg: *mut c_void) {
+	let conn = unsafe { &*(this_arg as *const Connection) };
+	let _ = conn.stream.shutdown(Shutdown::Both);
+}
+extern "C" fn Connection_eq(this_arg: *const c_void, other_arg: &SocketDescriptor) -> bool {
+	// All descriptors for the same connection point to the same Arc'd Connection.
+	this_arg == other_arg.this_arg as *const c_void
+}
+extern "C" fn Connection_hash(this_arg: *const c_void) -> u64 {
+	unsafe { &*(this_arg as *const Connection) }.id
+}
+extern "C" fn Connection_cloned(new_descriptor: &mut SocketDescriptor) {
+	unsafe { Arc::increment_strong_count(new_descriptor.this_arg as *const Connection); }
+}
+extern "C" fn Connection_free(this_arg: *mut c_void) {
+	unsafe { let _ = Arc::from_raw(this_arg as *const Connection); }
+}
+
+pub(crate) struct NetState {
+	peer_manager: *const nativePeerManager,
+	poller: Arc<Poller>,
+	next_id: AtomicU64,
+	stopped: AtomicBool,
+	connections: Mutex<HashMap<u64, Arc<Connection>>>,
+	listeners: Mutex<HashMap<u64, Arc<TcpListener>>>,
+	policy: Mutex<Option<Arc<nativeConnectionPolicy>>>,
+	/// The peers which had completed their handshake and passed the peer filter, if any, as of
+	/// the last check.
+	filtered_peers: Mutex<HashSet<SecpPublicKey>>,
+}
+// The PeerManager is only ever accessed by reference, and is itself Sync.
+unsafe impl Send for NetState {}
+unsafe impl Sync for NetState {}
+
+impl NetState {
+	pub(crate) fn peer_manager(&self) -> &nativePeerManager {
+		unsafe { &*self.peer_manager }
+	}
+
+	fn policy(&self) -> Option<Arc<nativeConnectionPolicy>> {
+		self.policy.lock().unwrap().clone()
+	}
+
+	fn new_connection(&self, stream: TcpStream, inbound_ip: Option<IpAddr>) -> Result<Arc<Connection>, LdkNetError> {
+		stream.set_nonblocking(true).map_err(|_| LdkNetError::SocketSetupFailed)?;
+		stream.set_nodelay(true).map_err(|_| LdkNetError::SocketSetupFailed)?;
+		let bucket = self.policy().and_then(|policy| policy.new_bucket());
+		Ok(Arc::new(Connection {
+			id: self.next_id.fetch_add(1, Ordering::AcqRel),
+			stream,
+			inbound_ip,
+			poller: Arc::clone(&self.poller),
+			state: Mutex::new(ConnectionState { read_paused: false, write_blocked: false, bucket, throttled: false, queued: Vec::new() }),
+		}))
+	}
+
+	/// Starts handling events for a connection which the `PeerManager` already knows about.
+	fn register_connection(&self, conn: Arc<Connection>) -> Result<(), LdkNetError> {
+		let fd = conn.stream.as_raw_fd();
+		let id = conn.id;
+		let events = if conn.state.lock().unwrap().write_blocked { libc::EPOLLOUT } else { libc::EPOLLIN | libc::EPOLLRDHUP };
+		{
+			let mut connections = self.connections.lock().unwrap();
+			if !self.stopped.load(Ordering::Acquire) {
+				connections.insert(id, conn);
+			} else {
+				std::mem::drop(connections);
+				self.peer_manager().socket_disconnected(&Connection::descriptor(&conn));
+				return Err(LdkNetError::Stopped);
+			}
+		}
+		if self.poller.ctl(libc::EPOLL_CTL_ADD, fd, events as u32, id).is_err() {
+			self.disconnect(id);
+			return Err(LdkNetError::SocketSetupFailed);
+		}
+		Ok(())
+	}
+
+	/// Hands an established outbound TCP connection to the `PeerManager` and starts handling its
+	/// events.
+	pub(crate) fn register_outbound(&self, stream: TcpStream, their_node_id: SecpPublicKey) -> Result<(), LdkNetError> {
+		if self.stopped.load(Ordering::Acquire) { return Err(LdkNetError::Stopped); }
+		let conn = self.new_connection(stream, None)?;
+		let initial_send = match self.peer_manager().new_outbound_connection(their_node_id, Connection::descriptor(&conn)) {
+			Ok(initial_send) => initial_send,
+			Err(_) => return Err(LdkNetError::PeerRejected),
+		};
+		// The socket is non-blocking now, so whatever doesn't fit in the send buffer is queued and
+		// written once the socket becomes writable.
+		{
+			let mut state = conn.state.lock().unwrap();
+			let written = conn.write(&initial_send);
+			if written < initial_send.len() {
+				state.queued = initial_send[written..].to_vec();
+				state.write_blocked = true;
+			}
+		}
+		self.register_connection(conn)
+	}
+
+	fn accept_inbound(&self, listener: &TcpListener) {
+		loop {
+			let (stream, remote_addr) = match listener.accept() {
+				Ok(res) => res,
+				Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+				Err(_) => return,
+			};
+			if let Some(policy) = self.policy() {
+				let connections = self.connections.lock().unwrap();
+				let inbound = connections.values().filter(|conn| conn.inbound_ip.is_some()).count();
+				let inbound_from_ip = connections.values().filter(|conn| conn.inbound_ip == Some(remote_addr.ip())).count();
+				// Dropping the stream closes the connection before the PeerManager ever sees it.
+				if !policy.allow_inbound(inbound, inbound_from_ip) { continue; }
+			}
+			let conn = match self.new_connection(stream, Some(remote_addr.ip())) {
+				Ok(conn) => conn,
+				Err(_) => continue,
+			};
+			if self.peer_manager().new_inbound_connection(Connection::descriptor(&conn)).is_ok() {
+				let _ = self.register_connection(conn);
+			}
+		}
+	}
+
+	/// Removes the connection from the event loop, closes it and informs the `PeerManager`.
+	fn disconnect(&self, id: u64) {
+		let conn = match self.connections.lock().unwrap().remove(&id) {
+			Some(conn) => conn,
+			None => return,
+		};
+		let _ = self.poller.ctl(libc::EPOLL_CTL_DEL, conn.stream.as_raw_fd(), 0, id);
+		let _ = conn.stream.shutdown(Shutdown::Both);
+		self.peer_manager().socket_disconnected(&Connection::descriptor(&conn));
+	}
+
+	fn handle_connection_event(&self, id: u64, events: u32, read_buf: &mut [u8]) {
+		let conn = match self.connections.lock().unwrap().get(&id) {
+			Some(conn) => Arc::clone(conn),
+			None => return,
+		};
+		if events & (libc::EPOLLHUP | libc::EPOLLERR) as u32 != 0 {
+			self.disconnect(id);
+			return;
+		}
+		let mut descriptor = Connection::descriptor(&conn);
+		if events & libc::EPOLLOUT as u32 != 0 {
+			{
+				let mut state = conn.state.lock().unwrap();
+				if !conn.flush_queued(&mut state) { return; }
+				state.write_blocked = false;
+				conn.update_interest(&state);
+			}
+			if self.peer_manager().write_buffer_space_avail(&mut descriptor).is_err() {
+				self.disconnect(id);
+				return;
+			}
+		}
+		if events & (libc::EPOLLIN | libc::EPOLLRDHUP) as u32 != 0 {
+			let read_limit = {
+				let mut state = conn.state.lock().unwrap();
+				if state.read_paused || state.write_blocked || state.throttled { return; }
+				match state.bucket.as_mut().map(|bucket| bucket.available()) {
+					Some(0) => {
+						state.throttled = true;
+						conn.update_interest(&state);
+						return;
+					},
+					Some(available) => std::cmp::min(available, read_buf.len()),
+					None => read_buf.len(),
+				}
+			};
+			match (&conn.stream).read(&mut read_buf[..read_limit]) {
+				Ok(0) => self.disconnect(id),
+				Ok(len) => {
+					if let Some(bucket) = conn.state.lock().unwrap().bucket.as_mut() { bucket.consume(len); }
+					match self.peer_manager().read_event(&mut descriptor, &read_buf[..len]) {
+						Ok(true) => {
+							let mut state = conn.state.lock().unwrap();
+							state.read_paused = true;
+							conn.update_interest(&state);
+						},
+						Ok(false) => {},
+						Err(_) => self.disconnect(id),
+					}
+				},
+				Err(e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::Interrupted => {},
+				Err(_) => self.disconnect(id),
+			}
+		}
+	}
+
+	/// Resumes reading from throttled connections whose token bucket has refilled. Returns
+	/// whether any connections remain throttled.
+	fn unthrottle_connections(&self) -> bool {
+		let mut any_throttled = false;
+		for conn in self.connections.lock().unwrap().values() {
+			let mut state = conn.state.lock().unwrap();
+			if !state.throttled { continue; }
+			if state.bucket.as_mut().map(|bucket| bucket.available()).unwrap_or(1) > 0 {
+				state.throttled = false;
+				conn.update_interest(&state);
+			} else {
+				any_throttled = true;
+			}
+		}
+		any_throttled
+	}
+
+	/// Runs newly-connected peers through the peer filter, disconnecting any which it rejects.
+	fn filter_new_peers(&self, policy: &nativeConnectionPolicy) {
+		let mut peers: HashSet<SecpPublicKey> = self.peer_manager().get_peer_node_ids().drain(..).collect();
+		let mut filtered_peers = self.filtered_peers.lock().unwrap();
+		let new_peers: Vec<SecpPublicKey> = peers.difference(&filtered_peers).cloned().collect();
+		for node_id in new_peers {
+			if !policy.accept_peer(&node_id) {
+				self.peer_manager().disconnect_by_node_id(node_id, false);
+				peers.remove(&node_id);
+			}
+		}
+		*filtered_peers = peers;
+	}
+
+	fn run(&self) {
+		let mut events = vec![libc::epoll_event { events: 0, u64: 0 }; MAX_EVENTS];
+		let mut read_buf = vec![0; READ_BUF_LEN];
+		let mut any_throttled = false;
+		let mut last_timer_tick = Instant::now();
+		while !self.stopped.load(Ordering::Acquire) {
+			let timeout = if any_throttled { THROTTLE_INTERVAL_MS } else { PROCESS_EVENTS_INTERVAL_MS };
+			let count = self.poller.wait(&mut events, timeout);
+			for event in events[..count].iter() {
+				let (token, flags) = (event.u64, event.events);
+				if token == WAKE_TOKEN {
+					self.poller.clear_wake();
+					continue;
+				}
+				// Don't hold the listeners lock while handling the socket, LdkNet_listen needs it.
+				let listener = self.listeners.lock().unwrap().get(&token).map(Arc::clone);
+				if let Some(listener) = listener {
+					self.accept_inbound(&listener);
+				} else {
+					self.handle_connection_event(token, flags, &mut read_buf);
+				}
+			}
+			if let Some(policy) = self.policy() {
+				if policy.is_rate_limited() { any_throttled = self.unthrottle_connections(); }
+				if policy.has_peer_filter() && count != 0 { self.filter_new_peers(&policy); }
+			}
+			if last_timer_tick.elapsed() >= TIMER_TICK_INTERVAL {
+				self.peer_manager().timer_tick_occurred();
+				last_timer_tick = Instant::now();
+			}
+			self.peer_manager().process_events();
+		}
+
+		let ids: Vec<u64> = self.connections.lock().unwrap().keys().cloned().collect();
+		for id in ids { self.disconnect(id); }
+		self.listeners.lock().unwrap().clear();
+	}
+
+	fn stop(&self) {
+		// Take the connections and listeners locks so that no new connections or listeners are
+		// registered after the event loop has disconnected and closed everything.
+		let _connections = self.connections.lock().unwrap();
+		let _listeners = self.listeners.lock().unwrap();
+		self.stopped.store(true, Ordering::Release);
+		self.poller.wake();
+	}
+}
+
+pub(crate) struct nativeLdkNet {
+	state: Arc<NetState>,
+	thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl nativeLdkNet {
+	pub(crate) fn state(&self) -> &Arc<NetState> { &self.state }
+
+	fn stop(&self) {
+		self.state.stop();
+		if let Some(thread) = self.thread.lock().unwrap().take() {
+			let _ = thread.join();
+		}
+	}
+}
+
+impl Drop for nativeLdkNet {
+	fn drop(&mut self) {
+		self.stop();
+	}
+}
+
+/// A running socket driver for a `PeerManager`, created with [`LdkNet_start`].
+#[must_use]
+#[repr(C)]
+pub struct LdkNet {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeLdkNet,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for LdkNet {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeLdkNet>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the LdkNet, if is_owned is set and inner is non-NULL.
+///
+/// This stops the driver first if [`LdkNet_stop`] has not been called.
+#[no_mangle]
+pub extern "C" fn LdkNet_free(this_obj: LdkNet) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn LdkNet_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeLdkNet); }
+}
+#[allow(unused)]
+impl LdkNet {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeLdkNet {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeLdkNet {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeLdkNet {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_LdkNetLdkNetErrorZ
+pub union CResult_LdkNetLdkNetErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut LdkNet,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut LdkNetError,
+}
+#[repr(C)]
+/// A CResult_LdkNetLdkNetErrorZ represents the result of a fallible operation,
+/// containing a LdkNet on success and a LdkNetError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_LdkNetLdkNetErrorZ {
+	/// The contents of this CResult_LdkNetLdkNetErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_LdkNetLdkNetErrorZPtr,
+	/// Whether this CResult_LdkNetLdkNetErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_LdkNetLdkNetErrorZ in the success state.
+pub extern "C" fn CResult_LdkNetLdkNetErrorZ_ok(o: LdkNet) -> CResult_LdkNetLdkNetErrorZ {
+	CResult_LdkNetLdkNetErrorZ {
+		contents: CResult_LdkNetLdkNetErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_LdkNetLdkNetErrorZ in the error state.
+pub extern "C" fn CResult_LdkNetLdkNetErrorZ_err(e: LdkNetError) -> CResult_LdkNetLdkNetErrorZ {
+	CResult_LdkNetLdkNetErrorZ {
+		contents: CResult_LdkNetLdkNetErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_LdkNetLdkNetErrorZ_is_ok(o: &CResult_LdkNetLdkNetErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_LdkNetLdkNetErrorZ.
+pub extern "C" fn CResult_LdkNetLdkNetErrorZ_free(_res: CResult_LdkNetLdkNetErrorZ) { }
+impl Drop for CResult_LdkNetLdkNetErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Starts a background thread which handles socket events for the given `PeerManager`.
+///
+/// The `PeerManager` is referenced, not copied, and must not be freed until the returned
+/// `LdkNet` has been stopped.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn LdkNet_start(peer_manager: &PeerManager) -> CResult_LdkNetLdkNetErrorZ {
+	let poller = match Poller::new() {
+		Ok(poller) => poller,
+		Err(_) => return CResult_LdkNetLdkNetErrorZ_err(LdkNetError::EventLoopSetupFailed),
+	};
+	let state = Arc::new(NetState {
+		peer_manager: ObjOps::untweak_ptr(peer_manager.inner),
+		poller: Arc::new(poller),
+		next_id: AtomicU64::new(WAKE_TOKEN + 1),
+		stopped: AtomicBool::new(false),
+		connections: Mutex::new(HashMap::new()),
+		listeners: Mutex::new(HashMap::new()),
+		policy: Mutex::new(None),
+		filtered_peers: Mutex::new(HashSet::new()),
+	});
+	let thread_state = Arc::clone(&state);
+	let thread = match std::thread::Builder::new().name("ldk-net".to_owned()).spawn(move || thread_state.run()) {
+		Ok(thread) => thread,
+		Err(_) => return CResult_LdkNetLdkNetErrorZ_err(LdkNetError::EventLoopSetupFailed),
+	};
+	CResult_LdkNetLdkNetErrorZ_ok(LdkNet { inner: ObjOps::heap_alloc(nativeLdkNet { state, thread: Mutex::new(Some(thread)) }), is_owned: true })
+}
+
+/// Resolves `addr` and connects to the first address which accepts a connection.
+pub(crate) fn connect_tcp(addr: &str, timeout: Duration) -> Result<TcpStream, LdkNetError> {
+	let addrs: Vec<SocketAddr> = addr.to_socket_addrs().map_err(|_| LdkNetError::InvalidAddress)?.collect();
+	if addrs.is_empty() { return Err(LdkNetError::InvalidAddress); }
+	let mut err = LdkNetError::ConnectFailed;
+	for addr in addrs {
+		match TcpStream::connect_timeout(&addr, timeout) {
+			Ok(stream) => return Ok(stream),
+			Err(e) if e.kind() == std::io::ErrorKind::TimedOut => err = LdkNetError::ConnectTimeout,
+			Err(_) => {},
+		}
+	}
+	Err(err)
+}
+
+/// Connects to the peer with the given node id at `addr`, and hands the connection to the
+/// `PeerManager`.
+///
+/// `addr` may be any `host:port` string, including IPv6 addresses in the form `[addr]:port`.
+/// Hostnames are resolved with the system resolver. `timeout_secs` must be non-zero.
+///
+/// Note that this blocks the calling thread while resolving `addr` and until the TCP connection
+/// has been established, for at most `timeout_secs` seconds per resolved address, so it should
+/// not be called from a thread which must stay responsive, such as one handling LDK events. The
+/// Lightning handshake itself completes in the background.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn LdkNet_connect(this_arg: &LdkNet, mut their_node_id: PublicKey, mut addr: Str, mut timeout_secs: u64) -> CResult_NoneLdkNetErrorZ {
+	let state = this_arg.get_native_ref().state();
+	if state.stopped.load(Ordering::Acquire) { return CResult_NoneLdkNetErrorZ_err(LdkNetError::Stopped); }
+	let res = connect_tcp(addr.into_str(), Duration::from_secs(timeout_secs))
+		.and_then(|stream| state.register_outbound(stream, their_node_id.into_rust()));
+	CResult_NoneLdkNetErrorZ::from_rust(res)
+}
+
+/// Binds a listening socket to `addr` (in the same format as for [`LdkNet_connect`]) and hands
+/// all inbound connections to the `PeerManager`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn LdkNet_listen(this_arg: &LdkNet, mut addr: Str) -> CResult_NoneLdkNetErrorZ {
+	let state = this_arg.get_native_ref().state();
+	let listener = match TcpListener::bind(addr.into_str()) {
+		Ok(listener) => listener,
+		Err(e) if e.kind() == std::io::ErrorKind::InvalidInput => return CResult_NoneLdkNetErrorZ_err(LdkNetError::InvalidAddress),
+		Err(_) => return CResult_NoneLdkNetErrorZ_err(LdkNetError::BindFailed),
+	};
+	if listener.set_nonblocking(true).is_err() { return CResult_NoneLdkNetErrorZ_err(LdkNetError::SocketSetupFailed); }
+	let id = state.next_id.fetch_add(1, Ordering::AcqRel);
+	let fd = listener.as_raw_fd();
+	{
+		let mut listeners = state.listeners.lock().unwrap();
+		if state.stopped.load(Ordering::Acquire) { return CResult_NoneLdkNetErrorZ_err(LdkNetError::Stopped); }
+		listeners.insert(id, Arc::new(listener));
+	}
+	if state.poller.ctl(libc::EPOLL_CTL_ADD, fd, libc::EPOLLIN as u32, id).is_err() {
+		state.listeners.lock().unwrap().remove(&id);
+		return CResult_NoneLdkNetErrorZ_err(LdkNetError::SocketSetupFailed);
+	}
+	CResult_NoneLdkNetErrorZ_ok()
+}
+
+/// Stops the background thread, disconnecting all peers and closing all listening sockets.
+///
+/// Blocks until the background thread has exited, after which the `PeerManager` may be freed.
+/// Calling this more than once has no effect.
+#[no_mangle]
+pub extern "C" fn LdkNet_stop(this_arg: &LdkNet) {
+	this_arg.get_native_ref().stop();
+}
+
+/// Installs a policy limiting inbound connections and per-connection bandwidth, replacing any
+/// existing policy.
+///
+/// Inbound connection limits and the peer filter apply immediately, while read rate limits only
+/// apply to connections made after this call.
+#[no_mangle]
+pub extern "C" fn LdkNet_set_connection_policy(this_arg: &LdkNet, mut policy: ConnectionPolicy) {
+	let policy = *unsafe { Box::from_raw(policy.take_inner()) };
+	*this_arg.get_native_ref().state().policy.lock().unwrap() = Some(Arc::new(policy));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::secp256k1::Secp256k1;
+	use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
+	use crate::extensions::test_utils::peer_manager;
+
+	fn node_id(seed: u8) -> SecpPublicKey {
+		SecpPublicKey::from_secret_key(&Secp256k1::new(), &SecpSecretKey::from_slice(&[seed; 32]).unwrap())
+	}
+
+	fn start(peer_manager: &PeerManager) -> LdkNet {
+		let mut res = LdkNet_start(peer_manager);
+		assert!(res.result_ok);
+		unsafe { std::mem::replace(&mut *res.contents.result, LdkNet { inner: std::ptr::null_mut(), is_owned: true }) }
+	}
+
+	fn result(res: CResult_NoneLdkNetErrorZ) -> Result<(), LdkNetError> {
+		if res.result_ok { Ok(()) } else { Err(unsafe { *res.contents.err }) }
+	}
+
+	/// Returns a local address nothing is listening on.
+	fn free_addr() -> String {
+		let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+		format!("127.0.0.1:{}", port)
+	}
+
+	fn wait_for<F: Fn() -> bool>(condition: F) {
+		let deadline = Instant::now() + Duration::from_secs(10);
+		while !condition() {
+			assert!(Instant::now() < deadline, "Timed out");
+			std::thread::sleep(Duration::from_millis(10));
+		}
+	}
+
+	fn peers(peer_manager: &PeerManager) -> Vec<SecpPublicKey> {
+		peer_manager.get_native_ref().get_peer_node_ids()
+	}
+
+	#[test]
+	fn connects_peers() {
+		let (manager_a, manager_b) = (peer_manager(1), peer_manager(2));
+		let (net_a, net_b) = (start(&manager_a), start(&manager_b));
+		let addr = free_addr();
+		assert_eq!(result(LdkNet_listen(&net_b, addr.clone().into())), Ok(()));
+		assert_eq!(result(LdkNet_connect(&net_a, PublicKey::from_rust(&node_id(2)), addr.clone().into(), 5)), Ok(()));
+		wait_for(|| peers(&manager_a) == vec![node_id(2)] && peers(&manager_b) == vec![node_id(1)]);
+
+		assert_eq!(result(LdkNet_connect(&net_a, PublicKey::from_rust(&node_id(3)), free_addr().into(), 5)), Err(LdkNetError::ConnectFailed));
+		assert_eq!(result(LdkNet_connect(&net_a, PublicKey::from_rust(&node_id(3)), "not an address".to_owned().into(), 5)), Err(LdkNetError::InvalidAddress));
+		assert_eq!(result(LdkNet_listen(&net_a, addr.into())), Err(LdkNetError::BindFailed));
+	}
+
+	#[test]
+	fn stop_disconnects_peers_and_closes_listeners() {
+		let (manager_a, manager_b) = (peer_manager(1), peer_manager(2));
+		let (net_a, net_b) = (start(&manager_a), start(&manager_b));
+		let addr = free_addr();
+		assert_eq!(result(LdkNet_listen(&net_b, addr.clone().into())), Ok(()));
+		assert_eq!(result(LdkNet_connect(&net_a, PublicKey::from_rust(&node_id(2)), addr.clone().into(), 5)), Ok(()));
+		wait_for(|| peers(&manager_a).len() == 1 && peers(&manager_b).len() == 1);
+
+		LdkNet_stop(&net_a);
+		assert!(peers(&manager_a).is_empty());
+		wait_for(|| peers(&manager_b).is_empty());
+		assert_eq!(result(LdkNet_connect(&net_a, PublicKey::from_rust(&node_id(2)), addr.clone().into(), 5)), Err(LdkNetError::Stopped));
+		assert_eq!(result(LdkNet_listen(&net_a, free_addr().into())), Err(LdkNetError::Stopped));
+		LdkNet_stop(&net_a);
+
+		LdkNet_stop(&net_b);
+		assert!(TcpStream::connect(&addr).is_err());
+		assert!(net_b.get_native_ref().state().listeners.lock().unwrap().is_empty());
+	}
+}
@@ -0,0 +1,820 @@
+//! A standalone implementation of the BOLT 8 transport, for building custom transports, test
+//! vectors and fuzzers without a `PeerManager`.
+//!
+//! A [`PeerChannelEncryptor`] performs one side of the three-act Noise_XK handshake and then
+//! encrypts and decrypts messages, rotating keys every 1000 messages as BOLT 8 requires. The
+//! caller is responsible for moving the bytes between the two sides.
+//!
+//! On the initiating side:
+//!  1. `PeerChannelEncryptor_new_outbound` with the responder's node id,
+//!  2. `PeerChannelEncryptor_get_act_one` and send the result,
+//!  3. read 50 bytes and pass them to `PeerChannelEncryptor_process_act_two`, sending the result.
+//!
+//! On the responding side:
+//!  1. `PeerChannelEncryptor_new_inbound` with our node secret,
+//!  2. read 50 bytes and pass them to `PeerChannelEncryptor_process_act_one`, sending the result,
+//!  3. read 66 bytes and pass them to `PeerChannelEncryptor_process_act_three`, which returns the
+//!     initiator's node id.
+//!
+//! Afterwards, each message is sent as the output of `PeerChannelEncryptor_encrypt_message`. To
+//! receive one, read 18 bytes and pass them to `PeerChannelEncryptor_decrypt_length_header`,
+//! then read the returned number of bytes plus 16 and pass them to
+//! `PeerChannelEncryptor_decrypt_message`.
+//!
+//! If any step fails the connection must be dropped, and the encryptor cannot be used further.
+
+use std::ffi::c_void;
+
+use bitcoin::hashes::{Hash, HashEngine, Hmac, HmacEngine};
+use bitcoin::hashes::sha256::Hash as Sha256;
+use bitcoin::secp256k1::{Secp256k1, SignOnly};
+use bitcoin::secp256k1::ecdh::SharedSecret;
+use bitcoin::secp256k1::key::{PublicKey as SecpPublicKey, SecretKey as SecpSecretKey};
+
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+
+use crate::c_types::*;
+
+const NOISE_PROTOCOL_NAME: &[u8] = b"Noise_XK_secp256k1_ChaChaPoly_SHA256";
+const NOISE_PROLOGUE: &[u8] = b"lightning";
+const HANDSHAKE_VERSION: u8 = 0;
+const TAG_LEN: usize = 16;
+/// The number of messages encrypted under a key before it is rotated.
+const KEY_ROTATION_INDEX: u64 = 1000;
+
+/// The length of act one and act two.
+pub const NOISE_ACT_ONE_TWO_LEN: usize = 1 + 33 + TAG_LEN;
+/// The length of act three.
+pub const NOISE_ACT_THREE_LEN: usize = 1 + 33 + TAG_LEN + TAG_LEN;
+/// The length of the encrypted length header preceding each message.
+pub const NOISE_LENGTH_HEADER_LEN: usize = 2 + TAG_LEN;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The reasons a BOLT 8 handshake or message encryption step can fail.
+pub enum NoiseError {
+	/// The call does not match the current stage of the handshake, e.g. a message was encrypted
+	/// before the handshake completed, or a previous step already failed.
+	InvalidState,
+	/// The given act or length header was not of the expected length.
+	InvalidLength,
+	/// The act had an unknown handshake version.
+	UnknownVersion,
+	/// The act contained an invalid public key.
+	InvalidPublicKey,
+	/// Authenticated decryption failed, i.e. the data was corrupted or not meant for us.
+	DecryptionFailed,
+	/// The message to encrypt is longer than the maximum of 65535 bytes.
+	MessageTooLong,
+}
+
+type Hash32 = [u8; 32];
+
+enum NoiseState {
+	InitiatorStarting { ephemeral_key: SecpSecretKey, their_node_id: SecpPublicKey, h: Hash32, ck: Hash32 },
+	InitiatorAwaitingActTwo { ephemeral_key: SecpSecretKey, h: Hash32, ck: Hash32 },
+	ResponderAwaitingActOne { our_node_secret: SecpSecretKey, h: Hash32, ck: Hash32 },
+	ResponderAwaitingActThree { ephemeral_key: SecpSecretKey, h: Hash32, ck: Hash32, temp_k2: Hash32 },
+	Finished { sk: Hash32, sn: u64, sck: Hash32, rk: Hash32, rn: u64, rck: Hash32 },
+	Failed,
+}
+
+fn sha256(parts: &[&[u8]]) -> Hash32 {
+	let mut engine = Sha256::engine();
+	for part in parts {
+		engine.input(part);
+	}
+	Sha256::from_engine(engine).into_inner()
+}
+
+fn hkdf_extract_expand(salt: &Hash32, ikm: &[u8]) -> (Hash32, Hash32) {
+	let mut extract = HmacEngine::<Sha256>::new(salt);
+	extract.input(ikm);
+	let prk = Hmac::from_engine(extract);
+	let mut t1_engine = HmacEngine::<Sha256>::new(&prk[..]);
+	t1_engine.input(&[1]);
+	let t1 = Hmac::from_engine(t1_engine).into_inner();
+	let mut t2_engine = HmacEngine::<Sha256>::new(&prk[..]);
+	t2_engine.input(&t1);
+	t2_engine.input(&[2]);
+	(t1, Hmac::from_engine(t2_engine).into_inner())
+}
+
+fn ecdh(point: &SecpPublicKey, scalar: &SecpSecretKey) -> Hash32 {
+	let mut res = [0; 32];
+	res.copy_from_slice(&SharedSecret::new(point, scalar)[..]);
+	res
+}
+
+fn nonce(n: u64) -> [u8; 12] {
+	let mut res = [0; 12];
+	res[4..].copy_from_slice(&n.to_le_bytes());
+	res
+}
+
+fn encrypt_with_ad(key: &Hash32, n: u64, ad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+	let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+	cipher.encrypt(Nonce::from_slice(&nonce(n)), Payload { msg: plaintext, aad: ad })
+		.expect("ChaCha20Poly1305 encryption cannot fail for messages of this size")
+}
+
+fn decrypt_with_ad(key: &Hash32, n: u64, ad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+	let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+	cipher.decrypt(Nonce::from_slice(&nonce(n)), Payload { msg: ciphertext, aad: ad })
+		.map_err(|_| NoiseError::DecryptionFailed)
+}
+
+/// Rotates a sending or receiving key once it has been used for [`KEY_ROTATION_INDEX`] messages.
+fn maybe_rotate(k: &mut Hash32, n: &mut u64, ck: &mut Hash32) {
+	if *n >= KEY_ROTATION_INDEX {
+		let (new_ck, new_k) = hkdf_extract_expand(ck, k);
+		*ck = new_ck;
+		*k = new_k;
+		*n = 0;
+	}
+}
+
+/// Returns the initial (h, ck) for a handshake in which the responder has the given static key.
+fn initial_state(responder_node_id: &SecpPublicKey) -> (Hash32, Hash32) {
+	let ck = sha256(&[NOISE_PROTOCOL_NAME]);
+	let h = sha256(&[&ck, NOISE_PROLOGUE]);
+	(sha256(&[&h, &responder_node_id.serialize()]), ck)
+}
+
+/// Checks an act's length and version and parses the public key which follows the version.
+fn parse_act<'a>(act: &'a [u8], expected_len: usize) -> Result<(SecpPublicKey, &'a [u8]), NoiseError> {
+	if act.len() != expected_len { return Err(NoiseError::InvalidLength); }
+	if act[0] != HANDSHAKE_VERSION { return Err(NoiseError::UnknownVersion); }
+	let key = SecpPublicKey::from_slice(&act[1..34]).map_err(|_| NoiseError::InvalidPublicKey)?;
+	Ok((key, &act[34..]))
+}
+
+pub(crate) struct nativePeerChannelEncryptor {
+	secp_ctx: Secp256k1<SignOnly>,
+	state: NoiseState,
+}
+
+impl nativePeerChannelEncryptor {
+	fn new_outbound(their_node_id: SecpPublicKey, ephemeral_key: SecpSecretKey) -> Self {
+		let (h, ck) = initial_state(&their_node_id);
+		Self { secp_ctx: Secp256k1::signing_only(), state: NoiseState::InitiatorStarting { ephemeral_key, their_node_id, h, ck } }
+	}
+
+	fn new_inbound(our_node_secret: SecpSecretKey) -> Self {
+		let secp_ctx = Secp256k1::signing_only();
+		let (h, ck) = initial_state(&SecpPublicKey::from_secret_key(&secp_ctx, &our_node_secret));
+		Self { secp_ctx, state: NoiseState::ResponderAwaitingActOne { our_node_secret, h, ck } }
+	}
+
+	/// Generates act one or two: our ephemeral key, mixed with `their_key`, and an empty
+	/// ciphertext authenticating the handshake so far. Returns the act and the new (h, ck, temp_k).
+	fn outbound_act(&self, h: &Hash32, ck: &Hash32, ephemeral_key: &SecpSecretKey, their_key: &SecpPublicKey) -> (Vec<u8>, Hash32, Hash32, Hash32) {
+		let our_pub = SecpPublicKey::from_secret_key(&self.secp_ctx, ephemeral_key).serialize();
+		let h = sha256(&[h, &our_pub]);
+		let (ck, temp_k) = hkdf_extract_expand(ck, &ecdh(their_key, ephemeral_key));
+		let c = encrypt_with_ad(&temp_k, 0, &h, &[]);
+		let h = sha256(&[&h, &c]);
+		let mut act = Vec::with_capacity(NOISE_ACT_ONE_TWO_LEN);
+		act.push(HANDSHAKE_VERSION);
+		act.extend_from_slice(&our_pub);
+		act.extend_from_slice(&c);
+		(act, h, ck, temp_k)
+	}
+
+	/// Processes act one or two, given the key we mix with their ephemeral key. Returns their
+	/// ephemeral key and the new (h, ck, temp_k).
+	fn inbound_act(act: &[u8], h: &Hash32, ck: &Hash32, our_key: &SecpSecretKey) -> Result<(SecpPublicKey, Hash32, Hash32, Hash32), NoiseError> {
+		let (their_ephemeral, c) = parse_act(act, NOISE_ACT_ONE_TWO_LEN)?;
+		let h = sha256(&[h, &their_ephemeral.serialize()]);
+		let (ck, temp_k) = hkdf_extract_expand(ck, &ecdh(&their_ephemeral, our_key));
+		decrypt_with_ad(&temp_k, 0, &h, c)?;
+		let h = sha256(&[&h, c]);
+		Ok((their_ephemeral, h, ck, temp_k))
+	}
+
+	fn get_act_one(&mut self) -> Result<Vec<u8>, NoiseError> {
+		match std::mem::replace(&mut self.state, NoiseState::Failed) {
+			NoiseState::InitiatorStarting { ephemeral_key, their_node_id, h, ck } => {
+				let (act, h, ck, _) = self.outbound_act(&h, &ck, &ephemeral_key, &their_node_id);
+				self.state = NoiseState::InitiatorAwaitingActTwo { ephemeral_key, h, ck };
+				Ok(act)
+			},
+			_ => Err(NoiseError::InvalidState),
+		}
+	}
+
+	fn process_act_one(&mut self, act_one: &[u8], ephemeral_key: SecpSecretKey) -> Result<Vec<u8>, NoiseError> {
+		match std::mem::replace(&mut self.state, NoiseState::Failed) {
+			NoiseState::ResponderAwaitingActOne { our_node_secret, h, ck } => {
+				let (their_ephemeral, h, ck, _) = Self::inbound_act(act_one, &h, &ck, &our_node_secret)?;
+				let (act_two, h, ck, temp_k2) = self.outbound_act(&h, &ck, &ephemeral_key, &their_ephemeral);
+				self.state = NoiseState::ResponderAwaitingActThree { ephemeral_key, h, ck, temp_k2 };
+				Ok(act_two)
+			},
+			_ => Err(NoiseError::InvalidState),
+		}
+	}
+
+	fn process_act_two(&mut self, act_two: &[u8], our_node_secret: SecpSecretKey) -> Result<Vec<u8>, NoiseError> {
+		match std::mem::replace(&mut self.state, NoiseState::Failed) {
+			NoiseState::InitiatorAwaitingActTwo { ephemeral_key, h, ck } => {
+				let (their_ephemeral, h, ck, temp_k2) = Self::inbound_act(act_two, &h, &ck, &ephemeral_key)?;
+
+				let our_node_id = SecpPublicKey::from_secret_key(&self.secp_ctx, &our_node_secret);
+				let c = encrypt_with_ad(&temp_k2, 1, &h, &our_node_id.serialize());
+				let h = sha256(&[&h, &c]);
+				let (ck, temp_k3) = hkdf_extract_expand(&ck, &ecdh(&their_ephemeral, &our_node_secret));
+				let t = encrypt_with_ad(&temp_k3, 0, &h, &[]);
+				let (sk, rk) = hkdf_extract_expand(&ck, &[]);
+
+				let mut act_three = Vec::with_capacity(NOISE_ACT_THREE_LEN);
+				act_three.push(HANDSHAKE_VERSION);
+				act_three.extend_from_slice(&c);
+				act_three.extend_from_slice(&t);
+				self.state = NoiseState::Finished { sk, sn: 0, sck: ck, rk, rn: 0, rck: ck };
+				Ok(act_three)
+			},
+			_ => Err(NoiseError::InvalidState),
+		}
+	}
+
+	fn process_act_three(&mut self, act_three: &[u8]) -> Result<SecpPublicKey, NoiseError> {
+		match std::mem::replace(&mut self.state, NoiseState::Failed) {
+			NoiseState::ResponderAwaitingActThree { ephemeral_key, h, ck, temp_k2 } => {
+				if act_three.len() != NOISE_ACT_THREE_LEN { return Err(NoiseError::InvalidLength); }
+				if act_three[0] != HANDSHAKE_VERSION { return Err(NoiseError::UnknownVersion); }
+				let c = &act_three[1..1 + 33 + TAG_LEN];
+				let t = &act_three[1 + 33 + TAG_LEN..];
+
+				let their_node_id = decrypt_with_ad(&temp_k2, 1, &h, c)?;
+				let their_node_id = SecpPublicKey::from_slice(&their_node_id).map_err(|_| NoiseError::InvalidPublicKey)?;
+				let h = sha256(&[&h, c]);
+				let (ck, temp_k3) = hkdf_extract_expand(&ck, &ecdh(&their_node_id, &ephemeral_key));
+				decrypt_with_ad(&temp_k3, 0, &h, t)?;
+				let (rk, sk) = hkdf_extract_expand(&ck, &[]);
+
+				self.state = NoiseState::Finished { sk, sn: 0, sck: ck, rk, rn: 0, rck: ck };
+				Ok(their_node_id)
+			},
+			_ => Err(NoiseError::InvalidState),
+		}
+	}
+
+	fn encrypt_message(&mut self, msg: &[u8]) -> Result<Vec<u8>, NoiseError> {
+		if msg.len() > u16::max_value() as usize { return Err(NoiseError::MessageTooLong); }
+		match self.state {
+			NoiseState::Finished { ref mut sk, ref mut sn, ref mut sck, .. } => {
+				maybe_rotate(sk, sn, sck);
+				let mut res = encrypt_with_ad(sk, *sn, &[], &(msg.len() as u16).to_be_bytes());
+				*sn += 1;
+				res.extend_from_slice(&encrypt_with_ad(sk, *sn, &[], msg));
+				*sn += 1;
+				Ok(res)
+			},
+			_ => Err(NoiseError::InvalidState),
+		}
+	}
+
+	fn decrypt_length_header(&mut self, header: &[u8]) -> Result<u16, NoiseError> {
+		if header.len() != NOISE_LENGTH_HEADER_LEN { return Err(NoiseError::InvalidLength); }
+		let res = match self.state {
+			NoiseState::Finished { ref mut rk, ref mut rn, ref mut rck, .. } => {
+				maybe_rotate(rk, rn, rck);
+				let len = decrypt_with_ad(rk, *rn, &[], header);
+				*rn += 1;
+				len.map(|len| u16::from_be_bytes([len[0], len[1]]))
+			},
+			_ => return Err(NoiseError::InvalidState),
+		};
+		if res.is_err() { self.state = NoiseState::Failed; }
+		res
+	}
+
+	fn decrypt_message(&mut self, msg: &[u8]) -> Result<Vec<u8>, NoiseError> {
+		if msg.len() < TAG_LEN || msg.len() > u16::max_value() as usize + TAG_LEN { return Err(NoiseError::InvalidLength); }
+		let res = match self.state {
+			NoiseState::Finished { ref rk, ref mut rn, .. } => {
+				let res = decrypt_with_ad(rk, *rn, &[], msg);
+				*rn += 1;
+				res
+			},
+			_ => return Err(NoiseError::InvalidState),
+		};
+		if res.is_err() { self.state = NoiseState::Failed; }
+		res
+	}
+
+	fn is_ready_for_encryption(&self) -> bool {
+		match self.state {
+			NoiseState::Finished { .. } => true,
+			_ => false,
+		}
+	}
+}
+
+/// One side of a BOLT 8 encrypted connection, see the module documentation for how to drive the
+/// handshake.
+#[must_use]
+#[repr(C)]
+pub struct PeerChannelEncryptor {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativePeerChannelEncryptor,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for PeerChannelEncryptor {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativePeerChannelEncryptor>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the PeerChannelEncryptor, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_free(this_obj: PeerChannelEncryptor) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn PeerChannelEncryptor_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativePeerChannelEncryptor); }
+}
+#[allow(unused)]
+impl PeerChannelEncryptor {
+	pub(crate) fn get_native_ref(&self) -> &'static nativePeerChannelEncryptor {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativePeerChannelEncryptor {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativePeerChannelEncryptor {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_CVec_u8ZNoiseErrorZ
+pub union CResult_CVec_u8ZNoiseErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut crate::c_types::derived::CVec_u8Z,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut NoiseError,
+}
+#[repr(C)]
+/// A CResult_CVec_u8ZNoiseErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::derived::CVec_u8Z on success and a NoiseError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_CVec_u8ZNoiseErrorZ {
+	/// The contents of this CResult_CVec_u8ZNoiseErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_CVec_u8ZNoiseErrorZPtr,
+	/// Whether this CResult_CVec_u8ZNoiseErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_CVec_u8ZNoiseErrorZ in the success state.
+pub extern "C" fn CResult_CVec_u8ZNoiseErrorZ_ok(o: crate::c_types::derived::CVec_u8Z) -> CResult_CVec_u8ZNoiseErrorZ {
+	CResult_CVec_u8ZNoiseErrorZ {
+		contents: CResult_CVec_u8ZNoiseErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_CVec_u8ZNoiseErrorZ in the error state.
+pub extern "C" fn CResult_CVec_u8ZNoiseErrorZ_err(e: NoiseError) -> CResult_CVec_u8ZNoiseErrorZ {
+	CResult_CVec_u8ZNoiseErrorZ {
+		contents: CResult_CVec_u8ZNoiseErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_CVec_u8ZNoiseErrorZ_is_ok(o: &CResult_CVec_u8ZNoiseErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_CVec_u8ZNoiseErrorZ.
+pub extern "C" fn CResult_CVec_u8ZNoiseErrorZ_free(_res: CResult_CVec_u8ZNoiseErrorZ) { }
+impl Drop for CResult_CVec_u8ZNoiseErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_CVec_u8ZNoiseErrorZ {
+	fn from_rust(res: Result<Vec<u8>, NoiseError>) -> Self {
+		match res {
+			Ok(data) => CResult_CVec_u8ZNoiseErrorZ_ok(data.into()),
+			Err(e) => CResult_CVec_u8ZNoiseErrorZ_err(e),
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_PublicKeyNoiseErrorZ
+pub union CResult_PublicKeyNoiseErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut PublicKey,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut NoiseError,
+}
+#[repr(C)]
+/// A CResult_PublicKeyNoiseErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::PublicKey on success and a NoiseError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_PublicKeyNoiseErrorZ {
+	/// The contents of this CResult_PublicKeyNoiseErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_PublicKeyNoiseErrorZPtr,
+	/// Whether this CResult_PublicKeyNoiseErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_PublicKeyNoiseErrorZ in the success state.
+pub extern "C" fn CResult_PublicKeyNoiseErrorZ_ok(o: PublicKey) -> CResult_PublicKeyNoiseErrorZ {
+	CResult_PublicKeyNoiseErrorZ {
+		contents: CResult_PublicKeyNoiseErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_PublicKeyNoiseErrorZ in the error state.
+pub extern "C" fn CResult_PublicKeyNoiseErrorZ_err(e: NoiseError) -> CResult_PublicKeyNoiseErrorZ {
+	CResult_PublicKeyNoiseErrorZ {
+		contents: CResult_PublicKeyNoiseErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_PublicKeyNoiseErrorZ_is_ok(o: &CResult_PublicKeyNoiseErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_PublicKeyNoiseErrorZ.
+pub extern "C" fn CResult_PublicKeyNoiseErrorZ_free(_res: CResult_PublicKeyNoiseErrorZ) { }
+impl Drop for CResult_PublicKeyNoiseErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_u16NoiseErrorZ
+pub union CResult_u16NoiseErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut u16,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut NoiseError,
+}
+#[repr(C)]
+/// A CResult_u16NoiseErrorZ represents the result of a fallible operation,
+/// containing a u16 on success and a NoiseError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_u16NoiseErrorZ {
+	/// The contents of this CResult_u16NoiseErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_u16NoiseErrorZPtr,
+	/// Whether this CResult_u16NoiseErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_u16NoiseErrorZ in the success state.
+pub extern "C" fn CResult_u16NoiseErrorZ_ok(o: u16) -> CResult_u16NoiseErrorZ {
+	CResult_u16NoiseErrorZ {
+		contents: CResult_u16NoiseErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_u16NoiseErrorZ in the error state.
+pub extern "C" fn CResult_u16NoiseErrorZ_err(e: NoiseError) -> CResult_u16NoiseErrorZ {
+	CResult_u16NoiseErrorZ {
+		contents: CResult_u16NoiseErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_u16NoiseErrorZ_is_ok(o: &CResult_u16NoiseErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_u16NoiseErrorZ.
+pub extern "C" fn CResult_u16NoiseErrorZ_free(_res: CResult_u16NoiseErrorZ) { }
+impl Drop for CResult_u16NoiseErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Creates a new PeerChannelEncryptor which initiates a connection to the node with the given
+/// node id.
+///
+/// `ephemeral_key` must be freshly generated from a secure random source for each connection. It
+/// is only taken as a parameter to allow reproducing test vectors.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_new_outbound(mut their_node_id: PublicKey, mut ephemeral_key: SecretKey) -> PeerChannelEncryptor {
+	PeerChannelEncryptor { inner: ObjOps::heap_alloc(nativePeerChannelEncryptor::new_outbound(their_node_id.into_rust(), ephemeral_key.into_rust())), is_owned: true }
+}
+
+/// Creates a new PeerChannelEncryptor which responds to a connection made to us, identified by
+/// `our_node_secret`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_new_inbound(mut our_node_secret: SecretKey) -> PeerChannelEncryptor {
+	PeerChannelEncryptor { inner: ObjOps::heap_alloc(nativePeerChannelEncryptor::new_inbound(our_node_secret.into_rust())), is_owned: true }
+}
+
+/// Generates the 50-byte act one, which the initiator sends first.
+///
+/// Fails with InvalidState unless this is a fresh outbound PeerChannelEncryptor.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_get_act_one(this_arg: &mut PeerChannelEncryptor) -> CResult_CVec_u8ZNoiseErrorZ {
+	CResult_CVec_u8ZNoiseErrorZ::from_rust(this_arg.get_native_mut_ref().get_act_one())
+}
+
+/// Processes the 50-byte act one received by the responder, returning the 50-byte act two to
+/// send back.
+///
+/// `ephemeral_key` must be freshly generated from a secure random source for each connection. It
+/// is only taken as a parameter to allow reproducing test vectors.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_process_act_one(this_arg: &mut PeerChannelEncryptor, mut act_one: u8slice, mut ephemeral_key: SecretKey) -> CResult_CVec_u8ZNoiseErrorZ {
+	CResult_CVec_u8ZNoiseErrorZ::from_rust(this_arg.get_native_mut_ref().process_act_one(act_one.to_slice(), ephemeral_key.into_rust()))
+}
+
+/// Processes the 50-byte act two received by the initiator, returning the 66-byte act three to
+/// send back. `our_node_secret` is the initiator's static key, which is revealed to the
+/// responder in act three.
+///
+/// Once this succeeds, the handshake is complete on the initiator's side and messages may be
+/// encrypted and decrypted.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_process_act_two(this_arg: &mut PeerChannelEncryptor, mut act_two: u8slice, mut our_node_secret: SecretKey) -> CResult_CVec_u8ZNoiseErrorZ {
+	CResult_CVec_u8ZNoiseErrorZ::from_rust(this_arg.get_native_mut_ref().process_act_two(act_two.to_slice(), our_node_secret.into_rust()))
+}
+
+/// Processes the 66-byte act three received by the responder, returning the initiator's node
+/// id.
+///
+/// Once this succeeds, the handshake is complete on the responder's side and messages may be
+/// encrypted and decrypted.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_process_act_three(this_arg: &mut PeerChannelEncryptor, mut act_three: u8slice) -> CResult_PublicKeyNoiseErrorZ {
+	match this_arg.get_native_mut_ref().process_act_three(act_three.to_slice()) {
+		Ok(their_node_id) => CResult_PublicKeyNoiseErrorZ_ok(PublicKey::from_rust(&their_node_id)),
+		Err(e) => CResult_PublicKeyNoiseErrorZ_err(e),
+	}
+}
+
+/// Returns true once the handshake has completed and messages may be encrypted and decrypted.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_is_ready_for_encryption(this_arg: &PeerChannelEncryptor) -> bool {
+	this_arg.get_native_ref().is_ready_for_encryption()
+}
+
+/// Encrypts a message of at most 65535 bytes, returning the encrypted length header followed by
+/// the encrypted message, i.e. 34 bytes more than the message itself.
+///
+/// `msg` is usually a serialized Lightning message starting with its two-byte type, but any
+/// bytes may be sent.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_encrypt_message(this_arg: &mut PeerChannelEncryptor, mut msg: u8slice) -> CResult_CVec_u8ZNoiseErrorZ {
+	CResult_CVec_u8ZNoiseErrorZ::from_rust(this_arg.get_native_mut_ref().encrypt_message(msg.to_slice()))
+}
+
+/// Decrypts the 18-byte length header which precedes each message, returning the length of the
+/// message. The next `length + 16` bytes should then be passed to
+/// `PeerChannelEncryptor_decrypt_message`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_decrypt_length_header(this_arg: &mut PeerChannelEncryptor, mut header: u8slice) -> CResult_u16NoiseErrorZ {
+	match this_arg.get_native_mut_ref().decrypt_length_header(header.to_slice()) {
+		Ok(len) => CResult_u16NoiseErrorZ_ok(len),
+		Err(e) => CResult_u16NoiseErrorZ_err(e),
+	}
+}
+
+/// Decrypts a message, including its 16-byte authentication tag, following a length header
+/// decrypted with `PeerChannelEncryptor_decrypt_length_header`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerChannelEncryptor_decrypt_message(this_arg: &mut PeerChannelEncryptor, mut msg: u8slice) -> CResult_CVec_u8ZNoiseErrorZ {
+	CResult_CVec_u8ZNoiseErrorZ::from_rust(this_arg.get_native_mut_ref().decrypt_message(msg.to_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::hashes::hex::FromHex;
+
+	// The test vectors from BOLT 8, Appendix A.
+	const ACT_ONE: &str = "00036360e856310ce5d294e8be33fc807077dc56ac80d95d9cd4ddbd21325eff73f70df6086551151f58b8afe6c195782c6a";
+	const ACT_TWO: &str = "0002466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f276e2470b93aac583c9ef6eafca3f730ae";
+	const ACT_THREE: &str = "00b9e3a702e93e3a9948c2ed6e5fd7590a6e1c3a0344cfc9d5b57357049aa22355361aa02e55a8fc28fef5bd6d71ad0c38228dc68b1c466263b47fdf31e560e139ba";
+	const SK: &str = "969ab31b4d288cedf6218839b27a3e2140827047f2c0f01bf5c04435d43511a9";
+	const RK: &str = "bb9020b8965f4df047e07f955f3c4b88418984aadc5cdb35096b9ea8fa5c3442";
+
+	fn hex(s: &str) -> Vec<u8> { Vec::from_hex(s).unwrap() }
+	fn secret(byte: u8) -> SecpSecretKey { SecpSecretKey::from_slice(&[byte; 32]).unwrap() }
+	fn node_id(byte: u8) -> SecpPublicKey { SecpPublicKey::from_secret_key(&Secp256k1::signing_only(), &secret(byte)) }
+
+	fn initiator() -> nativePeerChannelEncryptor {
+		let mut initiator = nativePeerChannelEncryptor::new_outbound(node_id(0x21), secret(0x12));
+		assert_eq!(initiator.get_act_one().unwrap(), hex(ACT_ONE));
+		assert_eq!(initiator.process_act_two(&hex(ACT_TWO), secret(0x11)).unwrap(), hex(ACT_THREE));
+		initiator
+	}
+
+	fn responder_after_act_one() -> nativePeerChannelEncryptor {
+		let mut responder = nativePeerChannelEncryptor::new_inbound(secret(0x21));
+		assert_eq!(responder.process_act_one(&hex(ACT_ONE), secret(0x22)).unwrap(), hex(ACT_TWO));
+		responder
+	}
+
+	#[test]
+	fn initiator_handshake() {
+		let initiator = initiator();
+		match initiator.state {
+			NoiseState::Finished { sk, rk, sn: 0, rn: 0, .. } => {
+				assert_eq!(&sk[..], &hex(SK)[..]);
+				assert_eq!(&rk[..], &hex(RK)[..]);
+			},
+			_ => panic!("the handshake should have finished"),
+		}
+	}
+
+	#[test]
+	fn responder_handshake() {
+		let mut responder = responder_after_act_one();
+		assert_eq!(responder.process_act_three(&hex(ACT_THREE)).unwrap(), node_id(0x11));
+		match responder.state {
+			NoiseState::Finished { sk, rk, .. } => {
+				assert_eq!(&sk[..], &hex(RK)[..]);
+				assert_eq!(&rk[..], &hex(SK)[..]);
+			},
+			_ => panic!("the handshake should have finished"),
+		}
+	}
+
+	#[test]
+	fn initiator_act_two_failures() {
+		let mut bad_version = hex(ACT_TWO);
+		bad_version[0] = 1;
+		let mut bad_key = hex(ACT_TWO);
+		bad_key[1] = 4;
+		let mut bad_mac = hex(ACT_TWO);
+		*bad_mac.last_mut().unwrap() ^= 1;
+		for (act_two, err) in [(bad_version, NoiseError::UnknownVersion), (bad_key, NoiseError::InvalidPublicKey),
+				(bad_mac, NoiseError::DecryptionFailed), (hex(&ACT_TWO[2..]), NoiseError::InvalidLength)].iter() {
+			let mut initiator = nativePeerChannelEncryptor::new_outbound(node_id(0x21), secret(0x12));
+			initiator.get_act_one().unwrap();
+			assert_eq!(initiator.process_act_two(act_two, secret(0x11)), Err(*err));
+			assert_eq!(initiator.encrypt_message(b"hello"), Err(NoiseError::InvalidState));
+		}
+	}
+
+	#[test]
+	fn responder_act_one_failures() {
+		let mut bad_version = hex(ACT_ONE);
+		bad_version[0] = 1;
+		let mut bad_key = hex(ACT_ONE);
+		bad_key[1] = 4;
+		let mut bad_mac = hex(ACT_ONE);
+		*bad_mac.last_mut().unwrap() ^= 1;
+		for (act_one, err) in [(bad_version, NoiseError::UnknownVersion), (bad_key, NoiseError::InvalidPublicKey),
+				(bad_mac, NoiseError::DecryptionFailed)].iter() {
+			let mut responder = nativePeerChannelEncryptor::new_inbound(secret(0x21));
+			assert_eq!(responder.process_act_one(act_one, secret(0x22)), Err(*err));
+			assert_eq!(responder.process_act_one(&hex(ACT_ONE), secret(0x22)), Err(NoiseError::InvalidState));
+		}
+	}
+
+	#[test]
+	fn responder_act_three_failures() {
+		let mut bad_version = hex(ACT_THREE);
+		bad_version[0] = 1;
+		// Corrupting the encrypted static key fails its MAC, and corrupting the final tag fails
+		// the second decryption.
+		let mut bad_key_mac = hex(ACT_THREE);
+		bad_key_mac[1] ^= 1;
+		let mut bad_mac = hex(ACT_THREE);
+		*bad_mac.last_mut().unwrap() ^= 1;
+		for (act_three, err) in [(bad_version, NoiseError::UnknownVersion), (bad_key_mac, NoiseError::DecryptionFailed),
+				(bad_mac, NoiseError::DecryptionFailed), (hex(&ACT_THREE[2..]), NoiseError::InvalidLength)].iter() {
+			let mut responder = responder_after_act_one();
+			assert_eq!(responder.process_act_three(act_three), Err(*err));
+			assert!(!responder.is_ready_for_encryption());
+		}
+	}
+
+	#[test]
+	fn message_encryption_and_key_rotation() {
+		let mut initiator = initiator();
+		let mut responder = responder_after_act_one();
+		responder.process_act_three(&hex(ACT_THREE)).unwrap();
+
+		let expected = [
+			(0, "cf2b30ddf0cf3f80e7c35a6e6730b59fe802473180f396d88a8fb0db8cbcf25d2f214cf9ea1d95"),
+			(1, "72887022101f0b6753e0c7de21657d35a4cb2a1f5cde2650528bbc8f837d0f0d7ad833b1a256a1"),
+			(500, "178cb9d7387190fa34db9c2d50027d21793c9bc2d40b1e14dcf30ebeeeb220f48364f7a4c68bf8"),
+			(501, "1b186c57d44eb6de4c057c49940d79bb838a145cb528d6e8fd26dbe50a60ca2c104b56b60e45bd"),
+			(1000, "4a2f3cc3b5e78ddb83dcb426d9863d9d9a723b0337c89dd0b005d89f8d3c05c52b76b29b740f09"),
+			(1001, "2ecd8c8a5629d0d02ab457a0fdd0f7b90a192cd46be5ecb6ca570bfc5e268338b1a16cf4ef2d36"),
+		];
+		for i in 0..1002 {
+			let encrypted = initiator.encrypt_message(b"hello").unwrap();
+			if let Some((_, vector)) = expected.iter().find(|(idx, _)| *idx == i) {
+				assert_eq!(encrypted, hex(vector));
+			}
+			let len = responder.decrypt_length_header(&encrypted[..NOISE_LENGTH_HEADER_LEN]).unwrap();
+			assert_eq!(len, 5);
+			assert_eq!(responder.decrypt_message(&encrypted[NOISE_LENGTH_HEADER_LEN..]).unwrap(), b"hello");
+		}
+	}
+
+	#[test]
+	fn corrupted_message_fails_the_connection() {
+		let mut initiator = initiator();
+		let mut responder = responder_after_act_one();
+		responder.process_act_three(&hex(ACT_THREE)).unwrap();
+
+		let mut encrypted = initiator.encrypt_message(b"hello").unwrap();
+		*encrypted.last_mut().unwrap() ^= 1;
+		assert_eq!(responder.decrypt_length_header(&encrypted[..NOISE_LENGTH_HEADER_LEN]), Ok(5));
+		assert_eq!(responder.decrypt_message(&encrypted[NOISE_LENGTH_HEADER_LEN..]), Err(NoiseError::DecryptionFailed));
+		assert!(!responder.is_ready_for_encryption());
+		assert_eq!(responder.encrypt_message(b"hello"), Err(NoiseError::InvalidState));
+	}
+
+	#[test]
+	fn encryption_before_the_handshake_and_oversized_messages() {
+		let mut initiator = nativePeerChannelEncryptor::new_outbound(node_id(0x21), secret(0x12));
+		assert_eq!(initiator.encrypt_message(b"hello"), Err(NoiseError::InvalidState));
+		assert_eq!(initiator.decrypt_length_header(&[0; NOISE_LENGTH_HEADER_LEN]), Err(NoiseError::InvalidState));
+
+		let mut initiator = self::initiator();
+		assert_eq!(initiator.encrypt_message(&vec![0; u16::max_value() as usize + 1]), Err(NoiseError::MessageTooLong));
+		assert_eq!(initiator.encrypt_message(&vec![0; u16::max_value() as usize]).unwrap().len(),
+			NOISE_LENGTH_HEADER_LEN + u16::max_value() as usize + TAG_LEN);
+	}
+}
@@ -0,0 +1,301 @@
+//! A non-blocking alternative to `ChannelManager_await_persistable_update`, for hosts which cannot
+//! dedicate a blocked thread of their own, such as Node.js addons, GUI main loops or embedded
+//! RTOS ports.
+//!
+//! A [`ChannelManagerNotifier`] is signalled whenever the `ChannelManager` needs persisting. LDK
+//! signals this on every `ChannelManager` state change, which includes all changes generating
+//! new `MessageSendEvent`s or `Event`s, so once signalled the host should call
+//! `ChannelManagerNotifier_clear`, then persist the `ChannelManager`, process pending events and
+//! call `PeerManager_process_events`. Sources outside the `ChannelManager`, such as
+//! `ChainMonitor` events, can signal the same notifier with `ChannelManagerNotifier_notify`.
+//!
+//! The host learns of a signal either by polling the file descriptor returned by
+//! `ChannelManagerNotifier_get_fd`, which is readable while signalled, or through a
+//! [`WakeupCallback`].
+
+use std::ffi::c_void;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::c_types::*;
+use crate::extensions::readiness::ReadinessFd;
+use crate::lightning::ln::channelmanager::{ChannelManager, nativeChannelManager};
+
+/// Wakes up the host's event loop when a [`ChannelManagerNotifier`] is signalled.
+#[repr(C)]
+pub struct WakeupCallback {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Called once each time the notifier goes from cleared to signalled.
+	///
+	/// This is called from an LDK-internal thread, or from whichever thread calls
+	/// `ChannelManagerNotifier_notify`, so it should only schedule work on the host's event loop
+	/// (eg with `uv_async_send` or `g_main_context_wakeup`) rather than do it directly.
+	pub wake: extern "C" fn (this_arg: *const c_void),
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for WakeupCallback {}
+unsafe impl Sync for WakeupCallback {}
+#[no_mangle]
+pub(crate) extern "C" fn WakeupCallback_clone_fields(orig: &WakeupCallback) -> WakeupCallback {
+	WakeupCallback {
+		this_arg: orig.this_arg,
+		wake: Clone::clone(&orig.wake),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn WakeupCallback_free(this_ptr: WakeupCallback) { }
+impl Drop for WakeupCallback {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+/// How long the notifier's thread waits on the `ChannelManager` before checking whether it should
+/// stop, bounding how long freeing a notifier blocks.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+struct NotifierState {
+	readiness: ReadinessFd,
+	callback: Option<WakeupCallback>,
+	stop: AtomicBool,
+}
+
+impl NotifierState {
+	fn signal(&self) {
+		if !self.readiness.set_ready(true) {
+			if let Some(callback) = &self.callback {
+				(callback.wake)(callback.this_arg);
+			}
+		}
+	}
+}
+
+pub(crate) struct nativeChannelManagerNotifier {
+	state: Arc<NotifierState>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl nativeChannelManagerNotifier {
+	fn start(channel_manager: &'static nativeChannelManager, callback: Option<WakeupCallback>) -> Self {
+		let state = Arc::new(NotifierState { readiness: ReadinessFd::new(), callback, stop: AtomicBool::new(false) });
+		let thread_state = Arc::clone(&state);
+		// The thread sleeps on the ChannelManager's persistence notifier and signals the readiness
+		// fd as soon as it is woken, so an update reaches the host without any polling delay. The
+		// wait is bounded so that the thread notices when it is stopped.
+		let thread = std::thread::spawn(move || {
+			while !thread_state.stop.load(Ordering::Acquire) {
+				if channel_manager.await_persistable_update_timeout(STOP_CHECK_INTERVAL) {
+					thread_state.signal();
+				}
+			}
+		});
+		Self { state, thread: Some(thread) }
+	}
+}
+
+impl Drop for nativeChannelManagerNotifier {
+	fn drop(&mut self) {
+		self.state.stop.store(true, Ordering::Release);
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+	}
+}
+
+/// Signals the host whenever a `ChannelManager` needs persisting, without blocking any of the
+/// host's threads. See `ChannelManagerNotifier_new`.
+#[must_use]
+#[repr(C)]
+pub struct ChannelManagerNotifier {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeChannelManagerNotifier,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for ChannelManagerNotifier {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeChannelManagerNotifier>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the ChannelManagerNotifier, if is_owned is set and inner is non-NULL.
+///
+/// This stops the notifier's thread, blocking for up to 100 milliseconds until it notices.
+#[no_mangle]
+pub extern "C" fn ChannelManagerNotifier_free(this_obj: ChannelManagerNotifier) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn ChannelManagerNotifier_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeChannelManagerNotifier); }
+}
+#[allow(unused)]
+impl ChannelManagerNotifier {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeChannelManagerNotifier {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeChannelManagerNotifier {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeChannelManagerNotifier {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Starts watching `channel_manager` for updates which need persisting, signalling the returned
+/// notifier's file descriptor (see `ChannelManagerNotifier_get_fd`) when one occurs.
+///
+/// The notifier waits on the `ChannelManager` from an internal thread. LDK only guarantees to
+/// wake one waiter per update, so the `ChannelManager` must not also be waited on with
+/// `ChannelManager_await_persistable_update` or a `BackgroundProcessor`.
+///
+/// The notifier must be freed before `channel_manager` is.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn ChannelManagerNotifier_new(channel_manager: &ChannelManager) -> ChannelManagerNotifier {
+	ChannelManagerNotifier { inner: ObjOps::heap_alloc(nativeChannelManagerNotifier::start(channel_manager.get_native_ref(), None)), is_owned: true }
+}
+
+/// Creates a notifier as `ChannelManagerNotifier_new` does, which additionally calls `callback`
+/// each time it is signalled after having been cleared.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn ChannelManagerNotifier_new_with_callback(channel_manager: &ChannelManager, mut callback: WakeupCallback) -> ChannelManagerNotifier {
+	ChannelManagerNotifier { inner: ObjOps::heap_alloc(nativeChannelManagerNotifier::start(channel_manager.get_native_ref(), Some(callback))), is_owned: true }
+}
+
+/// Returns a file descriptor which is readable while the notifier is signalled, or -1 if none
+/// could be created on this platform.
+///
+/// The file descriptor is owned by the notifier and closed when it is freed. It must only be
+/// polled, never read from or written to; use `ChannelManagerNotifier_clear` instead.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn ChannelManagerNotifier_get_fd(this_arg: &ChannelManagerNotifier) -> i32 {
+	this_arg.get_native_ref().state.readiness.fd()
+}
+
+/// Returns whether the notifier is signalled, without clearing it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn ChannelManagerNotifier_is_signalled(this_arg: &ChannelManagerNotifier) -> bool {
+	this_arg.get_native_ref().state.readiness.is_ready()
+}
+
+/// Clears the notifier, returning whether it was signalled.
+///
+/// Call this before persisting the `ChannelManager` and processing events, so that updates which
+/// happen while doing so signal the notifier again rather than being missed.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn ChannelManagerNotifier_clear(this_arg: &ChannelManagerNotifier) -> bool {
+	this_arg.get_native_ref().state.readiness.set_ready(false)
+}
+
+/// Signals the notifier, as if the `ChannelManager` needed persisting. Useful for waking the
+/// host when events are pending from elsewhere, such as the `ChainMonitor`.
+#[no_mangle]
+pub extern "C" fn ChannelManagerNotifier_notify(this_arg: &ChannelManagerNotifier) {
+	this_arg.get_native_ref().state.signal()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::AtomicUsize;
+	use std::time::Instant;
+	use bitcoin::secp256k1::Secp256k1;
+	use bitcoin::secp256k1::key::{PublicKey as SecpPublicKey, SecretKey as SecpSecretKey};
+	use lightning::ln::msgs::ChannelMessageHandler;
+	use crate::extensions::test_utils::channel_manager;
+
+	fn counting_callback() -> (WakeupCallback, Arc<AtomicUsize>) {
+		let wakes = Arc::new(AtomicUsize::new(0));
+		(WakeupCallback {
+			this_arg: Arc::into_raw(Arc::clone(&wakes)) as *mut c_void,
+			wake: Counter_wake,
+			free: Some(Counter_free),
+		}, wakes)
+	}
+	extern "C" fn Counter_wake(this_arg: *const c_void) {
+		unsafe { &*(this_arg as *const AtomicUsize) }.fetch_add(1, Ordering::AcqRel);
+	}
+	extern "C" fn Counter_free(this_arg: *mut c_void) {
+		let _ = unsafe { Arc::from_raw(this_arg as *const AtomicUsize) };
+	}
+
+	/// Triggers a persistable update without changing any state.
+	fn trigger_update(channel_manager: &ChannelManager) {
+		let peer = SecpPublicKey::from_secret_key(&Secp256k1::new(), &SecpSecretKey::from_slice(&[7; 32]).unwrap());
+		channel_manager.get_native_ref().peer_disconnected(&peer, false);
+	}
+
+	fn wait_for_signal(notifier: &ChannelManagerNotifier) {
+		let start = Instant::now();
+		while !ChannelManagerNotifier_is_signalled(notifier) {
+			assert!(start.elapsed() < Duration::from_secs(10), "Notifier was not signalled");
+			std::thread::sleep(Duration::from_millis(10));
+		}
+	}
+
+	#[test]
+	fn notify_wakes_once_until_cleared() {
+		let channel_manager = channel_manager(0);
+		let (callback, wakes) = counting_callback();
+		let notifier = ChannelManagerNotifier_new_with_callback(&channel_manager, callback);
+		assert!(!ChannelManagerNotifier_is_signalled(&notifier));
+
+		ChannelManagerNotifier_notify(&notifier);
+		ChannelManagerNotifier_notify(&notifier);
+		assert!(ChannelManagerNotifier_is_signalled(&notifier));
+		assert_eq!(wakes.load(Ordering::Acquire), 1);
+
+		assert!(ChannelManagerNotifier_clear(&notifier));
+		assert!(!ChannelManagerNotifier_clear(&notifier));
+		ChannelManagerNotifier_notify(&notifier);
+		assert_eq!(wakes.load(Ordering::Acquire), 2);
+	}
+
+	#[test]
+	fn channel_manager_updates_signal_the_notifier() {
+		let channel_manager = channel_manager(0);
+		let notifier = ChannelManagerNotifier_new(&channel_manager);
+		trigger_update(&channel_manager);
+		wait_for_signal(&notifier);
+
+		assert!(ChannelManagerNotifier_clear(&notifier));
+		trigger_update(&channel_manager);
+		wait_for_signal(&notifier);
+	}
+
+	#[test]
+	fn freeing_the_notifier_does_not_update_the_channel_manager() {
+		let channel_manager = channel_manager(0);
+		let notifier = ChannelManagerNotifier_new(&channel_manager);
+		let start = Instant::now();
+		ChannelManagerNotifier_free(notifier);
+		assert!(start.elapsed() < STOP_CHECK_INTERVAL * 10);
+		assert!(!channel_manager.get_native_ref().await_persistable_update_timeout(Duration::from_millis(1)));
+	}
+}
@@ -0,0 +1,970 @@
+//! A persisted record of issued invoices and outbound payments, eg for a wallet's transaction
+//! history.
+//!
+//! Invoices are recorded with `PaymentStore_record_invoice` once created, and outbound payments
+//! are recorded by making them through `PaymentStore_pay_invoice`,
+//! `PaymentStore_pay_zero_value_invoice` or `PaymentStore_pay_pubkey` rather than the
+//! `InvoicePayer` methods of the same name, or through the `PaymentStore_retrying_*` equivalents
+//! for a `RetryingInvoicePayer`. Statuses are updated from payment events by the `EventHandler`
+//! returned by `PaymentStore_event_handler`, which should be the one passed to `InvoicePayer_new`
+//! (or `RetryingInvoicePayer_new`) so that only path failures which will not be retried reach it.
+//!
+//! An outbound payment is recorded before it is sent, so events which are handled before the
+//! payment call returns are applied to it as well. As those events may only carry the payment
+//! hash, only one payment per payment hash may be in the process of being sent at a time. Inbound payments are marked received by
+//! `Event::PaymentReceived` and only succeed once claimed with `PaymentStore_claim_funds` (or
+//! reported as claimed with `PaymentStore_payment_claimed`).
+//!
+//! Inbound payments are stored in a [`KVStore`] under the `inbound_payments` namespace, keyed by
+//! the hex-encoded payment hash, and outbound payments under `outbound_payments`, keyed by the
+//! hex-encoded payment id.
+//!
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use bitcoin::hashes::Hash;
+use bitcoin::hashes::hex::{FromHex, ToHex};
+use bitcoin::hashes::sha256::Hash as Sha256;
+
+use lightning::ln::msgs::DecodeError;
+use lightning::util::events::{Event as nativeEvent, PaymentPurpose};
+use lightning::util::logger::{Level, Logger as _};
+use lightning::util::ser::{Readable, Writeable, Writer};
+use lightning_invoice::InvoiceDescription;
+
+use crate::c_types::*;
+use crate::c_types::derived::{COption_u64Z, CResult_NoneErrorZ, CResult_PaymentIdPaymentErrorZ, CResult_PaymentIdPaymentErrorZ_err};
+use crate::extensions::kv_store::{KVStore, none_result_from_rust};
+use crate::extensions::now_secs;
+use crate::extensions::retrying_payer::*;
+use crate::lightning::ln::channelmanager::ChannelManager;
+use crate::lightning::util::events::{Event, EventHandler};
+use crate::lightning::util::logger::Logger;
+use crate::lightning_invoice::Invoice;
+use crate::lightning_invoice::payment::*;
+
+/// The namespace under which inbound payments are stored.
+const INBOUND_PAYMENTS_NAMESPACE: &str = "inbound_payments";
+/// The namespace under which outbound payments are stored.
+const OUTBOUND_PAYMENTS_NAMESPACE: &str = "outbound_payments";
+
+const SERIALIZATION_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// Whether a payment was received or sent.
+pub enum PaymentDirection {
+	/// The payment was (or will be) received by us.
+	Inbound,
+	/// The payment was sent by us.
+	Outbound,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The status of a recorded payment.
+pub enum PaymentStatus {
+	/// The invoice has not been paid yet, or the outbound payment is still in flight.
+	Pending,
+	/// The inbound payment was claimed, or the outbound payment reached its destination.
+	Succeeded,
+	/// The outbound payment failed and will not be retried.
+	Failed,
+	/// The invoice expired without being paid. Only reported for inbound payments.
+	Expired,
+	/// The inbound payment was received (i.e. `Event::PaymentReceived` was handled for it) but
+	/// has not been claimed yet. Only reported for inbound payments.
+	Received,
+}
+
+impl PaymentStatus {
+	fn to_byte(&self) -> u8 {
+		match self {
+			PaymentStatus::Pending => 0,
+			PaymentStatus::Succeeded => 1,
+			PaymentStatus::Failed => 2,
+			PaymentStatus::Expired => 3,
+			PaymentStatus::Received => 4,
+		}
+	}
+	fn from_byte(b: u8) -> Result<Self, DecodeError> {
+		match b {
+			0 => Ok(PaymentStatus::Pending),
+			1 => Ok(PaymentStatus::Succeeded),
+			2 => Ok(PaymentStatus::Failed),
+			3 => Ok(PaymentStatus::Expired),
+			4 => Ok(PaymentStatus::Received),
+			_ => Err(DecodeError::InvalidValue),
+		}
+	}
+}
+
+/// A recorded payment, as returned by the `PaymentStore` query methods.
+#[repr(C)]
+pub struct PaymentDetails {
+	/// Whether the payment is inbound or outbound.
+	pub direction: PaymentDirection,
+	/// The current status of the payment.
+	pub status: PaymentStatus,
+	/// The payment hash.
+	pub payment_hash: ThirtyTwoBytes,
+	/// The payment id returned when the payment was sent.
+	///
+	/// Note that this is all-0s for inbound payments.
+	pub payment_id: ThirtyTwoBytes,
+	/// The payment preimage, once known.
+	///
+	/// Note that this may be all-0s to represent None
+	pub payment_preimage: ThirtyTwoBytes,
+	/// The payment secret, if the payment was made to or received for an invoice.
+	///
+	/// Note that this may be all-0s to represent None
+	pub payment_secret: ThirtyTwoBytes,
+	/// For inbound payments, the amount received once received and the invoice amount, if any,
+	/// before. For outbound payments, the amount sent to the destination, excluding fees.
+	pub amount_msat: COption_u64Z,
+	/// The fees paid to intermediate hops, once an outbound payment succeeded.
+	pub fee_paid_msat: COption_u64Z,
+	/// The description of the invoice, or an empty string if the invoice only contained a
+	/// description hash or the payment was not made for an invoice.
+	pub description: Str,
+	/// When the payment was recorded, in seconds since the UNIX epoch.
+	pub created_at: u64,
+	/// When the invoice expires, in seconds since the UNIX epoch, or 0 for outbound payments and
+	/// payments received without an invoice.
+	pub expires_at: u64,
+	/// When the status of the payment last changed, in seconds since the UNIX epoch.
+	pub updated_at: u64,
+}
+#[no_mangle]
+/// Frees any resources used by the PaymentDetails.
+pub extern "C" fn PaymentDetails_free(_res: PaymentDetails) { }
+
+#[repr(C)]
+/// A dynamically-allocated array of PaymentDetailss of arbitrary size.
+/// This corresponds to std::vector in C++
+pub struct CVec_PaymentDetailsZ {
+	/// The elements in the array.
+	/// If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
+	pub data: *mut PaymentDetails,
+	/// The number of elements pointed to by `data`.
+	pub datalen: usize
+}
+impl From<Vec<PaymentDetails>> for CVec_PaymentDetailsZ {
+	fn from(v: Vec<PaymentDetails>) -> Self {
+		let datalen = v.len();
+		let data = Box::into_raw(v.into_boxed_slice());
+		Self { datalen, data: unsafe { (*data).as_mut_ptr() } }
+	}
+}
+#[no_mangle]
+/// Frees the buffer pointed to by `data` if `datalen` is non-0.
+pub extern "C" fn CVec_PaymentDetailsZ_free(_res: CVec_PaymentDetailsZ) { }
+impl Drop for CVec_PaymentDetailsZ {
+	fn drop(&mut self) {
+		if self.datalen == 0 { return; }
+		unsafe { Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.datalen)) };
+	}
+}
+
+#[repr(C)]
+/// An enum which can either contain a PaymentDetails or not
+pub enum COption_PaymentDetailsZ {
+	/// When we're in this state, this COption_PaymentDetailsZ contains a PaymentDetails
+	Some(PaymentDetails),
+	/// When we're in this state, this COption_PaymentDetailsZ contains nothing
+	None
+}
+#[no_mangle]
+/// Frees any resources associated with the PaymentDetails, if we are in the Some state
+pub extern "C" fn COption_PaymentDetailsZ_free(_res: COption_PaymentDetailsZ) { }
+
+#[derive(Clone)]
+struct PaymentRecord {
+	payment_hash: [u8; 32],
+	/// Only set for outbound payments.
+	payment_id: Option<[u8; 32]>,
+	payment_preimage: Option<[u8; 32]>,
+	payment_secret: Option<[u8; 32]>,
+	amount_msat: Option<u64>,
+	fee_paid_msat: Option<u64>,
+	description: String,
+	created_at: u64,
+	expires_at: Option<u64>,
+	updated_at: u64,
+	status: PaymentStatus,
+}
+
+impl Writeable for PaymentRecord {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), std::io::Error> {
+		SERIALIZATION_VERSION.write(w)?;
+		self.payment_hash.write(w)?;
+		self.payment_id.write(w)?;
+		self.payment_preimage.write(w)?;
+		self.payment_secret.write(w)?;
+		self.amount_msat.write(w)?;
+		self.fee_paid_msat.write(w)?;
+		self.description.as_bytes().to_vec().write(w)?;
+		self.created_at.write(w)?;
+		self.expires_at.write(w)?;
+		self.updated_at.write(w)?;
+		self.status.to_byte().write(w)
+	}
+}
+
+impl Readable for PaymentRecord {
+	fn read<R: std::io::Read>(r: &mut R) -> Result<Self, DecodeError> {
+		let ver: u8 = Readable::read(r)?;
+		if ver > SERIALIZATION_VERSION { return Err(DecodeError::UnknownVersion); }
+		let payment_hash = Readable::read(r)?;
+		let payment_id = Readable::read(r)?;
+		let payment_preimage = Readable::read(r)?;
+		let payment_secret = Readable::read(r)?;
+		let amount_msat = Readable::read(r)?;
+		let fee_paid_msat = Readable::read(r)?;
+		let description_bytes: Vec<u8> = Readable::read(r)?;
+		let description = String::from_utf8(description_bytes).map_err(|_| DecodeError::InvalidValue)?;
+		let created_at = Readable::read(r)?;
+		let expires_at = Readable::read(r)?;
+		let updated_at = Readable::read(r)?;
+		let status = PaymentStatus::from_byte(Readable::read(r)?)?;
+		Ok(Self {
+			payment_hash, payment_id, payment_preimage, payment_secret, amount_msat, fee_paid_msat,
+			description, created_at, expires_at, updated_at, status,
+		})
+	}
+}
+
+impl PaymentRecord {
+	fn to_details(&self, now: u64) -> PaymentDetails {
+		let status = match (self.status, self.expires_at) {
+			(PaymentStatus::Pending, Some(expires_at)) if self.payment_id.is_none() && now > expires_at => PaymentStatus::Expired,
+			(status, _) => status,
+		};
+		PaymentDetails {
+			direction: if self.payment_id.is_some() { PaymentDirection::Outbound } else { PaymentDirection::Inbound },
+			status,
+			payment_hash: ThirtyTwoBytes { data: self.payment_hash },
+			payment_id: ThirtyTwoBytes { data: self.payment_id.unwrap_or([0; 32]) },
+			payment_preimage: ThirtyTwoBytes { data: self.payment_preimage.unwrap_or([0; 32]) },
+			payment_secret: ThirtyTwoBytes { data: self.payment_secret.unwrap_or([0; 32]) },
+			amount_msat: match self.amount_msat { Some(amt) => COption_u64Z::Some(amt), None => COption_u64Z::None },
+			fee_paid_msat: match self.fee_paid_msat { Some(fee) => COption_u64Z::Some(fee), None => COption_u64Z::None },
+			description: self.description.clone().into(),
+			created_at: self.created_at,
+			expires_at: self.expires_at.unwrap_or(0),
+			updated_at: self.updated_at,
+		}
+	}
+}
+
+fn invoice_description(invoice: &lightning_invoice::Invoice) -> String {
+	match invoice.description() {
+		InvoiceDescription::Direct(description) => description.clone().into_inner(),
+		InvoiceDescription::Hash(_) => String::new(),
+	}
+}
+
+pub(crate) struct nativePaymentStore {
+	store: KVStore,
+	logger: Logger,
+	inbound: Mutex<HashMap<[u8; 32], PaymentRecord>>,
+	outbound: Mutex<HashMap<[u8; 32], PaymentRecord>>,
+	/// Outbound payments whose payment call has not returned yet, and whose payment id is thus
+	/// unknown, by payment hash. Events for them are applied here until they move to `outbound`.
+	/// A second payment for a payment hash in here is refused, as events could not be told apart.
+	/// If both are locked, `outbound` must be locked first.
+	sending: Mutex<HashMap<[u8; 32], PaymentRecord>>,
+}
+
+fn load_records(store: &KVStore, namespace: &str) -> Result<HashMap<[u8; 32], PaymentRecord>, std::io::Error> {
+	let mut records = HashMap::new();
+	for key in store.list(namespace)? {
+		let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid stored payment");
+		let id = <[u8; 32]>::from_hex(&key).map_err(|_| invalid())?;
+		let data = store.read(namespace, &key)?;
+		records.insert(id, PaymentRecord::read(&mut Cursor::new(data)).map_err(|_| invalid())?);
+	}
+	Ok(records)
+}
+
+impl nativePaymentStore {
+	fn load(store: KVStore, logger: Logger) -> Result<Self, std::io::Error> {
+		let inbound = load_records(&store, INBOUND_PAYMENTS_NAMESPACE)?;
+		let outbound = load_records(&store, OUTBOUND_PAYMENTS_NAMESPACE)?;
+		Ok(Self { store, logger, inbound: Mutex::new(inbound), outbound: Mutex::new(outbound), sending: Mutex::new(HashMap::new()) })
+	}
+
+	fn persist(&self, namespace: &str, id: &[u8; 32], record: &PaymentRecord) -> Result<(), std::io::Error> {
+		self.store.write(namespace, &id.to_hex(), &record.encode())
+	}
+
+	/// Persists a record updated from an event, which can only be logged if it fails.
+	fn persist_logged(&self, namespace: &str, id: &[u8; 32], record: &PaymentRecord) {
+		if let Err(e) = self.persist(namespace, id, record) {
+			log_record!(self.logger, Level::Error, "Failed to persist payment {}: {}", id.to_hex(), e);
+		}
+	}
+
+	fn record_invoice(&self, invoice: &lightning_invoice::Invoice) -> Result<(), std::io::Error> {
+		let now = now_secs();
+		let created_at = invoice.timestamp().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(now);
+		let payment_hash = invoice.payment_hash().into_inner();
+		let record = PaymentRecord {
+			payment_hash,
+			payment_id: None,
+			payment_preimage: None,
+			payment_secret: Some(invoice.payment_secret().0),
+			amount_msat: invoice.amount_milli_satoshis(),
+			fee_paid_msat: None,
+			description: invoice_description(invoice),
+			created_at,
+			expires_at: Some(created_at + invoice.expiry_time().as_secs()),
+			updated_at: now,
+			status: PaymentStatus::Pending,
+		};
+		let mut inbound = self.inbound.lock().unwrap();
+		self.persist(INBOUND_PAYMENTS_NAMESPACE, &payment_hash, &record)?;
+		inbound.insert(payment_hash, record);
+		Ok(())
+	}
+
+	/// Records an outbound payment which is about to be sent, until [`Self::sent_outbound`] is
+	/// called with the result. Returns false, recording nothing, if a payment for the same payment
+	/// hash is already being sent.
+	fn sending_outbound(&self, payment_hash: [u8; 32], payment_preimage: Option<[u8; 32]>, payment_secret: Option<[u8; 32]>, amount_msat: Option<u64>, description: String) -> bool {
+		let mut sending = self.sending.lock().unwrap();
+		if sending.contains_key(&payment_hash) { return false; }
+		let now = now_secs();
+		let record = PaymentRecord {
+			payment_hash,
+			payment_id: None,
+			payment_preimage,
+			payment_secret,
+			amount_msat,
+			fee_paid_msat: None,
+			description,
+			created_at: now,
+			expires_at: None,
+			updated_at: now,
+			status: PaymentStatus::Pending,
+		};
+		sending.insert(payment_hash, record);
+		true
+	}
+
+	/// Moves a payment recorded by [`Self::sending_outbound`] to the outbound payments, keyed by
+	/// its payment id, or forgets it if it was not sent.
+	fn sent_outbound(&self, payment_hash: [u8; 32], payment_id: Option<[u8; 32]>) {
+		let mut outbound = self.outbound.lock().unwrap();
+		let mut record = match self.sending.lock().unwrap().remove(&payment_hash) {
+			Some(record) => record,
+			None => return,
+		};
+		if let Some(payment_id) = payment_id {
+			record.payment_id = Some(payment_id);
+			// The payment is already in flight, so a failure to persist it cannot be reported as
+			// one to pay.
+			self.persist_logged(OUTBOUND_PAYMENTS_NAMESPACE, &payment_id, &record);
+			outbound.insert(payment_id, record);
+		}
+	}
+
+	fn payment_received(&self, payment_hash: [u8; 32], amount_msat: u64, purpose: &PaymentPurpose) {
+		let now = now_secs();
+		let (payment_preimage, payment_secret) = match purpose {
+			PaymentPurpose::InvoicePayment { payment_preimage, payment_secret } => (payment_preimage.as_ref().map(|p| p.0), Some(payment_secret.0)),
+			PaymentPurpose::SpontaneousPayment(payment_preimage) => (Some(payment_preimage.0), None),
+		};
+		let mut inbound = self.inbound.lock().unwrap();
+		let record = inbound.entry(payment_hash).or_insert_with(|| PaymentRecord {
+			payment_hash,
+			payment_id: None,
+			payment_preimage: None,
+			payment_secret: None,
+			amount_msat: None,
+			fee_paid_msat: None,
+			description: String::new(),
+			created_at: now,
+			expires_at: None,
+			updated_at: now,
+			status: PaymentStatus::Pending,
+		});
+		if payment_preimage.is_some() { record.payment_preimage = payment_preimage; }
+		if payment_secret.is_some() { record.payment_secret = payment_secret; }
+		record.amount_msat = Some(amount_msat);
+		if record.status != PaymentStatus::Succeeded { record.status = PaymentStatus::Received; }
+		record.updated_at = now;
+		self.persist_logged(INBOUND_PAYMENTS_NAMESPACE, &payment_hash, record);
+	}
+
+	fn payment_claimed(&self, payment_hash: [u8; 32]) {
+		let mut inbound = self.inbound.lock().unwrap();
+		if let Some(record) = inbound.get_mut(&payment_hash) {
+			record.status = PaymentStatus::Succeeded;
+			record.updated_at = now_secs();
+			self.persist_logged(INBOUND_PAYMENTS_NAMESPACE, &payment_hash, record);
+		}
+	}
+
+	/// Updates the outbound payment with the given id, or the given hash for events which do not
+	/// carry a payment id or arrive before the payment call returned.
+	fn update_outbound<F: FnOnce(&mut PaymentRecord)>(&self, payment_id: Option<[u8; 32]>, payment_hash: [u8; 32], update: F) {
+		let mut outbound = self.outbound.lock().unwrap();
+		let payment_id = match payment_id {
+			Some(payment_id) if outbound.contains_key(&payment_id) => Some(payment_id),
+			Some(_) => None,
+			None => outbound.iter().find(|(_, record)| record.payment_hash == payment_hash).map(|(payment_id, _)| *payment_id),
+		};
+		if let Some(payment_id) = payment_id {
+			let record = outbound.get_mut(&payment_id).unwrap();
+			update(record);
+			record.updated_at = now_secs();
+			self.persist_logged(OUTBOUND_PAYMENTS_NAMESPACE, &payment_id, record);
+		} else if let Some(record) = self.sending.lock().unwrap().get_mut(&payment_hash) {
+			// This is persisted once the payment call returns.
+			update(record);
+			record.updated_at = now_secs();
+		}
+	}
+
+	fn list_payments<F: Fn(&PaymentDetails) -> bool>(&self, filter: F) -> Vec<PaymentDetails> {
+		let mut records: Vec<PaymentRecord> = self.inbound.lock().unwrap().values().cloned().collect();
+		records.extend(self.outbound.lock().unwrap().values().cloned());
+		records.sort_unstable_by(|a, b| b.created_at.cmp(&a.created_at));
+		let now = now_secs();
+		records.iter().map(|record| record.to_details(now)).filter(|payment| filter(payment)).collect()
+	}
+
+	fn handle_event(&self, event: &nativeEvent) {
+		match event {
+			nativeEvent::PaymentReceived { payment_hash, amt, purpose } => {
+				self.payment_received(payment_hash.0, *amt, purpose);
+			},
+			nativeEvent::PaymentSent { payment_id, payment_preimage, payment_hash, fee_paid_msat } => {
+				self.update_outbound(payment_id.as_ref().map(|id| id.0), payment_hash.0, |record| {
+					record.payment_preimage = Some(payment_preimage.0);
+					record.fee_paid_msat = *fee_paid_msat;
+					record.status = PaymentStatus::Succeeded;
+				});
+			},
+			nativeEvent::PaymentPathFailed { payment_id, payment_hash, all_paths_failed: true, .. } => {
+				self.update_outbound(payment_id.as_ref().map(|id| id.0), payment_hash.0, |record| {
+					// An MPP payment may fail some paths and still succeed, see `Event::PaymentSent`.
+					if record.status == PaymentStatus::Pending { record.status = PaymentStatus::Failed; }
+				});
+			},
+			nativeEvent::PaymentFailed { payment_id, payment_hash } => {
+				self.update_outbound(Some(payment_id.0), payment_hash.0, |record| {
+					if record.status == PaymentStatus::Pending { record.status = PaymentStatus::Failed; }
+				});
+			},
+			_ => {},
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_PaymentStoreIOErrorZ
+pub union CResult_PaymentStoreIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut PaymentStore,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_PaymentStoreIOErrorZ represents the result of a fallible operation,
+/// containing a PaymentStore on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_PaymentStoreIOErrorZ {
+	/// The contents of this CResult_PaymentStoreIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_PaymentStoreIOErrorZPtr,
+	/// Whether this CResult_PaymentStoreIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_PaymentStoreIOErrorZ in the success state.
+pub extern "C" fn CResult_PaymentStoreIOErrorZ_ok(o: PaymentStore) -> CResult_PaymentStoreIOErrorZ {
+	CResult_PaymentStoreIOErrorZ {
+		contents: CResult_PaymentStoreIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_PaymentStoreIOErrorZ in the error state.
+pub extern "C" fn CResult_PaymentStoreIOErrorZ_err(e: IOError) -> CResult_PaymentStoreIOErrorZ {
+	CResult_PaymentStoreIOErrorZ {
+		contents: CResult_PaymentStoreIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_PaymentStoreIOErrorZ_is_ok(o: &CResult_PaymentStoreIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_PaymentStoreIOErrorZ.
+pub extern "C" fn CResult_PaymentStoreIOErrorZ_free(_res: CResult_PaymentStoreIOErrorZ) { }
+impl Drop for CResult_PaymentStoreIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Records issued invoices and outbound payments, see the module documentation.
+#[must_use]
+#[repr(C)]
+pub struct PaymentStore {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativePaymentStore,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for PaymentStore {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativePaymentStore>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the PaymentStore, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn PaymentStore_free(this_obj: PaymentStore) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn PaymentStore_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativePaymentStore); }
+}
+#[allow(unused)]
+impl PaymentStore {
+	pub(crate) fn get_native_ref(&self) -> &'static nativePaymentStore {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativePaymentStore {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativePaymentStore {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a payment store backed by `store`, loading the payments previously recorded in it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_new(mut store: KVStore, mut logger: Logger) -> CResult_PaymentStoreIOErrorZ {
+	match nativePaymentStore::load(store, logger) {
+		Ok(payment_store) => CResult_PaymentStoreIOErrorZ_ok(PaymentStore { inner: ObjOps::heap_alloc(payment_store), is_owned: true }),
+		Err(e) => CResult_PaymentStoreIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+/// Records an invoice we created, eg with `create_invoice_from_channelmanager`, as a pending
+/// inbound payment. Recording an invoice for an already recorded payment hash replaces it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_record_invoice(this_arg: &PaymentStore, invoice: &Invoice) -> CResult_NoneErrorZ {
+	none_result_from_rust(this_arg.get_native_ref().record_invoice(invoice.get_native_ref()))
+}
+
+/// Records an outbound payment, sends it with `pay` and, depending on the result, keeps or forgets
+/// the record. Fails without calling `pay` if another payment for the same payment hash is being
+/// sent.
+fn record_payment<F: FnOnce() -> CResult_PaymentIdPaymentErrorZ>(payment_store: &nativePaymentStore, payment_hash: [u8; 32], payment_preimage: Option<[u8; 32]>, payment_secret: Option<[u8; 32]>, amount_msat: Option<u64>, description: String, pay: F) -> CResult_PaymentIdPaymentErrorZ {
+	if !payment_store.sending_outbound(payment_hash, payment_preimage, payment_secret, amount_msat, description) {
+		return CResult_PaymentIdPaymentErrorZ_err(PaymentError::Invoice("A payment for this payment hash is already being sent".into()));
+	}
+	let res = pay();
+	let payment_id = if res.result_ok { Some(unsafe { (*res.contents.result).data }) } else { None };
+	payment_store.sent_outbound(payment_hash, payment_id);
+	res
+}
+
+fn record_invoice_payment<F: FnOnce() -> CResult_PaymentIdPaymentErrorZ>(payment_store: &nativePaymentStore, invoice: &lightning_invoice::Invoice, amount_msats: Option<u64>, pay: F) -> CResult_PaymentIdPaymentErrorZ {
+	record_payment(payment_store, invoice.payment_hash().into_inner(), None, Some(invoice.payment_secret().0),
+		amount_msats.or(invoice.amount_milli_satoshis()), invoice_description(invoice), pay)
+}
+
+/// Records `invoice` as a pending outbound payment and pays it through `payer`. The record is
+/// dropped if the payment was not sent. See `InvoicePayer_pay_invoice` for details.
+///
+/// Fails with `PaymentError_Invoice` if a payment for the same invoice is being sent by another
+/// thread.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_pay_invoice(this_arg: &PaymentStore, payer: &InvoicePayer, invoice: &Invoice) -> CResult_PaymentIdPaymentErrorZ {
+	record_invoice_payment(this_arg.get_native_ref(), invoice.get_native_ref(), None, || InvoicePayer_pay_invoice(payer, invoice))
+}
+
+/// Records the zero-value `invoice` as a pending outbound payment and pays it through `payer`
+/// using the given amount. The record is dropped if the payment was not sent. See
+/// `InvoicePayer_pay_zero_value_invoice` for details.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_pay_zero_value_invoice(this_arg: &PaymentStore, payer: &InvoicePayer, invoice: &Invoice, mut amount_msats: u64) -> CResult_PaymentIdPaymentErrorZ {
+	record_invoice_payment(this_arg.get_native_ref(), invoice.get_native_ref(), Some(amount_msats), || InvoicePayer_pay_zero_value_invoice(payer, invoice, amount_msats))
+}
+
+/// Records a pending outbound payment to `pubkey` and pays it through `payer` using the hash of
+/// `payment_preimage`. The record is dropped if the payment was not sent. See
+/// `InvoicePayer_pay_pubkey` for details.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_pay_pubkey(this_arg: &PaymentStore, payer: &InvoicePayer, mut pubkey: PublicKey, mut payment_preimage: ThirtyTwoBytes, mut amount_msats: u64, mut final_cltv_expiry_delta: u32) -> CResult_PaymentIdPaymentErrorZ {
+	let preimage = payment_preimage.data;
+	record_payment(this_arg.get_native_ref(), Sha256::hash(&preimage).into_inner(), Some(preimage), None, Some(amount_msats), String::new(),
+		|| InvoicePayer_pay_pubkey(payer, pubkey, payment_preimage, amount_msats, final_cltv_expiry_delta))
+}
+
+/// Records `invoice` as a pending outbound payment and pays it through `payer`, as
+/// [`PaymentStore_pay_invoice`] does for an `InvoicePayer`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_retrying_pay_invoice(this_arg: &PaymentStore, payer: &RetryingInvoicePayer, invoice: &Invoice) -> CResult_PaymentIdPaymentErrorZ {
+	record_invoice_payment(this_arg.get_native_ref(), invoice.get_native_ref(), None, || RetryingInvoicePayer_pay_invoice(payer, invoice))
+}
+
+/// Records the zero-value `invoice` as a pending outbound payment and pays it through `payer`, as
+/// [`PaymentStore_pay_zero_value_invoice`] does for an `InvoicePayer`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_retrying_pay_zero_value_invoice(this_arg: &PaymentStore, payer: &RetryingInvoicePayer, invoice: &Invoice, mut amount_msats: u64) -> CResult_PaymentIdPaymentErrorZ {
+	record_invoice_payment(this_arg.get_native_ref(), invoice.get_native_ref(), Some(amount_msats), || RetryingInvoicePayer_pay_zero_value_invoice(payer, invoice, amount_msats))
+}
+
+/// Records a pending outbound payment to `pubkey` and pays it through `payer`, as
+/// [`PaymentStore_pay_pubkey`] does for an `InvoicePayer`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_retrying_pay_pubkey(this_arg: &PaymentStore, payer: &RetryingInvoicePayer, mut pubkey: PublicKey, mut payment_preimage: ThirtyTwoBytes, mut amount_msats: u64, mut final_cltv_expiry_delta: u32) -> CResult_PaymentIdPaymentErrorZ {
+	let preimage = payment_preimage.data;
+	record_payment(this_arg.get_native_ref(), Sha256::hash(&preimage).into_inner(), Some(preimage), None, Some(amount_msats), String::new(),
+		|| RetryingInvoicePayer_pay_pubkey(payer, pubkey, payment_preimage, amount_msats, final_cltv_expiry_delta))
+}
+
+/// Claims a received payment with `channel_manager`, as `ChannelManager_claim_funds` does, and
+/// marks the inbound payment as succeeded if it could be claimed.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_claim_funds(this_arg: &PaymentStore, channel_manager: &ChannelManager, mut payment_preimage: ThirtyTwoBytes) -> bool {
+	let payment_hash = Sha256::hash(&payment_preimage.data).into_inner();
+	let claimed = channel_manager.get_native_ref().claim_funds(lightning::ln::PaymentPreimage(payment_preimage.data));
+	if claimed { this_arg.get_native_ref().payment_claimed(payment_hash); }
+	claimed
+}
+
+/// Marks the inbound payment with the given payment hash as succeeded, for payments claimed
+/// other than through [`PaymentStore_claim_funds`], eg with `HodlInvoiceManager_settle`.
+#[no_mangle]
+pub extern "C" fn PaymentStore_payment_claimed(this_arg: &PaymentStore, payment_hash: *const [u8; 32]) {
+	this_arg.get_native_ref().payment_claimed(unsafe { *payment_hash });
+}
+
+/// Returns the inbound payment with the given payment hash, if one was recorded.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_get_inbound_payment(this_arg: &PaymentStore, payment_hash: *const [u8; 32]) -> COption_PaymentDetailsZ {
+	match this_arg.get_native_ref().inbound.lock().unwrap().get(unsafe { &*payment_hash }) {
+		Some(record) => COption_PaymentDetailsZ::Some(record.to_details(now_secs())),
+		None => COption_PaymentDetailsZ::None,
+	}
+}
+
+/// Returns the outbound payment with the given payment id, if one was recorded.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_get_outbound_payment(this_arg: &PaymentStore, payment_id: *const [u8; 32]) -> COption_PaymentDetailsZ {
+	match this_arg.get_native_ref().outbound.lock().unwrap().get(unsafe { &*payment_id }) {
+		Some(record) => COption_PaymentDetailsZ::Some(record.to_details(now_secs())),
+		None => COption_PaymentDetailsZ::None,
+	}
+}
+
+/// Returns all recorded inbound and outbound payments, most recently created first.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_list_payments(this_arg: &PaymentStore) -> CVec_PaymentDetailsZ {
+	this_arg.get_native_ref().list_payments(|_| true).into()
+}
+
+/// Returns the inbound payments which have not been received, and the outbound payments which
+/// are still in flight, most recently created first. Expired invoices and received but unclaimed
+/// payments are not included.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_list_pending_payments(this_arg: &PaymentStore) -> CVec_PaymentDetailsZ {
+	this_arg.get_native_ref().list_payments(|payment| payment.status == PaymentStatus::Pending).into()
+}
+
+struct PaymentStoreEventHandler {
+	payment_store: &'static nativePaymentStore,
+	inner: EventHandler,
+}
+
+/// Wraps `inner` in an [`EventHandler`] which updates recorded payments from payment events
+/// before passing every event on to `inner`.
+///
+/// Inbound payments received without a recorded invoice, eg spontaneous payments, are recorded
+/// when received.
+///
+/// The returned handler must be freed before `this_arg` is.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PaymentStore_event_handler(this_arg: &PaymentStore, mut inner: EventHandler) -> EventHandler {
+	let handler = Box::new(PaymentStoreEventHandler { payment_store: this_arg.get_native_ref(), inner });
+	EventHandler {
+		this_arg: Box::into_raw(handler) as *mut c_void,
+		handle_event: PaymentStoreEventHandler_handle_event,
+		free: Some(PaymentStoreEventHandler_free),
+	}
+}
+
+extern "C" fn PaymentStoreEventHandler_handle_event(this_arg: *const c_void, event: &Event) {
+	let handler = unsafe { &*(this_arg as *const PaymentStoreEventHandler) };
+	handler.payment_store.handle_event(&event.to_native());
+	(handler.inner.handle_event)(handler.inner.this_arg, event);
+}
+extern "C" fn PaymentStoreEventHandler_free(this_arg: *mut c_void) {
+	let _ = unsafe { Box::from_raw(this_arg as *mut PaymentStoreEventHandler) };
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Arc;
+	use bitcoin::secp256k1::{Secp256k1, SecretKey};
+	use lightning::ln::{PaymentHash, PaymentPreimage, PaymentSecret};
+	use lightning::ln::channelmanager::PaymentId;
+	use lightning_invoice::{Currency, InvoiceBuilder};
+	use crate::c_types::derived::CResult_PaymentIdPaymentErrorZ_ok;
+	use crate::extensions::test_utils::{channel_manager, logger, MemoryStore};
+
+	const PAYMENT_PREIMAGE: [u8; 32] = [7; 32];
+	const PAYMENT_ID: [u8; 32] = [9; 32];
+
+	fn payment_hash() -> [u8; 32] {
+		Sha256::hash(&PAYMENT_PREIMAGE).into_inner()
+	}
+
+	fn load(store: &Arc<MemoryStore>) -> nativePaymentStore {
+		nativePaymentStore::load(store.as_kv_store(), logger()).unwrap()
+	}
+
+	fn invoice() -> lightning_invoice::Invoice {
+		InvoiceBuilder::new(Currency::Regtest)
+			.description("coffee".to_owned())
+			.payment_hash(Sha256::from_inner(payment_hash()))
+			.payment_secret(PaymentSecret([8; 32]))
+			.amount_milli_satoshis(10_000)
+			.current_timestamp()
+			.min_final_cltv_expiry(144)
+			.build_signed(|hash| Secp256k1::new().sign_recoverable(hash, &SecretKey::from_slice(&[1; 32]).unwrap()))
+			.unwrap()
+	}
+
+	fn inbound(payment_store: &nativePaymentStore) -> PaymentRecord {
+		payment_store.inbound.lock().unwrap().get(&payment_hash()).cloned().unwrap()
+	}
+
+	fn outbound(payment_store: &nativePaymentStore) -> PaymentRecord {
+		payment_store.outbound.lock().unwrap().get(&PAYMENT_ID).cloned().unwrap()
+	}
+
+	/// Records a keysend payment with `PAYMENT_PREIMAGE`, running `during` before the payment
+	/// call returns `PAYMENT_ID`.
+	fn pay<F: FnOnce()>(payment_store: &nativePaymentStore, during: F) -> CResult_PaymentIdPaymentErrorZ {
+		record_payment(payment_store, payment_hash(), Some(PAYMENT_PREIMAGE), None, Some(5_000), String::new(), || {
+			during();
+			CResult_PaymentIdPaymentErrorZ_ok(ThirtyTwoBytes { data: PAYMENT_ID })
+		})
+	}
+
+	fn payment_sent(payment_id: Option<[u8; 32]>) -> nativeEvent {
+		nativeEvent::PaymentSent {
+			payment_id: payment_id.map(PaymentId),
+			payment_preimage: PaymentPreimage(PAYMENT_PREIMAGE),
+			payment_hash: PaymentHash(payment_hash()),
+			fee_paid_msat: Some(10),
+		}
+	}
+
+	#[test]
+	fn invoice_is_received_then_claimed() {
+		let store = MemoryStore::new();
+		let payment_store = load(&store);
+		payment_store.record_invoice(&invoice()).unwrap();
+		let record = inbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Pending);
+		assert_eq!(record.amount_msat, Some(10_000));
+		assert_eq!(record.description, "coffee");
+		assert_eq!(record.payment_secret, Some([8; 32]));
+		assert_eq!(record.expires_at, Some(record.created_at + 3600));
+
+		payment_store.handle_event(&nativeEvent::PaymentReceived {
+			payment_hash: PaymentHash(payment_hash()),
+			amt: 11_000,
+			purpose: PaymentPurpose::InvoicePayment { payment_preimage: Some(PaymentPreimage(PAYMENT_PREIMAGE)), payment_secret: PaymentSecret([8; 32]) },
+		});
+		let record = inbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Received);
+		assert_eq!(record.amount_msat, Some(11_000));
+		assert_eq!(record.payment_preimage, Some(PAYMENT_PREIMAGE));
+
+		// The ChannelManager has no such HTLC, so claiming fails and the payment stays received.
+		let payments = PaymentStore { inner: ObjOps::heap_alloc(payment_store), is_owned: true };
+		let channel_manager = channel_manager(100);
+		assert!(!PaymentStore_claim_funds(&payments, &channel_manager, ThirtyTwoBytes { data: PAYMENT_PREIMAGE }));
+		assert_eq!(inbound(payments.get_native_ref()).status, PaymentStatus::Received);
+		PaymentStore_payment_claimed(&payments, &payment_hash());
+		assert_eq!(inbound(payments.get_native_ref()).status, PaymentStatus::Succeeded);
+
+		// A late duplicate PaymentReceived does not undo the claim.
+		payments.get_native_ref().handle_event(&nativeEvent::PaymentReceived {
+			payment_hash: PaymentHash(payment_hash()),
+			amt: 11_000,
+			purpose: PaymentPurpose::SpontaneousPayment(PaymentPreimage(PAYMENT_PREIMAGE)),
+		});
+		assert_eq!(inbound(payments.get_native_ref()).status, PaymentStatus::Succeeded);
+	}
+
+	#[test]
+	fn spontaneous_payments_are_recorded_when_received() {
+		let store = MemoryStore::new();
+		let payment_store = load(&store);
+		payment_store.handle_event(&nativeEvent::PaymentReceived {
+			payment_hash: PaymentHash(payment_hash()),
+			amt: 1_000,
+			purpose: PaymentPurpose::SpontaneousPayment(PaymentPreimage(PAYMENT_PREIMAGE)),
+		});
+		let record = inbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Received);
+		assert_eq!(record.payment_secret, None);
+		assert_eq!(record.expires_at, None);
+	}
+
+	#[test]
+	fn outbound_payments_are_updated_from_events() {
+		let store = MemoryStore::new();
+		let payment_store = load(&store);
+		assert!(pay(&payment_store, || {}).result_ok);
+		let record = outbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Pending);
+		assert_eq!(record.amount_msat, Some(5_000));
+		assert!(payment_store.sending.lock().unwrap().is_empty());
+
+		payment_store.handle_event(&payment_sent(Some(PAYMENT_ID)));
+		let record = outbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Succeeded);
+		assert_eq!(record.fee_paid_msat, Some(10));
+		// A PaymentFailed for an already succeeded payment is ignored.
+		payment_store.handle_event(&nativeEvent::PaymentFailed { payment_id: PaymentId(PAYMENT_ID), payment_hash: PaymentHash(payment_hash()) });
+		assert_eq!(outbound(&payment_store).status, PaymentStatus::Succeeded);
+	}
+
+	#[test]
+	fn failed_outbound_payments_are_marked_failed() {
+		let store = MemoryStore::new();
+		let payment_store = load(&store);
+		assert!(pay(&payment_store, || {}).result_ok);
+		payment_store.handle_event(&nativeEvent::PaymentFailed { payment_id: PaymentId(PAYMENT_ID), payment_hash: PaymentHash(payment_hash()) });
+		assert_eq!(outbound(&payment_store).status, PaymentStatus::Failed);
+		assert!(payment_store.list_payments(|payment| payment.status == PaymentStatus::Pending).is_empty());
+	}
+
+	#[test]
+	fn events_before_the_payment_call_returns_are_applied() {
+		let store = MemoryStore::new();
+		let payment_store = load(&store);
+		// The payment id is not known to the store until the call returns, so the event is
+		// matched by payment hash.
+		assert!(pay(&payment_store, || payment_store.handle_event(&payment_sent(Some(PAYMENT_ID)))).result_ok);
+		let record = outbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Succeeded);
+		assert_eq!(record.fee_paid_msat, Some(10));
+		assert_eq!(load(&store).outbound.lock().unwrap().get(&PAYMENT_ID).unwrap().status, PaymentStatus::Succeeded);
+	}
+
+	#[test]
+	fn concurrent_payments_to_the_same_hash_are_refused() {
+		let store = MemoryStore::new();
+		let payment_store = load(&store);
+		let mut second_sent = false;
+		let res = pay(&payment_store, || {
+			let res = record_payment(&payment_store, payment_hash(), Some(PAYMENT_PREIMAGE), None, Some(5_000), String::new(), || {
+				second_sent = true;
+				CResult_PaymentIdPaymentErrorZ_ok(ThirtyTwoBytes { data: [10; 32] })
+			});
+			assert!(!res.result_ok);
+		});
+		assert!(res.result_ok);
+		assert!(!second_sent);
+		assert_eq!(payment_store.outbound.lock().unwrap().len(), 1);
+		// Once the first call returned, the hash may be paid again.
+		assert!(pay(&payment_store, || {}).result_ok);
+	}
+
+	#[test]
+	fn unsent_payments_are_forgotten() {
+		let store = MemoryStore::new();
+		let payment_store = load(&store);
+		let res = record_payment(&payment_store, payment_hash(), Some(PAYMENT_PREIMAGE), None, Some(5_000), String::new(),
+			|| CResult_PaymentIdPaymentErrorZ_err(PaymentError::Invoice("no route".into())));
+		assert!(!res.result_ok);
+		assert!(payment_store.outbound.lock().unwrap().is_empty());
+		assert!(payment_store.sending.lock().unwrap().is_empty());
+		assert_eq!(store.len(), 0);
+	}
+
+	#[test]
+	fn payments_survive_reload() {
+		let store = MemoryStore::new();
+		{
+			let payment_store = load(&store);
+			payment_store.record_invoice(&invoice()).unwrap();
+			assert!(pay(&payment_store, || {}).result_ok);
+			payment_store.handle_event(&payment_sent(None));
+		}
+		let payment_store = load(&store);
+		let record = inbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Pending);
+		assert_eq!(record.description, "coffee");
+		let record = outbound(&payment_store);
+		assert_eq!(record.status, PaymentStatus::Succeeded);
+		assert_eq!(record.payment_preimage, Some(PAYMENT_PREIMAGE));
+		let payments = payment_store.list_payments(|_| true);
+		assert_eq!(payments.len(), 2);
+		assert!(payments.iter().any(|payment| payment.direction == PaymentDirection::Inbound));
+		assert!(payments.iter().any(|payment| payment.direction == PaymentDirection::Outbound && payment.payment_id.data == PAYMENT_ID));
+	}
+}
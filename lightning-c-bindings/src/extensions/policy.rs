@@ -0,0 +1,284 @@
+//! Limits on which peers may connect to us and how much they may send, applied by an [`LdkNet`]
+//! socket driver.
+//!
+//! A [`ConnectionPolicy`] is checked before an inbound connection is handed to
+//! `PeerManager_new_inbound_connection`, limiting the total number of inbound connections and
+//! the number from any single IP address. Each connection also gets a token bucket which limits
+//! how fast data is read from the socket and passed to `PeerManager_read_event`. Finally, a
+//! [`PeerFilter`] may reject peers by node id once their handshake completes, after which they
+//! are disconnected with `PeerManager_disconnect_by_node_id`.
+//!
+//! [`LdkNet`]: crate::extensions::net::LdkNet
+
+use std::ffi::c_void;
+use std::time::Instant;
+
+use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
+
+use crate::c_types::*;
+
+/// A token bucket limiting the rate at which bytes are read from a single connection.
+pub(crate) struct TokenBucket {
+	bytes_per_sec: f64,
+	capacity: f64,
+	tokens: f64,
+	last_refill: Instant,
+}
+
+impl TokenBucket {
+	fn new(bytes_per_sec: u64, burst_bytes: u64) -> Self {
+		let capacity = burst_bytes as f64;
+		Self { bytes_per_sec: bytes_per_sec as f64, capacity, tokens: capacity, last_refill: Instant::now() }
+	}
+
+	/// Returns the number of bytes which may be read right now.
+	pub(crate) fn available(&mut self) -> usize {
+		let now = Instant::now();
+		let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+		self.tokens = (self.tokens + elapsed * self.bytes_per_sec).min(self.capacity);
+		self.last_refill = now;
+		self.tokens as usize
+	}
+
+	pub(crate) fn consume(&mut self, bytes: usize) {
+		self.tokens = (self.tokens - bytes as f64).max(0.0);
+	}
+}
+
+/// Decides whether to keep a connection to a peer once its handshake has completed and its node
+/// id is known.
+#[repr(C)]
+pub struct PeerFilter {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Returns true if we should stay connected to the peer with the given node id, or false if
+	/// it should be disconnected.
+	///
+	/// This is called from the socket driver's event loop, which is blocked until it returns.
+	#[must_use]
+	pub accept_peer: extern "C" fn (this_arg: *const c_void, node_id: PublicKey) -> bool,
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for PeerFilter {}
+unsafe impl Sync for PeerFilter {}
+#[no_mangle]
+pub(crate) extern "C" fn PeerFilter_clone_fields(orig: &PeerFilter) -> PeerFilter {
+	PeerFilter {
+		this_arg: orig.this_arg,
+		accept_peer: Clone::clone(&orig.accept_peer),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn PeerFilter_free(this_ptr: PeerFilter) { }
+impl Drop for PeerFilter {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+pub(crate) struct nativeConnectionPolicy {
+	max_inbound: usize,
+	max_inbound_per_ip: usize,
+	bytes_per_sec: u64,
+	burst_bytes: u64,
+	peer_filter: Option<PeerFilter>,
+}
+
+impl nativeConnectionPolicy {
+	/// Whether a new inbound connection may be accepted, given the number of inbound connections
+	/// we already have in total and from the same IP address.
+	pub(crate) fn allow_inbound(&self, inbound: usize, inbound_from_ip: usize) -> bool {
+		(self.max_inbound == 0 || inbound < self.max_inbound)
+			&& (self.max_inbound_per_ip == 0 || inbound_from_ip < self.max_inbound_per_ip)
+	}
+
+	pub(crate) fn is_rate_limited(&self) -> bool { self.bytes_per_sec != 0 && self.burst_bytes != 0 }
+
+	pub(crate) fn new_bucket(&self) -> Option<TokenBucket> {
+		if !self.is_rate_limited() { return None; }
+		Some(TokenBucket::new(self.bytes_per_sec, self.burst_bytes))
+	}
+
+	pub(crate) fn has_peer_filter(&self) -> bool { self.peer_filter.is_some() }
+
+	pub(crate) fn accept_peer(&self, node_id: &SecpPublicKey) -> bool {
+		match &self.peer_filter {
+			Some(filter) => (filter.accept_peer)(filter.this_arg, PublicKey::from_rust(node_id)),
+			None => true,
+		}
+	}
+}
+
+/// Limits on inbound connections and per-connection bandwidth, installed on a socket driver with
+/// `LdkNet_set_connection_policy`.
+#[must_use]
+#[repr(C)]
+pub struct ConnectionPolicy {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeConnectionPolicy,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for ConnectionPolicy {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeConnectionPolicy>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the ConnectionPolicy, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn ConnectionPolicy_free(this_obj: ConnectionPolicy) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn ConnectionPolicy_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeConnectionPolicy); }
+}
+#[allow(unused)]
+impl ConnectionPolicy {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeConnectionPolicy {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeConnectionPolicy {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeConnectionPolicy {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a new ConnectionPolicy without a peer filter.
+///
+/// At most `max_inbound` inbound connections are accepted at once, and at most
+/// `max_inbound_per_ip` from any single IP address. Outbound connections do not count towards
+/// either limit.
+///
+/// Data is read from each connection (inbound or outbound) at no more than `bytes_per_sec`
+/// bytes per second on average, with bursts of up to `burst_bytes`. Peers sending faster simply
+/// have their reads delayed, relying on TCP flow control to slow them down.
+///
+/// Any of the limits may be set to 0 to disable it. Setting either `bytes_per_sec` or
+/// `burst_bytes` to 0 disables rate limiting entirely.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn ConnectionPolicy_new(mut max_inbound: usize, mut max_inbound_per_ip: usize, mut bytes_per_sec: u64, mut burst_bytes: u64) -> ConnectionPolicy {
+	ConnectionPolicy { inner: ObjOps::heap_alloc(nativeConnectionPolicy {
+		max_inbound, max_inbound_per_ip, bytes_per_sec, burst_bytes, peer_filter: None,
+	}), is_owned: true }
+}
+
+/// Sets the filter used to decide whether to stay connected to peers once their handshake
+/// completes, replacing any existing filter.
+///
+/// Peers which are rejected are disconnected with `PeerManager_disconnect_by_node_id`.
+#[no_mangle]
+pub extern "C" fn ConnectionPolicy_set_peer_filter(this_arg: &mut ConnectionPolicy, mut peer_filter: PeerFilter) {
+	this_arg.get_native_mut_ref().peer_filter = Some(peer_filter);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicBool, Ordering};
+	use std::time::Duration;
+	use bitcoin::secp256k1::Secp256k1;
+	use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
+
+	fn node_id(seed: u8) -> SecpPublicKey {
+		SecpPublicKey::from_secret_key(&Secp256k1::new(), &SecpSecretKey::from_slice(&[seed; 32]).unwrap())
+	}
+
+	/// Accepts only the node id `this_arg` points to.
+	extern "C" fn accept_peer(this_arg: *const c_void, node_id: PublicKey) -> bool {
+		node_id.into_rust() == unsafe { *(this_arg as *const SecpPublicKey) }
+	}
+
+	static FILTER_FREED: AtomicBool = AtomicBool::new(false);
+	extern "C" fn free_filter(_this_arg: *mut c_void) {
+		FILTER_FREED.store(true, Ordering::Release);
+	}
+
+	#[test]
+	fn limits_inbound_connections() {
+		let policy = ConnectionPolicy_new(3, 2, 0, 0);
+		let policy = policy.get_native_ref();
+		assert!(policy.allow_inbound(0, 0));
+		assert!(policy.allow_inbound(2, 1));
+		assert!(!policy.allow_inbound(2, 2));
+		assert!(!policy.allow_inbound(3, 0));
+
+		let unlimited = ConnectionPolicy_new(0, 0, 0, 0);
+		assert!(unlimited.get_native_ref().allow_inbound(usize::max_value() - 1, usize::max_value() - 1));
+		let per_ip_only = ConnectionPolicy_new(0, 1, 0, 0);
+		assert!(per_ip_only.get_native_ref().allow_inbound(1000, 0));
+		assert!(!per_ip_only.get_native_ref().allow_inbound(1000, 1));
+	}
+
+	#[test]
+	fn zero_rate_or_burst_disables_rate_limiting() {
+		for (bytes_per_sec, burst_bytes) in [(0, 0), (1000, 0), (0, 1000)].iter() {
+			let policy = ConnectionPolicy_new(0, 0, *bytes_per_sec, *burst_bytes);
+			assert!(!policy.get_native_ref().is_rate_limited());
+			assert!(policy.get_native_ref().new_bucket().is_none());
+		}
+		assert!(ConnectionPolicy_new(0, 0, 1000, 1000).get_native_ref().is_rate_limited());
+	}
+
+	#[test]
+	fn bucket_refills_up_to_burst() {
+		let policy = ConnectionPolicy_new(0, 0, 1000, 500);
+		let mut bucket = policy.get_native_ref().new_bucket().unwrap();
+		assert_eq!(bucket.available(), 500);
+		bucket.consume(600);
+		assert!(bucket.available() < 10);
+
+		bucket.last_refill -= Duration::from_millis(200);
+		let available = bucket.available();
+		assert!(available >= 200 && available < 210);
+		bucket.consume(150);
+		let available = bucket.available();
+		assert!(available >= 50 && available < 60);
+
+		bucket.last_refill -= Duration::from_secs(10);
+		assert_eq!(bucket.available(), 500);
+	}
+
+	#[test]
+	fn filters_peers() {
+		let mut policy = ConnectionPolicy_new(0, 0, 0, 0);
+		assert!(!policy.get_native_ref().has_peer_filter());
+		assert!(policy.get_native_ref().accept_peer(&node_id(1)));
+
+		let accepted = node_id(1);
+		ConnectionPolicy_set_peer_filter(&mut policy, PeerFilter {
+			this_arg: &accepted as *const SecpPublicKey as *mut c_void,
+			accept_peer,
+			free: Some(free_filter),
+		});
+		assert!(policy.get_native_ref().has_peer_filter());
+		assert!(policy.get_native_ref().accept_peer(&node_id(1)));
+		assert!(!policy.get_native_ref().accept_peer(&node_id(2)));
+
+		assert!(!FILTER_FREED.load(Ordering::Acquire));
+		drop(policy);
+		assert!(FILTER_FREED.load(Ordering::Acquire));
+	}
+}
@@ -0,0 +1,561 @@
+//! Funds channels through Partially Signed Bitcoin Transactions (BIP 174), for users whose funding
+//! keys live outside of this process, eg in a hardware wallet or an external coordinator.
+//!
+//! `ChannelManager_funding_transaction_generated` requires a fully signed transaction. Instead of
+//! building one directly when handling `Event::FundingGenerationReady`, create a [`PsbtFunding`]
+//! from the event with `PsbtFunding_from_event` and:
+//!  * hand the PSBT from `PsbtFunding_get_unsigned_psbt`, which only contains the channel output,
+//!    to a wallet which adds inputs and change and signs it, or pass a wallet-built PSBT through
+//!    `PsbtFunding_add_channel_output` before having it signed,
+//!  * pass the signed PSBT to `PsbtFunding_funding_transaction_generated`, which checks that it
+//!    pays the channel output, finalizes it and hands the transaction to the `ChannelManager`.
+//!
+//! LDK only accepts funding transactions whose inputs all spend segwit outputs, as the funding
+//! transaction id must not be malleable. Inputs may be finalized by the wallet already; spends of
+//! P2WPKH outputs which only carry a partial signature are finalized here.
+//!
+//! Signatures are not verified, an invalidly signed transaction will simply fail to broadcast.
+//! Channels which are still waiting for their funding transaction are not persisted by the
+//! `ChannelManager`, so a `PsbtFunding` is only useful until the node restarts.
+
+use std::ffi::c_void;
+
+use bitcoin::blockdata::script::Script;
+use bitcoin::blockdata::transaction::{Transaction as BitcoinTransaction, TxOut};
+use bitcoin::hash_types::WPubkeyHash;
+use bitcoin::hashes::Hash;
+use bitcoin::util::psbt::{self, PartiallySignedTransaction};
+
+use lightning::util::events::Event as nativeEvent;
+
+use crate::c_types::*;
+use crate::c_types::derived::CVec_u8Z;
+use crate::lightning::ln::channelmanager::ChannelManager;
+use crate::lightning::util::events::Event;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The reasons a PSBT cannot be used to fund a channel.
+pub enum PsbtFundingError {
+	/// The PSBT could not be deserialized, or its unsigned transaction already carries signatures.
+	InvalidPsbt,
+	/// No output of the PSBT pays the channel's funding script and value.
+	MissingChannelOutput,
+	/// More than one output pays the channel's funding script, making the funding output ambiguous.
+	DuplicateChannelOutput,
+	/// The PSBT does not spend any inputs.
+	NoInputs,
+	/// An input is neither finalized nor a P2WPKH spend with a partial signature which could be
+	/// finalized here.
+	InputNotFinalized,
+	/// An input was finalized without a witness, ie spends a non-segwit output.
+	NonSegwitInput,
+	/// The `ChannelManager` rejected the funding transaction, eg because the channel has since been
+	/// closed. Call `PsbtFunding_finalize` and `ChannelManager_funding_transaction_generated`
+	/// directly to learn why.
+	FundingRejected,
+}
+
+#[repr(C)]
+/// The contents of CResult_PsbtPsbtFundingErrorZ
+pub union CResult_PsbtPsbtFundingErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut Psbt,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut PsbtFundingError,
+}
+#[repr(C)]
+/// A CResult_PsbtPsbtFundingErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::Psbt on success and a PsbtFundingError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_PsbtPsbtFundingErrorZ {
+	/// The contents of this CResult_PsbtPsbtFundingErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_PsbtPsbtFundingErrorZPtr,
+	/// Whether this CResult_PsbtPsbtFundingErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_PsbtPsbtFundingErrorZ in the success state.
+pub extern "C" fn CResult_PsbtPsbtFundingErrorZ_ok(o: Psbt) -> CResult_PsbtPsbtFundingErrorZ {
+	CResult_PsbtPsbtFundingErrorZ {
+		contents: CResult_PsbtPsbtFundingErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_PsbtPsbtFundingErrorZ in the error state.
+pub extern "C" fn CResult_PsbtPsbtFundingErrorZ_err(e: PsbtFundingError) -> CResult_PsbtPsbtFundingErrorZ {
+	CResult_PsbtPsbtFundingErrorZ {
+		contents: CResult_PsbtPsbtFundingErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_PsbtPsbtFundingErrorZ_is_ok(o: &CResult_PsbtPsbtFundingErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_PsbtPsbtFundingErrorZ.
+pub extern "C" fn CResult_PsbtPsbtFundingErrorZ_free(_res: CResult_PsbtPsbtFundingErrorZ) { }
+impl Drop for CResult_PsbtPsbtFundingErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_PsbtPsbtFundingErrorZ {
+	fn from_rust(res: Result<PartiallySignedTransaction, PsbtFundingError>) -> Self {
+		match res {
+			Ok(psbt) => CResult_PsbtPsbtFundingErrorZ_ok(Psbt::from_bitcoin(&psbt)),
+			Err(e) => CResult_PsbtPsbtFundingErrorZ_err(e),
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_TransactionPsbtFundingErrorZ
+pub union CResult_TransactionPsbtFundingErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut Transaction,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut PsbtFundingError,
+}
+#[repr(C)]
+/// A CResult_TransactionPsbtFundingErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::Transaction on success and a PsbtFundingError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_TransactionPsbtFundingErrorZ {
+	/// The contents of this CResult_TransactionPsbtFundingErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_TransactionPsbtFundingErrorZPtr,
+	/// Whether this CResult_TransactionPsbtFundingErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_TransactionPsbtFundingErrorZ in the success state.
+pub extern "C" fn CResult_TransactionPsbtFundingErrorZ_ok(o: Transaction) -> CResult_TransactionPsbtFundingErrorZ {
+	CResult_TransactionPsbtFundingErrorZ {
+		contents: CResult_TransactionPsbtFundingErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_TransactionPsbtFundingErrorZ in the error state.
+pub extern "C" fn CResult_TransactionPsbtFundingErrorZ_err(e: PsbtFundingError) -> CResult_TransactionPsbtFundingErrorZ {
+	CResult_TransactionPsbtFundingErrorZ {
+		contents: CResult_TransactionPsbtFundingErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_TransactionPsbtFundingErrorZ_is_ok(o: &CResult_TransactionPsbtFundingErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_TransactionPsbtFundingErrorZ.
+pub extern "C" fn CResult_TransactionPsbtFundingErrorZ_free(_res: CResult_TransactionPsbtFundingErrorZ) { }
+impl Drop for CResult_TransactionPsbtFundingErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_TransactionPsbtFundingErrorZ {
+	fn from_rust(res: Result<BitcoinTransaction, PsbtFundingError>) -> Self {
+		match res {
+			Ok(tx) => CResult_TransactionPsbtFundingErrorZ_ok(Transaction::from_bitcoin(&tx)),
+			Err(e) => CResult_TransactionPsbtFundingErrorZ_err(e),
+		}
+	}
+}
+
+pub(crate) struct nativePsbtFunding {
+	temporary_channel_id: [u8; 32],
+	output_script: Script,
+	channel_value_satoshis: u64,
+}
+
+impl nativePsbtFunding {
+	fn channel_output(&self) -> TxOut {
+		TxOut { script_pubkey: self.output_script.clone(), value: self.channel_value_satoshis }
+	}
+
+	/// Checks that exactly one output pays the channel's funding script, and that it pays the
+	/// channel value. Returns whether such an output exists at all.
+	fn check_channel_output(&self, tx: &BitcoinTransaction) -> Result<bool, PsbtFundingError> {
+		let mut matching = tx.output.iter().filter(|output| output.script_pubkey == self.output_script);
+		match (matching.next(), matching.next()) {
+			(None, _) => Ok(false),
+			(Some(output), None) if output.value == self.channel_value_satoshis => Ok(true),
+			(Some(_), None) => Err(PsbtFundingError::MissingChannelOutput),
+			(Some(_), Some(_)) => Err(PsbtFundingError::DuplicateChannelOutput),
+		}
+	}
+
+	fn unsigned_psbt(&self) -> PartiallySignedTransaction {
+		let tx = BitcoinTransaction { version: 2, lock_time: 0, input: Vec::new(), output: vec![self.channel_output()] };
+		PartiallySignedTransaction::from_unsigned_tx(tx).expect("A transaction without inputs cannot carry signatures")
+	}
+
+	fn add_channel_output(&self, mut psbt: PartiallySignedTransaction) -> Result<PartiallySignedTransaction, PsbtFundingError> {
+		if !self.check_channel_output(&psbt.global.unsigned_tx)? {
+			psbt.global.unsigned_tx.output.push(self.channel_output());
+			psbt.outputs.push(psbt::Output::default());
+		}
+		Ok(psbt)
+	}
+
+	fn finalize(&self, mut psbt: PartiallySignedTransaction) -> Result<BitcoinTransaction, PsbtFundingError> {
+		if !self.check_channel_output(&psbt.global.unsigned_tx)? {
+			return Err(PsbtFundingError::MissingChannelOutput);
+		}
+		if psbt.inputs.is_empty() {
+			return Err(PsbtFundingError::NoInputs);
+		}
+		for (idx, input) in psbt.inputs.iter_mut().enumerate() {
+			if input.final_script_witness.is_none() {
+				if input.final_script_sig.is_some() {
+					return Err(PsbtFundingError::NonSegwitInput);
+				}
+				let prevout = psbt.global.unsigned_tx.input[idx].previous_output;
+				finalize_p2wpkh_input(input, prevout.vout)?;
+			}
+		}
+		let tx = psbt.extract_tx();
+		if tx.input.iter().any(|input| input.witness.is_empty()) {
+			return Err(PsbtFundingError::NonSegwitInput);
+		}
+		Ok(tx)
+	}
+}
+
+/// Finalizes a spend of a P2WPKH output which carries the single partial signature it needs,
+/// following the BIP 174 finalizer role.
+fn finalize_p2wpkh_input(input: &mut psbt::Input, vout: u32) -> Result<(), PsbtFundingError> {
+	let spent_script = match (&input.witness_utxo, &input.non_witness_utxo) {
+		(Some(utxo), _) => utxo.script_pubkey.clone(),
+		(None, Some(prev_tx)) => match prev_tx.output.get(vout as usize) {
+			Some(utxo) => utxo.script_pubkey.clone(),
+			None => return Err(PsbtFundingError::InvalidPsbt),
+		},
+		(None, None) => return Err(PsbtFundingError::InputNotFinalized),
+	};
+	if !spent_script.is_v0_p2wpkh() || input.partial_sigs.len() != 1 {
+		return Err(PsbtFundingError::InputNotFinalized);
+	}
+	let (pubkey, sig) = input.partial_sigs.iter().next().unwrap();
+	if !pubkey.compressed || spent_script.as_bytes()[2..] != WPubkeyHash::hash(&pubkey.to_bytes())[..] {
+		return Err(PsbtFundingError::InputNotFinalized);
+	}
+	input.final_script_witness = Some(vec![sig.clone(), pubkey.to_bytes()]);
+	// BIP 174 has finalizers clear everything but the UTXO and the final scripts.
+	input.partial_sigs.clear();
+	input.sighash_type = None;
+	input.redeem_script = None;
+	input.witness_script = None;
+	input.bip32_derivation.clear();
+	Ok(())
+}
+
+/// The funding of a single channel through a PSBT, as requested by an
+/// `Event::FundingGenerationReady`. See `PsbtFunding_from_event`.
+#[must_use]
+#[repr(C)]
+pub struct PsbtFunding {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativePsbtFunding,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for PsbtFunding {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativePsbtFunding>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the PsbtFunding, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn PsbtFunding_free(this_obj: PsbtFunding) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn PsbtFunding_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativePsbtFunding); }
+}
+#[allow(unused)]
+impl PsbtFunding {
+	pub(crate) fn get_native_ref(&self) -> &'static nativePsbtFunding {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativePsbtFunding {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativePsbtFunding {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a PsbtFunding from the fields of an `Event::FundingGenerationReady`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_new(mut temporary_channel_id: ThirtyTwoBytes, mut output_script: CVec_u8Z, mut channel_value_satoshis: u64) -> PsbtFunding {
+	PsbtFunding { inner: ObjOps::heap_alloc(nativePsbtFunding {
+		temporary_channel_id: temporary_channel_id.data,
+		output_script: Script::from(output_script.into_rust()),
+		channel_value_satoshis,
+	}), is_owned: true }
+}
+
+/// Creates a PsbtFunding for the channel `event` requests funding for.
+///
+/// Note that the return value (or a relevant inner pointer) may be NULL or all-0s to represent None,
+/// which is the case for any event other than `Event::FundingGenerationReady`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_from_event(event: &Event) -> PsbtFunding {
+	match event.to_native() {
+		nativeEvent::FundingGenerationReady { temporary_channel_id, channel_value_satoshis, output_script, .. } => {
+			PsbtFunding { inner: ObjOps::heap_alloc(nativePsbtFunding { temporary_channel_id, output_script, channel_value_satoshis }), is_owned: true }
+		},
+		_ => PsbtFunding { inner: std::ptr::null_mut(), is_owned: true },
+	}
+}
+
+/// The temporary channel id of the channel being funded.
+#[no_mangle]
+pub extern "C" fn PsbtFunding_get_temporary_channel_id(this_ptr: &PsbtFunding) -> *const [u8; 32] {
+	&this_ptr.get_native_ref().temporary_channel_id
+}
+
+/// The script which the funding output must pay to.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_get_output_script(this_ptr: &PsbtFunding) -> u8slice {
+	u8slice::from_slice(&this_ptr.get_native_ref().output_script[..])
+}
+
+/// The value which the funding output must pay, in satoshis.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_get_channel_value_satoshis(this_ptr: &PsbtFunding) -> u64 {
+	this_ptr.get_native_ref().channel_value_satoshis
+}
+
+/// Returns an unsigned PSBT containing only the channel's funding output, to which a wallet should
+/// add inputs (and change) before signing it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_get_unsigned_psbt(this_arg: &PsbtFunding) -> Psbt {
+	Psbt::from_bitcoin(&this_arg.get_native_ref().unsigned_psbt())
+}
+
+/// Adds the channel's funding output to an unsigned PSBT built by a wallet, eg one which already
+/// spends the inputs to fund the channel with.
+///
+/// The PSBT is returned unchanged if it already pays the funding output.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_add_channel_output(this_arg: &PsbtFunding, mut psbt: Psbt) -> CResult_PsbtPsbtFundingErrorZ {
+	let psbt = match psbt.into_bitcoin() {
+		Ok(psbt) => psbt,
+		Err(_) => return CResult_PsbtPsbtFundingErrorZ_err(PsbtFundingError::InvalidPsbt),
+	};
+	CResult_PsbtPsbtFundingErrorZ::from_rust(this_arg.get_native_ref().add_channel_output(psbt))
+}
+
+/// Checks that a signed PSBT pays the channel's funding output and only spends segwit outputs,
+/// finalizing any P2WPKH inputs which are not yet, and extracts the funding transaction from it.
+///
+/// The returned transaction may be passed to `ChannelManager_funding_transaction_generated`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_finalize(this_arg: &PsbtFunding, mut signed_psbt: Psbt) -> CResult_TransactionPsbtFundingErrorZ {
+	let psbt = match signed_psbt.into_bitcoin() {
+		Ok(psbt) => psbt,
+		Err(_) => return CResult_TransactionPsbtFundingErrorZ_err(PsbtFundingError::InvalidPsbt),
+	};
+	CResult_TransactionPsbtFundingErrorZ::from_rust(this_arg.get_native_ref().finalize(psbt))
+}
+
+/// Finalizes a signed PSBT as `PsbtFunding_finalize` does and passes the resulting transaction to
+/// `channel_manager`, which broadcasts it once the channel is set up.
+///
+/// The funding transaction is returned on success, so that the wallet can track it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PsbtFunding_funding_transaction_generated(this_arg: &PsbtFunding, channel_manager: &ChannelManager, mut signed_psbt: Psbt) -> CResult_TransactionPsbtFundingErrorZ {
+	let funding = this_arg.get_native_ref();
+	let res = signed_psbt.into_bitcoin().map_err(|_| PsbtFundingError::InvalidPsbt)
+		.and_then(|psbt| funding.finalize(psbt))
+		.and_then(|tx| {
+			channel_manager.get_native_ref().funding_transaction_generated(&funding.temporary_channel_id, tx.clone())
+				.map(|_| tx).map_err(|_| PsbtFundingError::FundingRejected)
+		});
+	CResult_TransactionPsbtFundingErrorZ::from_rust(res)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::blockdata::transaction::{OutPoint as BitcoinOutPoint, TxIn};
+	use bitcoin::hash_types::Txid;
+	use bitcoin::secp256k1::Secp256k1;
+	use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
+	use bitcoin::util::key::PublicKey as BitcoinPublicKey;
+
+	const CHANNEL_VALUE: u64 = 100_000;
+
+	fn funding() -> nativePsbtFunding {
+		let mut output_script = vec![0, 32];
+		output_script.extend_from_slice(&[7; 32]);
+		nativePsbtFunding { temporary_channel_id: [1; 32], output_script: Script::from(output_script), channel_value_satoshis: CHANNEL_VALUE }
+	}
+
+	fn wallet_key() -> BitcoinPublicKey {
+		let key = SecpSecretKey::from_slice(&[2; 32]).unwrap();
+		BitcoinPublicKey { compressed: true, key: bitcoin::secp256k1::key::PublicKey::from_secret_key(&Secp256k1::new(), &key) }
+	}
+
+	/// A PSBT as a wallet would build it, spending one P2WPKH output to change, without the
+	/// channel output.
+	fn wallet_psbt() -> PartiallySignedTransaction {
+		let tx = BitcoinTransaction {
+			version: 2, lock_time: 0,
+			input: vec![TxIn { previous_output: BitcoinOutPoint { txid: Txid::from_slice(&[3; 32]).unwrap(), vout: 1 }, script_sig: Script::new(), sequence: 0xffffffff, witness: Vec::new() }],
+			output: vec![TxOut { value: 50_000, script_pubkey: Script::new_v0_wpkh(&WPubkeyHash::hash(&[4; 33])) }],
+		};
+		let mut psbt = PartiallySignedTransaction::from_unsigned_tx(tx).unwrap();
+		psbt.inputs[0].witness_utxo = Some(TxOut { value: 200_000, script_pubkey: Script::new_v0_wpkh(&WPubkeyHash::hash(&wallet_key().to_bytes())) });
+		psbt
+	}
+
+	fn signed_psbt() -> PartiallySignedTransaction {
+		let mut psbt = funding().add_channel_output(wallet_psbt()).unwrap();
+		psbt.inputs[0].partial_sigs.insert(wallet_key(), vec![0x30, 0x44, 0x01]);
+		psbt
+	}
+
+	#[test]
+	fn channel_output_is_added_once() {
+		let funding = funding();
+		let psbt = funding.add_channel_output(wallet_psbt()).unwrap();
+		assert_eq!(psbt.global.unsigned_tx.output.len(), 2);
+		assert_eq!(psbt.outputs.len(), 2);
+		assert_eq!(psbt.global.unsigned_tx.output[1], funding.channel_output());
+
+		let psbt = funding.add_channel_output(psbt).unwrap();
+		assert_eq!(psbt.global.unsigned_tx.output.len(), 2);
+		assert_eq!(psbt.outputs.len(), 2);
+	}
+
+	#[test]
+	fn mismatched_channel_outputs_are_rejected() {
+		let funding = funding();
+		let mut psbt = wallet_psbt();
+		psbt.global.unsigned_tx.output.push(TxOut { value: CHANNEL_VALUE - 1, script_pubkey: funding.output_script.clone() });
+		psbt.outputs.push(psbt::Output::default());
+		assert_eq!(funding.add_channel_output(psbt.clone()).unwrap_err(), PsbtFundingError::MissingChannelOutput);
+
+		psbt.global.unsigned_tx.output.push(funding.channel_output());
+		psbt.outputs.push(psbt::Output::default());
+		assert_eq!(funding.add_channel_output(psbt).unwrap_err(), PsbtFundingError::DuplicateChannelOutput);
+
+		assert_eq!(funding.finalize(wallet_psbt()).unwrap_err(), PsbtFundingError::MissingChannelOutput);
+		assert_eq!(funding.finalize(funding.unsigned_psbt()).unwrap_err(), PsbtFundingError::NoInputs);
+	}
+
+	#[test]
+	fn p2wpkh_inputs_are_finalized() {
+		let tx = funding().finalize(signed_psbt()).unwrap();
+		assert_eq!(tx.input[0].witness, vec![vec![0x30, 0x44, 0x01], wallet_key().to_bytes()]);
+		assert!(tx.input[0].script_sig.is_empty());
+		assert_eq!(tx.output[1], funding().channel_output());
+	}
+
+	#[test]
+	fn inputs_which_cannot_be_finalized_are_rejected() {
+		// No signature at all.
+		let psbt = funding().add_channel_output(wallet_psbt()).unwrap();
+		assert_eq!(funding().finalize(psbt).unwrap_err(), PsbtFundingError::InputNotFinalized);
+
+		// A signature by a key other than the one the output pays to.
+		let mut psbt = funding().add_channel_output(wallet_psbt()).unwrap();
+		let other_key = SecpSecretKey::from_slice(&[5; 32]).unwrap();
+		let other_key = BitcoinPublicKey { compressed: true, key: bitcoin::secp256k1::key::PublicKey::from_secret_key(&Secp256k1::new(), &other_key) };
+		psbt.inputs[0].partial_sigs.insert(other_key, vec![0x30, 0x44, 0x01]);
+		assert_eq!(funding().finalize(psbt).unwrap_err(), PsbtFundingError::InputNotFinalized);
+
+		// A spend of a P2PKH output, which would need a script_sig.
+		let mut psbt = signed_psbt();
+		psbt.inputs[0].witness_utxo = Some(TxOut { value: 200_000, script_pubkey: Script::new_p2pkh(&wallet_key().pubkey_hash()) });
+		assert_eq!(funding().finalize(psbt).unwrap_err(), PsbtFundingError::InputNotFinalized);
+	}
+
+	#[test]
+	fn non_segwit_inputs_are_rejected() {
+		let mut psbt = funding().add_channel_output(wallet_psbt()).unwrap();
+		psbt.inputs[0].final_script_sig = Some(Script::from(vec![0x51]));
+		assert_eq!(funding().finalize(psbt).unwrap_err(), PsbtFundingError::NonSegwitInput);
+
+		// An input finalized with an empty witness spends a non-segwit output as well.
+		let mut psbt = funding().add_channel_output(wallet_psbt()).unwrap();
+		psbt.inputs[0].final_script_witness = Some(Vec::new());
+		assert_eq!(funding().finalize(psbt).unwrap_err(), PsbtFundingError::NonSegwitInput);
+	}
+
+	#[test]
+	fn invalid_psbts_are_rejected() {
+		let funding = PsbtFunding { inner: ObjOps::heap_alloc(funding()), is_owned: true };
+		let mut garbage = vec![0x70, 0x73, 0x62, 0x74, 0xff, 0x00];
+		let psbt = Psbt { data: garbage.as_mut_ptr(), datalen: garbage.len(), data_is_owned: false };
+		let res = PsbtFunding_finalize(&funding, psbt);
+		assert!(!res.result_ok);
+		assert_eq!(unsafe { *res.contents.err }, PsbtFundingError::InvalidPsbt);
+
+		let psbt = Psbt { data: std::ptr::null_mut(), datalen: 0, data_is_owned: false };
+		assert!(!PsbtFunding_add_channel_output(&funding, psbt).result_ok);
+
+		let res = PsbtFunding_finalize(&funding, Psbt::from_bitcoin(&signed_psbt()));
+		assert!(res.result_ok);
+	}
+}
@@ -0,0 +1,147 @@
+//! A file descriptor which is readable while some condition holds, for integrating LDK state
+//! into `epoll`, `poll`, libuv or GLib event loops.
+//!
+//! On Linux this is an `eventfd`, on other Unix platforms a non-blocking pipe. Elsewhere, or if
+//! the file descriptor could not be created, -1 is reported instead.
+
+use std::sync::Mutex;
+
+#[cfg(unix)]
+use std::ffi::c_void;
+
+pub(crate) struct ReadinessFd {
+	read_fd: i32,
+	write_fd: i32,
+	/// Whether the file descriptor currently holds a token, and is thus readable.
+	ready: Mutex<bool>,
+}
+
+impl ReadinessFd {
+	#[cfg(target_os = "linux")]
+	pub(crate) fn new() -> Self {
+		// eventfd returns -1 on failure, which we report as-is.
+		let fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+		Self { read_fd: fd, write_fd: fd, ready: Mutex::new(false) }
+	}
+
+	#[cfg(all(unix, not(target_os = "linux")))]
+	pub(crate) fn new() -> Self {
+		let mut fds = [-1; 2];
+		if unsafe { libc::pipe(fds.as_mut_ptr()) } < 0 {
+			return Self { read_fd: -1, write_fd: -1, ready: Mutex::new(false) };
+		}
+		for fd in fds.iter() {
+			unsafe {
+				libc::fcntl(*fd, libc::F_SETFL, libc::fcntl(*fd, libc::F_GETFL) | libc::O_NONBLOCK);
+				libc::fcntl(*fd, libc::F_SETFD, libc::FD_CLOEXEC);
+			}
+		}
+		Self { read_fd: fds[0], write_fd: fds[1], ready: Mutex::new(false) }
+	}
+
+	#[cfg(not(unix))]
+	pub(crate) fn new() -> Self {
+		Self { read_fd: -1, write_fd: -1, ready: Mutex::new(false) }
+	}
+
+	/// The file descriptor to poll for readability, or -1 if none is available on this platform.
+	pub(crate) fn fd(&self) -> i32 {
+		self.read_fd
+	}
+
+	/// Sets whether the file descriptor should be readable, returning whether it was before. The
+	/// file descriptor must only be read through this method, never by users polling it.
+	pub(crate) fn set_ready(&self, ready: bool) -> bool {
+		let mut is_ready = self.ready.lock().unwrap();
+		let was_ready = *is_ready;
+		if was_ready == ready { return was_ready; }
+		*is_ready = ready;
+		if self.read_fd >= 0 {
+			if ready { self.write_token(); } else { self.read_token(); }
+		}
+		was_ready
+	}
+
+	/// Whether the file descriptor is currently readable.
+	pub(crate) fn is_ready(&self) -> bool {
+		*self.ready.lock().unwrap()
+	}
+
+	#[cfg(target_os = "linux")]
+	fn write_token(&self) {
+		let one: u64 = 1;
+		unsafe { libc::write(self.write_fd, &one as *const u64 as *const c_void, 8); }
+	}
+	#[cfg(target_os = "linux")]
+	fn read_token(&self) {
+		let mut count: u64 = 0;
+		unsafe { libc::read(self.read_fd, &mut count as *mut u64 as *mut c_void, 8); }
+	}
+
+	#[cfg(all(unix, not(target_os = "linux")))]
+	fn write_token(&self) {
+		let byte: u8 = 1;
+		unsafe { libc::write(self.write_fd, &byte as *const u8 as *const c_void, 1); }
+	}
+	#[cfg(all(unix, not(target_os = "linux")))]
+	fn read_token(&self) {
+		let mut byte: u8 = 0;
+		unsafe { libc::read(self.read_fd, &mut byte as *mut u8 as *mut c_void, 1); }
+	}
+
+	#[cfg(not(unix))]
+	fn write_token(&self) {}
+	#[cfg(not(unix))]
+	fn read_token(&self) {}
+}
+
+impl Drop for ReadinessFd {
+	fn drop(&mut self) {
+		#[cfg(unix)]
+		unsafe {
+			if self.read_fd < 0 { return; }
+			libc::close(self.read_fd);
+			if self.write_fd != self.read_fd { libc::close(self.write_fd); }
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	fn is_readable(fd: i32) -> bool {
+		let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+		assert!(unsafe { libc::poll(&mut pollfd, 1, 0) } >= 0);
+		pollfd.revents & libc::POLLIN != 0
+	}
+
+	#[test]
+	fn set_ready_returns_the_previous_state() {
+		let readiness = ReadinessFd::new();
+		assert!(!readiness.is_ready());
+		assert!(!readiness.set_ready(true));
+		assert!(readiness.is_ready());
+		assert!(readiness.set_ready(true));
+		assert!(readiness.set_ready(false));
+		assert!(!readiness.is_ready());
+		assert!(!readiness.set_ready(false));
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn fd_is_readable_only_while_ready() {
+		let readiness = ReadinessFd::new();
+		assert!(readiness.fd() >= 0);
+		assert!(!is_readable(readiness.fd()));
+		readiness.set_ready(true);
+		assert!(is_readable(readiness.fd()));
+		// Setting it ready again must not add a second token which clearing it would leave behind.
+		readiness.set_ready(true);
+		readiness.set_ready(false);
+		assert!(!is_readable(readiness.fd()));
+		readiness.set_ready(true);
+		assert!(is_readable(readiness.fd()));
+	}
+}
@@ -0,0 +1,661 @@
+//! Keeps us connected to the peers we care about.
+//!
+//! A [`PeerReconnector`] keeps a persisted address book of peers, which can be seeded from the
+//! counterparties of our channels and the addresses they announce in the `NetworkGraph`, and
+//! reconnects to any of them which are not connected through an [`LdkNet`] socket driver.
+//! Failed connection attempts are retried with exponential backoff and jitter, and at most a
+//! fixed number of connection attempts are made at once.
+//!
+//! [`LdkNet`]: crate::extensions::net::LdkNet
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
+
+use lightning::ln::msgs::{DecodeError, NetAddress as RustNetAddress};
+use lightning::util::ser::{Readable, Writeable, Writer};
+
+use crate::c_types::*;
+use crate::c_types::derived::{CResult_NoneErrorZ, CVec_NetAddressZ};
+use crate::extensions::kv_store::*;
+use crate::extensions::net::*;
+use crate::extensions::socks5::{Socks5Destination, Socks5Proxy, nativeSocks5Proxy};
+use crate::lightning::ln::channelmanager::ChannelManager;
+use crate::lightning::routing::network_graph::NetworkGraph;
+
+/// The key under which the address book is stored, in the `""` namespace.
+pub(crate) const PEER_ADDRESS_BOOK_KEY: &str = "peer_address_book";
+const ADDRESS_BOOK_VERSION: u8 = 1;
+/// How often we check which peers are connected and start any due connection attempts.
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Writes the address book: the number of peers as a u32, then each peer's node id, number of
+/// addresses as a u16 and addresses, serialized as in a node_announcement.
+fn write_address_book<W: Writer>(peers: &HashMap<SecpPublicKey, PeerEntry>, w: &mut W) -> Result<(), std::io::Error> {
+	ADDRESS_BOOK_VERSION.write(w)?;
+	(peers.len() as u32).write(w)?;
+	for (node_id, entry) in peers.iter() {
+		node_id.write(w)?;
+		(entry.addresses.len() as u16).write(w)?;
+		for addr in entry.addresses.iter() { addr.write(w)?; }
+	}
+	Ok(())
+}
+
+fn read_address_book(data: &[u8]) -> Result<HashMap<SecpPublicKey, Vec<RustNetAddress>>, DecodeError> {
+	let mut reader = Cursor::new(data);
+	let ver: u8 = Readable::read(&mut reader)?;
+	if ver != ADDRESS_BOOK_VERSION { return Err(DecodeError::UnknownVersion); }
+	let mut book = HashMap::new();
+	let peer_count: u32 = Readable::read(&mut reader)?;
+	for _ in 0..peer_count {
+		let node_id: SecpPublicKey = Readable::read(&mut reader)?;
+		let address_count: u16 = Readable::read(&mut reader)?;
+		let mut addresses = Vec::new();
+		for _ in 0..address_count { addresses.push(Readable::read(&mut reader)?); }
+		book.insert(node_id, addresses);
+	}
+	Ok(book)
+}
+
+/// A connect, disconnect or connection failure of a peer in a [`PeerReconnector`]'s address book.
+#[must_use]
+#[derive(Clone)]
+#[repr(C)]
+pub enum PeerEvent {
+	/// We completed the handshake with the peer, either on an outbound connection we made or an
+	/// inbound connection from them.
+	Connected {
+		/// The node id of the peer.
+		node_id: PublicKey,
+	},
+	/// A previously-connected peer disconnected. A reconnection attempt will be made shortly.
+	Disconnected {
+		/// The node id of the peer.
+		node_id: PublicKey,
+	},
+	/// An attempt to connect to the peer failed.
+	ConnectionFailed {
+		/// The node id of the peer.
+		node_id: PublicKey,
+		/// Why the connection attempt failed.
+		error: LdkNetError,
+		/// The number of seconds until the next connection attempt.
+		retry_in_secs: u64,
+	},
+}
+/// Frees any resources used by the PeerEvent
+#[no_mangle]
+pub extern "C" fn PeerEvent_free(this_ptr: PeerEvent) { }
+/// Creates a copy of the PeerEvent
+#[no_mangle]
+pub extern "C" fn PeerEvent_clone(orig: &PeerEvent) -> PeerEvent {
+	orig.clone()
+}
+
+/// A handler for [`PeerEvent`]s generated by a [`PeerReconnector`].
+///
+/// Events are delivered from the reconnector's background threads, possibly concurrently.
+#[repr(C)]
+pub struct PeerEventHandler {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Handles the given [`PeerEvent`].
+	pub handle_peer_event: extern "C" fn (this_arg: *const c_void, event: PeerEvent),
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for PeerEventHandler {}
+unsafe impl Sync for PeerEventHandler {}
+#[no_mangle]
+pub(crate) extern "C" fn PeerEventHandler_clone_fields(orig: &PeerEventHandler) -> PeerEventHandler {
+	PeerEventHandler {
+		this_arg: orig.this_arg,
+		handle_peer_event: Clone::clone(&orig.handle_peer_event),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn PeerEventHandler_free(this_ptr: PeerEventHandler) { }
+impl Drop for PeerEventHandler {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+struct PeerEntry {
+	addresses: Vec<RustNetAddress>,
+	/// The index into `addresses` of the next address to try.
+	next_address: usize,
+	backoff: Duration,
+	next_attempt: Instant,
+	connecting: bool,
+}
+
+impl PeerEntry {
+	fn new(addresses: Vec<RustNetAddress>, base_backoff: Duration) -> Self {
+		PeerEntry { addresses, next_address: 0, backoff: base_backoff, next_attempt: Instant::now(), connecting: false }
+	}
+
+	/// Returns the address to try next, skipping those `can_reach` rejects.
+	fn address_to_try<F: Fn(&RustNetAddress) -> bool>(&self, can_reach: F) -> Option<RustNetAddress> {
+		let reachable: Vec<&RustNetAddress> = self.addresses.iter().filter(|addr| can_reach(addr)).collect();
+		if reachable.is_empty() { return None; }
+		Some(reachable[self.next_address % reachable.len()].clone())
+	}
+
+	/// Schedules the next attempt after a failed one, at the next address, returning the delay
+	/// until it.
+	fn connection_failed(&mut self, max_backoff: Duration) -> Duration {
+		let delay = jittered(self.backoff);
+		self.next_attempt = Instant::now() + delay;
+		self.backoff = std::cmp::min(self.backoff * 2, max_backoff);
+		self.next_address = self.next_address.wrapping_add(1);
+		delay
+	}
+}
+
+struct ReconnectorState {
+	net: Arc<NetState>,
+	proxy: Option<nativeSocks5Proxy>,
+	store: KVStore,
+	event_handler: PeerEventHandler,
+	peers: Mutex<HashMap<SecpPublicKey, PeerEntry>>,
+	/// Peers which were connected as of the last tick.
+	connected: Mutex<HashSet<SecpPublicKey>>,
+	in_flight: AtomicUsize,
+	max_in_flight: usize,
+	base_backoff: Duration,
+	max_backoff: Duration,
+	connect_timeout: Duration,
+	stopped: Mutex<bool>,
+	stop_cv: Condvar,
+}
+
+/// Returns a random duration between half of `backoff` and `backoff`, so that peers which
+/// failed at the same time (e.g. when our own connection dropped) are not retried in lockstep.
+fn jittered(backoff: Duration) -> Duration {
+	let mut random = [0; 8];
+	if getrandom::getrandom(&mut random).is_err() { return backoff; }
+	let permille = u64::from_le_bytes(random) % 1001;
+	backoff / 2 + Duration::from_millis(backoff.as_millis() as u64 / 2 * permille / 1000)
+}
+
+impl ReconnectorState {
+	fn new_entry(&self, addresses: Vec<RustNetAddress>) -> PeerEntry {
+		PeerEntry::new(addresses, self.base_backoff)
+	}
+
+	fn is_stopped(&self) -> bool { *self.stopped.lock().unwrap() }
+
+	fn emit(&self, event: PeerEvent) {
+		if !self.is_stopped() {
+			(self.event_handler.handle_peer_event)(self.event_handler.this_arg, event);
+		}
+	}
+
+	fn persist(&self) -> Result<(), std::io::Error> {
+		let mut data = Vec::new();
+		write_address_book(&self.peers.lock().unwrap(), &mut data)?;
+		self.store.write(NODE_NAMESPACE, PEER_ADDRESS_BOOK_KEY, &data)
+	}
+
+	/// Whether we can reach the given address, i.e. whether it isn't an onion address or we have
+	/// a proxy to reach it through.
+	fn can_reach(&self, addr: &RustNetAddress) -> bool {
+		match addr {
+			RustNetAddress::IPv4 { .. } | RustNetAddress::IPv6 { .. } => true,
+			RustNetAddress::OnionV2(_) | RustNetAddress::OnionV3 { .. } => self.proxy.is_some(),
+		}
+	}
+
+	fn connect(&self, node_id: SecpPublicKey, addr: &RustNetAddress) -> Result<(), LdkNetError> {
+		if let Some(proxy) = &self.proxy {
+			// Use whatever credentials the proxy was configured with.
+			return proxy.connect_peer(&self.net, node_id, &Socks5Destination::from_net_address(addr), None, self.connect_timeout);
+		}
+		let sock_addr = match addr {
+			RustNetAddress::IPv4 { addr, port } => SocketAddr::from((Ipv4Addr::from(*addr), *port)),
+			RustNetAddress::IPv6 { addr, port } => SocketAddr::from((Ipv6Addr::from(*addr), *port)),
+			_ => return Err(LdkNetError::InvalidAddress),
+		};
+		let stream = TcpStream::connect_timeout(&sock_addr, self.connect_timeout).map_err(|e| match e.kind() {
+			std::io::ErrorKind::TimedOut => LdkNetError::ConnectTimeout,
+			_ => LdkNetError::ConnectFailed,
+		})?;
+		self.net.register_outbound(stream, node_id)
+	}
+
+	fn attempt_connection(self: &Arc<Self>, node_id: SecpPublicKey, addr: RustNetAddress) {
+		let res = self.connect(node_id, &addr);
+		let failure = {
+			let mut peers = self.peers.lock().unwrap();
+			match peers.get_mut(&node_id) {
+				Some(entry) => {
+					entry.connecting = false;
+					match res {
+						Ok(()) => {
+							// Give the handshake until the next backoff to complete before trying again.
+							entry.next_attempt = Instant::now() + entry.backoff;
+							None
+						},
+						Err(e) => Some((e, entry.connection_failed(self.max_backoff))),
+					}
+				},
+				// The peer was removed while we were connecting.
+				None => None,
+			}
+		};
+		if let Some((error, delay)) = failure {
+			self.emit(PeerEvent::ConnectionFailed { node_id: PublicKey::from_rust(&node_id), error, retry_in_secs: delay.as_secs() });
+		}
+		self.in_flight.fetch_sub(1, Ordering::AcqRel);
+		self.stop_cv.notify_all();
+	}
+
+	fn tick(self: &Arc<Self>) {
+		let connected_now: HashSet<SecpPublicKey> = self.net.peer_manager().get_peer_node_ids().drain(..).collect();
+		let (newly_connected, newly_disconnected) = {
+			let mut connected = self.connected.lock().unwrap();
+			let newly_connected: Vec<SecpPublicKey> = connected_now.difference(&connected).cloned().collect();
+			let newly_disconnected: Vec<SecpPublicKey> = connected.difference(&connected_now).cloned().collect();
+			*connected = connected_now.clone();
+			(newly_connected, newly_disconnected)
+		};
+
+		let mut to_connect = Vec::new();
+		{
+			let mut peers = self.peers.lock().unwrap();
+			let now = Instant::now();
+			for node_id in newly_connected.iter() {
+				if let Some(entry) = peers.get_mut(node_id) { entry.backoff = self.base_backoff; }
+			}
+			for node_id in newly_disconnected.iter() {
+				if let Some(entry) = peers.get_mut(node_id) { entry.next_attempt = now; }
+			}
+			for (node_id, entry) in peers.iter_mut() {
+				if connected_now.contains(node_id) || entry.connecting || entry.next_attempt > now { continue; }
+				if self.in_flight.load(Ordering::Acquire) + to_connect.len() >= self.max_in_flight { break; }
+				let addr = match entry.address_to_try(|addr| self.can_reach(addr)) { Some(addr) => addr, None => continue };
+				entry.connecting = true;
+				to_connect.push((*node_id, addr));
+			}
+		}
+
+		for node_id in newly_connected {
+			if self.peers.lock().unwrap().contains_key(&node_id) {
+				self.emit(PeerEvent::Connected { node_id: PublicKey::from_rust(&node_id) });
+			}
+		}
+		for node_id in newly_disconnected {
+			if self.peers.lock().unwrap().contains_key(&node_id) {
+				self.emit(PeerEvent::Disconnected { node_id: PublicKey::from_rust(&node_id) });
+			}
+		}
+		for (node_id, addr) in to_connect {
+			self.in_flight.fetch_add(1, Ordering::AcqRel);
+			let state = Arc::clone(self);
+			let spawn_res = std::thread::Builder::new().name("ldk-reconnect".to_owned())
+				.spawn(move || state.attempt_connection(node_id, addr));
+			if spawn_res.is_err() {
+				self.in_flight.fetch_sub(1, Ordering::AcqRel);
+				if let Some(entry) = self.peers.lock().unwrap().get_mut(&node_id) { entry.connecting = false; }
+			}
+		}
+	}
+
+	fn run(self: Arc<Self>) {
+		let mut stopped = self.stopped.lock().unwrap();
+		while !*stopped {
+			std::mem::drop(stopped);
+			self.tick();
+			stopped = self.stopped.lock().unwrap();
+			stopped = self.stop_cv.wait_timeout(stopped, TICK_INTERVAL).unwrap().0;
+		}
+	}
+
+	fn stop(&self) {
+		*self.stopped.lock().unwrap() = true;
+		self.stop_cv.notify_all();
+	}
+
+	/// Waits for outstanding connection attempts to finish. Must only be called once the
+	/// background thread has exited, as it may otherwise start new attempts.
+	fn wait_for_attempts(&self) {
+		let mut stopped = self.stopped.lock().unwrap();
+		while self.in_flight.load(Ordering::Acquire) != 0 {
+			stopped = self.stop_cv.wait_timeout(stopped, TICK_INTERVAL).unwrap().0;
+		}
+	}
+}
+
+pub(crate) struct nativePeerReconnector {
+	state: Arc<ReconnectorState>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for nativePeerReconnector {
+	fn drop(&mut self) {
+		self.state.stop();
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+		self.state.wait_for_attempts();
+	}
+}
+
+/// Keeps a persisted address book of peers and reconnects to them whenever they are not
+/// connected, created with [`PeerReconnector_new`].
+#[must_use]
+#[repr(C)]
+pub struct PeerReconnector {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativePeerReconnector,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for PeerReconnector {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativePeerReconnector>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the PeerReconnector, if is_owned is set and inner is non-NULL.
+///
+/// This stops reconnecting and blocks until any in-progress connection attempts complete. It
+/// must be called before the `LdkNet` and `PeerManager` are freed.
+#[no_mangle]
+pub extern "C" fn PeerReconnector_free(this_obj: PeerReconnector) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn PeerReconnector_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativePeerReconnector); }
+}
+#[allow(unused)]
+impl PeerReconnector {
+	pub(crate) fn get_native_ref(&self) -> &'static nativePeerReconnector {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativePeerReconnector {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativePeerReconnector {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_PeerReconnectorIOErrorZ
+pub union CResult_PeerReconnectorIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut PeerReconnector,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_PeerReconnectorIOErrorZ represents the result of a fallible operation,
+/// containing a PeerReconnector on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_PeerReconnectorIOErrorZ {
+	/// The contents of this CResult_PeerReconnectorIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_PeerReconnectorIOErrorZPtr,
+	/// Whether this CResult_PeerReconnectorIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_PeerReconnectorIOErrorZ in the success state.
+pub extern "C" fn CResult_PeerReconnectorIOErrorZ_ok(o: PeerReconnector) -> CResult_PeerReconnectorIOErrorZ {
+	CResult_PeerReconnectorIOErrorZ {
+		contents: CResult_PeerReconnectorIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_PeerReconnectorIOErrorZ in the error state.
+pub extern "C" fn CResult_PeerReconnectorIOErrorZ_err(e: IOError) -> CResult_PeerReconnectorIOErrorZ {
+	CResult_PeerReconnectorIOErrorZ {
+		contents: CResult_PeerReconnectorIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_PeerReconnectorIOErrorZ_is_ok(o: &CResult_PeerReconnectorIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_PeerReconnectorIOErrorZ.
+pub extern "C" fn CResult_PeerReconnectorIOErrorZ_free(_res: CResult_PeerReconnectorIOErrorZ) { }
+impl Drop for CResult_PeerReconnectorIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Loads the peer address book from `store` (if one has been stored) and starts a background
+/// thread which reconnects to all peers in it whenever they are not connected via `net`.
+///
+/// If `proxy` is set, all connections are made through it, authenticating with the credentials
+/// it was created with (see `Socks5Proxy_new_with_credentials`), otherwise onion addresses are
+/// ignored. Failed connection attempts are retried after `base_backoff_secs`, doubling after
+/// each consecutive failure up to `max_backoff_secs`, with random jitter. At most
+/// `max_concurrent_connects` connection attempts, each taking at most `connect_timeout_secs`,
+/// are made at once.
+///
+/// Note that proxy (or a relevant inner pointer) may be NULL or all-0s to represent None
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerReconnector_new(net: &LdkNet, mut store: KVStore, mut proxy: Socks5Proxy, mut event_handler: PeerEventHandler, mut max_concurrent_connects: usize, mut base_backoff_secs: u64, mut max_backoff_secs: u64, mut connect_timeout_secs: u64) -> CResult_PeerReconnectorIOErrorZ {
+	let book = match store.read(NODE_NAMESPACE, PEER_ADDRESS_BOOK_KEY) {
+		Ok(data) => match read_address_book(&data) {
+			Ok(book) => book,
+			Err(e) => return CResult_PeerReconnectorIOErrorZ_err(IOError::from_rust(
+				std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Failed to read peer address book: {:?}", e)))),
+		},
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+		Err(e) => return CResult_PeerReconnectorIOErrorZ_err(IOError::from_rust(e)),
+	};
+	let local_proxy = if proxy.inner.is_null() { None } else { Some(*unsafe { Box::from_raw(proxy.take_inner()) }) };
+	let base_backoff = Duration::from_secs(std::cmp::max(base_backoff_secs, 1));
+	let state = Arc::new(ReconnectorState {
+		net: Arc::clone(net.get_native_ref().state()),
+		proxy: local_proxy,
+		store,
+		event_handler,
+		peers: Mutex::new(HashMap::new()),
+		connected: Mutex::new(HashSet::new()),
+		in_flight: AtomicUsize::new(0),
+		max_in_flight: std::cmp::max(max_concurrent_connects, 1),
+		base_backoff,
+		max_backoff: std::cmp::max(Duration::from_secs(max_backoff_secs), base_backoff),
+		connect_timeout: Duration::from_secs(std::cmp::max(connect_timeout_secs, 1)),
+		stopped: Mutex::new(false),
+		stop_cv: Condvar::new(),
+	});
+	{
+		let mut peers = state.peers.lock().unwrap();
+		for (node_id, addresses) in book { peers.insert(node_id, state.new_entry(addresses)); }
+	}
+	let thread_state = Arc::clone(&state);
+	let thread = match std::thread::Builder::new().name("ldk-reconnector".to_owned()).spawn(move || thread_state.run()) {
+		Ok(thread) => thread,
+		Err(e) => return CResult_PeerReconnectorIOErrorZ_err(IOError::from_rust(e)),
+	};
+	CResult_PeerReconnectorIOErrorZ_ok(PeerReconnector { inner: ObjOps::heap_alloc(nativePeerReconnector { state, thread: Some(thread) }), is_owned: true })
+}
+
+/// Adds the given peer to the address book, or adds the given addresses to it if the peer is
+/// already known, and persists the address book.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerReconnector_add_peer(this_arg: &PeerReconnector, mut node_id: PublicKey, mut addresses: CVec_NetAddressZ) -> CResult_NoneErrorZ {
+	let state = &this_arg.get_native_ref().state;
+	let mut new_addresses: Vec<RustNetAddress> = addresses.into_rust().drain(..).map(|addr| addr.into_native()).collect();
+	{
+		let mut peers = state.peers.lock().unwrap();
+		let entry = peers.entry(node_id.into_rust()).or_insert_with(|| state.new_entry(Vec::new()));
+		for addr in new_addresses.drain(..) {
+			if !entry.addresses.contains(&addr) { entry.addresses.push(addr); }
+		}
+	}
+	none_result_from_rust(state.persist())
+}
+
+/// Removes the given peer from the address book and persists the address book. Any existing
+/// connection to the peer is left alone, but it will no longer be reconnected.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerReconnector_remove_peer(this_arg: &PeerReconnector, mut node_id: PublicKey) -> CResult_NoneErrorZ {
+	let state = &this_arg.get_native_ref().state;
+	state.peers.lock().unwrap().remove(&node_id.into_rust());
+	none_result_from_rust(state.persist())
+}
+
+/// Adds the counterparties of all channels in `channel_manager` to the address book, along with
+/// the addresses they announced in `network_graph`, and persists the address book.
+///
+/// Counterparties which are not already in the address book and have not announced any
+/// addresses are skipped, as we have no way to reach them. Call this on startup and whenever
+/// new channels are opened.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PeerReconnector_seed(this_arg: &PeerReconnector, channel_manager: &ChannelManager, network_graph: &NetworkGraph) -> CResult_NoneErrorZ {
+	let state = &this_arg.get_native_ref().state;
+	let graph = network_graph.get_native_ref().read_only();
+	{
+		let mut peers = state.peers.lock().unwrap();
+		for channel in channel_manager.get_native_ref().list_channels() {
+			let node_id = channel.counterparty.node_id;
+			let announced = graph.get_addresses(&node_id).unwrap_or(Vec::new());
+			if announced.is_empty() && !peers.contains_key(&node_id) { continue; }
+			let entry = peers.entry(node_id).or_insert_with(|| state.new_entry(Vec::new()));
+			for addr in announced {
+				if !entry.addresses.contains(&addr) { entry.addresses.push(addr); }
+			}
+		}
+	}
+	none_result_from_rust(state.persist())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::secp256k1::Secp256k1;
+	use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
+	use crate::extensions::test_utils::MemoryStore;
+
+	fn node_id(seed: u8) -> SecpPublicKey {
+		SecpPublicKey::from_secret_key(&Secp256k1::new(), &SecpSecretKey::from_slice(&[seed; 32]).unwrap())
+	}
+
+	fn ipv4(port: u16) -> RustNetAddress {
+		RustNetAddress::IPv4 { addr: [127, 0, 0, 1], port }
+	}
+
+	fn onion() -> RustNetAddress {
+		RustNetAddress::OnionV3 { ed25519_pubkey: [7; 32], checksum: 42, version: 3, port: 9735 }
+	}
+
+	#[test]
+	fn address_book_round_trip() {
+		let mut peers = HashMap::new();
+		peers.insert(node_id(1), PeerEntry::new(vec![ipv4(9735), RustNetAddress::IPv6 { addr: [1; 16], port: 9736 }, onion()], Duration::from_secs(1)));
+		peers.insert(node_id(2), PeerEntry::new(Vec::new(), Duration::from_secs(1)));
+		let mut data = Vec::new();
+		write_address_book(&peers, &mut data).unwrap();
+
+		let store = MemoryStore::new().as_kv_store();
+		store.write(NODE_NAMESPACE, PEER_ADDRESS_BOOK_KEY, &data).unwrap();
+		let book = read_address_book(&store.read(NODE_NAMESPACE, PEER_ADDRESS_BOOK_KEY).unwrap()).unwrap();
+		assert_eq!(book.len(), 2);
+		assert_eq!(book[&node_id(1)], peers[&node_id(1)].addresses);
+		assert!(book[&node_id(2)].is_empty());
+
+		assert!(read_address_book(&data[..data.len() - 1]).is_err());
+		let mut unknown_version = data.clone();
+		unknown_version[0] = ADDRESS_BOOK_VERSION + 1;
+		assert!(read_address_book(&unknown_version).is_err());
+	}
+
+	#[test]
+	fn address_book_uses_node_announcement_addresses() {
+		let mut data = vec![ADDRESS_BOOK_VERSION, 0, 0, 0, 1];
+		data.extend_from_slice(&node_id(1).serialize());
+		data.extend_from_slice(&[0, 1, 1, 127, 0, 0, 1, 0x26, 0x07]);
+		let book = read_address_book(&data).unwrap();
+		assert_eq!(book[&node_id(1)], vec![ipv4(9735)]);
+
+		// An unknown address type cannot be skipped without knowing its length.
+		data[5 + 33 + 2] = 42;
+		assert!(read_address_book(&data).is_err());
+	}
+
+	#[test]
+	fn failed_connections_back_off() {
+		let base_backoff = Duration::from_secs(2);
+		let max_backoff = Duration::from_secs(16);
+		let mut entry = PeerEntry::new(vec![ipv4(1), onion(), ipv4(2)], base_backoff);
+		let is_ip = |addr: &RustNetAddress| !matches!(addr, RustNetAddress::OnionV3 { .. });
+
+		let mut backoff = base_backoff;
+		for attempt in 0..6 {
+			assert_eq!(entry.address_to_try(is_ip), Some(ipv4(if attempt % 2 == 0 { 1 } else { 2 })));
+			let before = Instant::now();
+			let delay = entry.connection_failed(max_backoff);
+			assert!(delay >= backoff / 2 && delay <= backoff);
+			assert!(entry.next_attempt >= before + delay);
+			backoff = std::cmp::min(backoff * 2, max_backoff);
+			assert_eq!(entry.backoff, backoff);
+		}
+		assert_eq!(entry.backoff, max_backoff);
+
+		assert_eq!(entry.address_to_try(|_| false), None);
+	}
+
+	#[test]
+	fn jitter_stays_within_half_of_backoff() {
+		for _ in 0..100 {
+			let delay = jittered(Duration::from_secs(10));
+			assert!(delay >= Duration::from_secs(5) && delay <= Duration::from_secs(10));
+		}
+	}
+}
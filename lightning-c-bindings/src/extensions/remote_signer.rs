@@ -0,0 +1,1023 @@
+//! A `KeysInterface` and `Sign` implementation which forwards every call to a signer in another
+//! process (an HSM, a secure enclave app or a separate hardened process), and the server side of
+//! the same protocol wrapping any local `KeysInterface`, such as a `KeysManager`.
+//!
+//! `RemoteKeysInterface_new` returns a `KeysInterface` to hand to the `ChannelManager` and
+//! `ChainMonitor` in place of a `KeysManager`. It sends requests over a [`SignerTransport`], either
+//! one implemented by the host or one built from a pipe or socket by `SignerTransport_new_fd`. On
+//! the signing side, a [`RemoteSignerServer`] answers the requests, either passed to
+//! `RemoteSignerServer_handle_request` by the host or read from a pipe or socket by
+//! `RemoteSignerServer_serve_fd`. `RemoteSignerServer_as_SignerTransport` connects a client to a
+//! server in the same process, eg for testing.
+//!
+//! Note that LDK requires the node secret key in the node's own process, so it is sent to the
+//! client on request. Only channel keys and the keys behind `sign_invoice` stay in the signer.
+//!
+//! Calls whose `KeysInterface` or `BaseSign` signature cannot return an error, namely
+//! `get_node_secret`, `get_destination_script`, `get_shutdown_scriptpubkey`, `get_channel_signer`,
+//! `get_secure_random_bytes`, `get_inbound_payment_key_material`, `get_per_commitment_point`,
+//! `release_commitment_secret` and `ready_channel`, are retried with exponential backoff while the
+//! transport fails, giving a restarting signer or reconnecting transport about 25 seconds to come
+//! back. They panic if it does not, or if the signer returns an error, as LDK cannot safely
+//! continue without their result. Transport and signer failures of all other calls are returned
+//! to LDK as errors, which may force-close the affected channel.
+//!
+//! # Wire format
+//!
+//! Each request is answered by exactly one response, in order. Over a pipe or socket, each request
+//! and response is prefixed with its length as a big-endian u32, which is not part of the message
+//! passed to `SignerTransport::send_request` or `RemoteSignerServer_handle_request`.
+//!
+//! A request consists of a big-endian u16 message type followed by its fields, and a response of a
+//! status byte, 0 for success or 1 for failure, followed by the fields of a successful response.
+//! Fields are serialized as by the `_write` functions of the bindings (eg `CommitmentTransaction_write`
+//! or `ChannelTransactionParameters_write`), integers as big-endian and transaction input indices
+//! as u64. Requests concerning a channel's signer start with the 32-byte `channel_keys_id` the
+//! signer was returned with.
+//!
+//! | Type | Request | Request fields | Response fields |
+//! |------|---------|----------------|-----------------|
+//! | 1 | `get_node_secret` | | SecretKey |
+//! | 2 | `get_destination_script` | | Script |
+//! | 3 | `get_shutdown_scriptpubkey` | | ShutdownScript |
+//! | 4 | `get_channel_signer` | bool inbound, u64 channel_value_satoshis | [u8; 32] channel_keys_id, ChannelPublicKeys |
+//! | 5 | `get_secure_random_bytes` | | [u8; 32] |
+//! | 6 | `sign_invoice` | Vec<u8> invoice_preimage | u8 recovery id, [u8; 64] compact signature |
+//! | 7 | `get_inbound_payment_key_material` | | [u8; 32] |
+//! | 16 | `get_per_commitment_point` | u64 idx | PublicKey |
+//! | 17 | `release_commitment_secret` | u64 idx | [u8; 32] |
+//! | 18 | `validate_holder_commitment` | HolderCommitmentTransaction | |
+//! | 19 | `sign_counterparty_commitment` | CommitmentTransaction | Signature, Vec<Signature> |
+//! | 20 | `validate_counterparty_revocation` | u64 idx, SecretKey | |
+//! | 21 | `sign_holder_commitment_and_htlcs` | HolderCommitmentTransaction | Signature, Vec<Signature> |
+//! | 22 | `sign_justice_revoked_output` | Transaction, u64 input, u64 amount, SecretKey | Signature |
+//! | 23 | `sign_justice_revoked_htlc` | Transaction, u64 input, u64 amount, SecretKey, HTLCOutputInCommitment | Signature |
+//! | 24 | `sign_counterparty_htlc_transaction` | Transaction, u64 input, u64 amount, PublicKey, HTLCOutputInCommitment | Signature |
+//! | 25 | `sign_closing_transaction` | ClosingTransaction (see below) | Signature |
+//! | 26 | `sign_channel_announcement` | UnsignedChannelAnnouncement | Signature |
+//! | 27 | `ready_channel` | ChannelTransactionParameters | |
+//!
+//! `ClosingTransaction` has no serialization of its own, so it is sent as its holder and
+//! counterparty values (u64s), holder and counterparty scripts (Scripts) and funding outpoint
+//! (a Txid and a u32 output index), from which the signer rebuilds it.
+//!
+//! # Server state
+//!
+//! The server keeps the signer of each channel, along with the channel parameters it was readied
+//! with, in a [`KVStore`] under the `remote_signer` namespace, keyed by the hex-encoded
+//! `channel_keys_id`. Signers are written before the request creating or readying them is
+//! answered, and are never removed, as LDK does not report when a channel's keys are no longer
+//! needed.
+//!
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bitcoin::blockdata::script::Script;
+use bitcoin::blockdata::transaction::{OutPoint as BitcoinOutPoint, Transaction as BitcoinTransaction};
+use bitcoin::hash_types::Txid;
+use bitcoin::hashes::hex::{FromHex, ToHex};
+use bitcoin::secp256k1::{All, Secp256k1, Signature as SecpSignature};
+use bitcoin::secp256k1::key::{PublicKey as SecpPublicKey, SecretKey as SecpSecretKey};
+use bitcoin::secp256k1::recovery::{RecoverableSignature as SecpRecoverableSignature, RecoveryId};
+
+use lightning::chain::keysinterface::{BaseSign as rustBaseSign, KeyMaterial, KeysInterface as rustKeysInterface, Sign as rustSign};
+use lightning::ln::chan_utils::{ChannelPublicKeys as nativeChannelPublicKeys, ChannelTransactionParameters as nativeChannelTransactionParameters, ClosingTransaction as nativeClosingTransaction, CommitmentTransaction as nativeCommitmentTransaction, HolderCommitmentTransaction as nativeHolderCommitmentTransaction, HTLCOutputInCommitment as nativeHTLCOutputInCommitment};
+use lightning::ln::msgs::{DecodeError as nativeDecodeError, UnsignedChannelAnnouncement as nativeUnsignedChannelAnnouncement};
+use lightning::ln::script::ShutdownScript as nativeShutdownScript;
+use lightning::util::ser::{Readable, Writeable, Writer};
+
+use crate::c_types::*;
+use crate::c_types::derived::{CResult_NoneErrorZ, CVec_u8Z};
+use crate::extensions::kv_store::*;
+use crate::extensions::signer_adapter::keys_interface_from_native;
+use crate::lightning::chain::keysinterface::{KeysInterface, Sign};
+
+/// The namespace server-side signers are stored under.
+const SIGNER_NAMESPACE: &str = "remote_signer";
+/// The largest message accepted over a pipe or socket. Commitment transactions with the maximum
+/// number of HTLCs are well below this.
+const MAX_MESSAGE_LEN: u32 = 4 * 1024 * 1024;
+
+const SERIALIZATION_VERSION: u8 = 1;
+
+/// How many times a request which cannot fail is resent after a transport failure.
+const TRANSPORT_RETRIES: u32 = 8;
+/// How long to wait before resending a request which cannot fail for the first time, doubling
+/// after each further attempt.
+const TRANSPORT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+const STATUS_OK: u8 = 0;
+const STATUS_ERR: u8 = 1;
+
+const GET_NODE_SECRET: u16 = 1;
+const GET_DESTINATION_SCRIPT: u16 = 2;
+const GET_SHUTDOWN_SCRIPTPUBKEY: u16 = 3;
+const GET_CHANNEL_SIGNER: u16 = 4;
+const GET_SECURE_RANDOM_BYTES: u16 = 5;
+const SIGN_INVOICE: u16 = 6;
+const GET_INBOUND_PAYMENT_KEY_MATERIAL: u16 = 7;
+const GET_PER_COMMITMENT_POINT: u16 = 16;
+const RELEASE_COMMITMENT_SECRET: u16 = 17;
+const VALIDATE_HOLDER_COMMITMENT: u16 = 18;
+const SIGN_COUNTERPARTY_COMMITMENT: u16 = 19;
+const VALIDATE_COUNTERPARTY_REVOCATION: u16 = 20;
+const SIGN_HOLDER_COMMITMENT_AND_HTLCS: u16 = 21;
+const SIGN_JUSTICE_REVOKED_OUTPUT: u16 = 22;
+const SIGN_JUSTICE_REVOKED_HTLC: u16 = 23;
+const SIGN_COUNTERPARTY_HTLC_TRANSACTION: u16 = 24;
+const SIGN_CLOSING_TRANSACTION: u16 = 25;
+const SIGN_CHANNEL_ANNOUNCEMENT: u16 = 26;
+const READY_CHANNEL: u16 = 27;
+
+/// Serializes a message type or status followed by the given fields.
+macro_rules! encode_msg {
+	($prefix: expr $(, $field: expr)*) => {{
+		let mut msg = Vec::new();
+		$prefix.write(&mut msg).expect("In-memory writes cannot fail");
+		$( $field.write(&mut msg).expect("In-memory writes cannot fail"); )*
+		msg
+	}}
+}
+
+/// Sends requests to a remote signer and returns its responses, eg over a pipe, a socket or an
+/// enclave's message interface. See the module documentation for the message format.
+#[repr(C)]
+pub struct SignerTransport {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Sends `request` to the signer and blocks until its response is received, returning the
+	/// response, or an error if the signer could not be reached.
+	///
+	/// This may be called from several threads at once, each of which must receive the response
+	/// to its own request.
+	///
+	/// Requests LDK cannot handle the failure of are sent again after an error, so the transport
+	/// may reconnect to a restarted signer from within a later call.
+	pub send_request: extern "C" fn (this_arg: *const c_void, request: u8slice) -> CResult_CVec_u8ZIOErrorZ,
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for SignerTransport {}
+unsafe impl Sync for SignerTransport {}
+#[no_mangle]
+pub(crate) extern "C" fn SignerTransport_clone_fields(orig: &SignerTransport) -> SignerTransport {
+	SignerTransport {
+		this_arg: orig.this_arg,
+		send_request: Clone::clone(&orig.send_request),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn SignerTransport_free(this_ptr: SignerTransport) { }
+impl Drop for SignerTransport {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+impl SignerTransport {
+	/// Sends a request, returning a reader over the fields of a successful response.
+	fn call(&self, request: Vec<u8>) -> Result<Cursor<Vec<u8>>, ()> {
+		let response = (self.send_request)(self.this_arg, u8slice::from_slice(&request)).into_rust().map_err(|_| ())?;
+		read_response(response)
+	}
+
+	/// Sends a request LDK cannot handle the failure of, resending it up to [`TRANSPORT_RETRIES`]
+	/// times while the transport fails, first after `retry_interval`. Failure responses from the
+	/// signer are not retried.
+	fn call_retrying(&self, request: Vec<u8>, mut retry_interval: Duration) -> Result<Cursor<Vec<u8>>, ()> {
+		let mut retries = 0;
+		loop {
+			match (self.send_request)(self.this_arg, u8slice::from_slice(&request)).into_rust() {
+				Ok(response) => return read_response(response),
+				Err(_) if retries < TRANSPORT_RETRIES => {
+					retries += 1;
+					thread::sleep(retry_interval);
+					retry_interval *= 2;
+				},
+				Err(_) => return Err(()),
+			}
+		}
+	}
+
+	fn call_and_read<T: Readable>(&self, request: Vec<u8>) -> Result<T, ()> {
+		let mut reader = self.call(request)?;
+		Readable::read(&mut reader).map_err(|_| ())
+	}
+
+	fn call_and_read_retrying<T: Readable>(&self, request: Vec<u8>, retry_interval: Duration) -> Result<T, ()> {
+		let mut reader = self.call_retrying(request, retry_interval)?;
+		Readable::read(&mut reader).map_err(|_| ())
+	}
+}
+
+/// Returns a reader over the fields of a successful response.
+fn read_response(response: Vec<u8>) -> Result<Cursor<Vec<u8>>, ()> {
+	match response.first() {
+		Some(&STATUS_OK) => {
+			let mut reader = Cursor::new(response);
+			reader.set_position(1);
+			Ok(reader)
+		},
+		_ => Err(()),
+	}
+}
+
+fn write_closing_transaction<W: Writer>(closing_tx: &nativeClosingTransaction, w: &mut W) -> Result<(), std::io::Error> {
+	let funding_outpoint = closing_tx.trust().built_transaction().input[0].previous_output;
+	closing_tx.to_holder_value_sat().write(w)?;
+	closing_tx.to_counterparty_value_sat().write(w)?;
+	closing_tx.to_holder_script().write(w)?;
+	closing_tx.to_counterparty_script().write(w)?;
+	funding_outpoint.txid.write(w)?;
+	funding_outpoint.vout.write(w)
+}
+
+fn read_closing_transaction<R: std::io::Read>(r: &mut R) -> Result<nativeClosingTransaction, nativeDecodeError> {
+	let to_holder_value_sat: u64 = Readable::read(r)?;
+	let to_counterparty_value_sat: u64 = Readable::read(r)?;
+	let to_holder_script: Script = Readable::read(r)?;
+	let to_counterparty_script: Script = Readable::read(r)?;
+	let txid: Txid = Readable::read(r)?;
+	let vout: u32 = Readable::read(r)?;
+	Ok(nativeClosingTransaction::new(to_holder_value_sat, to_counterparty_value_sat, to_holder_script, to_counterparty_script, BitcoinOutPoint { txid, vout }))
+}
+
+fn write_recoverable_signature<W: Writer>(sig: &SecpRecoverableSignature, w: &mut W) -> Result<(), std::io::Error> {
+	let (recovery_id, compact) = sig.serialize_compact();
+	(recovery_id.to_i32() as u8).write(w)?;
+	w.write_all(&compact)
+}
+
+fn read_recoverable_signature<R: std::io::Read>(r: &mut R) -> Result<SecpRecoverableSignature, nativeDecodeError> {
+	let recovery_id: u8 = Readable::read(r)?;
+	let mut compact = [0; 64];
+	r.read_exact(&mut compact)?;
+	let recovery_id = RecoveryId::from_i32(recovery_id as i32).map_err(|_| nativeDecodeError::InvalidValue)?;
+	SecpRecoverableSignature::from_compact(&compact, recovery_id).map_err(|_| nativeDecodeError::InvalidValue)
+}
+
+/// The client side of a channel's signer, which only knows its public keys.
+#[derive(Clone)]
+struct RemoteSigner {
+	transport: Arc<SignerTransport>,
+	retry_interval: Duration,
+	channel_keys_id: [u8; 32],
+	pubkeys: nativeChannelPublicKeys,
+}
+
+impl RemoteSigner {
+	fn call<T: Readable>(&self, request: Vec<u8>) -> Result<T, ()> {
+		self.transport.call_and_read(request)
+	}
+	fn call_retrying<T: Readable>(&self, request: Vec<u8>) -> Result<T, ()> {
+		self.transport.call_and_read_retrying(request, self.retry_interval)
+	}
+	fn call_none(&self, request: Vec<u8>) -> Result<(), ()> {
+		self.transport.call(request).map(|_| ())
+	}
+	fn call_signatures(&self, request: Vec<u8>) -> Result<(SecpSignature, Vec<SecpSignature>), ()> {
+		let mut reader = self.transport.call(request)?;
+		let sig: SecpSignature = Readable::read(&mut reader).map_err(|_| ())?;
+		let htlc_sigs: Vec<SecpSignature> = Readable::read(&mut reader).map_err(|_| ())?;
+		Ok((sig, htlc_sigs))
+	}
+}
+
+impl rustBaseSign for RemoteSigner {
+	fn get_per_commitment_point(&self, idx: u64, _secp_ctx: &Secp256k1<All>) -> SecpPublicKey {
+		self.call_retrying(encode_msg!(GET_PER_COMMITMENT_POINT, self.channel_keys_id, idx))
+			.expect("Remote signer failed to provide a per-commitment point")
+	}
+	fn release_commitment_secret(&self, idx: u64) -> [u8; 32] {
+		self.call_retrying(encode_msg!(RELEASE_COMMITMENT_SECRET, self.channel_keys_id, idx))
+			.expect("Remote signer failed to release a commitment secret")
+	}
+	fn validate_holder_commitment(&self, holder_tx: &nativeHolderCommitmentTransaction) -> Result<(), ()> {
+		self.call_none(encode_msg!(VALIDATE_HOLDER_COMMITMENT, self.channel_keys_id, holder_tx))
+	}
+	fn pubkeys(&self) -> &nativeChannelPublicKeys {
+		&self.pubkeys
+	}
+	fn channel_keys_id(&self) -> [u8; 32] {
+		self.channel_keys_id
+	}
+	fn sign_counterparty_commitment(&self, commitment_tx: &nativeCommitmentTransaction, _secp_ctx: &Secp256k1<All>) -> Result<(SecpSignature, Vec<SecpSignature>), ()> {
+		self.call_signatures(encode_msg!(SIGN_COUNTERPARTY_COMMITMENT, self.channel_keys_id, commitment_tx))
+	}
+	fn validate_counterparty_revocation(&self, idx: u64, secret: &SecpSecretKey) -> Result<(), ()> {
+		self.call_none(encode_msg!(VALIDATE_COUNTERPARTY_REVOCATION, self.channel_keys_id, idx, secret))
+	}
+	fn sign_holder_commitment_and_htlcs(&self, commitment_tx: &nativeHolderCommitmentTransaction, _secp_ctx: &Secp256k1<All>) -> Result<(SecpSignature, Vec<SecpSignature>), ()> {
+		self.call_signatures(encode_msg!(SIGN_HOLDER_COMMITMENT_AND_HTLCS, self.channel_keys_id, commitment_tx))
+	}
+	fn sign_justice_revoked_output(&self, justice_tx: &BitcoinTransaction, input: usize, amount: u64, per_commitment_key: &SecpSecretKey, _secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.call(encode_msg!(SIGN_JUSTICE_REVOKED_OUTPUT, self.channel_keys_id, justice_tx, (input as u64), amount, per_commitment_key))
+	}
+	fn sign_justice_revoked_htlc(&self, justice_tx: &BitcoinTransaction, input: usize, amount: u64, per_commitment_key: &SecpSecretKey, htlc: &nativeHTLCOutputInCommitment, _secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.call(encode_msg!(SIGN_JUSTICE_REVOKED_HTLC, self.channel_keys_id, justice_tx, (input as u64), amount, per_commitment_key, htlc))
+	}
+	fn sign_counterparty_htlc_transaction(&self, htlc_tx: &BitcoinTransaction, input: usize, amount: u64, per_commitment_point: &SecpPublicKey, htlc: &nativeHTLCOutputInCommitment, _secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.call(encode_msg!(SIGN_COUNTERPARTY_HTLC_TRANSACTION, self.channel_keys_id, htlc_tx, (input as u64), amount, per_commitment_point, htlc))
+	}
+	fn sign_closing_transaction(&self, closing_tx: &nativeClosingTransaction, _secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		let mut request = encode_msg!(SIGN_CLOSING_TRANSACTION, self.channel_keys_id);
+		write_closing_transaction(closing_tx, &mut request).expect("In-memory writes cannot fail");
+		self.call(request)
+	}
+	fn sign_channel_announcement(&self, msg: &nativeUnsignedChannelAnnouncement, _secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.call(encode_msg!(SIGN_CHANNEL_ANNOUNCEMENT, self.channel_keys_id, msg))
+	}
+	fn ready_channel(&mut self, channel_parameters: &nativeChannelTransactionParameters) {
+		self.transport.call_retrying(encode_msg!(READY_CHANNEL, self.channel_keys_id, channel_parameters), self.retry_interval)
+			.map(|_| ()).expect("Remote signer failed to ready a channel")
+	}
+}
+
+impl Writeable for RemoteSigner {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), std::io::Error> {
+		SERIALIZATION_VERSION.write(w)?;
+		self.channel_keys_id.write(w)?;
+		self.pubkeys.write(w)
+	}
+}
+
+impl rustSign for RemoteSigner {}
+
+/// The client side of a remote signer.
+struct RemoteKeysInterface {
+	transport: Arc<SignerTransport>,
+	retry_interval: Duration,
+}
+
+impl rustKeysInterface for RemoteKeysInterface {
+	type Signer = RemoteSigner;
+
+	fn get_node_secret(&self) -> SecpSecretKey {
+		self.transport.call_and_read_retrying(encode_msg!(GET_NODE_SECRET), self.retry_interval)
+			.expect("Remote signer failed to provide the node secret")
+	}
+	fn get_destination_script(&self) -> Script {
+		self.transport.call_and_read_retrying(encode_msg!(GET_DESTINATION_SCRIPT), self.retry_interval)
+			.expect("Remote signer failed to provide a destination script")
+	}
+	fn get_shutdown_scriptpubkey(&self) -> nativeShutdownScript {
+		self.transport.call_and_read_retrying(encode_msg!(GET_SHUTDOWN_SCRIPTPUBKEY), self.retry_interval)
+			.expect("Remote signer failed to provide a shutdown script")
+	}
+	fn get_channel_signer(&self, inbound: bool, channel_value_satoshis: u64) -> RemoteSigner {
+		let mut reader = self.transport.call_retrying(encode_msg!(GET_CHANNEL_SIGNER, inbound, channel_value_satoshis), self.retry_interval)
+			.expect("Remote signer failed to provide a channel signer");
+		let channel_keys_id: [u8; 32] = Readable::read(&mut reader).expect("Remote signer returned an invalid channel signer");
+		let pubkeys: nativeChannelPublicKeys = Readable::read(&mut reader).expect("Remote signer returned an invalid channel signer");
+		RemoteSigner { transport: Arc::clone(&self.transport), retry_interval: self.retry_interval, channel_keys_id, pubkeys }
+	}
+	fn get_secure_random_bytes(&self) -> [u8; 32] {
+		self.transport.call_and_read_retrying(encode_msg!(GET_SECURE_RANDOM_BYTES), self.retry_interval)
+			.expect("Remote signer failed to provide random bytes")
+	}
+	fn read_chan_signer(&self, mut reader: &[u8]) -> Result<RemoteSigner, nativeDecodeError> {
+		let ver: u8 = Readable::read(&mut reader)?;
+		if ver > SERIALIZATION_VERSION { return Err(nativeDecodeError::UnknownVersion); }
+		let channel_keys_id = Readable::read(&mut reader)?;
+		let pubkeys = Readable::read(&mut reader)?;
+		Ok(RemoteSigner { transport: Arc::clone(&self.transport), retry_interval: self.retry_interval, channel_keys_id, pubkeys })
+	}
+	fn sign_invoice(&self, invoice_preimage: Vec<u8>) -> Result<SecpRecoverableSignature, ()> {
+		let mut reader = self.transport.call(encode_msg!(SIGN_INVOICE, invoice_preimage))?;
+		read_recoverable_signature(&mut reader).map_err(|_| ())
+	}
+	fn get_inbound_payment_key_material(&self) -> KeyMaterial {
+		KeyMaterial(self.transport.call_and_read_retrying(encode_msg!(GET_INBOUND_PAYMENT_KEY_MATERIAL), self.retry_interval)
+			.expect("Remote signer failed to provide inbound payment key material"))
+	}
+}
+
+/// Creates a `KeysInterface` whose signers forward all calls to a remote signer over `transport`.
+///
+/// Channel signers serialized by LDK (eg within `ChannelMonitor`s) only contain their
+/// `channel_keys_id` and public keys, and must be read back with a `KeysInterface` connected to the
+/// same signer.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RemoteKeysInterface_new(mut transport: SignerTransport) -> KeysInterface {
+	keys_interface_from_native(RemoteKeysInterface { transport: Arc::new(transport), retry_interval: TRANSPORT_RETRY_INTERVAL })
+}
+
+/// A channel signer held by the server, along with the parameters it was readied with.
+struct ServerSigner {
+	signer: Sign,
+	channel_parameters: Option<nativeChannelTransactionParameters>,
+}
+
+impl Writeable for ServerSigner {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), std::io::Error> {
+		SERIALIZATION_VERSION.write(w)?;
+		self.signer.encode().write(w)?;
+		self.channel_parameters.write(w)
+	}
+}
+
+pub(crate) struct nativeRemoteSignerServer {
+	keys: KeysInterface,
+	store: KVStore,
+	/// The signers by `channel_keys_id`. Each signer has its own lock, so that the map is only
+	/// locked while looking a signer up and not while it signs.
+	signers: Mutex<HashMap<[u8; 32], Arc<Mutex<ServerSigner>>>>,
+}
+
+impl nativeRemoteSignerServer {
+	pub(crate) fn load(keys: KeysInterface, store: KVStore) -> Result<Self, std::io::Error> {
+		let mut signers = HashMap::new();
+		for key in store.list(SIGNER_NAMESPACE)? {
+			let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid stored remote signer");
+			let channel_keys_id = <[u8; 32]>::from_hex(&key).map_err(|_| invalid())?;
+			let data = store.read(SIGNER_NAMESPACE, &key)?;
+			let mut reader = Cursor::new(data);
+			let ver: u8 = Readable::read(&mut reader).map_err(|_| invalid())?;
+			if ver > SERIALIZATION_VERSION { return Err(invalid()); }
+			let signer_data: Vec<u8> = Readable::read(&mut reader).map_err(|_| invalid())?;
+			let channel_parameters: Option<nativeChannelTransactionParameters> = Readable::read(&mut reader).map_err(|_| invalid())?;
+			let mut signer = keys.read_chan_signer(&signer_data).map_err(|_| invalid())?;
+			if let Some(params) = &channel_parameters {
+				signer.ready_channel(params);
+			}
+			signers.insert(channel_keys_id, Arc::new(Mutex::new(ServerSigner { signer, channel_parameters })));
+		}
+		Ok(Self { keys, store, signers: Mutex::new(signers) })
+	}
+
+	fn persist(&self, channel_keys_id: &[u8; 32], signer: &ServerSigner) -> Result<(), ()> {
+		self.store.write(SIGNER_NAMESPACE, &channel_keys_id.to_hex(), &signer.encode()).map_err(|_| ())
+	}
+
+	fn handle_request(&self, request: &[u8]) -> Vec<u8> {
+		match self.process(&mut Cursor::new(request)) {
+			Ok(mut response) => { response.insert(0, STATUS_OK); response },
+			Err(()) => vec![STATUS_ERR],
+		}
+	}
+
+	/// Processes a request, returning the fields of the response.
+	fn process(&self, r: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ()> {
+		macro_rules! read { () => { Readable::read(r).map_err(|_| ())? } }
+		macro_rules! response { ($($field: expr),*) => {{
+			let mut msg: Vec<u8> = Vec::new();
+			$( $field.write(&mut msg).expect("In-memory writes cannot fail"); )*
+			msg
+		}} }
+
+		let msg_type: u16 = read!();
+		match msg_type {
+			GET_NODE_SECRET => return Ok(response!(self.keys.get_node_secret())),
+			GET_DESTINATION_SCRIPT => return Ok(response!(self.keys.get_destination_script())),
+			GET_SHUTDOWN_SCRIPTPUBKEY => return Ok(response!(self.keys.get_shutdown_scriptpubkey())),
+			GET_CHANNEL_SIGNER => {
+				let inbound: bool = read!();
+				let channel_value_satoshis: u64 = read!();
+				let signer = ServerSigner { signer: self.keys.get_channel_signer(inbound, channel_value_satoshis), channel_parameters: None };
+				let channel_keys_id = signer.signer.channel_keys_id();
+				let pubkeys = signer.signer.pubkeys().clone();
+				self.persist(&channel_keys_id, &signer)?;
+				self.signers.lock().unwrap().insert(channel_keys_id, Arc::new(Mutex::new(signer)));
+				return Ok(response!(channel_keys_id, pubkeys));
+			},
+			GET_SECURE_RANDOM_BYTES => return Ok(response!(self.keys.get_secure_random_bytes())),
+			SIGN_INVOICE => {
+				let invoice_preimage: Vec<u8> = read!();
+				let sig = self.keys.sign_invoice(invoice_preimage)?;
+				let mut msg = Vec::new();
+				write_recoverable_signature(&sig, &mut msg).expect("In-memory writes cannot fail");
+				return Ok(msg);
+			},
+			GET_INBOUND_PAYMENT_KEY_MATERIAL => return Ok(response!(self.keys.get_inbound_payment_key_material().0)),
+			_ => {},
+		}
+
+		let channel_keys_id: [u8; 32] = read!();
+		let server_signer = self.signers.lock().unwrap().get(&channel_keys_id).map(Arc::clone).ok_or(())?;
+		let mut server_signer = server_signer.lock().unwrap();
+		let signer = &server_signer.signer;
+		let secp_ctx = secp256k1::SECP256K1;
+		match msg_type {
+			GET_PER_COMMITMENT_POINT => {
+				let idx: u64 = read!();
+				Ok(response!(signer.get_per_commitment_point(idx, secp_ctx)))
+			},
+			RELEASE_COMMITMENT_SECRET => {
+				let idx: u64 = read!();
+				Ok(response!(signer.release_commitment_secret(idx)))
+			},
+			VALIDATE_HOLDER_COMMITMENT => {
+				let holder_tx: nativeHolderCommitmentTransaction = read!();
+				signer.validate_holder_commitment(&holder_tx)?;
+				Ok(Vec::new())
+			},
+			SIGN_COUNTERPARTY_COMMITMENT => {
+				let commitment_tx: nativeCommitmentTransaction = read!();
+				let (sig, htlc_sigs) = signer.sign_counterparty_commitment(&commitment_tx, secp_ctx)?;
+				Ok(response!(sig, htlc_sigs))
+			},
+			VALIDATE_COUNTERPARTY_REVOCATION => {
+				let idx: u64 = read!();
+				let secret: SecpSecretKey = read!();
+				signer.validate_counterparty_revocation(idx, &secret)?;
+				Ok(Vec::new())
+			},
+			SIGN_HOLDER_COMMITMENT_AND_HTLCS => {
+				let commitment_tx: nativeHolderCommitmentTransaction = read!();
+				let (sig, htlc_sigs) = signer.sign_holder_commitment_and_htlcs(&commitment_tx, secp_ctx)?;
+				Ok(response!(sig, htlc_sigs))
+			},
+			SIGN_JUSTICE_REVOKED_OUTPUT => {
+				let justice_tx: BitcoinTransaction = read!();
+				let input: u64 = read!();
+				let amount: u64 = read!();
+				let per_commitment_key: SecpSecretKey = read!();
+				if input >= justice_tx.input.len() as u64 { return Err(()); }
+				Ok(response!(signer.sign_justice_revoked_output(&justice_tx, input as usize, amount, &per_commitment_key, secp_ctx)?))
+			},
+			SIGN_JUSTICE_REVOKED_HTLC => {
+				let justice_tx: BitcoinTransaction = read!();
+				let input: u64 = read!();
+				let amount: u64 = read!();
+				let per_commitment_key: SecpSecretKey = read!();
+				let htlc: nativeHTLCOutputInCommitment = read!();
+				if input >= justice_tx.input.len() as u64 { return Err(()); }
+				Ok(response!(signer.sign_justice_revoked_htlc(&justice_tx, input as usize, amount, &per_commitment_key, &htlc, secp_ctx)?))
+			},
+			SIGN_COUNTERPARTY_HTLC_TRANSACTION => {
+				let htlc_tx: BitcoinTransaction = read!();
+				let input: u64 = read!();
+				let amount: u64 = read!();
+				let per_commitment_point: SecpPublicKey = read!();
+				let htlc: nativeHTLCOutputInCommitment = read!();
+				if input >= htlc_tx.input.len() as u64 { return Err(()); }
+				Ok(response!(signer.sign_counterparty_htlc_transaction(&htlc_tx, input as usize, amount, &per_commitment_point, &htlc, secp_ctx)?))
+			},
+			SIGN_CLOSING_TRANSACTION => {
+				let closing_tx = read_closing_transaction(r).map_err(|_| ())?;
+				Ok(response!(signer.sign_closing_transaction(&closing_tx, secp_ctx)?))
+			},
+			SIGN_CHANNEL_ANNOUNCEMENT => {
+				let msg: nativeUnsignedChannelAnnouncement = read!();
+				Ok(response!(signer.sign_channel_announcement(&msg, secp_ctx)?))
+			},
+			READY_CHANNEL => {
+				let channel_parameters: nativeChannelTransactionParameters = read!();
+				if !channel_parameters.is_populated() { return Err(()); }
+				server_signer.signer.ready_channel(&channel_parameters);
+				server_signer.channel_parameters = Some(channel_parameters);
+				self.persist(&channel_keys_id, &server_signer)?;
+				Ok(Vec::new())
+			},
+			_ => Err(()),
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_RemoteSignerServerIOErrorZ
+pub union CResult_RemoteSignerServerIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut RemoteSignerServer,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_RemoteSignerServerIOErrorZ represents the result of a fallible operation,
+/// containing a RemoteSignerServer on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_RemoteSignerServerIOErrorZ {
+	/// The contents of this CResult_RemoteSignerServerIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_RemoteSignerServerIOErrorZPtr,
+	/// Whether this CResult_RemoteSignerServerIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_RemoteSignerServerIOErrorZ in the success state.
+pub extern "C" fn CResult_RemoteSignerServerIOErrorZ_ok(o: RemoteSignerServer) -> CResult_RemoteSignerServerIOErrorZ {
+	CResult_RemoteSignerServerIOErrorZ {
+		contents: CResult_RemoteSignerServerIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_RemoteSignerServerIOErrorZ in the error state.
+pub extern "C" fn CResult_RemoteSignerServerIOErrorZ_err(e: IOError) -> CResult_RemoteSignerServerIOErrorZ {
+	CResult_RemoteSignerServerIOErrorZ {
+		contents: CResult_RemoteSignerServerIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_RemoteSignerServerIOErrorZ_is_ok(o: &CResult_RemoteSignerServerIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_RemoteSignerServerIOErrorZ.
+pub extern "C" fn CResult_RemoteSignerServerIOErrorZ_free(_res: CResult_RemoteSignerServerIOErrorZ) { }
+impl Drop for CResult_RemoteSignerServerIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// The signing side of the remote signer protocol, answering requests with a local
+/// `KeysInterface`. See `RemoteSignerServer_new`.
+#[must_use]
+#[repr(C)]
+pub struct RemoteSignerServer {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeRemoteSignerServer,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for RemoteSignerServer {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeRemoteSignerServer>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the RemoteSignerServer, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn RemoteSignerServer_free(this_obj: RemoteSignerServer) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn RemoteSignerServer_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeRemoteSignerServer); }
+}
+#[allow(unused)]
+impl RemoteSignerServer {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeRemoteSignerServer {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeRemoteSignerServer {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeRemoteSignerServer {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a server answering remote signer requests with `keys`, eg the result of
+/// `KeysManager_as_KeysInterface`, loading the channel signers previously stored in `store`.
+///
+/// Returns an error if a stored signer could not be read or `keys` failed to deserialize it.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RemoteSignerServer_new(mut keys: KeysInterface, mut store: KVStore) -> CResult_RemoteSignerServerIOErrorZ {
+	match nativeRemoteSignerServer::load(keys, store) {
+		Ok(server) => CResult_RemoteSignerServerIOErrorZ_ok(RemoteSignerServer { inner: ObjOps::heap_alloc(server), is_owned: true }),
+		Err(e) => CResult_RemoteSignerServerIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+/// Processes a single request, returning the response to send back to the client.
+///
+/// Malformed requests, requests for unknown signers and requests the `KeysInterface` refuses are
+/// answered with a failure response.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RemoteSignerServer_handle_request(this_arg: &RemoteSignerServer, mut request: u8slice) -> CVec_u8Z {
+	this_arg.get_native_ref().handle_request(request.to_slice()).into()
+}
+
+/// Returns the number of channel signers the server holds.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RemoteSignerServer_signer_count(this_arg: &RemoteSignerServer) -> usize {
+	this_arg.get_native_ref().signers.lock().unwrap().len()
+}
+
+/// Constructs a new SignerTransport which passes requests directly to this_arg, for running the
+/// client and server in the same process.
+/// This copies the `inner` pointer in this_arg and thus the returned SignerTransport must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn RemoteSignerServer_as_SignerTransport(this_arg: &RemoteSignerServer) -> SignerTransport {
+	SignerTransport {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		send_request: RemoteSignerServer_SignerTransport_send_request,
+	}
+}
+
+extern "C" fn RemoteSignerServer_SignerTransport_send_request(this_arg: *const c_void, request: u8slice) -> CResult_CVec_u8ZIOErrorZ {
+	let server = unsafe { &*(this_arg as *const nativeRemoteSignerServer) };
+	CResult_CVec_u8ZIOErrorZ::from_rust(Ok(server.handle_request(request.to_slice())))
+}
+
+#[cfg(unix)]
+mod fd {
+	use std::fs::File;
+	use std::io::{Read, Write};
+	use std::mem::ManuallyDrop;
+	use std::os::unix::io::FromRawFd;
+
+	use super::MAX_MESSAGE_LEN;
+
+	/// Wraps a file descriptor owned by the caller, which is not closed on drop.
+	pub(super) fn borrow_fd(fd: i32) -> ManuallyDrop<File> {
+		ManuallyDrop::new(unsafe { File::from_raw_fd(fd) })
+	}
+
+	pub(super) fn write_frame(fd: i32, msg: &[u8]) -> Result<(), std::io::Error> {
+		let mut file = borrow_fd(fd);
+		let mut frame = Vec::with_capacity(4 + msg.len());
+		frame.extend_from_slice(&(msg.len() as u32).to_be_bytes());
+		frame.extend_from_slice(msg);
+		file.write_all(&frame)?;
+		file.flush()
+	}
+
+	/// Reads a length-prefixed message, returning None on a clean end-of-file before its length.
+	pub(super) fn read_frame(fd: i32) -> Result<Option<Vec<u8>>, std::io::Error> {
+		let mut file = borrow_fd(fd);
+		let mut len_bytes = [0; 4];
+		let mut read = 0;
+		while read < 4 {
+			match file.read(&mut len_bytes[read..])? {
+				0 if read == 0 => return Ok(None),
+				0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+				n => read += n,
+			}
+		}
+		let len = u32::from_be_bytes(len_bytes);
+		if len > MAX_MESSAGE_LEN {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Remote signer message too long"));
+		}
+		let mut msg = vec![0; len as usize];
+		file.read_exact(&mut msg)?;
+		Ok(Some(msg))
+	}
+}
+
+#[cfg(unix)]
+struct FdTransport {
+	/// Held for the duration of each request, so that concurrent requests are not interleaved.
+	fds: Mutex<(i32, i32)>,
+}
+
+#[cfg(unix)]
+extern "C" fn FdTransport_send_request(this_arg: *const c_void, request: u8slice) -> CResult_CVec_u8ZIOErrorZ {
+	let transport = unsafe { &*(this_arg as *const FdTransport) };
+	let fds = transport.fds.lock().unwrap();
+	let res = fd::write_frame(fds.1, request.to_slice()).and_then(|()| fd::read_frame(fds.0))
+		.and_then(|response| response.ok_or_else(|| std::io::ErrorKind::UnexpectedEof.into()));
+	CResult_CVec_u8ZIOErrorZ::from_rust(res)
+}
+
+#[cfg(unix)]
+extern "C" fn FdTransport_free(this_arg: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_arg as *mut FdTransport); }
+}
+
+/// Creates a SignerTransport which sends length-prefixed requests to `write_fd` and reads the
+/// responses from `read_fd`, which may be the same file descriptor, eg for a socket.
+///
+/// The file descriptors must be blocking, and remain owned by the caller, who must close them
+/// only once the transport (and any `KeysInterface` and signers using it) has been freed.
+#[cfg(unix)]
+#[must_use]
+#[no_mangle]
+pub extern "C" fn SignerTransport_new_fd(mut read_fd: i32, mut write_fd: i32) -> SignerTransport {
+	SignerTransport {
+		this_arg: Box::into_raw(Box::new(FdTransport { fds: Mutex::new((read_fd, write_fd)) })) as *mut c_void,
+		send_request: FdTransport_send_request,
+		free: Some(FdTransport_free),
+	}
+}
+
+/// Answers length-prefixed requests read from `read_fd`, writing the responses to `write_fd`,
+/// until `read_fd` reaches end-of-file or an I/O error occurs. This blocks the calling thread
+/// throughout.
+///
+/// The file descriptors must be blocking, and remain owned by the caller.
+#[cfg(unix)]
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RemoteSignerServer_serve_fd(this_arg: &RemoteSignerServer, mut read_fd: i32, mut write_fd: i32) -> CResult_NoneErrorZ {
+	let server = this_arg.get_native_ref();
+	let res = (|| -> Result<(), std::io::Error> {
+		while let Some(request) = fd::read_frame(read_fd)? {
+			fd::write_frame(write_fd, &server.handle_request(&request))?;
+		}
+		Ok(())
+	})();
+	none_result_from_rust(res)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use crate::extensions::test_utils::{channel_parameters, closing_transaction, commitment_transaction, holder_commitment, keys_manager, MemoryStore, remote_signer_server};
+
+	const INITIAL_COMMITMENT_NUMBER: u64 = (1 << 48) - 1;
+
+	fn client(server: &RemoteSignerServer) -> RemoteKeysInterface {
+		RemoteKeysInterface { transport: Arc::new(RemoteSignerServer_as_SignerTransport(server)), retry_interval: TRANSPORT_RETRY_INTERVAL }
+	}
+
+	/// A transport to `server` whose first `failures` requests fail.
+	struct FlakyTransport {
+		server: &'static nativeRemoteSignerServer,
+		failures: AtomicUsize,
+	}
+
+	extern "C" fn FlakyTransport_send_request(this_arg: *const c_void, request: u8slice) -> CResult_CVec_u8ZIOErrorZ {
+		let transport = unsafe { &*(this_arg as *const FlakyTransport) };
+		let res = if transport.failures.fetch_update(Ordering::AcqRel, Ordering::Acquire, |failures| failures.checked_sub(1)).is_ok() {
+			Err(std::io::ErrorKind::BrokenPipe.into())
+		} else {
+			Ok(transport.server.handle_request(request.to_slice()))
+		};
+		CResult_CVec_u8ZIOErrorZ::from_rust(res)
+	}
+
+	extern "C" fn FlakyTransport_free(this_arg: *mut c_void) {
+		unsafe { let _ = Box::from_raw(this_arg as *mut FlakyTransport); }
+	}
+
+	/// Returns a client whose first `failures` requests to `server` fail, along with the number of
+	/// failures left.
+	fn flaky_client(server: &RemoteSignerServer, failures: usize) -> (RemoteKeysInterface, &'static AtomicUsize) {
+		let transport = Box::into_raw(Box::new(FlakyTransport { server: server.get_native_ref(), failures: AtomicUsize::new(failures) }));
+		let failures = unsafe { &(*transport).failures };
+		let transport = SignerTransport { this_arg: transport as *mut c_void, send_request: FlakyTransport_send_request, free: Some(FlakyTransport_free) };
+		(RemoteKeysInterface { transport: Arc::new(transport), retry_interval: Duration::from_millis(1) }, failures)
+	}
+
+	#[test]
+	fn keys_interface_round_trip() {
+		let store = MemoryStore::new();
+		let server = remote_signer_server(&store);
+		let client = client(&server);
+		let reference = keys_manager(1);
+
+		assert_eq!(client.get_node_secret(), reference.get_node_secret());
+		assert_eq!(client.get_destination_script(), reference.get_destination_script());
+		assert_eq!(client.get_shutdown_scriptpubkey().into_inner(), reference.get_shutdown_scriptpubkey().into_inner());
+		assert_eq!(client.get_inbound_payment_key_material().0, reference.get_inbound_payment_key_material().0);
+		assert_eq!(client.sign_invoice(vec![42; 100]).unwrap(), reference.sign_invoice(vec![42; 100]).unwrap());
+		assert_ne!(client.get_secure_random_bytes(), client.get_secure_random_bytes());
+	}
+
+	#[test]
+	fn channel_signer_round_trip() {
+		let store = MemoryStore::new();
+		let server = remote_signer_server(&store);
+		let client = client(&server);
+		let reference = keys_manager(1);
+		let secp_ctx = Secp256k1::new();
+
+		let mut signer = client.get_channel_signer(false, 1_000_000);
+		let mut reference_signer = reference.get_channel_signer(false, 1_000_000);
+		assert_eq!(signer.channel_keys_id(), reference_signer.channel_keys_id());
+		assert_eq!(signer.pubkeys(), reference_signer.pubkeys());
+		assert_eq!(RemoteSignerServer_signer_count(&server), 1);
+		assert_eq!(store.len(), 1);
+
+		let holder_point = signer.get_per_commitment_point(INITIAL_COMMITMENT_NUMBER, &secp_ctx);
+		assert_eq!(holder_point, reference_signer.get_per_commitment_point(INITIAL_COMMITMENT_NUMBER, &secp_ctx));
+
+		let counterparty_signer = keys_manager(2).get_channel_signer(true, 1_000_000);
+		let params = channel_parameters(signer.pubkeys(), counterparty_signer.pubkeys(), 144);
+		signer.ready_channel(&params);
+		reference_signer.ready_channel(&params);
+
+		let counterparty_point = counterparty_signer.get_per_commitment_point(INITIAL_COMMITMENT_NUMBER, &secp_ctx);
+		let counterparty_tx = commitment_transaction(&params, false, INITIAL_COMMITMENT_NUMBER, &counterparty_point);
+		assert_eq!(signer.sign_counterparty_commitment(&counterparty_tx, &secp_ctx).unwrap(),
+			reference_signer.sign_counterparty_commitment(&counterparty_tx, &secp_ctx).unwrap());
+
+		let holder_tx = holder_commitment(&params, INITIAL_COMMITMENT_NUMBER, &holder_point);
+		assert!(signer.validate_holder_commitment(&holder_tx).is_ok());
+		assert_eq!(signer.sign_holder_commitment_and_htlcs(&holder_tx, &secp_ctx).unwrap(),
+			reference_signer.sign_holder_commitment_and_htlcs(&holder_tx, &secp_ctx).unwrap());
+		assert_eq!(signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER),
+			reference_signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER));
+
+		let closing_tx = closing_transaction(client.get_shutdown_scriptpubkey().into_inner(), Script::new());
+		assert_eq!(signer.sign_closing_transaction(&closing_tx, &secp_ctx).unwrap(),
+			reference_signer.sign_closing_transaction(&closing_tx, &secp_ctx).unwrap());
+
+		let read_signer = client.read_chan_signer(&signer.encode()).unwrap();
+		assert_eq!(read_signer.channel_keys_id(), signer.channel_keys_id());
+		assert_eq!(read_signer.pubkeys(), signer.pubkeys());
+	}
+
+	#[test]
+	fn signers_survive_restart() {
+		let store = MemoryStore::new();
+		let secp_ctx = Secp256k1::new();
+		let counterparty_signer = keys_manager(2).get_channel_signer(true, 1_000_000);
+		let closing_tx = closing_transaction(keys_manager(1).get_shutdown_scriptpubkey().into_inner(), Script::new());
+
+		let (serialized_signer, sig) = {
+			let server = remote_signer_server(&store);
+			let client = client(&server);
+			let mut signer = client.get_channel_signer(false, 1_000_000);
+			signer.ready_channel(&channel_parameters(signer.pubkeys(), counterparty_signer.pubkeys(), 144));
+			(signer.encode(), signer.sign_closing_transaction(&closing_tx, &secp_ctx).unwrap())
+		};
+		assert_eq!(store.len(), 1);
+
+		let server = remote_signer_server(&store);
+		assert_eq!(RemoteSignerServer_signer_count(&server), 1);
+		let client = client(&server);
+		let signer = client.read_chan_signer(&serialized_signer).unwrap();
+		assert_eq!(signer.sign_closing_transaction(&closing_tx, &secp_ctx).unwrap(), sig);
+	}
+
+	#[test]
+	fn failed_requests() {
+		let store = MemoryStore::new();
+		let server = remote_signer_server(&store);
+		let client = client(&server);
+		let native_server = server.get_native_ref();
+
+		assert_eq!(native_server.handle_request(&[]), vec![STATUS_ERR]);
+		assert_eq!(native_server.handle_request(&encode_msg!(0xffffu16)), vec![STATUS_ERR]);
+		assert_eq!(native_server.handle_request(&encode_msg!(GET_CHANNEL_SIGNER, false)), vec![STATUS_ERR]);
+		assert_eq!(native_server.handle_request(&encode_msg!(GET_PER_COMMITMENT_POINT, [42u8; 32], INITIAL_COMMITMENT_NUMBER)), vec![STATUS_ERR]);
+
+		let signer = client.get_channel_signer(false, 1_000_000);
+		let unknown_signer = RemoteSigner { channel_keys_id: [42; 32], ..signer.clone() };
+		let secret = SecpSecretKey::from_slice(&[42; 32]).unwrap();
+		assert!(unknown_signer.validate_counterparty_revocation(INITIAL_COMMITMENT_NUMBER, &secret).is_err());
+		assert!(signer.validate_counterparty_revocation(INITIAL_COMMITMENT_NUMBER, &secret).is_ok());
+	}
+
+	#[test]
+	fn infallible_requests_are_retried() {
+		let store = MemoryStore::new();
+		let server = remote_signer_server(&store);
+		let (client, failures) = flaky_client(&server, TRANSPORT_RETRIES as usize);
+		let reference_signer = keys_manager(1).get_channel_signer(false, 1_000_000);
+
+		let signer = client.get_channel_signer(false, 1_000_000);
+		assert_eq!(signer.pubkeys(), reference_signer.pubkeys());
+		assert_eq!(failures.load(Ordering::Acquire), 0);
+
+		failures.store(2, Ordering::Release);
+		assert_eq!(signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER),
+			reference_signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER));
+
+		// Requests which can fail are not retried.
+		failures.store(1, Ordering::Release);
+		assert!(client.sign_invoice(vec![42; 100]).is_err());
+		assert!(client.sign_invoice(vec![42; 100]).is_ok());
+	}
+
+	#[test]
+	#[should_panic(expected = "Remote signer failed to provide a per-commitment point")]
+	fn unreachable_signer_panics_after_retrying() {
+		let store = MemoryStore::new();
+		let server = remote_signer_server(&store);
+		let (client, failures) = flaky_client(&server, 0);
+		let signer = client.get_channel_signer(false, 1_000_000);
+
+		failures.store(TRANSPORT_RETRIES as usize + 1, Ordering::Release);
+		signer.get_per_commitment_point(INITIAL_COMMITMENT_NUMBER, secp256k1::SECP256K1);
+	}
+}
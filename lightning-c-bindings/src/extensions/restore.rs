@@ -0,0 +1,449 @@
+//! Restores a node's `ChannelManager` and `ChannelMonitor`s from a `FilesystemPersister` data
+//! directory, from a [`KVStorePersister`] (including one on top of an [`EncryptedStore`]), from an
+//! [`UpdateLogPersister`], or from a `SqliteStore`, in a single call.
+//!
+//! Restarting a node requires a strict sequence of steps (see the `ChannelManagerReadArgs`
+//! documentation), which is easy to get subtly wrong across the C boundary. This performs them
+//! in order:
+//!  1. read all `ChannelMonitor`s from disk,
+//!  2. read the `ChannelManager`, handing it references to the monitors,
+//!  3. replay the chain to each `ChannelMonitor` from its own best block,
+//!  4. replay the chain to the `ChannelManager` from its own best block,
+//!  5. move the `ChannelMonitor`s into the `ChainMonitor`.
+//!
+//! [`KVStorePersister`]: crate::extensions::kv_store::KVStorePersister
+//! [`EncryptedStore`]: crate::extensions::encrypted_store::EncryptedStore
+//! [`UpdateLogPersister`]: crate::extensions::update_log::UpdateLogPersister
+
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::fs;
+use std::io::{BufReader, Cursor, Read};
+use std::path::PathBuf;
+
+use bitcoin::blockdata::block::{Block, BlockHeader};
+use bitcoin::hash_types::BlockHash;
+use bitcoin::hashes::Hash;
+
+use lightning::chain::channelmonitor::ChannelMonitor as RustChannelMonitor;
+use lightning::ln::channelmanager::{ChannelManager as RustChannelManager, ChannelManagerReadArgs};
+use lightning::util::ser::ReadableArgs;
+
+use crate::c_types::*;
+use crate::extensions::kv_store::KVStorePersister;
+use crate::extensions::update_log::UpdateLogPersister;
+#[cfg(feature = "sqlite")]
+use crate::extensions::kv_store::{CHANNEL_MANAGER_KEY, NODE_NAMESPACE};
+#[cfg(feature = "sqlite")]
+use crate::extensions::sqlite_store::SqliteStore;
+use crate::lightning::chain::{BestBlock, Listen, Watch};
+use crate::lightning::chain::chaininterface::{BroadcasterInterface, BroadcasterInterface_clone_fields, FeeEstimator, FeeEstimator_clone_fields};
+use crate::lightning::chain::chainmonitor::{ChainMonitor, ChainMonitor_as_Watch, nativeChainMonitor};
+use crate::lightning::chain::keysinterface::{KeysInterface, Sign};
+use crate::lightning::chain::transaction::OutPoint;
+use crate::lightning::ln::channelmanager::{ChannelManager, ChannelManager_as_Listen};
+use crate::lightning::util::config::UserConfig;
+use crate::lightning::util::logger::{Logger, Logger_clone_fields};
+use crate::lightning_persister::FilesystemPersister;
+
+type RestoredChannelManager = RustChannelManager<Sign, Watch, BroadcasterInterface, KeysInterface, FeeEstimator, Logger>;
+
+/// A source of chain data which can replay blocks into a [`Listen`]er, used to bring restored
+/// objects up to the current chain tip before they go live.
+#[repr(C)]
+pub struct ChainReplay {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Brings `listener` from `from` to the current chain tip.
+	///
+	/// If `from` is no longer in the best chain, the blocks which were reorganized out must first
+	/// be disconnected (in descending height order) down to the fork point. Every block after the
+	/// fork point must then be connected, in ascending height order, up to the current tip.
+	///
+	/// `listener` is only valid for the duration of this call.
+	///
+	/// Return false if the required blocks could not be fetched, which aborts the restore.
+	#[must_use]
+	pub replay: extern "C" fn (this_arg: *const c_void, from: &BestBlock, listener: &Listen) -> bool,
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for ChainReplay {}
+unsafe impl Sync for ChainReplay {}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn ChainReplay_free(this_ptr: ChainReplay) { }
+impl Drop for ChainReplay {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq)]
+/// The reasons a node restore can fail.
+pub enum RestoreError {
+	/// The data directory (or store) contains neither a `ChannelManager` nor any `ChannelMonitor`s,
+	/// i.e. this is a fresh node and a new `ChannelManager` should be created with `ChannelManager_new`.
+	NoPersistedState,
+	/// `ChannelMonitor`s were found but no `ChannelManager` was. Starting a fresh `ChannelManager`
+	/// here would lose track of the channels, so this requires manual intervention.
+	ManagerNotFound,
+	/// One or more `ChannelMonitor`s could not be read or deserialized.
+	MonitorReadFailed,
+	/// The `ChannelManager` could not be read or deserialized, or was inconsistent with the
+	/// `ChannelMonitor`s on disk.
+	ManagerReadFailed,
+	/// The [`ChainReplay`] failed to bring an object up to the chain tip.
+	ChainReplayFailed,
+	/// The `ChainMonitor` already held `ChannelMonitor`s, or refused one of the restored ones, e.g.
+	/// because its `Persist` implementation failed.
+	MonitorWatchFailed,
+	/// The [`ChainReplay`] provided a block which could not be deserialized.
+	InvalidBlockData,
+}
+
+/// A `ChannelMonitor` which was restored and loaded into the `ChainMonitor`.
+#[repr(C)]
+pub struct RestoredMonitor {
+	/// The funding outpoint of the channel the monitor is for.
+	pub funding_txo: OutPoint,
+	/// The latest update ID which had been applied to the monitor on disk.
+	pub latest_update_id: u64,
+	/// The best block the monitor had seen when it was last persisted, from which it was synced.
+	pub synced_from: BestBlock,
+}
+#[no_mangle]
+/// Frees any resources used by the RestoredMonitor.
+pub extern "C" fn RestoredMonitor_free(_res: RestoredMonitor) { }
+
+#[repr(C)]
+/// A dynamically-allocated array of RestoredMonitors of arbitrary size.
+/// This corresponds to std::vector in C++
+pub struct CVec_RestoredMonitorZ {
+	/// The elements in the array.
+	/// If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
+	pub data: *mut RestoredMonitor,
+	/// The number of elements pointed to by `data`.
+	pub datalen: usize
+}
+impl From<Vec<RestoredMonitor>> for CVec_RestoredMonitorZ {
+	fn from(v: Vec<RestoredMonitor>) -> Self {
+		let datalen = v.len();
+		let data = Box::into_raw(v.into_boxed_slice());
+		Self { datalen, data: unsafe { (*data).as_mut_ptr() } }
+	}
+}
+#[no_mangle]
+/// Frees the buffer pointed to by `data` if `datalen` is non-0.
+pub extern "C" fn CVec_RestoredMonitorZ_free(_res: CVec_RestoredMonitorZ) { }
+impl Drop for CVec_RestoredMonitorZ {
+	fn drop(&mut self) {
+		if self.datalen == 0 { return; }
+		unsafe { Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.datalen)) };
+	}
+}
+
+/// The result of a successful [`FilesystemPersister_restore_node`], [`KVStorePersister_restore_node`],
+/// [`UpdateLogPersister_restore_node`] or `SqliteStore_restore_node`.
+#[repr(C)]
+pub struct RestoredNode {
+	/// The restored `ChannelManager`, synced to the chain tip.
+	pub channel_manager: ChannelManager,
+	/// The best block the `ChannelManager` had seen when it was last persisted, from which it
+	/// was synced.
+	pub manager_synced_from: BestBlock,
+	/// The `ChannelMonitor`s which are now being watched by the `ChainMonitor`.
+	pub monitors: CVec_RestoredMonitorZ,
+	/// The chain tip all restored objects were synced to. Further chain data should be provided
+	/// starting from the block after this one.
+	pub best_block: BestBlock,
+}
+#[no_mangle]
+/// Frees any resources used by the RestoredNode, including the `ChannelManager`.
+pub extern "C" fn RestoredNode_free(_res: RestoredNode) { }
+
+#[repr(C)]
+/// The contents of CResult_RestoredNodeRestoreErrorZ
+pub union CResult_RestoredNodeRestoreErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut RestoredNode,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut RestoreError,
+}
+#[repr(C)]
+/// A CResult_RestoredNodeRestoreErrorZ represents the result of a fallible operation,
+/// containing a RestoredNode on success and a RestoreError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_RestoredNodeRestoreErrorZ {
+	/// The contents of this CResult_RestoredNodeRestoreErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_RestoredNodeRestoreErrorZPtr,
+	/// Whether this CResult_RestoredNodeRestoreErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_RestoredNodeRestoreErrorZ in the success state.
+pub extern "C" fn CResult_RestoredNodeRestoreErrorZ_ok(o: RestoredNode) -> CResult_RestoredNodeRestoreErrorZ {
+	CResult_RestoredNodeRestoreErrorZ {
+		contents: CResult_RestoredNodeRestoreErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_RestoredNodeRestoreErrorZ in the error state.
+pub extern "C" fn CResult_RestoredNodeRestoreErrorZ_err(e: RestoreError) -> CResult_RestoredNodeRestoreErrorZ {
+	CResult_RestoredNodeRestoreErrorZ {
+		contents: CResult_RestoredNodeRestoreErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_RestoredNodeRestoreErrorZ_is_ok(o: &CResult_RestoredNodeRestoreErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_RestoredNodeRestoreErrorZ.
+pub extern "C" fn CResult_RestoredNodeRestoreErrorZ_free(_res: CResult_RestoredNodeRestoreErrorZ) { }
+impl Drop for CResult_RestoredNodeRestoreErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Feeds replayed blocks to a single `ChannelMonitor`, which (unlike `ChannelManager`) needs the
+/// broadcaster, fee estimator and logger passed in on every call.
+struct MonitorListener<'a> {
+	monitor: &'a RustChannelMonitor<Sign>,
+	broadcaster: &'a BroadcasterInterface,
+	fee_estimator: &'a FeeEstimator,
+	logger: &'a Logger,
+	/// Set if we were handed a block or header we could not deserialize, as `Listen` gives us no
+	/// way to report it to the caller.
+	decode_failed: Cell<bool>,
+}
+extern "C" fn MonitorListener_block_connected(this_arg: *const c_void, block: u8slice, height: u32) {
+	let listener = unsafe { &*(this_arg as *const MonitorListener) };
+	let block: Block = match ::bitcoin::consensus::encode::deserialize(block.to_slice()) {
+		Ok(block) => block,
+		Err(_) => { listener.decode_failed.set(true); return; },
+	};
+	let txdata: Vec<_> = block.txdata.iter().enumerate().collect();
+	let _ = listener.monitor.block_connected(&block.header, &txdata, height, listener.broadcaster, listener.fee_estimator, listener.logger);
+}
+extern "C" fn MonitorListener_block_disconnected(this_arg: *const c_void, header: *const [u8; 80], height: u32) {
+	let listener = unsafe { &*(this_arg as *const MonitorListener) };
+	let header: BlockHeader = match ::bitcoin::consensus::encode::deserialize(unsafe { &*header }) {
+		Ok(header) => header,
+		Err(_) => { listener.decode_failed.set(true); return; },
+	};
+	listener.monitor.block_disconnected(&header, height, listener.broadcaster, listener.fee_estimator, listener.logger);
+}
+
+/// Restores a node from the data directory of this `FilesystemPersister`, which must have been
+/// written by `FilesystemPersister_persist_manager` and the `Persist` implementation of
+/// `FilesystemPersister`.
+///
+/// On success, the `ChannelManager` is returned synced to the chain tip and every
+/// `ChannelMonitor` has been synced to the chain tip and moved into `chain_monitor`. The
+/// `chain_monitor` must thus not have been handed any `ChannelMonitor`s yet, and it must outlive
+/// the returned `ChannelManager`, which uses it as its `Watch`.
+///
+/// `chain_replay` is called once for each `ChannelMonitor` and then once for the
+/// `ChannelManager`, each time from the best block that object was persisted at. If you use a
+/// `Filter` to fetch only relevant transactions, note that the `ChannelMonitor`s only register
+/// their outputs with it once they are moved into the `ChainMonitor`, so `chain_replay` must
+/// provide full blocks.
+///
+/// If the data directory is empty, `RestoreError_NoPersistedState` is returned, and a new
+/// `ChannelManager` should be created instead.
+///
+/// Everything is validated before the first `ChannelMonitor` is moved into `chain_monitor`, so on
+/// any error other than `RestoreError_MonitorWatchFailed` the `chain_monitor` is untouched. If
+/// `RestoreError_MonitorWatchFailed` is returned because its `Persist` implementation failed
+/// part-way through, `chain_monitor` may hold some of the `ChannelMonitor`s and must be discarded.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn FilesystemPersister_restore_node(this_arg: &FilesystemPersister, mut keys_manager: KeysInterface, mut fee_estimator: FeeEstimator, chain_monitor: &ChainMonitor, mut tx_broadcaster: BroadcasterInterface, mut logger: Logger, mut default_config: UserConfig, chain_replay: &ChainReplay) -> CResult_RestoredNodeRestoreErrorZ {
+	let persister = this_arg.get_native_ref();
+	restore_node(|keys_manager, _, _, _| persister.read_channelmonitors(keys_manager),
+		|| {
+			let mut manager_path = PathBuf::from(persister.get_data_dir());
+			manager_path.push("manager");
+			fs::File::open(&manager_path).map(BufReader::new)
+		},
+		keys_manager, fee_estimator, chain_monitor, tx_broadcaster, logger, default_config, chain_replay)
+}
+
+/// Restores a node from the store of this `KVStorePersister`, exactly as
+/// [`FilesystemPersister_restore_node`] does from a data directory. See its documentation for
+/// details.
+///
+/// Values are read through the persister's `KVStore`, so if it was built on top of an
+/// `EncryptedStore`, the `ChannelManager` and `ChannelMonitor`s are decrypted (and
+/// authenticated) as they are read, and any value which fails authentication is reported as
+/// `RestoreError_MonitorReadFailed` or `RestoreError_ManagerReadFailed`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn KVStorePersister_restore_node(this_arg: &KVStorePersister, mut keys_manager: KeysInterface, mut fee_estimator: FeeEstimator, chain_monitor: &ChainMonitor, mut tx_broadcaster: BroadcasterInterface, mut logger: Logger, mut default_config: UserConfig, chain_replay: &ChainReplay) -> CResult_RestoredNodeRestoreErrorZ {
+	let persister = this_arg.get_native_ref();
+	restore_node(|keys_manager, _, _, _| persister.read_channelmonitors(keys_manager),
+		|| persister.read_channel_manager_data().map(Cursor::new),
+		keys_manager, fee_estimator, chain_monitor, tx_broadcaster, logger, default_config, chain_replay)
+}
+
+/// Restores a node from this `UpdateLogPersister` and the `KVStorePersister` which stores the
+/// `ChannelManager` in the same `KVStore`, exactly as [`FilesystemPersister_restore_node`] does
+/// from a data directory. See its documentation for details.
+///
+/// The `ChannelMonitor`s are read through the `UpdateLogPersister`, applying any logged
+/// `ChannelMonitorUpdate`s before they are handed to the `ChannelManager`, and a failure to
+/// apply one is reported as `RestoreError_MonitorReadFailed`. `manager_persister` is only used to
+/// read the `ChannelManager`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn UpdateLogPersister_restore_node(this_arg: &UpdateLogPersister, manager_persister: &KVStorePersister, mut keys_manager: KeysInterface, mut fee_estimator: FeeEstimator, chain_monitor: &ChainMonitor, mut tx_broadcaster: BroadcasterInterface, mut logger: Logger, mut default_config: UserConfig, chain_replay: &ChainReplay) -> CResult_RestoredNodeRestoreErrorZ {
+	let persister = this_arg.get_native_ref();
+	let manager_persister = manager_persister.get_native_ref();
+	restore_node(|keys_manager, broadcaster, fee_estimator, logger| persister.read_channelmonitors(keys_manager, broadcaster, fee_estimator, logger),
+		|| manager_persister.read_channel_manager_data().map(Cursor::new),
+		keys_manager, fee_estimator, chain_monitor, tx_broadcaster, logger, default_config, chain_replay)
+}
+
+/// Restores a node from this `SqliteStore`, exactly as [`FilesystemPersister_restore_node`] does
+/// from a data directory. See its documentation for details.
+///
+/// The `ChannelManager` must have been stored through a `KVStorePersister` on top of
+/// `SqliteStore_as_KVStore`, and the `ChannelMonitor`s through `SqliteStore_as_Persist`. Any
+/// stored `ChannelMonitorUpdate`s are applied to their `ChannelMonitor` before it is handed to
+/// the `ChannelManager`, and a failure to apply one is reported as
+/// `RestoreError_MonitorReadFailed`.
+#[cfg(feature = "sqlite")]
+#[must_use]
+#[no_mangle]
+pub extern "C" fn SqliteStore_restore_node(this_arg: &SqliteStore, mut keys_manager: KeysInterface, mut fee_estimator: FeeEstimator, chain_monitor: &ChainMonitor, mut tx_broadcaster: BroadcasterInterface, mut logger: Logger, mut default_config: UserConfig, chain_replay: &ChainReplay) -> CResult_RestoredNodeRestoreErrorZ {
+	let store = this_arg.get_native_ref();
+	restore_node(|keys_manager, broadcaster, fee_estimator, logger| store.read_channelmonitors(keys_manager, broadcaster, fee_estimator, logger),
+		|| store.read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY).map(Cursor::new),
+		keys_manager, fee_estimator, chain_monitor, tx_broadcaster, logger, default_config, chain_replay)
+}
+
+/// Performs the restore, reading the `ChannelMonitor`s with `read_monitors` and the serialized
+/// `ChannelManager` from the reader returned by `open_manager`, which must fail with
+/// `ErrorKind::NotFound` if no `ChannelManager` was persisted.
+///
+/// `read_monitors` is also handed the broadcaster, fee estimator and logger, for use when
+/// applying stored `ChannelMonitorUpdate`s.
+fn restore_node<M, O, R>(read_monitors: M, open_manager: O, keys_manager: KeysInterface, fee_estimator: FeeEstimator, chain_monitor: &ChainMonitor, tx_broadcaster: BroadcasterInterface, logger: Logger, mut default_config: UserConfig, chain_replay: &ChainReplay) -> CResult_RestoredNodeRestoreErrorZ
+where M: FnOnce(&KeysInterface, &BroadcasterInterface, &FeeEstimator, &Logger) -> Result<Vec<(BlockHash, RustChannelMonitor<Sign>)>, std::io::Error>,
+      O: FnOnce() -> Result<R, std::io::Error>, R: Read {
+	// Watching our monitors would fail part-way through if the ChainMonitor already has some.
+	if !chain_monitor.get_native_ref().list_monitors().is_empty() {
+		return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::MonitorWatchFailed);
+	}
+
+	// The ChannelManager takes ownership of the interfaces we were handed, but we also need them to
+	// read the ChannelMonitors and replay blocks to them. We keep non-freeing copies, which are
+	// only used while the originals are still alive.
+	let mut replay_broadcaster = BroadcasterInterface_clone_fields(&tx_broadcaster);
+	replay_broadcaster.free = None;
+	let mut replay_fee_estimator = FeeEstimator_clone_fields(&fee_estimator);
+	replay_fee_estimator.free = None;
+	let mut replay_logger = Logger_clone_fields(&logger);
+	replay_logger.free = None;
+
+	// Step 1: Read the ChannelMonitors.
+	let mut monitors = match read_monitors(&keys_manager, &replay_broadcaster, &replay_fee_estimator, &replay_logger) {
+		Ok(monitors) => monitors,
+		Err(_) => return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::MonitorReadFailed),
+	};
+
+	let mut manager_reader = match open_manager() {
+		Ok(reader) => reader,
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+			if monitors.is_empty() {
+				return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::NoPersistedState);
+			}
+			return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::ManagerNotFound);
+		},
+		Err(_) => return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::ManagerReadFailed),
+	};
+
+	// Step 2: Read the ChannelManager, giving it a chance to reconcile with the ChannelMonitors.
+	let read_args = ChannelManagerReadArgs::new(keys_manager, fee_estimator, ChainMonitor_as_Watch(chain_monitor),
+		tx_broadcaster, logger, *unsafe { Box::from_raw(default_config.take_inner()) },
+		monitors.iter_mut().map(|(_, monitor)| monitor).collect());
+	let (_, manager): (BlockHash, RestoredChannelManager) = match ReadableArgs::read(&mut manager_reader, read_args) {
+		Ok(res) => res,
+		Err(_) => return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::ManagerReadFailed),
+	};
+
+	// Step 3: Bring each ChannelMonitor up to the chain tip, starting from its own best block.
+	let mut restored_monitors = Vec::with_capacity(monitors.len());
+	for (_, monitor) in monitors.iter() {
+		let synced_from = BestBlock { inner: ObjOps::heap_alloc(monitor.current_best_block()), is_owned: true };
+		let listener = MonitorListener {
+			monitor, broadcaster: &replay_broadcaster, fee_estimator: &replay_fee_estimator, logger: &replay_logger,
+			decode_failed: Cell::new(false),
+		};
+		let c_listener = Listen {
+			this_arg: &listener as *const MonitorListener as *mut c_void,
+			block_connected: MonitorListener_block_connected,
+			block_disconnected: MonitorListener_block_disconnected,
+			free: None,
+		};
+		if !(chain_replay.replay)(chain_replay.this_arg, &synced_from, &c_listener) {
+			return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::ChainReplayFailed);
+		}
+		if listener.decode_failed.get() {
+			return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::InvalidBlockData);
+		}
+		restored_monitors.push(RestoredMonitor {
+			funding_txo: OutPoint { inner: ObjOps::heap_alloc(monitor.get_funding_txo().0), is_owned: true },
+			latest_update_id: monitor.get_latest_update_id(),
+			synced_from,
+		});
+	}
+
+	// Step 4: Bring the ChannelManager up to the chain tip, starting from its own best block.
+	let manager_synced_from = BestBlock { inner: ObjOps::heap_alloc(manager.current_best_block()), is_owned: true };
+	let channel_manager = ChannelManager { inner: ObjOps::heap_alloc(manager), is_owned: true };
+	if !(chain_replay.replay)(chain_replay.this_arg, &manager_synced_from, &ChannelManager_as_Listen(&channel_manager)) {
+		return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::ChainReplayFailed);
+	}
+	let best_block = BestBlock { inner: ObjOps::heap_alloc(channel_manager.get_native_ref().current_best_block()), is_owned: true };
+
+	// Step 5: Only now that everything is in sync, hand the ChannelMonitors to the ChainMonitor.
+	for (_, monitor) in monitors.drain(..) {
+		let funding_txo = monitor.get_funding_txo().0;
+		if <nativeChainMonitor as lightning::chain::Watch<_>>::watch_channel(chain_monitor.get_native_ref(), funding_txo, monitor).is_err() {
+			return CResult_RestoredNodeRestoreErrorZ_err(RestoreError::MonitorWatchFailed);
+		}
+	}
+
+	CResult_RestoredNodeRestoreErrorZ_ok(RestoredNode {
+		channel_manager,
+		manager_synced_from,
+		monitors: restored_monitors.into(),
+		best_block,
+	})
+}
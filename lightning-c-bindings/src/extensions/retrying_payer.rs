@@ -0,0 +1,1015 @@
+//! An invoice payer, like `InvoicePayer`, whose retries are governed by a [`RetryStrategy`]
+//! rather than only an attempt count, and which reports the outcome of each payment.
+//!
+//! A payment is retried after a path fails until it has been attempted `max_attempts` times, until
+//! `timeout_secs` have passed since it was started, or until no route is found within
+//! `max_total_fee_msat`, whichever comes first. Channels and nodes which failed a path can be
+//! excluded from routes for the remainder of the payment. Once the payment succeeds or fails, a
+//! [`PaymentOutcome`] listing every attempted path, and why each failed, is passed to the
+//! [`PaymentOutcomeHandler`].
+
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::ffi::c_void;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use bitcoin::hashes::Hash;
+use bitcoin::hashes::hex::ToHex;
+use bitcoin::hashes::sha256::Hash as Sha256;
+use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
+
+use lightning::ln::{PaymentHash, PaymentPreimage};
+use lightning::ln::channelmanager::{PaymentId, PaymentSendFailure};
+use lightning::ln::msgs::{ErrorAction, LightningError};
+use lightning::routing::network_graph::{NetworkUpdate, NodeId};
+use lightning::routing::router::{Payee, Route, RouteHop, RouteParameters};
+use lightning::routing::scoring::{LockableScore as _, Score as _};
+use lightning::util::events::Event as nativeEvent;
+use lightning::util::logger::{Level, Logger as _};
+use lightning::util::ser::Writeable;
+use lightning_invoice::payment::{Payer as _, PaymentError as nativePaymentError, Router as _};
+
+use crate::c_types::*;
+use crate::c_types::derived::{COption_NetworkUpdateZ, COption_u64Z, CResult_PaymentIdPaymentErrorZ, CVec_RouteHopZ, CVec_u8Z};
+use crate::c_types::derived::{CResult_PaymentIdPaymentErrorZ_err, CResult_PaymentIdPaymentErrorZ_ok};
+use crate::extensions::now_secs;
+use crate::lightning::routing::scoring::{nativeMultiThreadedLockableScore, MultiThreadedLockableScore, Score};
+use crate::lightning::util::events::{Event, EventHandler};
+use crate::lightning::util::logger::Logger;
+use crate::lightning_invoice::Invoice;
+use crate::lightning_invoice::payment::{Payer, PaymentError, Router};
+
+/// The penalty given to excluded channels while routing. Routes through them are rejected
+/// regardless, this only keeps the router from picking them when another route exists, while
+/// leaving room to add the penalties of the other channels along a path.
+const EXCLUDED_CHANNEL_PENALTY_MSAT: u64 = u64::MAX / 64;
+
+/// When a [`RetryingInvoicePayer`] stops retrying a payment.
+#[repr(C)]
+pub struct RetryStrategy {
+	/// The maximum number of routes a payment is attempted over, including the first. Values
+	/// below 1 are treated as 1, ie no retries.
+	pub max_attempts: u32,
+	/// How long after a payment is started retries may be attempted, in seconds.
+	pub timeout_secs: COption_u64Z,
+	/// The maximum total fee, in millisatoshis, of the paths of a payment which are in flight at
+	/// once. Routes which would exceed it are not attempted.
+	pub max_total_fee_msat: COption_u64Z,
+	/// Whether a channel responsible for a failed path is avoided for the rest of the payment.
+	pub exclude_failed_channels: bool,
+	/// Whether a node reported as failing by a failed path is avoided for the rest of the
+	/// payment.
+	pub exclude_failed_nodes: bool,
+}
+#[no_mangle]
+/// Frees any resources used by the RetryStrategy.
+pub extern "C" fn RetryStrategy_free(_res: RetryStrategy) { }
+/// Returns a strategy which attempts a payment up to four times, with no timeout or fee limit,
+/// excluding failed channels but not failed nodes.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RetryStrategy_default() -> RetryStrategy {
+	RetryStrategy {
+		max_attempts: 4,
+		timeout_secs: COption_u64Z::None,
+		max_total_fee_msat: COption_u64Z::None,
+		exclude_failed_channels: true,
+		exclude_failed_nodes: false,
+	}
+}
+
+struct Strategy {
+	max_attempts: u32,
+	timeout: Option<Duration>,
+	max_total_fee_msat: Option<u64>,
+	exclude_failed_channels: bool,
+	exclude_failed_nodes: bool,
+}
+
+impl Strategy {
+	fn from_c(strategy: RetryStrategy) -> Self {
+		Self {
+			max_attempts: std::cmp::max(strategy.max_attempts, 1),
+			timeout: if let COption_u64Z::Some(secs) = strategy.timeout_secs { Some(Duration::from_secs(secs)) } else { None },
+			max_total_fee_msat: if let COption_u64Z::Some(fee) = strategy.max_total_fee_msat { Some(fee) } else { None },
+			exclude_failed_channels: strategy.exclude_failed_channels,
+			exclude_failed_nodes: strategy.exclude_failed_nodes,
+		}
+	}
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// How a payment ended.
+pub enum PaymentOutcomeStatus {
+	/// The payment reached its destination.
+	Succeeded,
+	/// The destination rejected the payment, eg because the invoice was already paid.
+	RejectedByDestination,
+	/// The payment failed after being attempted `max_attempts` times.
+	RetriesExhausted,
+	/// The payment failed after the strategy's `timeout_secs` passed.
+	TimedOut,
+	/// No route for a retry was found within the strategy's `max_total_fee_msat`.
+	FeeLimitReached,
+	/// No route for a retry was found, or every route used an excluded channel or node.
+	NoRouteFound,
+	/// The invoice expired before the payment could be retried.
+	InvoiceExpired,
+	/// The `Payer` refused to retry the payment.
+	SendFailed,
+	/// A failed path did not carry what is needed to retry it.
+	NotRetryable,
+	/// The payment was abandoned by something other than this payer.
+	Abandoned,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// What became of one path of a payment.
+pub enum PaymentPathStatus {
+	/// The path had not succeeded or failed when the outcome was reported.
+	InFlight,
+	/// The path reached the destination.
+	Succeeded,
+	/// The path failed.
+	Failed,
+}
+
+/// A path over which part or all of a payment was attempted.
+#[repr(C)]
+pub struct PaymentPathAttempt {
+	/// Which attempt the path was part of, starting from 1 for the first route.
+	pub attempt: u32,
+	/// The hops of the path.
+	pub path: CVec_RouteHopZ,
+	/// What became of the path.
+	pub status: PaymentPathStatus,
+	/// The channel responsible for the failure, if the path failed and it is known.
+	pub short_channel_id: COption_u64Z,
+	/// The update to the network graph conveyed by the failure, if any.
+	pub network_update: COption_NetworkUpdateZ,
+	/// Whether the destination rejected the path.
+	pub rejected_by_dest: bool,
+}
+#[no_mangle]
+/// Frees any resources used by the PaymentPathAttempt.
+pub extern "C" fn PaymentPathAttempt_free(_res: PaymentPathAttempt) { }
+
+#[repr(C)]
+/// A dynamically-allocated array of PaymentPathAttempts of arbitrary size.
+/// This corresponds to std::vector in C++
+pub struct CVec_PaymentPathAttemptZ {
+	/// The elements in the array.
+	/// If datalen is non-0 this must be a valid, non-NULL pointer allocated by malloc().
+	pub data: *mut PaymentPathAttempt,
+	/// The number of elements pointed to by `data`.
+	pub datalen: usize
+}
+impl From<Vec<PaymentPathAttempt>> for CVec_PaymentPathAttemptZ {
+	fn from(v: Vec<PaymentPathAttempt>) -> Self {
+		let datalen = v.len();
+		let data = Box::into_raw(v.into_boxed_slice());
+		Self { datalen, data: unsafe { (*data).as_mut_ptr() } }
+	}
+}
+#[no_mangle]
+/// Frees the buffer pointed to by `data` if `datalen` is non-0.
+pub extern "C" fn CVec_PaymentPathAttemptZ_free(_res: CVec_PaymentPathAttemptZ) { }
+impl Drop for CVec_PaymentPathAttemptZ {
+	fn drop(&mut self) {
+		if self.datalen == 0 { return; }
+		unsafe { Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.datalen)) };
+	}
+}
+
+/// A summary of a payment once it succeeded or failed.
+#[repr(C)]
+pub struct PaymentOutcome {
+	/// The id returned when the payment was sent.
+	pub payment_id: ThirtyTwoBytes,
+	/// The payment hash.
+	pub payment_hash: ThirtyTwoBytes,
+	/// How the payment ended.
+	pub status: PaymentOutcomeStatus,
+	/// The total fee paid, if the payment succeeded.
+	pub fee_paid_msat: COption_u64Z,
+	/// The number of routes the payment was attempted over.
+	pub attempts: u32,
+	/// Every path the payment was attempted over, in the order they were attempted.
+	pub paths: CVec_PaymentPathAttemptZ,
+}
+#[no_mangle]
+/// Frees any resources used by the PaymentOutcome.
+pub extern "C" fn PaymentOutcome_free(_res: PaymentOutcome) { }
+
+/// Receives the [`PaymentOutcome`] of each payment made through a [`RetryingInvoicePayer`].
+#[repr(C)]
+pub struct PaymentOutcomeHandler {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Called once a payment succeeded or failed, before the corresponding `Event::PaymentSent`
+	/// or `Event::PaymentFailed` is passed to the payer's `EventHandler`.
+	pub handle_outcome: extern "C" fn (this_arg: *const c_void, outcome: &PaymentOutcome),
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for PaymentOutcomeHandler {}
+unsafe impl Sync for PaymentOutcomeHandler {}
+#[no_mangle]
+pub(crate) extern "C" fn PaymentOutcomeHandler_clone_fields(orig: &PaymentOutcomeHandler) -> PaymentOutcomeHandler {
+	PaymentOutcomeHandler {
+		this_arg: orig.this_arg,
+		handle_outcome: Clone::clone(&orig.handle_outcome),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn PaymentOutcomeHandler_free(this_ptr: PaymentOutcomeHandler) { }
+impl Drop for PaymentOutcomeHandler {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+/// Wraps the locked scorer to steer the router away from excluded channels and nodes.
+struct ExcludingScore<'a> {
+	inner: &'a Score,
+	channels: &'a HashSet<u64>,
+	nodes: &'a HashSet<NodeId>,
+}
+
+impl<'a> ExcludingScore<'a> {
+	fn as_score(&mut self) -> Score {
+		Score {
+			this_arg: self as *mut ExcludingScore as *mut c_void,
+			channel_penalty_msat: ExcludingScore_channel_penalty_msat,
+			payment_path_failed: ExcludingScore_payment_path_failed,
+			payment_path_successful: ExcludingScore_payment_path_successful,
+			write: ExcludingScore_write,
+			free: None,
+		}
+	}
+}
+
+extern "C" fn ExcludingScore_channel_penalty_msat(this_arg: *const c_void, short_channel_id: u64, send_amt_msat: u64, channel_capacity_msat: COption_u64Z, source: &crate::lightning::routing::network_graph::NodeId, target: &crate::lightning::routing::network_graph::NodeId) -> u64 {
+	let score = unsafe { &*(this_arg as *const ExcludingScore) };
+	if score.channels.contains(&short_channel_id) || score.nodes.contains(source.get_native_ref()) || score.nodes.contains(target.get_native_ref()) {
+		return EXCLUDED_CHANNEL_PENALTY_MSAT;
+	}
+	(score.inner.channel_penalty_msat)(score.inner.this_arg, short_channel_id, send_amt_msat, channel_capacity_msat, source, target)
+}
+extern "C" fn ExcludingScore_payment_path_failed(this_arg: *mut c_void, path: CVec_RouteHopZ, short_channel_id: u64) {
+	let score = unsafe { &*(this_arg as *const ExcludingScore) };
+	(score.inner.payment_path_failed)(score.inner.this_arg, path, short_channel_id)
+}
+extern "C" fn ExcludingScore_payment_path_successful(this_arg: *mut c_void, path: CVec_RouteHopZ) {
+	let score = unsafe { &*(this_arg as *const ExcludingScore) };
+	(score.inner.payment_path_successful)(score.inner.this_arg, path)
+}
+extern "C" fn ExcludingScore_write(this_arg: *const c_void) -> CVec_u8Z {
+	let score = unsafe { &*(this_arg as *const ExcludingScore) };
+	score.inner.encode().into()
+}
+
+fn path_fees_msat(hops: &[RouteHop]) -> u64 {
+	hops.split_last().map(|(_, hops)| hops.iter().map(|hop| hop.fee_msat).sum()).unwrap_or(0)
+}
+
+fn has_expired(params: &RouteParameters) -> bool {
+	let now = now_secs();
+	params.payee.expiry_time.map(|expiry_time| expiry_time < now).unwrap_or(false)
+}
+
+fn route_error(err: &str) -> LightningError {
+	LightningError { err: err.to_owned(), action: ErrorAction::IgnoreError }
+}
+
+struct AttemptedPath {
+	attempt: u32,
+	hops: Vec<RouteHop>,
+	status: PaymentPathStatus,
+	short_channel_id: Option<u64>,
+	network_update: Option<NetworkUpdate>,
+	rejected_by_dest: bool,
+}
+
+impl AttemptedPath {
+	fn into_c(self) -> PaymentPathAttempt {
+		PaymentPathAttempt {
+			attempt: self.attempt,
+			path: self.hops.into_iter().map(|hop| crate::lightning::routing::router::RouteHop { inner: ObjOps::heap_alloc(hop), is_owned: true }).collect::<Vec<_>>().into(),
+			status: self.status,
+			short_channel_id: match self.short_channel_id { Some(scid) => COption_u64Z::Some(scid), None => COption_u64Z::None },
+			network_update: match self.network_update {
+				Some(update) => COption_NetworkUpdateZ::Some(crate::lightning::routing::network_graph::NetworkUpdate::native_into(update)),
+				None => COption_NetworkUpdateZ::None,
+			},
+			rejected_by_dest: self.rejected_by_dest,
+		}
+	}
+}
+
+struct PendingPayment {
+	payment_id: Option<PaymentId>,
+	started_at: Instant,
+	attempts: u32,
+	fees_in_flight_msat: u64,
+	excluded_channels: HashSet<u64>,
+	excluded_nodes: HashSet<NodeId>,
+	paths: Vec<AttemptedPath>,
+	/// Set once the payment will no longer be retried.
+	failure: Option<PaymentOutcomeStatus>,
+}
+
+impl PendingPayment {
+	fn new() -> Self {
+		Self {
+			payment_id: None,
+			started_at: Instant::now(),
+			attempts: 0,
+			fees_in_flight_msat: 0,
+			excluded_channels: HashSet::new(),
+			excluded_nodes: HashSet::new(),
+			paths: Vec::new(),
+			failure: None,
+		}
+	}
+
+	fn route_attempted(&mut self, route: &Route) {
+		self.attempts += 1;
+		self.fees_in_flight_msat += route.get_total_fees();
+		for hops in route.paths.iter() {
+			self.paths.push(AttemptedPath {
+				attempt: self.attempts, hops: hops.clone(), status: PaymentPathStatus::InFlight,
+				short_channel_id: None, network_update: None, rejected_by_dest: false,
+			});
+		}
+	}
+
+	fn path_failed(&mut self, hops: &[RouteHop], short_channel_id: Option<u64>, network_update: Option<NetworkUpdate>, rejected_by_dest: bool) {
+		if let Some(path) = self.paths.iter_mut().find(|path| path.status == PaymentPathStatus::InFlight && &path.hops[..] == hops) {
+			path.status = PaymentPathStatus::Failed;
+			path.short_channel_id = short_channel_id;
+			path.network_update = network_update;
+			path.rejected_by_dest = rejected_by_dest;
+			self.fees_in_flight_msat = self.fees_in_flight_msat.saturating_sub(path_fees_msat(hops));
+		}
+	}
+
+	/// Marks the paths of `route` which could not be sent as failed, or all of them if `results`
+	/// is `None`.
+	fn route_failed(&mut self, route: &Route, results: Option<&Vec<Result<(), lightning::util::errors::APIError>>>) {
+		for (idx, hops) in route.paths.iter().enumerate() {
+			let failed = results.map(|results| results.get(idx).map(|res| res.is_err()).unwrap_or(false)).unwrap_or(true);
+			if failed { self.path_failed(hops, None, None, false); }
+		}
+	}
+}
+
+pub(crate) struct nativeRetryingInvoicePayer {
+	payer: Payer,
+	router: Router,
+	scorer: &'static nativeMultiThreadedLockableScore,
+	logger: Logger,
+	event_handler: EventHandler,
+	outcome_handler: PaymentOutcomeHandler,
+	strategy: Strategy,
+	payments: Mutex<HashMap<PaymentHash, PendingPayment>>,
+}
+
+impl nativeRetryingInvoicePayer {
+	fn find_route(&self, payment_hash: &PaymentHash, params: &RouteParameters, payment: &PendingPayment) -> Result<Route, (PaymentOutcomeStatus, LightningError)> {
+		let payer = self.payer.node_id();
+		let first_hops = self.payer.first_hops();
+		let first_hops = first_hops.iter().collect::<Vec<_>>();
+		let route = {
+			let scorer = self.scorer.lock();
+			let mut excluding_score = ExcludingScore { inner: &*scorer, channels: &payment.excluded_channels, nodes: &payment.excluded_nodes };
+			let score = excluding_score.as_score();
+			self.router.find_route(&payer, params, payment_hash, Some(&first_hops[..]), &score)
+		}.map_err(|e| (PaymentOutcomeStatus::NoRouteFound, e))?;
+		let uses_excluded = route.paths.iter().flatten().any(|hop| {
+			payment.excluded_channels.contains(&hop.short_channel_id) || payment.excluded_nodes.contains(&NodeId::from_pubkey(&hop.pubkey))
+		});
+		if uses_excluded {
+			return Err((PaymentOutcomeStatus::NoRouteFound, route_error("No route avoids the channels and nodes which failed the payment")));
+		}
+		if let Some(max_total_fee_msat) = self.strategy.max_total_fee_msat {
+			if payment.fees_in_flight_msat + route.get_total_fees() > max_total_fee_msat {
+				return Err((PaymentOutcomeStatus::FeeLimitReached, route_error("No route within the maximum total fee was found")));
+			}
+		}
+		Ok(route)
+	}
+
+	fn check_retry(&self, payment: &PendingPayment, params: &RouteParameters) -> Result<(), PaymentOutcomeStatus> {
+		if payment.attempts >= self.strategy.max_attempts {
+			Err(PaymentOutcomeStatus::RetriesExhausted)
+		} else if self.strategy.timeout.map(|timeout| payment.started_at.elapsed() >= timeout).unwrap_or(false) {
+			Err(PaymentOutcomeStatus::TimedOut)
+		} else if has_expired(params) {
+			Err(PaymentOutcomeStatus::InvoiceExpired)
+		} else {
+			Ok(())
+		}
+	}
+
+	fn pay<F>(&self, payment_hash: PaymentHash, params: &RouteParameters, send_payment: F) -> Result<PaymentId, nativePaymentError>
+	where F: Fn(&Route) -> Result<PaymentId, PaymentSendFailure> {
+		let mut payments = self.payments.lock().unwrap();
+		let payment = match payments.entry(payment_hash) {
+			Entry::Occupied(_) => return Err(nativePaymentError::Invoice("payment pending")),
+			Entry::Vacant(entry) => entry.insert(PendingPayment::new()),
+		};
+		let res = self.send(payment, payment_hash, params, send_payment);
+		if res.is_err() { payments.remove(&payment_hash); }
+		res
+	}
+
+	fn send<F>(&self, payment: &mut PendingPayment, payment_hash: PaymentHash, params: &RouteParameters, send_payment: F) -> Result<PaymentId, nativePaymentError>
+	where F: Fn(&Route) -> Result<PaymentId, PaymentSendFailure> {
+		loop {
+			if has_expired(params) { return Err(nativePaymentError::Invoice("Invoice expired prior to send")); }
+			let route = self.find_route(&payment_hash, params, payment).map_err(|(_, e)| nativePaymentError::Routing(e))?;
+			payment.route_attempted(&route);
+			match send_payment(&route) {
+				Ok(payment_id) => {
+					payment.payment_id = Some(payment_id);
+					return Ok(payment_id);
+				},
+				Err(PaymentSendFailure::AllFailedRetrySafe(errs)) => {
+					payment.route_failed(&route, None);
+					if self.check_retry(payment, params).is_err() {
+						return Err(nativePaymentError::Sending(PaymentSendFailure::AllFailedRetrySafe(errs)));
+					}
+				},
+				Err(PaymentSendFailure::PartialFailure { results, failed_paths_retry, payment_id }) => {
+					// Some paths were sent, so the payment may be claimed whether or not retrying the
+					// others succeeds.
+					payment.payment_id = Some(payment_id);
+					payment.route_failed(&route, Some(&results));
+					if let Some(retry) = failed_paths_retry {
+						let _ = self.retry(payment, payment_hash, &retry);
+					}
+					return Ok(payment_id);
+				},
+				Err(e) => return Err(nativePaymentError::Sending(e)),
+			}
+		}
+	}
+
+	fn retry(&self, payment: &mut PendingPayment, payment_hash: PaymentHash, params: &RouteParameters) -> Result<(), PaymentOutcomeStatus> {
+		let payment_id = payment.payment_id.ok_or(PaymentOutcomeStatus::NotRetryable)?;
+		loop {
+			self.check_retry(payment, params)?;
+			let route = self.find_route(&payment_hash, params, payment).map_err(|(status, e)| {
+				log_record!(self.logger, Level::Trace, "Failed to find a route to retry payment {}: {}", payment_hash.0.to_hex(), e.err);
+				status
+			})?;
+			payment.route_attempted(&route);
+			match self.payer.retry_payment(&route, payment_id) {
+				Ok(()) => return Ok(()),
+				Err(PaymentSendFailure::AllFailedRetrySafe(_)) => payment.route_failed(&route, None),
+				Err(PaymentSendFailure::PartialFailure { results, failed_paths_retry, .. }) => {
+					payment.route_failed(&route, Some(&results));
+					if let Some(retry) = failed_paths_retry {
+						let _ = self.retry(payment, payment_hash, &retry);
+					}
+					return Ok(());
+				},
+				Err(e) => {
+					payment.route_failed(&route, None);
+					log_record!(self.logger, Level::Trace, "Failed to retry payment {}: {:?}", payment_hash.0.to_hex(), e);
+					return Err(PaymentOutcomeStatus::SendFailed);
+				},
+			}
+		}
+	}
+
+	/// Handles a failed path, returning whether the event should be passed on.
+	fn payment_path_failed(&self, payment_hash: PaymentHash, path: &[RouteHop], short_channel_id: Option<u64>, network_update: &Option<NetworkUpdate>, rejected_by_dest: bool, retry: &Option<RouteParameters>) -> bool {
+		let mut payments = self.payments.lock().unwrap();
+		let payment = match payments.get_mut(&payment_hash) {
+			Some(payment) => payment,
+			None => return true,
+		};
+		payment.path_failed(path, short_channel_id, network_update.clone(), rejected_by_dest);
+		if self.strategy.exclude_failed_channels {
+			if let Some(short_channel_id) = short_channel_id { payment.excluded_channels.insert(short_channel_id); }
+		}
+		if self.strategy.exclude_failed_nodes {
+			if let Some(NetworkUpdate::NodeFailure { node_id, .. }) = network_update { payment.excluded_nodes.insert(NodeId::from_pubkey(node_id)); }
+		}
+		if payment.failure.is_some() { return true; }
+		let res = if rejected_by_dest {
+			Err(PaymentOutcomeStatus::RejectedByDestination)
+		} else if let Some(retry) = retry {
+			self.retry(payment, payment_hash, retry)
+		} else {
+			Err(PaymentOutcomeStatus::NotRetryable)
+		};
+		match res {
+			Ok(()) => false,
+			Err(status) => {
+				log_record!(self.logger, Level::Trace, "Not retrying payment {} after {} attempts: {:?}", payment_hash.0.to_hex(), payment.attempts, status);
+				payment.failure = Some(status);
+				if let Some(payment_id) = payment.payment_id { self.payer.abandon_payment(payment_id); }
+				true
+			},
+		}
+	}
+
+	fn report_outcome(&self, payment_hash: PaymentHash, payment: PendingPayment, status: PaymentOutcomeStatus, fee_paid_msat: Option<u64>) {
+		let outcome = PaymentOutcome {
+			payment_id: ThirtyTwoBytes { data: payment.payment_id.map(|id| id.0).unwrap_or([0; 32]) },
+			payment_hash: ThirtyTwoBytes { data: payment_hash.0 },
+			status,
+			fee_paid_msat: match fee_paid_msat { Some(fee) => COption_u64Z::Some(fee), None => COption_u64Z::None },
+			attempts: payment.attempts,
+			paths: payment.paths.into_iter().map(|path| path.into_c()).collect::<Vec<_>>().into(),
+		};
+		(self.outcome_handler.handle_outcome)(self.outcome_handler.this_arg, &outcome);
+	}
+
+	fn handle_event(&self, event: &Event) {
+		match event.to_native() {
+			nativeEvent::PaymentPathFailed { payment_hash, rejected_by_dest, network_update, path, short_channel_id, retry, .. } => {
+				if let Some(short_channel_id) = short_channel_id {
+					self.scorer.lock().payment_path_failed(&path.iter().collect::<Vec<_>>(), short_channel_id);
+				}
+				if !self.payment_path_failed(payment_hash, &path, short_channel_id, &network_update, rejected_by_dest, &retry) {
+					return;
+				}
+			},
+			nativeEvent::PaymentPathSuccessful { path, .. } => {
+				self.scorer.lock().payment_path_successful(&path.iter().collect::<Vec<_>>());
+			},
+			nativeEvent::PaymentSent { payment_hash, fee_paid_msat, .. } => {
+				let payment = self.payments.lock().unwrap().remove(&payment_hash);
+				if let Some(mut payment) = payment {
+					// Any part which had not failed by now reaches the destination along with the rest.
+					for path in payment.paths.iter_mut().filter(|path| path.status == PaymentPathStatus::InFlight) {
+						path.status = PaymentPathStatus::Succeeded;
+					}
+					self.report_outcome(payment_hash, payment, PaymentOutcomeStatus::Succeeded, fee_paid_msat);
+				}
+			},
+			nativeEvent::PaymentFailed { payment_hash, .. } => {
+				let payment = self.payments.lock().unwrap().remove(&payment_hash);
+				if let Some(payment) = payment {
+					let status = payment.failure.unwrap_or(PaymentOutcomeStatus::Abandoned);
+					self.report_outcome(payment_hash, payment, status, None);
+				}
+			},
+			_ => {},
+		}
+		(self.event_handler.handle_event)(self.event_handler.this_arg, event);
+	}
+}
+
+/// Pays invoices like `InvoicePayer`, retrying according to a [`RetryStrategy`], see the module
+/// documentation.
+#[must_use]
+#[repr(C)]
+pub struct RetryingInvoicePayer {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeRetryingInvoicePayer,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for RetryingInvoicePayer {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeRetryingInvoicePayer>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the RetryingInvoicePayer, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn RetryingInvoicePayer_free(this_obj: RetryingInvoicePayer) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn RetryingInvoicePayer_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeRetryingInvoicePayer); }
+}
+#[allow(unused)]
+impl RetryingInvoicePayer {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeRetryingInvoicePayer {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeRetryingInvoicePayer {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeRetryingInvoicePayer {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+fn payment_result(res: Result<PaymentId, nativePaymentError>) -> CResult_PaymentIdPaymentErrorZ {
+	match res {
+		Ok(payment_id) => CResult_PaymentIdPaymentErrorZ_ok(ThirtyTwoBytes { data: payment_id.0 }),
+		Err(e) => CResult_PaymentIdPaymentErrorZ_err(PaymentError::native_into(e)),
+	}
+}
+
+/// Creates a payer which retries failed payment paths according to `strategy`.
+///
+/// Events are passed on to `event_handler` except for `Event::PaymentPathFailed` events for
+/// paths which were retried. Each payment's [`PaymentOutcome`] is passed to `outcome_handler`.
+///
+/// The payer must be freed before `scorer` is.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RetryingInvoicePayer_new(mut payer: Payer, mut router: Router, scorer: &MultiThreadedLockableScore, mut logger: Logger, mut event_handler: EventHandler, mut outcome_handler: PaymentOutcomeHandler, mut strategy: RetryStrategy) -> RetryingInvoicePayer {
+	let native = nativeRetryingInvoicePayer {
+		payer, router, scorer: scorer.get_native_ref(), logger, event_handler, outcome_handler,
+		strategy: Strategy::from_c(strategy),
+		payments: Mutex::new(HashMap::new()),
+	};
+	RetryingInvoicePayer { inner: ObjOps::heap_alloc(native), is_owned: true }
+}
+
+fn pay_invoice_using_amount(payer: &nativeRetryingInvoicePayer, invoice: &lightning_invoice::Invoice, amount_msats: Option<u64>) -> Result<PaymentId, nativePaymentError> {
+	let final_value_msat = match (invoice.amount_milli_satoshis(), amount_msats) {
+		(Some(amount_msats), None) | (None, Some(amount_msats)) => amount_msats,
+		(Some(_), Some(_)) => return Err(nativePaymentError::Invoice("amount unexpected")),
+		(None, None) => return Err(nativePaymentError::Invoice("amount missing")),
+	};
+	let payment_hash = PaymentHash(invoice.payment_hash().into_inner());
+	let payment_secret = Some(*invoice.payment_secret());
+	let payee_pubkey = invoice.payee_pub_key().cloned().unwrap_or_else(|| invoice.recover_payee_pub_key());
+	let mut payee = Payee::from_node_id(payee_pubkey);
+	payee.features = invoice.features().cloned();
+	payee.route_hints = invoice.route_hints();
+	payee.expiry_time = invoice.timestamp().duration_since(SystemTime::UNIX_EPOCH).ok().map(|timestamp| (timestamp + invoice.expiry_time()).as_secs());
+	let params = RouteParameters { payee, final_value_msat, final_cltv_expiry_delta: invoice.min_final_cltv_expiry() as u32 };
+	payer.pay(payment_hash, &params, |route| payer.payer.send_payment(route, payment_hash, &payment_secret))
+}
+
+/// Pays the given [`Invoice`], tracking it for retries until it succeeds or fails.
+///
+/// Fails if a payment for the invoice's payment hash is already pending through this payer.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RetryingInvoicePayer_pay_invoice(this_arg: &RetryingInvoicePayer, invoice: &Invoice) -> CResult_PaymentIdPaymentErrorZ {
+	payment_result(pay_invoice_using_amount(this_arg.get_native_ref(), invoice.get_native_ref(), None))
+}
+
+/// Pays the given zero-value [`Invoice`] using the given amount, tracking it for retries until it
+/// succeeds or fails.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RetryingInvoicePayer_pay_zero_value_invoice(this_arg: &RetryingInvoicePayer, invoice: &Invoice, mut amount_msats: u64) -> CResult_PaymentIdPaymentErrorZ {
+	payment_result(pay_invoice_using_amount(this_arg.get_native_ref(), invoice.get_native_ref(), Some(amount_msats)))
+}
+
+/// Pays `pubkey` an amount using the hash of the given preimage, tracking it for retries until it
+/// succeeds or fails.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn RetryingInvoicePayer_pay_pubkey(this_arg: &RetryingInvoicePayer, mut pubkey: PublicKey, mut payment_preimage: ThirtyTwoBytes, mut amount_msats: u64, mut final_cltv_expiry_delta: u32) -> CResult_PaymentIdPaymentErrorZ {
+	let payer = this_arg.get_native_ref();
+	let pubkey: SecpPublicKey = pubkey.into_rust();
+	let payment_preimage = PaymentPreimage(payment_preimage.data);
+	let payment_hash = PaymentHash(Sha256::hash(&payment_preimage.0).into_inner());
+	let params = RouteParameters { payee: Payee::for_keysend(pubkey), final_value_msat: amount_msats, final_cltv_expiry_delta };
+	payment_result(payer.pay(payment_hash, &params, |route| payer.payer.send_spontaneous_payment(route, payment_preimage)))
+}
+
+/// Constructs a new EventHandler which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned EventHandler must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn RetryingInvoicePayer_as_EventHandler(this_arg: &RetryingInvoicePayer) -> EventHandler {
+	EventHandler {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		handle_event: RetryingInvoicePayer_EventHandler_handle_event,
+	}
+}
+
+extern "C" fn RetryingInvoicePayer_EventHandler_handle_event(this_arg: *const c_void, event: &Event) {
+	unsafe { &*(this_arg as *const nativeRetryingInvoicePayer) }.handle_event(event)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Arc;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
+	use lightning::ln::features::{ChannelFeatures, NodeFeatures};
+	use crate::c_types::derived::{CResult_NonePaymentSendFailureZ, CResult_NonePaymentSendFailureZ_ok, CResult_PaymentIdPaymentSendFailureZ, CResult_PaymentIdPaymentSendFailureZ_ok, CResult_RouteLightningErrorZ, CResult_RouteLightningErrorZ_ok, CVec_ChannelDetailsZ};
+	use crate::extensions::test_utils::logger;
+	use crate::lightning::routing::scoring::MultiThreadedLockableScore_new;
+
+	const PAYMENT_ID: PaymentId = PaymentId([1; 32]);
+	const PAYMENT_HASH: PaymentHash = PaymentHash([2; 32]);
+	/// The fee of the first route found, each further route costing as much more.
+	const ROUTE_FEE_MSAT: u64 = 100;
+
+	fn pubkey(byte: u8) -> SecpPublicKey {
+		SecpPublicKey::from_secret_key(secp256k1::SECP256K1, &SecpSecretKey::from_slice(&[byte; 32]).unwrap())
+	}
+
+	fn hop(pubkey: SecpPublicKey, short_channel_id: u64, fee_msat: u64) -> RouteHop {
+		RouteHop { pubkey, node_features: NodeFeatures::empty(), short_channel_id, channel_features: ChannelFeatures::empty(), fee_msat, cltv_expiry_delta: 40 }
+	}
+
+	/// The status of an attempted path, the channel which failed it and the serialized update it
+	/// conveyed, as reported in a [`PaymentOutcome`].
+	type PathRecord = (PaymentPathStatus, Option<u64>, Option<Vec<u8>>);
+
+	/// What the fake `Payer`, `Router` and handlers were asked to do.
+	#[derive(Default)]
+	struct Calls {
+		/// The first channels of the routes the router picks from, with the node each leads to.
+		channels: Vec<(u64, u8)>,
+		routes: AtomicUsize,
+		retries: AtomicUsize,
+		abandoned: AtomicUsize,
+		events: AtomicUsize,
+		outcomes: Mutex<Vec<(PaymentOutcomeStatus, u32, Option<u64>, Vec<PathRecord>)>>,
+	}
+
+	fn calls<'a>(this_arg: *const c_void) -> &'a Calls {
+		unsafe { &*(this_arg as *const Calls) }
+	}
+
+	fn penalty_msat(scorer: &Score, short_channel_id: u64, amount_msat: u64, source: &SecpPublicKey, target: &SecpPublicKey) -> u64 {
+		let source = crate::lightning::routing::network_graph::NodeId_from_pubkey(PublicKey::from_rust(source));
+		let target = crate::lightning::routing::network_graph::NodeId_from_pubkey(PublicKey::from_rust(target));
+		(scorer.channel_penalty_msat)(scorer.this_arg, short_channel_id, amount_msat, COption_u64Z::None, &source, &target)
+	}
+
+	/// Finds a two-hop route to node 3 over whichever of `Calls::channels` the scorer penalizes
+	/// least, each route found costing `ROUTE_FEE_MSAT` more in fees than the last. Like LDK's
+	/// router, a route is returned even if every channel is penalized.
+	extern "C" fn find_route(this_arg: *const c_void, _payer: PublicKey, params: &crate::lightning::routing::router::RouteParameters, _payment_hash: *const [u8; 32], _first_hops: *mut CVec_ChannelDetailsZ, scorer: &Score) -> CResult_RouteLightningErrorZ {
+		let calls = calls(this_arg);
+		let amount_msat = params.get_native_ref().final_value_msat;
+		let &(short_channel_id, node) = calls.channels.iter().min_by_key(|(short_channel_id, node)| {
+			penalty_msat(scorer, *short_channel_id, amount_msat, &pubkey(1), &pubkey(*node))
+				.saturating_add(penalty_msat(scorer, 1000 + short_channel_id, amount_msat, &pubkey(*node), &pubkey(3)))
+		}).unwrap();
+		let n = calls.routes.fetch_add(1, Ordering::SeqCst) as u64 + 1;
+		let path = vec![hop(pubkey(node), short_channel_id, ROUTE_FEE_MSAT * n), hop(pubkey(3), 1000 + short_channel_id, amount_msat)];
+		let route = Route { paths: vec![path], payee: None };
+		CResult_RouteLightningErrorZ_ok(crate::lightning::routing::router::Route { inner: ObjOps::heap_alloc(route), is_owned: true })
+	}
+	extern "C" fn node_id(_this_arg: *const c_void) -> PublicKey {
+		PublicKey::from_rust(&pubkey(1))
+	}
+	extern "C" fn first_hops(_this_arg: *const c_void) -> CVec_ChannelDetailsZ {
+		Vec::new().into()
+	}
+	extern "C" fn send_payment(_this_arg: *const c_void, _route: &crate::lightning::routing::router::Route, _payment_hash: ThirtyTwoBytes, _payment_secret: ThirtyTwoBytes) -> CResult_PaymentIdPaymentSendFailureZ {
+		CResult_PaymentIdPaymentSendFailureZ_ok(ThirtyTwoBytes { data: PAYMENT_ID.0 })
+	}
+	extern "C" fn send_spontaneous_payment(_this_arg: *const c_void, _route: &crate::lightning::routing::router::Route, _payment_preimage: ThirtyTwoBytes) -> CResult_PaymentIdPaymentSendFailureZ {
+		CResult_PaymentIdPaymentSendFailureZ_ok(ThirtyTwoBytes { data: PAYMENT_ID.0 })
+	}
+	extern "C" fn retry_payment(this_arg: *const c_void, _route: &crate::lightning::routing::router::Route, _payment_id: ThirtyTwoBytes) -> CResult_NonePaymentSendFailureZ {
+		calls(this_arg).retries.fetch_add(1, Ordering::SeqCst);
+		CResult_NonePaymentSendFailureZ_ok()
+	}
+	extern "C" fn abandon_payment(this_arg: *const c_void, _payment_id: ThirtyTwoBytes) {
+		calls(this_arg).abandoned.fetch_add(1, Ordering::SeqCst);
+	}
+	extern "C" fn handle_event(this_arg: *const c_void, _event: &Event) {
+		calls(this_arg).events.fetch_add(1, Ordering::SeqCst);
+	}
+	extern "C" fn handle_outcome(this_arg: *const c_void, outcome: &PaymentOutcome) {
+		let paths = if outcome.paths.datalen == 0 { &[][..] } else { unsafe { std::slice::from_raw_parts(outcome.paths.data, outcome.paths.datalen) } };
+		let fee_paid_msat = if let COption_u64Z::Some(fee) = outcome.fee_paid_msat { Some(fee) } else { None };
+		let paths = paths.iter().map(|path| {
+			let short_channel_id = if let COption_u64Z::Some(scid) = path.short_channel_id { Some(scid) } else { None };
+			let network_update = if let COption_NetworkUpdateZ::Some(update) = &path.network_update { Some(update.to_native().encode()) } else { None };
+			(path.status, short_channel_id, network_update)
+		}).collect();
+		calls(this_arg).outcomes.lock().unwrap().push((outcome.status, outcome.attempts, fee_paid_msat, paths));
+	}
+	extern "C" fn channel_penalty_msat(_this_arg: *const c_void, _short_channel_id: u64, _send_amt_msat: u64, _channel_capacity_msat: COption_u64Z, _source: &crate::lightning::routing::network_graph::NodeId, _target: &crate::lightning::routing::network_graph::NodeId) -> u64 {
+		0
+	}
+	extern "C" fn payment_path_failed(_this_arg: *mut c_void, _path: CVec_RouteHopZ, _short_channel_id: u64) {}
+	extern "C" fn payment_path_successful(_this_arg: *mut c_void, _path: CVec_RouteHopZ) {}
+	extern "C" fn write(_this_arg: *const c_void) -> CVec_u8Z {
+		Vec::new().into()
+	}
+
+	struct TestPayer {
+		payer: RetryingInvoicePayer,
+		_scorer: MultiThreadedLockableScore,
+		calls: Arc<Calls>,
+	}
+
+	impl TestPayer {
+		/// A payer whose router can route over any number of channels to node 2.
+		fn new(strategy: RetryStrategy) -> Self {
+			Self::with_channels(strategy, (1..100).map(|short_channel_id| (short_channel_id, 2)).collect())
+		}
+
+		fn with_channels(strategy: RetryStrategy, channels: Vec<(u64, u8)>) -> Self {
+			let calls = Arc::new(Calls { channels, ..Calls::default() });
+			let this_arg = Arc::as_ptr(&calls) as *mut c_void;
+			let scorer = MultiThreadedLockableScore_new(Score {
+				this_arg: std::ptr::null_mut(), channel_penalty_msat, payment_path_failed, payment_path_successful, write, free: None,
+			});
+			let payer = Payer { this_arg, node_id, first_hops, send_payment, send_spontaneous_payment, retry_payment, abandon_payment, free: None };
+			let router = Router { this_arg, find_route, free: None };
+			let event_handler = EventHandler { this_arg, handle_event, free: None };
+			let outcome_handler = PaymentOutcomeHandler { this_arg, handle_outcome, free: None };
+			let payer = RetryingInvoicePayer_new(payer, router, &scorer, logger(), event_handler, outcome_handler, strategy);
+			Self { payer, _scorer: scorer, calls }
+		}
+
+		fn native(&self) -> &nativeRetryingInvoicePayer {
+			self.payer.get_native_ref()
+		}
+
+		fn params() -> RouteParameters {
+			RouteParameters { payee: Payee::for_keysend(pubkey(3)), final_value_msat: 1000, final_cltv_expiry_delta: 40 }
+		}
+
+		fn pay(&self) -> Result<PaymentId, nativePaymentError> {
+			self.native().pay(PAYMENT_HASH, &Self::params(), |_| Ok(PAYMENT_ID))
+		}
+
+		/// Fails the most recently attempted path at its first channel, returning whether the
+		/// failure would be passed on to the event handler, ie whether the payment was not retried.
+		fn fail_latest_path(&self) -> bool {
+			self.fail_latest_path_with(None)
+		}
+
+		/// Fails the most recently attempted path like `fail_latest_path`, conveying the given
+		/// update.
+		fn fail_latest_path_with(&self, network_update: Option<NetworkUpdate>) -> bool {
+			let path = self.latest_path();
+			self.native().payment_path_failed(PAYMENT_HASH, &path, Some(path[0].short_channel_id), &network_update, false, &Some(Self::params()))
+		}
+
+		fn latest_path(&self) -> Vec<RouteHop> {
+			self.native().payments.lock().unwrap()[&PAYMENT_HASH].paths.last().unwrap().hops.clone()
+		}
+
+		fn fail_payment(&self) {
+			self.native().handle_event(&Event::PaymentFailed { payment_id: ThirtyTwoBytes { data: PAYMENT_ID.0 }, payment_hash: ThirtyTwoBytes { data: PAYMENT_HASH.0 } });
+		}
+
+		fn failure(&self) -> Option<PaymentOutcomeStatus> {
+			self.native().payments.lock().unwrap()[&PAYMENT_HASH].failure
+		}
+	}
+
+	fn strategy() -> RetryStrategy {
+		RetryStrategy { timeout_secs: COption_u64Z::Some(3600), max_total_fee_msat: COption_u64Z::Some(1_000_000), ..RetryStrategy_default() }
+	}
+
+	#[test]
+	fn stops_at_attempt_count() {
+		let test = TestPayer::new(RetryStrategy { max_attempts: 3, ..strategy() });
+		assert_eq!(test.pay().unwrap(), PAYMENT_ID);
+		assert!(!test.fail_latest_path());
+		assert!(!test.fail_latest_path());
+		assert_eq!(test.failure(), None);
+		assert!(test.fail_latest_path());
+		assert_eq!(test.failure(), Some(PaymentOutcomeStatus::RetriesExhausted));
+		assert_eq!(test.calls.routes.load(Ordering::SeqCst), 3);
+		assert_eq!(test.calls.retries.load(Ordering::SeqCst), 2);
+		assert_eq!(test.calls.abandoned.load(Ordering::SeqCst), 1);
+
+		// A second payment to the same hash is refused while the first is pending.
+		assert!(test.pay().is_err());
+
+		test.fail_payment();
+		assert_eq!(test.calls.events.load(Ordering::SeqCst), 1);
+		let outcomes = test.calls.outcomes.lock().unwrap();
+		assert_eq!(outcomes.len(), 1);
+		let paths = (1..4).map(|scid| (PaymentPathStatus::Failed, Some(scid), None)).collect::<Vec<_>>();
+		assert_eq!(outcomes[0], (PaymentOutcomeStatus::RetriesExhausted, 3, None, paths));
+		assert!(test.native().payments.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn stops_at_deadline() {
+		let test = TestPayer::new(RetryStrategy { timeout_secs: COption_u64Z::Some(60), ..strategy() });
+		assert!(test.pay().is_ok());
+		assert!(!test.fail_latest_path());
+
+		test.native().payments.lock().unwrap().get_mut(&PAYMENT_HASH).unwrap().started_at = Instant::now() - Duration::from_secs(60);
+		assert!(test.fail_latest_path());
+		assert_eq!(test.failure(), Some(PaymentOutcomeStatus::TimedOut));
+		assert_eq!(test.calls.retries.load(Ordering::SeqCst), 1);
+		assert_eq!(test.calls.abandoned.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn stops_at_fee_limit() {
+		let test = TestPayer::new(RetryStrategy { max_total_fee_msat: COption_u64Z::Some(ROUTE_FEE_MSAT * 2), ..strategy() });
+		assert!(test.pay().is_ok());
+		assert!(!test.fail_latest_path());
+		// The third route costs more than the limit.
+		assert!(test.fail_latest_path());
+		assert_eq!(test.failure(), Some(PaymentOutcomeStatus::FeeLimitReached));
+		assert_eq!(test.calls.routes.load(Ordering::SeqCst), 3);
+		assert_eq!(test.calls.retries.load(Ordering::SeqCst), 1);
+
+		// A first route above the limit fails the payment outright.
+		let test = TestPayer::new(RetryStrategy { max_total_fee_msat: COption_u64Z::Some(ROUTE_FEE_MSAT - 1), ..strategy() });
+		match test.pay() {
+			Err(nativePaymentError::Routing(_)) => {},
+			_ => panic!("Expected a routing failure"),
+		}
+		assert!(test.native().payments.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn reports_success() {
+		let test = TestPayer::new(strategy());
+		assert!(test.pay().is_ok());
+		assert!(!test.fail_latest_path());
+		test.native().handle_event(&Event::PaymentSent {
+			payment_id: ThirtyTwoBytes { data: PAYMENT_ID.0 }, payment_preimage: ThirtyTwoBytes { data: [3; 32] },
+			payment_hash: ThirtyTwoBytes { data: PAYMENT_HASH.0 }, fee_paid_msat: COption_u64Z::Some(ROUTE_FEE_MSAT * 2),
+		});
+		let outcomes = test.calls.outcomes.lock().unwrap();
+		let paths = vec![(PaymentPathStatus::Failed, Some(1), None), (PaymentPathStatus::Succeeded, None, None)];
+		assert_eq!(outcomes[..], [(PaymentOutcomeStatus::Succeeded, 2, Some(ROUTE_FEE_MSAT * 2), paths)]);
+	}
+
+	#[test]
+	fn excluded_channels_leave_no_route() {
+		let test = TestPayer::with_channels(strategy(), vec![(1, 2), (2, 2)]);
+		assert!(test.pay().is_ok());
+		assert!(!test.fail_latest_path());
+		assert_eq!(test.latest_path()[0].short_channel_id, 2);
+		// The router falls back to the excluded channel, which the payer refuses to retry over.
+		assert!(test.fail_latest_path());
+		assert_eq!(test.failure(), Some(PaymentOutcomeStatus::NoRouteFound));
+		assert_eq!(test.calls.routes.load(Ordering::SeqCst), 3);
+		assert_eq!(test.calls.retries.load(Ordering::SeqCst), 1);
+		assert_eq!(test.calls.abandoned.load(Ordering::SeqCst), 1);
+
+		// Without excluding failed channels, the payment is retried over the same channel.
+		let test = TestPayer::with_channels(RetryStrategy { exclude_failed_channels: false, ..strategy() }, vec![(1, 2)]);
+		assert!(test.pay().is_ok());
+		assert!(!test.fail_latest_path());
+		assert_eq!(test.latest_path()[0].short_channel_id, 1);
+		assert_eq!(test.failure(), None);
+	}
+
+	#[test]
+	fn node_failures_exclude_the_node() {
+		let strategy = RetryStrategy { exclude_failed_channels: false, exclude_failed_nodes: true, ..strategy() };
+		let test = TestPayer::with_channels(strategy, vec![(1, 2), (2, 2), (3, 4)]);
+		assert!(test.pay().is_ok());
+		assert_eq!(test.latest_path()[0].pubkey, pubkey(2));
+		// Every channel through the failed node is avoided, not only the one which failed.
+		assert!(!test.fail_latest_path_with(Some(NetworkUpdate::NodeFailure { node_id: pubkey(2), is_permanent: false })));
+		assert_eq!(test.latest_path()[0].short_channel_id, 3);
+		assert!(test.fail_latest_path_with(Some(NetworkUpdate::NodeFailure { node_id: pubkey(4), is_permanent: false })));
+		assert_eq!(test.failure(), Some(PaymentOutcomeStatus::NoRouteFound));
+
+		// Without excluding failed nodes, a channel failure only excludes the channel.
+		let test = TestPayer::with_channels(strategy(), vec![(1, 2), (2, 2), (3, 4)]);
+		assert!(test.pay().is_ok());
+		assert!(!test.fail_latest_path_with(Some(NetworkUpdate::NodeFailure { node_id: pubkey(2), is_permanent: false })));
+		assert_eq!(test.latest_path()[0].short_channel_id, 2);
+	}
+
+	#[test]
+	fn outcome_reports_path_failures() {
+		let test = TestPayer::new(RetryStrategy { max_attempts: 2, ..strategy() });
+		assert!(test.pay().is_ok());
+		let channel_closed = NetworkUpdate::ChannelClosed { short_channel_id: 1, is_permanent: true };
+		let node_failure = NetworkUpdate::NodeFailure { node_id: pubkey(2), is_permanent: false };
+		assert!(!test.fail_latest_path_with(Some(channel_closed.clone())));
+		assert!(test.fail_latest_path_with(Some(node_failure.clone())));
+		test.fail_payment();
+
+		let outcomes = test.calls.outcomes.lock().unwrap();
+		let paths = vec![
+			(PaymentPathStatus::Failed, Some(1), Some(channel_closed.encode())),
+			(PaymentPathStatus::Failed, Some(2), Some(node_failure.encode())),
+		];
+		assert_eq!(outcomes[..], [(PaymentOutcomeStatus::RetriesExhausted, 2, None, paths)]);
+	}
+}
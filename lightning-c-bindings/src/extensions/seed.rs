@@ -0,0 +1,612 @@
+//! BIP 39 mnemonics and passphrase-protected seed files, so that every application built on these
+//! bindings backs up and restores node seeds the same way.
+//!
+//! A node is backed up by its mnemonic alone. The 32-byte seed passed to `KeysManager_new` (and
+//! to the other seed-based utilities, such as `OnChainWallet_new`) is derived from it as follows:
+//!  1. the 64-byte BIP 39 seed is computed from the mnemonic and the (possibly empty) BIP 39
+//!     passphrase,
+//!  2. a BIP 32 master key is derived from it, and from that the key at path `m/535'/0'`,
+//!  3. that key's 32-byte private key is the LDK seed.
+//!
+//! Mnemonics are always in the English wordlist. They are matched case-insensitively and with any
+//! amount of whitespace between words, and generated in lower case with single spaces.
+//!
+//! `SeedFile_write` and `SeedFile_read` store a mnemonic on disk, encrypted under a file
+//! passphrase. The file passphrase only protects the file, unlike the BIP 39 passphrase which
+//! changes the derived seed and is not stored. The file format is:
+//!  * the 8-byte magic `LDKSEED\0` and a version byte, currently 1,
+//!  * the PBKDF2-HMAC-SHA256 iteration count, as a big-endian u32, and a 16-byte random salt,
+//!  * a 24-byte random nonce,
+//!  * the mnemonic's entropy, encrypted with XChaCha20-Poly1305 under the PBKDF2 output and with
+//!    all preceding bytes as associated data, followed by the 16-byte authentication tag.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use bip39::{Language, Mnemonic};
+
+use bitcoin::hashes::{Hash, HashEngine, Hmac, HmacEngine};
+use bitcoin::hashes::sha256::Hash as Sha256;
+use bitcoin::network::constants::Network as BitcoinNetwork;
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::util::bip32::{ChildNumber, ExtendedPrivKey};
+
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+
+use crate::c_types::*;
+
+const SEED_FILE_MAGIC: &[u8; 8] = b"LDKSEED\0";
+const SEED_FILE_VERSION: u8 = 1;
+/// The PBKDF2 iteration count used for new seed files. Files record their own count, so this can
+/// be raised without breaking existing files.
+const PBKDF2_ITERATIONS: u32 = 200_000;
+/// Files claiming more iterations than this are rejected rather than keeping us busy for hours.
+const MAX_PBKDF2_ITERATIONS: u32 = 100_000_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+const HEADER_LEN: usize = 8 + 1 + 4 + SALT_LEN + NONCE_LEN;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The reasons a mnemonic or seed file operation can fail.
+pub enum SeedError {
+	/// The mnemonic does not have 12, 15, 18, 21 or 24 words, or such a word count was not given
+	/// to `Mnemonic_generate`.
+	BadWordCount,
+	/// A word of the mnemonic is not in the BIP 39 English wordlist.
+	UnknownWord,
+	/// The mnemonic's checksum does not match, usually because a word was mistyped or the words
+	/// are out of order.
+	InvalidChecksum,
+	/// The system's random number generator could not be read.
+	EntropyUnavailable,
+	/// `SeedFile_write` was given the path of an existing file, which it never overwrites.
+	FileExists,
+	/// The seed file could not be read or written.
+	FileAccessFailed,
+	/// The file is not a seed file, or is of an unknown version or truncated.
+	InvalidSeedFile,
+	/// The seed file failed to decrypt, either because the passphrase is wrong or because the file
+	/// was corrupted.
+	WrongPassphrase,
+}
+
+impl From<bip39::Error> for SeedError {
+	fn from(e: bip39::Error) -> Self {
+		match e {
+			bip39::Error::UnknownWord(_) => SeedError::UnknownWord,
+			bip39::Error::InvalidChecksum => SeedError::InvalidChecksum,
+			_ => SeedError::BadWordCount,
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_StrSeedErrorZ
+pub union CResult_StrSeedErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut Str,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut SeedError,
+}
+#[repr(C)]
+/// A CResult_StrSeedErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::Str on success and a SeedError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_StrSeedErrorZ {
+	/// The contents of this CResult_StrSeedErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_StrSeedErrorZPtr,
+	/// Whether this CResult_StrSeedErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_StrSeedErrorZ in the success state.
+pub extern "C" fn CResult_StrSeedErrorZ_ok(o: Str) -> CResult_StrSeedErrorZ {
+	CResult_StrSeedErrorZ {
+		contents: CResult_StrSeedErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_StrSeedErrorZ in the error state.
+pub extern "C" fn CResult_StrSeedErrorZ_err(e: SeedError) -> CResult_StrSeedErrorZ {
+	CResult_StrSeedErrorZ {
+		contents: CResult_StrSeedErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_StrSeedErrorZ_is_ok(o: &CResult_StrSeedErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_StrSeedErrorZ.
+pub extern "C" fn CResult_StrSeedErrorZ_free(_res: CResult_StrSeedErrorZ) { }
+impl Drop for CResult_StrSeedErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_StrSeedErrorZ {
+	fn from_rust(res: Result<Mnemonic, SeedError>) -> Self {
+		match res {
+			Ok(mnemonic) => CResult_StrSeedErrorZ_ok(mnemonic.to_string().into()),
+			Err(e) => CResult_StrSeedErrorZ_err(e),
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_NoneSeedErrorZ
+pub union CResult_NoneSeedErrorZPtr {
+	/// Note that this value is always NULL, as there are no contents in the OK variant
+	pub result: *mut std::ffi::c_void,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut SeedError,
+}
+#[repr(C)]
+/// A CResult_NoneSeedErrorZ represents the result of a fallible operation,
+/// containing a () on success and a SeedError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_NoneSeedErrorZ {
+	/// The contents of this CResult_NoneSeedErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_NoneSeedErrorZPtr,
+	/// Whether this CResult_NoneSeedErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_NoneSeedErrorZ in the success state.
+pub extern "C" fn CResult_NoneSeedErrorZ_ok() -> CResult_NoneSeedErrorZ {
+	CResult_NoneSeedErrorZ {
+		contents: CResult_NoneSeedErrorZPtr {
+			result: std::ptr::null_mut(),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_NoneSeedErrorZ in the error state.
+pub extern "C" fn CResult_NoneSeedErrorZ_err(e: SeedError) -> CResult_NoneSeedErrorZ {
+	CResult_NoneSeedErrorZ {
+		contents: CResult_NoneSeedErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_NoneSeedErrorZ_is_ok(o: &CResult_NoneSeedErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_NoneSeedErrorZ.
+pub extern "C" fn CResult_NoneSeedErrorZ_free(_res: CResult_NoneSeedErrorZ) { }
+impl Drop for CResult_NoneSeedErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_NoneSeedErrorZ {
+	fn from_rust(res: Result<(), SeedError>) -> Self {
+		match res {
+			Ok(()) => CResult_NoneSeedErrorZ_ok(),
+			Err(e) => CResult_NoneSeedErrorZ_err(e),
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_ThirtyTwoBytesSeedErrorZ
+pub union CResult_ThirtyTwoBytesSeedErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut ThirtyTwoBytes,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut SeedError,
+}
+#[repr(C)]
+/// A CResult_ThirtyTwoBytesSeedErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::ThirtyTwoBytes on success and a SeedError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_ThirtyTwoBytesSeedErrorZ {
+	/// The contents of this CResult_ThirtyTwoBytesSeedErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_ThirtyTwoBytesSeedErrorZPtr,
+	/// Whether this CResult_ThirtyTwoBytesSeedErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_ThirtyTwoBytesSeedErrorZ in the success state.
+pub extern "C" fn CResult_ThirtyTwoBytesSeedErrorZ_ok(o: ThirtyTwoBytes) -> CResult_ThirtyTwoBytesSeedErrorZ {
+	CResult_ThirtyTwoBytesSeedErrorZ {
+		contents: CResult_ThirtyTwoBytesSeedErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_ThirtyTwoBytesSeedErrorZ in the error state.
+pub extern "C" fn CResult_ThirtyTwoBytesSeedErrorZ_err(e: SeedError) -> CResult_ThirtyTwoBytesSeedErrorZ {
+	CResult_ThirtyTwoBytesSeedErrorZ {
+		contents: CResult_ThirtyTwoBytesSeedErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_ThirtyTwoBytesSeedErrorZ_is_ok(o: &CResult_ThirtyTwoBytesSeedErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_ThirtyTwoBytesSeedErrorZ.
+pub extern "C" fn CResult_ThirtyTwoBytesSeedErrorZ_free(_res: CResult_ThirtyTwoBytesSeedErrorZ) { }
+impl Drop for CResult_ThirtyTwoBytesSeedErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_ThirtyTwoBytesSeedErrorZ {
+	fn from_rust(res: Result<[u8; 32], SeedError>) -> Self {
+		match res {
+			Ok(data) => CResult_ThirtyTwoBytesSeedErrorZ_ok(ThirtyTwoBytes { data }),
+			Err(e) => CResult_ThirtyTwoBytesSeedErrorZ_err(e),
+		}
+	}
+}
+
+fn parse_mnemonic(mnemonic: &str) -> Result<Mnemonic, SeedError> {
+	Ok(Mnemonic::parse_in(Language::English, &mnemonic.to_lowercase())?)
+}
+
+fn generate_mnemonic(word_count: u8) -> Result<Mnemonic, SeedError> {
+	let entropy_len = match word_count {
+		12 | 15 | 18 | 21 | 24 => word_count as usize * 4 / 3,
+		_ => return Err(SeedError::BadWordCount),
+	};
+	let mut entropy = [0; 32];
+	getrandom::getrandom(&mut entropy[..entropy_len]).map_err(|_| SeedError::EntropyUnavailable)?;
+	Ok(Mnemonic::from_entropy(&entropy[..entropy_len])?)
+}
+
+fn derive_ldk_seed(mnemonic: &Mnemonic, passphrase: &str) -> [u8; 32] {
+	let bip39_seed = mnemonic.to_seed(passphrase);
+	// The network only affects how extended keys are serialized, not the keys themselves.
+	let master_key = ExtendedPrivKey::new_master(BitcoinNetwork::Bitcoin, &bip39_seed).expect("Any 64-byte seed is a valid BIP 32 seed");
+	let path = [ChildNumber::from_hardened_idx(535).unwrap(), ChildNumber::from_hardened_idx(0).unwrap()];
+	let key = master_key.derive_priv(&Secp256k1::new(), &path).expect("Derivation of hardened keys cannot fail");
+	let mut seed = [0; 32];
+	seed.copy_from_slice(&key.private_key.key[..]);
+	seed
+}
+
+/// PBKDF2-HMAC-SHA256 (RFC 8018), producing a single 32-byte block.
+fn pbkdf2_sha256(passphrase: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+	let mut engine = HmacEngine::<Sha256>::new(passphrase);
+	engine.input(salt);
+	engine.input(&1u32.to_be_bytes());
+	let mut u = Hmac::from_engine(engine).into_inner();
+	let mut key = u;
+	for _ in 1..iterations {
+		let mut engine = HmacEngine::<Sha256>::new(passphrase);
+		engine.input(&u);
+		u = Hmac::from_engine(engine).into_inner();
+		for (k, u) in key.iter_mut().zip(u.iter()) { *k ^= u; }
+	}
+	key
+}
+
+fn seed_file_cipher(passphrase: &str, salt: &[u8], iterations: u32) -> XChaCha20Poly1305 {
+	let key = pbkdf2_sha256(passphrase.as_bytes(), salt, iterations);
+	XChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+fn encrypt_seed_file(mnemonic: &Mnemonic, passphrase: &str) -> Result<Vec<u8>, SeedError> {
+	let mut salt_and_nonce = [0; SALT_LEN + NONCE_LEN];
+	getrandom::getrandom(&mut salt_and_nonce).map_err(|_| SeedError::EntropyUnavailable)?;
+	let (salt, nonce) = salt_and_nonce.split_at(SALT_LEN);
+
+	let mut data = Vec::with_capacity(HEADER_LEN + 32 + TAG_LEN);
+	data.extend_from_slice(SEED_FILE_MAGIC);
+	data.push(SEED_FILE_VERSION);
+	data.extend_from_slice(&PBKDF2_ITERATIONS.to_be_bytes());
+	data.extend_from_slice(salt);
+	data.extend_from_slice(nonce);
+
+	let cipher = seed_file_cipher(passphrase, salt, PBKDF2_ITERATIONS);
+	let ciphertext = cipher.encrypt(XNonce::from_slice(nonce), Payload { msg: &mnemonic.to_entropy(), aad: &data })
+		.expect("Encrypting a short message cannot fail");
+	data.extend_from_slice(&ciphertext);
+	Ok(data)
+}
+
+fn decrypt_seed_file(data: &[u8], passphrase: &str) -> Result<Mnemonic, SeedError> {
+	if data.len() < HEADER_LEN + TAG_LEN || &data[..8] != SEED_FILE_MAGIC || data[8] != SEED_FILE_VERSION {
+		return Err(SeedError::InvalidSeedFile);
+	}
+	let mut iterations_bytes = [0; 4];
+	iterations_bytes.copy_from_slice(&data[9..13]);
+	let iterations = u32::from_be_bytes(iterations_bytes);
+	if iterations == 0 || iterations > MAX_PBKDF2_ITERATIONS { return Err(SeedError::InvalidSeedFile); }
+	let salt = &data[13..13 + SALT_LEN];
+	let nonce = &data[13 + SALT_LEN..HEADER_LEN];
+
+	let cipher = seed_file_cipher(passphrase, salt, iterations);
+	let entropy = cipher.decrypt(XNonce::from_slice(nonce), Payload { msg: &data[HEADER_LEN..], aad: &data[..HEADER_LEN] })
+		.map_err(|_| SeedError::WrongPassphrase)?;
+	Mnemonic::from_entropy(&entropy).map_err(|_| SeedError::InvalidSeedFile)
+}
+
+/// Writes to a temporary file which is then hard-linked to the destination, so that a crash never
+/// leaves a partially-written seed file behind. Unlike a rename, linking fails with
+/// `ErrorKind::AlreadyExists` rather than replacing a seed file created in the meantime.
+fn write_seed_file(path: &Path, data: &[u8]) -> Result<(), std::io::Error> {
+	let mut tmp_path = path.as_os_str().to_owned();
+	tmp_path.push(".tmp");
+	{
+		let mut options = fs::OpenOptions::new();
+		options.write(true).create(true).truncate(true);
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::OpenOptionsExt;
+			options.mode(0o600);
+		}
+		let mut tmp_file = options.open(&tmp_path)?;
+		tmp_file.write_all(data)?;
+		tmp_file.sync_all()?;
+	}
+	let link_res = fs::hard_link(&tmp_path, path);
+	let _ = fs::remove_file(&tmp_path);
+	link_res?;
+	#[cfg(not(target_os = "windows"))]
+	{
+		if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+			fs::File::open(dir)?.sync_all()?;
+		}
+	}
+	Ok(())
+}
+
+/// Generates a new mnemonic of `word_count` words (12, 15, 18, 21 or 24) from the system's random
+/// number generator.
+///
+/// 24 words are recommended for new nodes.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Mnemonic_generate(mut word_count: u8) -> CResult_StrSeedErrorZ {
+	CResult_StrSeedErrorZ::from_rust(generate_mnemonic(word_count))
+}
+
+/// Checks that `mnemonic` consists of words from the BIP 39 English wordlist with a valid
+/// checksum, returning the reason it does not otherwise.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Mnemonic_validate(mut mnemonic: Str) -> CResult_NoneSeedErrorZ {
+	CResult_NoneSeedErrorZ::from_rust(parse_mnemonic(mnemonic.into_str()).map(|_| ()))
+}
+
+/// Returns `mnemonic` in its canonical form: lower case, with single spaces between words.
+///
+/// Applications should display and store mnemonics in this form.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Mnemonic_normalize(mut mnemonic: Str) -> CResult_StrSeedErrorZ {
+	CResult_StrSeedErrorZ::from_rust(parse_mnemonic(mnemonic.into_str()))
+}
+
+/// Derives the 32-byte seed for `KeysManager_new` from `mnemonic` and a BIP 39 passphrase, which
+/// may be empty, as described in the module documentation.
+///
+/// Note that any passphrase yields a valid seed, so a mistyped passphrase silently results in a
+/// different, empty, node.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Mnemonic_to_ldk_seed(mut mnemonic: Str, mut bip39_passphrase: Str) -> CResult_ThirtyTwoBytesSeedErrorZ {
+	CResult_ThirtyTwoBytesSeedErrorZ::from_rust(parse_mnemonic(mnemonic.into_str())
+		.map(|mnemonic| derive_ldk_seed(&mnemonic, bip39_passphrase.into_str())))
+}
+
+/// Writes `mnemonic` to a new seed file at `path`, encrypted under `file_passphrase`.
+///
+/// Fails with `SeedError_FileExists` rather than overwriting an existing file. On Unix the file is
+/// only readable by the current user.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn SeedFile_write(mut path: Str, mut mnemonic: Str, mut file_passphrase: Str) -> CResult_NoneSeedErrorZ {
+	let path = Path::new(path.into_str());
+	let res = parse_mnemonic(mnemonic.into_str()).and_then(|mnemonic| {
+		let data = encrypt_seed_file(&mnemonic, file_passphrase.into_str())?;
+		write_seed_file(path, &data).map_err(|e| match e.kind() {
+			std::io::ErrorKind::AlreadyExists => SeedError::FileExists,
+			_ => SeedError::FileAccessFailed,
+		})
+	});
+	CResult_NoneSeedErrorZ::from_rust(res)
+}
+
+/// Reads the mnemonic from the seed file at `path`, decrypting it with `file_passphrase`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn SeedFile_read(mut path: Str, mut file_passphrase: Str) -> CResult_StrSeedErrorZ {
+	let res = fs::read(path.into_str()).map_err(|_| SeedError::FileAccessFailed)
+		.and_then(|data| decrypt_seed_file(&data, file_passphrase.into_str()));
+	CResult_StrSeedErrorZ::from_rust(res)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::hashes::hex::{FromHex, ToHex};
+
+	const ABANDON: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+	#[test]
+	fn bip39_test_vectors() {
+		// From the BIP 39 reference vectors, which all use the passphrase "TREZOR".
+		let vectors = [
+			("00000000000000000000000000000000", ABANDON,
+				"c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"),
+			("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f", "legal winner thank year wave sausage worth useful legal winner thank yellow",
+				"2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607"),
+			("ffffffffffffffffffffffffffffffff", "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+				"ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069"),
+		];
+		for (entropy, words, seed) in vectors.iter() {
+			let entropy = Vec::<u8>::from_hex(entropy).unwrap();
+			assert_eq!(Mnemonic::from_entropy(&entropy).unwrap().to_string(), *words);
+			let mnemonic = parse_mnemonic(words).unwrap();
+			assert_eq!(mnemonic.to_entropy(), entropy);
+			assert_eq!(mnemonic.to_seed("TREZOR").to_hex(), *seed);
+		}
+	}
+
+	#[test]
+	fn ldk_seed_derivation() {
+		// m/535'/0' from the BIP 39 seeds above, as computed by an independent BIP 32
+		// implementation.
+		let mnemonic = parse_mnemonic(ABANDON).unwrap();
+		assert_eq!(derive_ldk_seed(&mnemonic, "").to_hex(), "c9e1abf64312a43d74b6452e5be41b6b430b777acaedaa7b1a67e077428bf9eb");
+		assert_eq!(derive_ldk_seed(&mnemonic, "TREZOR").to_hex(), "8fc9a408eef1e3d891b5b7d9ac04c0eb11c9d1acf2e471ac3a9c363ed4971eb7");
+		let mnemonic = parse_mnemonic("legal winner thank year wave sausage worth useful legal winner thank yellow").unwrap();
+		assert_eq!(derive_ldk_seed(&mnemonic, "TREZOR").to_hex(), "e7e65707af0cd2c091cee81b96c1f5f125dce8e72556f0177a184ea5c64438d4");
+	}
+
+	#[test]
+	fn mnemonic_parsing() {
+		let messy = format!("  {}\t\n", ABANDON.to_uppercase().replace(' ', "   "));
+		assert_eq!(parse_mnemonic(&messy).unwrap().to_string(), ABANDON);
+
+		assert_eq!(parse_mnemonic(&ABANDON[..ABANDON.len() - " about".len()]).err(), Some(SeedError::BadWordCount));
+		assert_eq!(parse_mnemonic(&ABANDON.replace("about", "abut")).err(), Some(SeedError::UnknownWord));
+		assert_eq!(parse_mnemonic(&ABANDON.replace("about", "abandon")).err(), Some(SeedError::InvalidChecksum));
+	}
+
+	#[test]
+	fn mnemonic_generation() {
+		for word_count in [12, 15, 18, 21, 24].iter() {
+			let mnemonic = generate_mnemonic(*word_count).unwrap();
+			assert_eq!(mnemonic.to_string().split(' ').count(), *word_count as usize);
+		}
+		assert_ne!(generate_mnemonic(24).unwrap(), generate_mnemonic(24).unwrap());
+		assert_eq!(generate_mnemonic(13).err(), Some(SeedError::BadWordCount));
+		assert_eq!(generate_mnemonic(0).err(), Some(SeedError::BadWordCount));
+	}
+
+	#[test]
+	fn pbkdf2_test_vectors() {
+		// From RFC 7914 and RFC 6070's vectors computed with SHA-256.
+		assert_eq!(pbkdf2_sha256(b"passwd", b"salt", 1).to_hex(), "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc");
+		assert_eq!(pbkdf2_sha256(b"password", b"salt", 4096).to_hex(), "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a");
+	}
+
+	/// Builds a seed file as `encrypt_seed_file` does, but with the given iteration count to keep
+	/// the tests fast.
+	fn seed_file_with_iterations(mnemonic: &Mnemonic, passphrase: &str, iterations: u32) -> Vec<u8> {
+		let mut data = SEED_FILE_MAGIC.to_vec();
+		data.push(SEED_FILE_VERSION);
+		data.extend_from_slice(&iterations.to_be_bytes());
+		data.extend_from_slice(&[1; SALT_LEN]);
+		data.extend_from_slice(&[2; NONCE_LEN]);
+		let cipher = seed_file_cipher(passphrase, &[1; SALT_LEN], iterations);
+		let ciphertext = cipher.encrypt(XNonce::from_slice(&[2; NONCE_LEN]), Payload { msg: &mnemonic.to_entropy(), aad: &data }).unwrap();
+		data.extend_from_slice(&ciphertext);
+		data
+	}
+
+	#[test]
+	fn seed_file_round_trip() {
+		let mnemonic = parse_mnemonic(ABANDON).unwrap();
+		let data = encrypt_seed_file(&mnemonic, "file passphrase").unwrap();
+		assert_eq!(data.len(), HEADER_LEN + 16 + TAG_LEN);
+		assert_eq!(&data[..9], b"LDKSEED\0\x01");
+		assert_eq!(data[9..13], PBKDF2_ITERATIONS.to_be_bytes());
+		assert_eq!(decrypt_seed_file(&data, "file passphrase").unwrap(), mnemonic);
+		// The salt and nonce are random.
+		assert_ne!(encrypt_seed_file(&mnemonic, "file passphrase").unwrap()[13..HEADER_LEN], data[13..HEADER_LEN]);
+	}
+
+	#[test]
+	fn seed_file_decryption_failures() {
+		let mnemonic = parse_mnemonic(ABANDON).unwrap();
+		let data = seed_file_with_iterations(&mnemonic, "file passphrase", 10);
+		assert_eq!(decrypt_seed_file(&data, "file passphrase").unwrap(), mnemonic);
+		assert_eq!(decrypt_seed_file(&data, "wrong passphrase").err(), Some(SeedError::WrongPassphrase));
+
+		// The header is authenticated, so changing the iteration count or the salt fails
+		// decryption.
+		for idx in [12, 13].iter() {
+			let mut tampered = data.clone();
+			tampered[*idx] ^= 1;
+			assert_eq!(decrypt_seed_file(&tampered, "file passphrase").err(), Some(SeedError::WrongPassphrase));
+		}
+		let mut tampered = data.clone();
+		*tampered.last_mut().unwrap() ^= 1;
+		assert_eq!(decrypt_seed_file(&tampered, "file passphrase").err(), Some(SeedError::WrongPassphrase));
+
+		let mut bad_magic = data.clone();
+		bad_magic[0] ^= 1;
+		assert_eq!(decrypt_seed_file(&bad_magic, "file passphrase").err(), Some(SeedError::InvalidSeedFile));
+		let mut bad_version = data.clone();
+		bad_version[8] = 2;
+		assert_eq!(decrypt_seed_file(&bad_version, "file passphrase").err(), Some(SeedError::InvalidSeedFile));
+		for iterations in [0, MAX_PBKDF2_ITERATIONS + 1].iter() {
+			let mut bad_iterations = data.clone();
+			bad_iterations[9..13].copy_from_slice(&iterations.to_be_bytes());
+			assert_eq!(decrypt_seed_file(&bad_iterations, "file passphrase").err(), Some(SeedError::InvalidSeedFile));
+		}
+		assert_eq!(decrypt_seed_file(&data[..HEADER_LEN + TAG_LEN - 1], "file passphrase").err(), Some(SeedError::InvalidSeedFile));
+	}
+
+	#[test]
+	fn seed_files_are_never_overwritten() {
+		let path = std::env::temp_dir().join(format!("ldk-seed-test-{}", std::process::id()));
+		let _ = fs::remove_file(&path);
+		write_seed_file(&path, b"first").unwrap();
+		assert_eq!(write_seed_file(&path, b"second").unwrap_err().kind(), std::io::ErrorKind::AlreadyExists);
+		assert_eq!(fs::read(&path).unwrap(), b"first");
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+		}
+		fs::remove_file(&path).unwrap();
+	}
+}
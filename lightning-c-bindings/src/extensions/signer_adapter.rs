@@ -0,0 +1,192 @@
+//! Conversions from Rust implementations of `lightning`'s `Sign` and `KeysInterface` traits to
+//! their C trait structs, for hand-written signers in this module.
+//!
+//! The generated bindings provide such conversions only for the concrete types of the upstream
+//! crates (eg `InMemorySigner_as_Sign`). These are the same conversions, generic over the
+//! implementation, with the returned trait struct owning the Rust object.
+
+use std::ffi::c_void;
+
+use bitcoin::secp256k1::key::SecretKey as SecpSecretKey;
+
+use lightning::chain::keysinterface::{BaseSign as rustBaseSign, KeysInterface as rustKeysInterface, Sign as rustSign};
+use lightning::util::ser::Writeable;
+
+use crate::c_types::*;
+use crate::c_types::derived::{CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ, CResult_NoneNoneZ, CResult_RecoverableSignatureNoneZ, CResult_SignatureNoneZ, CResult_SignDecodeErrorZ, CVec_u8Z};
+use crate::lightning::chain::keysinterface::{BaseSign, KeysInterface, Sign};
+use crate::lightning::ln::chan_utils::{ChannelPublicKeys, ChannelTransactionParameters, ClosingTransaction, CommitmentTransaction, HolderCommitmentTransaction, HTLCOutputInCommitment};
+use crate::lightning::ln::msgs::{DecodeError, UnsignedChannelAnnouncement};
+use crate::lightning::ln::script::ShutdownScript;
+
+/// Wraps a Rust `Sign` implementation in a C [`Sign`], which frees it when dropped.
+pub(crate) fn sign_from_native<S: rustSign + 'static>(signer: S) -> Sign {
+	let this_arg = Box::into_raw(Box::new(signer)) as *mut c_void;
+	Sign {
+		this_arg,
+		free: Some(free_void::<S>),
+		BaseSign: BaseSign {
+			this_arg,
+			free: None,
+			get_per_commitment_point: get_per_commitment_point::<S>,
+			release_commitment_secret: release_commitment_secret::<S>,
+			validate_holder_commitment: validate_holder_commitment::<S>,
+
+			pubkeys: ChannelPublicKeys { inner: std::ptr::null_mut(), is_owned: true },
+			set_pubkeys: Some(set_pubkeys::<S>),
+			channel_keys_id: channel_keys_id::<S>,
+			sign_counterparty_commitment: sign_counterparty_commitment::<S>,
+			validate_counterparty_revocation: validate_counterparty_revocation::<S>,
+			sign_holder_commitment_and_htlcs: sign_holder_commitment_and_htlcs::<S>,
+			sign_justice_revoked_output: sign_justice_revoked_output::<S>,
+			sign_justice_revoked_htlc: sign_justice_revoked_htlc::<S>,
+			sign_counterparty_htlc_transaction: sign_counterparty_htlc_transaction::<S>,
+			sign_closing_transaction: sign_closing_transaction::<S>,
+			sign_channel_announcement: sign_channel_announcement::<S>,
+			ready_channel: ready_channel::<S>,
+		},
+		write: write_void::<S>,
+		cloned: Some(cloned::<S>),
+	}
+}
+
+/// Wraps a Rust `KeysInterface` implementation in a C [`KeysInterface`], which frees it when
+/// dropped. The signers it provides are wrapped with [`sign_from_native`].
+pub(crate) fn keys_interface_from_native<K: rustKeysInterface + 'static>(keys: K) -> KeysInterface where K::Signer: 'static {
+	KeysInterface {
+		this_arg: Box::into_raw(Box::new(keys)) as *mut c_void,
+		free: Some(free_void::<K>),
+		get_node_secret: get_node_secret::<K>,
+		get_destination_script: get_destination_script::<K>,
+		get_shutdown_scriptpubkey: get_shutdown_scriptpubkey::<K>,
+		get_channel_signer: get_channel_signer::<K>,
+		get_secure_random_bytes: get_secure_random_bytes::<K>,
+		read_chan_signer: read_chan_signer::<K>,
+		sign_invoice: sign_invoice::<K>,
+		get_inbound_payment_key_material: get_inbound_payment_key_material::<K>,
+	}
+}
+
+fn obj<'a, T>(this_arg: *const c_void) -> &'a T {
+	unsafe { &*(this_arg as *const T) }
+}
+
+extern "C" fn free_void<T>(this_arg: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_arg as *mut T); }
+}
+
+fn secret_key(key: *const [u8; 32]) -> SecpSecretKey {
+	SecpSecretKey::from_slice(&unsafe { *key }[..]).unwrap()
+}
+
+fn signatures_result(res: Result<(bitcoin::secp256k1::Signature, Vec<bitcoin::secp256k1::Signature>), ()>) -> CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ {
+	match res {
+		Ok((sig, mut htlc_sigs)) => {
+			let mut local_htlc_sigs = Vec::new(); for item in htlc_sigs.drain(..) { local_htlc_sigs.push(Signature::from_rust(&item)); };
+			CResultTempl::ok((Signature::from_rust(&sig), local_htlc_sigs.into()).into()).into()
+		},
+		Err(()) => CResultTempl::err(()).into(),
+	}
+}
+
+fn signature_result(res: Result<bitcoin::secp256k1::Signature, ()>) -> CResult_SignatureNoneZ {
+	match res {
+		Ok(sig) => CResultTempl::ok(Signature::from_rust(&sig)).into(),
+		Err(()) => CResultTempl::err(()).into(),
+	}
+}
+
+fn none_result(res: Result<(), ()>) -> CResult_NoneNoneZ {
+	match res {
+		Ok(()) => CResultTempl::ok(()).into(),
+		Err(()) => CResultTempl::err(()).into(),
+	}
+}
+
+extern "C" fn get_per_commitment_point<S: rustBaseSign>(this_arg: *const c_void, idx: u64) -> PublicKey {
+	PublicKey::from_rust(&obj::<S>(this_arg).get_per_commitment_point(idx, secp256k1::SECP256K1))
+}
+extern "C" fn release_commitment_secret<S: rustBaseSign>(this_arg: *const c_void, idx: u64) -> ThirtyTwoBytes {
+	ThirtyTwoBytes { data: obj::<S>(this_arg).release_commitment_secret(idx) }
+}
+extern "C" fn validate_holder_commitment<S: rustBaseSign>(this_arg: *const c_void, holder_tx: &HolderCommitmentTransaction) -> CResult_NoneNoneZ {
+	none_result(obj::<S>(this_arg).validate_holder_commitment(holder_tx.get_native_ref()))
+}
+extern "C" fn set_pubkeys<S: rustBaseSign>(trait_self_arg: &BaseSign) {
+	// As in the generated bindings, this relies on the first call never happening in parallel.
+	if trait_self_arg.pubkeys.inner.is_null() {
+		let pubkeys = obj::<S>(trait_self_arg.this_arg).pubkeys();
+		unsafe { &mut *(trait_self_arg as *const BaseSign as *mut BaseSign) }.pubkeys =
+			ChannelPublicKeys { inner: ObjOps::nonnull_ptr_to_inner(pubkeys as *const _ as *mut _), is_owned: false };
+	}
+}
+extern "C" fn channel_keys_id<S: rustBaseSign>(this_arg: *const c_void) -> ThirtyTwoBytes {
+	ThirtyTwoBytes { data: obj::<S>(this_arg).channel_keys_id() }
+}
+extern "C" fn sign_counterparty_commitment<S: rustBaseSign>(this_arg: *const c_void, commitment_tx: &CommitmentTransaction) -> CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ {
+	signatures_result(obj::<S>(this_arg).sign_counterparty_commitment(commitment_tx.get_native_ref(), secp256k1::SECP256K1))
+}
+extern "C" fn validate_counterparty_revocation<S: rustBaseSign>(this_arg: *const c_void, idx: u64, secret: *const [u8; 32]) -> CResult_NoneNoneZ {
+	none_result(obj::<S>(this_arg).validate_counterparty_revocation(idx, &secret_key(secret)))
+}
+extern "C" fn sign_holder_commitment_and_htlcs<S: rustBaseSign>(this_arg: *const c_void, commitment_tx: &HolderCommitmentTransaction) -> CResult_C2Tuple_SignatureCVec_SignatureZZNoneZ {
+	signatures_result(obj::<S>(this_arg).sign_holder_commitment_and_htlcs(commitment_tx.get_native_ref(), secp256k1::SECP256K1))
+}
+extern "C" fn sign_justice_revoked_output<S: rustBaseSign>(this_arg: *const c_void, mut justice_tx: Transaction, input: usize, amount: u64, per_commitment_key: *const [u8; 32]) -> CResult_SignatureNoneZ {
+	signature_result(obj::<S>(this_arg).sign_justice_revoked_output(&justice_tx.into_bitcoin(), input, amount, &secret_key(per_commitment_key), secp256k1::SECP256K1))
+}
+extern "C" fn sign_justice_revoked_htlc<S: rustBaseSign>(this_arg: *const c_void, mut justice_tx: Transaction, input: usize, amount: u64, per_commitment_key: *const [u8; 32], htlc: &HTLCOutputInCommitment) -> CResult_SignatureNoneZ {
+	signature_result(obj::<S>(this_arg).sign_justice_revoked_htlc(&justice_tx.into_bitcoin(), input, amount, &secret_key(per_commitment_key), htlc.get_native_ref(), secp256k1::SECP256K1))
+}
+extern "C" fn sign_counterparty_htlc_transaction<S: rustBaseSign>(this_arg: *const c_void, mut htlc_tx: Transaction, input: usize, amount: u64, per_commitment_point: PublicKey, htlc: &HTLCOutputInCommitment) -> CResult_SignatureNoneZ {
+	signature_result(obj::<S>(this_arg).sign_counterparty_htlc_transaction(&htlc_tx.into_bitcoin(), input, amount, &per_commitment_point.into_rust(), htlc.get_native_ref(), secp256k1::SECP256K1))
+}
+extern "C" fn sign_closing_transaction<S: rustBaseSign>(this_arg: *const c_void, closing_tx: &ClosingTransaction) -> CResult_SignatureNoneZ {
+	signature_result(obj::<S>(this_arg).sign_closing_transaction(closing_tx.get_native_ref(), secp256k1::SECP256K1))
+}
+extern "C" fn sign_channel_announcement<S: rustBaseSign>(this_arg: *const c_void, msg: &UnsignedChannelAnnouncement) -> CResult_SignatureNoneZ {
+	signature_result(obj::<S>(this_arg).sign_channel_announcement(msg.get_native_ref(), secp256k1::SECP256K1))
+}
+extern "C" fn ready_channel<S: rustBaseSign>(this_arg: *mut c_void, channel_parameters: &ChannelTransactionParameters) {
+	unsafe { &mut *(this_arg as *mut S) }.ready_channel(channel_parameters.get_native_ref())
+}
+extern "C" fn write_void<S: Writeable>(this_arg: *const c_void) -> CVec_u8Z {
+	serialize_obj(obj::<S>(this_arg))
+}
+extern "C" fn cloned<S: rustSign + 'static>(new_obj: &mut Sign) {
+	new_obj.this_arg = Box::into_raw(Box::new(obj::<S>(new_obj.this_arg).clone())) as *mut c_void;
+	new_obj.free = Some(free_void::<S>);
+	new_obj.BaseSign.this_arg = new_obj.this_arg;
+	new_obj.BaseSign.free = None;
+}
+
+extern "C" fn get_node_secret<K: rustKeysInterface>(this_arg: *const c_void) -> SecretKey {
+	SecretKey::from_rust(obj::<K>(this_arg).get_node_secret())
+}
+extern "C" fn get_destination_script<K: rustKeysInterface>(this_arg: *const c_void) -> CVec_u8Z {
+	obj::<K>(this_arg).get_destination_script().into_bytes().into()
+}
+extern "C" fn get_shutdown_scriptpubkey<K: rustKeysInterface>(this_arg: *const c_void) -> ShutdownScript {
+	ShutdownScript { inner: ObjOps::heap_alloc(obj::<K>(this_arg).get_shutdown_scriptpubkey()), is_owned: true }
+}
+extern "C" fn get_channel_signer<K: rustKeysInterface>(this_arg: *const c_void, inbound: bool, channel_value_satoshis: u64) -> Sign where K::Signer: 'static {
+	sign_from_native(obj::<K>(this_arg).get_channel_signer(inbound, channel_value_satoshis))
+}
+extern "C" fn get_secure_random_bytes<K: rustKeysInterface>(this_arg: *const c_void) -> ThirtyTwoBytes {
+	ThirtyTwoBytes { data: obj::<K>(this_arg).get_secure_random_bytes() }
+}
+extern "C" fn read_chan_signer<K: rustKeysInterface>(this_arg: *const c_void, reader: u8slice) -> CResult_SignDecodeErrorZ where K::Signer: 'static {
+	match obj::<K>(this_arg).read_chan_signer(reader.to_slice()) {
+		Ok(signer) => CResultTempl::ok(sign_from_native(signer)).into(),
+		Err(e) => CResultTempl::err(DecodeError { inner: ObjOps::heap_alloc(e), is_owned: true }).into(),
+	}
+}
+extern "C" fn sign_invoice<K: rustKeysInterface>(this_arg: *const c_void, mut invoice_preimage: CVec_u8Z) -> CResult_RecoverableSignatureNoneZ {
+	match obj::<K>(this_arg).sign_invoice(invoice_preimage.into_rust()) {
+		Ok(sig) => CResultTempl::ok(RecoverableSignature::from_rust(&sig)).into(),
+		Err(()) => CResultTempl::err(()).into(),
+	}
+}
+extern "C" fn get_inbound_payment_key_material<K: rustKeysInterface>(this_arg: *const c_void) -> ThirtyTwoBytes {
+	ThirtyTwoBytes { data: obj::<K>(this_arg).get_inbound_payment_key_material().0 }
+}
@@ -0,0 +1,744 @@
+//! A `KeysInterface` wrapping another, such as a `KeysManager`, whose signers track each channel's
+//! state and refuse to sign anything which could lose the channel's funds if the process asking
+//! for signatures has been compromised.
+//!
+//! `PolicyKeysInterface_new` is most useful where the keys live apart from the node, eg wrapping
+//! the `KeysInterface` passed to `RemoteSignerServer_new`, but may also be used in-process. The
+//! [`SignerPolicy`] it enforces consists of the rules in [`SignerPolicyRule`], each of which may be
+//! disabled. A request breaking an enabled rule is logged at the `Error` level and refused, which
+//! LDK treats as a failure to sign and may force-close the channel for.
+//!
+//! The state of each channel is shared by every copy of its signer (LDK keeps separate copies in
+//! the `ChannelManager` and the `ChannelMonitor`), kept in memory only while a copy exists, and
+//! written to a [`KVStore`] under the `signer_policy` namespace, keyed by the hex-encoded
+//! `channel_keys_id`, before any signature depending on it is returned. If the state cannot be
+//! written, signing fails. If it cannot be written while releasing a commitment secret, which LDK
+//! cannot fail, an invalid secret is returned instead, so the counterparty will close the channel
+//! using a commitment transaction we can still sign.
+//!
+//! Transactions claiming channel outputs may only pay to scripts returned by the wrapped
+//! `KeysInterface`'s `get_destination_script`, and closing transactions may only pay our balance
+//! to a script returned by its `get_shutdown_scriptpubkey`, or to scripts added to the policy.
+//! Both are called once on startup, so with a `KeysManager`, which always returns the same
+//! scripts, channels opened before a restart are covered. Other implementations must ensure any
+//! script handed out before a restart is added to the policy.
+//!
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
+use std::fmt;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+use bitcoin::blockdata::script::Script;
+use bitcoin::blockdata::transaction::Transaction as BitcoinTransaction;
+use bitcoin::hashes::hex::ToHex;
+use bitcoin::secp256k1::{All, Secp256k1, Signature as SecpSignature};
+use bitcoin::secp256k1::key::{PublicKey as SecpPublicKey, SecretKey as SecpSecretKey};
+use bitcoin::secp256k1::recovery::RecoverableSignature as SecpRecoverableSignature;
+
+use lightning::chain::keysinterface::{BaseSign as rustBaseSign, KeyMaterial, KeysInterface as rustKeysInterface, Sign as rustSign};
+use lightning::ln::chan_utils::{ChannelPublicKeys as nativeChannelPublicKeys, ChannelTransactionParameters as nativeChannelTransactionParameters, ClosingTransaction as nativeClosingTransaction, CommitmentTransaction as nativeCommitmentTransaction, HolderCommitmentTransaction as nativeHolderCommitmentTransaction, HTLCOutputInCommitment as nativeHTLCOutputInCommitment};
+use lightning::ln::msgs::{DecodeError, UnsignedChannelAnnouncement as nativeUnsignedChannelAnnouncement};
+use lightning::ln::script::ShutdownScript as nativeShutdownScript;
+use lightning::util::logger::{Level, Logger as _};
+use lightning::util::ser::{Readable, Writeable, Writer};
+
+use crate::c_types::*;
+use crate::c_types::derived::CVec_u8Z;
+use crate::extensions::kv_store::KVStore;
+use crate::extensions::signer_adapter::keys_interface_from_native;
+use crate::lightning::chain::keysinterface::{KeysInterface, Sign};
+use crate::lightning::util::logger::Logger;
+
+/// The namespace under which the state of each channel is stored, keyed by its channel_keys_id.
+const STATE_NAMESPACE: &str = "signer_policy";
+
+const SERIALIZATION_VERSION: u8 = 1;
+
+/// Written ahead of a [`PolicySigner`]'s serialization to tell it apart from one written by the
+/// inner `KeysInterface` directly.
+const POLICY_SIGNER_MAGIC: [u8; 8] = *b"LDKSPOL1";
+
+/// Returned by `release_commitment_secret` when the secret may not be released. It is not a valid
+/// secret key, so the counterparty rejects it rather than mistaking it for a revocation.
+const REFUSED_COMMITMENT_SECRET: [u8; 32] = [0; 32];
+
+/// A rule enforced by a [`SignerPolicy`].
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SignerPolicyRule {
+	/// Holder commitment transactions whose per-commitment secret has been released may not be
+	/// validated or signed, as our counterparty could claim the entire channel balance if one was
+	/// broadcast.
+	RevokedHolderCommitment,
+	/// Commitment transactions may only be validated or signed if their outputs, including HTLC
+	/// outputs, are exactly those derived from the channel's keys, and transactions claiming
+	/// channel outputs may only pay to known destination scripts.
+	UnknownDestination,
+	/// Closing transactions may only pay our balance to a known shutdown script, which may not
+	/// change once a closing transaction has been signed for the channel.
+	ShutdownScriptMismatch,
+	/// Commitment transactions may not be validated or signed, and counterparty revocations not
+	/// accepted, for a commitment number older than one already seen for the same party.
+	CommitmentNumberRegression,
+}
+
+pub(crate) struct nativeSignerPolicy {
+	disabled_rules: Vec<SignerPolicyRule>,
+	destination_scripts: Vec<Script>,
+	shutdown_scripts: Vec<Script>,
+}
+
+impl nativeSignerPolicy {
+	fn enforces(&self, rule: SignerPolicyRule) -> bool {
+		!self.disabled_rules.contains(&rule)
+	}
+}
+
+/// The rules enforced by the signers of a `PolicyKeysInterface_new`, and the scripts they may
+/// pay to in addition to those of the wrapped `KeysInterface`.
+#[must_use]
+#[repr(C)]
+pub struct SignerPolicy {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeSignerPolicy,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for SignerPolicy {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeSignerPolicy>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the SignerPolicy, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn SignerPolicy_free(this_obj: SignerPolicy) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn SignerPolicy_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeSignerPolicy); }
+}
+#[allow(unused)]
+impl SignerPolicy {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeSignerPolicy {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeSignerPolicy {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeSignerPolicy {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a new SignerPolicy enforcing every rule, without any additional scripts.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn SignerPolicy_new() -> SignerPolicy {
+	SignerPolicy { inner: ObjOps::heap_alloc(nativeSignerPolicy {
+		disabled_rules: Vec::new(), destination_scripts: Vec::new(), shutdown_scripts: Vec::new(),
+	}), is_owned: true }
+}
+
+/// Stops enforcing the given rule. Requests breaking it are still logged, at the `Warn` level.
+#[no_mangle]
+pub extern "C" fn SignerPolicy_disable_rule(this_arg: &mut SignerPolicy, mut rule: SignerPolicyRule) {
+	let policy = this_arg.get_native_mut_ref();
+	if policy.enforces(rule) { policy.disabled_rules.push(rule); }
+}
+
+/// Allows transactions claiming channel outputs to pay to the given script, eg one handed out by
+/// a previous `KeysInterface` before a restart.
+#[no_mangle]
+pub extern "C" fn SignerPolicy_add_destination_script(this_arg: &mut SignerPolicy, mut script: CVec_u8Z) {
+	this_arg.get_native_mut_ref().destination_scripts.push(Script::from(script.into_rust()));
+}
+
+/// Allows closing transactions to pay our balance to the given script, eg one handed out by a
+/// previous `KeysInterface` before a restart.
+#[no_mangle]
+pub extern "C" fn SignerPolicy_add_shutdown_script(this_arg: &mut SignerPolicy, mut script: CVec_u8Z) {
+	this_arg.get_native_mut_ref().shutdown_scripts.push(Script::from(script.into_rust()));
+}
+
+/// What a channel's signers have seen so far.
+#[derive(Default)]
+struct ChannelState {
+	channel_parameters: Option<nativeChannelTransactionParameters>,
+	/// The lowest holder commitment number whose per-commitment secret has been released.
+	/// Commitment numbers count down, so every commitment numbered at or above it is revoked.
+	revoked_holder_commitment: Option<u64>,
+	/// The lowest holder commitment number validated.
+	latest_holder_commitment: Option<u64>,
+	/// The lowest counterparty commitment number signed.
+	latest_counterparty_commitment: Option<u64>,
+	/// The lowest counterparty commitment number revoked.
+	latest_counterparty_revocation: Option<u64>,
+	/// The script our balance was paid to in the first closing transaction signed.
+	closing_script: Option<Script>,
+}
+
+impl Writeable for ChannelState {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), std::io::Error> {
+		SERIALIZATION_VERSION.write(w)?;
+		self.channel_parameters.write(w)?;
+		self.revoked_holder_commitment.write(w)?;
+		self.latest_holder_commitment.write(w)?;
+		self.latest_counterparty_commitment.write(w)?;
+		self.latest_counterparty_revocation.write(w)?;
+		self.closing_script.write(w)
+	}
+}
+
+impl Readable for ChannelState {
+	fn read<R: std::io::Read>(r: &mut R) -> Result<Self, DecodeError> {
+		let ver: u8 = Readable::read(r)?;
+		if ver > SERIALIZATION_VERSION { return Err(DecodeError::UnknownVersion); }
+		Ok(Self {
+			channel_parameters: Readable::read(r)?,
+			revoked_holder_commitment: Readable::read(r)?,
+			latest_holder_commitment: Readable::read(r)?,
+			latest_counterparty_commitment: Readable::read(r)?,
+			latest_counterparty_revocation: Readable::read(r)?,
+			closing_script: Readable::read(r)?,
+		})
+	}
+}
+
+/// Lowers `latest` to `number` if it is lower, returning whether it changed.
+fn advance(latest: &mut Option<u64>, number: u64) -> bool {
+	if latest.map_or(true, |latest| number < latest) {
+		*latest = Some(number);
+		true
+	} else { false }
+}
+
+/// The state shared by a `PolicyKeysInterface` and all of its signers.
+struct PolicyContext {
+	policy: nativeSignerPolicy,
+	store: KVStore,
+	logger: Logger,
+	destination_scripts: Mutex<HashSet<Script>>,
+	shutdown_scripts: Mutex<HashSet<Script>>,
+	/// The state of each channel with a live signer. Entries are dropped along with the channel's
+	/// last signer, once its `ChannelManager` and `ChannelMonitor` no longer need it.
+	channels: Mutex<HashMap<[u8; 32], Weak<Mutex<ChannelState>>>>,
+}
+
+impl PolicyContext {
+	/// Returns the state of the given channel, reading it from the store if no signer for the
+	/// channel currently exists.
+	fn channel_state(&self, channel_keys_id: [u8; 32]) -> Result<Arc<Mutex<ChannelState>>, DecodeError> {
+		let mut channels = self.channels.lock().unwrap();
+		if let Some(state) = channels.get(&channel_keys_id).and_then(Weak::upgrade) {
+			return Ok(state);
+		}
+		let state = match self.store.read(STATE_NAMESPACE, &channel_keys_id.to_hex()) {
+			Ok(data) => Readable::read(&mut Cursor::new(data))?,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => ChannelState::default(),
+			Err(e) => return Err(DecodeError::Io(e.kind())),
+		};
+		let state = Arc::new(Mutex::new(state));
+		channels.retain(|_, state| state.strong_count() != 0);
+		channels.insert(channel_keys_id, Arc::downgrade(&state));
+		Ok(state)
+	}
+}
+
+#[derive(Clone)]
+struct PolicySigner {
+	inner: Sign,
+	channel_keys_id: [u8; 32],
+	/// The channel's state, or None if it could not be read when the signer was created, in which
+	/// case we refuse to sign anything for the channel.
+	state: Option<Arc<Mutex<ChannelState>>>,
+	ctx: Arc<PolicyContext>,
+}
+
+impl PolicySigner {
+	fn state(&self) -> Result<MutexGuard<ChannelState>, ()> {
+		match &self.state {
+			Some(state) => Ok(state.lock().unwrap()),
+			None => {
+				log_record!(self.ctx.logger, Level::Error, "Refusing to sign for channel {} as its signer state could not be read", self.channel_keys_id.to_hex());
+				Err(())
+			},
+		}
+	}
+
+	/// Logs a request breaking `rule`, returning an error if the rule is enforced.
+	fn violation(&self, rule: SignerPolicyRule, args: fmt::Arguments) -> Result<(), ()> {
+		if self.ctx.policy.enforces(rule) {
+			log_record!(self.ctx.logger, Level::Error, "Refusing to sign for channel {} ({:?}): {}", self.channel_keys_id.to_hex(), rule, args);
+			Err(())
+		} else {
+			log_record!(self.ctx.logger, Level::Warn, "Signing for channel {} despite disabled rule {:?}: {}", self.channel_keys_id.to_hex(), rule, args);
+			Ok(())
+		}
+	}
+
+	fn persist(&self, state: &ChannelState) -> Result<(), ()> {
+		self.ctx.store.write(STATE_NAMESPACE, &self.channel_keys_id.to_hex(), &state.encode()).map_err(|e| {
+			log_record!(self.ctx.logger, Level::Error, "Failed to persist the signer state of channel {}: {}", self.channel_keys_id.to_hex(), e);
+		})
+	}
+
+	fn check_not_revoked(&self, state: &ChannelState, commitment_number: u64) -> Result<(), ()> {
+		match state.revoked_holder_commitment {
+			Some(revoked) if commitment_number >= revoked =>
+				self.violation(SignerPolicyRule::RevokedHolderCommitment, format_args!("holder commitment {} has been revoked", commitment_number)),
+			_ => Ok(()),
+		}
+	}
+
+	fn check_order(&self, latest: Option<u64>, commitment_number: u64, what: &str) -> Result<(), ()> {
+		match latest {
+			Some(latest) if commitment_number > latest =>
+				self.violation(SignerPolicyRule::CommitmentNumberRegression, format_args!("{} {} is older than {}", what, commitment_number, latest)),
+			_ => Ok(()),
+		}
+	}
+
+	/// Checks that the outputs of a commitment transaction are those derived from the channel's
+	/// keys. Without channel parameters the inner signer cannot sign either, so this is skipped.
+	fn check_commitment_outputs(&self, state: &ChannelState, commitment_tx: &nativeCommitmentTransaction, holder_broadcast: bool) -> Result<(), ()> {
+		let params = match &state.channel_parameters { Some(params) => params, None => return Ok(()) };
+		let directed = if holder_broadcast { params.as_holder_broadcastable() } else { params.as_counterparty_broadcastable() };
+		if commitment_tx.verify(&directed, directed.broadcaster_pubkeys(), directed.countersignatory_pubkeys(), secp256k1::SECP256K1).is_err() {
+			return self.violation(SignerPolicyRule::UnknownDestination,
+				format_args!("commitment transaction {} does not match the channel's keys", commitment_tx.trust().txid()));
+		}
+		Ok(())
+	}
+
+	fn check_destinations(&self, tx: &BitcoinTransaction) -> Result<(), ()> {
+		let destinations = self.ctx.destination_scripts.lock().unwrap();
+		for output in tx.output.iter() {
+			if !destinations.contains(&output.script_pubkey) {
+				self.violation(SignerPolicyRule::UnknownDestination,
+					format_args!("transaction {} pays to unknown script {}", tx.txid(), output.script_pubkey.as_bytes().to_hex()))?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl rustBaseSign for PolicySigner {
+	fn get_per_commitment_point(&self, idx: u64, secp_ctx: &Secp256k1<All>) -> SecpPublicKey {
+		self.inner.get_per_commitment_point(idx, secp_ctx)
+	}
+	fn release_commitment_secret(&self, idx: u64) -> [u8; 32] {
+		// We must not release a secret without recording it, but cannot return an error either.
+		let mut state = match self.state() { Ok(state) => state, Err(()) => return REFUSED_COMMITMENT_SECRET };
+		let revoked_holder_commitment = state.revoked_holder_commitment;
+		if advance(&mut state.revoked_holder_commitment, idx) && self.persist(&state).is_err() {
+			// Leave the commitment unrevoked so we can still sign it if the channel is closed.
+			state.revoked_holder_commitment = revoked_holder_commitment;
+			log_record!(self.ctx.logger, Level::Error, "Refusing to release the secret of holder commitment {} for channel {}", idx, self.channel_keys_id.to_hex());
+			return REFUSED_COMMITMENT_SECRET;
+		}
+		self.inner.release_commitment_secret(idx)
+	}
+	fn validate_holder_commitment(&self, holder_tx: &nativeHolderCommitmentTransaction) -> Result<(), ()> {
+		let mut state = self.state()?;
+		let commitment_number = holder_tx.commitment_number();
+		self.check_not_revoked(&state, commitment_number)?;
+		self.check_order(state.latest_holder_commitment, commitment_number, "holder commitment")?;
+		self.check_commitment_outputs(&state, holder_tx, true)?;
+		self.inner.validate_holder_commitment(holder_tx)?;
+		if advance(&mut state.latest_holder_commitment, commitment_number) {
+			self.persist(&state)?;
+		}
+		Ok(())
+	}
+	fn pubkeys(&self) -> &nativeChannelPublicKeys {
+		self.inner.pubkeys()
+	}
+	fn channel_keys_id(&self) -> [u8; 32] {
+		self.channel_keys_id
+	}
+	fn sign_counterparty_commitment(&self, commitment_tx: &nativeCommitmentTransaction, secp_ctx: &Secp256k1<All>) -> Result<(SecpSignature, Vec<SecpSignature>), ()> {
+		let mut state = self.state()?;
+		let commitment_number = commitment_tx.commitment_number();
+		self.check_order(state.latest_counterparty_commitment, commitment_number, "counterparty commitment")?;
+		self.check_commitment_outputs(&state, commitment_tx, false)?;
+		let sigs = self.inner.sign_counterparty_commitment(commitment_tx, secp_ctx)?;
+		if advance(&mut state.latest_counterparty_commitment, commitment_number) {
+			self.persist(&state)?;
+		}
+		Ok(sigs)
+	}
+	fn validate_counterparty_revocation(&self, idx: u64, secret: &SecpSecretKey) -> Result<(), ()> {
+		let mut state = self.state()?;
+		self.check_order(state.latest_counterparty_revocation, idx, "counterparty revocation")?;
+		self.inner.validate_counterparty_revocation(idx, secret)?;
+		if advance(&mut state.latest_counterparty_revocation, idx) {
+			self.persist(&state)?;
+		}
+		Ok(())
+	}
+	fn sign_holder_commitment_and_htlcs(&self, commitment_tx: &nativeHolderCommitmentTransaction, secp_ctx: &Secp256k1<All>) -> Result<(SecpSignature, Vec<SecpSignature>), ()> {
+		let state = self.state()?;
+		self.check_not_revoked(&state, commitment_tx.commitment_number())?;
+		self.check_commitment_outputs(&state, commitment_tx, true)?;
+		self.inner.sign_holder_commitment_and_htlcs(commitment_tx, secp_ctx)
+	}
+	fn sign_justice_revoked_output(&self, justice_tx: &BitcoinTransaction, input: usize, amount: u64, per_commitment_key: &SecpSecretKey, secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.check_destinations(justice_tx)?;
+		self.inner.sign_justice_revoked_output(justice_tx, input, amount, per_commitment_key, secp_ctx)
+	}
+	fn sign_justice_revoked_htlc(&self, justice_tx: &BitcoinTransaction, input: usize, amount: u64, per_commitment_key: &SecpSecretKey, htlc: &nativeHTLCOutputInCommitment, secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.check_destinations(justice_tx)?;
+		self.inner.sign_justice_revoked_htlc(justice_tx, input, amount, per_commitment_key, htlc, secp_ctx)
+	}
+	fn sign_counterparty_htlc_transaction(&self, htlc_tx: &BitcoinTransaction, input: usize, amount: u64, per_commitment_point: &SecpPublicKey, htlc: &nativeHTLCOutputInCommitment, secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.check_destinations(htlc_tx)?;
+		self.inner.sign_counterparty_htlc_transaction(htlc_tx, input, amount, per_commitment_point, htlc, secp_ctx)
+	}
+	fn sign_closing_transaction(&self, closing_tx: &nativeClosingTransaction, secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		let mut state = self.state()?;
+		let funding_outpoint = state.channel_parameters.as_ref().and_then(|params| params.funding_outpoint);
+		if let Some(funding_outpoint) = funding_outpoint {
+			if closing_tx.verify(funding_outpoint.into_bitcoin_outpoint()).is_err() {
+				self.violation(SignerPolicyRule::UnknownDestination, format_args!("closing transaction does not spend the funding output"))?;
+			}
+		}
+		let script = closing_tx.to_holder_script();
+		if closing_tx.to_holder_value_sat() != 0 {
+			if !self.ctx.shutdown_scripts.lock().unwrap().contains(script) {
+				self.violation(SignerPolicyRule::ShutdownScriptMismatch, format_args!("closing transaction pays to unknown script {}", script.as_bytes().to_hex()))?;
+			}
+			if let Some(closing_script) = &state.closing_script {
+				if closing_script != script {
+					self.violation(SignerPolicyRule::ShutdownScriptMismatch, format_args!("closing transaction pays to {} rather than {}", script.as_bytes().to_hex(), closing_script.as_bytes().to_hex()))?;
+				}
+			}
+		}
+		let sig = self.inner.sign_closing_transaction(closing_tx, secp_ctx)?;
+		if closing_tx.to_holder_value_sat() != 0 && state.closing_script.is_none() {
+			state.closing_script = Some(script.clone());
+			self.persist(&state)?;
+		}
+		Ok(sig)
+	}
+	fn sign_channel_announcement(&self, msg: &nativeUnsignedChannelAnnouncement, secp_ctx: &Secp256k1<All>) -> Result<SecpSignature, ()> {
+		self.inner.sign_channel_announcement(msg, secp_ctx)
+	}
+	fn ready_channel(&mut self, channel_parameters: &nativeChannelTransactionParameters) {
+		self.inner.ready_channel(channel_parameters);
+		let mut state = match &self.state { Some(state) => state.lock().unwrap(), None => return };
+		if state.channel_parameters.is_none() {
+			state.channel_parameters = Some(channel_parameters.clone());
+			// Without the parameters we can only skip checking commitment outputs, so don't fail.
+			let _ = self.persist(&state);
+		}
+	}
+}
+
+impl Writeable for PolicySigner {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), std::io::Error> {
+		w.write_all(&POLICY_SIGNER_MAGIC)?;
+		SERIALIZATION_VERSION.write(w)?;
+		self.inner.encode().write(w)
+	}
+}
+
+impl rustSign for PolicySigner {}
+
+/// Returns the inner signer's serialization given that of a [`PolicySigner`], or `data` itself if
+/// it was written by the inner `KeysInterface` directly, i.e. before the channel was put under a
+/// policy.
+///
+/// A `PolicySigner` is serialized as [`POLICY_SIGNER_MAGIC`] and a version byte followed by the
+/// length-prefixed inner serialization. A `KeysManager` signer starts with a version byte of 1, so
+/// it can never be mistaken for one.
+fn inner_signer_data(data: &[u8]) -> Result<Vec<u8>, DecodeError> {
+	if !data.starts_with(&POLICY_SIGNER_MAGIC) { return Ok(data.to_vec()); }
+	let mut reader = Cursor::new(&data[POLICY_SIGNER_MAGIC.len()..]);
+	let ver: u8 = Readable::read(&mut reader)?;
+	if ver > SERIALIZATION_VERSION { return Err(DecodeError::UnknownVersion); }
+	Readable::read(&mut reader)
+}
+
+struct PolicyKeysInterface {
+	inner: KeysInterface,
+	ctx: Arc<PolicyContext>,
+}
+
+impl PolicyKeysInterface {
+	fn new(inner: KeysInterface, policy: nativeSignerPolicy, store: KVStore, logger: Logger) -> Self {
+		let mut destination_scripts: HashSet<Script> = policy.destination_scripts.iter().cloned().collect();
+		destination_scripts.insert(inner.get_destination_script());
+		let mut shutdown_scripts: HashSet<Script> = policy.shutdown_scripts.iter().cloned().collect();
+		shutdown_scripts.insert(inner.get_shutdown_scriptpubkey().into_inner());
+		let ctx = PolicyContext {
+			policy, store, logger,
+			destination_scripts: Mutex::new(destination_scripts),
+			shutdown_scripts: Mutex::new(shutdown_scripts),
+			channels: Mutex::new(HashMap::new()),
+		};
+		Self { inner, ctx: Arc::new(ctx) }
+	}
+}
+
+impl rustKeysInterface for PolicyKeysInterface {
+	type Signer = PolicySigner;
+
+	fn get_node_secret(&self) -> SecpSecretKey {
+		self.inner.get_node_secret()
+	}
+	fn get_destination_script(&self) -> Script {
+		let script = self.inner.get_destination_script();
+		self.ctx.destination_scripts.lock().unwrap().insert(script.clone());
+		script
+	}
+	fn get_shutdown_scriptpubkey(&self) -> nativeShutdownScript {
+		let script = self.inner.get_shutdown_scriptpubkey();
+		self.ctx.shutdown_scripts.lock().unwrap().insert(script.clone().into_inner());
+		script
+	}
+	fn get_channel_signer(&self, inbound: bool, channel_value_satoshis: u64) -> PolicySigner {
+		let inner = self.inner.get_channel_signer(inbound, channel_value_satoshis);
+		let channel_keys_id = inner.channel_keys_id();
+		let state = match self.ctx.channel_state(channel_keys_id) {
+			Ok(state) => Some(state),
+			Err(e) => {
+				log_record!(self.ctx.logger, Level::Error, "Failed to read the signer state of channel {}: {:?}", channel_keys_id.to_hex(), e);
+				None
+			},
+		};
+		PolicySigner { inner, channel_keys_id, state, ctx: Arc::clone(&self.ctx) }
+	}
+	fn get_secure_random_bytes(&self) -> [u8; 32] {
+		self.inner.get_secure_random_bytes()
+	}
+	fn read_chan_signer(&self, reader: &[u8]) -> Result<PolicySigner, DecodeError> {
+		let inner = self.inner.read_chan_signer(&inner_signer_data(reader)?)?;
+		let channel_keys_id = inner.channel_keys_id();
+		let state = Some(self.ctx.channel_state(channel_keys_id)?);
+		Ok(PolicySigner { inner, channel_keys_id, state, ctx: Arc::clone(&self.ctx) })
+	}
+	fn sign_invoice(&self, invoice_preimage: Vec<u8>) -> Result<SecpRecoverableSignature, ()> {
+		self.inner.sign_invoice(invoice_preimage)
+	}
+	fn get_inbound_payment_key_material(&self) -> KeyMaterial {
+		self.inner.get_inbound_payment_key_material()
+	}
+}
+
+/// Creates a `KeysInterface` whose signers enforce `policy` on top of those of `inner`, storing
+/// the state of each channel in `store` and logging refused requests to `logger`.
+///
+/// Channel signers serialized by LDK contain those of `inner`, and must be read back with a
+/// `KeysInterface` created by this function, using the same `store`. Signers serialized by a
+/// `KeysManager` before it was wrapped (e.g. in existing `ChannelMonitor`s) can be read back as
+/// well, so existing channels can be moved under a policy, with their state starting empty.
+///
+/// If a new channel's state cannot be read from `store`, its signer refuses to sign anything or
+/// release any commitment secret.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn PolicyKeysInterface_new(mut inner: KeysInterface, mut policy: SignerPolicy, mut store: KVStore, mut logger: Logger) -> KeysInterface {
+	let policy = *unsafe { Box::from_raw(policy.take_inner()) };
+	keys_interface_from_native(PolicyKeysInterface::new(inner, policy, store, logger))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::extensions::test_utils::{channel_parameters, closing_transaction, commitment_transaction, funding_outpoint, holder_commitment, keys_manager, logger, MemoryStore, policy_keys};
+
+	const INITIAL_COMMITMENT_NUMBER: u64 = (1 << 48) - 1;
+
+	/// Returns a readied signer along with its channel's parameters.
+	fn signer(keys: &KeysInterface) -> (Sign, nativeChannelTransactionParameters) {
+		let mut signer = keys.get_channel_signer(false, 1_000_000);
+		let counterparty_signer = keys_manager(2).get_channel_signer(true, 1_000_000);
+		let params = channel_parameters(signer.pubkeys(), counterparty_signer.pubkeys(), 144);
+		signer.ready_channel(&params);
+		(signer, params)
+	}
+
+	fn holder_tx(signer: &Sign, params: &nativeChannelTransactionParameters, commitment_number: u64) -> nativeHolderCommitmentTransaction {
+		holder_commitment(params, commitment_number, &signer.get_per_commitment_point(commitment_number, secp256k1::SECP256K1))
+	}
+
+	fn counterparty_tx(params: &nativeChannelTransactionParameters, commitment_number: u64) -> nativeCommitmentTransaction {
+		let per_commitment_point = SecpPublicKey::from_secret_key(secp256k1::SECP256K1, &SecpSecretKey::from_slice(&[5; 32]).unwrap());
+		commitment_transaction(params, false, commitment_number, &per_commitment_point)
+	}
+
+	#[test]
+	fn refuses_revoked_holder_commitment() {
+		let store = MemoryStore::new();
+		let keys = policy_keys(SignerPolicy_new(), &store);
+		let (signer, params) = signer(&keys);
+		let secp_ctx = Secp256k1::new();
+
+		let current_tx = holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER);
+		assert!(signer.validate_holder_commitment(&current_tx).is_ok());
+		assert!(signer.sign_holder_commitment_and_htlcs(&current_tx, &secp_ctx).is_ok());
+
+		let next_tx = holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER - 1);
+		assert!(signer.validate_holder_commitment(&next_tx).is_ok());
+		signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER);
+		assert!(signer.sign_holder_commitment_and_htlcs(&current_tx, &secp_ctx).is_err());
+		assert!(signer.sign_holder_commitment_and_htlcs(&next_tx, &secp_ctx).is_ok());
+
+		// The revocation is shared with other copies of the signer, including after a restart.
+		let copy = keys.read_chan_signer(&signer.encode()).unwrap();
+		assert!(copy.sign_holder_commitment_and_htlcs(&current_tx, &secp_ctx).is_err());
+		let restarted_keys = policy_keys(SignerPolicy_new(), &store);
+		let restarted = restarted_keys.read_chan_signer(&signer.encode()).unwrap();
+		assert!(restarted.sign_holder_commitment_and_htlcs(&current_tx, &secp_ctx).is_err());
+
+		let mut policy = SignerPolicy_new();
+		SignerPolicy_disable_rule(&mut policy, SignerPolicyRule::RevokedHolderCommitment);
+		let keys = policy_keys(policy, &MemoryStore::new());
+		let (signer, params) = self::signer(&keys);
+		let current_tx = holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER);
+		signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER);
+		assert!(signer.validate_holder_commitment(&current_tx).is_ok());
+		assert!(signer.sign_holder_commitment_and_htlcs(&current_tx, &secp_ctx).is_ok());
+	}
+
+	#[test]
+	fn refuses_commitment_number_regression() {
+		let store = MemoryStore::new();
+		let keys = policy_keys(SignerPolicy_new(), &store);
+		let (signer, params) = signer(&keys);
+		let secp_ctx = Secp256k1::new();
+
+		assert!(signer.validate_holder_commitment(&holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER - 1)).is_ok());
+		assert!(signer.validate_holder_commitment(&holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER)).is_err());
+		assert!(signer.validate_holder_commitment(&holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER - 1)).is_ok());
+
+		assert!(signer.sign_counterparty_commitment(&counterparty_tx(&params, INITIAL_COMMITMENT_NUMBER - 1), &secp_ctx).is_ok());
+		assert!(signer.sign_counterparty_commitment(&counterparty_tx(&params, INITIAL_COMMITMENT_NUMBER), &secp_ctx).is_err());
+		assert!(signer.sign_counterparty_commitment(&counterparty_tx(&params, INITIAL_COMMITMENT_NUMBER - 2), &secp_ctx).is_ok());
+
+		let secret = SecpSecretKey::from_slice(&[42; 32]).unwrap();
+		assert!(signer.validate_counterparty_revocation(INITIAL_COMMITMENT_NUMBER - 1, &secret).is_ok());
+		assert!(signer.validate_counterparty_revocation(INITIAL_COMMITMENT_NUMBER, &secret).is_err());
+		assert!(signer.validate_counterparty_revocation(INITIAL_COMMITMENT_NUMBER - 2, &secret).is_ok());
+
+		let restarted_keys = policy_keys(SignerPolicy_new(), &store);
+		let restarted = restarted_keys.read_chan_signer(&signer.encode()).unwrap();
+		assert!(restarted.validate_counterparty_revocation(INITIAL_COMMITMENT_NUMBER - 1, &secret).is_err());
+		assert!(restarted.sign_counterparty_commitment(&counterparty_tx(&params, INITIAL_COMMITMENT_NUMBER - 1), &secp_ctx).is_err());
+	}
+
+	#[test]
+	fn refuses_commitment_with_unknown_outputs() {
+		let keys = policy_keys(SignerPolicy_new(), &MemoryStore::new());
+		let (signer, params) = signer(&keys);
+		let secp_ctx = Secp256k1::new();
+
+		let mut other_params = params.clone();
+		other_params.holder_selected_contest_delay = 2016;
+		other_params.counterparty_parameters.as_mut().unwrap().selected_contest_delay = 2016;
+		let other_tx = holder_tx(&signer, &other_params, INITIAL_COMMITMENT_NUMBER);
+		assert!(signer.validate_holder_commitment(&other_tx).is_err());
+		assert!(signer.sign_holder_commitment_and_htlcs(&other_tx, &secp_ctx).is_err());
+		assert!(signer.sign_counterparty_commitment(&counterparty_tx(&other_params, INITIAL_COMMITMENT_NUMBER), &secp_ctx).is_err());
+	}
+
+	#[test]
+	fn refuses_wrong_shutdown_script() {
+		let mut policy = SignerPolicy_new();
+		let extra_script = Script::from(vec![0; 22]);
+		SignerPolicy_add_shutdown_script(&mut policy, extra_script.to_bytes().into());
+		let keys = policy_keys(policy, &MemoryStore::new());
+		let (signer, _) = signer(&keys);
+		let secp_ctx = Secp256k1::new();
+		let shutdown_script = keys.get_shutdown_scriptpubkey().into_inner();
+
+		assert!(signer.sign_closing_transaction(&closing_transaction(Script::from(vec![1; 22]), Script::new()), &secp_ctx).is_err());
+		let other_funding = bitcoin::blockdata::transaction::OutPoint { txid: funding_outpoint().txid, index: 0 };
+		let other_funding_tx = nativeClosingTransaction::new(500_000, 400_000, shutdown_script.clone(), Script::new(), other_funding);
+		assert!(signer.sign_closing_transaction(&other_funding_tx, &secp_ctx).is_err());
+
+		let (extra_signer, _) = self::signer(&keys);
+		assert!(extra_signer.sign_closing_transaction(&closing_transaction(extra_script.clone(), Script::new()), &secp_ctx).is_ok());
+
+		assert!(signer.sign_closing_transaction(&closing_transaction(shutdown_script.clone(), Script::new()), &secp_ctx).is_ok());
+		// Once a closing transaction was signed, our balance may not go elsewhere, even to a script
+		// in the policy.
+		assert!(signer.sign_closing_transaction(&closing_transaction(extra_script, Script::new()), &secp_ctx).is_err());
+		assert!(signer.sign_closing_transaction(&closing_transaction(shutdown_script, Script::new()), &secp_ctx).is_ok());
+	}
+
+	#[test]
+	fn refuses_to_release_unpersisted_secret() {
+		let store = MemoryStore::new();
+		let keys = policy_keys(SignerPolicy_new(), &store);
+		let (signer, params) = signer(&keys);
+		let secp_ctx = Secp256k1::new();
+		let current_tx = holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER);
+		assert!(signer.validate_holder_commitment(&holder_tx(&signer, &params, INITIAL_COMMITMENT_NUMBER - 1)).is_ok());
+
+		store.set_fail_writes(true);
+		assert_eq!(signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER), REFUSED_COMMITMENT_SECRET);
+		// The commitment was not revoked, so it may still be signed to close the channel.
+		assert!(signer.sign_holder_commitment_and_htlcs(&current_tx, &secp_ctx).is_ok());
+
+		store.set_fail_writes(false);
+		let secret = signer.release_commitment_secret(INITIAL_COMMITMENT_NUMBER);
+		assert_ne!(secret, REFUSED_COMMITMENT_SECRET);
+		assert!(SecpSecretKey::from_slice(&secret).is_ok());
+		assert!(signer.sign_holder_commitment_and_htlcs(&current_tx, &secp_ctx).is_err());
+	}
+
+	#[test]
+	fn reads_policy_and_inner_signers() {
+		let keys = policy_keys(SignerPolicy_new(), &MemoryStore::new());
+		let (signer, _) = signer(&keys);
+		let encoded = signer.encode();
+		assert!(encoded.starts_with(&POLICY_SIGNER_MAGIC));
+		assert_eq!(keys.read_chan_signer(&encoded).unwrap().channel_keys_id(), signer.channel_keys_id());
+
+		// Signers written before the channel was put under a policy are read as well.
+		let inner_signer = keys_manager(1).get_channel_signer(false, 1_000_000);
+		let read = keys.read_chan_signer(&inner_signer.encode()).unwrap();
+		assert_eq!(read.channel_keys_id(), inner_signer.channel_keys_id());
+
+		let mut unknown_version = encoded.clone();
+		unknown_version[POLICY_SIGNER_MAGIC.len()] = SERIALIZATION_VERSION + 1;
+		assert!(keys.read_chan_signer(&unknown_version).is_err());
+		assert!(keys.read_chan_signer(&encoded[..encoded.len() - 1]).is_err());
+	}
+
+	#[test]
+	fn forgets_channels_without_signers() {
+		let store = MemoryStore::new();
+		let inner = KeysInterface::from(keys_manager(1));
+		let keys = PolicyKeysInterface::new(inner, *unsafe { Box::from_raw(SignerPolicy_new().take_inner()) }, store.as_kv_store(), logger());
+		let live_channels = || keys.ctx.channels.lock().unwrap().values().filter(|state| state.strong_count() != 0).count();
+
+		let signer = keys.get_channel_signer(false, 1_000_000);
+		let copy = signer.clone();
+		assert_eq!(live_channels(), 1);
+		drop(signer);
+		assert_eq!(live_channels(), 1);
+		drop(copy);
+		assert_eq!(live_channels(), 0);
+
+		let _other = keys.get_channel_signer(false, 1_000_000);
+		assert_eq!(keys.ctx.channels.lock().unwrap().len(), 1);
+	}
+}
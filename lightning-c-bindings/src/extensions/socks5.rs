@@ -0,0 +1,476 @@
+//! Outbound peer connections through a SOCKS5 proxy, e.g. Tor.
+//!
+//! Destinations are always passed to the proxy by name (or literal IP), never resolved locally,
+//! so `.onion` addresses can be reached and no DNS requests leak around the proxy. Once the
+//! proxy has established the connection, the socket is handed to the `PeerManager` through the
+//! given [`LdkNet`] exactly as for direct connections.
+//!
+//! Tor isolates streams which use different SOCKS5 usernames and passwords onto separate
+//! circuits (with its default `IsolateSOCKSAuth` setting), so passing distinct credentials per
+//! connection prevents peers from being linked by circuit.
+//!
+//! [`LdkNet`]: crate::extensions::net::LdkNet
+
+use std::ffi::c_void;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use lightning::ln::msgs::NetAddress as RustNetAddress;
+
+use crate::c_types::*;
+use crate::extensions::net::*;
+use crate::lightning::ln::msgs::NetAddress;
+
+const SOCKS_VERSION: u8 = 5;
+const AUTH_NONE: u8 = 0;
+const AUTH_USERNAME_PASSWORD: u8 = 2;
+const AUTH_NO_ACCEPTABLE_METHOD: u8 = 0xff;
+const USERNAME_PASSWORD_VERSION: u8 = 1;
+const CMD_CONNECT: u8 = 1;
+const ATYP_IPV4: u8 = 1;
+const ATYP_DOMAIN: u8 = 3;
+const ATYP_IPV6: u8 = 4;
+const REPLY_SUCCEEDED: u8 = 0;
+const REPLY_TTL_EXPIRED: u8 = 6;
+
+/// Encodes `data` as lowercase, unpadded RFC 4648 base32, as used in onion addresses.
+fn base32_encode(data: &[u8]) -> String {
+	const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+	let mut ret = String::with_capacity((data.len() * 8 + 4) / 5);
+	let (mut buffer, mut bits) = (0u16, 0);
+	for byte in data {
+		buffer = (buffer << 8) | *byte as u16;
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			ret.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+		}
+	}
+	if bits > 0 {
+		ret.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+	}
+	ret
+}
+
+/// A destination as it is passed to the proxy in a CONNECT request.
+pub(crate) enum Socks5Destination {
+	IPv4([u8; 4], u16),
+	IPv6([u8; 16], u16),
+	Domain(String, u16),
+}
+
+impl Socks5Destination {
+	pub(crate) fn from_net_address(addr: &RustNetAddress) -> Self {
+		match addr {
+			RustNetAddress::IPv4 { addr, port } => Socks5Destination::IPv4(*addr, *port),
+			RustNetAddress::IPv6 { addr, port } => Socks5Destination::IPv6(*addr, *port),
+			RustNetAddress::OnionV2(data) => {
+				let port = ((data[10] as u16) << 8) | data[11] as u16;
+				Socks5Destination::Domain(format!("{}.onion", base32_encode(&data[..10])), port)
+			},
+			RustNetAddress::OnionV3 { ed25519_pubkey, checksum, version, port } => {
+				let mut addr = Vec::with_capacity(35);
+				addr.extend_from_slice(&ed25519_pubkey[..]);
+				addr.extend_from_slice(&checksum.to_be_bytes());
+				addr.push(*version);
+				Socks5Destination::Domain(format!("{}.onion", base32_encode(&addr)), *port)
+			},
+		}
+	}
+
+	fn write_to(&self, request: &mut Vec<u8>) -> Result<(), LdkNetError> {
+		let port = match self {
+			Socks5Destination::IPv4(addr, port) => {
+				request.push(ATYP_IPV4);
+				request.extend_from_slice(addr);
+				port
+			},
+			Socks5Destination::IPv6(addr, port) => {
+				request.push(ATYP_IPV6);
+				request.extend_from_slice(addr);
+				port
+			},
+			Socks5Destination::Domain(host, port) => {
+				if host.is_empty() || host.len() > 255 { return Err(LdkNetError::InvalidAddress); }
+				request.push(ATYP_DOMAIN);
+				request.push(host.len() as u8);
+				request.extend_from_slice(host.as_bytes());
+				port
+			},
+		};
+		request.extend_from_slice(&port.to_be_bytes());
+		Ok(())
+	}
+}
+
+fn read_exact(stream: &mut TcpStream, buf: &mut [u8]) -> Result<(), LdkNetError> {
+	stream.read_exact(buf).map_err(|e| match e.kind() {
+		std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => LdkNetError::ConnectTimeout,
+		_ => LdkNetError::ProxyProtocolError,
+	})
+}
+fn write_all(stream: &mut TcpStream, buf: &[u8]) -> Result<(), LdkNetError> {
+	stream.write_all(buf).map_err(|e| match e.kind() {
+		std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => LdkNetError::ConnectTimeout,
+		_ => LdkNetError::ProxyProtocolError,
+	})
+}
+
+pub(crate) struct nativeSocks5Proxy {
+	proxy_addr: String,
+	/// The username and password used when none are given for a particular connection.
+	credentials: Option<(String, String)>,
+}
+
+impl nativeSocks5Proxy {
+	/// Returns the given credentials, or the configured ones if none were given.
+	pub(crate) fn credentials<'a>(&'a self, credentials: Option<(&'a str, &'a str)>) -> Option<(&'a str, &'a str)> {
+		credentials.or_else(|| self.credentials.as_ref().map(|(username, password)| (username.as_str(), password.as_str())))
+	}
+
+	/// Connects to `dest` through the proxy, returning the established (blocking) stream.
+	pub(crate) fn connect(&self, dest: &Socks5Destination, credentials: Option<(&str, &str)>, timeout: Duration) -> Result<TcpStream, LdkNetError> {
+		let mut stream = connect_tcp(&self.proxy_addr, timeout).map_err(|e| match e {
+			LdkNetError::InvalidAddress => LdkNetError::InvalidAddress,
+			_ => LdkNetError::ProxyUnreachable,
+		})?;
+		stream.set_read_timeout(Some(timeout)).map_err(|_| LdkNetError::SocketSetupFailed)?;
+		stream.set_write_timeout(Some(timeout)).map_err(|_| LdkNetError::SocketSetupFailed)?;
+
+		// Method negotiation. If we have credentials we only offer username/password auth, so
+		// that a misconfigured proxy can't silently drop our stream isolation.
+		let method = if credentials.is_some() { AUTH_USERNAME_PASSWORD } else { AUTH_NONE };
+		write_all(&mut stream, &[SOCKS_VERSION, 1, method])?;
+		let mut reply = [0; 2];
+		read_exact(&mut stream, &mut reply)?;
+		if reply[0] != SOCKS_VERSION { return Err(LdkNetError::ProxyProtocolError); }
+		if reply[1] == AUTH_NO_ACCEPTABLE_METHOD { return Err(LdkNetError::ProxyAuthFailed); }
+		if reply[1] != method { return Err(LdkNetError::ProxyProtocolError); }
+
+		if let Some((username, password)) = credentials {
+			if username.is_empty() || username.len() > 255 || password.is_empty() || password.len() > 255 {
+				return Err(LdkNetError::ProxyAuthFailed);
+			}
+			let mut auth = Vec::with_capacity(3 + username.len() + password.len());
+			auth.push(USERNAME_PASSWORD_VERSION);
+			auth.push(username.len() as u8);
+			auth.extend_from_slice(username.as_bytes());
+			auth.push(password.len() as u8);
+			auth.extend_from_slice(password.as_bytes());
+			write_all(&mut stream, &auth)?;
+			read_exact(&mut stream, &mut reply)?;
+			if reply[0] != USERNAME_PASSWORD_VERSION { return Err(LdkNetError::ProxyProtocolError); }
+			if reply[1] != 0 { return Err(LdkNetError::ProxyAuthFailed); }
+		}
+
+		let mut request = vec![SOCKS_VERSION, CMD_CONNECT, 0];
+		dest.write_to(&mut request)?;
+		write_all(&mut stream, &request)?;
+
+		let mut header = [0; 4];
+		read_exact(&mut stream, &mut header)?;
+		if header[0] != SOCKS_VERSION { return Err(LdkNetError::ProxyProtocolError); }
+		match header[1] {
+			REPLY_SUCCEEDED => {},
+			REPLY_TTL_EXPIRED => return Err(LdkNetError::ConnectTimeout),
+			_ => return Err(LdkNetError::ConnectFailed),
+		}
+		// Skip the bound address, which we have no use for.
+		let bound_addr_len = match header[3] {
+			ATYP_IPV4 => 4,
+			ATYP_IPV6 => 16,
+			ATYP_DOMAIN => {
+				let mut len = [0; 1];
+				read_exact(&mut stream, &mut len)?;
+				len[0] as usize
+			},
+			_ => return Err(LdkNetError::ProxyProtocolError),
+		};
+		let mut bound_addr = vec![0; bound_addr_len + 2];
+		read_exact(&mut stream, &mut bound_addr)?;
+
+		stream.set_read_timeout(None).map_err(|_| LdkNetError::SocketSetupFailed)?;
+		stream.set_write_timeout(None).map_err(|_| LdkNetError::SocketSetupFailed)?;
+		Ok(stream)
+	}
+
+	pub(crate) fn connect_peer(&self, net: &NetState, their_node_id: bitcoin::secp256k1::key::PublicKey, dest: &Socks5Destination, credentials: Option<(&str, &str)>, timeout: Duration) -> Result<(), LdkNetError> {
+		let stream = self.connect(dest, self.credentials(credentials), timeout)?;
+		net.register_outbound(stream, their_node_id)
+	}
+}
+
+impl Clone for nativeSocks5Proxy {
+	fn clone(&self) -> Self {
+		Self { proxy_addr: self.proxy_addr.clone(), credentials: self.credentials.clone() }
+	}
+}
+
+/// The address of a SOCKS5 proxy through which outbound peer connections can be made.
+#[must_use]
+#[repr(C)]
+pub struct Socks5Proxy {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeSocks5Proxy,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for Socks5Proxy {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeSocks5Proxy>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the Socks5Proxy, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn Socks5Proxy_free(this_obj: Socks5Proxy) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn Socks5Proxy_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeSocks5Proxy); }
+}
+#[allow(unused)]
+impl Socks5Proxy {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeSocks5Proxy {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeSocks5Proxy {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeSocks5Proxy {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+impl Clone for Socks5Proxy {
+	fn clone(&self) -> Self {
+		Self {
+			inner: if <*mut nativeSocks5Proxy>::is_null(self.inner) { std::ptr::null_mut() } else {
+				ObjOps::heap_alloc(unsafe { &*ObjOps::untweak_ptr(self.inner) }.clone()) },
+			is_owned: true,
+		}
+	}
+}
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn Socks5Proxy_clone_void(this_ptr: *const c_void) -> *mut c_void {
+	Box::into_raw(Box::new(unsafe { (*(this_ptr as *mut nativeSocks5Proxy)).clone() })) as *mut c_void
+}
+#[no_mangle]
+/// Creates a copy of the Socks5Proxy
+pub extern "C" fn Socks5Proxy_clone(orig: &Socks5Proxy) -> Socks5Proxy {
+	orig.clone()
+}
+
+/// Creates a new Socks5Proxy for the proxy listening at `proxy_addr`, e.g. `"127.0.0.1:9050"`
+/// for a local Tor daemon.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Socks5Proxy_new(mut proxy_addr: Str) -> Socks5Proxy {
+	Socks5Proxy { inner: ObjOps::heap_alloc(nativeSocks5Proxy { proxy_addr: proxy_addr.into_string(), credentials: None }), is_owned: true }
+}
+
+/// Creates a new Socks5Proxy for the proxy listening at `proxy_addr`, which authenticates with
+/// `username` and `password` whenever no other credentials are given for a connection, including
+/// for the connections made by a `PeerReconnector`.
+///
+/// `username` and `password` must each be between 1 and 255 bytes long.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Socks5Proxy_new_with_credentials(mut proxy_addr: Str, mut username: Str, mut password: Str) -> Socks5Proxy {
+	Socks5Proxy { inner: ObjOps::heap_alloc(nativeSocks5Proxy {
+		proxy_addr: proxy_addr.into_string(),
+		credentials: Some((username.into_string(), password.into_string())),
+	}), is_owned: true }
+}
+
+fn credentials<'a>(username: &'a Str, password: &'a Str) -> Option<(&'a str, &'a str)> {
+	if username.len == 0 && password.len == 0 { None } else { Some((username.into_str(), password.into_str())) }
+}
+
+/// Connects to the peer with the given node id at `addr` through the proxy, and hands the
+/// connection to the `PeerManager` driven by `net`.
+///
+/// Onion addresses are passed to the proxy by name. If `username` and `password` are both
+/// empty, the credentials given to [`Socks5Proxy_new_with_credentials`] are used, or no
+/// authentication is offered to the proxy if there are none. Otherwise they are used as stream
+/// isolation credentials and must each be between 1 and 255 bytes long.
+///
+/// This blocks until the proxy has established the connection, with each step taking at most
+/// `timeout_secs` seconds, which must be non-zero.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Socks5Proxy_connect(this_arg: &Socks5Proxy, net: &LdkNet, mut their_node_id: PublicKey, addr: &NetAddress, mut username: Str, mut password: Str, mut timeout_secs: u64) -> CResult_NoneLdkNetErrorZ {
+	let dest = Socks5Destination::from_net_address(&addr.to_native());
+	CResult_NoneLdkNetErrorZ::from_rust(this_arg.get_native_ref().connect_peer(net.get_native_ref().state(), their_node_id.into_rust(),
+		&dest, credentials(&username, &password), Duration::from_secs(timeout_secs)))
+}
+
+/// Connects to the peer with the given node id at `host`:`port` through the proxy, as
+/// [`Socks5Proxy_connect`] does. `host` is resolved by the proxy, and may be a hostname, an
+/// onion address or a literal IP address.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn Socks5Proxy_connect_host(this_arg: &Socks5Proxy, net: &LdkNet, mut their_node_id: PublicKey, mut host: Str, mut port: u16, mut username: Str, mut password: Str, mut timeout_secs: u64) -> CResult_NoneLdkNetErrorZ {
+	let dest = Socks5Destination::Domain(host.into_str().to_owned(), port);
+	CResult_NoneLdkNetErrorZ::from_rust(this_arg.get_native_ref().connect_peer(net.get_native_ref().state(), their_node_id.into_rust(),
+		&dest, credentials(&username, &password), Duration::from_secs(timeout_secs)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::net::TcpListener;
+	use std::thread::{self, JoinHandle};
+
+	const TIMEOUT: Duration = Duration::from_secs(5);
+
+	/// Starts a stand-in proxy accepting a single connection, which it handles with `script`.
+	fn proxy<F: FnOnce(TcpStream) + Send + 'static>(credentials: Option<(&str, &str)>, script: F) -> (nativeSocks5Proxy, JoinHandle<()>) {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let proxy_addr = listener.local_addr().unwrap().to_string();
+		let handle = thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+			script(stream);
+		});
+		let credentials = credentials.map(|(username, password)| (username.to_owned(), password.to_owned()));
+		(nativeSocks5Proxy { proxy_addr, credentials }, handle)
+	}
+
+	fn expect(stream: &mut TcpStream, expected: &[u8]) {
+		let mut buf = vec![0; expected.len()];
+		stream.read_exact(&mut buf).unwrap();
+		assert_eq!(&buf[..], expected);
+	}
+
+	fn connect_request(host: &str, port: u16) -> Vec<u8> {
+		let mut request = vec![SOCKS_VERSION, CMD_CONNECT, 0, ATYP_DOMAIN, host.len() as u8];
+		request.extend_from_slice(host.as_bytes());
+		request.extend_from_slice(&port.to_be_bytes());
+		request
+	}
+
+	const SUCCESS_REPLY: [u8; 10] = [SOCKS_VERSION, REPLY_SUCCEEDED, 0, ATYP_IPV4, 127, 0, 0, 1, 0x23, 0x82];
+
+	#[test]
+	fn base32_test_vectors() {
+		// From RFC 4648, lowercased and without padding.
+		for (data, encoded) in [("", ""), ("f", "my"), ("fo", "mzxq"), ("foo", "mzxw6"), ("foob", "mzxw6yq"),
+				("fooba", "mzxw6ytb"), ("foobar", "mzxw6ytboi")].iter() {
+			assert_eq!(base32_encode(data.as_bytes()), *encoded);
+		}
+	}
+
+	#[test]
+	fn onion_v3_destination() {
+		let addr = RustNetAddress::OnionV3 { ed25519_pubkey: [0; 32], checksum: 0x0102, version: 3, port: 9735 };
+		match Socks5Destination::from_net_address(&addr) {
+			Socks5Destination::Domain(host, port) => {
+				assert_eq!(host, format!("{}caqd.onion", "a".repeat(52)));
+				assert_eq!(port, 9735);
+			},
+			_ => panic!("onion addresses must be passed by name"),
+		}
+	}
+
+	#[test]
+	fn connect_without_auth() {
+		let (proxy, handle) = proxy(None, |mut stream| {
+			expect(&mut stream, &[SOCKS_VERSION, 1, AUTH_NONE]);
+			stream.write_all(&[SOCKS_VERSION, AUTH_NONE]).unwrap();
+			expect(&mut stream, &connect_request("example.onion", 9735));
+			stream.write_all(&SUCCESS_REPLY).unwrap();
+			stream.write_all(b"hello").unwrap();
+			expect(&mut stream, b"world");
+		});
+		let dest = Socks5Destination::Domain("example.onion".to_owned(), 9735);
+		let mut stream = proxy.connect(&dest, proxy.credentials(None), TIMEOUT).unwrap();
+		let mut buf = [0; 5];
+		stream.read_exact(&mut buf).unwrap();
+		assert_eq!(&buf, b"hello");
+		stream.write_all(b"world").unwrap();
+		handle.join().unwrap();
+	}
+
+	#[test]
+	fn connect_with_configured_credentials() {
+		let (proxy, handle) = proxy(Some(("user", "pass")), |mut stream| {
+			expect(&mut stream, &[SOCKS_VERSION, 1, AUTH_USERNAME_PASSWORD]);
+			stream.write_all(&[SOCKS_VERSION, AUTH_USERNAME_PASSWORD]).unwrap();
+			expect(&mut stream, b"\x01\x04user\x04pass");
+			stream.write_all(&[USERNAME_PASSWORD_VERSION, 0]).unwrap();
+			let mut request = vec![SOCKS_VERSION, CMD_CONNECT, 0, ATYP_IPV4, 10, 0, 0, 1];
+			request.extend_from_slice(&9735u16.to_be_bytes());
+			expect(&mut stream, &request);
+			stream.write_all(&[SOCKS_VERSION, REPLY_SUCCEEDED, 0, ATYP_DOMAIN, 3]).unwrap();
+			stream.write_all(b"abc\x00\x00").unwrap();
+		});
+		let dest = Socks5Destination::IPv4([10, 0, 0, 1], 9735);
+		assert!(proxy.connect(&dest, proxy.credentials(None), TIMEOUT).is_ok());
+		handle.join().unwrap();
+	}
+
+	#[test]
+	fn per_connection_credentials_override_configured_ones() {
+		let (proxy, handle) = proxy(Some(("user", "pass")), |mut stream| {
+			expect(&mut stream, &[SOCKS_VERSION, 1, AUTH_USERNAME_PASSWORD]);
+			stream.write_all(&[SOCKS_VERSION, AUTH_USERNAME_PASSWORD]).unwrap();
+			expect(&mut stream, b"\x01\x08isolated\x01x");
+			stream.write_all(&[USERNAME_PASSWORD_VERSION, 1]).unwrap();
+		});
+		let dest = Socks5Destination::Domain("example.com".to_owned(), 9735);
+		let res = proxy.connect(&dest, proxy.credentials(Some(("isolated", "x"))), TIMEOUT);
+		assert_eq!(res.err(), Some(LdkNetError::ProxyAuthFailed));
+		handle.join().unwrap();
+	}
+
+	#[test]
+	fn no_acceptable_method() {
+		let (proxy, handle) = proxy(None, |mut stream| {
+			expect(&mut stream, &[SOCKS_VERSION, 1, AUTH_NONE]);
+			stream.write_all(&[SOCKS_VERSION, AUTH_NO_ACCEPTABLE_METHOD]).unwrap();
+		});
+		let dest = Socks5Destination::Domain("example.com".to_owned(), 9735);
+		assert_eq!(proxy.connect(&dest, None, TIMEOUT).err(), Some(LdkNetError::ProxyAuthFailed));
+		handle.join().unwrap();
+	}
+
+	#[test]
+	fn connect_refused_by_proxy() {
+		for (reply, err) in [(5, LdkNetError::ConnectFailed), (REPLY_TTL_EXPIRED, LdkNetError::ConnectTimeout)].iter() {
+			let reply = *reply;
+			let (proxy, handle) = proxy(None, move |mut stream| {
+				expect(&mut stream, &[SOCKS_VERSION, 1, AUTH_NONE]);
+				stream.write_all(&[SOCKS_VERSION, AUTH_NONE]).unwrap();
+				expect(&mut stream, &connect_request("example.com", 9735));
+				stream.write_all(&[SOCKS_VERSION, reply, 0, ATYP_IPV4, 0, 0, 0, 0, 0, 0]).unwrap();
+			});
+			let dest = Socks5Destination::Domain("example.com".to_owned(), 9735);
+			assert_eq!(proxy.connect(&dest, None, TIMEOUT).err(), Some(*err));
+			handle.join().unwrap();
+		}
+	}
+
+	#[test]
+	fn unreachable_proxy() {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let proxy_addr = listener.local_addr().unwrap().to_string();
+		drop(listener);
+		let proxy = nativeSocks5Proxy { proxy_addr, credentials: None };
+		let dest = Socks5Destination::Domain("example.com".to_owned(), 9735);
+		assert_eq!(proxy.connect(&dest, None, TIMEOUT).err(), Some(LdkNetError::ProxyUnreachable));
+	}
+}
@@ -0,0 +1,712 @@
+//! A persistence backend which keeps all node state in a single SQLite database file.
+//!
+//! [`SqliteStore`] implements [`KVStore`], and can thus be used with a [`KVStorePersister`] to
+//! store the `ChannelManager`, `NetworkGraph` and `Scorer`. It also implements `Persist`
+//! directly, which, once a `ChainMonitor` has been registered, commits `ChannelMonitor`s on a
+//! background thread and completes the updates via `ChainMonitor_channel_monitor_updated`.
+//!
+//! Rather than rewriting the full `ChannelMonitor` on every update, each `ChannelMonitorUpdate`
+//! is stored in the `monitor_updates` table keyed by its funding outpoint and update id, and the
+//! full monitor is only rewritten once a configurable number of updates has been stored for it.
+//! The stored monitors may thus be missing updates, so they must be read with
+//! [`SqliteStore_read_channelmonitors`] or restored with `SqliteStore_restore_node`, which
+//! replay the stored updates, rather than through a [`KVStorePersister`].
+//!
+//! SQLite is built into the library, so no system SQLite is required. This module is only
+//! available when the bindings are built with the `sqlite` feature.
+//!
+//! [`KVStore`]: crate::extensions::kv_store::KVStore
+//! [`KVStorePersister`]: crate::extensions::kv_store::KVStorePersister
+
+use std::cmp;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+use bitcoin::hash_types::BlockHash;
+use bitcoin::hashes::Hash;
+
+use lightning::chain::ChannelMonitorUpdateErr;
+use lightning::chain::chainmonitor::MonitorUpdateId;
+use lightning::chain::channelmonitor::{ChannelMonitor as RustChannelMonitor, ChannelMonitorUpdate as RustChannelMonitorUpdate, CLOSED_CHANNEL_UPDATE_ID};
+use lightning::chain::transaction::OutPoint as RustOutPoint;
+use lightning::util::ser::{Readable, Writeable};
+
+use crate::c_types::*;
+use crate::c_types::derived::CResult_NoneErrorZ;
+use crate::extensions::kv_store::*;
+use crate::lightning::chain::chaininterface::{BroadcasterInterface, FeeEstimator};
+use crate::lightning::chain::chainmonitor::{ChainMonitor, nativeChainMonitor};
+use crate::lightning::chain::keysinterface::{KeysInterface, Sign};
+use crate::lightning::util::logger::Logger;
+
+/// How long the background writer waits before retrying a failed commit.
+const WRITE_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+fn sqlite_to_io_error(e: rusqlite::Error) -> std::io::Error {
+	std::io::Error::new(std::io::ErrorKind::Other, e)
+}
+
+fn invalid_data(msg: String) -> std::io::Error {
+	std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+/// What to store for a `Persist` call.
+enum MonitorWrite {
+	/// The full, serialized monitor, which replaces all stored updates up to `latest_update_id`.
+	Monitor { latest_update_id: u64, data: Vec<u8> },
+	/// A serialized `ChannelMonitorUpdate`, which is applied to the stored monitor when reading it.
+	Update { update_id: u64, data: Vec<u8> },
+}
+
+/// A `ChannelMonitor` or `ChannelMonitorUpdate` which has been handed to us but not yet committed.
+struct PendingMonitorWrite {
+	funding_txo: RustOutPoint,
+	update_id: MonitorUpdateId,
+	write: MonitorWrite,
+}
+
+/// A pointer to the registered `ChainMonitor`, which the user guarantees outlives us.
+#[derive(Clone, Copy)]
+struct ChainMonitorRef(*const nativeChainMonitor);
+unsafe impl Send for ChainMonitorRef {}
+
+struct PendingWrites {
+	writes: Vec<PendingMonitorWrite>,
+	chain_monitor: Option<ChainMonitorRef>,
+	shutdown: bool,
+}
+
+struct WriterState {
+	pending: Mutex<PendingWrites>,
+	cv: Condvar,
+}
+
+pub(crate) struct nativeSqliteStore {
+	conn: Arc<Mutex<Connection>>,
+	state: Arc<WriterState>,
+	writer: Option<JoinHandle<()>>,
+	max_pending_updates: usize,
+	/// The number of updates stored for each channel since its full monitor was last written.
+	pending_updates: Mutex<HashMap<RustOutPoint, usize>>,
+}
+
+/// Writes the given monitors and updates, in order, in a single transaction. Writing a full
+/// monitor removes the stored updates it contains.
+fn commit_monitors<'a, I: Iterator<Item=(&'a RustOutPoint, &'a MonitorWrite)>>(conn: &mut Connection, writes: I) -> Result<(), rusqlite::Error> {
+	let tx = conn.transaction()?;
+	{
+		let mut write_monitor = tx.prepare_cached("INSERT OR REPLACE INTO kv (namespace, key, value) VALUES (?1, ?2, ?3)")?;
+		let mut compact_updates = tx.prepare_cached("DELETE FROM monitor_updates WHERE funding_txo = ?1 AND update_id <= ?2")?;
+		let mut write_update = tx.prepare_cached("INSERT OR REPLACE INTO monitor_updates (funding_txo, update_id, value) VALUES (?1, ?2, ?3)")?;
+		for (funding_txo, write) in writes {
+			let key = monitor_key(funding_txo);
+			match write {
+				MonitorWrite::Monitor { latest_update_id, data } => {
+					write_monitor.execute(params![CHANNEL_MONITOR_NAMESPACE, key, data])?;
+					// Closed channels have a latest update id of u64::MAX, which SQLite can't store.
+					compact_updates.execute(params![key, cmp::min(*latest_update_id, i64::MAX as u64) as i64])?;
+				},
+				MonitorWrite::Update { update_id, data } => {
+					write_update.execute(params![key, *update_id as i64, data])?;
+				},
+			}
+		}
+	}
+	tx.commit()
+}
+
+fn writer_loop(conn: Arc<Mutex<Connection>>, state: Arc<WriterState>) {
+	loop {
+		let (mut batch, chain_monitor) = {
+			let mut pending = state.pending.lock().unwrap();
+			while pending.writes.is_empty() && !pending.shutdown {
+				pending = state.cv.wait(pending).unwrap();
+			}
+			if pending.writes.is_empty() { return; }
+			(std::mem::replace(&mut pending.writes, Vec::new()), pending.chain_monitor)
+		};
+
+		let res = commit_monitors(&mut conn.lock().unwrap(), batch.iter().map(|write| (&write.funding_txo, &write.write)));
+		match res {
+			Ok(()) => {
+				// The writes are only ever queued once a ChainMonitor has been registered.
+				let chain_monitor = unsafe { &*chain_monitor.unwrap().0 };
+				for write in batch.drain(..) {
+					let _ = chain_monitor.channel_monitor_updated(write.funding_txo, write.update_id);
+				}
+			},
+			Err(_) => {
+				// Put the batch back in front of anything queued since and try again later. The
+				// channels stay paused until then, which is exactly what we want. This applies at
+				// shutdown too, as dropping a monitor update would leave it behind the
+				// ChannelManager.
+				let mut pending = state.pending.lock().unwrap();
+				batch.extend(pending.writes.drain(..));
+				pending.writes = batch;
+				std::mem::drop(pending);
+				thread::sleep(WRITE_RETRY_INTERVAL);
+			},
+		}
+	}
+}
+
+impl nativeSqliteStore {
+	fn open(path: &str, max_pending_updates: usize) -> Result<Self, rusqlite::Error> {
+		let conn = Connection::open(path)?;
+		conn.execute_batch("PRAGMA journal_mode = WAL;
+			PRAGMA synchronous = FULL;
+			CREATE TABLE IF NOT EXISTS kv (
+				namespace TEXT NOT NULL,
+				key TEXT NOT NULL,
+				value BLOB NOT NULL,
+				PRIMARY KEY (namespace, key)
+			);
+			CREATE TABLE IF NOT EXISTS monitor_updates (
+				funding_txo TEXT NOT NULL,
+				update_id INTEGER NOT NULL,
+				value BLOB NOT NULL,
+				PRIMARY KEY (funding_txo, update_id)
+			);")?;
+		let mut pending_updates = HashMap::new();
+		{
+			let mut stmt = conn.prepare("SELECT funding_txo, COUNT(*) FROM monitor_updates GROUP BY funding_txo")?;
+			let mut rows = stmt.query([])?;
+			while let Some(row) = rows.next()? {
+				let key: String = row.get(0)?;
+				let count: i64 = row.get(1)?;
+				if let Some(funding_txo) = parse_monitor_key(&key) {
+					pending_updates.insert(funding_txo, count as usize);
+				}
+			}
+		}
+		let conn = Arc::new(Mutex::new(conn));
+		let state = Arc::new(WriterState {
+			pending: Mutex::new(PendingWrites { writes: Vec::new(), chain_monitor: None, shutdown: false }),
+			cv: Condvar::new(),
+		});
+		let writer = {
+			let (conn, state) = (Arc::clone(&conn), Arc::clone(&state));
+			thread::spawn(move || writer_loop(conn, state))
+		};
+		Ok(Self { conn, state, writer: Some(writer), max_pending_updates, pending_updates: Mutex::new(pending_updates) })
+	}
+
+	pub(crate) fn read(&self, namespace: &str, key: &str) -> Result<Vec<u8>, std::io::Error> {
+		self.conn.lock().unwrap()
+			.query_row("SELECT value FROM kv WHERE namespace = ?1 AND key = ?2", params![namespace, key], |row| row.get(0))
+			.optional().map_err(sqlite_to_io_error)?
+			.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Key not found"))
+	}
+	/// Runs `statement` with `params` in its own transaction.
+	fn execute_in_transaction<P: rusqlite::Params>(&self, statement: &str, params: P) -> Result<(), std::io::Error> {
+		let mut conn = self.conn.lock().unwrap();
+		let tx = conn.transaction().map_err(sqlite_to_io_error)?;
+		tx.execute(statement, params).map_err(sqlite_to_io_error)?;
+		tx.commit().map_err(sqlite_to_io_error)
+	}
+	fn write(&self, namespace: &str, key: &str, value: &[u8]) -> Result<(), std::io::Error> {
+		self.execute_in_transaction("INSERT OR REPLACE INTO kv (namespace, key, value) VALUES (?1, ?2, ?3)", params![namespace, key, value])
+	}
+	fn remove(&self, namespace: &str, key: &str) -> Result<(), std::io::Error> {
+		self.execute_in_transaction("DELETE FROM kv WHERE namespace = ?1 AND key = ?2", params![namespace, key])
+	}
+	fn list(&self, namespace: &str) -> Result<Vec<String>, std::io::Error> {
+		let conn = self.conn.lock().unwrap();
+		let mut stmt = conn.prepare_cached("SELECT key FROM kv WHERE namespace = ?1").map_err(sqlite_to_io_error)?;
+		let keys = stmt.query_map(params![namespace], |row| row.get(0)).map_err(sqlite_to_io_error)?;
+		keys.collect::<Result<Vec<String>, _>>().map_err(sqlite_to_io_error)
+	}
+
+	/// The stored updates for the given channel with an update id greater than `after_update_id`,
+	/// in order.
+	fn read_updates(&self, funding_txo: &RustOutPoint, after_update_id: u64) -> Result<Vec<(u64, Vec<u8>)>, std::io::Error> {
+		let conn = self.conn.lock().unwrap();
+		let mut stmt = conn.prepare_cached("SELECT update_id, value FROM monitor_updates WHERE funding_txo = ?1 AND update_id > ?2 ORDER BY update_id")
+			.map_err(sqlite_to_io_error)?;
+		let updates = stmt.query_map(params![monitor_key(funding_txo), cmp::min(after_update_id, i64::MAX as u64) as i64],
+			|row| Ok((row.get::<_, i64>(0)? as u64, row.get(1)?))).map_err(sqlite_to_io_error)?;
+		updates.collect::<Result<Vec<_>, _>>().map_err(sqlite_to_io_error)
+	}
+
+	/// Reads all `ChannelMonitor`s and applies the stored updates which they don't contain yet.
+	pub(crate) fn read_channelmonitors(&self, keys_manager: &KeysInterface, broadcaster: &BroadcasterInterface, fee_estimator: &FeeEstimator, logger: &Logger)
+	-> Result<Vec<(BlockHash, RustChannelMonitor<Sign>)>, std::io::Error> {
+		let store = KVStore {
+			this_arg: self as *const nativeSqliteStore as *mut c_void,
+			free: None,
+			read: SqliteStore_KVStore_read,
+			write: SqliteStore_KVStore_write,
+			remove: SqliteStore_KVStore_remove,
+			list: SqliteStore_KVStore_list,
+		};
+		let monitors = read_channelmonitors(&store, keys_manager)?;
+		for (_, monitor) in monitors.iter() {
+			for (update_id, data) in self.read_updates(&monitor.get_funding_txo().0, monitor.get_latest_update_id())? {
+				let update: RustChannelMonitorUpdate = Readable::read(&mut Cursor::new(&data))
+					.map_err(|e| invalid_data(format!("Failed to read ChannelMonitorUpdate: {:?}", e)))?;
+				if update.update_id != update_id {
+					return Err(invalid_data("ChannelMonitorUpdate was stored under the wrong update id".to_owned()));
+				}
+				monitor.update_monitor(&update, broadcaster, fee_estimator, logger)
+					.map_err(|_| invalid_data(format!("Failed to apply stored ChannelMonitorUpdate {}", update_id)))?;
+			}
+		}
+		Ok(monitors)
+	}
+
+	/// Decides whether to store `update` or the full `monitor`.
+	fn monitor_write(&self, funding_txo: &RustOutPoint, update: Option<&RustChannelMonitorUpdate>, monitor: &RustChannelMonitor<Sign>) -> MonitorWrite {
+		let mut pending_updates = self.pending_updates.lock().unwrap();
+		let pending = pending_updates.entry(*funding_txo).or_insert(0);
+		match update {
+			// Post-close updates all share the same update id, so can't be stored individually.
+			Some(update) if update.update_id != CLOSED_CHANNEL_UPDATE_ID && *pending < self.max_pending_updates => {
+				*pending += 1;
+				MonitorWrite::Update { update_id: update.update_id, data: update.encode() }
+			},
+			// Chain sync updates carry no ChannelMonitorUpdate, so we have to write everything.
+			_ => {
+				*pending = 0;
+				// Fetch the update id before encoding, as further updates may be applied
+				// concurrently, and we must never remove an update which isn't in what we wrote.
+				let latest_update_id = monitor.get_latest_update_id();
+				MonitorWrite::Monitor { latest_update_id, data: monitor.encode() }
+			},
+		}
+	}
+
+	fn persist_monitor(&self, funding_txo: RustOutPoint, update: Option<&RustChannelMonitorUpdate>, monitor: &RustChannelMonitor<Sign>, update_id: MonitorUpdateId) -> Result<(), ChannelMonitorUpdateErr> {
+		let write = self.monitor_write(&funding_txo, update, monitor);
+		let mut pending = self.state.pending.lock().unwrap();
+		if pending.chain_monitor.is_none() {
+			// Without a ChainMonitor to notify we have to commit synchronously.
+			std::mem::drop(pending);
+			return commit_monitors(&mut self.conn.lock().unwrap(), std::iter::once((&funding_txo, &write)))
+				.map_err(|_| ChannelMonitorUpdateErr::PermanentFailure);
+		}
+		pending.writes.push(PendingMonitorWrite { funding_txo, update_id, write });
+		self.state.cv.notify_one();
+		Err(ChannelMonitorUpdateErr::TemporaryFailure)
+	}
+}
+
+impl Drop for nativeSqliteStore {
+	fn drop(&mut self) {
+		self.state.pending.lock().unwrap().shutdown = true;
+		self.state.cv.notify_one();
+		if let Some(writer) = self.writer.take() {
+			let _ = writer.join();
+		}
+	}
+}
+
+impl lightning::chain::chainmonitor::Persist<Sign> for nativeSqliteStore {
+	fn persist_new_channel(&self, funding_txo: RustOutPoint, monitor: &RustChannelMonitor<Sign>, update_id: MonitorUpdateId) -> Result<(), ChannelMonitorUpdateErr> {
+		self.persist_monitor(funding_txo, None, monitor, update_id)
+	}
+
+	fn update_persisted_channel(&self, funding_txo: RustOutPoint, update: &Option<RustChannelMonitorUpdate>, monitor: &RustChannelMonitor<Sign>, update_id: MonitorUpdateId) -> Result<(), ChannelMonitorUpdateErr> {
+		self.persist_monitor(funding_txo, update.as_ref(), monitor, update_id)
+	}
+}
+
+/// A [`KVStore`] and `Persist` implementation backed by a single SQLite database file.
+///
+/// Until [`SqliteStore_register_chain_monitor`] is called, `ChannelMonitor`s are committed
+/// synchronously. Afterwards, `Persist` calls return `ChannelMonitorUpdateErr_TemporaryFailure`
+/// and the monitors are committed in batches on a background thread, after which
+/// `ChainMonitor_channel_monitor_updated` is called for each of them. If a commit fails it is
+/// retried, with the affected channels remaining paused until it succeeds.
+///
+/// `ChannelMonitorUpdate`s are stored individually, and the full `ChannelMonitor` is only
+/// rewritten periodically, in the same transaction which removes the updates it contains. Each
+/// `KVStore` write is committed in its own transaction.
+///
+/// [`KVStore`]: crate::extensions::kv_store::KVStore
+#[must_use]
+#[repr(C)]
+pub struct SqliteStore {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeSqliteStore,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for SqliteStore {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeSqliteStore>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the SqliteStore, if is_owned is set and inner is non-NULL.
+///
+/// Any `ChannelMonitor`s which are still queued are committed before this returns. If the
+/// commit fails it is retried every second, so this blocks until the database is writable again.
+#[no_mangle]
+pub extern "C" fn SqliteStore_free(this_obj: SqliteStore) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn SqliteStore_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeSqliteStore); }
+}
+#[allow(unused)]
+impl SqliteStore {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeSqliteStore {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeSqliteStore {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeSqliteStore {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_SqliteStoreIOErrorZ
+pub union CResult_SqliteStoreIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut SqliteStore,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_SqliteStoreIOErrorZ represents the result of a fallible operation,
+/// containing a SqliteStore on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_SqliteStoreIOErrorZ {
+	/// The contents of this CResult_SqliteStoreIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_SqliteStoreIOErrorZPtr,
+	/// Whether this CResult_SqliteStoreIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_SqliteStoreIOErrorZ in the success state.
+pub extern "C" fn CResult_SqliteStoreIOErrorZ_ok(o: SqliteStore) -> CResult_SqliteStoreIOErrorZ {
+	CResult_SqliteStoreIOErrorZ {
+		contents: CResult_SqliteStoreIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_SqliteStoreIOErrorZ in the error state.
+pub extern "C" fn CResult_SqliteStoreIOErrorZ_err(e: IOError) -> CResult_SqliteStoreIOErrorZ {
+	CResult_SqliteStoreIOErrorZ {
+		contents: CResult_SqliteStoreIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_SqliteStoreIOErrorZ_is_ok(o: &CResult_SqliteStoreIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_SqliteStoreIOErrorZ.
+pub extern "C" fn CResult_SqliteStoreIOErrorZ_free(_res: CResult_SqliteStoreIOErrorZ) { }
+impl Drop for CResult_SqliteStoreIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Opens (or creates) the SQLite database at `path`.
+///
+/// Once `max_pending_updates` `ChannelMonitorUpdate`s have been stored for a channel, the next
+/// update causes the full `ChannelMonitor` to be rewritten and the stored updates to be removed.
+/// Larger values reduce write volume at the cost of slower loading. A value of 0 rewrites the
+/// full `ChannelMonitor` on every update.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn SqliteStore_open(mut path: crate::c_types::Str, mut max_pending_updates: usize) -> CResult_SqliteStoreIOErrorZ {
+	match nativeSqliteStore::open(path.into_str(), max_pending_updates) {
+		Ok(store) => CResult_SqliteStoreIOErrorZ_ok(SqliteStore { inner: ObjOps::heap_alloc(store), is_owned: true }),
+		Err(e) => CResult_SqliteStoreIOErrorZ_err(IOError::from_rust(sqlite_to_io_error(e))),
+	}
+}
+
+/// Switches `ChannelMonitor` persistence to asynchronous commits which are completed via
+/// `ChainMonitor_channel_monitor_updated` on `chain_monitor`.
+///
+/// `chain_monitor` must be the `ChainMonitor` this store's `Persist` implementation was given
+/// to, and it must not be freed until this `SqliteStore` has been.
+#[no_mangle]
+pub extern "C" fn SqliteStore_register_chain_monitor(this_arg: &SqliteStore, chain_monitor: &ChainMonitor) {
+	let store = this_arg.get_native_ref();
+	store.state.pending.lock().unwrap().chain_monitor = Some(ChainMonitorRef(chain_monitor.get_native_ref()));
+}
+
+/// Read all `ChannelMonitor`s from the database, applying any stored `ChannelMonitorUpdate`s which
+/// were not yet written as part of the full `ChannelMonitor`.
+///
+/// The `broadcaster`, `fee_estimator` and `logger` are passed to `ChannelMonitor_update_monitor`
+/// when applying updates.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn SqliteStore_read_channelmonitors(this_arg: &SqliteStore, keys_manager: &KeysInterface, broadcaster: &BroadcasterInterface, fee_estimator: &FeeEstimator, logger: &Logger) -> crate::c_types::derived::CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ {
+	let mut ret = this_arg.get_native_ref().read_channelmonitors(keys_manager, broadcaster, fee_estimator, logger);
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { let mut local_ret_0 = Vec::new(); for mut item in o.drain(..) { local_ret_0.push( { let (mut orig_ret_0_0_0, mut orig_ret_0_0_1) = item; let mut local_ret_0_0 = (crate::c_types::ThirtyTwoBytes { data: orig_ret_0_0_0.into_inner() }, crate::lightning::chain::channelmonitor::ChannelMonitor { inner: ObjOps::heap_alloc(orig_ret_0_0_1), is_owned: true }).into(); local_ret_0_0 }); }; local_ret_0.into() }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::c_types::IOError::from_rust(e) }).into() };
+	local_ret
+}
+
+impl From<nativeSqliteStore> for KVStore {
+	fn from(obj: nativeSqliteStore) -> Self {
+		let mut rust_obj = SqliteStore { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = SqliteStore_as_KVStore(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(SqliteStore_free_void);
+		ret
+	}
+}
+/// Constructs a new KVStore which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned KVStore must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn SqliteStore_as_KVStore(this_arg: &SqliteStore) -> KVStore {
+	KVStore {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		read: SqliteStore_KVStore_read,
+		write: SqliteStore_KVStore_write,
+		remove: SqliteStore_KVStore_remove,
+		list: SqliteStore_KVStore_list,
+	}
+}
+
+#[must_use]
+extern "C" fn SqliteStore_KVStore_read(this_arg: *const c_void, mut key_namespace: Str, mut key: Str) -> CResult_CVec_u8ZIOErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeSqliteStore) };
+	CResult_CVec_u8ZIOErrorZ::from_rust(store.read(key_namespace.into_str(), key.into_str()))
+}
+#[must_use]
+extern "C" fn SqliteStore_KVStore_write(this_arg: *const c_void, mut key_namespace: Str, mut key: Str, mut value: u8slice) -> CResult_NoneErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeSqliteStore) };
+	none_result_from_rust(store.write(key_namespace.into_str(), key.into_str(), value.to_slice()))
+}
+#[must_use]
+extern "C" fn SqliteStore_KVStore_remove(this_arg: *const c_void, mut key_namespace: Str, mut key: Str) -> CResult_NoneErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeSqliteStore) };
+	none_result_from_rust(store.remove(key_namespace.into_str(), key.into_str()))
+}
+#[must_use]
+extern "C" fn SqliteStore_KVStore_list(this_arg: *const c_void, mut key_namespace: Str) -> CResult_CVec_StrZIOErrorZ {
+	let store = unsafe { &*(this_arg as *const nativeSqliteStore) };
+	CResult_CVec_StrZIOErrorZ::from_rust(store.list(key_namespace.into_str()))
+}
+
+/// Constructs a new Persist which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned Persist must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn SqliteStore_as_Persist(this_arg: &SqliteStore) -> crate::lightning::chain::chainmonitor::Persist {
+	crate::lightning::chain::chainmonitor::Persist {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		persist_new_channel: SqliteStore_Persist_persist_new_channel,
+		update_persisted_channel: SqliteStore_Persist_update_persisted_channel,
+	}
+}
+
+#[must_use]
+extern "C" fn SqliteStore_Persist_persist_new_channel(this_arg: *const c_void, mut funding_txo: crate::lightning::chain::transaction::OutPoint, monitor: &crate::lightning::chain::channelmonitor::ChannelMonitor, mut update_id: crate::lightning::chain::chainmonitor::MonitorUpdateId) -> crate::c_types::derived::CResult_NoneChannelMonitorUpdateErrZ {
+	let mut ret = <nativeSqliteStore as lightning::chain::chainmonitor::Persist<_>>::persist_new_channel(unsafe { &mut *(this_arg as *mut nativeSqliteStore) }, *unsafe { Box::from_raw(funding_txo.take_inner()) }, monitor.get_native_ref(), *unsafe { Box::from_raw(update_id.take_inner()) });
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { () /*o*/ }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::chain::ChannelMonitorUpdateErr::native_into(e) }).into() };
+	local_ret
+}
+#[must_use]
+extern "C" fn SqliteStore_Persist_update_persisted_channel(this_arg: *const c_void, mut funding_txo: crate::lightning::chain::transaction::OutPoint, update: &crate::lightning::chain::channelmonitor::ChannelMonitorUpdate, monitor: &crate::lightning::chain::channelmonitor::ChannelMonitor, mut update_id: crate::lightning::chain::chainmonitor::MonitorUpdateId) -> crate::c_types::derived::CResult_NoneChannelMonitorUpdateErrZ {
+	let mut local_update = if update.inner.is_null() { None } else { Some((* { update.get_native_ref() }).clone()) };
+	let mut ret = <nativeSqliteStore as lightning::chain::chainmonitor::Persist<_>>::update_persisted_channel(unsafe { &mut *(this_arg as *mut nativeSqliteStore) }, *unsafe { Box::from_raw(funding_txo.take_inner()) }, &local_update, monitor.get_native_ref(), *unsafe { Box::from_raw(update_id.take_inner()) });
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { () /*o*/ }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::chain::ChannelMonitorUpdateErr::native_into(e) }).into() };
+	local_ret
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::extensions::test_utils::*;
+
+	use bitcoin::hash_types::Txid;
+
+
+	/// A database path unique to this test, with any leftovers from a previous run removed.
+	fn db_path(name: &str) -> String {
+		let path = std::env::temp_dir().join(format!("ldk-sqlite-test-{}-{}", name, std::process::id()));
+		let path = path.to_str().unwrap().to_owned();
+		remove_db(&path);
+		path
+	}
+
+	fn remove_db(path: &str) {
+		for suffix in ["", "-wal", "-shm"].iter() {
+			let _ = std::fs::remove_file(format!("{}{}", path, suffix));
+		}
+	}
+
+	fn monitor(latest_update_id: u64, data: &[u8]) -> MonitorWrite {
+		MonitorWrite::Monitor { latest_update_id, data: data.to_vec() }
+	}
+
+	fn update(update_id: u64) -> MonitorWrite {
+		MonitorWrite::Update { update_id, data: vec![update_id as u8; 4] }
+	}
+
+	fn commit(store: &nativeSqliteStore, funding_txo: &RustOutPoint, writes: &[MonitorWrite]) {
+		commit_monitors(&mut store.conn.lock().unwrap(), writes.iter().map(|write| (funding_txo, write))).unwrap();
+	}
+
+	fn stored_update_ids(store: &nativeSqliteStore, funding_txo: &RustOutPoint) -> Vec<u64> {
+		store.read_updates(funding_txo, 0).unwrap().into_iter().map(|(update_id, _)| update_id).collect()
+	}
+
+	#[test]
+	fn kv_writes_survive_restart() {
+		let path = db_path("kv");
+		{
+			let store = nativeSqliteStore::open(&path, 10).unwrap();
+			store.write(NODE_NAMESPACE, CHANNEL_MANAGER_KEY, &[1, 2, 3]).unwrap();
+			store.write("ns", "a", &[4]).unwrap();
+			store.write("ns", "b", &[5]).unwrap();
+			store.write("ns", "a", &[6]).unwrap();
+			store.remove("ns", "b").unwrap();
+		}
+		let store = nativeSqliteStore::open(&path, 10).unwrap();
+		assert_eq!(store.read(NODE_NAMESPACE, CHANNEL_MANAGER_KEY).unwrap(), vec![1, 2, 3]);
+		assert_eq!(store.read("ns", "a").unwrap(), vec![6]);
+		assert_eq!(store.read("ns", "b").unwrap_err().kind(), std::io::ErrorKind::NotFound);
+		assert_eq!(store.list("ns").unwrap(), vec!["a".to_owned()]);
+		assert!(store.list("other").unwrap().is_empty());
+		std::mem::drop(store);
+		remove_db(&path);
+	}
+
+	#[test]
+	fn monitor_updates_survive_restart() {
+		let path = db_path("updates");
+		let funding_txo = funding_outpoint();
+		{
+			let store = nativeSqliteStore::open(&path, 10).unwrap();
+			commit(&store, &funding_txo, &[monitor(0, &[42; 8]), update(1), update(2), update(3)]);
+		}
+		let store = nativeSqliteStore::open(&path, 10).unwrap();
+		assert_eq!(store.read(CHANNEL_MONITOR_NAMESPACE, &monitor_key(&funding_txo)).unwrap(), vec![42; 8]);
+		assert_eq!(store.read_updates(&funding_txo, 0).unwrap(),
+			vec![(1, vec![1; 4]), (2, vec![2; 4]), (3, vec![3; 4])]);
+		// Only updates the monitor doesn't contain yet are returned.
+		assert_eq!(store.read_updates(&funding_txo, 2).unwrap(), vec![(3, vec![3; 4])]);
+		// The number of stored updates is restored, so compaction happens on schedule.
+		assert_eq!(store.pending_updates.lock().unwrap().get(&funding_txo), Some(&3));
+		std::mem::drop(store);
+		remove_db(&path);
+	}
+
+	#[test]
+	fn writing_a_monitor_compacts_its_updates() {
+		let path = db_path("compact");
+		let funding_txo = funding_outpoint();
+		let other_txo = RustOutPoint { txid: Txid::from_inner([43; 32]), index: 0 };
+		let store = nativeSqliteStore::open(&path, 10).unwrap();
+		commit(&store, &funding_txo, &[monitor(0, &[1]), update(1), update(2), update(3)]);
+		commit(&store, &other_txo, &[monitor(0, &[2]), update(1)]);
+
+		// A monitor written concurrently with update 3 only contains updates up to 2.
+		commit(&store, &funding_txo, &[monitor(2, &[3])]);
+		assert_eq!(store.read(CHANNEL_MONITOR_NAMESPACE, &monitor_key(&funding_txo)).unwrap(), vec![3]);
+		assert_eq!(stored_update_ids(&store, &funding_txo), vec![3]);
+		assert_eq!(stored_update_ids(&store, &other_txo), vec![1]);
+
+		// Closed channels report u64::MAX, which must remove every update.
+		commit(&store, &funding_txo, &[update(4), monitor(CLOSED_CHANNEL_UPDATE_ID, &[4])]);
+		assert!(stored_update_ids(&store, &funding_txo).is_empty());
+		assert_eq!(stored_update_ids(&store, &other_txo), vec![1]);
+		std::mem::drop(store);
+
+		let store = nativeSqliteStore::open(&path, 10).unwrap();
+		assert_eq!(store.read(CHANNEL_MONITOR_NAMESPACE, &monitor_key(&funding_txo)).unwrap(), vec![4]);
+		assert_eq!(store.pending_updates.lock().unwrap().get(&funding_txo), None);
+		assert_eq!(store.pending_updates.lock().unwrap().get(&other_txo), Some(&1));
+		let mut monitor_keys = store.list(CHANNEL_MONITOR_NAMESPACE).unwrap();
+		monitor_keys.sort();
+		let mut expected = vec![monitor_key(&funding_txo), monitor_key(&other_txo)];
+		expected.sort();
+		assert_eq!(monitor_keys, expected);
+		std::mem::drop(store);
+		remove_db(&path);
+	}
+
+	#[test]
+	fn monitor_writes_commit_atomically() {
+		let path = db_path("atomic");
+		let funding_txo = funding_outpoint();
+		let store = nativeSqliteStore::open(&path, 10).unwrap();
+		commit(&store, &funding_txo, &[monitor(0, &[1])]);
+		// Make the next update insert fail after the monitor write in the same transaction.
+		store.conn.lock().unwrap().execute_batch("CREATE TRIGGER reject_updates BEFORE INSERT ON monitor_updates
+			BEGIN SELECT RAISE(ABORT, 'rejected'); END;").unwrap();
+		let writes = [monitor(0, &[2]), update(1)];
+		assert!(commit_monitors(&mut store.conn.lock().unwrap(), writes.iter().map(|write| (&funding_txo, write))).is_err());
+		assert_eq!(store.read(CHANNEL_MONITOR_NAMESPACE, &monitor_key(&funding_txo)).unwrap(), vec![1]);
+		assert!(stored_update_ids(&store, &funding_txo).is_empty());
+		std::mem::drop(store);
+		remove_db(&path);
+	}
+
+	#[test]
+	fn monitor_updates_are_replayed_on_read() {
+		let path = db_path("replay");
+		let monitor = channel_monitor();
+		let funding_txo = monitor.get_funding_txo().0;
+		let keys = KeysInterface::from(keys_manager(2));
+		{
+			let store = SqliteStore { inner: ObjOps::heap_alloc(nativeSqliteStore::open(&path, 2).unwrap()), is_owned: true };
+			let chain_monitor = chain_monitor(SqliteStore_as_Persist(&store));
+			watch_channel(&chain_monitor, monitor).unwrap();
+			for update_id in 1..=4 {
+				update_channel(&chain_monitor, funding_txo, update_id).unwrap();
+			}
+			// Updates 1 and 2 were stored, 3 rewrote the monitor, and 4 was stored again.
+			assert_eq!(stored_update_ids(store.get_native_ref(), &funding_txo), vec![4]);
+		}
+
+		let store = SqliteStore { inner: ObjOps::heap_alloc(nativeSqliteStore::open(&path, 2).unwrap()), is_owned: true };
+		let stored = read_channelmonitors(&SqliteStore_as_KVStore(&store), &keys).unwrap();
+		assert_eq!(stored[0].1.get_latest_update_id(), 3);
+		let monitors = store.get_native_ref().read_channelmonitors(&keys, &broadcaster().0, &fee_estimator(253), &logger()).unwrap();
+		assert_eq!(monitors.len(), 1);
+		assert_eq!(monitors[0].1.get_funding_txo().0, funding_txo);
+		assert_eq!(monitors[0].1.get_latest_update_id(), 4);
+		std::mem::drop(store);
+		remove_db(&path);
+	}
+}
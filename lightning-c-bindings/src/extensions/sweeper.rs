@@ -0,0 +1,781 @@
+//! A persisted sweeper for the outputs LDK hands back in `Event::SpendableOutputs`.
+//!
+//! The `SpendableOutputDescriptor`s in the event are the only record of how to spend those
+//! outputs, and they are lost if the process restarts before a sweep confirms. An
+//! [`OutputSweeper`] stores each descriptor in a [`KVStore`] as soon as it sees it, sweeps it to a
+//! destination script of the user's choosing, and keeps rebroadcasting the sweep, re-signing it at
+//! a higher feerate every few blocks, until it has confirmed beyond reorg risk.
+//!
+//! Pass the result of `OutputSweeper_event_handler` to the `ChannelManager` and `ChainMonitor`
+//! (or `BackgroundProcessor`) in place of your own `EventHandler`, and connect blocks via
+//! `OutputSweeper_as_Listen` or `OutputSweeper_as_Confirm`. When using `Confirm` with a chain
+//! source which only reports registered transactions and outputs, pass it to the sweeper as a
+//! `Filter`, with which the tracked outputs and their sweeps are registered.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use bitcoin::blockdata::script::Script;
+use bitcoin::blockdata::transaction::Transaction as BitcoinTransaction;
+use bitcoin::hash_types::Txid;
+use bitcoin::hashes::hex::{FromHex, ToHex};
+use bitcoin::secp256k1::Secp256k1;
+
+use lightning::chain::{Filter as _, WatchedOutput};
+use lightning::chain::chaininterface::{BroadcasterInterface as _, ConfirmationTarget, FeeEstimator as _};
+use lightning::chain::keysinterface::SpendableOutputDescriptor;
+use lightning::chain::transaction::OutPoint as RustOutPoint;
+use lightning::util::events::Event as nativeEvent;
+use lightning::util::logger::{Level, Logger as _};
+use lightning::util::ser::{Readable, Writeable, Writer};
+
+use crate::c_types::*;
+use crate::c_types::derived::{COption_FilterZ, CResult_NoneErrorZ, CVec_SpendableOutputDescriptorZ, CVec_u8Z};
+use crate::extensions::output_keys_manager;
+use crate::extensions::chain_listener::{as_confirm, as_listen, ChainListener};
+use crate::extensions::kv_store::{KVStore, none_result_from_rust};
+use crate::lightning::chain::{Confirm, Filter, Listen};
+use crate::lightning::chain::chaininterface::{BroadcasterInterface, FeeEstimator};
+use crate::lightning::chain::keysinterface::nativeKeysManager;
+use crate::lightning::util::events::{Event, EventHandler};
+use crate::lightning::util::logger::Logger;
+
+/// The namespace under which tracked outputs are stored, keyed by their outpoint.
+pub(crate) const SPENDABLE_OUTPUTS_NAMESPACE: &str = "spendable_outputs";
+
+/// How deep a sweep must be buried before the swept output is forgotten.
+const ANTI_REORG_DELAY: u32 = 6;
+/// How many blocks a sweep may stay unconfirmed before it is re-signed at a higher feerate.
+const FEE_BUMP_INTERVAL_BLOCKS: u32 = 3;
+/// The minimum feerate increase for a replacement to be relayed under BIP 125.
+const MIN_FEERATE_INCREMENT_SAT_PER_1000_WEIGHT: u32 = 253;
+
+fn descriptor_outpoint(descriptor: &SpendableOutputDescriptor) -> RustOutPoint {
+	match descriptor {
+		SpendableOutputDescriptor::StaticOutput { outpoint, .. } => *outpoint,
+		SpendableOutputDescriptor::DelayedPaymentOutput(descriptor) => descriptor.outpoint,
+		SpendableOutputDescriptor::StaticPaymentOutput(descriptor) => descriptor.outpoint,
+	}
+}
+
+fn descriptor_script(descriptor: &SpendableOutputDescriptor) -> &Script {
+	match descriptor {
+		SpendableOutputDescriptor::StaticOutput { output, .. } => &output.script_pubkey,
+		SpendableOutputDescriptor::DelayedPaymentOutput(descriptor) => &descriptor.output.script_pubkey,
+		SpendableOutputDescriptor::StaticPaymentOutput(descriptor) => &descriptor.output.script_pubkey,
+	}
+}
+
+struct SweepTransaction {
+	tx: BitcoinTransaction,
+	feerate_sat_per_1000_weight: u32,
+	/// The height at which the transaction was signed, or 0 if it was signed before we learned
+	/// of any block.
+	signed_height: u32,
+}
+
+struct TrackedOutput {
+	descriptor: SpendableOutputDescriptor,
+	/// The latest transaction we signed sweeping this output, if any.
+	sweep: Option<SweepTransaction>,
+	/// The transaction which spent the output and the height it confirmed at. This need not be
+	/// the latest sweep, an earlier version may confirm instead.
+	spent: Option<(Txid, u32)>,
+}
+
+impl Writeable for TrackedOutput {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), ::std::io::Error> {
+		self.descriptor.write(w)?;
+		match &self.sweep {
+			None => 0u8.write(w)?,
+			Some(sweep) => {
+				1u8.write(w)?;
+				sweep.tx.write(w)?;
+				sweep.feerate_sat_per_1000_weight.write(w)?;
+				sweep.signed_height.write(w)?;
+			},
+		}
+		match &self.spent {
+			None => 0u8.write(w),
+			Some((txid, height)) => {
+				1u8.write(w)?;
+				txid.write(w)?;
+				height.write(w)
+			},
+		}
+	}
+}
+impl Readable for TrackedOutput {
+	fn read<R: ::std::io::Read>(r: &mut R) -> Result<Self, lightning::ln::msgs::DecodeError> {
+		let descriptor = Readable::read(r)?;
+		let sweep = match <u8 as Readable>::read(r)? {
+			0 => None,
+			1 => Some(SweepTransaction { tx: Readable::read(r)?, feerate_sat_per_1000_weight: Readable::read(r)?, signed_height: Readable::read(r)? }),
+			_ => return Err(lightning::ln::msgs::DecodeError::InvalidValue),
+		};
+		let spent = match <u8 as Readable>::read(r)? {
+			0 => None,
+			1 => Some((Readable::read(r)?, Readable::read(r)?)),
+			_ => return Err(lightning::ln::msgs::DecodeError::InvalidValue),
+		};
+		Ok(Self { descriptor, sweep, spent })
+	}
+}
+
+struct SweeperState {
+	outputs: HashMap<RustOutPoint, TrackedOutput>,
+	/// The height of the best block we know of, or 0 if we have not seen one since starting.
+	best_height: u32,
+}
+
+pub(crate) struct nativeOutputSweeper {
+	store: KVStore,
+	/// A `KeysManager` for the user's seed, used to sign sweeps.
+	keys_manager: nativeKeysManager,
+	destination_script: Script,
+	/// The chain source with which tracked outputs and sweeps are registered, if any.
+	chain_source: Option<Filter>,
+	broadcaster: BroadcasterInterface,
+	fee_estimator: FeeEstimator,
+	logger: Logger,
+	secp_ctx: Secp256k1<bitcoin::secp256k1::All>,
+	state: Mutex<SweeperState>,
+}
+
+/// The key under which a tracked output is stored.
+fn output_key(outpoint: &RustOutPoint) -> String {
+	format!("{}_{}", outpoint.txid.to_hex(), outpoint.index)
+}
+/// Parses a key generated by [`output_key`].
+fn parse_output_key(key: &str) -> Option<RustOutPoint> {
+	let mut parts = key.splitn(2, '_');
+	let txid = Txid::from_hex(parts.next()?).ok()?;
+	let index = u16::from_str(parts.next()?).ok()?;
+	Some(RustOutPoint { txid, index })
+}
+
+/// Store updates and broadcasts decided on under the state lock. They are only carried out once
+/// the lock has been released, as the `KVStore` and `BroadcasterInterface` are user callbacks.
+#[derive(Default)]
+struct SweeperWork {
+	/// Tracked outputs to (re-)write, in order.
+	writes: Vec<(RustOutPoint, Vec<u8>)>,
+	/// Outputs which have been swept beyond reorg risk and can be forgotten.
+	removals: Vec<RustOutPoint>,
+	broadcasts: Vec<BitcoinTransaction>,
+	/// Tracked outputs to register with the chain source.
+	watched_outputs: Vec<WatchedOutput>,
+	/// Sweeps to register with the chain source, with the script they pay to.
+	watched_txs: Vec<(Txid, Script)>,
+}
+
+impl SweeperWork {
+	fn persist(&mut self, outpoint: &RustOutPoint, output: &TrackedOutput) {
+		self.writes.push((*outpoint, output.encode()));
+	}
+
+	fn watch_output(&mut self, outpoint: &RustOutPoint, output: &TrackedOutput) {
+		self.watched_outputs.push(WatchedOutput { block_hash: None, outpoint: *outpoint, script_pubkey: descriptor_script(&output.descriptor).clone() });
+	}
+}
+
+impl nativeOutputSweeper {
+	fn load(store: KVStore, seed: &[u8; 32], destination_script: Script, chain_source: Option<Filter>, broadcaster: BroadcasterInterface, fee_estimator: FeeEstimator, logger: Logger) -> Result<Self, std::io::Error> {
+		let mut outputs = HashMap::new();
+		for key in store.list(SPENDABLE_OUTPUTS_NAMESPACE)? {
+			let outpoint = parse_output_key(&key)
+				.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Invalid tracked output key {}", key)))?;
+			let data = store.read(SPENDABLE_OUTPUTS_NAMESPACE, &key)?;
+			let output: TrackedOutput = Readable::read(&mut Cursor::new(&data))
+				.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Failed to read tracked output {}: {:?}", key, e)))?;
+			outputs.insert(outpoint, output);
+		}
+		let mut work = SweeperWork::default();
+		for (outpoint, output) in outputs.iter() {
+			work.watch_output(outpoint, output);
+			if let Some(sweep) = &output.sweep {
+				work.watched_txs.push((sweep.tx.txid(), destination_script.clone()));
+			}
+		}
+		let sweeper = Self {
+			store, keys_manager: output_keys_manager(seed), destination_script, chain_source,
+			broadcaster, fee_estimator, logger, secp_ctx: Secp256k1::new(),
+			state: Mutex::new(SweeperState { outputs, best_height: 0 }),
+		};
+		sweeper.complete(work);
+		Ok(sweeper)
+	}
+
+	/// Carries out the work decided on under the state lock, which must no longer be held. Writes
+	/// happen before broadcasts, so that we never lose track of a transaction which may confirm.
+	fn complete(&self, work: SweeperWork) {
+		for (outpoint, data) in work.writes {
+			if let Err(e) = self.store.write(SPENDABLE_OUTPUTS_NAMESPACE, &output_key(&outpoint), &data) {
+				log_record!(self.logger, Level::Error, "Failed to persist tracked output {}: {}", output_key(&outpoint), e);
+			}
+		}
+		for outpoint in work.removals {
+			// If this fails the output is reloaded on restart, and forgotten again once we see a
+			// block, so there is no harm done.
+			if let Err(e) = self.store.remove(SPENDABLE_OUTPUTS_NAMESPACE, &output_key(&outpoint)) {
+				log_record!(self.logger, Level::Error, "Failed to remove swept output {}: {}", output_key(&outpoint), e);
+			}
+		}
+		if let Some(chain_source) = &self.chain_source {
+			// We never register outputs while processing a block, so there is no transaction in it
+			// spending them which would need processing.
+			for output in work.watched_outputs {
+				let _ = chain_source.register_output(output);
+			}
+			for (txid, script_pubkey) in work.watched_txs {
+				chain_source.register_tx(&txid, &script_pubkey);
+			}
+		}
+		for tx in work.broadcasts {
+			self.broadcaster.broadcast_transaction(&tx);
+		}
+	}
+
+	/// Runs `f` under the state lock, then carries out the work it queued.
+	fn update_state<F: FnOnce(&mut SweeperState, &mut SweeperWork)>(&self, f: F) {
+		let mut work = SweeperWork::default();
+		f(&mut self.state.lock().unwrap(), &mut work);
+		self.complete(work);
+	}
+
+	/// Starts tracking the given outputs, persisting them before sweeping them.
+	fn track_outputs(&self, descriptors: Vec<SpendableOutputDescriptor>) -> Result<(), std::io::Error> {
+		let new_outputs: Vec<TrackedOutput> = {
+			let state = self.state.lock().unwrap();
+			descriptors.into_iter()
+				.filter(|descriptor| !state.outputs.contains_key(&descriptor_outpoint(descriptor)))
+				.map(|descriptor| TrackedOutput { descriptor, sweep: None, spent: None })
+				.collect()
+		};
+		let mut persisted = Vec::with_capacity(new_outputs.len());
+		let mut res = Ok(());
+		for output in new_outputs {
+			let outpoint = descriptor_outpoint(&output.descriptor);
+			if let Err(e) = self.store.write(SPENDABLE_OUTPUTS_NAMESPACE, &output_key(&outpoint), &output.encode()) {
+				res = Err(e);
+				break;
+			}
+			persisted.push((outpoint, output));
+		}
+		self.update_state(|state, work| {
+			let mut new_outpoints = Vec::with_capacity(persisted.len());
+			for (outpoint, output) in persisted {
+				// Another call may have started tracking the same output in the meantime.
+				if state.outputs.contains_key(&outpoint) { continue; }
+				work.watch_output(&outpoint, &output);
+				state.outputs.insert(outpoint, output);
+				new_outpoints.push(outpoint);
+			}
+			if !new_outpoints.is_empty() {
+				let feerate = self.fee_estimator.get_est_sat_per_1000_weight(ConfirmationTarget::Background);
+				self.sweep(state, work, &new_outpoints, feerate);
+			}
+		});
+		res
+	}
+
+	/// Signs a transaction sweeping the given outputs at the given feerate, recording it as their
+	/// latest sweep and queueing it for broadcast. Returns false if the sweep could not be signed.
+	fn sweep(&self, state: &mut SweeperState, work: &mut SweeperWork, outpoints: &[RustOutPoint], feerate_sat_per_1000_weight: u32) -> bool {
+		let descriptors: Vec<_> = outpoints.iter().map(|outpoint| &state.outputs[outpoint].descriptor).collect();
+		let tx = match self.keys_manager.spend_spendable_outputs(&descriptors, Vec::new(), self.destination_script.clone(), feerate_sat_per_1000_weight, &self.secp_ctx) {
+			Ok(tx) => tx,
+			Err(()) => {
+				log_record!(self.logger, Level::Error, "Failed to sweep {} outputs at {} sat/kW, will retry next block", outpoints.len(), feerate_sat_per_1000_weight);
+				return false;
+			},
+		};
+		log_record!(self.logger, Level::Info, "Sweeping {} outputs in {} at {} sat/kW", outpoints.len(), tx.txid(), feerate_sat_per_1000_weight);
+		for outpoint in outpoints {
+			let output = state.outputs.get_mut(outpoint).unwrap();
+			output.sweep = Some(SweepTransaction { tx: tx.clone(), feerate_sat_per_1000_weight, signed_height: state.best_height });
+			work.persist(outpoint, output);
+		}
+		work.watched_txs.push((tx.txid(), self.destination_script.clone()));
+		work.broadcasts.push(tx);
+		true
+	}
+
+	fn bumped_feerate(&self, previous: u32) -> u32 {
+		let estimate = self.fee_estimator.get_est_sat_per_1000_weight(ConfirmationTarget::Normal);
+		std::cmp::max(std::cmp::max(estimate, previous + previous / 4), previous + MIN_FEERATE_INCREMENT_SAT_PER_1000_WEIGHT)
+	}
+
+	fn transaction_confirmed(&self, state: &mut SweeperState, work: &mut SweeperWork, tx: &BitcoinTransaction, height: u32) {
+		let txid = tx.txid();
+		for input in tx.input.iter() {
+			if input.previous_output.vout > u16::max_value() as u32 { continue; }
+			let outpoint = RustOutPoint { txid: input.previous_output.txid, index: input.previous_output.vout as u16 };
+			if let Some(output) = state.outputs.get_mut(&outpoint) {
+				output.spent = Some((txid, height));
+				work.persist(&outpoint, output);
+			}
+		}
+	}
+
+	fn transaction_unconfirmed(&self, state: &mut SweeperState, work: &mut SweeperWork, txid: &Txid) {
+		for (outpoint, output) in state.outputs.iter_mut() {
+			if output.spent.map(|(spend_txid, _)| spend_txid == *txid).unwrap_or(false) {
+				output.spent = None;
+				work.persist(outpoint, output);
+			}
+		}
+	}
+
+	fn block_disconnected(&self, state: &mut SweeperState, work: &mut SweeperWork, height: u32) {
+		let txids: Vec<Txid> = state.outputs.values()
+			.filter_map(|output| output.spent.filter(|(_, spend_height)| *spend_height >= height).map(|(txid, _)| txid))
+			.collect();
+		for txid in txids.iter() {
+			self.transaction_unconfirmed(state, work, txid);
+		}
+		state.best_height = height.saturating_sub(1);
+	}
+
+	/// Forgets outputs whose spend is buried deep enough, and rebroadcasts or fee-bumps the
+	/// sweeps of the remaining ones.
+	fn best_block_updated(&self, state: &mut SweeperState, work: &mut SweeperWork, height: u32) {
+		state.best_height = height;
+
+		let mut finished = Vec::new();
+		for (outpoint, output) in state.outputs.iter() {
+			if let Some((txid, spend_height)) = output.spent {
+				if spend_height + ANTI_REORG_DELAY <= height + 1 {
+					log_record!(self.logger, Level::Info, "Output {} was swept by {}", output_key(outpoint), txid);
+					finished.push(*outpoint);
+				}
+			}
+		}
+		for outpoint in finished {
+			state.outputs.remove(&outpoint);
+			work.removals.push(outpoint);
+		}
+
+		// Group unspent outputs by their latest sweep, so that each group is replaced as a whole.
+		let mut unswept = Vec::new();
+		let mut sweeps: HashMap<Txid, (Vec<RustOutPoint>, u32, u32)> = HashMap::new();
+		for (outpoint, output) in state.outputs.iter_mut() {
+			if output.spent.is_some() { continue; }
+			match &mut output.sweep {
+				None => unswept.push(*outpoint),
+				Some(sweep) => {
+					if sweep.signed_height == 0 { sweep.signed_height = height; }
+					let entry = sweeps.entry(sweep.tx.txid()).or_insert((Vec::new(), sweep.feerate_sat_per_1000_weight, sweep.signed_height));
+					entry.0.push(*outpoint);
+				},
+			}
+		}
+
+		if !unswept.is_empty() {
+			let feerate = self.fee_estimator.get_est_sat_per_1000_weight(ConfirmationTarget::Background);
+			self.sweep(state, work, &unswept, feerate);
+		}
+		for (_, (outpoints, feerate, signed_height)) in sweeps {
+			// If re-signing fails, e.g. because the outputs cannot pay the higher fee, keep
+			// rebroadcasting the previous sweep.
+			if height >= signed_height + FEE_BUMP_INTERVAL_BLOCKS && self.sweep(state, work, &outpoints, self.bumped_feerate(feerate)) {
+				continue;
+			}
+			work.broadcasts.push(state.outputs[&outpoints[0]].sweep.as_ref().unwrap().tx.clone());
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_OutputSweeperIOErrorZ
+pub union CResult_OutputSweeperIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut OutputSweeper,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_OutputSweeperIOErrorZ represents the result of a fallible operation,
+/// containing a OutputSweeper on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_OutputSweeperIOErrorZ {
+	/// The contents of this CResult_OutputSweeperIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_OutputSweeperIOErrorZPtr,
+	/// Whether this CResult_OutputSweeperIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_OutputSweeperIOErrorZ in the success state.
+pub extern "C" fn CResult_OutputSweeperIOErrorZ_ok(o: OutputSweeper) -> CResult_OutputSweeperIOErrorZ {
+	CResult_OutputSweeperIOErrorZ {
+		contents: CResult_OutputSweeperIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_OutputSweeperIOErrorZ in the error state.
+pub extern "C" fn CResult_OutputSweeperIOErrorZ_err(e: IOError) -> CResult_OutputSweeperIOErrorZ {
+	CResult_OutputSweeperIOErrorZ {
+		contents: CResult_OutputSweeperIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_OutputSweeperIOErrorZ_is_ok(o: &CResult_OutputSweeperIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_OutputSweeperIOErrorZ.
+pub extern "C" fn CResult_OutputSweeperIOErrorZ_free(_res: CResult_OutputSweeperIOErrorZ) { }
+impl Drop for CResult_OutputSweeperIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Persists `SpendableOutputDescriptor`s and sweeps them, rebroadcasting and fee-bumping the
+/// sweeps until they confirm. See the module documentation for details.
+#[must_use]
+#[repr(C)]
+pub struct OutputSweeper {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeOutputSweeper,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for OutputSweeper {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeOutputSweeper>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the OutputSweeper, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn OutputSweeper_free(this_obj: OutputSweeper) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn OutputSweeper_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeOutputSweeper); }
+}
+#[allow(unused)]
+impl OutputSweeper {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeOutputSweeper {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeOutputSweeper {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeOutputSweeper {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a sweeper which sweeps outputs to `destination_script`, loading any outputs tracked
+/// before a restart from `store`.
+///
+/// `seed` must be the seed passed to `KeysManager_new`. Sweeps are first signed at
+/// `fee_estimator`'s `Background` feerate, and are re-signed at the higher of its `Normal`
+/// feerate and a 25% increase every 3 blocks they stay unconfirmed.
+///
+/// If `chain_source` is set, each tracked output is registered with it, as is each sweep along
+/// with `destination_script`, including those loaded from `store`.
+///
+/// Previously tracked outputs are rebroadcast or swept once the first block is connected.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OutputSweeper_new(mut store: KVStore, seed: *const [u8; 32], mut destination_script: CVec_u8Z, mut chain_source: COption_FilterZ, mut broadcaster: BroadcasterInterface, mut fee_estimator: FeeEstimator, mut logger: Logger) -> CResult_OutputSweeperIOErrorZ {
+	let chain_source = if chain_source.is_some() { Some(chain_source.take()) } else { None };
+	match nativeOutputSweeper::load(store, unsafe { &*seed }, Script::from(destination_script.into_rust()), chain_source, broadcaster, fee_estimator, logger) {
+		Ok(sweeper) => CResult_OutputSweeperIOErrorZ_ok(OutputSweeper { inner: ObjOps::heap_alloc(sweeper), is_owned: true }),
+		Err(e) => CResult_OutputSweeperIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+/// Persists the given descriptors and sweeps them, as done for `Event::SpendableOutputs` by the
+/// handler returned from `OutputSweeper_event_handler`.
+///
+/// Returns an error if a descriptor could not be persisted, in which case it may not be tracked.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OutputSweeper_track_spendable_outputs(this_arg: &OutputSweeper, mut descriptors: CVec_SpendableOutputDescriptorZ) -> CResult_NoneErrorZ {
+	let mut local_descriptors = Vec::new(); for mut item in descriptors.into_rust().drain(..) { local_descriptors.push(item.into_native()); };
+	none_result_from_rust(this_arg.get_native_ref().track_outputs(local_descriptors))
+}
+
+/// Returns the number of outputs currently tracked, i.e. not yet swept beyond reorg risk.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OutputSweeper_tracked_output_count(this_arg: &OutputSweeper) -> usize {
+	this_arg.get_native_ref().state.lock().unwrap().outputs.len()
+}
+
+struct SweeperEventHandler {
+	sweeper: &'static nativeOutputSweeper,
+	inner: EventHandler,
+}
+
+/// Wraps `inner` in an [`EventHandler`] which tracks the outputs of `Event::SpendableOutputs`
+/// with this sweeper. All other events are passed on to `inner`, as are `SpendableOutputs`
+/// events whose outputs could not be persisted.
+///
+/// The returned handler must be freed before `this_arg` is.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OutputSweeper_event_handler(this_arg: &OutputSweeper, mut inner: EventHandler) -> EventHandler {
+	let handler = Box::new(SweeperEventHandler { sweeper: this_arg.get_native_ref(), inner });
+	EventHandler {
+		this_arg: Box::into_raw(handler) as *mut c_void,
+		handle_event: SweeperEventHandler_handle_event,
+		free: Some(SweeperEventHandler_free),
+	}
+}
+
+extern "C" fn SweeperEventHandler_handle_event(this_arg: *const c_void, event: &Event) {
+	let handler = unsafe { &*(this_arg as *const SweeperEventHandler) };
+	if let Event::SpendableOutputs { .. } = event {
+		if let nativeEvent::SpendableOutputs { outputs } = event.to_native() {
+			match handler.sweeper.track_outputs(outputs) {
+				Ok(()) => return,
+				Err(e) => log_record!(handler.sweeper.logger, Level::Error, "Failed to persist spendable outputs, passing them on: {}", e),
+			}
+		}
+	}
+	(handler.inner.handle_event)(handler.inner.this_arg, event);
+}
+extern "C" fn SweeperEventHandler_free(this_arg: *mut c_void) {
+	let _ = unsafe { Box::from_raw(this_arg as *mut SweeperEventHandler) };
+}
+
+impl ChainListener for nativeOutputSweeper {
+	fn transactions_confirmed(&self, txdata: &[BitcoinTransaction], height: u32) {
+		self.update_state(|state, work| {
+			for tx in txdata.iter() {
+				self.transaction_confirmed(state, work, tx, height);
+			}
+		});
+	}
+	fn transaction_unconfirmed(&self, txid: &Txid) {
+		self.update_state(|state, work| self.transaction_unconfirmed(state, work, txid));
+	}
+	fn best_block_updated(&self, height: u32) {
+		self.update_state(|state, work| self.best_block_updated(state, work, height));
+	}
+	fn block_disconnected(&self, height: u32) {
+		self.update_state(|state, work| self.block_disconnected(state, work, height));
+	}
+	fn relevant_txids(&self) -> Vec<Txid> {
+		let state = self.state.lock().unwrap();
+		state.outputs.values().filter_map(|output| output.spent.map(|(txid, _)| txid)).collect()
+	}
+}
+
+impl From<nativeOutputSweeper> for Listen {
+	fn from(obj: nativeOutputSweeper) -> Self {
+		let mut rust_obj = OutputSweeper { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = OutputSweeper_as_Listen(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(OutputSweeper_free_void);
+		ret
+	}
+}
+/// Constructs a new Listen which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned Listen must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn OutputSweeper_as_Listen(this_arg: &OutputSweeper) -> Listen {
+	as_listen(unsafe { ObjOps::untweak_ptr((*this_arg).inner) })
+}
+
+impl From<nativeOutputSweeper> for Confirm {
+	fn from(obj: nativeOutputSweeper) -> Self {
+		let mut rust_obj = OutputSweeper { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = OutputSweeper_as_Confirm(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(OutputSweeper_free_void);
+		ret
+	}
+}
+/// Constructs a new Confirm which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned Confirm must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn OutputSweeper_as_Confirm(this_arg: &OutputSweeper) -> Confirm {
+	as_confirm(unsafe { ObjOps::untweak_ptr((*this_arg).inner) })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Arc;
+	use bitcoin::blockdata::transaction::TxOut;
+	use bitcoin::hashes::Hash;
+	use lightning::chain::keysinterface::KeysInterface;
+	use crate::extensions::test_utils::{broadcaster, fee_estimator, filter, logger, MemoryStore, Registrations};
+
+	const SEED: [u8; 32] = [42; 32];
+
+	fn destination_script() -> Script {
+		let mut script = vec![0, 20];
+		script.extend_from_slice(&[3; 20]);
+		Script::from(script)
+	}
+
+	fn descriptor() -> SpendableOutputDescriptor {
+		SpendableOutputDescriptor::StaticOutput {
+			outpoint: RustOutPoint { txid: Txid::from_slice(&[1; 32]).unwrap(), index: 0 },
+			output: TxOut { value: 100_000, script_pubkey: output_keys_manager(&SEED).get_destination_script() },
+		}
+	}
+
+	fn sweeper(store: &Arc<MemoryStore>) -> (nativeOutputSweeper, Arc<Mutex<Vec<BitcoinTransaction>>>, Arc<Mutex<Registrations>>) {
+		let (broadcaster, broadcasted) = broadcaster();
+		let (filter, registrations) = filter();
+		let sweeper = nativeOutputSweeper::load(store.as_kv_store(), &SEED, destination_script(), Some(filter), broadcaster, fee_estimator(1000), logger()).unwrap();
+		(sweeper, broadcasted, registrations)
+	}
+
+	fn tracked_output_count(sweeper: &nativeOutputSweeper) -> usize {
+		sweeper.state.lock().unwrap().outputs.len()
+	}
+
+	#[test]
+	fn sweeps_are_fee_bumped_every_interval() {
+		let store = MemoryStore::new();
+		let (sweeper, broadcasted, registrations) = sweeper(&store);
+		ChainListener::best_block_updated(&sweeper, 100);
+		sweeper.track_outputs(vec![descriptor()]).unwrap();
+		let first = broadcasted.lock().unwrap()[0].clone();
+		assert_eq!(first.output[0].script_pubkey, destination_script());
+
+		// The sweep is rebroadcast until FEE_BUMP_INTERVAL_BLOCKS have passed since it was signed.
+		for height in 101..100 + FEE_BUMP_INTERVAL_BLOCKS {
+			ChainListener::best_block_updated(&sweeper, height);
+			assert_eq!(broadcasted.lock().unwrap().last().unwrap().txid(), first.txid());
+		}
+		ChainListener::best_block_updated(&sweeper, 100 + FEE_BUMP_INTERVAL_BLOCKS);
+		let bumped = broadcasted.lock().unwrap().last().unwrap().clone();
+		assert_ne!(bumped.txid(), first.txid());
+		assert!(bumped.output[0].value < first.output[0].value);
+		assert_eq!(broadcasted.lock().unwrap().len(), FEE_BUMP_INTERVAL_BLOCKS as usize + 1);
+
+		// The replacement is bumped again once another interval has passed.
+		ChainListener::best_block_updated(&sweeper, 100 + 2 * FEE_BUMP_INTERVAL_BLOCKS - 1);
+		assert_eq!(broadcasted.lock().unwrap().last().unwrap().txid(), bumped.txid());
+		ChainListener::best_block_updated(&sweeper, 100 + 2 * FEE_BUMP_INTERVAL_BLOCKS);
+		let rebumped = broadcasted.lock().unwrap().last().unwrap().clone();
+		assert!(rebumped.output[0].value < bumped.output[0].value);
+
+		// The output and each sweep were registered with the chain source.
+		let registrations = registrations.lock().unwrap();
+		assert_eq!(registrations.outputs, vec![(descriptor_outpoint(&descriptor()), descriptor_script(&descriptor()).clone())]);
+		assert_eq!(registrations.txs, vec![
+			(first.txid(), destination_script()), (bumped.txid(), destination_script()), (rebumped.txid(), destination_script()),
+		]);
+	}
+
+	#[test]
+	fn spent_outputs_are_forgotten_after_anti_reorg_delay() {
+		let store = MemoryStore::new();
+		let (sweeper, broadcasted, _) = sweeper(&store);
+		ChainListener::best_block_updated(&sweeper, 100);
+		sweeper.track_outputs(vec![descriptor()]).unwrap();
+		let sweep = broadcasted.lock().unwrap()[0].clone();
+
+		sweeper.transactions_confirmed(&[sweep.clone()], 101);
+		ChainListener::best_block_updated(&sweeper, 101);
+		assert_eq!(sweeper.relevant_txids(), vec![sweep.txid()]);
+		// The output is kept until its spend is ANTI_REORG_DELAY blocks deep, and not rebroadcast.
+		ChainListener::best_block_updated(&sweeper, 101 + ANTI_REORG_DELAY - 2);
+		assert_eq!(tracked_output_count(&sweeper), 1);
+		assert_eq!(broadcasted.lock().unwrap().len(), 1);
+
+		// Once the block confirming it is disconnected, the sweep is broadcast again.
+		ChainListener::block_disconnected(&sweeper, 101);
+		assert!(sweeper.relevant_txids().is_empty());
+		ChainListener::best_block_updated(&sweeper, 101);
+		assert_eq!(broadcasted.lock().unwrap().len(), 2);
+		assert_eq!(broadcasted.lock().unwrap()[1].txid(), sweep.txid());
+
+		// Likewise if it is reported unconfirmed through Confirm.
+		sweeper.transactions_confirmed(&[sweep.clone()], 102);
+		ChainListener::best_block_updated(&sweeper, 102);
+		ChainListener::transaction_unconfirmed(&sweeper, &sweep.txid());
+		assert!(sweeper.relevant_txids().is_empty());
+
+		sweeper.transactions_confirmed(&[sweep.clone()], 102);
+		ChainListener::best_block_updated(&sweeper, 102 + ANTI_REORG_DELAY - 2);
+		assert_eq!(tracked_output_count(&sweeper), 1);
+		assert_eq!(store.len(), 1);
+		ChainListener::best_block_updated(&sweeper, 102 + ANTI_REORG_DELAY - 1);
+		assert_eq!(tracked_output_count(&sweeper), 0);
+		assert_eq!(store.len(), 0);
+		assert!(sweeper.relevant_txids().is_empty());
+	}
+
+	#[test]
+	fn tracked_outputs_survive_restart() {
+		let store = MemoryStore::new();
+		let sweep = {
+			let (sweeper, broadcasted, _) = sweeper(&store);
+			ChainListener::best_block_updated(&sweeper, 100);
+			sweeper.track_outputs(vec![descriptor()]).unwrap();
+			let broadcasted = broadcasted.lock().unwrap();
+			broadcasted[0].clone()
+		};
+
+		let (sweeper, broadcasted, registrations) = sweeper(&store);
+		assert_eq!(tracked_output_count(&sweeper), 1);
+		{
+			let registrations = registrations.lock().unwrap();
+			assert_eq!(registrations.outputs, vec![(descriptor_outpoint(&descriptor()), descriptor_script(&descriptor()).clone())]);
+			assert_eq!(registrations.txs, vec![(sweep.txid(), destination_script())]);
+		}
+		// The previous sweep is rebroadcast rather than re-signed, as it was signed recently.
+		ChainListener::best_block_updated(&sweeper, 101);
+		assert_eq!(broadcasted.lock().unwrap().len(), 1);
+		assert_eq!(broadcasted.lock().unwrap()[0].txid(), sweep.txid());
+	}
+
+	#[test]
+	fn tracking_an_output_twice_sweeps_it_once() {
+		let store = MemoryStore::new();
+		let (sweeper, broadcasted, registrations) = sweeper(&store);
+		ChainListener::best_block_updated(&sweeper, 100);
+		sweeper.track_outputs(vec![descriptor()]).unwrap();
+		sweeper.track_outputs(vec![descriptor()]).unwrap();
+		assert_eq!(tracked_output_count(&sweeper), 1);
+		assert_eq!(broadcasted.lock().unwrap().len(), 1);
+		assert_eq!(registrations.lock().unwrap().outputs.len(), 1);
+	}
+}
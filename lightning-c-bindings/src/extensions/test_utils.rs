@@ -0,0 +1,372 @@
+//! Implementations of the bindings' callback traits for the tests of the other extension modules,
+//! standing in for the ones a host application would provide.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bitcoin::blockdata::script::Script;
+use bitcoin::blockdata::transaction::{OutPoint as BitcoinOutPoint, Transaction as BitcoinTransaction, TxIn, TxOut};
+use bitcoin::hash_types::Txid;
+use bitcoin::hashes::Hash;
+use bitcoin::secp256k1::{Message, Secp256k1, Signature as SecpSignature};
+use bitcoin::secp256k1::key::{PublicKey as SecpPublicKey, SecretKey as SecpSecretKey};
+
+use lightning::chain::ChannelMonitorUpdateErr;
+use lightning::chain::channelmonitor::{ChannelMonitor as RustChannelMonitor, ChannelMonitorUpdate as RustChannelMonitorUpdate};
+use lightning::chain::keysinterface::KeysManager;
+use lightning::chain::transaction::OutPoint as RustOutPoint;
+use lightning::ln::chan_utils::{ChannelPublicKeys, ChannelTransactionParameters, ClosingTransaction, CommitmentTransaction, CounterpartyChannelTransactionParameters, HolderCommitmentTransaction, HTLCOutputInCommitment, TxCreationKeys};
+use lightning::ln::features::InitFeatures;
+use lightning::ln::msgs::{ChannelMessageHandler, Init};
+use lightning::util::events::{Event, EventsProvider, MessageSendEvent, MessageSendEventsProvider};
+use lightning::util::ser::Readable;
+
+use crate::c_types::*;
+use crate::c_types::derived::{COption_C2Tuple_usizeTransactionZZ, COption_FilterZ, CResult_NoneChannelMonitorUpdateErrZ, CResult_NoneChannelMonitorUpdateErrZ_ok, CResult_NoneErrorZ, CVec_MonitorEventZ};
+use crate::extensions::hodl_invoice::{HodlInvoiceManager, nativeHodlInvoiceManager};
+use crate::extensions::kv_store::*;
+use crate::extensions::remote_signer::{nativeRemoteSignerServer, RemoteSignerServer};
+use crate::extensions::signer_policy::{PolicyKeysInterface_new, SignerPolicy};
+use crate::lightning::chain::{BestBlock_new, Filter, Watch, WatchedOutput};
+use crate::lightning::chain::chaininterface::{BroadcasterInterface, ConfirmationTarget, FeeEstimator};
+use crate::lightning::chain::chainmonitor::{ChainMonitor, ChainMonitor_new, nativeChainMonitor, Persist};
+use crate::lightning::chain::channelmonitor::{ChannelMonitor, ChannelMonitorUpdate};
+use crate::lightning::chain::keysinterface::{KeysInterface, Sign};
+use crate::lightning::chain::transaction::OutPoint;
+use crate::lightning::ln::channelmanager::{ChainParameters_new, ChannelManager, ChannelManager_new};
+use crate::lightning::ln::peer_handler::{ErroringMessageHandler_as_ChannelMessageHandler, ErroringMessageHandler_new, IgnoringMessageHandler_as_CustomMessageHandler, IgnoringMessageHandler_as_RoutingMessageHandler, IgnoringMessageHandler_new, MessageHandler_new, PeerManager, PeerManager_new};
+use crate::lightning::util::config::UserConfig_default;
+use crate::lightning::util::logger::{Logger, Record};
+
+/// An in-memory [`KVStore`], which can be handed out several times to simulate restarts.
+pub(crate) struct MemoryStore {
+	entries: Mutex<HashMap<(String, String), Vec<u8>>>,
+	fail_writes: AtomicBool,
+}
+
+impl MemoryStore {
+	pub(crate) fn new() -> Arc<Self> {
+		Arc::new(MemoryStore { entries: Mutex::new(HashMap::new()), fail_writes: AtomicBool::new(false) })
+	}
+
+	/// Makes writes fail without storing anything until called again with `false`.
+	pub(crate) fn set_fail_writes(&self, fail_writes: bool) {
+		self.fail_writes.store(fail_writes, Ordering::Release);
+	}
+
+	pub(crate) fn as_kv_store(self: &Arc<Self>) -> KVStore {
+		KVStore {
+			this_arg: Arc::into_raw(Arc::clone(self)) as *mut c_void,
+			read: MemoryStore_read,
+			write: MemoryStore_write,
+			remove: MemoryStore_remove,
+			list: MemoryStore_list,
+			free: Some(MemoryStore_free),
+		}
+	}
+
+	pub(crate) fn len(&self) -> usize {
+		self.entries.lock().unwrap().len()
+	}
+}
+
+fn memory_store<'a>(this_arg: *const c_void) -> &'a MemoryStore {
+	unsafe { &*(this_arg as *const MemoryStore) }
+}
+extern "C" fn MemoryStore_read(this_arg: *const c_void, key_namespace: Str, key: Str) -> CResult_CVec_u8ZIOErrorZ {
+	let entries = memory_store(this_arg).entries.lock().unwrap();
+	CResult_CVec_u8ZIOErrorZ::from_rust(entries.get(&(key_namespace.into_str().to_owned(), key.into_str().to_owned())).cloned()
+		.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "No value stored under the key")))
+}
+extern "C" fn MemoryStore_write(this_arg: *const c_void, key_namespace: Str, key: Str, value: u8slice) -> CResult_NoneErrorZ {
+	let store = memory_store(this_arg);
+	if store.fail_writes.load(Ordering::Acquire) {
+		return none_result_from_rust(Err(std::io::Error::new(std::io::ErrorKind::Other, "Writes are failing")));
+	}
+	store.entries.lock().unwrap().insert((key_namespace.into_str().to_owned(), key.into_str().to_owned()), value.to_slice().to_vec());
+	none_result_from_rust(Ok(()))
+}
+extern "C" fn MemoryStore_remove(this_arg: *const c_void, key_namespace: Str, key: Str) -> CResult_NoneErrorZ {
+	memory_store(this_arg).entries.lock().unwrap().remove(&(key_namespace.into_str().to_owned(), key.into_str().to_owned()));
+	none_result_from_rust(Ok(()))
+}
+extern "C" fn MemoryStore_list(this_arg: *const c_void, key_namespace: Str) -> CResult_CVec_StrZIOErrorZ {
+	let entries = memory_store(this_arg).entries.lock().unwrap();
+	CResult_CVec_StrZIOErrorZ::from_rust(Ok(entries.keys()
+		.filter(|(entry_namespace, _)| entry_namespace == key_namespace.into_str())
+		.map(|(_, key)| key.clone()).collect()))
+}
+extern "C" fn MemoryStore_free(this_arg: *mut c_void) {
+	let _ = unsafe { Arc::from_raw(this_arg as *const MemoryStore) };
+}
+
+/// Returns a `BroadcasterInterface` which records the transactions it is given.
+pub(crate) fn broadcaster() -> (BroadcasterInterface, Arc<Mutex<Vec<BitcoinTransaction>>>) {
+	let broadcasted = Arc::new(Mutex::new(Vec::new()));
+	(BroadcasterInterface {
+		this_arg: Arc::into_raw(Arc::clone(&broadcasted)) as *mut c_void,
+		broadcast_transaction: Broadcaster_broadcast_transaction,
+		free: Some(Broadcaster_free),
+	}, broadcasted)
+}
+extern "C" fn Broadcaster_broadcast_transaction(this_arg: *const c_void, tx: Transaction) {
+	let broadcasted = unsafe { &*(this_arg as *const Mutex<Vec<BitcoinTransaction>>) };
+	broadcasted.lock().unwrap().push(tx.into_bitcoin());
+}
+extern "C" fn Broadcaster_free(this_arg: *mut c_void) {
+	let _ = unsafe { Arc::from_raw(this_arg as *const Mutex<Vec<BitcoinTransaction>>) };
+}
+
+/// Returns a `FeeEstimator` which returns `feerate_sat_per_1000_weight` for every target.
+pub(crate) fn fee_estimator(feerate_sat_per_1000_weight: u32) -> FeeEstimator {
+	FeeEstimator {
+		this_arg: feerate_sat_per_1000_weight as usize as *mut c_void,
+		get_est_sat_per_1000_weight: FeeEstimator_get_est_sat_per_1000_weight,
+		free: None,
+	}
+}
+extern "C" fn FeeEstimator_get_est_sat_per_1000_weight(this_arg: *const c_void, _confirmation_target: ConfirmationTarget) -> u32 {
+	this_arg as usize as u32
+}
+
+/// Returns a `Logger` which discards everything.
+pub(crate) fn logger() -> Logger {
+	Logger { this_arg: std::ptr::null_mut(), log: Logger_log, free: None }
+}
+extern "C" fn Logger_log(_this_arg: *const c_void, _record: &Record) {}
+
+/// The transactions and outputs registered with a [`filter`].
+#[derive(Default)]
+pub(crate) struct Registrations {
+	pub(crate) txs: Vec<(Txid, Script)>,
+	pub(crate) outputs: Vec<(RustOutPoint, Script)>,
+}
+
+/// Returns a `Filter` which records what is registered with it.
+pub(crate) fn filter() -> (Filter, Arc<Mutex<Registrations>>) {
+	let registrations = Arc::new(Mutex::new(Registrations::default()));
+	(Filter {
+		this_arg: Arc::into_raw(Arc::clone(&registrations)) as *mut c_void,
+		register_tx: Filter_register_tx,
+		register_output: Filter_register_output,
+		free: Some(Filter_free),
+	}, registrations)
+}
+extern "C" fn Filter_register_tx(this_arg: *const c_void, txid: *const [u8; 32], script_pubkey: u8slice) {
+	let registrations = unsafe { &*(this_arg as *const Mutex<Registrations>) };
+	let txid = Txid::from_slice(&unsafe { &*txid }[..]).unwrap();
+	registrations.lock().unwrap().txs.push((txid, Script::from(script_pubkey.to_slice().to_vec())));
+}
+extern "C" fn Filter_register_output(this_arg: *const c_void, output: WatchedOutput) -> COption_C2Tuple_usizeTransactionZZ {
+	let registrations = unsafe { &*(this_arg as *const Mutex<Registrations>) };
+	let output = output.get_native_ref();
+	registrations.lock().unwrap().outputs.push((output.outpoint, output.script_pubkey.clone()));
+	COption_C2Tuple_usizeTransactionZZ::None
+}
+extern "C" fn Filter_free(this_arg: *mut c_void) {
+	let _ = unsafe { Arc::from_raw(this_arg as *const Mutex<Registrations>) };
+}
+
+/// Returns a `Watch` which accepts every channel monitor and update without storing them.
+pub(crate) fn watch() -> Watch {
+	Watch {
+		this_arg: std::ptr::null_mut(),
+		watch_channel: Watch_watch_channel,
+		update_channel: Watch_update_channel,
+		release_pending_monitor_events: Watch_release_pending_monitor_events,
+		free: None,
+	}
+}
+extern "C" fn Watch_watch_channel(_this_arg: *const c_void, _funding_txo: OutPoint, _monitor: ChannelMonitor) -> CResult_NoneChannelMonitorUpdateErrZ {
+	CResult_NoneChannelMonitorUpdateErrZ_ok()
+}
+extern "C" fn Watch_update_channel(_this_arg: *const c_void, _funding_txo: OutPoint, _update: ChannelMonitorUpdate) -> CResult_NoneChannelMonitorUpdateErrZ {
+	CResult_NoneChannelMonitorUpdateErrZ_ok()
+}
+extern "C" fn Watch_release_pending_monitor_events(_this_arg: *const c_void) -> CVec_MonitorEventZ {
+	Vec::new().into()
+}
+
+/// Returns a `Watch` which keeps the `ChannelMonitor`s it is handed, and accepts every update
+/// without applying it.
+pub(crate) fn recording_watch() -> (Watch, Arc<Mutex<Vec<RustChannelMonitor<Sign>>>>) {
+	let monitors = Arc::new(Mutex::new(Vec::new()));
+	(Watch {
+		this_arg: Arc::into_raw(Arc::clone(&monitors)) as *mut c_void,
+		watch_channel: RecordingWatch_watch_channel,
+		update_channel: Watch_update_channel,
+		release_pending_monitor_events: Watch_release_pending_monitor_events,
+		free: Some(RecordingWatch_free),
+	}, monitors)
+}
+extern "C" fn RecordingWatch_watch_channel(this_arg: *const c_void, _funding_txo: OutPoint, monitor: ChannelMonitor) -> CResult_NoneChannelMonitorUpdateErrZ {
+	let monitors = unsafe { &*(this_arg as *const Mutex<Vec<RustChannelMonitor<Sign>>>) };
+	monitors.lock().unwrap().push(*unsafe { Box::from_raw(monitor.take_inner()) });
+	CResult_NoneChannelMonitorUpdateErrZ_ok()
+}
+extern "C" fn RecordingWatch_free(this_arg: *mut c_void) {
+	let _ = unsafe { Arc::from_raw(this_arg as *const Mutex<Vec<RustChannelMonitor<Sign>>>) };
+}
+
+/// Returns a `KeysManager` derived from `seed`, which hands out the same keys every time.
+pub(crate) fn keys_manager(seed: u8) -> KeysManager {
+	KeysManager::new(&[seed; 32], 1, 2)
+}
+
+/// Returns a testnet `ChannelManager` with keys derived from `seed`, at the given height.
+fn node(seed: u8, watch: Watch, best_height: u32) -> ChannelManager {
+	let keys = KeysInterface::from(keys_manager(seed));
+	let params = ChainParameters_new(crate::bitcoin::network::Network::Testnet, BestBlock_new(ThirtyTwoBytes { data: [0; 32] }, best_height));
+	ChannelManager_new(fee_estimator(253), watch, broadcaster().0, logger(), keys, UserConfig_default(), params)
+}
+
+/// Returns a testnet `ChannelManager` without any channels, at the given height.
+pub(crate) fn channel_manager(best_height: u32) -> ChannelManager {
+	node(3, watch(), best_height)
+}
+
+/// Returns a `PeerManager` with the node secret `[seed; 32]`, which ignores gossip and custom
+/// messages and rejects channels. Its message handlers are leaked.
+pub(crate) fn peer_manager(seed: u8) -> PeerManager {
+	let ignoring = Box::leak(Box::new(IgnoringMessageHandler_new()));
+	let erroring = Box::leak(Box::new(ErroringMessageHandler_new()));
+	let message_handler = MessageHandler_new(ErroringMessageHandler_as_ChannelMessageHandler(erroring), IgnoringMessageHandler_as_RoutingMessageHandler(ignoring));
+	let node_secret = SecretKey::from_rust(SecpSecretKey::from_slice(&[seed; 32]).unwrap());
+	PeerManager_new(message_handler, node_secret, &[seed; 32], logger(), IgnoringMessageHandler_as_CustomMessageHandler(ignoring))
+}
+
+/// Opens an (unconfirmed) channel between two fresh `ChannelManager`s and returns the
+/// `ChannelMonitor` the accepting node handed to its `Watch`, at update id 0.
+///
+/// The monitor's signer can be read back by any `KeysManager`.
+pub(crate) fn channel_monitor() -> RustChannelMonitor<Sign> {
+	let (fundee_watch, monitors) = recording_watch();
+	let funder_manager = node(1, watch(), 0);
+	let fundee_manager = node(2, fundee_watch, 0);
+	let (funder, fundee) = (funder_manager.get_native_ref(), fundee_manager.get_native_ref());
+	let (funder_id, fundee_id) = (funder.get_our_node_id(), fundee.get_our_node_id());
+	funder.peer_connected(&fundee_id, &Init { features: InitFeatures::known() });
+	fundee.peer_connected(&funder_id, &Init { features: InitFeatures::known() });
+
+	let temporary_channel_id = funder.create_channel(fundee_id, 100_000, 0, 42, None).unwrap();
+	let open_channel = match &funder.get_and_clear_pending_msg_events()[..] {
+		[MessageSendEvent::SendOpenChannel { msg, .. }] => msg.clone(),
+		_ => panic!("Expected an open_channel message"),
+	};
+	fundee.handle_open_channel(&funder_id, InitFeatures::known(), &open_channel);
+	let accept_channel = match &fundee.get_and_clear_pending_msg_events()[..] {
+		[MessageSendEvent::SendAcceptChannel { msg, .. }] => msg.clone(),
+		_ => panic!("Expected an accept_channel message"),
+	};
+	funder.handle_accept_channel(&fundee_id, InitFeatures::known(), &accept_channel);
+
+	let funding_output = RefCell::new(None);
+	funder.process_pending_events(|event: &Event| if let Event::FundingGenerationReady { channel_value_satoshis, output_script, .. } = event {
+		*funding_output.borrow_mut() = Some(TxOut { value: *channel_value_satoshis, script_pubkey: output_script.clone() });
+	});
+	let funding_tx = BitcoinTransaction {
+		version: 2,
+		lock_time: 0,
+		// The ChannelManager only checks that the inputs look like signed segwit spends.
+		input: vec![TxIn { previous_output: BitcoinOutPoint::null(), script_sig: Script::new(), sequence: 0xffffffff, witness: vec![vec![1]] }],
+		output: vec![funding_output.into_inner().expect("Expected a FundingGenerationReady event")],
+	};
+	funder.funding_transaction_generated(&temporary_channel_id, funding_tx).unwrap();
+	let funding_created = match &funder.get_and_clear_pending_msg_events()[..] {
+		[MessageSendEvent::SendFundingCreated { msg, .. }] => msg.clone(),
+		_ => panic!("Expected a funding_created message"),
+	};
+	fundee.handle_funding_created(&funder_id, &funding_created);
+	let monitor = monitors.lock().unwrap().pop().expect("Expected the fundee to watch the channel");
+	monitor
+}
+
+/// Returns a `ChainMonitor` without a chain source, which persists through `persister`.
+pub(crate) fn chain_monitor(persister: Persist) -> ChainMonitor {
+	ChainMonitor_new(COption_FilterZ::None, broadcaster().0, logger(), fee_estimator(253), persister)
+}
+
+/// Hands `monitor` to `chain_monitor`, as the `ChannelManager` does once a channel is funded.
+pub(crate) fn watch_channel(chain_monitor: &ChainMonitor, monitor: RustChannelMonitor<Sign>) -> Result<(), ChannelMonitorUpdateErr> {
+	let funding_txo = monitor.get_funding_txo().0;
+	<nativeChainMonitor as lightning::chain::Watch<_>>::watch_channel(chain_monitor.get_native_ref(), funding_txo, monitor)
+}
+
+/// Applies [`monitor_update`]`(update_id)` to the channel's monitor in `chain_monitor`.
+pub(crate) fn update_channel(chain_monitor: &ChainMonitor, funding_txo: RustOutPoint, update_id: u64) -> Result<(), ChannelMonitorUpdateErr> {
+	<nativeChainMonitor as lightning::chain::Watch<_>>::update_channel(chain_monitor.get_native_ref(), funding_txo, monitor_update(update_id))
+}
+
+/// Builds a `ChannelMonitorUpdate` with the given id and no steps, which a `ChannelMonitor` at
+/// `update_id - 1` accepts without any other change.
+pub(crate) fn monitor_update(update_id: u64) -> RustChannelMonitorUpdate {
+	// The serialization version prefix, the update id, no steps and an empty TLV stream.
+	let mut data = vec![1, 1];
+	data.extend_from_slice(&update_id.to_be_bytes());
+	data.extend_from_slice(&0u64.to_be_bytes());
+	data.push(0);
+	Readable::read(&mut Cursor::new(&data)).unwrap()
+}
+
+/// The funding outpoint of the channels built by [`channel_parameters`].
+pub(crate) fn funding_outpoint() -> RustOutPoint {
+	RustOutPoint { txid: Txid::from_inner([42; 32]), index: 1 }
+}
+
+/// Builds the parameters of an outbound channel between the given keys.
+pub(crate) fn channel_parameters(holder_pubkeys: &ChannelPublicKeys, counterparty_pubkeys: &ChannelPublicKeys, holder_selected_contest_delay: u16) -> ChannelTransactionParameters {
+	ChannelTransactionParameters {
+		holder_pubkeys: holder_pubkeys.clone(),
+		holder_selected_contest_delay,
+		is_outbound_from_holder: true,
+		counterparty_parameters: Some(CounterpartyChannelTransactionParameters { pubkeys: counterparty_pubkeys.clone(), selected_contest_delay: 144 }),
+		funding_outpoint: Some(funding_outpoint()),
+		opt_anchors: None,
+	}
+}
+
+/// Builds a commitment transaction without HTLCs for the channel, broadcastable by us if
+/// `holder_broadcast` and by our counterparty otherwise.
+pub(crate) fn commitment_transaction(params: &ChannelTransactionParameters, holder_broadcast: bool, commitment_number: u64, per_commitment_point: &SecpPublicKey) -> CommitmentTransaction {
+	let directed = if holder_broadcast { params.as_holder_broadcastable() } else { params.as_counterparty_broadcastable() };
+	let keys = TxCreationKeys::from_channel_static_keys(per_commitment_point, directed.broadcaster_pubkeys(), directed.countersignatory_pubkeys(), secp256k1::SECP256K1).unwrap();
+	CommitmentTransaction::new_with_auxiliary_htlc_data(commitment_number, 600_000, 400_000, false,
+		directed.broadcaster_pubkeys().funding_pubkey, directed.countersignatory_pubkeys().funding_pubkey,
+		keys, 253, &mut Vec::<(HTLCOutputInCommitment, ())>::new(), &directed)
+}
+
+/// Builds a closing transaction spending the channel's funding output.
+pub(crate) fn closing_transaction(to_holder_script: Script, to_counterparty_script: Script) -> ClosingTransaction {
+	ClosingTransaction::new(500_000, 400_000, to_holder_script, to_counterparty_script, funding_outpoint().into_bitcoin_outpoint())
+}
+
+/// Builds a holder commitment transaction for the channel, with a dummy counterparty signature.
+pub(crate) fn holder_commitment(params: &ChannelTransactionParameters, commitment_number: u64, per_commitment_point: &SecpPublicKey) -> HolderCommitmentTransaction {
+	let secp_ctx = Secp256k1::new();
+	let counterparty_sig: SecpSignature = secp_ctx.sign(&Message::from_slice(&[1; 32]).unwrap(), &SecpSecretKey::from_slice(&[1; 32]).unwrap());
+	let counterparty_funding_key = params.counterparty_parameters.as_ref().unwrap().pubkeys.funding_pubkey;
+	HolderCommitmentTransaction::new(commitment_transaction(params, true, commitment_number, per_commitment_point),
+		counterparty_sig, Vec::new(), &params.holder_pubkeys.funding_pubkey, &counterparty_funding_key)
+}
+
+/// Returns a `RemoteSignerServer` for [`keys_manager`]`(1)`, loading its signers from `store`.
+pub(crate) fn remote_signer_server(store: &Arc<MemoryStore>) -> RemoteSignerServer {
+	let server = nativeRemoteSignerServer::load(KeysInterface::from(keys_manager(1)), store.as_kv_store()).unwrap();
+	RemoteSignerServer { inner: ObjOps::heap_alloc(server), is_owned: true }
+}
+
+/// Returns a `KeysInterface` enforcing `policy` on top of [`keys_manager`]`(1)`, storing its
+/// channels' state in `store`.
+pub(crate) fn policy_keys(policy: SignerPolicy, store: &Arc<MemoryStore>) -> KeysInterface {
+	PolicyKeysInterface_new(KeysInterface::from(keys_manager(1)), policy, store.as_kv_store(), logger())
+}
+
+/// Returns a `HodlInvoiceManager` for `channel_manager`, loading its invoices from `store`.
+pub(crate) fn hodl_invoice_manager(channel_manager: &ChannelManager, store: &Arc<MemoryStore>) -> HodlInvoiceManager {
+	let manager = nativeHodlInvoiceManager::load(channel_manager.get_native_ref(), store.as_kv_store(), logger()).unwrap();
+	HodlInvoiceManager { inner: ObjOps::heap_alloc(manager), is_owned: true }
+}
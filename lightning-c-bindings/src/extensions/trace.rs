@@ -0,0 +1,832 @@
+//! Tracing of the decrypted Lightning messages exchanged with peers, for debugging
+//! interoperability issues.
+//!
+//! A [`TracingMessageHandler`] wraps the `ChannelMessageHandler` and `RoutingMessageHandler`
+//! passed to `MessageHandler_new` and the `CustomMessageHandler` passed to `PeerManager_new`,
+//! reporting each message the `PeerManager` delivers to them (inbound) and each message they ask
+//! the `PeerManager` to send (outbound) to a [`MessageTracer`] before passing it on unchanged. A [`MessageTraceFile`] is provided which
+//! writes every message to a file with a timestamp.
+//!
+//! Messages are reported as their BOLT 1 type number and their serialized payload (excluding
+//! the type), which can be decoded with the matching `*_read` function, eg `OpenChannel_read`
+//! for type 32. Custom messages are reported with the type and payload given by their `Type`.
+//!
+//! As the tracer sits between the `PeerManager` and its message handlers, it does not see
+//! messages which the `PeerManager` handles itself (`ping`, `pong` and `gossip_timestamp_filter`),
+//! and outbound `init` messages. Gossip messages which the handlers do not associate with a
+//! single peer (inbound gossip handled by the `RoutingMessageHandler`, outbound broadcasts and
+//! the announcements the `PeerManager` sends a new peer to sync its routing table) are reported
+//! with an all-zero node id.
+
+use std::ffi::c_void;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use lightning::util::ser::Writeable;
+
+use crate::c_types::*;
+use crate::lightning::ln::msgs::{ChannelMessageHandler, RoutingMessageHandler, ErrorAction};
+use crate::lightning::ln::peer_handler::CustomMessageHandler;
+use crate::lightning::ln::wire::{CustomMessageReader, Type};
+use crate::lightning::util::events::{MessageSendEvent, MessageSendEventsProvider};
+
+/// The magic bytes at the start of a trace file, followed by a big-endian u16 version.
+const TRACE_FILE_MAGIC: &[u8; 8] = b"LDKTRACE";
+const TRACE_FILE_VERSION: u16 = 1;
+
+// LDK does not expose the BOLT 1 type of its messages (`wire::Encode` is crate-private), so
+// they are listed here.
+const INIT: u16 = 16;
+const ERROR: u16 = 17;
+const OPEN_CHANNEL: u16 = 32;
+const ACCEPT_CHANNEL: u16 = 33;
+const FUNDING_CREATED: u16 = 34;
+const FUNDING_SIGNED: u16 = 35;
+const FUNDING_LOCKED: u16 = 36;
+const SHUTDOWN: u16 = 38;
+const CLOSING_SIGNED: u16 = 39;
+const UPDATE_ADD_HTLC: u16 = 128;
+const UPDATE_FULFILL_HTLC: u16 = 130;
+const UPDATE_FAIL_HTLC: u16 = 131;
+const COMMITMENT_SIGNED: u16 = 132;
+const REVOKE_AND_ACK: u16 = 133;
+const UPDATE_FEE: u16 = 134;
+const UPDATE_FAIL_MALFORMED_HTLC: u16 = 135;
+const CHANNEL_REESTABLISH: u16 = 136;
+const CHANNEL_ANNOUNCEMENT: u16 = 256;
+const NODE_ANNOUNCEMENT: u16 = 257;
+const CHANNEL_UPDATE: u16 = 258;
+const ANNOUNCEMENT_SIGNATURES: u16 = 259;
+const QUERY_SHORT_CHANNEL_IDS: u16 = 261;
+const REPLY_SHORT_CHANNEL_IDS_END: u16 = 262;
+const QUERY_CHANNEL_RANGE: u16 = 263;
+const REPLY_CHANNEL_RANGE: u16 = 264;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// Whether a traced message was received from or sent to a peer.
+pub enum MessageDirection {
+	/// The message was received from the peer.
+	Inbound,
+	/// The message is being sent to the peer.
+	Outbound,
+}
+
+/// Receives every message passing through a [`TracingMessageHandler`].
+#[repr(C)]
+pub struct MessageTracer {
+	/// An opaque pointer which is passed to your function implementations as an argument.
+	/// This has no meaning in the LDK, and can be NULL or any other value.
+	pub this_arg: *mut c_void,
+	/// Called with each message received from or about to be sent to the peer with the given
+	/// node id. `msg_type` is the BOLT 1 message type and `data` the serialized message without
+	/// its type, which is only valid for the duration of the call.
+	///
+	/// This is called from within `PeerManager` and `ChannelManager` calls and should return
+	/// quickly. The message is passed on whether or not tracing succeeds.
+	pub trace: extern "C" fn (this_arg: *const c_void, node_id: PublicKey, direction: MessageDirection, msg_type: u16, data: u8slice),
+	/// Frees any resources associated with this object given its this_arg pointer.
+	/// Does not need to free the outer struct containing function pointers and may be NULL is no resources need to be freed.
+	pub free: Option<extern "C" fn(this_arg: *mut c_void)>,
+}
+unsafe impl Send for MessageTracer {}
+unsafe impl Sync for MessageTracer {}
+#[no_mangle]
+pub(crate) extern "C" fn MessageTracer_clone_fields(orig: &MessageTracer) -> MessageTracer {
+	MessageTracer {
+		this_arg: orig.this_arg,
+		trace: Clone::clone(&orig.trace),
+		free: Clone::clone(&orig.free),
+	}
+}
+/// Calls the free function if one is set
+#[no_mangle]
+pub extern "C" fn MessageTracer_free(this_ptr: MessageTracer) { }
+impl Drop for MessageTracer {
+	fn drop(&mut self) {
+		if let Some(f) = self.free {
+			f(self.this_arg);
+		}
+	}
+}
+
+pub(crate) struct nativeTracingMessageHandler {
+	chan_handler: ChannelMessageHandler,
+	route_handler: RoutingMessageHandler,
+	custom_handler: CustomMessageHandler,
+	tracer: MessageTracer,
+}
+
+impl nativeTracingMessageHandler {
+	fn trace<M: Writeable>(&self, node_id: &PublicKey, direction: MessageDirection, msg_type: u16, msg: &M) {
+		let data = msg.encode();
+		(self.tracer.trace)(self.tracer.this_arg, node_id.clone(), direction, msg_type, u8slice::from_slice(&data));
+	}
+
+	fn trace_custom(&self, node_id: &PublicKey, direction: MessageDirection, msg: &Type) {
+		let data = (msg.write)(msg.this_arg);
+		(self.tracer.trace)(self.tracer.this_arg, node_id.clone(), direction, (msg.type_id)(msg.this_arg), u8slice::from_slice(data.as_slice()));
+	}
+
+	fn trace_gossip<M: Writeable>(&self, direction: MessageDirection, msg_type: u16, msg: &M) {
+		self.trace(&PublicKey { compressed_form: [0; 33] }, direction, msg_type, msg);
+	}
+
+	fn trace_outbound_events(&self, events: &crate::c_types::derived::CVec_MessageSendEventZ) {
+		let out = MessageDirection::Outbound;
+		for event in events.as_slice() {
+			match event {
+				MessageSendEvent::SendAcceptChannel { node_id, msg } => self.trace(node_id, out, ACCEPT_CHANNEL, msg.get_native_ref()),
+				MessageSendEvent::SendOpenChannel { node_id, msg } => self.trace(node_id, out, OPEN_CHANNEL, msg.get_native_ref()),
+				MessageSendEvent::SendFundingCreated { node_id, msg } => self.trace(node_id, out, FUNDING_CREATED, msg.get_native_ref()),
+				MessageSendEvent::SendFundingSigned { node_id, msg } => self.trace(node_id, out, FUNDING_SIGNED, msg.get_native_ref()),
+				MessageSendEvent::SendFundingLocked { node_id, msg } => self.trace(node_id, out, FUNDING_LOCKED, msg.get_native_ref()),
+				MessageSendEvent::SendAnnouncementSignatures { node_id, msg } => self.trace(node_id, out, ANNOUNCEMENT_SIGNATURES, msg.get_native_ref()),
+				MessageSendEvent::UpdateHTLCs { node_id, updates } => {
+					// Traced in the order the PeerManager sends them.
+					let updates = updates.get_native_ref();
+					for msg in updates.update_add_htlcs.iter() { self.trace(node_id, out, UPDATE_ADD_HTLC, msg); }
+					for msg in updates.update_fulfill_htlcs.iter() { self.trace(node_id, out, UPDATE_FULFILL_HTLC, msg); }
+					for msg in updates.update_fail_htlcs.iter() { self.trace(node_id, out, UPDATE_FAIL_HTLC, msg); }
+					for msg in updates.update_fail_malformed_htlcs.iter() { self.trace(node_id, out, UPDATE_FAIL_MALFORMED_HTLC, msg); }
+					if let Some(msg) = &updates.update_fee { self.trace(node_id, out, UPDATE_FEE, msg); }
+					self.trace(node_id, out, COMMITMENT_SIGNED, &updates.commitment_signed);
+				},
+				MessageSendEvent::SendRevokeAndACK { node_id, msg } => self.trace(node_id, out, REVOKE_AND_ACK, msg.get_native_ref()),
+				MessageSendEvent::SendClosingSigned { node_id, msg } => self.trace(node_id, out, CLOSING_SIGNED, msg.get_native_ref()),
+				MessageSendEvent::SendShutdown { node_id, msg } => self.trace(node_id, out, SHUTDOWN, msg.get_native_ref()),
+				MessageSendEvent::SendChannelReestablish { node_id, msg } => self.trace(node_id, out, CHANNEL_REESTABLISH, msg.get_native_ref()),
+				MessageSendEvent::BroadcastChannelAnnouncement { msg, update_msg } => {
+					self.trace_gossip(out, CHANNEL_ANNOUNCEMENT, msg.get_native_ref());
+					self.trace_gossip(out, CHANNEL_UPDATE, update_msg.get_native_ref());
+				},
+				MessageSendEvent::BroadcastNodeAnnouncement { msg } => self.trace_gossip(out, NODE_ANNOUNCEMENT, msg.get_native_ref()),
+				MessageSendEvent::BroadcastChannelUpdate { msg } => self.trace_gossip(out, CHANNEL_UPDATE, msg.get_native_ref()),
+				MessageSendEvent::SendChannelUpdate { node_id, msg } => self.trace(node_id, out, CHANNEL_UPDATE, msg.get_native_ref()),
+				MessageSendEvent::HandleError { node_id, action } => match action {
+					ErrorAction::DisconnectPeer { msg } if !msg.inner.is_null() => self.trace(node_id, out, ERROR, msg.get_native_ref()),
+					ErrorAction::SendErrorMessage { msg } => self.trace(node_id, out, ERROR, msg.get_native_ref()),
+					_ => {},
+				},
+				MessageSendEvent::SendChannelRangeQuery { node_id, msg } => self.trace(node_id, out, QUERY_CHANNEL_RANGE, msg.get_native_ref()),
+				MessageSendEvent::SendShortIdsQuery { node_id, msg } => self.trace(node_id, out, QUERY_SHORT_CHANNEL_IDS, msg.get_native_ref()),
+				MessageSendEvent::SendReplyChannelRange { node_id, msg } => self.trace(node_id, out, REPLY_CHANNEL_RANGE, msg.get_native_ref()),
+			}
+		}
+	}
+}
+
+/// Wraps a ChannelMessageHandler, RoutingMessageHandler and CustomMessageHandler, reporting
+/// every message passed to or generated by them to a [`MessageTracer`].
+#[must_use]
+#[repr(C)]
+pub struct TracingMessageHandler {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeTracingMessageHandler,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for TracingMessageHandler {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeTracingMessageHandler>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the TracingMessageHandler, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn TracingMessageHandler_free(this_obj: TracingMessageHandler) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn TracingMessageHandler_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeTracingMessageHandler); }
+}
+#[allow(unused)]
+impl TracingMessageHandler {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeTracingMessageHandler {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeTracingMessageHandler {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeTracingMessageHandler {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a new TracingMessageHandler which reports messages to `tracer` before passing them on
+/// to `chan_handler`, `route_handler` and `custom_handler`.
+///
+/// Pass the results of `TracingMessageHandler_as_ChannelMessageHandler` and
+/// `TracingMessageHandler_as_RoutingMessageHandler` to `MessageHandler_new`, and the result of
+/// `TracingMessageHandler_as_CustomMessageHandler` to `PeerManager_new`, in place of the wrapped
+/// handlers.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn TracingMessageHandler_new(mut chan_handler: ChannelMessageHandler, mut route_handler: RoutingMessageHandler, mut custom_handler: CustomMessageHandler, mut tracer: MessageTracer) -> TracingMessageHandler {
+	TracingMessageHandler { inner: ObjOps::heap_alloc(nativeTracingMessageHandler { chan_handler, route_handler, custom_handler, tracer }), is_owned: true }
+}
+
+/// Constructs a new ChannelMessageHandler which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned ChannelMessageHandler must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn TracingMessageHandler_as_ChannelMessageHandler(this_arg: &TracingMessageHandler) -> ChannelMessageHandler {
+	ChannelMessageHandler {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		handle_open_channel: TracingMessageHandler_ChannelMessageHandler_handle_open_channel,
+		handle_accept_channel: TracingMessageHandler_ChannelMessageHandler_handle_accept_channel,
+		handle_funding_created: TracingMessageHandler_ChannelMessageHandler_handle_funding_created,
+		handle_funding_signed: TracingMessageHandler_ChannelMessageHandler_handle_funding_signed,
+		handle_funding_locked: TracingMessageHandler_ChannelMessageHandler_handle_funding_locked,
+		handle_shutdown: TracingMessageHandler_ChannelMessageHandler_handle_shutdown,
+		handle_closing_signed: TracingMessageHandler_ChannelMessageHandler_handle_closing_signed,
+		handle_update_add_htlc: TracingMessageHandler_ChannelMessageHandler_handle_update_add_htlc,
+		handle_update_fulfill_htlc: TracingMessageHandler_ChannelMessageHandler_handle_update_fulfill_htlc,
+		handle_update_fail_htlc: TracingMessageHandler_ChannelMessageHandler_handle_update_fail_htlc,
+		handle_update_fail_malformed_htlc: TracingMessageHandler_ChannelMessageHandler_handle_update_fail_malformed_htlc,
+		handle_commitment_signed: TracingMessageHandler_ChannelMessageHandler_handle_commitment_signed,
+		handle_revoke_and_ack: TracingMessageHandler_ChannelMessageHandler_handle_revoke_and_ack,
+		handle_update_fee: TracingMessageHandler_ChannelMessageHandler_handle_update_fee,
+		handle_announcement_signatures: TracingMessageHandler_ChannelMessageHandler_handle_announcement_signatures,
+		peer_disconnected: TracingMessageHandler_ChannelMessageHandler_peer_disconnected,
+		peer_connected: TracingMessageHandler_ChannelMessageHandler_peer_connected,
+		handle_channel_reestablish: TracingMessageHandler_ChannelMessageHandler_handle_channel_reestablish,
+		handle_channel_update: TracingMessageHandler_ChannelMessageHandler_handle_channel_update,
+		handle_error: TracingMessageHandler_ChannelMessageHandler_handle_error,
+		MessageSendEventsProvider: MessageSendEventsProvider {
+			this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+			free: None,
+			get_and_clear_pending_msg_events: TracingMessageHandler_ChannelMessageHandler_get_and_clear_pending_msg_events,
+		},
+	}
+}
+
+/// Defines a ChannelMessageHandler method which traces the inbound message and then passes it on
+/// to the wrapped handler.
+macro_rules! traced_channel_msg {
+	($fn_name: ident, $field: ident, $msg: ident, $msg_type: expr) => {
+		extern "C" fn $fn_name(this_arg: *const c_void, their_node_id: PublicKey, msg: &crate::lightning::ln::msgs::$msg) {
+			let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+			handler.trace(&their_node_id, MessageDirection::Inbound, $msg_type, msg.get_native_ref());
+			(handler.chan_handler.$field)(handler.chan_handler.this_arg, their_node_id, msg)
+		}
+	}
+}
+
+extern "C" fn TracingMessageHandler_ChannelMessageHandler_handle_open_channel(this_arg: *const c_void, their_node_id: PublicKey, their_features: crate::lightning::ln::features::InitFeatures, msg: &crate::lightning::ln::msgs::OpenChannel) {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	handler.trace(&their_node_id, MessageDirection::Inbound, OPEN_CHANNEL, msg.get_native_ref());
+	(handler.chan_handler.handle_open_channel)(handler.chan_handler.this_arg, their_node_id, their_features, msg)
+}
+extern "C" fn TracingMessageHandler_ChannelMessageHandler_handle_accept_channel(this_arg: *const c_void, their_node_id: PublicKey, their_features: crate::lightning::ln::features::InitFeatures, msg: &crate::lightning::ln::msgs::AcceptChannel) {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	handler.trace(&their_node_id, MessageDirection::Inbound, ACCEPT_CHANNEL, msg.get_native_ref());
+	(handler.chan_handler.handle_accept_channel)(handler.chan_handler.this_arg, their_node_id, their_features, msg)
+}
+extern "C" fn TracingMessageHandler_ChannelMessageHandler_handle_shutdown(this_arg: *const c_void, their_node_id: PublicKey, their_features: &crate::lightning::ln::features::InitFeatures, msg: &crate::lightning::ln::msgs::Shutdown) {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	handler.trace(&their_node_id, MessageDirection::Inbound, SHUTDOWN, msg.get_native_ref());
+	(handler.chan_handler.handle_shutdown)(handler.chan_handler.this_arg, their_node_id, their_features, msg)
+}
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_funding_created, handle_funding_created, FundingCreated, FUNDING_CREATED);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_funding_signed, handle_funding_signed, FundingSigned, FUNDING_SIGNED);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_funding_locked, handle_funding_locked, FundingLocked, FUNDING_LOCKED);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_closing_signed, handle_closing_signed, ClosingSigned, CLOSING_SIGNED);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_update_add_htlc, handle_update_add_htlc, UpdateAddHTLC, UPDATE_ADD_HTLC);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_update_fulfill_htlc, handle_update_fulfill_htlc, UpdateFulfillHTLC, UPDATE_FULFILL_HTLC);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_update_fail_htlc, handle_update_fail_htlc, UpdateFailHTLC, UPDATE_FAIL_HTLC);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_update_fail_malformed_htlc, handle_update_fail_malformed_htlc, UpdateFailMalformedHTLC, UPDATE_FAIL_MALFORMED_HTLC);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_commitment_signed, handle_commitment_signed, CommitmentSigned, COMMITMENT_SIGNED);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_revoke_and_ack, handle_revoke_and_ack, RevokeAndACK, REVOKE_AND_ACK);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_update_fee, handle_update_fee, UpdateFee, UPDATE_FEE);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_announcement_signatures, handle_announcement_signatures, AnnouncementSignatures, ANNOUNCEMENT_SIGNATURES);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_peer_connected, peer_connected, Init, INIT);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_channel_reestablish, handle_channel_reestablish, ChannelReestablish, CHANNEL_REESTABLISH);
+// The PeerManager passes channel_updates to both handlers, so we only trace them here, where
+// the sending peer is known.
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_channel_update, handle_channel_update, ChannelUpdate, CHANNEL_UPDATE);
+traced_channel_msg!(TracingMessageHandler_ChannelMessageHandler_handle_error, handle_error, ErrorMessage, ERROR);
+
+extern "C" fn TracingMessageHandler_ChannelMessageHandler_peer_disconnected(this_arg: *const c_void, their_node_id: PublicKey, no_connection_possible: bool) {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	(handler.chan_handler.peer_disconnected)(handler.chan_handler.this_arg, their_node_id, no_connection_possible)
+}
+#[must_use]
+extern "C" fn TracingMessageHandler_ChannelMessageHandler_get_and_clear_pending_msg_events(this_arg: *const c_void) -> crate::c_types::derived::CVec_MessageSendEventZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	let provider = &handler.chan_handler.MessageSendEventsProvider;
+	let ret = (provider.get_and_clear_pending_msg_events)(provider.this_arg);
+	handler.trace_outbound_events(&ret);
+	ret
+}
+
+/// Constructs a new RoutingMessageHandler which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned RoutingMessageHandler must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn TracingMessageHandler_as_RoutingMessageHandler(this_arg: &TracingMessageHandler) -> RoutingMessageHandler {
+	RoutingMessageHandler {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		handle_node_announcement: TracingMessageHandler_RoutingMessageHandler_handle_node_announcement,
+		handle_channel_announcement: TracingMessageHandler_RoutingMessageHandler_handle_channel_announcement,
+		handle_channel_update: TracingMessageHandler_RoutingMessageHandler_handle_channel_update,
+		get_next_channel_announcements: TracingMessageHandler_RoutingMessageHandler_get_next_channel_announcements,
+		get_next_node_announcements: TracingMessageHandler_RoutingMessageHandler_get_next_node_announcements,
+		sync_routing_table: TracingMessageHandler_RoutingMessageHandler_sync_routing_table,
+		handle_reply_channel_range: TracingMessageHandler_RoutingMessageHandler_handle_reply_channel_range,
+		handle_reply_short_channel_ids_end: TracingMessageHandler_RoutingMessageHandler_handle_reply_short_channel_ids_end,
+		handle_query_channel_range: TracingMessageHandler_RoutingMessageHandler_handle_query_channel_range,
+		handle_query_short_channel_ids: TracingMessageHandler_RoutingMessageHandler_handle_query_short_channel_ids,
+		MessageSendEventsProvider: MessageSendEventsProvider {
+			this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+			free: None,
+			get_and_clear_pending_msg_events: TracingMessageHandler_RoutingMessageHandler_get_and_clear_pending_msg_events,
+		},
+	}
+}
+
+/// Defines a RoutingMessageHandler method which traces an inbound gossip query and then passes it
+/// on to the wrapped handler.
+macro_rules! traced_gossip_query {
+	($fn_name: ident, $field: ident, $msg: ident, $msg_type: expr) => {
+		#[must_use]
+		extern "C" fn $fn_name(this_arg: *const c_void, their_node_id: PublicKey, msg: crate::lightning::ln::msgs::$msg) -> crate::c_types::derived::CResult_NoneLightningErrorZ {
+			let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+			handler.trace(&their_node_id, MessageDirection::Inbound, $msg_type, msg.get_native_ref());
+			(handler.route_handler.$field)(handler.route_handler.this_arg, their_node_id, msg)
+		}
+	}
+}
+
+#[must_use]
+extern "C" fn TracingMessageHandler_RoutingMessageHandler_handle_node_announcement(this_arg: *const c_void, msg: &crate::lightning::ln::msgs::NodeAnnouncement) -> crate::c_types::derived::CResult_boolLightningErrorZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	handler.trace_gossip(MessageDirection::Inbound, NODE_ANNOUNCEMENT, msg.get_native_ref());
+	(handler.route_handler.handle_node_announcement)(handler.route_handler.this_arg, msg)
+}
+#[must_use]
+extern "C" fn TracingMessageHandler_RoutingMessageHandler_handle_channel_announcement(this_arg: *const c_void, msg: &crate::lightning::ln::msgs::ChannelAnnouncement) -> crate::c_types::derived::CResult_boolLightningErrorZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	handler.trace_gossip(MessageDirection::Inbound, CHANNEL_ANNOUNCEMENT, msg.get_native_ref());
+	(handler.route_handler.handle_channel_announcement)(handler.route_handler.this_arg, msg)
+}
+#[must_use]
+extern "C" fn TracingMessageHandler_RoutingMessageHandler_handle_channel_update(this_arg: *const c_void, msg: &crate::lightning::ln::msgs::ChannelUpdate) -> crate::c_types::derived::CResult_boolLightningErrorZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	(handler.route_handler.handle_channel_update)(handler.route_handler.this_arg, msg)
+}
+// The PeerManager sends the announcements returned by get_next_*_announcements to a peer which
+// is syncing its routing table, but does not tell us which one.
+#[must_use]
+extern "C" fn TracingMessageHandler_RoutingMessageHandler_get_next_channel_announcements(this_arg: *const c_void, starting_point: u64, batch_amount: u8) -> crate::c_types::derived::CVec_C3Tuple_ChannelAnnouncementChannelUpdateChannelUpdateZZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	let ret = (handler.route_handler.get_next_channel_announcements)(handler.route_handler.this_arg, starting_point, batch_amount);
+	for announcement in ret.as_slice() {
+		handler.trace_gossip(MessageDirection::Outbound, CHANNEL_ANNOUNCEMENT, announcement.a.get_native_ref());
+		for update in [&announcement.b, &announcement.c].iter() {
+			if !update.inner.is_null() { handler.trace_gossip(MessageDirection::Outbound, CHANNEL_UPDATE, update.get_native_ref()); }
+		}
+	}
+	ret
+}
+#[must_use]
+extern "C" fn TracingMessageHandler_RoutingMessageHandler_get_next_node_announcements(this_arg: *const c_void, starting_point: PublicKey, batch_amount: u8) -> crate::c_types::derived::CVec_NodeAnnouncementZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	let ret = (handler.route_handler.get_next_node_announcements)(handler.route_handler.this_arg, starting_point, batch_amount);
+	for announcement in ret.as_slice() {
+		handler.trace_gossip(MessageDirection::Outbound, NODE_ANNOUNCEMENT, announcement.get_native_ref());
+	}
+	ret
+}
+extern "C" fn TracingMessageHandler_RoutingMessageHandler_sync_routing_table(this_arg: *const c_void, their_node_id: PublicKey, init: &crate::lightning::ln::msgs::Init) {
+	// The init message was already traced when it was passed to the ChannelMessageHandler.
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	(handler.route_handler.sync_routing_table)(handler.route_handler.this_arg, their_node_id, init)
+}
+traced_gossip_query!(TracingMessageHandler_RoutingMessageHandler_handle_reply_channel_range, handle_reply_channel_range, ReplyChannelRange, REPLY_CHANNEL_RANGE);
+traced_gossip_query!(TracingMessageHandler_RoutingMessageHandler_handle_reply_short_channel_ids_end, handle_reply_short_channel_ids_end, ReplyShortChannelIdsEnd, REPLY_SHORT_CHANNEL_IDS_END);
+traced_gossip_query!(TracingMessageHandler_RoutingMessageHandler_handle_query_channel_range, handle_query_channel_range, QueryChannelRange, QUERY_CHANNEL_RANGE);
+traced_gossip_query!(TracingMessageHandler_RoutingMessageHandler_handle_query_short_channel_ids, handle_query_short_channel_ids, QueryShortChannelIds, QUERY_SHORT_CHANNEL_IDS);
+
+#[must_use]
+extern "C" fn TracingMessageHandler_RoutingMessageHandler_get_and_clear_pending_msg_events(this_arg: *const c_void) -> crate::c_types::derived::CVec_MessageSendEventZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	let provider = &handler.route_handler.MessageSendEventsProvider;
+	let ret = (provider.get_and_clear_pending_msg_events)(provider.this_arg);
+	handler.trace_outbound_events(&ret);
+	ret
+}
+
+/// Constructs a new CustomMessageHandler which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned CustomMessageHandler must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn TracingMessageHandler_as_CustomMessageHandler(this_arg: &TracingMessageHandler) -> CustomMessageHandler {
+	CustomMessageHandler {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		handle_custom_message: TracingMessageHandler_CustomMessageHandler_handle_custom_message,
+		get_and_clear_pending_msg: TracingMessageHandler_CustomMessageHandler_get_and_clear_pending_msg,
+		CustomMessageReader: CustomMessageReader {
+			this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+			free: None,
+			read: TracingMessageHandler_CustomMessageReader_read,
+		},
+	}
+}
+
+#[must_use]
+extern "C" fn TracingMessageHandler_CustomMessageHandler_handle_custom_message(this_arg: *const c_void, msg: Type, sender_node_id: PublicKey) -> crate::c_types::derived::CResult_NoneLightningErrorZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	handler.trace_custom(&sender_node_id, MessageDirection::Inbound, &msg);
+	(handler.custom_handler.handle_custom_message)(handler.custom_handler.this_arg, msg, sender_node_id)
+}
+#[must_use]
+extern "C" fn TracingMessageHandler_CustomMessageHandler_get_and_clear_pending_msg(this_arg: *const c_void) -> crate::c_types::derived::CVec_C2Tuple_PublicKeyTypeZZ {
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	let ret = (handler.custom_handler.get_and_clear_pending_msg)(handler.custom_handler.this_arg);
+	for msg in ret.as_slice() {
+		handler.trace_custom(&msg.a, MessageDirection::Outbound, &msg.b);
+	}
+	ret
+}
+#[must_use]
+extern "C" fn TracingMessageHandler_CustomMessageReader_read(this_arg: *const c_void, message_type: u16, buffer: u8slice) -> crate::c_types::derived::CResult_COption_TypeZDecodeErrorZ {
+	// Messages are traced once they have been read and are passed to handle_custom_message.
+	let handler = unsafe { &*(this_arg as *const nativeTracingMessageHandler) };
+	let reader = &handler.custom_handler.CustomMessageReader;
+	(reader.read)(reader.this_arg, message_type, buffer)
+}
+
+pub(crate) struct nativeMessageTraceFile {
+	file: Mutex<BufWriter<File>>,
+}
+
+impl nativeMessageTraceFile {
+	fn open(path: &str) -> Result<Self, std::io::Error> {
+		let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+		if file.metadata()?.len() == 0 {
+			file.write_all(TRACE_FILE_MAGIC)?;
+			file.write_all(&TRACE_FILE_VERSION.to_be_bytes())?;
+		}
+		Ok(Self { file: Mutex::new(BufWriter::new(file)) })
+	}
+
+	fn write_record(&self, node_id: &[u8; 33], direction: MessageDirection, msg_type: u16, data: &[u8]) -> Result<(), std::io::Error> {
+		let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+		let mut file = self.file.lock().unwrap();
+		file.write_all(&timestamp.as_secs().to_be_bytes())?;
+		file.write_all(&timestamp.subsec_nanos().to_be_bytes())?;
+		file.write_all(&[match direction { MessageDirection::Inbound => 0, MessageDirection::Outbound => 1 }])?;
+		file.write_all(node_id)?;
+		file.write_all(&msg_type.to_be_bytes())?;
+		file.write_all(&(data.len() as u32).to_be_bytes())?;
+		file.write_all(data)?;
+		file.flush()
+	}
+}
+
+/// A MessageTracer which appends every traced message to a file, see `MessageTraceFile_new` for
+/// the file format.
+#[must_use]
+#[repr(C)]
+pub struct MessageTraceFile {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeMessageTraceFile,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for MessageTraceFile {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeMessageTraceFile>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the MessageTraceFile, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn MessageTraceFile_free(this_obj: MessageTraceFile) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn MessageTraceFile_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeMessageTraceFile); }
+}
+#[allow(unused)]
+impl MessageTraceFile {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeMessageTraceFile {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeMessageTraceFile {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeMessageTraceFile {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_MessageTraceFileIOErrorZ
+pub union CResult_MessageTraceFileIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut MessageTraceFile,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_MessageTraceFileIOErrorZ represents the result of a fallible operation,
+/// containing a MessageTraceFile on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_MessageTraceFileIOErrorZ {
+	/// The contents of this CResult_MessageTraceFileIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_MessageTraceFileIOErrorZPtr,
+	/// Whether this CResult_MessageTraceFileIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_MessageTraceFileIOErrorZ in the success state.
+pub extern "C" fn CResult_MessageTraceFileIOErrorZ_ok(o: MessageTraceFile) -> CResult_MessageTraceFileIOErrorZ {
+	CResult_MessageTraceFileIOErrorZ {
+		contents: CResult_MessageTraceFileIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_MessageTraceFileIOErrorZ in the error state.
+pub extern "C" fn CResult_MessageTraceFileIOErrorZ_err(e: IOError) -> CResult_MessageTraceFileIOErrorZ {
+	CResult_MessageTraceFileIOErrorZ {
+		contents: CResult_MessageTraceFileIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_MessageTraceFileIOErrorZ_is_ok(o: &CResult_MessageTraceFileIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_MessageTraceFileIOErrorZ.
+pub extern "C" fn CResult_MessageTraceFileIOErrorZ_free(_res: CResult_MessageTraceFileIOErrorZ) { }
+impl Drop for CResult_MessageTraceFileIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Opens the trace file at `path`, creating it if it does not exist. New records are appended to
+/// any existing trace.
+///
+/// The file starts with the 8 bytes `LDKTRACE` and a big-endian u16 version (currently 1),
+/// followed by one record per message, with all integers big-endian:
+///  * u64 seconds and u32 nanoseconds since the UNIX epoch at which the message was traced,
+///  * u8 direction, 0 for inbound and 1 for outbound,
+///  * the 33-byte compressed node id of the peer,
+///  * u16 message type,
+///  * u32 payload length, followed by the payload.
+///
+/// Each record is flushed as it is written. Failures to write are ignored, as the traced message
+/// is handled regardless.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn MessageTraceFile_new(mut path: Str) -> CResult_MessageTraceFileIOErrorZ {
+	match nativeMessageTraceFile::open(path.into_str()) {
+		Ok(file) => CResult_MessageTraceFileIOErrorZ_ok(MessageTraceFile { inner: ObjOps::heap_alloc(file), is_owned: true }),
+		Err(e) => CResult_MessageTraceFileIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+impl From<nativeMessageTraceFile> for MessageTracer {
+	fn from(obj: nativeMessageTraceFile) -> Self {
+		let mut rust_obj = MessageTraceFile { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = MessageTraceFile_as_MessageTracer(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(MessageTraceFile_free_void);
+		ret
+	}
+}
+/// Constructs a new MessageTracer which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned MessageTracer must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn MessageTraceFile_as_MessageTracer(this_arg: &MessageTraceFile) -> MessageTracer {
+	MessageTracer {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		trace: MessageTraceFile_MessageTracer_trace,
+	}
+}
+
+extern "C" fn MessageTraceFile_MessageTracer_trace(this_arg: *const c_void, node_id: PublicKey, direction: MessageDirection, msg_type: u16, data: u8slice) {
+	let file = unsafe { &*(this_arg as *const nativeMessageTraceFile) };
+	let _ = file.write_record(&node_id.compressed_form, direction, msg_type, data.to_slice());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::Read;
+	use bitcoin::secp256k1::{Message, Secp256k1};
+	use bitcoin::secp256k1::key::{PublicKey as SecpPublicKey, SecretKey as SecpSecretKey};
+	use lightning::ln::features::NodeFeatures;
+	use crate::c_types::derived::{CResult_COption_TypeZDecodeErrorZ, CResult_COption_TypeZDecodeErrorZ_ok, CResult_NoneLightningErrorZ, CResult_NoneLightningErrorZ_ok, COption_TypeZ, CVec_C2Tuple_PublicKeyTypeZZ, CVec_NodeAnnouncementZ, CVec_u8Z};
+	use crate::lightning::ln::msgs::{ErrorMessage_read, FundingLocked_new, NodeAnnouncement, ReplyShortChannelIdsEnd_new};
+	use crate::lightning::ln::peer_handler::{ErroringMessageHandler_as_ChannelMessageHandler, ErroringMessageHandler_new, IgnoringMessageHandler, IgnoringMessageHandler_as_CustomMessageHandler, IgnoringMessageHandler_as_RoutingMessageHandler, IgnoringMessageHandler_new};
+
+	type Trace = ([u8; 33], MessageDirection, u16, Vec<u8>);
+
+	extern "C" fn record(this_arg: *const c_void, node_id: PublicKey, direction: MessageDirection, msg_type: u16, data: u8slice) {
+		let traces = unsafe { &*(this_arg as *const Mutex<Vec<Trace>>) };
+		traces.lock().unwrap().push((node_id.compressed_form, direction, msg_type, data.to_slice().to_vec()));
+	}
+
+	fn tracer(traces: &Mutex<Vec<Trace>>) -> MessageTracer {
+		MessageTracer { this_arg: traces as *const Mutex<Vec<Trace>> as *mut c_void, trace: record, free: None }
+	}
+
+	fn ignoring() -> &'static IgnoringMessageHandler {
+		Box::leak(Box::new(IgnoringMessageHandler_new()))
+	}
+
+	fn tracing_handler(traces: &Mutex<Vec<Trace>>, route_handler: RoutingMessageHandler, custom_handler: CustomMessageHandler) -> TracingMessageHandler {
+		let erroring = Box::leak(Box::new(ErroringMessageHandler_new()));
+		TracingMessageHandler_new(ErroringMessageHandler_as_ChannelMessageHandler(erroring), route_handler, custom_handler, tracer(traces))
+	}
+
+	fn node_id(seed: u8) -> SecpPublicKey {
+		SecpPublicKey::from_secret_key(&Secp256k1::new(), &SecpSecretKey::from_slice(&[seed; 32]).unwrap())
+	}
+
+	fn node_announcement() -> NodeAnnouncement {
+		let contents = lightning::ln::msgs::UnsignedNodeAnnouncement {
+			features: NodeFeatures::known(), timestamp: 42, node_id: node_id(1), rgb: [1, 2, 3], alias: [4; 32],
+			addresses: Vec::new(), excess_address_data: Vec::new(), excess_data: Vec::new(),
+		};
+		let signature = Secp256k1::new().sign(&Message::from_slice(&[1; 32]).unwrap(), &SecpSecretKey::from_slice(&[1; 32]).unwrap());
+		NodeAnnouncement { inner: ObjOps::heap_alloc(lightning::ln::msgs::NodeAnnouncement { signature, contents }), is_owned: true }
+	}
+
+	extern "C" fn next_node_announcements(_this_arg: *const c_void, _starting_point: PublicKey, _batch_amount: u8) -> CVec_NodeAnnouncementZ {
+		vec![node_announcement()].into()
+	}
+
+	const CUSTOM_TYPE: u16 = 32769;
+	extern "C" fn custom_type_id(_this_arg: *const c_void) -> u16 { CUSTOM_TYPE }
+	extern "C" fn custom_debug_str(_this_arg: *const c_void) -> Str { "custom".into() }
+	extern "C" fn custom_write(_this_arg: *const c_void) -> CVec_u8Z { vec![1, 2, 3].into() }
+	fn custom_msg() -> Type {
+		Type { this_arg: std::ptr::null_mut(), type_id: custom_type_id, debug_str: custom_debug_str, write: custom_write, free: None }
+	}
+
+	extern "C" fn handle_custom_message(_this_arg: *const c_void, _msg: Type, _sender_node_id: PublicKey) -> CResult_NoneLightningErrorZ {
+		CResult_NoneLightningErrorZ_ok()
+	}
+	extern "C" fn pending_custom_msgs(_this_arg: *const c_void) -> CVec_C2Tuple_PublicKeyTypeZZ {
+		vec![(PublicKey::from_rust(&node_id(2)), custom_msg()).into()].into()
+	}
+	extern "C" fn read_custom_message(_this_arg: *const c_void, message_type: u16, _buffer: u8slice) -> CResult_COption_TypeZDecodeErrorZ {
+		assert_eq!(message_type, CUSTOM_TYPE);
+		CResult_COption_TypeZDecodeErrorZ_ok(COption_TypeZ::Some(custom_msg()))
+	}
+	fn custom_handler() -> CustomMessageHandler {
+		CustomMessageHandler {
+			this_arg: std::ptr::null_mut(),
+			handle_custom_message,
+			get_and_clear_pending_msg: pending_custom_msgs,
+			CustomMessageReader: CustomMessageReader { this_arg: std::ptr::null_mut(), read: read_custom_message, free: None },
+			free: None,
+		}
+	}
+
+	#[test]
+	fn traces_channel_messages() {
+		let traces = Mutex::new(Vec::new());
+		let ignoring = ignoring();
+		let handler = tracing_handler(&traces, IgnoringMessageHandler_as_RoutingMessageHandler(ignoring), IgnoringMessageHandler_as_CustomMessageHandler(ignoring));
+		let chan_handler = TracingMessageHandler_as_ChannelMessageHandler(&handler);
+
+		let msg = FundingLocked_new(ThirtyTwoBytes { data: [42; 32] }, PublicKey::from_rust(&node_id(3)));
+		(chan_handler.handle_funding_locked)(chan_handler.this_arg, PublicKey::from_rust(&node_id(2)), &msg);
+		// The ErroringMessageHandler responds to every channel message with an error.
+		let provider = &chan_handler.MessageSendEventsProvider;
+		let events = (provider.get_and_clear_pending_msg_events)(provider.this_arg);
+		assert_eq!(events.as_slice().len(), 1);
+
+		let traces = traces.lock().unwrap();
+		assert_eq!(traces.len(), 2);
+		assert_eq!(traces[0], (node_id(2).serialize(), MessageDirection::Inbound, FUNDING_LOCKED, msg.get_native_ref().encode()));
+		assert_eq!((traces[1].0, traces[1].1, traces[1].2), (node_id(2).serialize(), MessageDirection::Outbound, ERROR));
+		assert!(ErrorMessage_read(u8slice::from_slice(&traces[1].3)).result_ok);
+	}
+
+	#[test]
+	fn traces_gossip_sync_and_query_replies() {
+		let traces = Mutex::new(Vec::new());
+		let ignoring = ignoring();
+		let mut route_handler = IgnoringMessageHandler_as_RoutingMessageHandler(ignoring);
+		route_handler.get_next_node_announcements = next_node_announcements;
+		let handler = tracing_handler(&traces, route_handler, IgnoringMessageHandler_as_CustomMessageHandler(ignoring));
+		let route_handler = TracingMessageHandler_as_RoutingMessageHandler(&handler);
+
+		let announcements = (route_handler.get_next_node_announcements)(route_handler.this_arg, PublicKey::from_rust(&node_id(1)), 1);
+		assert_eq!(announcements.as_slice().len(), 1);
+		let reply = ReplyShortChannelIdsEnd_new(ThirtyTwoBytes { data: [0; 32] }, true);
+		let reply_data = reply.get_native_ref().encode();
+		let _ = (route_handler.handle_reply_short_channel_ids_end)(route_handler.this_arg, PublicKey::from_rust(&node_id(2)), reply);
+
+		assert_eq!(*traces.lock().unwrap(), vec![
+			([0; 33], MessageDirection::Outbound, NODE_ANNOUNCEMENT, node_announcement().get_native_ref().encode()),
+			(node_id(2).serialize(), MessageDirection::Inbound, REPLY_SHORT_CHANNEL_IDS_END, reply_data),
+		]);
+	}
+
+	#[test]
+	fn traces_custom_messages() {
+		let traces = Mutex::new(Vec::new());
+		let handler = tracing_handler(&traces, IgnoringMessageHandler_as_RoutingMessageHandler(ignoring()), custom_handler());
+		let custom_handler = TracingMessageHandler_as_CustomMessageHandler(&handler);
+
+		// Reading is passed through without tracing, as the message is traced once it is handled.
+		let reader = &custom_handler.CustomMessageReader;
+		let read = (reader.read)(reader.this_arg, CUSTOM_TYPE, u8slice::from_slice(&[1, 2, 3]));
+		assert!(read.result_ok);
+		assert!(traces.lock().unwrap().is_empty());
+
+		assert!((custom_handler.handle_custom_message)(custom_handler.this_arg, custom_msg(), PublicKey::from_rust(&node_id(1))).result_ok);
+		assert_eq!((custom_handler.get_and_clear_pending_msg)(custom_handler.this_arg).as_slice().len(), 1);
+		assert_eq!(*traces.lock().unwrap(), vec![
+			(node_id(1).serialize(), MessageDirection::Inbound, CUSTOM_TYPE, vec![1, 2, 3]),
+			(node_id(2).serialize(), MessageDirection::Outbound, CUSTOM_TYPE, vec![1, 2, 3]),
+		]);
+	}
+
+	#[test]
+	fn writes_trace_file() {
+		let path = std::env::temp_dir().join(format!("ldk-trace-test-{}", std::process::id()));
+		let _ = std::fs::remove_file(&path);
+		for _ in 0..2 {
+			// Reopening the file appends to it without repeating the header.
+			let mut file = MessageTraceFile_new(path.to_str().unwrap().to_owned().into());
+			assert!(file.result_ok);
+			let file = unsafe { std::mem::replace(&mut *file.contents.result, MessageTraceFile { inner: std::ptr::null_mut(), is_owned: true }) };
+			let tracer = MessageTraceFile_as_MessageTracer(&file);
+			(tracer.trace)(tracer.this_arg, PublicKey::from_rust(&node_id(1)), MessageDirection::Outbound, CUSTOM_TYPE, u8slice::from_slice(&[1, 2, 3]));
+		}
+
+		let mut data = Vec::new();
+		File::open(&path).unwrap().read_to_end(&mut data).unwrap();
+		assert_eq!(&data[..8], TRACE_FILE_MAGIC);
+		assert_eq!(data[8..10], TRACE_FILE_VERSION.to_be_bytes());
+		let mut record = Vec::new();
+		record.push(1);
+		record.extend_from_slice(&node_id(1).serialize());
+		record.extend_from_slice(&CUSTOM_TYPE.to_be_bytes());
+		record.extend_from_slice(&3u32.to_be_bytes());
+		record.extend_from_slice(&[1, 2, 3]);
+		let record_len = 12 + record.len();
+		assert_eq!(data.len(), 10 + 2 * record_len);
+		for i in 0..2 {
+			// Skip the timestamp.
+			let start = 10 + i * record_len + 12;
+			assert_eq!(data[start..start + record.len()], record[..]);
+		}
+		std::fs::remove_file(&path).unwrap();
+	}
+}
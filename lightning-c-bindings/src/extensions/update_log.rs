@@ -0,0 +1,434 @@
+//! A `ChannelMonitor` persister which logs each `ChannelMonitorUpdate` instead of rewriting the
+//! full `ChannelMonitor` on every update.
+//!
+//! `ChannelMonitor`s grow with the number of payments forwarded over a channel, so rewriting the
+//! whole monitor on each update (as `FilesystemPersister` and [`KVStorePersister`] do) is
+//! expensive for busy channels. [`UpdateLogPersister`] instead appends each serialized update to
+//! a per-channel log, and only rewrites the full monitor once a configurable number of updates
+//! have been logged, after which the logged updates are removed.
+//!
+//! Full `ChannelMonitor`s use the same keys as [`KVStorePersister`]. Logged updates are stored in
+//! the `"monitor_updates"` namespace, keyed by `<txid>_<output index>_<update id>`. Because the
+//! stored monitors may be missing logged updates, they must always be loaded with
+//! [`UpdateLogPersister_read_channelmonitors`] or [`UpdateLogPersister_restore_node`], which
+//! replay any logged updates newer than the full monitor.
+//!
+//! [`KVStorePersister`]: crate::extensions::kv_store::KVStorePersister
+//! [`UpdateLogPersister_restore_node`]: crate::extensions::restore::UpdateLogPersister_restore_node
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::Cursor;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use bitcoin::hash_types::BlockHash;
+use bitcoin::hashes::Hash;
+
+use lightning::chain::ChannelMonitorUpdateErr;
+use lightning::chain::chainmonitor::MonitorUpdateId;
+use lightning::chain::channelmonitor::{ChannelMonitor as RustChannelMonitor, ChannelMonitorUpdate as RustChannelMonitorUpdate, CLOSED_CHANNEL_UPDATE_ID};
+use lightning::chain::transaction::OutPoint as RustOutPoint;
+use lightning::util::ser::{Readable, Writeable};
+
+use crate::c_types::*;
+use crate::extensions::kv_store::*;
+use crate::lightning::chain::chaininterface::{BroadcasterInterface, FeeEstimator};
+use crate::lightning::chain::keysinterface::{KeysInterface, Sign};
+use crate::lightning::util::logger::Logger;
+
+/// The namespace under which logged `ChannelMonitorUpdate`s are stored.
+pub(crate) const MONITOR_UPDATE_NAMESPACE: &str = "monitor_updates";
+
+fn update_key(funding_txo: &RustOutPoint, update_id: u64) -> String {
+	format!("{}_{}", monitor_key(funding_txo), update_id)
+}
+fn parse_update_key(key: &str) -> Option<(RustOutPoint, u64)> {
+	let mut parts = key.rsplitn(2, '_');
+	let update_id = u64::from_str(parts.next()?).ok()?;
+	Some((parse_monitor_key(parts.next()?)?, update_id))
+}
+
+fn invalid_data(msg: String) -> std::io::Error {
+	std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+pub(crate) struct nativeUpdateLogPersister {
+	store: KVStore,
+	max_pending_updates: usize,
+	/// The ids of the updates currently logged for each channel, which may include updates
+	/// already contained in the full monitor if a previous compaction failed to remove them.
+	logged_updates: Mutex<HashMap<RustOutPoint, Vec<u64>>>,
+}
+
+impl nativeUpdateLogPersister {
+	pub(crate) fn new(store: KVStore, max_pending_updates: usize) -> Result<Self, std::io::Error> {
+		let mut logged_updates: HashMap<RustOutPoint, Vec<u64>> = HashMap::new();
+		for key in store.list(MONITOR_UPDATE_NAMESPACE)? {
+			let (funding_txo, update_id) = parse_update_key(&key)
+				.ok_or_else(|| invalid_data(format!("Invalid ChannelMonitorUpdate key {}", key)))?;
+			logged_updates.entry(funding_txo).or_insert_with(Vec::new).push(update_id);
+		}
+		Ok(Self { store, max_pending_updates, logged_updates: Mutex::new(logged_updates) })
+	}
+
+	/// Writes the full monitor and then removes all logged updates it contains. Updates which
+	/// fail to be removed are kept track of and retried on the next compaction, and are skipped
+	/// when loading in the meantime.
+	fn write_full_monitor(&self, funding_txo: &RustOutPoint, monitor: &RustChannelMonitor<Sign>) -> Result<(), std::io::Error> {
+		// Fetch the update id before encoding, as further updates may be applied concurrently,
+		// and we must never remove an update which isn't contained in what we wrote.
+		let latest_update_id = monitor.get_latest_update_id();
+		self.store.write(CHANNEL_MONITOR_NAMESPACE, &monitor_key(funding_txo), &monitor.encode())?;
+
+		let compacted: Vec<u64> = match self.logged_updates.lock().unwrap().get(funding_txo) {
+			Some(ids) => ids.iter().filter(|id| **id <= latest_update_id).cloned().collect(),
+			None => return Ok(()),
+		};
+		let mut removed = Vec::with_capacity(compacted.len());
+		for update_id in compacted {
+			if self.store.remove(MONITOR_UPDATE_NAMESPACE, &update_key(funding_txo, update_id)).is_ok() {
+				removed.push(update_id);
+			}
+		}
+		let mut logged_updates = self.logged_updates.lock().unwrap();
+		if let Some(ids) = logged_updates.get_mut(funding_txo) {
+			ids.retain(|id| !removed.contains(id));
+			if ids.is_empty() { logged_updates.remove(funding_txo); }
+		}
+		Ok(())
+	}
+
+	fn log_update(&self, funding_txo: &RustOutPoint, update: &RustChannelMonitorUpdate) -> Result<(), std::io::Error> {
+		self.store.write(MONITOR_UPDATE_NAMESPACE, &update_key(funding_txo, update.update_id), &update.encode())?;
+		self.logged_updates.lock().unwrap().entry(*funding_txo).or_insert_with(Vec::new).push(update.update_id);
+		Ok(())
+	}
+
+	fn pending_update_count(&self, funding_txo: &RustOutPoint) -> usize {
+		self.logged_updates.lock().unwrap().get(funding_txo).map(|ids| ids.len()).unwrap_or(0)
+	}
+
+	/// Reads all full `ChannelMonitor`s and applies any logged updates which they don't contain
+	/// yet, in order.
+	pub(crate) fn read_channelmonitors(&self, keys_manager: &KeysInterface, broadcaster: &BroadcasterInterface, fee_estimator: &FeeEstimator, logger: &Logger)
+	-> Result<Vec<(BlockHash, RustChannelMonitor<Sign>)>, std::io::Error> {
+		let monitors = read_channelmonitors(&self.store, keys_manager)?;
+		let logged_updates = self.logged_updates.lock().unwrap().clone();
+		for (_, monitor) in monitors.iter() {
+			let funding_txo = monitor.get_funding_txo().0;
+			let mut update_ids = match logged_updates.get(&funding_txo) {
+				Some(ids) => ids.clone(),
+				None => continue,
+			};
+			update_ids.sort_unstable();
+			for update_id in update_ids {
+				if update_id <= monitor.get_latest_update_id() { continue; }
+				let data = self.store.read(MONITOR_UPDATE_NAMESPACE, &update_key(&funding_txo, update_id))?;
+				let update: RustChannelMonitorUpdate = Readable::read(&mut Cursor::new(&data))
+					.map_err(|e| invalid_data(format!("Failed to read ChannelMonitorUpdate: {:?}", e)))?;
+				if update.update_id != update_id {
+					return Err(invalid_data("ChannelMonitorUpdate was stored under the wrong key".to_owned()));
+				}
+				monitor.update_monitor(&update, broadcaster, fee_estimator, logger)
+					.map_err(|_| invalid_data(format!("Failed to apply logged ChannelMonitorUpdate {}", update_id)))?;
+			}
+		}
+		Ok(monitors)
+	}
+}
+
+impl lightning::chain::chainmonitor::Persist<Sign> for nativeUpdateLogPersister {
+	fn persist_new_channel(&self, funding_txo: RustOutPoint, monitor: &RustChannelMonitor<Sign>, _update_id: MonitorUpdateId) -> Result<(), ChannelMonitorUpdateErr> {
+		self.write_full_monitor(&funding_txo, monitor)
+			.map_err(|_| ChannelMonitorUpdateErr::PermanentFailure)
+	}
+
+	fn update_persisted_channel(&self, funding_txo: RustOutPoint, update: &Option<RustChannelMonitorUpdate>, monitor: &RustChannelMonitor<Sign>, _update_id: MonitorUpdateId) -> Result<(), ChannelMonitorUpdateErr> {
+		let res = match update {
+			// Post-close updates all share the same update id, so can't be logged, but are rare.
+			Some(update) if update.update_id != CLOSED_CHANNEL_UPDATE_ID
+				&& self.pending_update_count(&funding_txo) < self.max_pending_updates
+				=> self.log_update(&funding_txo, update),
+			// Chain sync updates carry no ChannelMonitorUpdate, so we have to write everything.
+			_ => self.write_full_monitor(&funding_txo, monitor),
+		};
+		res.map_err(|_| ChannelMonitorUpdateErr::PermanentFailure)
+	}
+}
+
+/// Persists `ChannelMonitor`s in a [`KVStore`] by logging each `ChannelMonitorUpdate` and only
+/// periodically rewriting the full `ChannelMonitor`.
+///
+/// The same store may be shared with a [`KVStorePersister`] to persist the `ChannelManager`,
+/// `NetworkGraph` and `Scorer`, though `ChannelMonitor`s must then only be read via
+/// [`UpdateLogPersister_read_channelmonitors`] or `UpdateLogPersister_restore_node`, never via
+/// `KVStorePersister_read_channelmonitors` or `KVStorePersister_restore_node`.
+///
+/// [`KVStore`]: crate::extensions::kv_store::KVStore
+/// [`KVStorePersister`]: crate::extensions::kv_store::KVStorePersister
+#[must_use]
+#[repr(C)]
+pub struct UpdateLogPersister {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeUpdateLogPersister,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for UpdateLogPersister {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeUpdateLogPersister>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the UpdateLogPersister, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn UpdateLogPersister_free(this_obj: UpdateLogPersister) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn UpdateLogPersister_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeUpdateLogPersister); }
+}
+#[allow(unused)]
+impl UpdateLogPersister {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeUpdateLogPersister {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeUpdateLogPersister {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeUpdateLogPersister {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_UpdateLogPersisterIOErrorZ
+pub union CResult_UpdateLogPersisterIOErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut UpdateLogPersister,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut IOError,
+}
+#[repr(C)]
+/// A CResult_UpdateLogPersisterIOErrorZ represents the result of a fallible operation,
+/// containing a UpdateLogPersister on success and a crate::c_types::IOError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_UpdateLogPersisterIOErrorZ {
+	/// The contents of this CResult_UpdateLogPersisterIOErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_UpdateLogPersisterIOErrorZPtr,
+	/// Whether this CResult_UpdateLogPersisterIOErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_UpdateLogPersisterIOErrorZ in the success state.
+pub extern "C" fn CResult_UpdateLogPersisterIOErrorZ_ok(o: UpdateLogPersister) -> CResult_UpdateLogPersisterIOErrorZ {
+	CResult_UpdateLogPersisterIOErrorZ {
+		contents: CResult_UpdateLogPersisterIOErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_UpdateLogPersisterIOErrorZ in the error state.
+pub extern "C" fn CResult_UpdateLogPersisterIOErrorZ_err(e: IOError) -> CResult_UpdateLogPersisterIOErrorZ {
+	CResult_UpdateLogPersisterIOErrorZ {
+		contents: CResult_UpdateLogPersisterIOErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_UpdateLogPersisterIOErrorZ_is_ok(o: &CResult_UpdateLogPersisterIOErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_UpdateLogPersisterIOErrorZ.
+pub extern "C" fn CResult_UpdateLogPersisterIOErrorZ_free(_res: CResult_UpdateLogPersisterIOErrorZ) { }
+impl Drop for CResult_UpdateLogPersisterIOErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Creates a new UpdateLogPersister which stores everything in the given `store`.
+///
+/// Once `max_pending_updates` updates have been logged for a channel, the next update causes
+/// the full `ChannelMonitor` to be rewritten and the logged updates to be removed. Larger values
+/// reduce write volume at the cost of slower loading. A value of 0 rewrites the full
+/// `ChannelMonitor` on every update.
+///
+/// Fails if the logged updates already in the store cannot be listed.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn UpdateLogPersister_new(mut store: KVStore, mut max_pending_updates: usize) -> CResult_UpdateLogPersisterIOErrorZ {
+	match nativeUpdateLogPersister::new(store, max_pending_updates) {
+		Ok(persister) => CResult_UpdateLogPersisterIOErrorZ_ok(UpdateLogPersister { inner: ObjOps::heap_alloc(persister), is_owned: true }),
+		Err(e) => CResult_UpdateLogPersisterIOErrorZ_err(IOError::from_rust(e)),
+	}
+}
+
+/// Read all `ChannelMonitor`s from the store, applying any logged `ChannelMonitorUpdate`s which
+/// were not yet compacted into the stored `ChannelMonitor`.
+///
+/// The `broadcaster`, `fee_estimator` and `logger` are passed to `ChannelMonitor_update_monitor`
+/// when replaying updates.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn UpdateLogPersister_read_channelmonitors(this_arg: &UpdateLogPersister, keys_manager: &KeysInterface, broadcaster: &BroadcasterInterface, fee_estimator: &FeeEstimator, logger: &Logger) -> crate::c_types::derived::CResult_CVec_C2Tuple_BlockHashChannelMonitorZZErrorZ {
+	let mut ret = this_arg.get_native_ref().read_channelmonitors(keys_manager, broadcaster, fee_estimator, logger);
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { let mut local_ret_0 = Vec::new(); for mut item in o.drain(..) { local_ret_0.push( { let (mut orig_ret_0_0_0, mut orig_ret_0_0_1) = item; let mut local_ret_0_0 = (crate::c_types::ThirtyTwoBytes { data: orig_ret_0_0_0.into_inner() }, crate::lightning::chain::channelmonitor::ChannelMonitor { inner: ObjOps::heap_alloc(orig_ret_0_0_1), is_owned: true }).into(); local_ret_0_0 }); }; local_ret_0.into() }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::c_types::IOError::from_rust(e) }).into() };
+	local_ret
+}
+
+impl From<nativeUpdateLogPersister> for crate::lightning::chain::chainmonitor::Persist {
+	fn from(obj: nativeUpdateLogPersister) -> Self {
+		let mut rust_obj = UpdateLogPersister { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = UpdateLogPersister_as_Persist(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(UpdateLogPersister_free_void);
+		ret
+	}
+}
+/// Constructs a new Persist which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned Persist must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn UpdateLogPersister_as_Persist(this_arg: &UpdateLogPersister) -> crate::lightning::chain::chainmonitor::Persist {
+	crate::lightning::chain::chainmonitor::Persist {
+		this_arg: unsafe { ObjOps::untweak_ptr((*this_arg).inner) as *mut c_void },
+		free: None,
+		persist_new_channel: UpdateLogPersister_Persist_persist_new_channel,
+		update_persisted_channel: UpdateLogPersister_Persist_update_persisted_channel,
+	}
+}
+
+#[must_use]
+extern "C" fn UpdateLogPersister_Persist_persist_new_channel(this_arg: *const c_void, mut funding_txo: crate::lightning::chain::transaction::OutPoint, monitor: &crate::lightning::chain::channelmonitor::ChannelMonitor, mut _update_id: crate::lightning::chain::chainmonitor::MonitorUpdateId) -> crate::c_types::derived::CResult_NoneChannelMonitorUpdateErrZ {
+	let mut ret = <nativeUpdateLogPersister as lightning::chain::chainmonitor::Persist<_>>::persist_new_channel(unsafe { &mut *(this_arg as *mut nativeUpdateLogPersister) }, *unsafe { Box::from_raw(funding_txo.take_inner()) }, monitor.get_native_ref(), *unsafe { Box::from_raw(_update_id.take_inner()) });
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { () /*o*/ }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::chain::ChannelMonitorUpdateErr::native_into(e) }).into() };
+	local_ret
+}
+#[must_use]
+extern "C" fn UpdateLogPersister_Persist_update_persisted_channel(this_arg: *const c_void, mut funding_txo: crate::lightning::chain::transaction::OutPoint, update: &crate::lightning::chain::channelmonitor::ChannelMonitorUpdate, monitor: &crate::lightning::chain::channelmonitor::ChannelMonitor, mut _update_id: crate::lightning::chain::chainmonitor::MonitorUpdateId) -> crate::c_types::derived::CResult_NoneChannelMonitorUpdateErrZ {
+	let mut local_update = if update.inner.is_null() { None } else { Some((* { update.get_native_ref() }).clone()) };
+	let mut ret = <nativeUpdateLogPersister as lightning::chain::chainmonitor::Persist<_>>::update_persisted_channel(unsafe { &mut *(this_arg as *mut nativeUpdateLogPersister) }, *unsafe { Box::from_raw(funding_txo.take_inner()) }, &local_update, monitor.get_native_ref(), *unsafe { Box::from_raw(_update_id.take_inner()) });
+	let mut local_ret = match ret { Ok(mut o) => crate::c_types::CResultTempl::ok( { () /*o*/ }).into(), Err(mut e) => crate::c_types::CResultTempl::err( { crate::lightning::chain::ChannelMonitorUpdateErr::native_into(e) }).into() };
+	local_ret
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::extensions::test_utils::*;
+
+	use std::sync::Arc;
+
+
+	fn persister(store: &Arc<MemoryStore>, max_pending_updates: usize) -> UpdateLogPersister {
+		UpdateLogPersister { inner: ObjOps::heap_alloc(nativeUpdateLogPersister::new(store.as_kv_store(), max_pending_updates).unwrap()), is_owned: true }
+	}
+
+	fn logged_update_ids(store: &Arc<MemoryStore>) -> Vec<u64> {
+		let mut ids: Vec<u64> = store.as_kv_store().list(MONITOR_UPDATE_NAMESPACE).unwrap().iter()
+			.map(|key| parse_update_key(key).unwrap().1).collect();
+		ids.sort_unstable();
+		ids
+	}
+
+	#[test]
+	fn update_keys_round_trip() {
+		let funding_txo = funding_outpoint();
+		assert_eq!(parse_update_key(&update_key(&funding_txo, 42)), Some((funding_txo, 42)));
+		assert_eq!(parse_update_key(&monitor_key(&funding_txo)), None);
+		assert_eq!(parse_update_key("not a key"), None);
+	}
+
+	#[test]
+	fn logs_updates_compacts_and_replays() {
+		let store = MemoryStore::new();
+		let keys = KeysInterface::from(keys_manager(2));
+		let monitor = channel_monitor();
+		let funding_txo = monitor.get_funding_txo().0;
+		{
+			let persister = persister(&store, 2);
+			let chain_monitor = chain_monitor(UpdateLogPersister_as_Persist(&persister));
+			watch_channel(&chain_monitor, monitor).unwrap();
+			assert!(logged_update_ids(&store).is_empty());
+
+			update_channel(&chain_monitor, funding_txo, 1).unwrap();
+			update_channel(&chain_monitor, funding_txo, 2).unwrap();
+			assert_eq!(logged_update_ids(&store), vec![1, 2]);
+			let stored = read_channelmonitors(&store.as_kv_store(), &keys).unwrap();
+			assert_eq!(stored[0].1.get_latest_update_id(), 0);
+
+			// The third update hits the limit, so the full monitor is written and the log cleared.
+			update_channel(&chain_monitor, funding_txo, 3).unwrap();
+			assert!(logged_update_ids(&store).is_empty());
+			let stored = read_channelmonitors(&store.as_kv_store(), &keys).unwrap();
+			assert_eq!(stored[0].1.get_latest_update_id(), 3);
+
+			update_channel(&chain_monitor, funding_txo, 4).unwrap();
+			assert_eq!(logged_update_ids(&store), vec![4]);
+		}
+
+		// After a restart the logged update is replayed on top of the full monitor.
+		let persister = persister(&store, 2);
+		assert_eq!(persister.get_native_ref().pending_update_count(&funding_txo), 1);
+		let monitors = persister.get_native_ref().read_channelmonitors(&keys, &broadcaster().0, &fee_estimator(253), &logger()).unwrap();
+		assert_eq!(monitors.len(), 1);
+		assert_eq!(monitors[0].1.get_funding_txo().0, funding_txo);
+		assert_eq!(monitors[0].1.get_latest_update_id(), 4);
+	}
+
+	#[test]
+	fn updates_already_in_the_monitor_are_skipped() {
+		let store = MemoryStore::new();
+		let keys = KeysInterface::from(keys_manager(2));
+		let monitor = channel_monitor();
+		let funding_txo = monitor.get_funding_txo().0;
+		{
+			let persister = persister(&store, 10);
+			let chain_monitor = chain_monitor(UpdateLogPersister_as_Persist(&persister));
+			watch_channel(&chain_monitor, monitor).unwrap();
+			update_channel(&chain_monitor, funding_txo, 1).unwrap();
+		}
+		// Simulate a compaction which wrote the full monitor but failed to remove the logged
+		// update, which must then not be applied a second time.
+		let mut stored = read_channelmonitors(&store.as_kv_store(), &keys).unwrap();
+		let (_, monitor) = stored.pop().unwrap();
+		assert!(monitor.update_monitor(&monitor_update(1), &broadcaster().0, &fee_estimator(253), &logger()).is_ok());
+		store.as_kv_store().write(CHANNEL_MONITOR_NAMESPACE, &monitor_key(&funding_txo), &monitor.encode()).unwrap();
+		assert_eq!(logged_update_ids(&store), vec![1]);
+
+		let persister = persister(&store, 10);
+		let monitors = persister.get_native_ref().read_channelmonitors(&keys, &broadcaster().0, &fee_estimator(253), &logger()).unwrap();
+		assert_eq!(monitors[0].1.get_latest_update_id(), 1);
+	}
+}
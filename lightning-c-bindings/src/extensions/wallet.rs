@@ -0,0 +1,1301 @@
+//! A minimal on-chain wallet for funding channels and sweeping the outputs LDK hands back.
+//!
+//! The wallet derives its keys from the same 32-byte seed as the `KeysManager`, following BIP 84
+//! (`wpkh(m/84'/coin'/0'/{0,1}/*)`), so that the seed is the only secret which needs backing up.
+//! It additionally watches the destination and shutdown scripts of the `KeysManager`, which
+//! cooperative channel closes pay to, so that those funds show up as ordinary wallet funds.
+//!
+//! Outputs are tracked as blocks or transactions are passed to `OnChainWallet_as_Listen` or
+//! `OnChainWallet_as_Confirm`, exactly as for the `ChannelManager` and `ChainMonitor`. When using
+//! `Confirm`, the scripts returned by `OnChainWallet_get_watched_scripts` must be watched with the
+//! chain source.
+//!
+//! Transactions the wallet broadcasts itself (payments and sweeps) are rebroadcast on every new
+//! block until they confirm. Funding transactions are broadcast by the `ChannelManager` instead.
+//!
+//! `OnChainWallet_event_handler` wraps an [`EventHandler`] to fund channels on
+//! `Event::FundingGenerationReady` and sweep `Event::SpendableOutputs` into the wallet
+//! automatically. Alternatively, `OnChainWallet_create_funding_transaction` and
+//! `OnChainWallet_sweep_spendable_outputs` can be called when handling those events.
+//!
+//! The wallet state (derivation indices and known outputs) must be persisted with
+//! `OnChainWallet_write` after any call which changes it, and restored with `OnChainWallet_read`.
+//! Keys are never serialized, the seed must be provided again on read.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use bitcoin::blockdata::opcodes;
+use bitcoin::blockdata::script::{Builder, Script};
+use bitcoin::blockdata::transaction::{OutPoint, SigHashType, Transaction as BitcoinTransaction, TxIn, TxOut};
+use bitcoin::hash_types::{Txid, WPubkeyHash};
+use bitcoin::hashes::Hash;
+use bitcoin::network::constants::Network as BitcoinNetwork;
+use bitcoin::secp256k1::{Message, Secp256k1, SecretKey as SecpSecretKey};
+use bitcoin::secp256k1::key::PublicKey as SecpPublicKey;
+use bitcoin::util::address::Address;
+use bitcoin::util::bip143;
+use bitcoin::util::bip32::{ChildNumber, ExtendedPrivKey, ExtendedPubKey};
+
+use lightning::chain::chaininterface::{BroadcasterInterface as _, ConfirmationTarget, FeeEstimator as _};
+use lightning::chain::keysinterface::{KeysInterface, SpendableOutputDescriptor};
+use lightning::ln::msgs::DecodeError as nativeDecodeError;
+use lightning::util::events::Event as nativeEvent;
+use lightning::util::ser::{Readable, ReadableArgs, Writeable, Writer};
+
+use crate::bitcoin::network::Network;
+use crate::c_types::*;
+use crate::c_types::derived::{CVec_CVec_u8ZZ, CVec_SpendableOutputDescriptorZ, CVec_u8Z};
+use crate::extensions::output_keys_manager;
+use crate::extensions::chain_listener::{as_confirm, as_listen, ChainListener};
+use crate::lightning::chain::{Confirm, Listen};
+use crate::lightning::chain::chaininterface::{BroadcasterInterface, FeeEstimator};
+use crate::lightning::chain::keysinterface::nativeKeysManager;
+use crate::lightning::ln::channelmanager::{ChannelManager, nativeChannelManager};
+use crate::lightning::ln::msgs::DecodeError;
+use crate::lightning::util::events::{Event, EventHandler};
+
+/// How many unused addresses past the last used one are watched on each chain.
+const LOOKAHEAD: u32 = 20;
+/// How deep a spend of one of our outputs must be buried before we forget the output.
+const ANTI_REORG_DELAY: u32 = 6;
+/// Outputs below this value are not created, any change below it goes to fees instead.
+const DUST_LIMIT_SATS: u64 = 546;
+/// The minimum feerate accepted by bitcoind's default relay policy.
+const MIN_FEERATE_SAT_PER_1000_WEIGHT: u32 = 253;
+/// nSequence signalling replaceability, as recommended by BIP 125.
+const RBF_SEQUENCE: u32 = 0xffff_fffd;
+
+/// Version, locktime, single-byte input and output counts and the segwit marker and flag.
+const TX_OVERHEAD_WEIGHT: u64 = (4 + 4 + 1 + 1) * 4 + 2;
+/// Outpoint, empty scriptSig and sequence, plus a witness with a maximum-size signature and a
+/// compressed public key.
+const P2WPKH_INPUT_WEIGHT: u64 = (32 + 4 + 1 + 4) * 4 + (1 + 1 + 73 + 1 + 33);
+/// Value, script length and a 22-byte P2WPKH script.
+const P2WPKH_OUTPUT_WEIGHT: u64 = (8 + 1 + 22) * 4;
+
+const SERIALIZATION_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The reasons an on-chain wallet operation can fail.
+pub enum WalletError {
+	/// The given address could not be parsed.
+	InvalidAddress,
+	/// The given address is for a different network than the wallet's.
+	WrongNetwork,
+	/// The amount to send is below the dust limit of 546 satoshis.
+	AmountBelowDust,
+	/// The wallet does not have enough confirmed funds to pay the amount and fees.
+	InsufficientFunds,
+	/// None of the given outputs need sweeping, as they pay to scripts the wallet already tracks.
+	NothingToSweep,
+	/// The `KeysManager` failed to build a transaction spending the given outputs, eg because
+	/// their value would not cover the fee.
+	SweepFailed,
+}
+
+#[repr(C)]
+/// The contents of CResult_TransactionWalletErrorZ
+pub union CResult_TransactionWalletErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut Transaction,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut WalletError,
+}
+#[repr(C)]
+/// A CResult_TransactionWalletErrorZ represents the result of a fallible operation,
+/// containing a crate::c_types::Transaction on success and a WalletError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_TransactionWalletErrorZ {
+	/// The contents of this CResult_TransactionWalletErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_TransactionWalletErrorZPtr,
+	/// Whether this CResult_TransactionWalletErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_TransactionWalletErrorZ in the success state.
+pub extern "C" fn CResult_TransactionWalletErrorZ_ok(o: Transaction) -> CResult_TransactionWalletErrorZ {
+	CResult_TransactionWalletErrorZ {
+		contents: CResult_TransactionWalletErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_TransactionWalletErrorZ in the error state.
+pub extern "C" fn CResult_TransactionWalletErrorZ_err(e: WalletError) -> CResult_TransactionWalletErrorZ {
+	CResult_TransactionWalletErrorZ {
+		contents: CResult_TransactionWalletErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_TransactionWalletErrorZ_is_ok(o: &CResult_TransactionWalletErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_TransactionWalletErrorZ.
+pub extern "C" fn CResult_TransactionWalletErrorZ_free(_res: CResult_TransactionWalletErrorZ) { }
+impl Drop for CResult_TransactionWalletErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+impl CResult_TransactionWalletErrorZ {
+	fn from_rust(res: Result<BitcoinTransaction, WalletError>) -> Self {
+		match res {
+			Ok(tx) => CResult_TransactionWalletErrorZ_ok(Transaction::from_bitcoin(&tx)),
+			Err(e) => CResult_TransactionWalletErrorZ_err(e),
+		}
+	}
+}
+
+#[repr(C)]
+/// The contents of CResult_OnChainWalletDecodeErrorZ
+pub union CResult_OnChainWalletDecodeErrorZPtr {
+	/// A pointer to the contents in the success state.
+	/// Reading from this pointer when `result_ok` is not set is undefined.
+	pub result: *mut OnChainWallet,
+	/// A pointer to the contents in the error state.
+	/// Reading from this pointer when `result_ok` is set is undefined.
+	pub err: *mut DecodeError,
+}
+#[repr(C)]
+/// A CResult_OnChainWalletDecodeErrorZ represents the result of a fallible operation,
+/// containing a OnChainWallet on success and a crate::lightning::ln::msgs::DecodeError on failure.
+/// `result_ok` indicates the overall state, and the contents are provided via `contents`.
+pub struct CResult_OnChainWalletDecodeErrorZ {
+	/// The contents of this CResult_OnChainWalletDecodeErrorZ, accessible via either
+	/// `err` or `result` depending on the state of `result_ok`.
+	pub contents: CResult_OnChainWalletDecodeErrorZPtr,
+	/// Whether this CResult_OnChainWalletDecodeErrorZ represents a success state.
+	pub result_ok: bool,
+}
+#[no_mangle]
+/// Creates a new CResult_OnChainWalletDecodeErrorZ in the success state.
+pub extern "C" fn CResult_OnChainWalletDecodeErrorZ_ok(o: OnChainWallet) -> CResult_OnChainWalletDecodeErrorZ {
+	CResult_OnChainWalletDecodeErrorZ {
+		contents: CResult_OnChainWalletDecodeErrorZPtr {
+			result: Box::into_raw(Box::new(o)),
+		},
+		result_ok: true,
+	}
+}
+#[no_mangle]
+/// Creates a new CResult_OnChainWalletDecodeErrorZ in the error state.
+pub extern "C" fn CResult_OnChainWalletDecodeErrorZ_err(e: DecodeError) -> CResult_OnChainWalletDecodeErrorZ {
+	CResult_OnChainWalletDecodeErrorZ {
+		contents: CResult_OnChainWalletDecodeErrorZPtr {
+			err: Box::into_raw(Box::new(e)),
+		},
+		result_ok: false,
+	}
+}
+/// Checks if the given object is currently in the success state
+#[no_mangle]
+pub extern "C" fn CResult_OnChainWalletDecodeErrorZ_is_ok(o: &CResult_OnChainWalletDecodeErrorZ) -> bool {
+	o.result_ok
+}
+#[no_mangle]
+/// Frees any resources used by the CResult_OnChainWalletDecodeErrorZ.
+pub extern "C" fn CResult_OnChainWalletDecodeErrorZ_free(_res: CResult_OnChainWalletDecodeErrorZ) { }
+impl Drop for CResult_OnChainWalletDecodeErrorZ {
+	fn drop(&mut self) {
+		if self.result_ok {
+			if unsafe { !(self.contents.result as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.result) };
+			}
+		} else {
+			if unsafe { !(self.contents.err as *mut ()).is_null() } {
+				let _ = unsafe { Box::from_raw(self.contents.err) };
+			}
+		}
+	}
+}
+
+/// Where the key for one of our scripts comes from.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum KeySource {
+	/// `m/84'/coin'/0'/0/i`, handed out as receive addresses.
+	External(u32),
+	/// `m/84'/coin'/0'/1/i`, used for change and sweeps.
+	Internal(u32),
+	/// The `KeysManager`'s destination script, `m/1'`.
+	LdkDestination,
+	/// The `KeysManager`'s shutdown script, `m/2'`.
+	LdkShutdown,
+}
+
+impl Writeable for KeySource {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), ::std::io::Error> {
+		match self {
+			KeySource::External(idx) => { 0u8.write(w)?; idx.write(w) },
+			KeySource::Internal(idx) => { 1u8.write(w)?; idx.write(w) },
+			KeySource::LdkDestination => 2u8.write(w),
+			KeySource::LdkShutdown => 3u8.write(w),
+		}
+	}
+}
+impl Readable for KeySource {
+	fn read<R: ::std::io::Read>(r: &mut R) -> Result<Self, nativeDecodeError> {
+		match <u8 as Readable>::read(r)? {
+			0 => Ok(KeySource::External(Readable::read(r)?)),
+			1 => Ok(KeySource::Internal(Readable::read(r)?)),
+			2 => Ok(KeySource::LdkDestination),
+			3 => Ok(KeySource::LdkShutdown),
+			_ => Err(nativeDecodeError::InvalidValue),
+		}
+	}
+}
+
+/// Writes an optional height, using u32::MAX for None.
+fn write_height<W: Writer>(height: Option<u32>, w: &mut W) -> Result<(), ::std::io::Error> {
+	height.unwrap_or(u32::max_value()).write(w)
+}
+fn read_height<R: ::std::io::Read>(r: &mut R) -> Result<Option<u32>, nativeDecodeError> {
+	let height: u32 = Readable::read(r)?;
+	Ok(if height == u32::max_value() { None } else { Some(height) })
+}
+
+struct Utxo {
+	txout: TxOut,
+	source: KeySource,
+	/// The height of the block containing the transaction creating this output, if any.
+	confirmation_height: Option<u32>,
+	/// The transaction spending this output and the height it confirmed at, if any. Outputs
+	/// spent by a transaction of ours which has not confirmed yet are locked.
+	spent_by: Option<(Txid, Option<u32>)>,
+}
+
+struct WalletState {
+	/// The next unused index on the external and internal chains.
+	next_index: [u32; 2],
+	/// The number of scripts derived and watched on the external and internal chains.
+	derived: [u32; 2],
+	scripts: HashMap<Script, KeySource>,
+	utxos: HashMap<OutPoint, Utxo>,
+	/// The transactions we broadcast and the height they confirmed at, if any, until their
+	/// confirmation is `ANTI_REORG_DELAY` deep.
+	broadcast: HashMap<Txid, (BitcoinTransaction, Option<u32>)>,
+	best_height: u32,
+}
+
+impl WalletState {
+	fn confirmations(&self, height: Option<u32>) -> u32 {
+		match height {
+			Some(height) if height <= self.best_height => self.best_height - height + 1,
+			_ => 0,
+		}
+	}
+}
+
+fn p2wpkh_script(pubkey: &SecpPublicKey) -> Script {
+	let wpubkey_hash = WPubkeyHash::hash(&pubkey.serialize());
+	Builder::new().push_opcode(opcodes::all::OP_PUSHBYTES_0).push_slice(&wpubkey_hash.into_inner()).into_script()
+}
+
+fn fee_for_weight(weight: u64, feerate_sat_per_1000_weight: u32) -> u64 {
+	(weight * feerate_sat_per_1000_weight as u64 + 999) / 1000
+}
+
+fn output_weight(script_pubkey: &Script) -> u64 {
+	// Scripts longer than 252 bytes would need a three-byte length, which no standard output has.
+	(8 + 1 + script_pubkey.len() as u64) * 4
+}
+
+/// The result of coin selection: the inputs to spend, the fee to pay and the value of the change
+/// output, if one should be added.
+struct CoinSelection {
+	inputs: Vec<OutPoint>,
+	fee: u64,
+	change: Option<u64>,
+}
+
+/// Selects inputs from `available` to pay `target` satoshis plus fees, largest first, for a
+/// transaction whose non-input, non-change parts weigh `fixed_weight`.
+fn select_coins(mut available: Vec<(OutPoint, u64)>, target: u64, fixed_weight: u64, feerate_sat_per_1000_weight: u32) -> Result<CoinSelection, WalletError> {
+	available.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+	let mut inputs = Vec::new();
+	let mut total = 0u64;
+	for (outpoint, value) in available {
+		inputs.push(outpoint);
+		total += value;
+		let weight = fixed_weight + inputs.len() as u64 * P2WPKH_INPUT_WEIGHT;
+		let fee = fee_for_weight(weight, feerate_sat_per_1000_weight);
+		if total < target + fee { continue; }
+
+		let fee_with_change = fee_for_weight(weight + P2WPKH_OUTPUT_WEIGHT, feerate_sat_per_1000_weight);
+		if total >= target + fee_with_change + DUST_LIMIT_SATS {
+			return Ok(CoinSelection { inputs, fee: fee_with_change, change: Some(total - target - fee_with_change) });
+		}
+		return Ok(CoinSelection { fee: total - target, inputs, change: None });
+	}
+	Err(WalletError::InsufficientFunds)
+}
+
+pub(crate) struct nativeOnChainWallet {
+	network: BitcoinNetwork,
+	master_key: ExtendedPrivKey,
+	/// `m/84'/coin'/0'`
+	account_key: ExtendedPrivKey,
+	secp_ctx: Secp256k1<bitcoin::secp256k1::All>,
+	/// A `KeysManager` for the same seed, used to spend `SpendableOutputDescriptor`s.
+	keys_manager: nativeKeysManager,
+	broadcaster: BroadcasterInterface,
+	fee_estimator: FeeEstimator,
+	state: Mutex<WalletState>,
+}
+
+impl nativeOnChainWallet {
+	fn new(seed: &[u8; 32], network: BitcoinNetwork, broadcaster: BroadcasterInterface, fee_estimator: FeeEstimator) -> Self {
+		let secp_ctx = Secp256k1::new();
+		let master_key = ExtendedPrivKey::new_master(network, seed).expect("Any 32-byte seed is a valid BIP 32 seed");
+		let coin_type = if network == BitcoinNetwork::Bitcoin { 0 } else { 1 };
+		let account_path = [ChildNumber::from_hardened_idx(84).unwrap(), ChildNumber::from_hardened_idx(coin_type).unwrap(), ChildNumber::from_hardened_idx(0).unwrap()];
+		let account_key = master_key.derive_priv(&secp_ctx, &account_path).expect("Derivation of hardened keys cannot fail");
+		let keys_manager = output_keys_manager(seed);
+
+		let wallet = Self {
+			network, master_key, account_key, secp_ctx, keys_manager, broadcaster, fee_estimator,
+			state: Mutex::new(WalletState {
+				next_index: [0; 2], derived: [0; 2], scripts: HashMap::new(), utxos: HashMap::new(), broadcast: HashMap::new(), best_height: 0,
+			}),
+		};
+		{
+			let mut state = wallet.state.lock().unwrap();
+			// Only watch the KeysManager's scripts if they match the keys we derive for them, in
+			// case a future KeysManager derives them differently.
+			let destination_script = wallet.keys_manager.get_destination_script();
+			if wallet.script_for(KeySource::LdkDestination) == destination_script {
+				state.scripts.insert(destination_script, KeySource::LdkDestination);
+			}
+			let shutdown_script = wallet.keys_manager.get_shutdown_scriptpubkey().into_inner();
+			if wallet.script_for(KeySource::LdkShutdown) == shutdown_script {
+				state.scripts.insert(shutdown_script, KeySource::LdkShutdown);
+			}
+			wallet.derive_lookahead(&mut state);
+		}
+		wallet
+	}
+
+	/// The BIP 32 fingerprint of the master key, identifying the seed.
+	fn fingerprint(&self) -> u32 {
+		let fingerprint = self.master_key.fingerprint(&self.secp_ctx);
+		u32::from_be_bytes([fingerprint[0], fingerprint[1], fingerprint[2], fingerprint[3]])
+	}
+
+	fn secret_key(&self, source: KeySource) -> SecpSecretKey {
+		let key = match source {
+			KeySource::External(idx) => self.account_key.derive_priv(&self.secp_ctx, &[ChildNumber::from_normal_idx(0).unwrap(), ChildNumber::from_normal_idx(idx).unwrap()]),
+			KeySource::Internal(idx) => self.account_key.derive_priv(&self.secp_ctx, &[ChildNumber::from_normal_idx(1).unwrap(), ChildNumber::from_normal_idx(idx).unwrap()]),
+			KeySource::LdkDestination => self.master_key.ckd_priv(&self.secp_ctx, ChildNumber::from_hardened_idx(1).unwrap()),
+			KeySource::LdkShutdown => self.master_key.ckd_priv(&self.secp_ctx, ChildNumber::from_hardened_idx(2).unwrap()),
+		};
+		key.expect("Indices are always below 2^31").private_key.key
+	}
+
+	fn script_for(&self, source: KeySource) -> Script {
+		p2wpkh_script(&SecpPublicKey::from_secret_key(&self.secp_ctx, &self.secret_key(source)))
+	}
+
+	fn address_for(&self, source: KeySource) -> Address {
+		Address::from_script(&self.script_for(source), self.network).expect("P2WPKH scripts always have an address")
+	}
+
+	/// Derives scripts on both chains until `LOOKAHEAD` unused ones are watched past the next
+	/// unused index.
+	fn derive_lookahead(&self, state: &mut WalletState) {
+		for chain in 0..2 {
+			while state.derived[chain] < state.next_index[chain] + LOOKAHEAD {
+				let idx = state.derived[chain];
+				let source = if chain == 0 { KeySource::External(idx) } else { KeySource::Internal(idx) };
+				state.scripts.insert(self.script_for(source), source);
+				state.derived[chain] += 1;
+			}
+		}
+	}
+
+	fn next_script(&self, state: &mut WalletState, chain: usize) -> (Script, KeySource) {
+		let idx = state.next_index[chain];
+		let source = if chain == 0 { KeySource::External(idx) } else { KeySource::Internal(idx) };
+		state.next_index[chain] += 1;
+		self.derive_lookahead(state);
+		(self.script_for(source), source)
+	}
+
+	fn mark_used(&self, state: &mut WalletState, source: KeySource) {
+		let (chain, idx) = match source {
+			KeySource::External(idx) => (0, idx),
+			KeySource::Internal(idx) => (1, idx),
+			_ => return,
+		};
+		if idx >= state.next_index[chain] {
+			state.next_index[chain] = idx + 1;
+			self.derive_lookahead(state);
+		}
+	}
+
+	fn feerate(&self, target: ConfirmationTarget) -> u32 {
+		std::cmp::max(self.fee_estimator.get_est_sat_per_1000_weight(target), MIN_FEERATE_SAT_PER_1000_WEIGHT)
+	}
+
+	/// Records the outputs a transaction pays us and the outputs of ours it spends.
+	fn process_transaction(&self, state: &mut WalletState, tx: &BitcoinTransaction, height: Option<u32>) {
+		let txid = tx.txid();
+		if let Some((_, confirmation_height)) = state.broadcast.get_mut(&txid) {
+			*confirmation_height = height;
+		}
+		for input in tx.input.iter() {
+			if let Some(utxo) = state.utxos.get_mut(&input.previous_output) {
+				utxo.spent_by = Some((txid, height));
+			}
+		}
+		for (vout, output) in tx.output.iter().enumerate() {
+			let source = match state.scripts.get(&output.script_pubkey) {
+				Some(source) => *source,
+				None => continue,
+			};
+			let outpoint = OutPoint { txid, vout: vout as u32 };
+			match state.utxos.get_mut(&outpoint) {
+				Some(utxo) => utxo.confirmation_height = height,
+				None => { state.utxos.insert(outpoint, Utxo { txout: output.clone(), source, confirmation_height: height, spent_by: None }); },
+			}
+			self.mark_used(state, source);
+		}
+	}
+
+	/// Returns the transactions we broadcast which are still unconfirmed, to be rebroadcast.
+	fn best_block_updated(&self, state: &mut WalletState, height: u32) -> Vec<BitcoinTransaction> {
+		state.best_height = height;
+		let utxos = &state.utxos;
+		state.broadcast.retain(|txid, (tx, confirmation_height)| match confirmation_height {
+			Some(confirmation_height) => *confirmation_height + ANTI_REORG_DELAY > height + 1,
+			// Give up on transactions which were double-spent by a confirmed transaction.
+			None => !tx.input.iter().any(|input| match utxos.get(&input.previous_output) {
+				Some(Utxo { spent_by: Some((spend_txid, Some(_))), .. }) => spend_txid != txid,
+				_ => false,
+			}),
+		});
+		// Forget outputs whose spend can no longer be reorganized out.
+		state.utxos.retain(|_, utxo| match utxo.spent_by {
+			Some((_, Some(spend_height))) => spend_height + ANTI_REORG_DELAY > height + 1,
+			_ => true,
+		});
+		state.broadcast.values().filter(|(_, confirmation_height)| confirmation_height.is_none()).map(|(tx, _)| tx.clone()).collect()
+	}
+
+	fn transaction_unconfirmed(&self, state: &mut WalletState, txid: &Txid) {
+		if let Some((_, confirmation_height)) = state.broadcast.get_mut(txid) {
+			*confirmation_height = None;
+		}
+		for (outpoint, utxo) in state.utxos.iter_mut() {
+			if outpoint.txid == *txid { utxo.confirmation_height = None; }
+			if let Some((spend_txid, spend_height)) = &mut utxo.spent_by {
+				if spend_txid == txid { *spend_height = None; }
+			}
+		}
+	}
+
+	fn block_disconnected(&self, state: &mut WalletState, height: u32) {
+		for (_, confirmation_height) in state.broadcast.values_mut() {
+			if confirmation_height.map(|h| h >= height).unwrap_or(false) { *confirmation_height = None; }
+		}
+		for utxo in state.utxos.values_mut() {
+			if utxo.confirmation_height.map(|h| h >= height).unwrap_or(false) { utxo.confirmation_height = None; }
+			if let Some((_, spend_height)) = &mut utxo.spent_by {
+				if spend_height.map(|h| h >= height).unwrap_or(false) { *spend_height = None; }
+			}
+		}
+		state.best_height = height.saturating_sub(1);
+	}
+
+	fn balance(&self, min_confirmations: u32) -> u64 {
+		let state = self.state.lock().unwrap();
+		state.utxos.values()
+			.filter(|utxo| utxo.spent_by.is_none() && state.confirmations(utxo.confirmation_height) >= min_confirmations)
+			.map(|utxo| utxo.txout.value)
+			.sum()
+	}
+
+	/// Builds and signs a transaction paying `outputs` from confirmed wallet funds, locking the
+	/// inputs and tracking any change. The transaction is not broadcast.
+	fn create_transaction(&self, mut outputs: Vec<TxOut>, target: ConfirmationTarget) -> Result<BitcoinTransaction, WalletError> {
+		let feerate = self.feerate(target);
+		let mut state = self.state.lock().unwrap();
+
+		let available = state.utxos.iter()
+			.filter(|(_, utxo)| utxo.spent_by.is_none() && state.confirmations(utxo.confirmation_height) >= 1)
+			.map(|(outpoint, utxo)| (*outpoint, utxo.txout.value))
+			.collect();
+		let target_value = outputs.iter().map(|output| output.value).sum();
+		let fixed_weight = TX_OVERHEAD_WEIGHT + outputs.iter().map(|output| output_weight(&output.script_pubkey)).sum::<u64>();
+		let selection = select_coins(available, target_value, fixed_weight, feerate)?;
+
+		if let Some(change) = selection.change {
+			let (script_pubkey, _) = self.next_script(&mut state, 1);
+			outputs.push(TxOut { value: change, script_pubkey });
+		}
+		let mut tx = BitcoinTransaction {
+			version: 2,
+			// Set the locktime to the current height to discourage fee sniping.
+			lock_time: state.best_height,
+			input: selection.inputs.iter().map(|outpoint| TxIn {
+				previous_output: *outpoint, script_sig: Script::new(), sequence: RBF_SEQUENCE, witness: Vec::new(),
+			}).collect(),
+			output: outputs,
+		};
+
+		let mut witnesses = Vec::with_capacity(tx.input.len());
+		{
+			let mut sighash_cache = bip143::SigHashCache::new(&tx);
+			for (idx, outpoint) in selection.inputs.iter().enumerate() {
+				let utxo = &state.utxos[outpoint];
+				let secret_key = self.secret_key(utxo.source);
+				let pubkey = bitcoin::PublicKey { compressed: true, key: SecpPublicKey::from_secret_key(&self.secp_ctx, &secret_key) };
+				let script_code = Address::p2pkh(&pubkey, self.network).script_pubkey();
+				let sighash = sighash_cache.signature_hash(idx, &script_code, utxo.txout.value, SigHashType::All);
+				let sig = self.secp_ctx.sign(&Message::from_slice(&sighash[..]).unwrap(), &secret_key);
+				let mut sig_bytes = sig.serialize_der().to_vec();
+				sig_bytes.push(SigHashType::All as u8);
+				witnesses.push(vec![sig_bytes, pubkey.to_bytes()]);
+			}
+		}
+		for (input, witness) in tx.input.iter_mut().zip(witnesses.drain(..)) {
+			input.witness = witness;
+		}
+
+		self.process_transaction(&mut state, &tx, None);
+		Ok(tx)
+	}
+
+	fn send_to_address(&self, address: &str, amount_sats: u64) -> Result<BitcoinTransaction, WalletError> {
+		let address = Address::from_str(address).map_err(|_| WalletError::InvalidAddress)?;
+		let same_network = match (address.network, self.network) {
+			// Testnet and signet addresses are indistinguishable.
+			(BitcoinNetwork::Testnet, BitcoinNetwork::Signet) => true,
+			(a, b) => a == b,
+		};
+		if !same_network { return Err(WalletError::WrongNetwork); }
+		if amount_sats < DUST_LIMIT_SATS { return Err(WalletError::AmountBelowDust); }
+		let tx = self.create_transaction(vec![TxOut { value: amount_sats, script_pubkey: address.script_pubkey() }], ConfirmationTarget::Normal)?;
+		self.state.lock().unwrap().broadcast.insert(tx.txid(), (tx.clone(), None));
+		self.broadcaster.broadcast_transaction(&tx);
+		Ok(tx)
+	}
+
+	fn create_funding_transaction(&self, output_script: Script, value_sats: u64) -> Result<BitcoinTransaction, WalletError> {
+		self.create_transaction(vec![TxOut { value: value_sats, script_pubkey: output_script }], ConfirmationTarget::Normal)
+	}
+
+	/// Unlocks the inputs of a transaction of ours which was never broadcast, and forgets its
+	/// outputs.
+	fn abandon_transaction(&self, tx: &BitcoinTransaction) {
+		let txid = tx.txid();
+		let mut state = self.state.lock().unwrap();
+		state.broadcast.remove(&txid);
+		state.utxos.retain(|outpoint, utxo| outpoint.txid != txid || utxo.confirmation_height.is_some());
+		for utxo in state.utxos.values_mut() {
+			if let Some((spend_txid, None)) = utxo.spent_by {
+				if spend_txid == txid { utxo.spent_by = None; }
+			}
+		}
+	}
+
+	fn sweep_spendable_outputs(&self, descriptors: Vec<SpendableOutputDescriptor>) -> Result<BitcoinTransaction, WalletError> {
+		let mut state = self.state.lock().unwrap();
+		// Outputs paying to scripts we watch already show up as wallet funds.
+		let descriptors: Vec<_> = descriptors.into_iter().filter(|descriptor| match descriptor {
+			SpendableOutputDescriptor::StaticOutput { output, .. } => !state.scripts.contains_key(&output.script_pubkey),
+			_ => true,
+		}).collect();
+		if descriptors.is_empty() { return Err(WalletError::NothingToSweep); }
+
+		let (change_script, _) = self.next_script(&mut state, 1);
+		let tx = self.keys_manager.spend_spendable_outputs(&descriptors.iter().collect::<Vec<_>>()[..], Vec::new(),
+			change_script, self.feerate(ConfirmationTarget::Background), &self.secp_ctx).map_err(|_| WalletError::SweepFailed)?;
+		self.process_transaction(&mut state, &tx, None);
+		state.broadcast.insert(tx.txid(), (tx.clone(), None));
+		// The broadcaster may call back into the wallet, so it must not be called under the lock.
+		std::mem::drop(state);
+		self.broadcaster.broadcast_transaction(&tx);
+		Ok(tx)
+	}
+
+	fn descriptor(&self, internal: bool) -> String {
+		let fingerprint = self.master_key.fingerprint(&self.secp_ctx);
+		let coin_type = if self.network == BitcoinNetwork::Bitcoin { 0 } else { 1 };
+		let account_xpub = ExtendedPubKey::from_private(&self.secp_ctx, &self.account_key);
+		let descriptor = format!("wpkh([{}/84h/{}h/0h]{}/{}/*)", fingerprint, coin_type, account_xpub, if internal { 1 } else { 0 });
+		let checksum = descriptor_checksum(&descriptor);
+		format!("{}#{}", descriptor, checksum)
+	}
+}
+
+/// Computes the BIP 380 checksum of an output script descriptor.
+fn descriptor_checksum(descriptor: &str) -> String {
+	const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+	const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+	const GENERATOR: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+
+	fn polymod(chk: u64, value: u64) -> u64 {
+		let top = chk >> 35;
+		let mut chk = ((chk & 0x7ffffffff) << 5) ^ value;
+		for (i, generator) in GENERATOR.iter().enumerate() {
+			if (top >> i) & 1 == 1 { chk ^= generator; }
+		}
+		chk
+	}
+
+	let mut chk = 1;
+	let mut class_group = 0;
+	let mut class_count = 0;
+	for c in descriptor.chars() {
+		// Our descriptors only contain characters from the input charset.
+		let pos = INPUT_CHARSET.find(c).expect("Descriptor contains an invalid character") as u64;
+		chk = polymod(chk, pos & 31);
+		class_group = class_group * 3 + (pos >> 5);
+		class_count += 1;
+		if class_count == 3 {
+			chk = polymod(chk, class_group);
+			class_group = 0;
+			class_count = 0;
+		}
+	}
+	if class_count > 0 { chk = polymod(chk, class_group); }
+	for _ in 0..8 { chk = polymod(chk, 0); }
+	chk ^= 1;
+	(0..8).map(|i| CHECKSUM_CHARSET[((chk >> (5 * (7 - i))) & 31) as usize] as char).collect()
+}
+
+impl Writeable for nativeOnChainWallet {
+	fn write<W: Writer>(&self, w: &mut W) -> Result<(), ::std::io::Error> {
+		let state = self.state.lock().unwrap();
+		SERIALIZATION_VERSION.write(w)?;
+		self.fingerprint().write(w)?;
+		state.next_index[0].write(w)?;
+		state.next_index[1].write(w)?;
+		state.best_height.write(w)?;
+		(state.utxos.len() as u32).write(w)?;
+		for (outpoint, utxo) in state.utxos.iter() {
+			outpoint.txid.write(w)?;
+			outpoint.vout.write(w)?;
+			utxo.txout.value.write(w)?;
+			utxo.txout.script_pubkey.write(w)?;
+			utxo.source.write(w)?;
+			write_height(utxo.confirmation_height, w)?;
+			match &utxo.spent_by {
+				None => 0u8.write(w)?,
+				Some((txid, height)) => {
+					1u8.write(w)?;
+					txid.write(w)?;
+					write_height(*height, w)?;
+				},
+			}
+		}
+		(state.broadcast.len() as u32).write(w)?;
+		for (tx, confirmation_height) in state.broadcast.values() {
+			tx.write(w)?;
+			write_height(*confirmation_height, w)?;
+		}
+		Ok(())
+	}
+}
+
+pub(crate) struct OnChainWalletReadArgs {
+	seed: [u8; 32],
+	network: BitcoinNetwork,
+	broadcaster: BroadcasterInterface,
+	fee_estimator: FeeEstimator,
+}
+
+impl ReadableArgs<OnChainWalletReadArgs> for nativeOnChainWallet {
+	fn read<R: ::std::io::Read>(r: &mut R, args: OnChainWalletReadArgs) -> Result<Self, nativeDecodeError> {
+		let version: u8 = Readable::read(r)?;
+		if version != SERIALIZATION_VERSION { return Err(nativeDecodeError::UnknownVersion); }
+		let wallet = nativeOnChainWallet::new(&args.seed, args.network, args.broadcaster, args.fee_estimator);
+		let fingerprint: u32 = Readable::read(r)?;
+		if fingerprint != wallet.fingerprint() {
+			// The wallet was written for a different seed.
+			return Err(nativeDecodeError::InvalidValue);
+		}
+		{
+			let mut state = wallet.state.lock().unwrap();
+			state.next_index = [Readable::read(r)?, Readable::read(r)?];
+			state.best_height = Readable::read(r)?;
+			let utxo_count: u32 = Readable::read(r)?;
+			for _ in 0..utxo_count {
+				let outpoint = OutPoint { txid: Readable::read(r)?, vout: Readable::read(r)? };
+				let txout = TxOut { value: Readable::read(r)?, script_pubkey: Readable::read(r)? };
+				let source = Readable::read(r)?;
+				let confirmation_height = read_height(r)?;
+				let spent_by = match <u8 as Readable>::read(r)? {
+					0 => None,
+					1 => Some((Readable::read(r)?, read_height(r)?)),
+					_ => return Err(nativeDecodeError::InvalidValue),
+				};
+				state.utxos.insert(outpoint, Utxo { txout, source, confirmation_height, spent_by });
+			}
+			let broadcast_count: u32 = Readable::read(r)?;
+			for _ in 0..broadcast_count {
+				let tx: BitcoinTransaction = Readable::read(r)?;
+				state.broadcast.insert(tx.txid(), (tx, read_height(r)?));
+			}
+			if state.next_index.iter().any(|idx| *idx >= (1 << 31) - LOOKAHEAD) {
+				return Err(nativeDecodeError::InvalidValue);
+			}
+			wallet.derive_lookahead(&mut state);
+		}
+		Ok(wallet)
+	}
+}
+
+/// A minimal BIP 84 on-chain wallet derived from the `KeysManager` seed, which funds channels and
+/// sweeps outputs LDK hands back. See the module documentation for details.
+#[must_use]
+#[repr(C)]
+pub struct OnChainWallet {
+	/// A pointer to the opaque Rust object.
+
+	/// Nearly everywhere, inner must be non-null, however in places where
+	/// the Rust equivalent takes an Option, it may be set to null to indicate None.
+	pub inner: *mut nativeOnChainWallet,
+	/// Indicates that this is the only struct which contains the same pointer.
+
+	/// Rust functions which take ownership of an object provided via an argument require
+	/// this to be true and invalidate the object pointed to by inner.
+	pub is_owned: bool,
+}
+
+impl Drop for OnChainWallet {
+	fn drop(&mut self) {
+		if self.is_owned && !<*mut nativeOnChainWallet>::is_null(self.inner) {
+			let _ = unsafe { Box::from_raw(ObjOps::untweak_ptr(self.inner)) };
+		}
+	}
+}
+/// Frees any resources used by the OnChainWallet, if is_owned is set and inner is non-NULL.
+#[no_mangle]
+pub extern "C" fn OnChainWallet_free(this_obj: OnChainWallet) { }
+#[allow(unused)]
+/// Used only if an object of this type is returned as a trait impl by a method
+pub(crate) extern "C" fn OnChainWallet_free_void(this_ptr: *mut c_void) {
+	unsafe { let _ = Box::from_raw(this_ptr as *mut nativeOnChainWallet); }
+}
+#[allow(unused)]
+impl OnChainWallet {
+	pub(crate) fn get_native_ref(&self) -> &'static nativeOnChainWallet {
+		unsafe { &*ObjOps::untweak_ptr(self.inner) }
+	}
+	pub(crate) fn get_native_mut_ref(&self) -> &'static mut nativeOnChainWallet {
+		unsafe { &mut *ObjOps::untweak_ptr(self.inner) }
+	}
+	/// When moving out of the pointer, we have to ensure we aren't a reference, this makes that easy
+	pub(crate) fn take_inner(mut self) -> *mut nativeOnChainWallet {
+		assert!(self.is_owned);
+		let ret = ObjOps::untweak_ptr(self.inner);
+		self.inner = std::ptr::null_mut();
+		ret
+	}
+}
+
+/// Creates a new wallet from the same seed as passed to `KeysManager_new`, with no known outputs.
+///
+/// Transactions are broadcast with `broadcaster`, and fees are taken from `fee_estimator`'s
+/// `Normal` target for payments and channel funding and its `Background` target for sweeps.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_new(seed: *const [u8; 32], mut network: Network, mut broadcaster: BroadcasterInterface, mut fee_estimator: FeeEstimator) -> OnChainWallet {
+	OnChainWallet { inner: ObjOps::heap_alloc(nativeOnChainWallet::new(unsafe { &*seed }, network.into_bitcoin(), broadcaster, fee_estimator)), is_owned: true }
+}
+
+#[no_mangle]
+/// Serialize the OnChainWallet object into a byte array which can be read by OnChainWallet_read
+pub extern "C" fn OnChainWallet_write(obj: &OnChainWallet) -> CVec_u8Z {
+	crate::c_types::serialize_obj(unsafe { &*obj }.get_native_ref())
+}
+#[no_mangle]
+/// Read an OnChainWallet from a byte array, created by OnChainWallet_write, given the same seed
+/// and network it was created with.
+pub extern "C" fn OnChainWallet_read(ser: u8slice, seed: *const [u8; 32], mut network: Network, mut broadcaster: BroadcasterInterface, mut fee_estimator: FeeEstimator) -> CResult_OnChainWalletDecodeErrorZ {
+	let args = OnChainWalletReadArgs { seed: unsafe { *seed }, network: network.into_bitcoin(), broadcaster, fee_estimator };
+	let res: Result<nativeOnChainWallet, nativeDecodeError> = crate::c_types::deserialize_obj_arg(ser, args);
+	match res {
+		Ok(wallet) => CResult_OnChainWalletDecodeErrorZ_ok(OnChainWallet { inner: ObjOps::heap_alloc(wallet), is_owned: true }),
+		Err(e) => CResult_OnChainWalletDecodeErrorZ_err(DecodeError { inner: ObjOps::heap_alloc(e), is_owned: true }),
+	}
+}
+
+/// Returns a new, unused receive address.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_get_new_address(this_arg: &OnChainWallet) -> Str {
+	let wallet = this_arg.get_native_ref();
+	let mut state = wallet.state.lock().unwrap();
+	let (_, source) = wallet.next_script(&mut state, 0);
+	wallet.address_for(source).to_string().into()
+}
+
+/// Returns the public output descriptor (with checksum) of the receive chain, or of the change
+/// chain if `internal` is set, eg for importing into a watch-only wallet.
+///
+/// This does not cover the `KeysManager` destination and shutdown scripts.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_get_descriptor(this_arg: &OnChainWallet, mut internal: bool) -> Str {
+	this_arg.get_native_ref().descriptor(internal).into()
+}
+
+/// Returns the total value of unspent outputs with at least `min_confirmations` confirmations.
+///
+/// Outputs spent by transactions of ours which have not confirmed yet are not included, while
+/// the change of such transactions is included if `min_confirmations` is 0.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_get_balance(this_arg: &OnChainWallet, mut min_confirmations: u32) -> u64 {
+	this_arg.get_native_ref().balance(min_confirmations)
+}
+
+/// Returns all scripts the wallet watches for incoming funds, which need to be registered with
+/// the chain source when using `OnChainWallet_as_Confirm`.
+///
+/// The set grows as addresses are used, so this should be re-checked after each block.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_get_watched_scripts(this_arg: &OnChainWallet) -> CVec_CVec_u8ZZ {
+	let state = this_arg.get_native_ref().state.lock().unwrap();
+	let scripts: Vec<CVec_u8Z> = state.scripts.keys().map(|script| script.to_bytes().into()).collect();
+	scripts.into()
+}
+
+/// Sends `amount_sats` to `address` from confirmed funds and broadcasts the signed transaction,
+/// which is returned.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_send_to_address(this_arg: &OnChainWallet, mut address: Str, mut amount_sats: u64) -> CResult_TransactionWalletErrorZ {
+	CResult_TransactionWalletErrorZ::from_rust(this_arg.get_native_ref().send_to_address(address.into_str(), amount_sats))
+}
+
+/// Creates a signed transaction paying `value_sats` to `output_script` from confirmed funds, as
+/// requested by `Event::FundingGenerationReady`, to be passed to
+/// `ChannelManager_funding_transaction_generated`.
+///
+/// The transaction is not broadcast, the `ChannelManager` does so once the channel is set up. If
+/// it rejects the transaction, pass it to `OnChainWallet_abandon_transaction`.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_create_funding_transaction(this_arg: &OnChainWallet, mut output_script: CVec_u8Z, mut value_sats: u64) -> CResult_TransactionWalletErrorZ {
+	CResult_TransactionWalletErrorZ::from_rust(this_arg.get_native_ref().create_funding_transaction(Script::from(output_script.into_rust()), value_sats))
+}
+
+/// Forgets a transaction created by the wallet which was never broadcast, making the outputs it
+/// spends available again.
+///
+/// This must not be called for transactions which may still confirm.
+#[no_mangle]
+pub extern "C" fn OnChainWallet_abandon_transaction(this_arg: &OnChainWallet, mut tx: Transaction) {
+	this_arg.get_native_ref().abandon_transaction(&tx.into_bitcoin())
+}
+
+/// Spends the outputs from an `Event::SpendableOutputs` to a wallet change address at the
+/// `Background` feerate and broadcasts the transaction, which is returned.
+///
+/// Outputs which pay to scripts the wallet already watches are skipped. If the sweep fails, the
+/// descriptors should be stored and the sweep retried later.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_sweep_spendable_outputs(this_arg: &OnChainWallet, mut descriptors: CVec_SpendableOutputDescriptorZ) -> CResult_TransactionWalletErrorZ {
+	let mut local_descriptors = Vec::new(); for mut item in descriptors.into_rust().drain(..) { local_descriptors.push(item.into_native()); };
+	CResult_TransactionWalletErrorZ::from_rust(this_arg.get_native_ref().sweep_spendable_outputs(local_descriptors))
+}
+
+impl ChainListener for nativeOnChainWallet {
+	fn transactions_confirmed(&self, txdata: &[BitcoinTransaction], height: u32) {
+		let mut state = self.state.lock().unwrap();
+		for tx in txdata.iter() {
+			self.process_transaction(&mut state, tx, Some(height));
+		}
+	}
+	fn transaction_unconfirmed(&self, txid: &Txid) {
+		self.transaction_unconfirmed(&mut self.state.lock().unwrap(), txid);
+	}
+	fn best_block_updated(&self, height: u32) {
+		let unconfirmed = self.best_block_updated(&mut self.state.lock().unwrap(), height);
+		// The broadcaster may call back into the wallet, so it must not be called under the lock.
+		for tx in unconfirmed.iter() {
+			self.broadcaster.broadcast_transaction(tx);
+		}
+	}
+	fn block_disconnected(&self, height: u32) {
+		self.block_disconnected(&mut self.state.lock().unwrap(), height);
+	}
+	fn relevant_txids(&self) -> Vec<Txid> {
+		let state = self.state.lock().unwrap();
+		let mut txids = Vec::new();
+		for (outpoint, utxo) in state.utxos.iter() {
+			if utxo.confirmation_height.is_some() { txids.push(outpoint.txid); }
+			if let Some((txid, Some(_))) = utxo.spent_by { txids.push(txid); }
+		}
+		for (txid, (_, confirmation_height)) in state.broadcast.iter() {
+			if confirmation_height.is_some() { txids.push(*txid); }
+		}
+		txids
+	}
+}
+
+impl From<nativeOnChainWallet> for Listen {
+	fn from(obj: nativeOnChainWallet) -> Self {
+		let mut rust_obj = OnChainWallet { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = OnChainWallet_as_Listen(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(OnChainWallet_free_void);
+		ret
+	}
+}
+/// Constructs a new Listen which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned Listen must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn OnChainWallet_as_Listen(this_arg: &OnChainWallet) -> Listen {
+	as_listen(unsafe { ObjOps::untweak_ptr((*this_arg).inner) })
+}
+
+impl From<nativeOnChainWallet> for Confirm {
+	fn from(obj: nativeOnChainWallet) -> Self {
+		let mut rust_obj = OnChainWallet { inner: ObjOps::heap_alloc(obj), is_owned: true };
+		let mut ret = OnChainWallet_as_Confirm(&rust_obj);
+		// We want to free rust_obj when ret gets drop()'d, not rust_obj, so wipe rust_obj's pointer and set ret's free() fn
+		rust_obj.inner = std::ptr::null_mut();
+		ret.free = Some(OnChainWallet_free_void);
+		ret
+	}
+}
+/// Constructs a new Confirm which calls the relevant methods on this_arg.
+/// This copies the `inner` pointer in this_arg and thus the returned Confirm must be freed before this_arg is
+#[no_mangle]
+pub extern "C" fn OnChainWallet_as_Confirm(this_arg: &OnChainWallet) -> Confirm {
+	as_confirm(unsafe { ObjOps::untweak_ptr((*this_arg).inner) })
+}
+
+struct WalletEventHandler {
+	wallet: &'static nativeOnChainWallet,
+	channel_manager: &'static nativeChannelManager,
+	inner: EventHandler,
+}
+
+impl WalletEventHandler {
+	/// Handles funding and sweeping events, returning whether the event was handled.
+	fn handle_event(&self, event: nativeEvent) -> bool {
+		match event {
+			nativeEvent::FundingGenerationReady { temporary_channel_id, channel_value_satoshis, output_script, .. } => {
+				let tx = match self.wallet.create_funding_transaction(output_script, channel_value_satoshis) {
+					Ok(tx) => tx,
+					Err(_) => return false,
+				};
+				if self.channel_manager.funding_transaction_generated(&temporary_channel_id, tx.clone()).is_err() {
+					self.wallet.abandon_transaction(&tx);
+					return false;
+				}
+				true
+			},
+			nativeEvent::SpendableOutputs { outputs } => {
+				match self.wallet.sweep_spendable_outputs(outputs) {
+					Ok(_) | Err(WalletError::NothingToSweep) => true,
+					Err(_) => false,
+				}
+			},
+			_ => false,
+		}
+	}
+}
+
+/// Wraps `inner` in an [`EventHandler`] which funds channels from the wallet on
+/// `Event::FundingGenerationReady`, passing the funding transaction to `channel_manager`, and
+/// sweeps the outputs of `Event::SpendableOutputs` into the wallet.
+///
+/// All other events, as well as funding and sweeping events the wallet fails to handle (eg for
+/// lack of funds), are passed on to `inner`.
+///
+/// The returned handler must be freed before `this_arg` and `channel_manager` are.
+#[must_use]
+#[no_mangle]
+pub extern "C" fn OnChainWallet_event_handler(this_arg: &OnChainWallet, channel_manager: &ChannelManager, mut inner: EventHandler) -> EventHandler {
+	let handler = Box::new(WalletEventHandler { wallet: this_arg.get_native_ref(), channel_manager: channel_manager.get_native_ref(), inner });
+	EventHandler {
+		this_arg: Box::into_raw(handler) as *mut c_void,
+		handle_event: WalletEventHandler_handle_event,
+		free: Some(WalletEventHandler_free),
+	}
+}
+
+extern "C" fn WalletEventHandler_handle_event(this_arg: *const c_void, event: &Event) {
+	let handler = unsafe { &*(this_arg as *const WalletEventHandler) };
+	if !handler.handle_event(event.to_native()) {
+		(handler.inner.handle_event)(handler.inner.this_arg, event);
+	}
+}
+extern "C" fn WalletEventHandler_free(this_arg: *mut c_void) {
+	let _ = unsafe { Box::from_raw(this_arg as *mut WalletEventHandler) };
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Arc;
+	use crate::extensions::test_utils::{broadcaster, fee_estimator};
+
+	const FEERATE: u32 = 1000;
+
+	fn wallet() -> (nativeOnChainWallet, Arc<Mutex<Vec<BitcoinTransaction>>>) {
+		let (broadcaster, broadcasted) = broadcaster();
+		(nativeOnChainWallet::new(&[42; 32], BitcoinNetwork::Testnet, broadcaster, fee_estimator(FEERATE)), broadcasted)
+	}
+
+	/// Passes the wallet a transaction paying `value` to `source`, confirmed at `height` if any.
+	fn receive(wallet: &nativeOnChainWallet, value: u64, source: KeySource, height: Option<u32>) -> OutPoint {
+		let tx = BitcoinTransaction {
+			version: 2, lock_time: 0,
+			input: vec![TxIn { previous_output: OutPoint { txid: Txid::from_inner([value as u8; 32]), vout: 0 }, script_sig: Script::new(), sequence: 0xffff_ffff, witness: Vec::new() }],
+			output: vec![TxOut { value, script_pubkey: wallet.script_for(source) }],
+		};
+		let mut state = wallet.state.lock().unwrap();
+		wallet.process_transaction(&mut state, &tx, height);
+		if let Some(height) = height {
+			let best_height = std::cmp::max(state.best_height, height);
+			wallet.best_block_updated(&mut state, best_height);
+		}
+		OutPoint { txid: tx.txid(), vout: 0 }
+	}
+
+	fn p2wsh_script() -> Script {
+		Builder::new().push_opcode(opcodes::all::OP_PUSHBYTES_0).push_slice(&[7; 32]).into_script()
+	}
+
+	fn outpoint(n: u8) -> OutPoint {
+		OutPoint { txid: Txid::from_inner([n; 32]), vout: 0 }
+	}
+
+	#[test]
+	fn descriptor_checksum_test_vectors() {
+		// From BIP 380 and Bitcoin Core's descriptor documentation.
+		assert_eq!(descriptor_checksum("raw(deadbeef)"), "89f8spxm");
+		assert_eq!(descriptor_checksum("addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)"), "02wpgw69");
+		assert_eq!(descriptor_checksum("wpkh([d34db33f/84h/0h/0h]xpub6DJ2dNUysrn5Vt36jH2KLBT2i1auw1tTSSomg8PhqNiUtx8QX2SvC9nrHu81fT41fvDUnhMjEzQgXnQjKEu3oaqMSzhSrHMxyyoEAmUHQbY/0/*)"), "cjjspncu");
+	}
+
+	#[test]
+	fn wallet_descriptors() {
+		let (wallet, _) = wallet();
+		for (internal, chain) in [(false, "/0/*)#"), (true, "/1/*)#")].iter() {
+			let descriptor = wallet.descriptor(*internal);
+			assert!(descriptor.starts_with(&format!("wpkh([{:08x}/84h/1h/0h]tpub", wallet.fingerprint())));
+			let (body, checksum) = descriptor.split_at(descriptor.len() - 8);
+			assert!(body.ends_with(chain));
+			assert_eq!(descriptor_checksum(&body[..body.len() - 1]), checksum);
+		}
+	}
+
+	#[test]
+	fn selects_largest_coins_first() {
+		let available = vec![(outpoint(1), 30_000), (outpoint(2), 40_000), (outpoint(3), 10_000)];
+		let fixed_weight = TX_OVERHEAD_WEIGHT + P2WPKH_OUTPUT_WEIGHT;
+		let selection = select_coins(available, 60_000, fixed_weight, FEERATE).unwrap();
+		assert_eq!(selection.inputs, vec![outpoint(2), outpoint(1)]);
+		let fee = fee_for_weight(fixed_weight + 2 * P2WPKH_INPUT_WEIGHT + P2WPKH_OUTPUT_WEIGHT, FEERATE);
+		assert_eq!(selection.fee, fee);
+		assert_eq!(selection.change, Some(70_000 - 60_000 - fee));
+	}
+
+	#[test]
+	fn dust_change_goes_to_fees() {
+		let fixed_weight = TX_OVERHEAD_WEIGHT + P2WPKH_OUTPUT_WEIGHT;
+		let fee = fee_for_weight(fixed_weight + P2WPKH_INPUT_WEIGHT, FEERATE);
+		let fee_with_change = fee_for_weight(fixed_weight + P2WPKH_INPUT_WEIGHT + P2WPKH_OUTPUT_WEIGHT, FEERATE);
+
+		// Exactly enough to pay the fee without change.
+		let selection = select_coins(vec![(outpoint(1), 50_000 + fee)], 50_000, fixed_weight, FEERATE).unwrap();
+		assert_eq!((selection.fee, selection.change), (fee, None));
+
+		// Change just below the dust limit is added to the fee.
+		let value = 50_000 + fee_with_change + DUST_LIMIT_SATS - 1;
+		let selection = select_coins(vec![(outpoint(1), value)], 50_000, fixed_weight, FEERATE).unwrap();
+		assert_eq!((selection.fee, selection.change), (value - 50_000, None));
+
+		// And change at the dust limit gets its own output.
+		let selection = select_coins(vec![(outpoint(1), value + 1)], 50_000, fixed_weight, FEERATE).unwrap();
+		assert_eq!((selection.fee, selection.change), (fee_with_change, Some(DUST_LIMIT_SATS)));
+	}
+
+	#[test]
+	fn insufficient_funds() {
+		let fixed_weight = TX_OVERHEAD_WEIGHT + P2WPKH_OUTPUT_WEIGHT;
+		let fee = fee_for_weight(fixed_weight + P2WPKH_INPUT_WEIGHT, FEERATE);
+		assert_eq!(select_coins(vec![(outpoint(1), 50_000 + fee - 1)], 50_000, fixed_weight, FEERATE).err(), Some(WalletError::InsufficientFunds));
+		assert_eq!(select_coins(Vec::new(), 1, fixed_weight, FEERATE).err(), Some(WalletError::InsufficientFunds));
+	}
+
+	#[test]
+	fn tracks_confirmed_and_unconfirmed_funds() {
+		let (wallet, _) = wallet();
+		receive(&wallet, 100_000, KeySource::External(0), Some(10));
+		receive(&wallet, 20_000, KeySource::External(1), None);
+		assert_eq!(wallet.balance(0), 120_000);
+		assert_eq!(wallet.balance(1), 100_000);
+		assert_eq!(wallet.balance(2), 0);
+
+		let mut state = wallet.state.lock().unwrap();
+		wallet.best_block_updated(&mut state, 11);
+		std::mem::drop(state);
+		assert_eq!(wallet.balance(2), 100_000);
+
+		// Receiving to the last watched address derives further ones.
+		receive(&wallet, 30_000, KeySource::External(LOOKAHEAD - 1), Some(11));
+		let state = wallet.state.lock().unwrap();
+		assert_eq!(state.next_index[0], LOOKAHEAD);
+		assert_eq!(state.derived[0], 2 * LOOKAHEAD);
+	}
+
+	#[test]
+	fn funds_and_abandons_transactions() {
+		let (wallet, broadcasted) = wallet();
+		let funding_outpoint = receive(&wallet, 100_000, KeySource::External(0), Some(1));
+
+		let tx = wallet.create_funding_transaction(p2wsh_script(), 60_000).unwrap();
+		assert_eq!(tx.input.len(), 1);
+		assert_eq!(tx.input[0].previous_output, funding_outpoint);
+		assert_eq!(tx.output[0], TxOut { value: 60_000, script_pubkey: p2wsh_script() });
+		assert_eq!(tx.output[1].script_pubkey, wallet.script_for(KeySource::Internal(0)));
+		let estimated_weight = TX_OVERHEAD_WEIGHT + output_weight(&p2wsh_script()) + P2WPKH_INPUT_WEIGHT + P2WPKH_OUTPUT_WEIGHT;
+		assert!(tx.get_weight() as u64 <= estimated_weight);
+		assert_eq!(100_000 - 60_000 - tx.output[1].value, fee_for_weight(estimated_weight, FEERATE));
+		// Funding transactions are broadcast by the ChannelManager, not the wallet.
+		assert!(broadcasted.lock().unwrap().is_empty());
+
+		// The input is locked, with only the unconfirmed change left.
+		assert_eq!(wallet.balance(0), tx.output[1].value);
+		assert_eq!(wallet.create_funding_transaction(p2wsh_script(), 10_000).err(), Some(WalletError::InsufficientFunds));
+
+		wallet.abandon_transaction(&tx);
+		assert_eq!(wallet.balance(0), 100_000);
+		assert!(wallet.create_funding_transaction(p2wsh_script(), 10_000).is_ok());
+	}
+
+	#[test]
+	fn spent_outputs_are_forgotten_after_reorg_safety() {
+		let (wallet, _) = wallet();
+		receive(&wallet, 100_000, KeySource::External(0), Some(1));
+		let tx = wallet.create_funding_transaction(p2wsh_script(), 60_000).unwrap();
+
+		let mut state = wallet.state.lock().unwrap();
+		wallet.process_transaction(&mut state, &tx, Some(2));
+		wallet.best_block_updated(&mut state, 2 + ANTI_REORG_DELAY - 2);
+		assert_eq!(state.utxos.len(), 2);
+		wallet.block_disconnected(&mut state, 2 + ANTI_REORG_DELAY - 2);
+		wallet.block_disconnected(&mut state, 2);
+		// Once unconfirmed, the spend stays locked but the change is no longer spendable.
+		assert_eq!(state.utxos.len(), 2);
+		wallet.process_transaction(&mut state, &tx, Some(2));
+		wallet.best_block_updated(&mut state, 2 + ANTI_REORG_DELAY - 1);
+		assert_eq!(state.utxos.len(), 1);
+	}
+
+	#[test]
+	fn send_to_address() {
+		let (wallet, broadcasted) = wallet();
+		receive(&wallet, 100_000, KeySource::External(0), Some(1));
+
+		assert_eq!(wallet.send_to_address("not an address", 10_000).err(), Some(WalletError::InvalidAddress));
+		assert_eq!(wallet.send_to_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", 10_000).err(), Some(WalletError::WrongNetwork));
+		let address = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+		assert_eq!(wallet.send_to_address(address, DUST_LIMIT_SATS - 1).err(), Some(WalletError::AmountBelowDust));
+		assert!(broadcasted.lock().unwrap().is_empty());
+
+		let tx = wallet.send_to_address(address, 10_000).unwrap();
+		assert_eq!(tx.output[0].script_pubkey, Address::from_str(address).unwrap().script_pubkey());
+		assert_eq!(*broadcasted.lock().unwrap(), vec![tx]);
+	}
+
+	#[test]
+	fn rebroadcasts_unconfirmed_transactions() {
+		let (wallet, broadcasted) = wallet();
+		receive(&wallet, 100_000, KeySource::External(0), Some(1));
+		let tx = wallet.send_to_address("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx", 10_000).unwrap();
+		// Funding transactions are left to the ChannelManager.
+		receive(&wallet, 100_000, KeySource::External(1), Some(1));
+		wallet.create_funding_transaction(p2wsh_script(), 60_000).unwrap();
+		broadcasted.lock().unwrap().clear();
+
+		ChainListener::best_block_updated(&wallet, 2);
+		assert_eq!(*broadcasted.lock().unwrap(), vec![tx.clone()]);
+
+		// Once confirmed it is no longer rebroadcast, unless it is reorganized out.
+		ChainListener::transactions_confirmed(&wallet, &[tx.clone()], 3);
+		ChainListener::best_block_updated(&wallet, 3);
+		assert_eq!(broadcasted.lock().unwrap().len(), 1);
+		assert!(ChainListener::relevant_txids(&wallet).contains(&tx.txid()));
+		ChainListener::transaction_unconfirmed(&wallet, &tx.txid());
+		ChainListener::best_block_updated(&wallet, 3);
+		assert_eq!(broadcasted.lock().unwrap().len(), 2);
+
+		ChainListener::transactions_confirmed(&wallet, &[tx.clone()], 4);
+		ChainListener::best_block_updated(&wallet, 4 + ANTI_REORG_DELAY - 1);
+		assert!(wallet.state.lock().unwrap().broadcast.is_empty());
+		assert_eq!(broadcasted.lock().unwrap().len(), 2);
+	}
+
+	#[test]
+	fn stops_rebroadcasting_double_spent_transactions() {
+		let (wallet, broadcasted) = wallet();
+		let outpoint = receive(&wallet, 100_000, KeySource::External(0), Some(1));
+		wallet.send_to_address("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx", 10_000).unwrap();
+
+		let double_spend = BitcoinTransaction {
+			version: 2, lock_time: 0,
+			input: vec![TxIn { previous_output: outpoint, script_sig: Script::new(), sequence: 0xffff_ffff, witness: Vec::new() }],
+			output: vec![TxOut { value: 90_000, script_pubkey: p2wsh_script() }],
+		};
+		ChainListener::transactions_confirmed(&wallet, &[double_spend], 2);
+		broadcasted.lock().unwrap().clear();
+		ChainListener::best_block_updated(&wallet, 2);
+		assert!(broadcasted.lock().unwrap().is_empty());
+		assert!(wallet.state.lock().unwrap().broadcast.is_empty());
+	}
+
+	#[test]
+	fn serialization_round_trip() {
+		let (wallet, _) = wallet();
+		receive(&wallet, 100_000, KeySource::External(3), Some(1));
+		wallet.create_funding_transaction(p2wsh_script(), 60_000).unwrap();
+		receive(&wallet, 100_000, KeySource::External(4), Some(1));
+		let tx = wallet.send_to_address("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx", 10_000).unwrap();
+		let serialized = wallet.encode();
+
+		let read_args = |seed| {
+			let (broadcaster, _) = broadcaster();
+			OnChainWalletReadArgs { seed, network: BitcoinNetwork::Testnet, broadcaster, fee_estimator: fee_estimator(FEERATE) }
+		};
+		let read_wallet = nativeOnChainWallet::read(&mut &serialized[..], read_args([42; 32])).unwrap();
+		assert_eq!(read_wallet.balance(0), wallet.balance(0));
+		assert_eq!(read_wallet.state.lock().unwrap().next_index, [5, 2]);
+		assert_eq!(read_wallet.state.lock().unwrap().broadcast[&tx.txid()], (tx, None));
+
+		assert!(nativeOnChainWallet::read(&mut &serialized[..], read_args([43; 32])).is_err());
+	}
+}
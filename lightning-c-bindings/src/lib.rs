@@ -19,6 +19,7 @@
 pub mod version;
 pub mod c_types;
 pub mod bitcoin;
+pub mod extensions;
 pub mod lightning;
 pub mod lightning_persister;
 pub mod lightning_background_processor;